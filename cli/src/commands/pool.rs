@@ -278,6 +278,61 @@ pub mod refresh_command {
     }
 }
 
+pub mod status_command {
+    use super::*;
+
+    command!(CommandMetadata::build("status", "Show health statistics of the nodes of current pool.")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let (pool_handle, pool_name) = ensure_connected_pool(&ctx)?;
+
+        let res = match Pool::get_status(pool_handle) {
+            Ok(status) => {
+                trace!("status {:?}", status);
+                let status: JSONValue = serde_json::from_str(&status)
+                    .map_err(|_| println_err!("Wrong data has been received"))?;
+
+                let mut nodes: Vec<JSONValue> = status["nodes"].as_object()
+                    .map(|nodes| nodes.iter()
+                        .map(|(alias, node)| {
+                            let mut node = node.clone();
+                            node["alias"] = JSONValue::String(alias.to_string());
+                            if let Some(time) = node["lastReplyTime"].as_i64() {
+                                node["lastReplyTime"] = JSONValue::String(NaiveDateTime::from_timestamp(time, 0).to_string());
+                            }
+                            node
+                        })
+                        .collect())
+                    .unwrap_or_default();
+                nodes.sort_by_key(|node| node["alias"].as_str().map(String::from));
+
+                print_list_table(&nodes,
+                                 &[("alias", "Node"),
+                                     ("lastReplyTime", "Last Reply"),
+                                     ("latencyMs", "Latency (ms)"),
+                                     ("replies", "Replies"),
+                                     ("timeouts", "Timeouts"),
+                                     ("nacks", "NACKs"),
+                                     ("rejects", "REJECTs"),
+                                     ("blsFailures", "BLS Failures")],
+                                 &format!("There are no statistics collected for pool \"{}\" yet", pool_name));
+                Ok(())
+            }
+            Err(err) => {
+                handle_indy_error(err, None, Some(&pool_name), None);
+                Err(())
+            }
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
 pub mod set_protocol_version_command {
     use super::*;
 
@@ -717,6 +772,36 @@ pub mod tests {
         }
     }
 
+    mod status {
+        use super::*;
+
+        #[test]
+        pub fn status_works() {
+            let ctx = setup();
+            create_and_connect_pool(&ctx);
+            {
+                let cmd = status_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            disconnect_and_delete_pool(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn status_works_for_not_opened() {
+            let ctx = setup();
+            create_pool(&ctx);
+            {
+                let cmd = status_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            delete_pool(&ctx);
+            tear_down();
+        }
+    }
+
     mod disconnect {
         use super::*;
 
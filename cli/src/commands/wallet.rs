@@ -477,6 +477,108 @@ pub mod export_command {
     }
 }
 
+pub mod rekey_command {
+    use super::*;
+
+    command!(CommandMetadata::build("rekey", "Rotate keys of opened wallet and re-encrypt its content with them")
+                .add_required_deferred_param("new_key", "New key or passphrase used for wallet key derivation.
+                                               Look to new_key_derivation_method param for information about supported key derivation methods.")
+                .add_optional_param("new_key_derivation_method", "Algorithm to use for wallet key derivation. One of:
+                                    argon2m - derive secured wallet key (used by default)
+                                    argon2i - derive secured wallet key (less secured but faster)
+                                    raw - raw key provided (skip derivation)")
+                .add_example("wallet rekey new_key")
+                .add_example("wallet rekey new_key new_key_derivation_method=argon2i")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, secret!(params));
+
+        let (wallet_handle, wallet_name) = ensure_opened_wallet(&ctx)?;
+
+        let new_key = get_str_param("new_key", params).map_err(error_err!())?;
+        let new_key_derivation_method = get_opt_str_param("new_key_derivation_method", params).map_err(error_err!())?;
+        let new_credentials: String = json!({ "key": new_key, "key_derivation_method": map_key_derivation_method(new_key_derivation_method)? }).to_string();
+
+        trace!("Wallet::rekey_wallet try: wallet_name {}", wallet_name);
+
+        let res = Wallet::rekey_wallet(wallet_handle, new_credentials.as_str());
+
+        trace!("Wallet::rekey_wallet return: {:?}", res);
+
+        let res = match res {
+            Ok(()) => {
+                println_succ!("Wallet \"{}\" has been re-keyed", wallet_name);
+                Ok(())
+            },
+            Err(err) => {
+                handle_indy_error(err, None, None, Some(wallet_name.as_ref()));
+                Err(())
+            }
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
+pub mod check_command {
+    use super::*;
+
+    command!(CommandMetadata::build("check", "Check that all records of opened wallet can be decrypted")
+                .add_optional_param("quarantine", "Move broken records to quarantine record type (false by default)")
+                .add_example("wallet check")
+                .add_example("wallet check quarantine=true")
+                .finalize()
+    );
+
+    fn execute(ctx: &CommandContext, params: &CommandParams) -> Result<(), ()> {
+        trace!("execute >> ctx {:?} params {:?}", ctx, params);
+
+        let (wallet_handle, wallet_name) = ensure_opened_wallet(&ctx)?;
+
+        let quarantine = get_opt_bool_param("quarantine", params).map_err(error_err!())?.unwrap_or(false);
+        let options: String = json!({ "quarantine": quarantine }).to_string();
+
+        trace!("Wallet::check_wallet try: wallet_name {}, options {}", wallet_name, options);
+
+        let res = Wallet::check_wallet(wallet_handle, options.as_str());
+
+        trace!("Wallet::check_wallet return: {:?}", res);
+
+        let res = match res {
+            Ok(report) => {
+                let report: JSONValue = serde_json::from_str(&report)
+                    .map_err(|_| println_err!("Wrong data has been received"))?;
+
+                let broken = report["broken"].as_array().cloned().unwrap_or_default();
+
+                if broken.is_empty() {
+                    println_succ!("Wallet \"{}\" has been checked: all {} records are valid", wallet_name, report["total"]);
+                } else {
+                    print_list_table(&broken,
+                                     &[("type", "Type"),
+                                         ("id", "Id"),
+                                         ("raw_id", "Raw Id"),
+                                         ("reason", "Reason"),
+                                         ("quarantined", "Quarantined")],
+                                     "");
+                    println_warn!("Wallet \"{}\" has been checked: {} of {} records are broken", wallet_name, broken.len(), report["total"]);
+                }
+                Ok(())
+            },
+            Err(err) => {
+                handle_indy_error(err, None, None, Some(wallet_name.as_ref()));
+                Err(())
+            }
+        };
+
+        trace!("execute << {:?}", res);
+        res
+    }
+}
+
 pub mod import_command {
     use super::*;
 
@@ -1166,6 +1268,93 @@ pub mod tests {
         }
     }
 
+    mod rekey {
+        use super::*;
+
+        #[test]
+        pub fn rekey_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = rekey_command::new();
+                let mut params = CommandParams::new();
+                params.insert("new_key", WALLET_KEY.to_string());
+                params.insert("new_key_derivation_method", "argon2i".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            close_wallet(&ctx);
+            {
+                let cmd = open_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY_RAW.to_string());
+                params.insert("key_derivation_method", "raw".to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            {
+                let cmd = open_command::new();
+                let mut params = CommandParams::new();
+                params.insert("name", WALLET.to_string());
+                params.insert("key", WALLET_KEY.to_string());
+                params.insert("key_derivation_method", "argon2i".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            close_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn rekey_works_for_not_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = rekey_command::new();
+                let mut params = CommandParams::new();
+                params.insert("new_key", WALLET_KEY.to_string());
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+
+    mod check {
+        use super::*;
+
+        #[test]
+        pub fn check_works() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = check_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            close_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_works_for_quarantine() {
+            let ctx = setup_with_wallet();
+            {
+                let cmd = check_command::new();
+                let mut params = CommandParams::new();
+                params.insert("quarantine", "true".to_string());
+                cmd.execute(&ctx, &params).unwrap();
+            }
+            close_wallet(&ctx);
+            tear_down();
+        }
+
+        #[test]
+        pub fn check_works_for_not_opened_wallet() {
+            let ctx = setup();
+            {
+                let cmd = check_command::new();
+                let params = CommandParams::new();
+                cmd.execute(&ctx, &params).unwrap_err();
+            }
+            tear_down();
+        }
+    }
+
     mod import {
         use super::*;
         use super::did::tests::{new_did, use_did, SEED_MY1, DID_MY1};
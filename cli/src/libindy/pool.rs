@@ -18,6 +18,10 @@ impl Pool {
         pool::refresh_pool_ledger(pool_handle).wait()
     }
 
+    pub fn get_status(pool_handle: PoolHandle) -> Result<String, IndyError> {
+        pool::get_pool_status(pool_handle).wait()
+    }
+
     pub fn list() -> Result<String, IndyError> {
         pool::list_pools().wait()
     }
@@ -26,6 +26,14 @@ impl Wallet {
         wallet::export_wallet(wallet_handle, export_config_json).wait()
     }
 
+    pub fn rekey_wallet(wallet_handle: WalletHandle, new_credentials: &str) -> Result<(), IndyError> {
+        wallet::rekey_wallet(wallet_handle, new_credentials).wait()
+    }
+
+    pub fn check_wallet(wallet_handle: WalletHandle, options_json: &str) -> Result<String, IndyError> {
+        wallet::check_wallet(wallet_handle, Some(options_json)).wait()
+    }
+
     pub fn import_wallet(config: &str, credentials: &str, import_config_json: &str) -> Result<(), IndyError> {
         wallet::import_wallet(config, credentials, import_config_json).wait()
     }
@@ -139,6 +139,7 @@ fn build_executor() -> CommandExecutor {
         .add_command(pool::create_command::new())
         .add_command(pool::connect_command::new())
         .add_command(pool::refresh_command::new())
+        .add_command(pool::status_command::new())
         .add_command(pool::list_command::new())
         .add_command(pool::disconnect_command::new())
         .add_command(pool::delete_command::new())
@@ -154,6 +155,8 @@ fn build_executor() -> CommandExecutor {
         .add_command(wallet::delete_command::new())
         .add_command(wallet::detach_command::new())
         .add_command(wallet::export_command::new())
+        .add_command(wallet::rekey_command::new())
+        .add_command(wallet::check_command::new())
         .add_command(wallet::import_command::new())
         .finalize_group()
         .add_group(ledger::group::new())
@@ -60,7 +60,7 @@ pub extern fn postgresstorage_init() -> libindy::ErrorCode {
         return err;
     }
 
-    libindy::wallet::register_wallet_storage(
+    let err = libindy::wallet::register_wallet_storage(
         postgres_storage_name.as_ptr(),
         PostgresWallet::create,
         PostgresWallet::open,
@@ -86,6 +86,15 @@ pub extern fn postgresstorage_init() -> libindy::ErrorCode {
         PostgresWallet::get_search_total_count,
         PostgresWallet::fetch_search_next_record,
         PostgresWallet::free_search,
+    );
+    if err != libindy::ErrorCode::Success {
+        return err;
+    }
+
+    libindy::wallet::register_wallet_storage_list(
+        postgres_storage_name.as_ptr(),
+        PostgresWallet::list_storages,
+        PostgresWallet::free_storage_list,
     )
 }
 
@@ -128,6 +137,11 @@ lazy_static! {
     static ref POSTGRES_ACTIVE_METADATAS: Mutex<HashMap<i32, CString>> = Default::default();
 }
 
+lazy_static! {
+    // wallet ids lists returned by list_storages
+    static ref POSTGRES_ACTIVE_STORAGE_LISTS: Mutex<HashMap<i32, CString>> = Default::default();
+}
+
 lazy_static! {
     // cache of Postgres fetched records
     static ref POSTGRES_ACTIVE_RECORDS: Mutex<HashMap<i32, PostgresWalletRecord>> = Default::default();
@@ -919,6 +933,49 @@ impl PostgresWallet {
             Err(_err) => ErrorCode::WalletStorageError
         }
     }
+
+
+    pub extern fn list_storages(config: *const c_char,
+                                credentials: *const c_char,
+                                ids_json_ptr: *mut *const c_char,
+                                ids_handle: *mut i32) -> ErrorCode {
+        check_useful_c_str!(config, ErrorCode::CommonInvalidState);
+        check_useful_c_str!(credentials, ErrorCode::CommonInvalidState);
+
+        let storage_type = ::postgres_storage::PostgresStorageType::new();
+        let ids = match storage_type.list_storages(Some(&config), Some(&credentials)) {
+            Ok(ids) => ids,
+            Err(err) => {
+                error!("Error listing storages. Error details: {:?}", err);
+                return ErrorCode::WalletStorageError;
+            }
+        };
+
+        let ids_json = CString::new(serde_json::to_string(&ids).unwrap()).unwrap();
+        let ids_json_pointer = ids_json.as_ptr();
+
+        let handle = SequenceUtils::get_next_id();
+
+        let mut lists = POSTGRES_ACTIVE_STORAGE_LISTS.lock().unwrap();
+        lists.insert(handle, ids_json);
+
+        unsafe { *ids_json_ptr = ids_json_pointer; }
+        unsafe { *ids_handle = handle };
+
+        ErrorCode::Success
+    }
+
+
+    pub extern fn free_storage_list(ids_handle: i32) -> ErrorCode {
+        let mut lists = POSTGRES_ACTIVE_STORAGE_LISTS.lock().unwrap();
+
+        if !lists.contains_key(&ids_handle) {
+            return ErrorCode::CommonInvalidState;
+        }
+        lists.remove(&ids_handle);
+
+        ErrorCode::Success
+    }
 }
 
 fn _storagerecord_to_postgresrecord(in_rec: &StorageRecord) -> Result<PostgresWalletRecord, WalletStorageError> {
@@ -394,7 +394,59 @@ pub fn register_wallet_storage(
     receiver.recv().unwrap()
 }
 
+/// Get ids of existing wallet storages.
+///
+/// #Params
+/// config: storage config (See create handler)
+/// credentials: storage credentials (See create handler)
+///
+/// returns: ids json ["id1", "id2", ...] and handle to free it (See free_storage_list handler)
+pub type WalletListStorages = extern fn(config: *const c_char,
+                                        credentials: *const c_char,
+                                        ids_json_p: *mut *const c_char,
+                                        ids_handle_p: *mut IndyHandle) -> ErrorCode;
+
+/// Free ids json returned by list_storages handler
+///
+/// #Params
+/// ids_handle: handle returned by list_storages handler
+pub type WalletFreeStorageList = extern fn(ids_handle: IndyHandle) -> ErrorCode;
+
+pub fn register_wallet_storage_list(
+    wallet_storage_name: *const c_char,
+    list_storages: WalletListStorages,
+    free_storage_list: WalletFreeStorageList,
+) -> ErrorCode {
+    let (sender, receiver) = channel();
+
+    let closure: Box<dyn FnMut(ErrorCode) + Send> = Box::new(move |err| {
+        sender.send(err).unwrap();
+    });
+
+    let (cmd_handle, cb) = callbacks::closure_to_cb_ec(closure);
+
+    unsafe {
+        indy_register_wallet_storage_list(
+            cmd_handle,
+            wallet_storage_name,
+            Some(list_storages),
+            Some(free_storage_list),
+            cb,
+        );
+    }
+
+    receiver.recv().unwrap()
+}
+
 extern {
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_list(command_handle: IndyHandle,
+                                             type_: *const c_char,
+                                             list_storages: Option<WalletListStorages>,
+                                             free_storage_list: Option<WalletFreeStorageList>,
+                                             cb: Option<extern fn(command_handle_: IndyHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode;
+
     #[no_mangle]
     pub fn indy_register_wallet_storage(command_handle: IndyHandle,
                                             type_: *const c_char,
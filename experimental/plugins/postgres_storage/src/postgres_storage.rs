@@ -16,8 +16,9 @@ use std::time::Duration;
 
 use self::percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
 
-use errors::wallet::WalletStorageError;
+use errors::wallet::{WalletStorageError, WalletQueryError};
 use errors::common::CommonError;
+use utils::crypto::base64 as util_base64;
 use wql::language;
 use wql::query;
 use wql::transaction;
@@ -86,6 +87,34 @@ pub struct SearchOptions {
     retrieve_value: bool,
     #[serde(default = "default_false")]
     retrieve_tags: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort_by: Option<SortBy>,
+    #[serde(default)]
+    sort_order: SortOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy {
+    Id,
+    TagName(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> SortOrder {
+        SortOrder::Asc
+    }
 }
 
 impl SearchOptions {
@@ -96,10 +125,24 @@ impl SearchOptions {
             retrieve_type: true,
             retrieve_value: true,
             retrieve_tags: false,
+            ..SearchOptions::default()
         };
 
         serde_json::to_string(&options).unwrap()
     }
+
+    // libindy passes the sorting tag name encrypted and base64 encoded: "~<base64 of encrypted name>"
+    fn sort_tag_name(&self) -> Result<Option<Vec<u8>>, WalletStorageError> {
+        match self.sort_by {
+            Some(SortBy::TagName(ref name)) if name.starts_with('~') =>
+                util_base64::decode(&name[1..])
+                    .map(Some)
+                    .map_err(|err| WalletStorageError::QueryError(WalletQueryError::StructureErr(err.to_string()))),
+            Some(SortBy::TagName(_)) =>
+                Err(WalletStorageError::QueryError(WalletQueryError::StructureErr("Sorting is supported only by unencrypted tags".to_string()))),
+            _ => Ok(None)
+        }
+    }
 }
 
 impl Default for SearchOptions {
@@ -110,6 +153,10 @@ impl Default for SearchOptions {
             retrieve_type: false,
             retrieve_value: true,
             retrieve_tags: false,
+            sort_by: None,
+            sort_order: SortOrder::Asc,
+            offset: None,
+            limit: None,
         }
     }
 }
@@ -459,6 +506,7 @@ pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), WalletStorageError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<PostgresStorage>, WalletStorageError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), WalletStorageError>;
+    fn list_storages(&self, config: Option<&str>, credentials: Option<&str>) -> Result<Vec<String>, WalletStorageError>;
 }
 
 #[derive(Deserialize, Debug)]
@@ -479,6 +527,8 @@ trait WalletStrategy {
     fn open_wallet(&self, id: &str, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Box<PostgresStorage>, WalletStorageError>;
     // delete a single wallet based on wallet storage strategy
     fn delete_wallet(&self, id: &str, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<(), WalletStorageError>;
+    // list ids of existing wallets based on wallet storage strategy
+    fn list_wallets(&self, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Vec<String>, WalletStorageError>;
     // determine physical table name based on wallet strategy
     fn table_name(&self, id: &str, base_name: &str) -> String;
     // determine additional query parameters based on wallet strategy
@@ -632,6 +682,16 @@ impl WalletStrategy for MultiWalletSingleTableStrategySharedPool {
         return ret;
     }
 
+    fn list_wallets(&self, _config: &PostgresConfig, _credentials: &PostgresCredentials) -> Result<Vec<String>, WalletStorageError> {
+        let connection = match self.pool.get() {
+            Ok(connection) => connection,
+            Err(error) => {
+                return Err(WalletStorageError::IOError(format!("Error retrieving connection from connection pool: {}", error)));
+            }
+        };
+        _query_wallet_ids(&connection)
+    }
+
     fn table_name(&self, _id: &str, base_name: &str) -> String {
         // TODO
         base_name.to_owned()
@@ -791,6 +851,46 @@ impl WalletStrategy for DatabasePerWalletStrategy {
         conn.finish()?;
         ret
     }
+    // list ids of existing wallets based on wallet storage strategy
+    fn list_wallets(&self, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Vec<String>, WalletStorageError> {
+        // every wallet has its own database, so databases can be listed only with admin user and password
+        if credentials.admin_account == None || credentials.admin_password == None {
+            return Err(WalletStorageError::ConfigError);
+        }
+
+        let url_base = PostgresStorageType::_admin_postgres_url(&config, &credentials);
+
+        let conn = postgres::Connection::connect(url_base, config.tls())?;
+        let databases: Vec<String> = {
+            let rows = conn.query("SELECT datname FROM pg_database WHERE datistemplate = false ORDER BY datname", &[])?;
+            rows.iter().map(|row| row.get(0)).collect()
+        };
+        conn.finish()?;
+
+        // wallet databases are the ones containing wallet metadata table
+        let mut ids = Vec::new();
+        for id in databases {
+            if id == _POSTGRES_DB || id == _WALLETS_DB {
+                continue;
+            }
+
+            let url = PostgresStorageType::_postgres_url(&id, &config, &credentials);
+            let conn = match postgres::Connection::connect(&url[..], config.tls()) {
+                Ok(conn) => conn,
+                Err(_) => continue
+            };
+            let is_wallet = match conn.query("SELECT to_regclass('metadata') IS NOT NULL", &[]) {
+                Ok(rows) => rows.iter().next().map(|row| row.get::<_, bool>(0)).unwrap_or(false),
+                Err(_) => false
+            };
+            conn.finish()?;
+
+            if is_wallet {
+                ids.push(id);
+            }
+        }
+        Ok(ids)
+    }
     // determine phyisical table name based on wallet strategy
     fn table_name(&self, _id: &str, base_name: &str) -> String {
         // TODO
@@ -803,6 +903,11 @@ impl WalletStrategy for DatabasePerWalletStrategy {
     }
 }
 
+// select ids of all wallets stored in the shared metadata table
+fn _query_wallet_ids(conn: &postgres::Connection) -> Result<Vec<String>, WalletStorageError> {
+    let rows = conn.query("SELECT wallet_id FROM metadata ORDER BY wallet_id", &[])?;
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
 
 // determine additional query parameters based on wallet strategy
 fn get_multi_database_name(config: &PostgresConfig) -> &str {
@@ -991,6 +1096,22 @@ impl WalletStrategy for MultiWalletSingleTableStrategy {
         conn.finish()?;
         return ret;
     }
+    // list ids of existing wallets based on wallet storage strategy
+    fn list_wallets(&self, config: &PostgresConfig, credentials: &PostgresCredentials) -> Result<Vec<String>, WalletStorageError> {
+        let wallet_db_name: &str = get_multi_database_name(config);
+        let url = PostgresStorageType::_postgres_url(wallet_db_name, &config, &credentials);
+
+        let conn = match postgres::Connection::connect(&url[..], config.tls()) {
+            Ok(conn) => conn,
+            Err(error) => {
+                return Err(WalletStorageError::IOError(format!("Error occurred while connecting to wallet schema: {}", error)));
+            }
+        };
+
+        let ret = _query_wallet_ids(&conn);
+        conn.finish()?;
+        ret
+    }
     // determine phyisical table name based on wallet strategy
     fn table_name(&self, _id: &str, base_name: &str) -> String {
         // TODO
@@ -1026,6 +1147,11 @@ impl WalletStrategy for MultiWalletMultiTableStrategy {
         // TODO
         Ok(())
     }
+    // list ids of existing wallets based on wallet storage strategy
+    fn list_wallets(&self, _config: &PostgresConfig, _credentials: &PostgresCredentials) -> Result<Vec<String>, WalletStorageError> {
+        // TODO
+        Ok(Vec::new())
+    }
     // determine phyisical table name based on wallet strategy
     fn table_name(&self, _id: &str, base_name: &str) -> String {
         // TODO
@@ -1756,6 +1882,12 @@ impl WalletStorage for PostgresStorage {
                 None => query::wql_to_sql(&type_, query, options)?
             };
 
+            let sort_tag_name = search_options.sort_tag_name()?;
+            let query_string = format!("{}{}", query_string, query::search_options_to_sql(sort_tag_name.as_ref().map(Vec::as_slice),
+                                                                                           search_options.sort_order == SortOrder::Desc,
+                                                                                           search_options.offset,
+                                                                                           search_options.limit));
+
             let statement = self._prepare_statement(&query_string)?;
             let tag_retriever = if fetch_options.retrieve_tags {
                 let pool = self.pool.clone();
@@ -1951,6 +2083,51 @@ impl WalletStorageType for PostgresStorageType {
         strategy_read_lock.as_ref().delete_wallet(id, &config, &credentials)
     }
 
+    ///
+    /// Lists ids of the wallets existing in the Postgres DB specified in the config file.
+    ///
+    /// # Arguments
+    ///
+    ///  * `storage_config` - Postgres DB connection config
+    ///  * `storage_credentials` - DB credentials
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `Vec<String>` - sorted ids of existing wallets
+    ///  * `WalletStorageError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `WalletStorageError` type_ of errors can be throw by this method:
+    ///
+    ///  * `WalletStorageError::ConfigError` - Config or credentials are missed or not sufficient
+    ///  * `IOError(..)` - Connection to the DB failed
+    ///
+    fn list_storages(&self, config: Option<&str>, credentials: Option<&str>) -> Result<Vec<String>, WalletStorageError> {
+        let config = config
+            .map(serde_json::from_str::<PostgresConfig>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .map_err(|err| CommonError::InvalidStructure(format!("Cannot deserialize config: {:?}", err)))?;
+        let credentials = credentials
+            .map(serde_json::from_str::<PostgresCredentials>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .map_err(|err| CommonError::InvalidStructure(format!("Cannot deserialize credentials: {:?}", err)))?;
+
+        let config = match config {
+            Some(config) => config,
+            None => return Err(WalletStorageError::ConfigError)
+        };
+        let credentials = match credentials {
+            Some(credentials) => credentials,
+            None => return Err(WalletStorageError::ConfigError)
+        };
+
+        let strategy_read_lock = SELECTED_STRATEGY.read().unwrap();
+        strategy_read_lock.as_ref().list_wallets(&config, &credentials)
+    }
+
     ///
     /// Creates the Postgres DB schema with the provided name in the id specified in the config file,
     /// and initializes the encryption keys needed for encryption and decryption of data.
@@ -2102,6 +2279,22 @@ mod tests {
         storage_type.delete_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
     }
 
+    #[test]
+    fn postgres_storage_type_list_works() {
+        _cleanup();
+
+        let storage_type = PostgresStorageType::new();
+        storage_type.create_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..]), &_metadata()).unwrap();
+
+        let ids = storage_type.list_storages(Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
+        assert!(ids.contains(&_wallet_id().to_string()));
+
+        storage_type.delete_storage(_wallet_id(), Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
+
+        let ids = storage_type.list_storages(Some(&_wallet_config()[..]), Some(&_wallet_credentials()[..])).unwrap();
+        assert!(!ids.contains(&_wallet_id().to_string()));
+    }
+
 
     #[test]
     fn postgres_storage_type_delete_works_for_non_existing() {
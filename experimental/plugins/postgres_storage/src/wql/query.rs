@@ -20,6 +20,31 @@ pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, _options: Option<&st
 }
 
 
+// Translates sorting and pagination of search options to SQL
+// It is appended to the query after all conditions are added
+pub fn search_options_to_sql(sort_tag_name: Option<&[u8]>, desc: bool, offset: Option<usize>, limit: Option<usize>) -> String {
+    let mut s = String::new();
+    let order = if desc { "DESC" } else { "ASC" };
+
+    if let Some(tag_name) = sort_tag_name {
+        let tag_name: String = tag_name.iter().map(|b| format!("{:02x}", b)).collect();
+        s.push_str(&format!(" ORDER BY (SELECT value FROM tags_plaintext WHERE item_id = i.id AND name = decode('{}', 'hex')) {}, i.id {}", tag_name, order, order));
+    } else if offset.is_some() || limit.is_some() {
+        s.push_str(" ORDER BY i.id");
+    }
+
+    if let Some(limit) = limit {
+        s.push_str(&format!(" LIMIT {}", limit));
+    }
+
+    if let Some(offset) = offset {
+        s.push_str(&format!(" OFFSET {}", offset));
+    }
+
+    s
+}
+
+
 pub fn wql_to_sql_count<'a>(class: &'a Vec<u8>, op: &'a Operator) -> Result<(String, Vec<&'a dyn ToSql>), WalletQueryError> {
     let mut arguments: Vec<&dyn ToSql> = Vec::new();
     arguments.push(class);
@@ -256,4 +281,12 @@ mod tests {
         let (query, _arguments) = wql_to_sql(&class, &query, None).unwrap();
         assert_eq!(query, "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = $1 AND (((i.id in (SELECT item_id FROM tags_encrypted WHERE name = $2 AND value = $3)) AND (i.id in (SELECT item_id FROM tags_plaintext WHERE name = $4 AND value = $5))) OR ((i.id in (SELECT item_id FROM tags_encrypted WHERE name = $6 AND value = $7)) AND NOT ((i.id in (SELECT item_id FROM tags_plaintext WHERE name = $8 AND value = $9)))))")
     }
+
+    #[test]
+    fn search_options_to_sql_works() {
+        assert_eq!(search_options_to_sql(None, false, None, None), "");
+        assert_eq!(search_options_to_sql(None, false, Some(10), Some(5)), " ORDER BY i.id LIMIT 5 OFFSET 10");
+        assert_eq!(search_options_to_sql(Some(&[1, 2, 3]), true, None, Some(5)),
+                   " ORDER BY (SELECT value FROM tags_plaintext WHERE item_id = i.id AND name = decode('010203', 'hex')) DESC, i.id DESC LIMIT 5");
+    }
 }
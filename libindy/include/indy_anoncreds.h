@@ -100,6 +100,61 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
 
+    extern indy_error_t indy_issuer_create_revoc_reg_manager(indy_handle_t command_handle,
+                                                             indy_handle_t wallet_handle,
+                                                             const char *  issuer_did,
+                                                             const char *  cred_def_id,
+                                                             const char *  config_json,
+                                                             indy_handle_t tails_writer_handle,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err,
+                                                                                  const char*   revoc_reg_id,
+                                                                                  const char*   revoc_reg_def_json,
+                                                                                  const char*   revoc_reg_entry_json)
+                                                             );
+
+    extern indy_error_t indy_issuer_create_credential_with_revoc_reg_manager(indy_handle_t command_handle,
+                                                                             indy_handle_t wallet_handle,
+                                                                             const char *  cred_offer_json,
+                                                                             const char *  cred_req_json,
+                                                                             const char *  cred_values_json,
+                                                                             indy_handle_t tails_writer_handle,
+                                                                             indy_handle_t blob_storage_reader_handle,
+
+                                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                                  indy_error_t  err,
+                                                                                                  const char*   cred_json,
+                                                                                                  const char*   cred_revoc_id,
+                                                                                                  const char*   revoc_reg_delta_json)
+                                                                             );
+
+    extern indy_error_t indy_issuer_get_revoc_reg_manager_state(indy_handle_t command_handle,
+                                                                indy_handle_t wallet_handle,
+                                                                const char *  cred_def_id,
+
+                                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                                     indy_error_t  err,
+                                                                                     const char*   registries_json)
+                                                                );
+
+    extern indy_error_t indy_issuer_mark_revoc_reg_published(indy_handle_t command_handle,
+                                                             indy_handle_t wallet_handle,
+                                                             const char *  rev_reg_id,
+
+                                                             void           (*cb)(indy_handle_t command_handle_,
+                                                                                  indy_error_t  err)
+                                                             );
+
+    extern indy_error_t indy_issuer_get_issued_credentials(indy_handle_t command_handle,
+                                                           indy_handle_t wallet_handle,
+                                                           const char *  query_json,
+
+                                                           void           (*cb)(indy_handle_t command_handle_,
+                                                                                indy_error_t  err,
+                                                                                const char*   issued_credentials_json)
+                                                           );
+
 /*    extern indy_error_t indy_issuer_recover_credential(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        indy_handle_t blob_storage_reader_handle,
@@ -246,6 +301,17 @@ extern "C" {
                                                                                                 indy_error_t  err)
                                                                            );
 
+    extern indy_error_t indy_prover_select_credentials_for_proof_req(indy_handle_t command_handle,
+                                                                     indy_handle_t wallet_handle,
+                                                                     const char *  proof_request_json,
+                                                                     const char *  policy_json,
+
+                                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                                          indy_error_t  err,
+                                                                                          const char*   requested_credentials_json,
+                                                                                          const char*   rev_states_json)
+                                                                     );
+
     extern indy_error_t indy_prover_create_proof(indy_handle_t command_handle,
                                                  indy_handle_t wallet_handle,
                                                  const char *  proof_req_json,
@@ -274,6 +340,19 @@ extern "C" {
                                                                         indy_bool_t   valid )
                                                    );
 
+    extern indy_error_t indy_verifier_verify_proof_with_report(indy_handle_t command_handle,
+                                                               const char *  proof_request_json,
+                                                               const char *  proof_json,
+                                                               const char *  schemas_json,
+                                                               const char *  credential_defs_jsons,
+                                                               const char *  rev_reg_defs_json,
+                                                               const char *  rev_regs_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   report_json)
+                                                               );
+
 
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
@@ -315,6 +394,13 @@ extern "C" {
                                                                  const char*   res)
                                             );
 
+    extern indy_error_t indy_encode_credential_values(indy_handle_t command_handle,
+                                                      const char *  cred_values_json,
+                                                      void           (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           const char*   encoded_cred_values_json)
+                                                      );
+
 #ifdef __cplusplus
 }
 #endif
@@ -123,6 +123,225 @@ extern "C" {
                                                   void          (*cb)(indy_handle_t command_handle_,
                                                                       indy_error_t  err)
                                                  );
+
+    /// Gets NYM data for specified DID.
+    /// If data is present inside of cache, cached data is returned.
+    /// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// target_did: DID to get NYM data for.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    ///
+    /// #Returns
+    /// NYM data json (the same as `indy_parse_get_nym_response` returns):
+    /// {
+    ///     did: DID as base58-encoded string for 16 or 32 bit DID value.
+    ///     verkey: verification key as base58-encoded string.
+    ///     role: Role associated number
+    /// }
+    extern indy_error_t indy_get_nym(indy_handle_t command_handle,
+                                     indy_handle_t pool_handle,
+                                     indy_handle_t wallet_handle,
+                                     const char *  submitter_did,
+                                     const char *  target_did,
+                                     const char *  options_json,
+                                     void          (*cb)(indy_handle_t command_handle_,
+                                                         indy_error_t  err,
+                                                         const char*   nym_json)
+                                    );
+
+    /// Gets ATTRIB data for specified DID and attribute.
+    /// If data is present inside of cache, cached data is returned.
+    /// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// target_did: Target DID as base58-encoded string for 16 or 32 bit DID value.
+    /// raw: (Optional) Requested attribute name.
+    /// hash: (Optional) Requested attribute hash.
+    /// enc: (Optional) Requested attribute encrypted value.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    ///
+    /// #Returns
+    /// Attribute data as stored on the ledger (for example `{"endpoint":{"ha":"127.0.0.1:5555"}}` for raw attribute).
+    extern indy_error_t indy_get_attrib(indy_handle_t command_handle,
+                                        indy_handle_t pool_handle,
+                                        indy_handle_t wallet_handle,
+                                        const char *  submitter_did,
+                                        const char *  target_did,
+                                        const char *  raw,
+                                        const char *  hash,
+                                        const char *  enc,
+                                        const char *  options_json,
+                                        void          (*cb)(indy_handle_t command_handle_,
+                                                            indy_error_t  err,
+                                                            const char*   attrib_data)
+                                       );
+
+    /// Gets revocation registry definition json data for specified revocation registry definition id.
+    /// If data is present inside of cache, cached data is returned.
+    /// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// id: identifier of revocation registry definition.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    extern indy_error_t indy_get_revoc_reg_def(indy_handle_t command_handle,
+                                               indy_handle_t pool_handle,
+                                               indy_handle_t wallet_handle,
+                                               const char *  submitter_did,
+                                               const char *  id,
+                                               const char *  options_json,
+                                               void          (*cb)(indy_handle_t command_handle_,
+                                                                   indy_error_t  err,
+                                                                   const char*   revoc_reg_def_json)
+                                              );
+
+    /// Gets revocation registry delta for specified revocation registry definition id and interval.
+    /// Deltas are cached per requested interval.
+    /// If data is present inside of cache, cached data is returned.
+    /// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// submitter_did: DID of the submitter stored in secured Wallet.
+    /// revoc_reg_def_id: ID of the corresponding Revocation Registry Definition in ledger.
+    /// from: Requested time represented as a total number of seconds from Unix Epoch (-1 to get the whole state till `to`).
+    /// to: Requested time represented as a total number of seconds from Unix Epoch.
+    /// options_json:
+    ///  {
+    ///    noCache: (bool, optional, false by default) Skip usage of cache,
+    ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+    ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+    ///  }
+    ///
+    /// #Returns
+    /// Revocation Registry Delta json (the same as `indy_parse_get_revoc_reg_delta_response` returns) and
+    /// timestamp of the delta on the ledger.
+    extern indy_error_t indy_get_revoc_reg_delta(indy_handle_t command_handle,
+                                                 indy_handle_t pool_handle,
+                                                 indy_handle_t wallet_handle,
+                                                 const char *  submitter_did,
+                                                 const char *  revoc_reg_def_id,
+                                                 long long     from,
+                                                 long long     to,
+                                                 const char *  options_json,
+                                                 void          (*cb)(indy_handle_t command_handle_,
+                                                                     indy_error_t  err,
+                                                                     const char*   revoc_reg_delta_json,
+                                                                     unsigned long long timestamp)
+                                                );
+
+    /// Purge NYM cache.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_nym_cache(indy_handle_t command_handle,
+                                             indy_handle_t wallet_handle,
+                                             const char *  options_json,
+                                             void          (*cb)(indy_handle_t command_handle_,
+                                                                 indy_error_t  err)
+                                            );
+
+    /// Purge ATTRIB cache.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_attrib_cache(indy_handle_t command_handle,
+                                                indy_handle_t wallet_handle,
+                                                const char *  options_json,
+                                                void          (*cb)(indy_handle_t command_handle_,
+                                                                    indy_error_t  err)
+                                               );
+
+    /// Purge revocation registry definition cache.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_revoc_reg_def_cache(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  options_json,
+                                                       void          (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err)
+                                                      );
+
+    /// Purge revocation registry delta cache.
+    ///
+    /// EXPERIMENTAL
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// options_json:
+    ///  {
+    ///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+    ///  }
+    extern indy_error_t indy_purge_revoc_reg_delta_cache(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         const char *  options_json,
+                                                         void          (*cb)(indy_handle_t command_handle_,
+                                                                             indy_error_t  err)
+                                                        );
 #ifdef __cplusplus
 }
 #endif
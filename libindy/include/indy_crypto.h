@@ -16,7 +16,9 @@ extern "C" {
     /// {
     ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
     ///                                Can be UTF-8, base64 or hex string.
-    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+    ///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and 'p256'.
+    ///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+    ///               to create the key in. Secret key never enters the wallet in this case.
     /// }
     /// cb: Callback that takes command result as parameter.
     ///
@@ -445,6 +447,137 @@ extern "C" {
                                                                  const indy_u8_t*  res_json_raw,
                                                                  indy_u32_t        res_json_len)
                                             );
+    /// Packs a message into DIDComm v2 encrypted envelope (JWE in General JSON Serialization).
+    ///
+    /// Authcrypt (ECDH-1PU+A256KW) is used if sender key id is passed, otherwise anoncrypt (ECDH-ES+A256KW).
+    /// Key agreement is done over X25519 keys derived from ed25519 keys stored in the wallet.
+    ///
+    /// Key id (kid) is either a verkey or a DID URL (`<did>#<fragment>`) of my or their DID stored in the wallet.
+    /// The DID URL is resolved to the verkey of the DID. Its fragment must identify this key:
+    /// `key-1`, multibase key fingerprint (as in `did:key`) or the verkey itself.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// message: a pointer to the first byte of the message to be packed
+    /// message_len: the length of the message
+    /// receiver_kids: json list of receiver's key ids the message is being encrypted for.
+    /// sender_kid: (optional) the sender's key id. When null pointer is used in this parameter, anoncrypt is used.
+    /// options_json: (optional) pack options as json:
+    /// {
+    ///     "enc": string, (optional) content encryption algorithm: "XC20P" (default) or "A256GCM"
+    /// }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// JWE with "protected", "recipients", "iv", "ciphertext" and "tag" fields (see libindy/src/api/crypto.rs for details)
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_pack_message_v2(indy_handle_t      command_handle,
+                                             indy_handle_t      wallet_handle,
+                                             const indy_u8_t*   message,
+                                             indy_u32_t         message_len,
+                                             const char *       receiver_kids,
+                                             const char *       sender_kid,
+                                             const char *       options_json,
+
+                                             void           (*cb)(indy_handle_t     command_handle_,
+                                                                  indy_error_t      err,
+                                                                  const indy_u8_t*  jwe_msg_raw,
+                                                                  indy_u32_t        jwe_msg_len)
+                                             );
+
+    /// Unpacks DIDComm v2 encrypted envelope outputted by indy_pack_message_v2.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to user context.
+    /// wallet_handle: wallet handle (created by open_wallet).
+    /// jwe_data: a pointer to the first byte of the JWE to be unpacked
+    /// jwe_len: the length of the JWE message in bytes
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// {
+    ///     message: <decrypted message>,
+    ///     recipient_kid: <recipient kid>,
+    ///     sender_kid: <sender kid> (authcrypt only)
+    /// }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+    /// Crypto*
+    extern indy_error_t indy_unpack_message_v2(indy_handle_t      command_handle,
+                                               indy_handle_t      wallet_handle,
+                                               const indy_u8_t*   jwe_msg,
+                                               indy_u32_t         jwe_len,
+
+                                               void           (*cb)(indy_handle_t     command_handle_,
+                                                                    indy_error_t      err,
+                                                                    const indy_u8_t*  res_json_raw,
+                                                                    indy_u32_t        res_json_len)
+                                               );
+
+    /// Register external key manager (e.g. KMS or HSM) implementation.
+    ///
+    /// Keys created with "key_manager" option of indy_create_key, indy_create_and_store_my_did
+    /// and indy_replace_keys_start are held by the key manager: the wallet stores only verkey
+    /// and key reference returned by create_key handler. Signing and decryption with such keys
+    /// (indy_crypto_sign, indy_sign_request, indy_pack_message, indy_unpack_message and others)
+    /// are delegated to the key manager handlers.
+    ///
+    /// Note that handlers are called synchronously from libindy thread.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// name: Key manager name.
+    /// create_key: Key creation handler
+    /// sign: Signing handler
+    /// crypto_box: Authenticated encryption handler
+    /// crypto_box_open: Authenticated decryption handler
+    /// crypto_box_seal_open: Anonymous decryption handler
+    /// key_agreement: X25519 key agreement handler
+    /// free: Handler that allows to de-allocate buffers returned by other handlers (called for every returned buffer, even if the handler failed)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_register_key_manager(indy_handle_t  command_handle,
+                                                  const char*    name,
+                                                  indy_error_t (*createKeyFn)(const char* crypto_type,
+                                                                              const indy_u8_t* seed, size_t seed_len,
+                                                                              const indy_u8_t** verkey_p, size_t* verkey_len_p,
+                                                                              const char** key_ref_p),
+                                                  indy_error_t (*signFn)(const char* key_ref,
+                                                                         const indy_u8_t* message, size_t message_len,
+                                                                         const indy_u8_t** signature_p, size_t* signature_len_p),
+                                                  indy_error_t (*cryptoBoxFn)(const char* key_ref,
+                                                                              const indy_u8_t* their_vk, size_t their_vk_len,
+                                                                              const indy_u8_t* message, size_t message_len,
+                                                                              const indy_u8_t* nonce, size_t nonce_len,
+                                                                              const indy_u8_t** encrypted_p, size_t* encrypted_len_p),
+                                                  indy_error_t (*cryptoBoxOpenFn)(const char* key_ref,
+                                                                                  const indy_u8_t* their_vk, size_t their_vk_len,
+                                                                                  const indy_u8_t* encrypted, size_t encrypted_len,
+                                                                                  const indy_u8_t* nonce, size_t nonce_len,
+                                                                                  const indy_u8_t** decrypted_p, size_t* decrypted_len_p),
+                                                  indy_error_t (*cryptoBoxSealOpenFn)(const char* key_ref,
+                                                                                      const indy_u8_t* encrypted, size_t encrypted_len,
+                                                                                      const indy_u8_t** decrypted_p, size_t* decrypted_len_p),
+                                                  indy_error_t (*keyAgreementFn)(const char* key_ref,
+                                                                                 const indy_u8_t* their_pk, size_t their_pk_len,
+                                                                                 const indy_u8_t** shared_secret_p, size_t* shared_secret_len_p),
+                                                  indy_error_t (*freeFn)(const indy_u8_t* buffer),
+                                                  void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                  );
+
 #ifdef __cplusplus
 }
 #endif
@@ -512,6 +512,181 @@ extern "C" {
                                                                                const char*   cred_def_json)
                                                           );
 
+    /// Resolves fully qualified did:indy identifier of a ledger object.
+    ///
+    /// The namespace of the identifier is used to select the opened pool to send request to
+    /// (see `namespace` parameter of indy_create_pool_ledger_config).
+    /// Then the object is fetched from the ledger and parsed.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// id: fully qualified identifier of the object:
+    ///     did:indy:<namespace>:<did> - DID
+    ///     did:indy:<namespace>:<did>/anoncreds/v0/SCHEMA/<name>/<version> - schema
+    ///     did:indy:<namespace>:<did>/anoncreds/v0/CLAIM_DEF/<schema seq no>/<tag> - credential definition
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// id: requested identifier.
+    /// object_json: parsed object (NYM data, schema or credential definition json).
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+     extern indy_error_t indy_resolve(indy_handle_t command_handle,
+                                      const char *  id,
+                                      void           (*cb)(indy_handle_t command_handle_,
+                                                           indy_error_t  err,
+                                                           const char*   id,
+                                                           const char*   object_json)
+                                      );
+
+    /// Downloads a range of ledger transactions.
+    ///
+    /// Transactions are fetched from the pool nodes in batches using catchup requests
+    /// and every batch is verified against the merkle root agreed by f+1 nodes.
+    /// Verified transactions are stored in the local append-only replica of the ledger,
+    /// so subsequent calls only fetch transactions missing in the replica.
+    /// Transactions aren't returned in the callback: they are appended to the file from `file` option
+    /// batch by batch or can be read by batches with indy_open_ledger_txns_search.
+    ///
+    /// Pool opened in offline mode serves transactions from the imported ledger snapshot
+    /// (DOMAIN ledger) and local replicas (other ledgers).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// ledger_type: (Optional) type of the ledger to download transactions from:
+    ///     DOMAIN - used default,
+    ///     POOL,
+    ///     CONFIG
+    ///     any number
+    /// from: sequence number of the first transaction to download (starting from 1).
+    /// to: sequence number of the last transaction to download (inclusive), -1 to download up to the current ledger size.
+    /// options_json: (Optional) download options:
+    ///     {
+    ///         file: (Optional) path to the file to append downloaded transactions to (one json per line).
+    ///     }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Download result json:
+    ///     {
+    ///         from: sequence number of the first downloaded transaction,
+    ///         to: sequence number of the last downloaded transaction,
+    ///         file: (Optional) path to the file transactions were appended to (if `file` option is set),
+    ///     }
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_download_ledger_txns(indy_handle_t command_handle,
+                                                  indy_handle_t pool_handle,
+                                                  const char *  ledger_type,
+                                                  indy_i32_t    from,
+                                                  indy_i32_t    to,
+                                                  const char *  options_json,
+                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       const char*   txns_json)
+                                                  );
+
+    /// Opens search over a range of ledger transactions.
+    ///
+    /// The range is downloaded to the local replica of the ledger like in indy_download_ledger_txns.
+    /// Instead of returning transactions this call returns search handle that can be used
+    /// to read them by batches (with indy_fetch_ledger_txns_search_next_batch).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger).
+    /// ledger_type: (Optional) type of the ledger to read transactions from: DOMAIN (default), POOL, CONFIG or any number.
+    /// from: sequence number of the first transaction to read (starting from 1).
+    /// to: sequence number of the last transaction to read (inclusive), -1 to read up to the current ledger size.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// search_handle: Ledger transactions search handle
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_open_ledger_txns_search(indy_handle_t command_handle,
+                                                     indy_handle_t pool_handle,
+                                                     const char *  ledger_type,
+                                                     indy_i32_t    from,
+                                                     indy_i32_t    to,
+                                                     void           (*cb)(indy_handle_t command_handle_,
+                                                                          indy_error_t  err,
+                                                                          indy_handle_t search_handle)
+                                                     );
+
+    /// Fetches next batch (at most 100) of transactions of the ledger transactions search.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// search_handle: ledger transactions search handle (created by indy_open_ledger_txns_search)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// txns_json: array of transactions ordered by seqNo, empty array if all transactions of the range are fetched.
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_fetch_ledger_txns_search_next_batch(indy_handle_t command_handle,
+                                                                 indy_handle_t search_handle,
+                                                                 void           (*cb)(indy_handle_t command_handle_,
+                                                                                      indy_error_t  err,
+                                                                                      const char*   txns_json)
+                                                                 );
+
+    /// Close ledger transactions search (make search handle invalid)
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// search_handle: ledger transactions search handle (created by indy_open_ledger_txns_search)
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Errors
+    /// Common*
+
+    extern indy_error_t indy_close_ledger_txns_search(indy_handle_t command_handle,
+                                                      indy_handle_t search_handle,
+                                                      void           (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err)
+                                                      );
+
+    /// Creates a local snapshot of the domain ledger from catchup of the opened pool
+    /// to use by the pool opened in offline mode later.
+    ///
+    /// Current size of the domain ledger and BLS multi-signature of its txn root are requested from the nodes,
+    /// then the local replica of the ledger is brought up to this size with catchup requests.
+    /// Snapshot is verified like the imported one and replaces previously stored snapshot of the pool.
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context.
+    /// pool_handle: pool handle (created by open_pool_ledger). Pool opened in offline mode isn't supported.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Snapshot info json: {ledgerSize: number of transactions in the snapshot, txnRootHash: signed txn root hash}
+    ///
+    /// #Errors
+    /// Common*
+    /// Ledger*
+
+    extern indy_error_t indy_create_pool_snapshot(indy_handle_t command_handle,
+                                                  indy_handle_t pool_handle,
+                                                  void           (*cb)(indy_handle_t command_handle_,
+                                                                       indy_error_t  err,
+                                                                       const char*   snapshot_info_json)
+                                                  );
+
     /// Builds a NODE request. Request to add a new node to the pool, or updates existing in the pool.
     ///
     /// #Params
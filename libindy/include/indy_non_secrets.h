@@ -200,6 +200,10 @@ extern "C" {
     ///    retrieveType: (optional, false by default) Retrieve record type,
     ///    retrieveValue: (optional, true by default) Retrieve record value,
     ///    retrieveTags: (optional, false by default) Retrieve record tags,
+    ///    sortBy: (optional) Sort records by id ("id") or by unencrypted tag value ({"tagName": "~tagName"}),
+    ///    sortOrder: (optional, "asc" by default) Sort order: "asc" or "desc",
+    ///    offset: (optional) Number of matching records to skip,
+    ///    limit: (optional) Max number of records to retrieve,
     ///  }
     /// #Returns
     /// search_handle: Wallet search handle that can be used later
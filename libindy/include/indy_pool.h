@@ -39,6 +39,18 @@ extern "C" {
                                                        void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
                                                        );
 
+    extern indy_error_t indy_import_pool_snapshot(indy_handle_t command_handle,
+                                                  const char *  config_name,
+                                                  const char *  snapshot_path,
+                                                  const char *  options,
+                                                  void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
+                                                  );
+
+    extern indy_error_t indy_get_pool_status(indy_handle_t command_handle,
+                                             indy_handle_t handle,
+                                             void          (*cb)(indy_handle_t command_handle_, indy_error_t err, const char *const status_json)
+                                             );
+
     extern indy_error_t indy_set_protocol_version(indy_handle_t command_handle,
                                                   indy_u64_t    protocol_version,
                                                   void          (*cb)(indy_handle_t command_handle_, indy_error_t err)
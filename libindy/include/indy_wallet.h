@@ -144,6 +144,51 @@ extern "C" {
                                                   void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                                   );
 
+    /// Register transaction handlers for custom wallet storage implementation.
+    /// Wallet storage type must be previously registered with indy_register_wallet_storage call.
+    /// Storages without registered transaction handlers don't support wallet transactions.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Storage type name.
+    /// begin_transaction: WalletType begin transaction operation handler
+    /// commit_transaction: WalletType commit transaction operation handler
+    /// rollback_transaction: WalletType rollback transaction operation handler
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_storage_transactions(indy_handle_t  command_handle,
+                                                                  const char*    type_,
+                                                                  indy_error_t (*beginTransactionFn)(indy_handle_t handle),
+                                                                  indy_error_t (*commitTransactionFn)(indy_handle_t handle),
+                                                                  indy_error_t (*rollbackTransactionFn)(indy_handle_t handle),
+                                                                  void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                                  );
+
+    /// Register storages listing handlers for custom wallet storage implementation.
+    /// Wallet storage type must be previously registered with indy_register_wallet_storage call.
+    /// Wallets of storages without registered listing handlers can't be listed with indy_list_wallets call.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Storage type name.
+    /// list_storages: WalletType list storages operation handler
+    /// free_storage_list: WalletType free storage list operation handler
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_storage_list(indy_handle_t  command_handle,
+                                                          const char*    type_,
+                                                          indy_error_t (*listStoragesFn)(const char* config,
+                                                                                         const char* credentials,
+                                                                                         const char** ids_json,
+                                                                                         indy_handle_t* ids_handle),
+                                                          indy_error_t (*freeStorageListFn)(indy_handle_t ids_handle),
+                                                          void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                          );
+
     /// Create a new secure wallet.
     ///
     /// #Params
@@ -153,6 +198,8 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+    ///                  and accepts the same configuration as 'default' one.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -190,6 +237,45 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                           );
 
+
+    /// Checks integrity of opened wallet.
+    ///
+    /// Every record of the wallet is verified to be decryptable with the wallet keys.
+    /// Encrypted type, id and tag names/values are additionally verified to match HMAC of their decrypted content,
+    /// so records damaged by a partial write or by a buggy storage plugin are detected.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// options_json: (optional) check options json
+    ///   {
+    ///       "quarantine": optional<bool>, Move broken records to "Indy::Quarantine" record type. false by default.
+    ///                     Quarantined record keeps raw (base64 encoded) content of the broken record as its value
+    ///                     and has "reason" tag, as well as "type" and "id" tags if they could be decrypted.
+    ///   }
+    ///
+    /// #Returns
+    /// report_json:
+    ///   {
+    ///       "total": int, amount of checked records
+    ///       "broken": [{
+    ///           "type": optional<string>, type of the broken record (absent if it can't be decrypted)
+    ///           "id": optional<string>, id of the broken record (absent if it can't be decrypted)
+    ///           "raw_id": string, base64 encoded id of the record as it is stored in the storage
+    ///           "reason": string, description of the damage
+    ///           "quarantined": bool, whether the record was moved to quarantine
+    ///       }]
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_check_wallet(indy_handle_t  command_handle,
+                                          indy_handle_t  wallet_handle,
+                                          const char*    options_json,
+                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* report_json)
+                                          );
+
     /// Open the wallet.
     ///
     /// Wallet must be previously created with indy_create_wallet method.
@@ -201,6 +287,8 @@ extern "C" {
     ///             Configured storage uses this identifier to lookup exact wallet data placement.
     ///       "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                       'Default' storage type allows to store wallet data in the local file.
+    ///                       'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+    ///                       and accepts the same configuration as 'default' one.
     ///                       Custom storage types can be registered with indy_register_wallet_storage call.
     ///       "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                         Can be optional if storage supports default configuration.
@@ -260,6 +348,15 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "version": optional<int> Export format version:
+    ///                 0 - single export file (used by default)
+    ///                 1 - "path" is a manifest file and encrypted records are stored in "chunks"
+    ///                     subdirectory of manifest directory. Records already stored there are not rewritten.
+    ///     "base_manifest": optional<string> Path of the manifest of previous version 1 export in the same directory
+    ///                      to make incremental backup to. The key derivation of the base export is used
+    ///                      and "key" has to match the key of the base export.
+    ///     "verkey": optional<string> Verkey of the wallet key (see indy_create_key) the manifest of version 1 export
+    ///               is signed with. Required for version 1. Base manifest has to be signed with the same key.
     ///   }
     ///
     /// #Returns
@@ -276,6 +373,46 @@ extern "C" {
                                            );
 
 
+    /// Rotates keys of opened wallet without export/import cycle.
+    ///
+    /// New wallet keys are generated and all wallet records are re-encrypted with them in place.
+    /// The keys are protected with a master key derived from the new credentials.
+    /// Progress of records re-encryption is reported with progress_cb.
+    ///
+    /// Operation is crash-safe: if it is interrupted after the new credentials were applied,
+    /// the wallet can be opened with the new credentials only and re-encryption of the rest of records
+    /// will be finished by indy_open_wallet.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// new_credentials: New wallet credentials json
+    ///   {
+    ///       "key": string, New key or passphrase used for wallet key derivation.
+    ///                      Look to key_derivation_method param for information about supported key derivation methods.
+    ///       "key_derivation_method": optional<string> Algorithm to use for wallet key derivation:
+    ///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+    ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+    ///                          RAW - raw wallet key master provided (skip derivation).
+    ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///   }
+    ///   Storage credentials can't be changed with this call, "storage_credentials" and "rekey" fields are ignored.
+    /// progress_cb: optional callback called with amount of processed and total amount of wallet records.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_rekey_wallet(indy_handle_t  command_handle,
+                                          indy_handle_t  wallet_handle,
+                                          const char*    new_credentials,
+                                          void           (*progress_fn)(indy_handle_t command_handle_, indy_u32_t processed, indy_u32_t total),
+                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                          );
+
+
     /// Creates a new secure wallet and then imports its content
     /// according to fields provided in import_config
     /// This can be seen as an indy_create_wallet call with additional content import
@@ -287,6 +424,8 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+    ///                  and accepts the same configuration as 'default' one.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -313,7 +452,10 @@ extern "C" {
     /// import_config: Import settings json.
     /// {
     ///   "path": <string>, path of the file that contains exported wallet content
+    ///           or of the manifest file of version 1 export (format is detected automatically)
     ///   "key": <string>, key used for export of the wallet
+    ///   "verkey": optional<string>, verkey the manifest of version 1 export is signed with.
+    ///             Required for version 1. Signatures of the manifest and of all its base manifests are verified.
     /// }
     ///
     /// #Returns
@@ -347,6 +489,116 @@ extern "C" {
                                           void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                          );
 
+    /// Begin a transaction on the opened wallet.
+    /// All following changes of wallet records (including non-secrets, DIDs, keys, credentials and etc.)
+    /// are applied atomically: they become visible for other wallet users
+    /// and durable only after indy_wallet_commit_transaction call.
+    ///
+    /// Note that transactions can't be nested and the wallet storage must support transactions
+    /// ('default' storage does, custom storages can register handlers with indy_register_wallet_storage_transactions call).
+    /// If the wallet is closed before commit all changes made in the transaction are discarded.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_wallet_begin_transaction(indy_handle_t  command_handle,
+                                                      indy_handle_t  wallet_handle,
+                                                      void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                     );
+
+    /// Commit the transaction started with indy_wallet_begin_transaction call.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_wallet_commit_transaction(indy_handle_t  command_handle,
+                                                       indy_handle_t  wallet_handle,
+                                                       void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                      );
+
+    /// Rollback the transaction started with indy_wallet_begin_transaction call.
+    /// All changes made in the transaction are discarded.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_wallet_rollback_transaction(indy_handle_t  command_handle,
+                                                         indy_handle_t  wallet_handle,
+                                                         void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                        );
+
+    /// Subscribes to changes of records of opened wallet.
+    ///
+    /// Events are produced for records added, updated or deleted through any libindy call
+    /// working with the wallet (non_secrets, did, anoncreds, etc.).
+    /// Changes made inside of a wallet transaction are reported after the transaction is committed.
+    /// Subscription is dropped with indy_unsubscribe_wallet_events call or when the wallet is closed.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    /// type_filter: (optional) type of records to receive events for. Events for all types are delivered if not set.
+    ///              Note that records created by libindy itself have "Indy::" prefix (for example "Indy::Credential").
+    /// event_cb: Callback called with command_handle of this call and event json for each change:
+    ///   {
+    ///       "event": string, one of "add", "update" (value or tags of the record were changed), "delete"
+    ///       "type": string, type of the record
+    ///       "id": string, id of the record
+    ///   }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// subscription_handle: handle to pass to indy_unsubscribe_wallet_events
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_subscribe_wallet_events(indy_handle_t  command_handle,
+                                                     indy_handle_t  wallet_handle,
+                                                     const char*    type_filter,
+                                                     void           (*event_fn)(indy_handle_t command_handle_, const char* event_json),
+                                                     void           (*fn)(indy_handle_t command_handle_, indy_error_t err, indy_handle_t subscription_handle)
+                                                    );
+
+    /// Cancels subscription to wallet records changes made with indy_subscribe_wallet_events call.
+    ///
+    /// #Params
+    /// subscription_handle: subscription handle returned by indy_subscribe_wallet_events.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_unsubscribe_wallet_events(indy_handle_t  command_handle,
+                                                       indy_handle_t  subscription_handle,
+                                                       void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                      );
+
     /// Deletes created wallet.
     ///
     /// #Params
@@ -356,6 +608,8 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+    ///                  and accepts the same configuration as 'default' one.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -393,6 +647,31 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                           );
 
+    /// Lists wallets existing in the wallet storage.
+    ///
+    /// #Params
+    /// storage_type: (optional) Type of the wallet storage. Defaults to 'default'.
+    ///               Custom storage types must register listing handlers with indy_register_wallet_storage_list call.
+    /// storage_config: (optional) Storage configuration json. The same as "storage_config" of indy_create_wallet config.
+    ///                 For 'default' and 'sled' storage types wallets are looked up in {path}
+    ///                 ($HOME/.indy_client/wallet by default).
+    /// storage_credentials: (optional) Credentials for wallet storage. The same as "storage_credentials" of indy_create_wallet credentials.
+    ///
+    /// #Returns
+    /// wallets_json: sorted list of ids of existing wallets
+    ///   ["id1", "id2", ...]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_list_wallets(indy_handle_t  command_handle,
+                                          const char*    storage_type,
+                                          const char*    storage_config,
+                                          const char*    storage_credentials,
+                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* wallets_json)
+                                          );
+
     /// Generate wallet master key.
     /// Returned key is compatible with "RAW" key derivation method.
     /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
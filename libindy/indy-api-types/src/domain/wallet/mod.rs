@@ -38,7 +38,22 @@ pub struct ExportConfig {
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
-    pub key_derivation_method: KeyDerivationMethod
+    pub key_derivation_method: KeyDerivationMethod,
+    // 0 - single export file, 1 - manifest file and directory of encrypted record chunks
+    #[serde(default)]
+    pub version: u32,
+    // Path to manifest of previous chunked export to make incremental backup to
+    pub base_manifest: Option<String>,
+    // Verkey of wallet key the manifest of chunked export is signed with (export)
+    // and the manifest chain is verified against (import). Required for version 1
+    pub verkey: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CheckOptions {
+    // Move broken records to the quarantine record type
+    #[serde(default)]
+    pub quarantine: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -276,6 +276,16 @@ impl From<rusqlite::Error> for IndyError {
     }
 }
 
+#[cfg(feature = "casting_errors")]
+impl From<sled::Error> for IndyError {
+    fn from(err: sled::Error) -> IndyError {
+        match err {
+            sled::Error::Io(_) => err.to_indy(IndyErrorKind::IOError, "IO error during access sled database"),
+            _ => err.to_indy(IndyErrorKind::InvalidState, "Unexpected sled error"),
+        }
+    }
+}
+
 impl<T> From<IndyResult<T>> for ErrorCode {
     fn from(r: Result<T, IndyError>) -> ErrorCode {
         match r {
@@ -538,6 +538,10 @@ pub mod wallet {
     ///    retrieveType: (optional, false by default) Retrieve record type,
     ///    retrieveValue: (optional, true by default) Retrieve record value,
     ///    retrieveTags: (optional, false by default) Retrieve record tags,
+    ///    sortBy: (optional) Sort records by unencrypted tag value ({"tagName": "~<base64 of encrypted tag name>"}),
+    ///    sortOrder: (optional, "asc" by default) Sort order: "asc" or "desc",
+    ///    offset: (optional) Number of matching records to skip,
+    ///    limit: (optional) Max number of records to retrieve,
     ///  }
     /// search_handle_p: pointer to store wallet search handle
     pub type WalletSearchRecords = extern fn(storage_handle: StorageHandle,
@@ -586,4 +590,148 @@ pub mod wallet {
     pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                           search_handle: i32) -> ErrorCode;
 
-}
\ No newline at end of file
+    /// Begin a transaction on the wallet storage.
+    /// All following record and tag operations must be applied atomically
+    /// until commit or rollback handler is called.
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    pub type WalletBeginTransaction = extern fn(storage_handle: StorageHandle) -> ErrorCode;
+
+    /// Commit the transaction started by begin_transaction handler
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    pub type WalletCommitTransaction = extern fn(storage_handle: StorageHandle) -> ErrorCode;
+
+    /// Rollback the transaction started by begin_transaction handler
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    pub type WalletRollbackTransaction = extern fn(storage_handle: StorageHandle) -> ErrorCode;
+
+    /// List ids of storages (wallets) known to the storage type
+    ///
+    /// #Params
+    /// config: storage config (For example, database config), null if not provided
+    /// credentials_json: storage credentials (For example, database credentials), null if not provided
+    /// ids_json_p: pointer to store json array of storage ids:
+    ///   ["id1", "id2", ...]
+    /// ids_handle_p: pointer to store handle of the retrieved list (See free_storage_list handler)
+    pub type WalletListStorages = extern fn(config: *const c_char,
+                                            credentials_json: *const c_char,
+                                            ids_json_p: *mut *const c_char,
+                                            ids_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free storage ids list retrieved with list_storages handler (make ids handle invalid)
+    ///
+    /// #Params
+    /// ids_handle: retrieved list handle (See list_storages handler)
+    pub type WalletFreeStorageList = extern fn(ids_handle: IndyHandle) -> ErrorCode;
+
+}
+pub mod key_manager {
+    use super::*;
+    use libc::c_char;
+
+    /// Create a new key in the key manager
+    ///
+    /// #Params
+    /// crypto_type: crypto type of the key (ed25519, secp256k1 or p256)
+    /// seed: (optional) seed for deterministic key creation, null if not set
+    /// seed_len: seed length
+    /// verkey_p: pointer to store raw public key bytes
+    /// verkey_len_p: pointer to store public key length
+    /// key_ref_p: pointer to store reference of the created key inside of the key manager
+    ///
+    /// returns: Note that returned buffers must be released with free handler
+    pub type KeyManagerCreateKey = extern fn(crypto_type: *const c_char,
+                                             seed: *const u8,
+                                             seed_len: usize,
+                                             verkey_p: *mut *const u8,
+                                             verkey_len_p: *mut usize,
+                                             key_ref_p: *mut *const c_char) -> ErrorCode;
+
+    /// Sign a message with the key
+    ///
+    /// #Params
+    /// key_ref: reference of the key (See create_key handler)
+    /// message: message to sign
+    /// message_len: message length
+    /// signature_p: pointer to store signature
+    /// signature_len_p: pointer to store signature length
+    pub type KeyManagerSign = extern fn(key_ref: *const c_char,
+                                        message: *const u8,
+                                        message_len: usize,
+                                        signature_p: *mut *const u8,
+                                        signature_len_p: *mut usize) -> ErrorCode;
+
+    /// Authenticated encryption (crypto_box) of a message with the key for other party
+    ///
+    /// #Params
+    /// key_ref: reference of the key (See create_key handler)
+    /// their_vk: raw public key of other party
+    /// their_vk_len: public key length
+    /// message: message to encrypt
+    /// message_len: message length
+    /// nonce: nonce to use
+    /// nonce_len: nonce length
+    /// encrypted_p: pointer to store encrypted message
+    /// encrypted_len_p: pointer to store encrypted message length
+    pub type KeyManagerCryptoBox = extern fn(key_ref: *const c_char,
+                                             their_vk: *const u8,
+                                             their_vk_len: usize,
+                                             message: *const u8,
+                                             message_len: usize,
+                                             nonce: *const u8,
+                                             nonce_len: usize,
+                                             encrypted_p: *mut *const u8,
+                                             encrypted_len_p: *mut usize) -> ErrorCode;
+
+    /// Decryption of a message encrypted by other party with crypto_box
+    ///
+    /// Parameters are the same as for crypto_box handler.
+    pub type KeyManagerCryptoBoxOpen = extern fn(key_ref: *const c_char,
+                                                 their_vk: *const u8,
+                                                 their_vk_len: usize,
+                                                 encrypted: *const u8,
+                                                 encrypted_len: usize,
+                                                 nonce: *const u8,
+                                                 nonce_len: usize,
+                                                 decrypted_p: *mut *const u8,
+                                                 decrypted_len_p: *mut usize) -> ErrorCode;
+
+    /// Decryption of an anonymously encrypted (sealed box) message
+    ///
+    /// #Params
+    /// key_ref: reference of the key (See create_key handler)
+    /// encrypted: encrypted message
+    /// encrypted_len: encrypted message length
+    /// decrypted_p: pointer to store decrypted message
+    /// decrypted_len_p: pointer to store decrypted message length
+    pub type KeyManagerCryptoBoxSealOpen = extern fn(key_ref: *const c_char,
+                                                     encrypted: *const u8,
+                                                     encrypted_len: usize,
+                                                     decrypted_p: *mut *const u8,
+                                                     decrypted_len_p: *mut usize) -> ErrorCode;
+
+    /// X25519 key agreement of the key (converted to X25519) with other party public key
+    ///
+    /// #Params
+    /// key_ref: reference of the key (See create_key handler)
+    /// their_pk: raw X25519 public key of other party
+    /// their_pk_len: public key length
+    /// shared_secret_p: pointer to store shared secret
+    /// shared_secret_len_p: pointer to store shared secret length
+    pub type KeyManagerKeyAgreement = extern fn(key_ref: *const c_char,
+                                                their_pk: *const u8,
+                                                their_pk_len: usize,
+                                                shared_secret_p: *mut *const u8,
+                                                shared_secret_len_p: *mut usize) -> ErrorCode;
+
+    /// Free buffer returned by one of key manager handlers
+    ///
+    /// #Params
+    /// buffer: buffer to free
+    pub type KeyManagerFree = extern fn(buffer: *const u8) -> ErrorCode;
+}
@@ -2,6 +2,7 @@ extern crate sodiumoxide;
 
 use indy_api_types::errors::prelude::*;
 use self::sodiumoxide::crypto::box_;
+use self::sodiumoxide::crypto::scalarmult::curve25519;
 
 
 pub const NONCEBYTES: usize = box_::curve25519xsalsa20poly1305::NONCEBYTES;
@@ -35,6 +36,25 @@ pub fn gen_nonce() -> Nonce {
     Nonce(box_::gen_nonce())
 }
 
+pub fn gen_keypair() -> (PublicKey, SecretKey) {
+    let (pk, sk) = box_::gen_keypair();
+    (PublicKey(pk), SecretKey(sk))
+}
+
+/// Raw X25519 Diffie-Hellman. Fails for low order public keys giving all-zero shared secret.
+pub fn scalarmult(secret_key: &SecretKey, public_key: &PublicKey) -> Result<Vec<u8>, IndyError> {
+    let scalar = curve25519::Scalar((secret_key.0).0);
+    let point = curve25519::GroupElement((public_key.0).0);
+
+    let shared_secret = curve25519::scalarmult(&scalar, &point);
+
+    if shared_secret.0.iter().all(|byte| *byte == 0) {
+        return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Invalid public key for X25519"));
+    }
+
+    Ok(shared_secret.0.to_vec())
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -66,4 +86,12 @@ mod tests {
         assert!(alice_decrypted_text.is_ok());
         assert_eq!(text, alice_decrypted_text.unwrap());
     }
+
+    #[test]
+    fn scalarmult_works() {
+        let (alice_pk, alice_sk) = gen_keypair();
+        let (bob_pk, bob_sk) = gen_keypair();
+
+        assert_eq!(scalarmult(&alice_sk, &bob_pk).unwrap(), scalarmult(&bob_sk, &alice_pk).unwrap());
+    }
 }
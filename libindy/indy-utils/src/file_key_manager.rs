@@ -0,0 +1,257 @@
+//! Local file based stand-in of external key manager (see indy_register_key_manager).
+//!
+//! Stores ed25519 secret keys as files in temporary directory. Intended for tests only.
+
+extern crate libc;
+extern crate sodiumoxide;
+
+use super::ErrorCode;
+use super::environment;
+
+use self::libc::{c_char, c_int};
+use self::sodiumoxide::crypto::{box_, sealedbox, sign};
+use self::sodiumoxide::crypto::scalarmult::curve25519;
+
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::path::PathBuf;
+use std::slice;
+use std::sync::Mutex;
+
+pub const FILE_KEY_MANAGER: &str = "file";
+
+extern {
+    fn crypto_sign_ed25519_pk_to_curve25519(curve25519_pk: *mut [u8; box_::PUBLICKEYBYTES],
+                                            ed25519_pk: *const [u8; sign::PUBLICKEYBYTES]) -> c_int;
+    fn crypto_sign_ed25519_sk_to_curve25519(curve25519_sk: *mut [u8; box_::SECRETKEYBYTES],
+                                            ed25519_sk: *const [u8; sign::SECRETKEYBYTES]) -> c_int;
+}
+
+lazy_static! {
+    static ref ACTIVE_BUFFERS: Mutex<HashMap<usize, Vec<u8>>> = Default::default();
+}
+
+pub struct FileKeyManager {}
+
+impl FileKeyManager {
+    pub extern "C" fn create_key(crypto_type: *const c_char,
+                                 seed: *const u8,
+                                 seed_len: usize,
+                                 verkey_p: *mut *const u8,
+                                 verkey_len_p: *mut usize,
+                                 key_ref_p: *mut *const c_char) -> ErrorCode {
+        match _c_str(crypto_type) {
+            Some(ref crypto_type) if crypto_type == "ed25519" => {}
+            _ => return ErrorCode::UnknownCryptoTypeError
+        }
+
+        let (vk, sk) = if seed.is_null() || seed_len == 0 {
+            sign::gen_keypair()
+        } else {
+            match sign::Seed::from_slice(unsafe { slice::from_raw_parts(seed, seed_len) }) {
+                Some(seed) => sign::keypair_from_seed(&seed),
+                None => return ErrorCode::CommonInvalidStructure
+            }
+        };
+
+        let key_ref = _hex(&vk[..]);
+
+        if fs::create_dir_all(_key_dir()).is_err() || fs::write(_key_path(&key_ref), &sk[..]).is_err() {
+            return ErrorCode::CommonIOError;
+        }
+
+        let key_ref = CString::new(key_ref).unwrap().into_bytes_with_nul();
+
+        unsafe {
+            *key_ref_p = _store_buffer(key_ref) as *const c_char;
+            *verkey_len_p = vk[..].len();
+            *verkey_p = _store_buffer(vk[..].to_vec());
+        }
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn sign(key_ref: *const c_char,
+                           message: *const u8,
+                           message_len: usize,
+                           signature_p: *mut *const u8,
+                           signature_len_p: *mut usize) -> ErrorCode {
+        let sk = match _load_key(key_ref) {
+            Some(sk) => sk,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let message = unsafe { slice::from_raw_parts(message, message_len) };
+        let signature = sign::sign_detached(message, &sk);
+
+        _return_buffer(signature[..].to_vec(), signature_p, signature_len_p)
+    }
+
+    pub extern "C" fn crypto_box(key_ref: *const c_char,
+                                 their_vk: *const u8,
+                                 their_vk_len: usize,
+                                 message: *const u8,
+                                 message_len: usize,
+                                 nonce: *const u8,
+                                 nonce_len: usize,
+                                 encrypted_p: *mut *const u8,
+                                 encrypted_len_p: *mut usize) -> ErrorCode {
+        let (sk, their_pk, nonce) = match _box_params(key_ref, their_vk, their_vk_len, nonce, nonce_len) {
+            Some(params) => params,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let message = unsafe { slice::from_raw_parts(message, message_len) };
+        let encrypted = box_::seal(message, &nonce, &their_pk, &sk);
+
+        _return_buffer(encrypted, encrypted_p, encrypted_len_p)
+    }
+
+    pub extern "C" fn crypto_box_open(key_ref: *const c_char,
+                                      their_vk: *const u8,
+                                      their_vk_len: usize,
+                                      encrypted: *const u8,
+                                      encrypted_len: usize,
+                                      nonce: *const u8,
+                                      nonce_len: usize,
+                                      decrypted_p: *mut *const u8,
+                                      decrypted_len_p: *mut usize) -> ErrorCode {
+        let (sk, their_pk, nonce) = match _box_params(key_ref, their_vk, their_vk_len, nonce, nonce_len) {
+            Some(params) => params,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let encrypted = unsafe { slice::from_raw_parts(encrypted, encrypted_len) };
+
+        match box_::open(encrypted, &nonce, &their_pk, &sk) {
+            Ok(decrypted) => _return_buffer(decrypted, decrypted_p, decrypted_len_p),
+            Err(_) => ErrorCode::CommonInvalidStructure
+        }
+    }
+
+    pub extern "C" fn crypto_box_seal_open(key_ref: *const c_char,
+                                           encrypted: *const u8,
+                                           encrypted_len: usize,
+                                           decrypted_p: *mut *const u8,
+                                           decrypted_len_p: *mut usize) -> ErrorCode {
+        let sk = match _load_key(key_ref) {
+            Some(sk) => sk,
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        // ed25519 secret key contains public key in the last 32 bytes
+        let pk = match sign::PublicKey::from_slice(&sk[32..]) {
+            Some(pk) => _pk_to_curve25519(&pk),
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let encrypted = unsafe { slice::from_raw_parts(encrypted, encrypted_len) };
+
+        match sealedbox::open(encrypted, &pk, &_sk_to_curve25519(&sk)) {
+            Ok(decrypted) => _return_buffer(decrypted, decrypted_p, decrypted_len_p),
+            Err(_) => ErrorCode::CommonInvalidStructure
+        }
+    }
+
+    pub extern "C" fn key_agreement(key_ref: *const c_char,
+                                    their_pk: *const u8,
+                                    their_pk_len: usize,
+                                    shared_secret_p: *mut *const u8,
+                                    shared_secret_len_p: *mut usize) -> ErrorCode {
+        let sk = match _load_key(key_ref) {
+            Some(sk) => _sk_to_curve25519(&sk),
+            None => return ErrorCode::WalletItemNotFound
+        };
+
+        let their_pk = match curve25519::GroupElement::from_slice(unsafe { slice::from_raw_parts(their_pk, their_pk_len) }) {
+            Some(their_pk) => their_pk,
+            None => return ErrorCode::CommonInvalidStructure
+        };
+
+        let shared_secret = curve25519::scalarmult(&curve25519::Scalar(sk.0), &their_pk);
+
+        _return_buffer(shared_secret.0.to_vec(), shared_secret_p, shared_secret_len_p)
+    }
+
+    pub extern "C" fn free(buffer: *const u8) -> ErrorCode {
+        match ACTIVE_BUFFERS.lock().unwrap().remove(&(buffer as usize)) {
+            Some(_) => ErrorCode::Success,
+            None => ErrorCode::CommonInvalidState
+        }
+    }
+
+    pub fn cleanup() {
+        let _ = fs::remove_dir_all(_key_dir());
+        ACTIVE_BUFFERS.lock().unwrap().clear();
+    }
+}
+
+fn _c_str(cstr: *const c_char) -> Option<String> {
+    if cstr.is_null() {
+        return None;
+    }
+
+    unsafe { CStr::from_ptr(cstr) }.to_str().ok().map(str::to_string)
+}
+
+fn _hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn _key_dir() -> PathBuf {
+    environment::tmp_path().join("file_key_manager")
+}
+
+fn _key_path(key_ref: &str) -> PathBuf {
+    _key_dir().join(key_ref)
+}
+
+fn _load_key(key_ref: *const c_char) -> Option<sign::SecretKey> {
+    _c_str(key_ref)
+        .and_then(|key_ref| fs::read(_key_path(&key_ref)).ok())
+        .and_then(|sk| sign::SecretKey::from_slice(&sk))
+}
+
+fn _box_params(key_ref: *const c_char,
+               their_vk: *const u8,
+               their_vk_len: usize,
+               nonce: *const u8,
+               nonce_len: usize) -> Option<(box_::SecretKey, box_::PublicKey, box_::Nonce)> {
+    let sk = _load_key(key_ref)?;
+    let their_vk = sign::PublicKey::from_slice(unsafe { slice::from_raw_parts(their_vk, their_vk_len) })?;
+    let nonce = box_::Nonce::from_slice(unsafe { slice::from_raw_parts(nonce, nonce_len) })?;
+
+    Some((_sk_to_curve25519(&sk), _pk_to_curve25519(&their_vk), nonce))
+}
+
+fn _sk_to_curve25519(sk: &sign::SecretKey) -> box_::SecretKey {
+    let mut to = [0u8; box_::SECRETKEYBYTES];
+    unsafe {
+        crypto_sign_ed25519_sk_to_curve25519(&mut to, &sk.0);
+    }
+    box_::SecretKey(to)
+}
+
+fn _pk_to_curve25519(pk: &sign::PublicKey) -> box_::PublicKey {
+    let mut to = [0u8; box_::PUBLICKEYBYTES];
+    unsafe {
+        crypto_sign_ed25519_pk_to_curve25519(&mut to, &pk.0);
+    }
+    box_::PublicKey(to)
+}
+
+fn _store_buffer(buffer: Vec<u8>) -> *const u8 {
+    let ptr = buffer.as_ptr();
+    ACTIVE_BUFFERS.lock().unwrap().insert(ptr as usize, buffer);
+    ptr
+}
+
+fn _return_buffer(buffer: Vec<u8>, buffer_p: *mut *const u8, buffer_len_p: *mut usize) -> ErrorCode {
+    unsafe {
+        *buffer_len_p = buffer.len();
+        *buffer_p = _store_buffer(buffer);
+    }
+
+    ErrorCode::Success
+}
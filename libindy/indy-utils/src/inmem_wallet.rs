@@ -88,6 +88,10 @@ lazy_static! {
     static ref ACTIVE_RECORDS: Mutex<HashMap<i32, InmemWalletRecord>> = Default::default();
 }
 
+lazy_static! {
+    static ref ACTIVE_STORAGE_LISTS: Mutex<HashMap<i32, CString>> = Default::default();
+}
+
 lazy_static! {
     static ref ACTIVE_SEARCHES: Mutex<HashMap<i32, Vec<InmemWalletRecord>,>> = Default::default();
 }
@@ -753,6 +757,38 @@ impl InmemWallet {
         ErrorCode::Success
     }
 
+    pub extern "C" fn list_storages(_: *const c_char,
+                                    _: *const c_char,
+                                    ids_json_ptr: *mut *const c_char,
+                                    ids_handle: *mut i32) -> ErrorCode {
+        let wallets = INMEM_WALLETS.lock().unwrap();
+
+        let mut ids: Vec<&String> = wallets.keys().collect();
+        ids.sort();
+
+        let ids_json = CString::new(serde_json::to_string(&ids).unwrap()).unwrap();
+        let ids_json_pointer = ids_json.as_ptr();
+
+        let handle = sequence::get_next_id();
+
+        let mut lists = ACTIVE_STORAGE_LISTS.lock().unwrap();
+        lists.insert(handle, ids_json);
+
+        unsafe { *ids_json_ptr = ids_json_pointer; }
+        unsafe { *ids_handle = handle };
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn free_storage_list(ids_handle: i32) -> ErrorCode {
+        let mut lists = ACTIVE_STORAGE_LISTS.lock().unwrap();
+
+        match lists.remove(&ids_handle) {
+            Some(_) => ErrorCode::Success,
+            None => ErrorCode::CommonInvalidState
+        }
+    }
+
     pub fn cleanup() {
         let mut wallets = INMEM_WALLETS.lock().unwrap();
         wallets.clear();
@@ -1,5 +1,9 @@
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::OsStr;
+use std::fs;
 use std::io;
 use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
@@ -8,14 +12,27 @@ use rmp_serde;
 use indy_api_types::domain::wallet::Record;
 use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
-use crate::encryption::KeyDerivationData;
-use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
+use crate::encryption::{KeyDerivationData, decrypt_merged, encrypt_as_not_searchable};
+use indy_utils::crypto::{base64, chacha20poly1305_ietf, hmacsha256, pwhash_argon2i13};
 use indy_utils::crypto::hash::{hash, HASHBYTES};
 
 use super::{Wallet, WalletRecord};
 
 const CHUNK_SIZE: usize = 1024;
 
+// Export format versions
+const VERSION_FILE: u32 = 0;
+const VERSION_CHUNKS: u32 = 1;
+
+// Subdirectory of backup directory with encrypted record chunks shared by all manifests of the directory
+const CHUNKS_DIR: &str = "chunks";
+
+// Signs serialized manifest with the wallet key of manifest verkey
+pub type ManifestSigner<'a> = &'a dyn Fn(&[u8]) -> IndyResult<Vec<u8>>;
+
+// Verifies signature of serialized manifest made by the key of the given verkey
+pub type ManifestVerifier<'a> = &'a dyn Fn(&str, &[u8], &[u8]) -> IndyResult<bool>;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EncryptionMethod {
     // **ChaCha20-Poly1305-IETF** cypher in blocks per chunk_size bytes
@@ -69,7 +86,172 @@ pub struct Header {
 //   "version": ..,
 // }
 
+// Version 1 export produces a directory of content-addressed record chunks plus a manifest file:
+//
+// <backup dir>/<manifest file> - header (same as version 0) followed by encrypted stream
+//                                with header hash and msgpack serialized SignedManifest
+// <backup dir>/chunks/<id>     - record encrypted with export key, id is urlsafe base64 of
+//                                HMAC-SHA256 of the record keyed by the export key
+//
+// Manifest of incremental backup lists all chunks of the wallet, but only chunks missing
+// in the directory are written. Incremental backup has to use the same export key as its base.
+//
+// Manifest is signed with a wallet key, so the holder of the export key alone can't forge
+// the backup. Incremental manifest includes hash of the signed base manifest, so the whole
+// chain of backups is verified on import.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    // File name of the manifest this backup was made incrementally to
+    pub base: Option<String>,
+    // Hash of the signed base manifest
+    pub base_hash: Option<Vec<u8>>,
+    // Verkey of the key the manifest is signed with
+    pub verkey: String,
+    // Ids of chunks with wallet records
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SignedManifest {
+    // msgpack serialized Manifest
+    manifest: Vec<u8>,
+    // Signature of serialized manifest
+    signature: Vec<u8>,
+}
+
+// Record is serialized with sorted tags as chunk id of unchanged record must be the same for every backup
+#[derive(Debug, Serialize, Deserialize)]
+struct ChunkRecord {
+    type_: String,
+    id: String,
+    value: String,
+    tags: BTreeMap<String, String>,
+}
+
 pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+    let mut writer = _write_header(writer, version, key, key_data)?;
+
+    let mut records = wallet.get_all()?;
+
+    while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
+        let record = Record {
+            type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
+            id,
+            value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
+            tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
+        };
+
+        let record = rmp_serde::to_vec(&record)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+
+        writer.write_u32::<LittleEndian>(record.len() as u32)?;
+        writer.write_all(&record)?;
+    }
+
+    writer.write_u32::<LittleEndian>(0)?; // END message
+    writer.flush()?;
+    Ok(())
+}
+
+pub(super) fn export_chunks_continue(wallet: &Wallet,
+                                     manifest_path: &Path,
+                                     base_manifest_path: Option<&Path>,
+                                     key: chacha20poly1305_ietf::Key,
+                                     key_data: &KeyDerivationData,
+                                     verkey: &str,
+                                     sign: ManifestSigner,
+                                     verify: ManifestVerifier) -> IndyResult<()> {
+    let backup_dir = manifest_path.parent().unwrap_or_else(|| Path::new(""));
+    let chunks_dir = backup_dir.join(CHUNKS_DIR);
+
+    let (base_manifest, base_hash) = match base_manifest_path {
+        Some(base_manifest_path) => {
+            if base_manifest_path.parent().unwrap_or_else(|| Path::new("")) != backup_dir {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Base manifest has to be located in the same directory as exported one"));
+            }
+
+            let file = fs::OpenOptions::new().read(true).open(base_manifest_path)?;
+            let signed_manifest = _read_manifest(file, key.clone())?;
+
+            let base_manifest = _verify_manifest(&signed_manifest, verkey, verify)?;
+            _verify_manifest_chain(backup_dir, &base_manifest, &key, verkey, verify)?;
+
+            (Some(base_manifest), Some(hash(&signed_manifest)?))
+        }
+        None => (None, None)
+    };
+
+    let base_chunks: HashSet<&str> = base_manifest
+        .as_ref()
+        .map(|manifest| manifest.chunks.iter().map(String::as_str).collect())
+        .unwrap_or_default();
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .create(&chunks_dir)?;
+
+    let chunk_id_key = _chunk_id_key(&key)?;
+    let mut chunks = Vec::new();
+    let mut records = wallet.get_all()?;
+
+    while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
+        let record = ChunkRecord {
+            type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
+            id,
+            value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
+            tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?.into_iter().collect(),
+        };
+
+        let record = rmp_serde::to_vec(&record)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+
+        let chunk_id = _chunk_id(&record, &chunk_id_key);
+        let chunk_path = chunks_dir.join(&chunk_id);
+
+        // Unchanged records are already stored by one of previous backups
+        if !base_chunks.contains(chunk_id.as_str()) && !chunk_path.exists() {
+            let tmp_path = chunks_dir.join(format!("{}.tmp", chunk_id));
+            fs::write(&tmp_path, encrypt_as_not_searchable(&record, &key))?;
+            fs::rename(&tmp_path, &chunk_path)?;
+        }
+
+        chunks.push(chunk_id);
+    }
+
+    let manifest = Manifest {
+        base: base_manifest_path.and_then(Path::file_name).map(|name| name.to_string_lossy().into_owned()),
+        base_hash,
+        verkey: verkey.to_string(),
+        chunks,
+    };
+
+    let manifest = rmp_serde::to_vec(&manifest)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize backup manifest")?;
+
+    let signed_manifest = SignedManifest {
+        signature: sign(&manifest)?,
+        manifest,
+    };
+
+    let manifest = rmp_serde::to_vec(&signed_manifest)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize signed backup manifest")?;
+
+    let mut manifest_file =
+        fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(manifest_path)?;
+
+    let mut writer = _write_header(&mut manifest_file, VERSION_CHUNKS, key, key_data)?;
+    writer.write_u32::<LittleEndian>(manifest.len() as u32)?;
+    writer.write_all(&manifest)?;
+    writer.write_u32::<LittleEndian>(0)?; // END message
+    writer.flush()?;
+    writer.into_inner().flush()?;
+    Ok(())
+}
+
+fn _write_header<W: Write>(writer: W, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<chacha20poly1305_ietf::Writer<BufWriter<W>>> {
     let nonce = chacha20poly1305_ietf::gen_nonce();
     let chunk_size = CHUNK_SIZE;
 
@@ -112,54 +294,18 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
     writer.write_all(&hash(&header)?)?;
 
-    let mut records = wallet.get_all()?;
-
-    while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
-        let record = Record {
-            type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
-            id,
-            value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
-            tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
-        };
-
-        let record = rmp_serde::to_vec(&record)
-            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
-
-        writer.write_u32::<LittleEndian>(record.len() as u32)?;
-        writer.write_all(&record)?;
-    }
-
-    writer.write_u32::<LittleEndian>(0)?; // END message
-    writer.flush()?;
-    Ok(())
+    Ok(writer)
 }
 
 #[cfg(test)]
-fn import<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> where T: Read {
+fn import<T>(wallet: &Wallet, reader: T, passphrase: &str, backup_path: &Path, verkey: Option<&str>, verify: ManifestVerifier) -> IndyResult<()> where T: Read {
     let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase)?;
     let import_key = import_key_derivation_data.calc_master_key()?;
-    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes)
+    finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes, backup_path, verkey, verify)
 }
 
 pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyResult<(BufReader<T>, KeyDerivationData, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)> where T: Read {
-    // Reads plain
-    let mut reader = BufReader::new(reader);
-
-    let header_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
-
-    if header_len == 0 {
-        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header length"));
-    }
-
-    let mut header_bytes = vec![0u8; header_len];
-    reader.read_exact(&mut header_bytes).map_err(_map_io_err)?;
-
-    let header: Header = rmp_serde::from_slice(&header_bytes)
-        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
-
-    if header.version != 0 {
-        return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported version"));
-    }
+    let (reader, header, header_bytes) = _read_header(reader)?;
 
     let key_derivation_method = match header.encryption_method {
         EncryptionMethod::ChaCha20Poly1305IETF { .. } => KeyDerivationMethod::ARGON2I_MOD,
@@ -200,17 +346,163 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     Ok((reader, import_key_derivation_data, nonce, chunk_size, header_bytes))
 }
 
-pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chacha20poly1305_ietf::Key, nonce: chacha20poly1305_ietf::Nonce, chunk_size: usize, header_bytes: Vec<u8>) -> IndyResult<()> where T: Read {
-    // Reads encrypted
-    let mut reader = chacha20poly1305_ietf::Reader::new(reader, key, nonce, chunk_size);
+fn _read_header<T>(reader: T) -> IndyResult<(BufReader<T>, Header, Vec<u8>)> where T: Read {
+    // Reads plain
+    let mut reader = BufReader::new(reader);
+
+    let header_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
 
+    if header_len == 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header length"));
+    }
+
+    let mut header_bytes = vec![0u8; header_len];
+    reader.read_exact(&mut header_bytes).map_err(_map_io_err)?;
+
+    let header: Header = rmp_serde::from_slice(&header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
+
+    if header.version != VERSION_FILE && header.version != VERSION_CHUNKS {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported version"));
+    }
+
+    Ok((reader, header, header_bytes))
+}
+
+fn _read_header_hash<T>(reader: &mut T, header_bytes: &[u8]) -> IndyResult<()> where T: Read {
     let mut header_hash = vec![0u8; HASHBYTES];
     reader.read_exact(&mut header_hash).map_err(_map_io_err)?;
 
-    if hash(&header_bytes)? != header_hash {
+    if hash(header_bytes)? != header_hash {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header hash"));
     }
 
+    Ok(())
+}
+
+fn _read_manifest<T>(reader: T, key: chacha20poly1305_ietf::Key) -> IndyResult<Vec<u8>> where T: Read {
+    let (reader, header, header_bytes) = _read_header(reader)?;
+
+    if header.version != VERSION_CHUNKS {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Base backup isn't a manifest of chunked export"));
+    }
+
+    let (nonce, chunk_size) = match header.encryption_method {
+        EncryptionMethod::ChaCha20Poly1305IETF { nonce, chunk_size, .. } |
+        EncryptionMethod::ChaCha20Poly1305IETFInteractive { nonce, chunk_size, .. } |
+        EncryptionMethod::ChaCha20Poly1305IETFRaw { nonce, chunk_size } => (nonce, chunk_size)
+    };
+
+    let nonce = chacha20poly1305_ietf::Nonce::from_slice(&nonce)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid nonce")?;
+
+    let mut reader = chacha20poly1305_ietf::Reader::new(reader, key, nonce, chunk_size);
+
+    _read_header_hash(&mut reader, &header_bytes)?;
+    _read_manifest_body(&mut reader)
+}
+
+// Returns serialized SignedManifest as it's hashed by the manifests made incrementally to this one
+fn _read_manifest_body<T>(reader: &mut T) -> IndyResult<Vec<u8>> where T: Read {
+    let manifest_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
+
+    if manifest_len == 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid manifest length"));
+    }
+
+    let mut manifest = vec![0u8; manifest_len];
+    reader.read_exact(&mut manifest).map_err(_map_io_err)?;
+
+    if reader.read_u32::<LittleEndian>().map_err(_map_io_err)? != 0 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid manifest end"));
+    }
+
+    Ok(manifest)
+}
+
+fn _verify_manifest(signed_manifest: &[u8], verkey: &str, verify: ManifestVerifier) -> IndyResult<Manifest> {
+    let signed_manifest: SignedManifest = rmp_serde::from_slice(signed_manifest)
+        .to_indy(IndyErrorKind::InvalidStructure, "Signed manifest is malformed msgpack")?;
+
+    if !verify(verkey, &signed_manifest.manifest, &signed_manifest.signature)? {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid backup manifest signature"));
+    }
+
+    let manifest: Manifest = rmp_serde::from_slice(&signed_manifest.manifest)
+        .to_indy(IndyErrorKind::InvalidStructure, "Manifest is malformed msgpack")?;
+
+    if manifest.verkey != verkey {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Backup manifest is signed by other key"));
+    }
+
+    Ok(manifest)
+}
+
+// Follows base links of the manifest checking that every base manifest is untouched and signed by the same key
+fn _verify_manifest_chain(backup_dir: &Path, manifest: &Manifest, key: &chacha20poly1305_ietf::Key, verkey: &str, verify: ManifestVerifier) -> IndyResult<()> {
+    let mut visited = HashSet::new();
+    let mut link = (manifest.base.clone(), manifest.base_hash.clone());
+
+    loop {
+        let (base, base_hash) = match link {
+            (Some(base), Some(base_hash)) => (base, base_hash),
+            (None, None) => return Ok(()),
+            _ => return Err(err_msg(IndyErrorKind::InvalidStructure, "Backup manifest has malformed base link"))
+        };
+
+        if Path::new(&base).file_name() != Some(OsStr::new(&base)) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Base manifest {} isn't a file name", base)));
+        }
+
+        if !visited.insert(base.clone()) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Base manifest {} is referenced twice", base)));
+        }
+
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .open(backup_dir.join(&base))
+            .to_indy(IndyErrorKind::IOError, format!("Can't read base manifest {}", base))?;
+
+        let signed_manifest = _read_manifest(file, key.clone())?;
+
+        if hash(&signed_manifest)? != base_hash {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Base manifest {} doesn't match its hash", base)));
+        }
+
+        let base_manifest = _verify_manifest(&signed_manifest, verkey, verify)?;
+        link = (base_manifest.base, base_manifest.base_hash);
+    }
+}
+
+pub(super) fn finish_import<T>(wallet: &Wallet,
+                               reader: BufReader<T>,
+                               key: chacha20poly1305_ietf::Key,
+                               nonce: chacha20poly1305_ietf::Nonce,
+                               chunk_size: usize,
+                               header_bytes: Vec<u8>,
+                               backup_path: &Path,
+                               verkey: Option<&str>,
+                               verify: ManifestVerifier) -> IndyResult<()> where T: Read {
+    let header: Header = rmp_serde::from_slice(&header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
+
+    // Reads encrypted
+    let mut reader = chacha20poly1305_ietf::Reader::new(reader, key.clone(), nonce, chunk_size);
+
+    _read_header_hash(&mut reader, &header_bytes)?;
+
+    if header.version == VERSION_CHUNKS {
+        let verkey = verkey
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Verkey of backup manifest signature is required to import chunked export"))?;
+
+        let manifest = _verify_manifest(&_read_manifest_body(&mut reader)?, verkey, verify)?;
+
+        let backup_dir = backup_path.parent().unwrap_or_else(|| Path::new(""));
+        _verify_manifest_chain(backup_dir, &manifest, &key, verkey, verify)?;
+
+        return _import_chunks(wallet, &manifest, backup_path, &key);
+    }
+
     loop {
         let record_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
 
@@ -230,6 +522,43 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
     Ok(())
 }
 
+fn _import_chunks(wallet: &Wallet, manifest: &Manifest, manifest_path: &Path, key: &chacha20poly1305_ietf::Key) -> IndyResult<()> {
+    let chunks_dir = manifest_path.parent().unwrap_or_else(|| Path::new("")).join(CHUNKS_DIR);
+    let chunk_id_key = _chunk_id_key(key)?;
+
+    for chunk_id in manifest.chunks.iter() {
+        let chunk = fs::read(chunks_dir.join(chunk_id))
+            .to_indy(IndyErrorKind::IOError, format!("Can't read backup chunk {}", chunk_id))?;
+
+        if chunk.len() < chacha20poly1305_ietf::NONCEBYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Backup chunk {} is too short", chunk_id)));
+        }
+
+        let record = decrypt_merged(&chunk, key)
+            .map_err(|err| err.map(IndyErrorKind::InvalidStructure, format!("Can't decrypt backup chunk {}", chunk_id)))?;
+
+        if _chunk_id(&record, &chunk_id_key) != *chunk_id {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Backup chunk {} doesn't match its id", chunk_id)));
+        }
+
+        let record: ChunkRecord = rmp_serde::from_slice(&record)
+            .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
+
+        wallet.add(&record.type_, &record.id, &record.value, &record.tags.into_iter().collect())?;
+    }
+
+    Ok(())
+}
+
+fn _chunk_id_key(key: &chacha20poly1305_ietf::Key) -> IndyResult<hmacsha256::Key> {
+    let key_hash = hash(&[b"chunk_id".as_ref(), &key[..]].concat())?;
+    hmacsha256::Key::from_slice(&key_hash)
+}
+
+fn _chunk_id(record: &[u8], chunk_id_key: &hmacsha256::Key) -> String {
+    base64::encode_urlsafe(&hmacsha256::authenticate(record, chunk_id_key)[..])
+}
+
 fn _map_io_err(e: io::Error) -> IndyError {
     match e {
         ref e if e.kind() == io::ErrorKind::UnexpectedEof
@@ -241,6 +570,7 @@ fn _map_io_err(e: io::Error) -> IndyError {
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::path::PathBuf;
     use std::rc::Rc;
 
     use serde_json;
@@ -250,7 +580,8 @@ mod tests {
     use crate::storage::default::SQLiteStorageType;
     use crate::storage::WalletStorageType;
     use crate::wallet::{Keys, Wallet};
-    use indy_utils::crypto::pwhash_argon2i13;
+    use indy_utils::crypto::{ed25519_sign, pwhash_argon2i13};
+    use indy_utils::environment;
     use indy_utils::test;
 
     use super::*;
@@ -283,7 +614,7 @@ mod tests {
             let mut wallet = _wallet("export_import_works_for_empty_wallet2");
             _assert_is_empty(&wallet);
 
-            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            import(&wallet, &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify).unwrap();
             _assert_is_empty(&wallet);
 
             wallet.close().unwrap();
@@ -302,7 +633,7 @@ mod tests {
             let wallet = _wallet("export_import_works_for_2_items2");
             _assert_is_empty(&wallet);
 
-            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            import(&wallet, &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify).unwrap();
             _assert_has_2_records(&wallet);
         }
         _cleanup("export_import_works_for_2_items1");
@@ -323,7 +654,7 @@ mod tests {
             let wallet = _wallet("export_import_works_for_2_items_and_interactive_method2");
             _assert_is_empty(&wallet);
 
-            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            import(&wallet, &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify).unwrap();
             _assert_has_2_records(&wallet);
         }
         _cleanup("export_import_works_for_2_items_and_interactive_method2");
@@ -340,18 +671,188 @@ mod tests {
             let wallet = _wallet("export_import_works_for_multiple_items2");
             _assert_is_empty(&wallet);
 
-            import(&wallet, &mut output.as_slice(), _passphrase()).unwrap();
+            import(&wallet, &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify).unwrap();
             _assert_has_300_records(&wallet);
         }
         _cleanup("export_import_works_for_multiple_items1");
         _cleanup("export_import_works_for_multiple_items2");
     }
 
+    #[test]
+    fn export_import_works_for_chunks() {
+        _cleanup("export_import_works_for_chunks1");
+        _cleanup("export_import_works_for_chunks2");
+        {
+            let backup_dir = _backup_dir("export_import_works_for_chunks");
+            let manifest_path = backup_dir.join("backup1");
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&_add_2_records(_wallet("export_import_works_for_chunks1")), &manifest_path, None, key, &key_data, &_verkey(), &_sign, &_verify).unwrap();
+            assert_eq!(2, _chunks_count(&backup_dir));
+
+            let wallet = _wallet("export_import_works_for_chunks2");
+            _assert_is_empty(&wallet);
+
+            import(&wallet, fs::File::open(&manifest_path).unwrap(), _passphrase(), &manifest_path, Some(&_verkey()), &_verify).unwrap();
+            _assert_has_2_records(&wallet);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("export_import_works_for_chunks1");
+        _cleanup("export_import_works_for_chunks2");
+    }
+
+    #[test]
+    fn export_import_works_for_incremental_chunks() {
+        _cleanup("export_import_works_for_incremental_chunks1");
+        _cleanup("export_import_works_for_incremental_chunks2");
+        {
+            let backup_dir = _backup_dir("export_import_works_for_incremental_chunks");
+            let base_manifest_path = backup_dir.join("backup1");
+            let manifest_path = backup_dir.join("backup2");
+
+            let wallet = _wallet("export_import_works_for_incremental_chunks1");
+            wallet.add(&_type1(), &_id1(), &_value1(), &_tags1()).unwrap();
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&wallet, &base_manifest_path, None, key.clone(), &key_data, &_verkey(), &_sign, &_verify).unwrap();
+            assert_eq!(1, _chunks_count(&backup_dir));
+
+            wallet.add(&_type2(), &_id2(), &_value2(), &_tags2()).unwrap();
+
+            export_chunks_continue(&wallet, &manifest_path, Some(&base_manifest_path), key, &key_data, &_verkey(), &_sign, &_verify).unwrap();
+            assert_eq!(2, _chunks_count(&backup_dir));
+
+            let wallet = _wallet("export_import_works_for_incremental_chunks2");
+            import(&wallet, fs::File::open(&manifest_path).unwrap(), _passphrase(), &manifest_path, Some(&_verkey()), &_verify).unwrap();
+            _assert_has_2_records(&wallet);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("export_import_works_for_incremental_chunks1");
+        _cleanup("export_import_works_for_incremental_chunks2");
+    }
+
+    #[test]
+    fn export_chunks_works_for_base_with_other_key() {
+        _cleanup("export_chunks_works_for_base_with_other_key");
+        {
+            let backup_dir = _backup_dir("export_chunks_works_for_base_with_other_key");
+            let base_manifest_path = backup_dir.join("backup1");
+            let manifest_path = backup_dir.join("backup2");
+
+            let wallet = _add_2_records(_wallet("export_chunks_works_for_base_with_other_key"));
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&wallet, &base_manifest_path, None, key, &key_data, &_verkey(), &_sign, &_verify).unwrap();
+
+            let (key, key_data) = _export_key();
+            let res = export_chunks_continue(&wallet, &manifest_path, Some(&base_manifest_path), key, &key_data, &_verkey(), &_sign, &_verify);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("export_chunks_works_for_base_with_other_key");
+    }
+
+    #[test]
+    fn import_works_for_missed_chunk() {
+        _cleanup("import_works_for_missed_chunk1");
+        _cleanup("import_works_for_missed_chunk2");
+        {
+            let backup_dir = _backup_dir("import_works_for_missed_chunk");
+            let manifest_path = backup_dir.join("backup1");
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&_add_2_records(_wallet("import_works_for_missed_chunk1")), &manifest_path, None, key, &key_data, &_verkey(), &_sign, &_verify).unwrap();
+
+            let chunk = fs::read_dir(backup_dir.join(CHUNKS_DIR)).unwrap().next().unwrap().unwrap();
+            fs::remove_file(chunk.path()).unwrap();
+
+            let res = import(&_wallet("import_works_for_missed_chunk2"), fs::File::open(&manifest_path).unwrap(), _passphrase(), &manifest_path, Some(&_verkey()), &_verify);
+            assert_kind!(IndyErrorKind::IOError, res);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("import_works_for_missed_chunk1");
+        _cleanup("import_works_for_missed_chunk2");
+    }
+
+    #[test]
+    fn import_works_for_other_verkey() {
+        _cleanup("import_works_for_other_verkey1");
+        _cleanup("import_works_for_other_verkey2");
+        {
+            let backup_dir = _backup_dir("import_works_for_other_verkey");
+            let manifest_path = backup_dir.join("backup1");
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&_add_2_records(_wallet("import_works_for_other_verkey1")), &manifest_path, None, key, &key_data, &_verkey(), &_sign, &_verify).unwrap();
+
+            let res = import(&_wallet("import_works_for_other_verkey2"), fs::File::open(&manifest_path).unwrap(), _passphrase(), &manifest_path, Some(&_other_verkey()), &_verify);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("import_works_for_other_verkey1");
+        _cleanup("import_works_for_other_verkey2");
+    }
+
+    #[test]
+    fn import_works_for_forged_manifest_signature() {
+        _cleanup("import_works_for_forged_manifest_signature1");
+        _cleanup("import_works_for_forged_manifest_signature2");
+        {
+            let backup_dir = _backup_dir("import_works_for_forged_manifest_signature");
+            let manifest_path = backup_dir.join("backup1");
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&_add_2_records(_wallet("import_works_for_forged_manifest_signature1")), &manifest_path, None, key, &key_data, &_verkey(), &_sign_other, &_verify).unwrap();
+
+            let res = import(&_wallet("import_works_for_forged_manifest_signature2"), fs::File::open(&manifest_path).unwrap(), _passphrase(), &manifest_path, Some(&_verkey()), &_verify);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("import_works_for_forged_manifest_signature1");
+        _cleanup("import_works_for_forged_manifest_signature2");
+    }
+
+    #[test]
+    fn import_works_for_replaced_base_manifest() {
+        _cleanup("import_works_for_replaced_base_manifest1");
+        _cleanup("import_works_for_replaced_base_manifest2");
+        {
+            let backup_dir = _backup_dir("import_works_for_replaced_base_manifest");
+            let base_manifest_path = backup_dir.join("backup1");
+            let manifest_path = backup_dir.join("backup2");
+
+            let wallet = _wallet("import_works_for_replaced_base_manifest1");
+            wallet.add(&_type1(), &_id1(), &_value1(), &_tags1()).unwrap();
+
+            let (key, key_data) = _export_key();
+            export_chunks_continue(&wallet, &base_manifest_path, None, key.clone(), &key_data, &_verkey(), &_sign, &_verify).unwrap();
+            export_chunks_continue(&wallet, &manifest_path, Some(&base_manifest_path), key.clone(), &key_data, &_verkey(), &_sign, &_verify).unwrap();
+
+            // Base manifest is replaced with other valid signed manifest
+            wallet.add(&_type2(), &_id2(), &_value2(), &_tags2()).unwrap();
+            fs::remove_file(&base_manifest_path).unwrap();
+            export_chunks_continue(&wallet, &base_manifest_path, None, key, &key_data, &_verkey(), &_sign, &_verify).unwrap();
+
+            let res = import(&_wallet("import_works_for_replaced_base_manifest2"), fs::File::open(&manifest_path).unwrap(), _passphrase(), &manifest_path, Some(&_verkey()), &_verify);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+        _cleanup("import_works_for_replaced_base_manifest1");
+        _cleanup("import_works_for_replaced_base_manifest2");
+    }
+
     #[test]
     fn import_works_for_empty() {
         _cleanup("import_works_for_empty");
 
-        let res = import(&_wallet("import_works_for_empty"), &mut "".as_bytes(), _passphrase());
+        let res = import(&_wallet("import_works_for_empty"), &mut "".as_bytes(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_empty");
     }
@@ -360,7 +861,7 @@ mod tests {
     fn import_works_for_cut_header_length() {
         _cleanup("import_works_for_cut_header_length");
 
-        let res = import(&_wallet("import_works_for_cut_header_length"), &mut "\x00".as_bytes(), _passphrase());
+        let res = import(&_wallet("import_works_for_cut_header_length"), &mut "\x00".as_bytes(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_cut_header_length");
     }
@@ -369,7 +870,7 @@ mod tests {
     fn import_works_for_cut_header_body() {
         _cleanup("import_works_for_cut_header_body");
 
-        let res = import(&_wallet("import_works_for_cut_header_body"), &mut "\x00\x20small".as_bytes(), _passphrase());
+        let res = import(&_wallet("import_works_for_cut_header_body"), &mut "\x00\x20small".as_bytes(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_cut_header_body");
     }
@@ -387,7 +888,7 @@ mod tests {
             output
         };
 
-        let res = import(&_wallet("import_works_for_invalid_header_body"), &mut output.as_slice(), _passphrase());
+        let res = import(&_wallet("import_works_for_invalid_header_body"), &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_invalid_header_body");
     }
@@ -404,7 +905,7 @@ mod tests {
         let pos = (&mut output.as_slice()).read_u32::<LittleEndian>().unwrap() as usize + 2;
         _change_byte(&mut output, pos);
 
-        let res = import(&mut _wallet("import_works_for_invalid_header_hash2"), &mut output.as_slice(), _passphrase());
+        let res = import(&mut _wallet("import_works_for_invalid_header_hash2"), &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_invalid_header_hash1");
         _cleanup("import_works_for_invalid_header_hash2");
@@ -422,7 +923,7 @@ mod tests {
         let pos = output.len() / 2;
         _change_byte(&mut output, pos);
 
-        let res = import(&mut _wallet("export_import_works_for_changed_record2"), &mut output.as_slice(), _passphrase());
+        let res = import(&mut _wallet("export_import_works_for_changed_record2"), &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("export_import_works_for_changed_record1");
         _cleanup("export_import_works_for_changed_record2");
@@ -438,7 +939,7 @@ mod tests {
 
         output.pop().unwrap();
 
-        let res = import(&mut _wallet("import_works_for_data_cut2"), &mut output.as_slice(), _passphrase());
+        let res = import(&mut _wallet("import_works_for_data_cut2"), &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_data_cut1");
         _cleanup("import_works_for_data_cut2");
@@ -454,7 +955,7 @@ mod tests {
 
         output.push(10);
 
-        let res = import(&mut _wallet("import_works_for_data_extended2"), &mut output.as_slice(), _passphrase());
+        let res = import(&mut _wallet("import_works_for_data_extended2"), &mut output.as_slice(), _passphrase(), Path::new(""), None, &_verify);
         assert_eq!(IndyErrorKind::InvalidStructure, res.unwrap_err().kind());
         _cleanup("import_works_for_data_extended1");
         _cleanup("import_works_for_data_extended2");
@@ -480,6 +981,7 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                next_keys: None,
             });
 
             serde_json::to_vec(&metadata)
@@ -538,6 +1040,54 @@ mod tests {
         }
     }
 
+    fn _export_key() -> (chacha20poly1305_ietf::Key, KeyDerivationData) {
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(_passphrase(), &KeyDerivationMethod::ARGON2I_INT);
+        let key = key_data.calc_master_key().unwrap();
+        (key, key_data)
+    }
+
+    fn _sign_key(seed: u8) -> (ed25519_sign::PublicKey, ed25519_sign::SecretKey) {
+        let seed = ed25519_sign::Seed::from_slice(&[seed; ed25519_sign::SEEDBYTES]).unwrap();
+        ed25519_sign::create_key_pair_for_signature(Some(&seed)).unwrap()
+    }
+
+    fn _verkey() -> String {
+        base64::encode(&_sign_key(1).0[..])
+    }
+
+    fn _other_verkey() -> String {
+        base64::encode(&_sign_key(2).0[..])
+    }
+
+    fn _sign(doc: &[u8]) -> IndyResult<Vec<u8>> {
+        Ok(ed25519_sign::sign(&_sign_key(1).1, doc)?[..].to_vec())
+    }
+
+    fn _sign_other(doc: &[u8]) -> IndyResult<Vec<u8>> {
+        Ok(ed25519_sign::sign(&_sign_key(2).1, doc)?[..].to_vec())
+    }
+
+    fn _verify(verkey: &str, doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+        let verkey = ed25519_sign::PublicKey::from_slice(&base64::decode(verkey)?)?;
+        let signature = ed25519_sign::Signature::from_slice(signature)?;
+        ed25519_sign::verify(&verkey, doc, &signature)
+    }
+
+    fn _backup_dir(name: &str) -> PathBuf {
+        let path = environment::tmp_path().join(name);
+
+        if path.exists() {
+            fs::remove_dir_all(&path).unwrap();
+        }
+
+        fs::create_dir_all(&path).unwrap();
+        path
+    }
+
+    fn _chunks_count(backup_dir: &Path) -> usize {
+        fs::read_dir(backup_dir.join(CHUNKS_DIR)).unwrap().count()
+    }
+
     fn _master_key() -> chacha20poly1305_ietf::Key {
         chacha20poly1305_ietf::gen_key()
     }
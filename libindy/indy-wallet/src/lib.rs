@@ -15,25 +15,28 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use serde_json::Value as SValue;
 
 use indy_api_types::wallet::*;
 
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, Tags};
+use indy_api_types::domain::wallet::{CheckOptions, Config, Credentials, ExportConfig, Tags};
 use indy_api_types::errors::prelude::*;
 pub use crate::encryption::KeyDerivationData;
+use indy_utils::crypto::base64;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 
-use self::export_import::{export_continue, finish_import, preparse_file_to_import};
+pub use self::export_import::{ManifestSigner, ManifestVerifier};
+use self::export_import::{export_chunks_continue, export_continue, finish_import, preparse_file_to_import};
 use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
-use self::storage::plugged::PluggedStorageType;
+use self::storage::sled::SledStorageType;
+use self::storage::plugged::{PluggedListHandlers, PluggedStorageType, PluggedTransactionHandlers};
 use self::wallet::{Keys, Wallet};
-use indy_api_types::{WalletHandle};
+use indy_api_types::{WalletHandle, IndyHandle};
 
 mod storage;
 mod encryption;
@@ -46,10 +49,14 @@ mod wallet;
 
 pub struct WalletService {
     storage_types: RefCell<HashMap<String, Box<dyn WalletStorageType>>>,
+    plugged_storage_types: RefCell<HashMap<String, Rc<PluggedStorageType>>>,
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
     wallet_ids: RefCell<HashSet<String>>,
     pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
-    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData, PathBuf, Option<String>)>>,
+    subscriptions: RefCell<HashMap<IndyHandle, WalletSubscription>>,
+    // Events of wallets with open transaction, delivered only after commit
+    transaction_events: RefCell<HashMap<WalletHandle, Vec<WalletEvent>>>,
 }
 
 impl WalletService {
@@ -57,15 +64,19 @@ impl WalletService {
         let storage_types = {
             let mut map: HashMap<String, Box<dyn WalletStorageType>> = HashMap::new();
             map.insert("default".to_string(), Box::new(SQLiteStorageType::new()));
+            map.insert("sled".to_string(), Box::new(SledStorageType::new()));
             RefCell::new(map)
         };
 
         WalletService {
             storage_types,
+            plugged_storage_types: RefCell::new(HashMap::new()),
             wallets: RefCell::new(HashMap::new()),
             wallet_ids: RefCell::new(HashSet::new()),
             pending_for_open: RefCell::new(HashMap::new()),
             pending_for_import: RefCell::new(HashMap::new()),
+            subscriptions: RefCell::new(HashMap::new()),
+            transaction_events: RefCell::new(HashMap::new()),
         }
     }
 
@@ -103,22 +114,86 @@ impl WalletService {
             return Err(err_msg(IndyErrorKind::WalletStorageTypeAlreadyRegistered, format!("Wallet storage is already registered for type: {}", type_)));
         }
 
-        storage_types.insert(type_.to_string(),
-                             Box::new(
-                                 PluggedStorageType::new(create, open, close, delete,
-                                                         add_record, update_record_value,
-                                                         update_record_tags, add_record_tags, delete_record_tags,
-                                                         delete_record, get_record, get_record_id,
-                                                         get_record_type, get_record_value, get_record_tags, free_record,
-                                                         get_storage_metadata, set_storage_metadata, free_storage_metadata,
-                                                         search_records, search_all_records,
-                                                         get_search_total_count,
-                                                         fetch_search_next_record, free_search)));
+        let storage_type = Rc::new(
+            PluggedStorageType::new(create, open, close, delete,
+                                    add_record, update_record_value,
+                                    update_record_tags, add_record_tags, delete_record_tags,
+                                    delete_record, get_record, get_record_id,
+                                    get_record_type, get_record_value, get_record_tags, free_record,
+                                    get_storage_metadata, set_storage_metadata, free_storage_metadata,
+                                    search_records, search_all_records,
+                                    get_search_total_count,
+                                    fetch_search_next_record, free_search));
+
+        storage_types.insert(type_.to_string(), Box::new(storage_type.clone()));
+        self.plugged_storage_types.borrow_mut().insert(type_.to_string(), storage_type);
 
         trace!("register_wallet_storage <<<");
         Ok(())
     }
 
+    pub fn register_wallet_storage_transactions(&self,
+                                                type_: &str,
+                                                begin_transaction: WalletBeginTransaction,
+                                                commit_transaction: WalletCommitTransaction,
+                                                rollback_transaction: WalletRollbackTransaction) -> IndyResult<()> {
+        trace!("register_wallet_storage_transactions >>> type_: {:?}", type_);
+
+        let plugged_storage_types = self.plugged_storage_types.borrow();
+
+        let storage_type = plugged_storage_types
+            .get(type_)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Custom wallet storage isn't registered for type: {}", type_)))?;
+
+        storage_type.set_transaction_handlers(PluggedTransactionHandlers {
+            begin_transaction_handler: begin_transaction,
+            commit_transaction_handler: commit_transaction,
+            rollback_transaction_handler: rollback_transaction,
+        });
+
+        trace!("register_wallet_storage_transactions <<<");
+        Ok(())
+    }
+
+    pub fn register_wallet_storage_list(&self,
+                                        type_: &str,
+                                        list_storages: WalletListStorages,
+                                        free_storage_list: WalletFreeStorageList) -> IndyResult<()> {
+        trace!("register_wallet_storage_list >>> type_: {:?}", type_);
+
+        let plugged_storage_types = self.plugged_storage_types.borrow();
+
+        let storage_type = plugged_storage_types
+            .get(type_)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Custom wallet storage isn't registered for type: {}", type_)))?;
+
+        storage_type.set_list_handlers(PluggedListHandlers {
+            list_storages_handler: list_storages,
+            free_storage_list_handler: free_storage_list,
+        });
+
+        trace!("register_wallet_storage_list <<<");
+        Ok(())
+    }
+
+    pub fn list_wallets(&self,
+                        storage_type: Option<&str>,
+                        storage_config: Option<&str>,
+                        storage_credentials: Option<&str>) -> IndyResult<Vec<String>> {
+        trace!("list_wallets >>> storage_type: {:?}, storage_config: {:?}", storage_type, storage_config);
+
+        let storage_types = self.storage_types.borrow();
+
+        let storage_type = storage_types
+            .get(storage_type.unwrap_or("default"))
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, "Unknown wallet storage type"))?;
+
+        let res = storage_type.list_storages(storage_config, storage_credentials)?;
+
+        trace!("list_wallets <<< res: {:?}", res);
+        Ok(res)
+    }
+
     pub fn create_wallet(&self,
                          config: &Config,
                          credentials: &Credentials,
@@ -137,7 +212,7 @@ impl WalletService {
         let (storage_type, storage_config, storage_credentials) = WalletService::_get_config_and_cred_for_storage(config, credentials, &storage_types)?;
 
         let keys = Keys::new();
-        let metadata = self._prepare_metadata(master_key, key_data, &keys)?;
+        let metadata = self._prepare_metadata(master_key, key_data, &keys, None)?;
 
         storage_type.create_storage(&config.id,
                                     storage_config
@@ -212,15 +287,30 @@ impl WalletService {
 
         let (master_key, rekey) = master_key;
         let keys = self._restore_keys(&metadata, &master_key)?;
+        let next_keys = self._restore_next_keys(&metadata, &master_key)?;
+
+        let mut wallet = Wallet::new(id.clone(), storage, Rc::new(keys));
+
+        // Resume re-encryption of records interrupted during rekey_wallet
+        if let Some(next_keys) = next_keys {
+            let next_keys = Rc::new(next_keys);
+            wallet.rekey(next_keys.clone(), &|_, _| {})?;
+
+            let mut metadata = metadata;
+            metadata.set_keys(next_keys.serialize_encrypted(master_key)?, None);
+
+            let metadata = serde_json::to_vec(&metadata)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet metadata")?;
+
+            wallet.set_storage_metadata(&metadata)?;
+        }
 
         // Rotate master key
         if let (Some(rekey), Some(rekey_data)) = (rekey, rekey_data) {
-            let metadata = self._prepare_metadata(rekey, &rekey_data, &keys)?;
-            storage.set_storage_metadata(&metadata)?;
+            let metadata = self._prepare_metadata(rekey, &rekey_data, wallet.get_keys(), None)?;
+            wallet.set_storage_metadata(&metadata)?;
         }
 
-        let wallet = Wallet::new(id.clone(), storage, Rc::new(keys));
-
         let mut wallets = self.wallets.borrow_mut();
         wallets.insert(wallet_handle, Box::new(wallet));
         let mut wallet_ids = self.wallet_ids.borrow_mut();
@@ -247,6 +337,8 @@ impl WalletService {
         match self.wallets.borrow_mut().remove(&handle) {
             Some(mut wallet) => {
                 self.wallet_ids.borrow_mut().remove(wallet.get_id());
+                self.subscriptions.borrow_mut().retain(|_, subscription| subscription.wallet_handle != handle);
+                self.transaction_events.borrow_mut().remove(&handle);
                 wallet.close()
             },
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -269,7 +361,10 @@ impl WalletService {
             Some(wallet) => wallet.add(type_, name, value, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Add, type_, name);
+        Ok(())
     }
 
     pub fn add_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str, value: &str, tags: &Tags)
@@ -292,20 +387,26 @@ impl WalletService {
                 wallet.update(type_, name, value)
                     .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Update, type_, name);
+        Ok(())
     }
 
     pub fn update_indy_object<T>(&self, wallet_handle: WalletHandle, name: &str, object: &T) -> IndyResult<String> where T: ::serde::Serialize + Sized {
-        let type_ = short_type_name::<T>();
-        match self.wallets.borrow().get(&wallet_handle) {
+        let type_ = self.add_prefix(short_type_name::<T>());
+        let object_json = match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => {
                 let object_json = serde_json::to_string(object)
-                    .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", type_))?;
-                wallet.update(&self.add_prefix(type_), name, &object_json)?;
+                    .to_indy(IndyErrorKind::InvalidState, format!("Cannot serialize {:?}", short_type_name::<T>()))?;
+                wallet.update(&type_, name, &object_json)?;
                 Ok(object_json)
             }
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Update, &type_, name);
+        Ok(object_json)
     }
 
     pub fn add_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
@@ -313,7 +414,10 @@ impl WalletService {
             Some(wallet) => wallet.add_tags(type_, name, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Update, type_, name);
+        Ok(())
     }
 
     pub fn update_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
@@ -321,7 +425,10 @@ impl WalletService {
             Some(wallet) => wallet.update_tags(type_, name, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Update, type_, name);
+        Ok(())
     }
 
     pub fn delete_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
@@ -329,7 +436,10 @@ impl WalletService {
             Some(wallet) => wallet.delete_tags(type_, name, tag_names)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Update, type_, name);
+        Ok(())
     }
 
     pub fn delete_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str) -> IndyResult<()> {
@@ -337,7 +447,10 @@ impl WalletService {
             Some(wallet) => wallet.delete(type_, name)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
-        }
+        }?;
+
+        self._notify(wallet_handle, WalletEventKind::Delete, type_, name);
+        Ok(())
     }
 
     pub fn delete_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str) -> IndyResult<()> where T: Sized {
@@ -429,6 +542,101 @@ impl WalletService {
         }
     }
 
+    pub fn begin_transaction(&self, wallet_handle: WalletHandle) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.begin_transaction(),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        self.transaction_events.borrow_mut().insert(wallet_handle, Vec::new());
+        Ok(())
+    }
+
+    pub fn commit_transaction(&self, wallet_handle: WalletHandle) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.commit_transaction(),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        let events = self.transaction_events.borrow_mut().remove(&wallet_handle).unwrap_or_default();
+
+        for event in events {
+            self._deliver(wallet_handle, &event);
+        }
+
+        Ok(())
+    }
+
+    pub fn rollback_transaction(&self, wallet_handle: WalletHandle) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.rollback_transaction(),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        self.transaction_events.borrow_mut().remove(&wallet_handle);
+        Ok(())
+    }
+
+    pub fn check_wallet(&self, wallet_handle: WalletHandle, options: &CheckOptions) -> IndyResult<WalletCheckReport> {
+        trace!("check_wallet >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
+
+        let quarantine_type = self.add_prefix(WalletService::QUARANTINE_TYPE);
+
+        let (total, broken) = match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.check(if options.quarantine { Some(&quarantine_type) } else { None }),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }?;
+
+        let res = WalletCheckReport { total, broken };
+
+        trace!("check_wallet <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    pub fn subscribe_events(&self, wallet_handle: WalletHandle, type_filter: Option<String>, listener: Box<dyn Fn(&WalletEvent)>) -> IndyResult<IndyHandle> {
+        trace!("subscribe_events >>> wallet_handle: {:?}, type_filter: {:?}", wallet_handle, type_filter);
+
+        if !self.wallets.borrow().contains_key(&wallet_handle) {
+            return Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"));
+        }
+
+        let subscription_handle = indy_utils::sequence::get_next_id();
+        self.subscriptions.borrow_mut().insert(subscription_handle, WalletSubscription { wallet_handle, type_filter, listener });
+
+        trace!("subscribe_events <<< subscription_handle: {:?}", subscription_handle);
+        Ok(subscription_handle)
+    }
+
+    pub fn unsubscribe_events(&self, subscription_handle: IndyHandle) -> IndyResult<()> {
+        trace!("unsubscribe_events >>> subscription_handle: {:?}", subscription_handle);
+
+        self.subscriptions.borrow_mut().remove(&subscription_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, format!("Unknown wallet events subscription handle: {:?}", subscription_handle)))?;
+
+        trace!("unsubscribe_events <<<");
+        Ok(())
+    }
+
+    fn _notify(&self, wallet_handle: WalletHandle, kind: WalletEventKind, type_: &str, name: &str) {
+        let event = WalletEvent { kind, type_: type_.to_string(), id: name.to_string() };
+
+        if let Some(events) = self.transaction_events.borrow_mut().get_mut(&wallet_handle) {
+            events.push(event);
+            return;
+        }
+
+        self._deliver(wallet_handle, &event);
+    }
+
+    fn _deliver(&self, wallet_handle: WalletHandle, event: &WalletEvent) {
+        for subscription in self.subscriptions.borrow().values() {
+            if subscription.wallet_handle == wallet_handle
+                && subscription.type_filter.as_ref().map(|type_| *type_ == event.type_).unwrap_or(true) {
+                (subscription.listener)(event);
+            }
+        }
+    }
+
     pub fn check(&self, handle: WalletHandle) -> IndyResult<()> {
         match self.wallets.borrow().get(&handle) {
             Some(_) => Ok(()),
@@ -436,13 +644,92 @@ impl WalletService {
         }
     }
 
-    pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+    pub fn rekey_wallet_prepare(&self, wallet_handle: WalletHandle, credentials: &Credentials) -> IndyResult<KeyDerivationData> {
+        trace!("rekey_wallet_prepare >>> wallet_handle: {:?}, credentials: {:?}", wallet_handle, secret!(credentials));
+
+        self.check(wallet_handle)?;
+
+        let res = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+
+        trace!("rekey_wallet_prepare <<<");
+        Ok(res)
+    }
+
+    pub fn rekey_wallet_continue(&self,
+                                 wallet_handle: WalletHandle,
+                                 key: (&KeyDerivationData, &MasterKey),
+                                 progress: &dyn Fn(usize, usize)) -> IndyResult<()> {
+        trace!("rekey_wallet_continue >>> wallet_handle: {:?}", wallet_handle);
+
+        let (key_data, master_key) = key;
+
+        let res = {
+            let mut wallets = self.wallets.borrow_mut();
+            let wallet = wallets
+                .get_mut(&wallet_handle)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+            let next_keys = Rc::new(Keys::new());
+
+            // From this point only the new credentials open the wallet. Both key sets are kept in metadata
+            // until all records are re-encrypted, so open_wallet is able to finish an interrupted re-key.
+            let metadata = self._prepare_metadata(master_key, key_data, wallet.get_keys(), Some(&*next_keys))?;
+            wallet.set_storage_metadata(&metadata)?;
+
+            wallet.rekey(next_keys.clone(), progress)
+                .and_then(|_| self._prepare_metadata(master_key, key_data, &next_keys, None))
+                .and_then(|metadata| wallet.set_storage_metadata(&metadata))
+        };
+
+        // Some records can be already re-encrypted, so keys of the opened wallet are stale now.
+        // The wallet is closed and has to be reopened with the new credentials that finishes re-key.
+        if let Err(err) = res {
+            let _ = self.close_wallet(wallet_handle);
+            return Err(err.extend("Wallet re-key is interrupted, the wallet is closed and has to be opened with the new credentials"));
+        }
+
+        trace!("rekey_wallet_continue <<<");
+        Ok(())
+    }
+
+    pub fn export_wallet_prepare(&self, export_config: &ExportConfig) -> IndyResult<KeyDerivationData> {
+        trace!("export_wallet_prepare >>> export_config: {:?}", secret!(export_config));
+
+        // Incremental backup shares chunks with its base, so it has to be encrypted with the same key
+        let key_data = match export_config.base_manifest {
+            Some(ref base_manifest) => {
+                let base_manifest =
+                    fs::OpenOptions::new()
+                        .read(true)
+                        .open(base_manifest)?;
+
+                let (_, key_data, _, _, _) = preparse_file_to_import(base_manifest, &export_config.key)?;
+                key_data
+            }
+            None => KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method)
+        };
+
+        trace!("export_wallet_prepare <<<");
+        Ok(key_data)
+    }
+
+    pub fn export_wallet(&self,
+                         wallet_handle: WalletHandle,
+                         export_config: &ExportConfig,
+                         version: u32,
+                         key: (&KeyDerivationData, &MasterKey),
+                         sign: ManifestSigner,
+                         verify: ManifestVerifier) -> IndyResult<()> {
         trace!("export_wallet >>> wallet_handle: {:?}, export_config: {:?}, version: {:?}", wallet_handle, secret!(export_config), version);
 
-        if version != 0 {
+        if version > 1 {
             return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
         }
 
+        if version == 0 && export_config.base_manifest.is_some() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Incremental export is supported only for version 1"));
+        }
+
         let (key_data, key) = key;
 
         let wallets = self.wallets.borrow();
@@ -458,13 +745,20 @@ impl WalletService {
                 .create(parent_path)?;
         }
 
-        let mut export_file =
-            fs::OpenOptions::new()
-                .write(true)
-                .create_new(true)
-                .open(export_config.path.clone())?;
+        let res = if version == 0 {
+            let mut export_file =
+                fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(export_config.path.clone())?;
 
-        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data);
+            export_continue(wallet, &mut export_file, version, key.clone(), key_data)
+        } else {
+            let verkey = export_config.verkey.as_ref()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Verkey to sign backup manifest is required for version 1"))?;
+
+            export_chunks_continue(wallet, &path, export_config.base_manifest.as_ref().map(Path::new), key.clone(), key_data, verkey, sign, verify)
+        };
 
         trace!("export_wallet <<<");
 
@@ -489,13 +783,13 @@ impl WalletService {
 
         let stashed_key_data = key_data.clone();
 
-        self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, stashed_key_data));
+        self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, stashed_key_data, PathBuf::from(&export_config.path), export_config.verkey.clone()));
 
         Ok((wallet_handle, key_data, import_key_derivation_data))
     }
 
-    pub fn import_wallet_continue(&self, wallet_handle: WalletHandle, config: &Config, credentials: &Credentials, key: (MasterKey, MasterKey)) -> IndyResult<()> {
-        let (reader, nonce, chunk_size, header_bytes, key_data) = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
+    pub fn import_wallet_continue(&self, wallet_handle: WalletHandle, config: &Config, credentials: &Credentials, key: (MasterKey, MasterKey), verify: ManifestVerifier) -> IndyResult<()> {
+        let (reader, nonce, chunk_size, header_bytes, key_data, import_path, verkey) = self.pending_for_import.borrow_mut().remove(&wallet_handle).unwrap();
 
         let (import_key, master_key) = key;
 
@@ -508,7 +802,7 @@ impl WalletService {
         let res = {
             let wallet = Wallet::new(WalletService::_get_wallet_id(&config), storage, Rc::new(keys));
 
-            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes)
+            finish_import(&wallet, reader, import_key, nonce, chunk_size, header_bytes, &import_path, verkey.as_ref().map(String::as_str), verify)
         };
 
         if res.is_err() {
@@ -583,12 +877,17 @@ impl WalletService {
         Ok(storage)
     }
 
-    fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys) -> IndyResult<Vec<u8>> {
+    fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys, next_keys: Option<&Keys>) -> IndyResult<Vec<u8>> {
         let encrypted_keys = keys.serialize_encrypted(master_key)?;
+        let encrypted_next_keys = match next_keys {
+            Some(next_keys) => Some(next_keys.serialize_encrypted(master_key)?),
+            None => None
+        };
+
         let metadata = match key_data {
             KeyDerivationData::Raw(_) => {
                 Metadata::MetadataRaw(
-                    MetadataRaw { keys: encrypted_keys }
+                    MetadataRaw { keys: encrypted_keys, next_keys: encrypted_next_keys }
                 )
             }
             KeyDerivationData::Argon2iInt(_, salt) | KeyDerivationData::Argon2iMod(_, salt) => {
@@ -596,6 +895,7 @@ impl WalletService {
                     MetadataArgon {
                         keys: encrypted_keys,
                         master_key_salt: salt[..].to_vec(),
+                        next_keys: encrypted_next_keys,
                     }
                 )
             }
@@ -616,8 +916,22 @@ impl WalletService {
         Ok(res)
     }
 
+    fn _restore_next_keys(&self, metadata: &Metadata, master_key: &MasterKey) -> IndyResult<Option<Keys>> {
+        match metadata.get_next_keys() {
+            Some(next_keys) => {
+                let res = Keys::deserialize_encrypted(next_keys, master_key)
+                    .map_err(|err| err.map(IndyErrorKind::WalletAccessFailed, "Invalid master key provided"))?;
+                Ok(Some(res))
+            }
+            None => Ok(None)
+        }
+    }
+
     pub const PREFIX: &'static str = "Indy";
 
+    // Type of records broken records are moved to by check_wallet
+    pub const QUARANTINE_TYPE: &'static str = "Quarantine";
+
     pub fn add_prefix(&self, type_: &str) -> String {
         format!("{}::{}", WalletService::PREFIX, type_)
     }
@@ -637,17 +951,84 @@ impl Metadata {
             Metadata::MetadataRaw(ref metadata) => &metadata.keys,
         }
     }
+
+    pub fn get_next_keys(&self) -> Option<&Vec<u8>> {
+        match *self {
+            Metadata::MetadataArgon(ref metadata) => metadata.next_keys.as_ref(),
+            Metadata::MetadataRaw(ref metadata) => metadata.next_keys.as_ref(),
+        }
+    }
+
+    fn set_keys(&mut self, keys: Vec<u8>, next_keys: Option<Vec<u8>>) {
+        match *self {
+            Metadata::MetadataArgon(ref mut metadata) => {
+                metadata.keys = keys;
+                metadata.next_keys = next_keys;
+            }
+            Metadata::MetadataRaw(ref mut metadata) => {
+                metadata.keys = keys;
+                metadata.next_keys = next_keys;
+            }
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetadataArgon {
     pub keys: Vec<u8>,
     pub master_key_salt: Vec<u8>,
+    // Keys the records are being re-encrypted to by an unfinished rekey_wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_keys: Option<Vec<u8>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetadataRaw {
-    pub keys: Vec<u8>
+    pub keys: Vec<u8>,
+    // Keys the records are being re-encrypted to by an unfinished rekey_wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_keys: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WalletCheckReport {
+    pub total: usize,
+    pub broken: Vec<BrokenRecord>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct BrokenRecord {
+    // Decrypted type and id of the record, absent if they are damaged
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub id: Option<String>,
+    // Base64 of encrypted record id as it is stored in the storage
+    pub raw_id: String,
+    pub reason: String,
+    pub quarantined: bool,
+}
+
+struct WalletSubscription {
+    wallet_handle: WalletHandle,
+    type_filter: Option<String>,
+    listener: Box<dyn Fn(&WalletEvent)>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WalletEventKind {
+    Add,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct WalletEvent {
+    #[serde(rename = "event")]
+    pub kind: WalletEventKind,
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -761,7 +1142,7 @@ impl WalletSearch {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchOptions {
     #[serde(default = "default_true")]
@@ -774,6 +1155,34 @@ pub struct SearchOptions {
     retrieve_value: bool,
     #[serde(default = "default_false")]
     retrieve_tags: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sort_by: Option<SortBy>,
+    #[serde(default)]
+    sort_order: SortOrder,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    offset: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy {
+    Id,
+    TagName(String),
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> SortOrder {
+        SortOrder::Asc
+    }
 }
 
 impl SearchOptions {
@@ -784,10 +1193,26 @@ impl SearchOptions {
             retrieve_type: true,
             retrieve_value: true,
             retrieve_tags: false,
+            ..SearchOptions::default()
         };
 
         serde_json::to_string(&options).unwrap()
     }
+
+    // Storages receive the sorting tag name already encrypted and base64 encoded
+    // in the same form as tag names of the query: "~<base64 of encrypted name>".
+    // Sorting by record id is resolved by the wallet as ids are stored encrypted.
+    fn sort_tag_name(&self) -> IndyResult<Option<Vec<u8>>> {
+        match self.sort_by {
+            Some(SortBy::TagName(ref name)) if name.starts_with('~') =>
+                base64::decode(&name[1..])
+                    .map(Some)
+                    .to_indy(IndyErrorKind::InvalidStructure, "Sorting tag name is invalid base64"),
+            Some(SortBy::TagName(_)) =>
+                Err(err_msg(IndyErrorKind::WalletQueryError, "Sorting is supported only by unencrypted tags")),
+            _ => Ok(None)
+        }
+    }
 }
 
 impl Default for SearchOptions {
@@ -798,6 +1223,10 @@ impl Default for SearchOptions {
             retrieve_type: false,
             retrieve_value: true,
             retrieve_tags: false,
+            sort_by: None,
+            sort_order: SortOrder::Asc,
+            offset: None,
+            limit: None,
         }
     }
 }
@@ -824,6 +1253,7 @@ mod tests {
     use indy_api_types::INVALID_WALLET_HANDLE;
 
     use indy_api_types::domain::wallet::KeyDerivationMethod;
+    use indy_utils::crypto::ed25519_sign;
     use indy_utils::environment;
     use indy_utils::inmem_wallet::InmemWallet;
     use indy_utils::test;
@@ -857,6 +1287,12 @@ mod tests {
             self.open_wallet_continue(wallet_handle, (&key, rekey.as_ref()))
         }
 
+        fn rekey_wallet(&self, wallet_handle: WalletHandle, credentials: &Credentials) -> IndyResult<()> {
+            let key_data = self.rekey_wallet_prepare(wallet_handle, credentials)?;
+            let key = key_data.calc_master_key()?;
+            self.rekey_wallet_continue(wallet_handle, (&key_data, &key), &|_, _| {})
+        }
+
         pub fn import_wallet(&self,
                              config: &Config,
                              credentials: &Credentials,
@@ -876,9 +1312,9 @@ mod tests {
             let import_key = import_key_derivation_data.calc_master_key()?;
             let master_key = key_data.calc_master_key()?;
 
-            self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, key_data));
+            self.pending_for_import.borrow_mut().insert(wallet_handle, (reader, nonce, chunk_size, header_bytes, key_data, PathBuf::from(&export_config.path), export_config.verkey.clone()));
 
-            self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key))
+            self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key), &_verify_manifest)
         }
 
         pub fn delete_wallet(&self, config: &Config, credentials: &Credentials) -> IndyResult<()> {
@@ -1297,6 +1733,256 @@ mod tests {
         test::cleanup_wallet("wallet_service_add_record_works");
     }
 
+    #[test]
+    fn wallet_service_transaction_commit_works() {
+        test::cleanup_wallet("wallet_service_transaction_commit_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_transaction_commit_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_transaction_commit_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.begin_transaction(wallet_handle).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+            wallet_service.commit_transaction(wallet_handle).unwrap();
+
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key2", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_transaction_commit_works");
+    }
+
+    #[test]
+    fn wallet_service_transaction_rollback_works() {
+        test::cleanup_wallet("wallet_service_transaction_rollback_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_transaction_rollback_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_transaction_rollback_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.begin_transaction(wallet_handle).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.rollback_transaction(wallet_handle).unwrap();
+
+            let res = wallet_service.get_record(wallet_handle, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        test::cleanup_wallet("wallet_service_transaction_rollback_works");
+    }
+
+    #[test]
+    fn wallet_service_subscribe_events_works() {
+        test::cleanup_wallet("wallet_service_subscribe_events_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_subscribe_events_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_subscribe_events_works"), &RAW_CREDENTIAL).unwrap();
+
+            let events = Rc::new(RefCell::new(Vec::new()));
+            let listener_events = events.clone();
+            wallet_service.subscribe_events(wallet_handle, Some("type".to_string()), Box::new(move |event| listener_events.borrow_mut().push(event.clone()))).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "other_type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.update_record_value(wallet_handle, "type", "key1", "value2").unwrap();
+            wallet_service.add_record_tags(wallet_handle, "type", "key1", &serde_json::from_str(r#"{"tag_name": "tag_value"}"#).unwrap()).unwrap();
+            wallet_service.delete_record(wallet_handle, "type", "key1").unwrap();
+            let _ = wallet_service.delete_record(wallet_handle, "type", "key1");
+
+            let kinds: Vec<WalletEventKind> = events.borrow().iter().map(|event| event.kind).collect();
+            assert_eq!(vec![WalletEventKind::Add, WalletEventKind::Update, WalletEventKind::Update, WalletEventKind::Delete], kinds);
+            assert!(events.borrow().iter().all(|event| event.type_ == "type" && event.id == "key1"));
+        }
+        test::cleanup_wallet("wallet_service_subscribe_events_works");
+    }
+
+    #[test]
+    fn wallet_service_subscribe_events_works_for_transaction() {
+        test::cleanup_wallet("wallet_service_subscribe_events_works_for_transaction");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_subscribe_events_works_for_transaction"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_subscribe_events_works_for_transaction"), &RAW_CREDENTIAL).unwrap();
+
+            let events = Rc::new(RefCell::new(Vec::new()));
+            let listener_events = events.clone();
+            wallet_service.subscribe_events(wallet_handle, None, Box::new(move |event| listener_events.borrow_mut().push(event.clone()))).unwrap();
+
+            wallet_service.begin_transaction(wallet_handle).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.rollback_transaction(wallet_handle).unwrap();
+            assert!(events.borrow().is_empty());
+
+            wallet_service.begin_transaction(wallet_handle).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+            assert!(events.borrow().is_empty());
+
+            wallet_service.commit_transaction(wallet_handle).unwrap();
+            assert_eq!(vec![WalletEvent { kind: WalletEventKind::Add, type_: "type".to_string(), id: "key2".to_string() }], *events.borrow());
+        }
+        test::cleanup_wallet("wallet_service_subscribe_events_works_for_transaction");
+    }
+
+    #[test]
+    fn wallet_service_unsubscribe_events_works() {
+        test::cleanup_wallet("wallet_service_unsubscribe_events_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_unsubscribe_events_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_unsubscribe_events_works"), &RAW_CREDENTIAL).unwrap();
+
+            let events = Rc::new(RefCell::new(Vec::new()));
+            let listener_events = events.clone();
+            let subscription_handle = wallet_service.subscribe_events(wallet_handle, None, Box::new(move |event| listener_events.borrow_mut().push(event.clone()))).unwrap();
+
+            wallet_service.unsubscribe_events(subscription_handle).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            assert!(events.borrow().is_empty());
+
+            let res = wallet_service.unsubscribe_events(subscription_handle);
+            assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+        }
+        test::cleanup_wallet("wallet_service_unsubscribe_events_works");
+    }
+
+    #[test]
+    fn wallet_service_subscribe_events_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.subscribe_events(INVALID_WALLET_HANDLE, None, Box::new(|_| {}));
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    #[test]
+    fn wallet_service_check_wallet_works() {
+        test::cleanup_wallet("wallet_service_check_wallet_works");
+        {
+            let config: &Config = &_config("wallet_service_check_wallet_works");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let report = wallet_service.check_wallet(wallet_handle, &CheckOptions { quarantine: true }).unwrap();
+            assert_eq!(WalletCheckReport { total: 1, broken: vec![] }, report);
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_check_wallet_works");
+    }
+
+    #[test]
+    fn wallet_service_check_wallet_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.check_wallet(INVALID_WALLET_HANDLE, &CheckOptions::default());
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    #[test]
+    fn wallet_service_transaction_works_for_plugged_without_transaction_handlers() {
+        _cleanup("wallet_service_transaction_works_for_plugged_without_transaction_handlers");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        let res = wallet_service.begin_transaction(wallet_handle);
+        assert_kind!(IndyErrorKind::WalletStorageError, res);
+    }
+
+    #[test]
+    fn wallet_service_register_storage_transactions_works_for_unknown_type() {
+        let wallet_service = WalletService::new();
+
+        let res = wallet_service.register_wallet_storage_transactions("unknown", _noop_transaction_handler, _noop_transaction_handler, _noop_transaction_handler);
+        assert_kind!(IndyErrorKind::UnknownWalletStorageType, res);
+    }
+
+    #[test]
+    fn wallet_service_list_wallets_works() {
+        let path = _custom_path("wallet_service_list_wallets_works");
+        let _ = fs::remove_dir_all(&path);
+
+        let wallet_service = WalletService::new();
+
+        let config = Config {
+            id: String::from("wallet1"),
+            storage_type: None,
+            storage_config: Some(json!({"path": path})),
+        };
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+        let storage_config = json!({"path": path}).to_string();
+        let ids = wallet_service.list_wallets(None, Some(&storage_config), None).unwrap();
+        assert_eq!(vec!["wallet1".to_string()], ids);
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    #[test]
+    fn wallet_service_list_wallets_works_for_plugged() {
+        _cleanup("wallet_service_list_wallets_works_for_plugged");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+        wallet_service.register_wallet_storage_list("inmem", InmemWallet::list_storages, InmemWallet::free_storage_list).unwrap();
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+
+        let ids = wallet_service.list_wallets(Some("inmem"), None, None).unwrap();
+        assert!(ids.contains(&"w1".to_string()));
+
+        _cleanup("wallet_service_list_wallets_works_for_plugged");
+    }
+
+    #[test]
+    fn wallet_service_list_wallets_works_for_plugged_without_list_handlers() {
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        let res = wallet_service.list_wallets(Some("inmem"), None, None);
+        assert_kind!(IndyErrorKind::WalletStorageError, res);
+    }
+
+    #[test]
+    fn wallet_service_list_wallets_works_for_unknown_type() {
+        let wallet_service = WalletService::new();
+
+        let res = wallet_service.list_wallets(Some("unknown"), None, None);
+        assert_kind!(IndyErrorKind::UnknownWalletStorageType, res);
+    }
+
+    extern fn _noop_transaction_handler(_storage_handle: indy_api_types::StorageHandle) -> indy_api_types::ErrorCode {
+        indy_api_types::ErrorCode::Success
+    }
+
+    #[test]
+    fn wallet_service_search_records_works_for_sled() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_sled");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config_sled("wallet_service_search_records_works_for_sled"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config_sled("wallet_service_search_records_works_for_sled"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &serde_json::from_str(r#"{"tag1":"a", "~tag2":"1"}"#).unwrap()).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &serde_json::from_str(r#"{"tag1":"b", "~tag2":"2"}"#).unwrap()).unwrap();
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", r#"{"tag1":"b"}"#, "{}").unwrap();
+            let record = search.fetch_next_record().unwrap().unwrap();
+            assert_eq!("key2", record.get_id());
+            assert!(search.fetch_next_record().unwrap().is_none());
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", r#"{"~tag2":{"$lt":"2"}}"#, "{}").unwrap();
+            let record = search.fetch_next_record().unwrap().unwrap();
+            assert_eq!("key1", record.get_id());
+            assert!(search.fetch_next_record().unwrap().is_none());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_sled");
+    }
+
     #[test]
     fn wallet_service_add_record_works_for_plugged() {
         _cleanup("wallet_service_add_record_works_for_plugged");
@@ -1896,6 +2582,78 @@ mod tests {
         test::cleanup_wallet("wallet_service_key_rotation_for_rekey_raw_method");
     }
 
+    #[test]
+    fn wallet_service_rekey_wallet_works() {
+        test::cleanup_wallet("wallet_service_rekey_wallet_works");
+        {
+            let config: &Config = &_config("wallet_service_rekey_wallet_works");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            wallet_service.rekey_wallet(wallet_handle, &_credentials_for_new_key_moderate()).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            // Access failed for old key
+            let res = wallet_service.open_wallet(config, &RAW_CREDENTIAL);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            // Works ok with new key when reopening
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_moderate()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_rekey_wallet_works");
+    }
+
+    #[test]
+    fn wallet_service_rekey_wallet_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.rekey_wallet(INVALID_WALLET_HANDLE, &_credentials_for_new_key_raw());
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    #[test]
+    fn wallet_service_open_wallet_works_for_interrupted_rekey() {
+        test::cleanup_wallet("wallet_service_open_wallet_works_for_interrupted_rekey");
+        {
+            let config: &Config = &_config("wallet_service_open_wallet_works_for_interrupted_rekey");
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            // simulate a crash right after new keys were stored in metadata
+            let key_data = wallet_service.rekey_wallet_prepare(wallet_handle, &_credentials_for_new_key_raw()).unwrap();
+            let key = key_data.calc_master_key().unwrap();
+            {
+                let wallets = wallet_service.wallets.borrow();
+                let wallet = wallets.get(&wallet_handle).unwrap();
+                let metadata = wallet_service._prepare_metadata(&key, &key_data, wallet.get_keys(), Some(&Keys::new())).unwrap();
+                wallet.set_storage_metadata(&metadata).unwrap();
+            }
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_raw()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(config, &_credentials_for_new_key_raw()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(true, true, true)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_open_wallet_works_for_interrupted_rekey");
+    }
+
     fn remove_exported_wallet(export_config: &ExportConfig) -> &Path {
         let export_path = Path::new(&export_config.path);
         if export_path.exists() {
@@ -1916,7 +2674,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_when_empty");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
 
             assert!(export_path.exists());
         }
@@ -1938,7 +2696,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_1_item");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1959,7 +2717,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_wallet_1_item_interactive_method");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -1980,7 +2738,7 @@ mod tests {
 
             let export_path = remove_exported_wallet(&export_config);
             let (kdd, master_key) = _export_key("wallet_service_export_wallet_1_item_raw_method");
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(&export_path.exists());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -2005,7 +2763,7 @@ mod tests {
             let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_returns_error_if_file_exists"), &RAW_CREDENTIAL).unwrap();
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_file_exists");
-            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key));
+            let res = wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest);
             assert_eq!(IndyErrorKind::IOError, res.unwrap_err().kind());
         }
         let _export_path = remove_exported_wallet(&export_config);
@@ -2023,7 +2781,7 @@ mod tests {
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_returns_error_if_wrong_handle");
             let export_config = _export_config_raw("wallet_service_export_wallet_returns_error_if_wrong_handle");
             let export_path = remove_exported_wallet(&export_config);
-            let res = wallet_service.export_wallet(INVALID_WALLET_HANDLE, &export_config, 0, (&kdd, &master_key));
+            let res = wallet_service.export_wallet(INVALID_WALLET_HANDLE, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest);
             assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
             assert!(!export_path.exists());
         }
@@ -2044,7 +2802,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_1_item");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2059,6 +2817,43 @@ mod tests {
         test::cleanup_wallet("wallet_service_export_import_wallet_1_item");
     }
 
+    #[test]
+    fn wallet_service_export_import_wallet_works_for_incremental_chunks() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_incremental_chunks");
+        let backup_dir = _export_file_path("wallet_service_export_import_wallet_works_for_incremental_chunks");
+        let _ = fs::remove_dir_all(&backup_dir);
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_import_wallet_works_for_incremental_chunks"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_works_for_incremental_chunks"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let base_export_config = _export_config_chunks(&backup_dir.join("backup1"), None);
+            let kdd = wallet_service.export_wallet_prepare(&base_export_config).unwrap();
+            let master_key = kdd.calc_master_key().unwrap();
+            wallet_service.export_wallet(wallet_handle, &base_export_config, 1, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let export_config = _export_config_chunks(&backup_dir.join("backup2"), Some(&base_export_config.path));
+            let kdd = wallet_service.export_wallet_prepare(&export_config).unwrap();
+            let master_key = kdd.calc_master_key().unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 1, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
+            assert_eq!(2, fs::read_dir(backup_dir.join("chunks")).unwrap().count());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            wallet_service.delete_wallet(&_config("wallet_service_export_import_wallet_works_for_incremental_chunks"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.import_wallet(&_config("wallet_service_export_import_wallet_works_for_incremental_chunks"), &RAW_CREDENTIAL, &export_config).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_works_for_incremental_chunks"), &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key2", "{}").unwrap();
+        }
+        fs::remove_dir_all(&backup_dir).unwrap();
+        test::cleanup_wallet("wallet_service_export_import_wallet_works_for_incremental_chunks");
+    }
+
     #[test]
     fn wallet_service_export_import_wallet_1_item_for_interactive_method() {
         test::cleanup_wallet("wallet_service_export_import_wallet_1_item_for_interactive_method");
@@ -2073,7 +2868,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_interactive_method");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2101,7 +2896,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_1_item_for_moderate_method");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2130,7 +2925,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_export_interactive_import_as_raw");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2159,7 +2954,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key_interactive("wallet_service_export_import_wallet_1_item_for_export_raw_import_as_interactive");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2185,7 +2980,7 @@ mod tests {
 
             let (kdd, master_key) = _export_key("wallet_service_export_import_wallet_if_empty");
             let export_path = remove_exported_wallet(&export_config);
-            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key)).unwrap();
+            wallet_service.export_wallet(wallet_handle, &export_config, 0, (&kdd, &master_key), &_sign_manifest, &_verify_manifest).unwrap();
             assert!(export_path.exists());
 
             wallet_service.close_wallet(wallet_handle).unwrap();
@@ -2238,6 +3033,14 @@ mod tests {
         }
     }
 
+    fn _config_sled(name: &str) -> Config {
+        Config {
+            id: name.to_string(),
+            storage_type: Some("sled".to_string()),
+            storage_config: None,
+        }
+    }
+
     fn _config_inmem() -> Config {
         Config {
             id: "w1".to_string(),
@@ -2399,6 +3202,9 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            version: 0,
+            base_manifest: None,
+            verkey: None,
         }
     }
 
@@ -2417,6 +3223,9 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            version: 0,
+            base_manifest: None,
+            verkey: None,
         }
     }
 
@@ -2429,9 +3238,42 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            version: 0,
+            base_manifest: None,
+            verkey: None,
+        }
+    }
+
+    fn _export_config_chunks(path: &Path, base_manifest: Option<&str>) -> ExportConfig {
+        ExportConfig {
+            key: "export_key".to_string(),
+            path: path.to_str().unwrap().to_string(),
+            key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            version: 1,
+            base_manifest: base_manifest.map(String::from),
+            verkey: Some(_manifest_verkey()),
         }
     }
 
+    fn _manifest_sign_key() -> (ed25519_sign::PublicKey, ed25519_sign::SecretKey) {
+        let seed = ed25519_sign::Seed::from_slice(&[1u8; ed25519_sign::SEEDBYTES]).unwrap();
+        ed25519_sign::create_key_pair_for_signature(Some(&seed)).unwrap()
+    }
+
+    fn _manifest_verkey() -> String {
+        base64::encode(&_manifest_sign_key().0[..])
+    }
+
+    fn _sign_manifest(doc: &[u8]) -> IndyResult<Vec<u8>> {
+        Ok(ed25519_sign::sign(&_manifest_sign_key().1, doc)?[..].to_vec())
+    }
+
+    fn _verify_manifest(verkey: &str, doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+        let verkey = ed25519_sign::PublicKey::from_slice(&base64::decode(verkey)?)?;
+        let signature = ed25519_sign::Signature::from_slice(signature)?;
+        ed25519_sign::verify(&verkey, doc, &signature)
+    }
+
     fn _export_key_raw(name: &str) -> (KeyDerivationData, MasterKey) {
         _calc_key(&_export_config_raw(name))
     }
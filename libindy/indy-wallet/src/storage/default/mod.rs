@@ -11,7 +11,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::environment;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{list_storage_dirs, EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 use self::owning_ref::OwningHandle;
@@ -233,11 +233,15 @@ impl SQLiteStorageType {
         SQLiteStorageType {}
     }
 
-    fn _db_path(id: &str, config: Option<&Config>) -> std::path::PathBuf {
-        let mut path = match config {
+    fn _base_path(config: Option<&Config>) -> std::path::PathBuf {
+        match config {
             Some(Config { path: Some(ref path) }) => std::path::PathBuf::from(path),
             _ => environment::wallet_home_path()
-        };
+        }
+    }
+
+    fn _db_path(id: &str, config: Option<&Config>) -> std::path::PathBuf {
+        let mut path = SQLiteStorageType::_base_path(config);
 
         path.push(id);
         path.push(_SQLITE_DB);
@@ -551,7 +555,7 @@ impl WalletStorage for SQLiteStorage {
                 retrieve_type: search_options.retrieve_type,
             };
 
-            let (query_string, query_arguments) = query::wql_to_sql(&type_, query, options)?;
+            let (query_string, query_arguments) = query::wql_to_sql(&type_, query, &search_options)?;
 
             let statement = self._prepare_statement(&query_string)?;
             let tag_retriever = if fetch_options.retrieve_tags {
@@ -567,7 +571,37 @@ impl WalletStorage for SQLiteStorage {
         }
     }
 
+    fn begin_transaction(&self) -> IndyResult<()> {
+        if !self.conn.is_autocommit() {
+            return Err(err_msg(IndyErrorKind::WalletStorageError, "Wallet transaction is already started"));
+        }
+
+        self.conn.execute_batch("BEGIN IMMEDIATE")?;
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> IndyResult<()> {
+        if self.conn.is_autocommit() {
+            return Err(err_msg(IndyErrorKind::WalletStorageError, "No wallet transaction is started"));
+        }
+
+        self.conn.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> IndyResult<()> {
+        if self.conn.is_autocommit() {
+            return Err(err_msg(IndyErrorKind::WalletStorageError, "No wallet transaction is started"));
+        }
+
+        self.conn.execute_batch("ROLLBACK")?;
+        Ok(())
+    }
+
     fn close(&mut self) -> IndyResult<()> {
+        if !self.conn.is_autocommit() {
+            self.conn.execute_batch("ROLLBACK")?;
+        }
         Ok(())
     }
 }
@@ -739,10 +773,35 @@ impl WalletStorageType for SQLiteStorageType {
 
         Ok(Box::new(SQLiteStorage { conn: Rc::new(conn) }))
     }
+
+    ///
+    /// Lists ids of SQLite DB files located in the path specified in the config.
+    ///
+    /// # Arguments
+    ///
+    ///  * `config` - config containing the location of SQLite DB files
+    ///  * `credentials` - DB credentials
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `Vec<String>` - sorted ids of wallets, empty if the path doesn't exist
+    ///  * `IndyError`
+    ///
+    fn list_storages(&self, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Vec<String>> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        list_storage_dirs(&SQLiteStorageType::_base_path(config.as_ref()), _SQLITE_DB)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use indy_utils::crypto::base64;
     use indy_utils::test;
 
     use super::*;
@@ -777,6 +836,40 @@ mod tests {
         _cleanup("sqlite_storage_type_create_works_for_custom_path");
     }
 
+    #[test]
+    fn sqlite_storage_type_list_works() {
+        _cleanup_custom_path("sqlite_storage_type_list_works");
+
+        let config = json!({
+            "path": _custom_path("sqlite_storage_type_list_works")
+        }).to_string();
+
+        let storage_type = SQLiteStorageType::new();
+        storage_type.create_storage("wallet2", Some(&config), None, &_metadata()).unwrap();
+        storage_type.create_storage("wallet1", Some(&config), None, &_metadata()).unwrap();
+
+        // directories without database file aren't reported
+        fs::create_dir_all(Path::new(&_custom_path("sqlite_storage_type_list_works")).join("not_wallet")).unwrap();
+
+        let ids = storage_type.list_storages(Some(&config), None).unwrap();
+        assert_eq!(vec!["wallet1".to_string(), "wallet2".to_string()], ids);
+
+        _cleanup_custom_path("sqlite_storage_type_list_works");
+    }
+
+    #[test]
+    fn sqlite_storage_type_list_works_for_not_existing_path() {
+        _cleanup_custom_path("sqlite_storage_type_list_works_for_not_existing_path");
+
+        let config = json!({
+            "path": _custom_path("sqlite_storage_type_list_works_for_not_existing_path")
+        }).to_string();
+
+        let storage_type = SQLiteStorageType::new();
+        let ids = storage_type.list_storages(Some(&config), None).unwrap();
+        assert!(ids.is_empty());
+    }
+
     fn _cleanup_custom_path(custom_path: &str) {
         let my_path = _custom_path(custom_path);
         let path = Path::new(&my_path);
@@ -1044,6 +1137,33 @@ mod tests {
         _cleanup("sqlite_storage_get_all_works_for_empty");
     }
 
+    #[test]
+    fn sqlite_storage_search_works_for_sort_by_tag_with_pagination() {
+        _cleanup("sqlite_storage_search_works_for_sort_by_tag_with_pagination");
+        {
+            let storage = _storage("sqlite_storage_search_works_for_sort_by_tag_with_pagination");
+            storage.add(&_type1(), &_id(1), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "b".to_string())]).unwrap();
+            storage.add(&_type1(), &_id(2), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "c".to_string())]).unwrap();
+            storage.add(&_type1(), &_id(3), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "a".to_string())]).unwrap();
+
+            let options = json!({
+                "sortBy": {"tagName": format!("~{}", base64::encode(&[1, 2, 3]))},
+                "sortOrder": "desc",
+                "offset": 1,
+                "limit": 1
+            }).to_string();
+
+            let mut storage_iterator = storage.search(&_type1(), &language::Operator::And(vec![]), Some(&options)).unwrap();
+
+            let record = storage_iterator.next().unwrap().unwrap();
+            assert_eq!(record.id, _id(1));
+
+            let record = storage_iterator.next().unwrap();
+            assert!(record.is_none());
+        }
+        _cleanup("sqlite_storage_search_works_for_sort_by_tag_with_pagination");
+    }
+
     #[test]
     fn sqlite_storage_update_works() {
         _cleanup("sqlite_storage_update_works");
@@ -1313,6 +1433,100 @@ mod tests {
         _cleanup("sqlite_storage_delete_tags_works_for_non_existing_id");
     }
 
+    #[test]
+    fn sqlite_storage_transaction_commit_works() {
+        _cleanup("sqlite_storage_transaction_commit_works");
+        {
+            let storage = _storage("sqlite_storage_transaction_commit_works");
+
+            storage.begin_transaction().unwrap();
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.add(&_type2(), &_id2(), &_value2(), &_tags()).unwrap();
+            storage.update_tags(&_type1(), &_id1(), &_new_tags()).unwrap();
+            storage.commit_transaction().unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+            let record = storage.get(&_type2(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+        }
+        _cleanup("sqlite_storage_transaction_commit_works");
+    }
+
+    #[test]
+    fn sqlite_storage_transaction_rollback_works() {
+        _cleanup("sqlite_storage_transaction_rollback_works");
+        {
+            let storage = _storage("sqlite_storage_transaction_rollback_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            storage.begin_transaction().unwrap();
+            storage.update(&_type1(), &_id1(), &_value2()).unwrap();
+            storage.add(&_type2(), &_id2(), &_value2(), &_tags()).unwrap();
+            storage.rollback_transaction().unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+
+            let res = storage.get(&_type2(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sqlite_storage_transaction_rollback_works");
+    }
+
+    #[test]
+    fn sqlite_storage_transaction_keeps_started_on_failed_operation() {
+        _cleanup("sqlite_storage_transaction_keeps_started_on_failed_operation");
+        {
+            let storage = _storage("sqlite_storage_transaction_keeps_started_on_failed_operation");
+
+            storage.begin_transaction().unwrap();
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            let res = storage.add(&_type1(), &_id1(), &_value1(), &_tags());
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+            storage.commit_transaction().unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+        }
+        _cleanup("sqlite_storage_transaction_keeps_started_on_failed_operation");
+    }
+
+    #[test]
+    fn sqlite_storage_transaction_begin_works_for_twice() {
+        _cleanup("sqlite_storage_transaction_begin_works_for_twice");
+        {
+            let storage = _storage("sqlite_storage_transaction_begin_works_for_twice");
+
+            storage.begin_transaction().unwrap();
+
+            let res = storage.begin_transaction();
+            assert_kind!(IndyErrorKind::WalletStorageError, res);
+
+            storage.rollback_transaction().unwrap();
+        }
+        _cleanup("sqlite_storage_transaction_begin_works_for_twice");
+    }
+
+    #[test]
+    fn sqlite_storage_transaction_commit_works_for_not_started() {
+        _cleanup("sqlite_storage_transaction_commit_works_for_not_started");
+        {
+            let storage = _storage("sqlite_storage_transaction_commit_works_for_not_started");
+
+            let res = storage.commit_transaction();
+            assert_kind!(IndyErrorKind::WalletStorageError, res);
+
+            let res = storage.rollback_transaction();
+            assert_kind!(IndyErrorKind::WalletStorageError, res);
+        }
+        _cleanup("sqlite_storage_transaction_commit_works_for_not_started");
+    }
+
     fn _cleanup(name: &str) {
         test::cleanup_storage(name)
     }
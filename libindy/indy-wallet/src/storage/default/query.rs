@@ -1,26 +1,51 @@
 use indy_api_types::errors::prelude::*;
 use rusqlite::types::ToSql;
 use crate::language::{Operator, TagName, TargetValue};
+use crate::{SearchOptions, SortOrder};
 
 
 // Translates Wallet Query Language to SQL
 // WQL input is provided as a reference to a top level Operator
 // Result is a tuple of query string and query arguments
-pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, _options: Option<&str>) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
+pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, options: &SearchOptions) -> Result<(String, Vec<&'a dyn ToSql>), IndyError> {
     let mut arguments: Vec<&dyn ToSql> = Vec::new();
     arguments.push(class);
     let clause_string = operator_to_sql(op, &mut arguments)?;
-    const BASE: &str = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?";
+    let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = ?".to_string();
     if !clause_string.is_empty() {
-        let mut query_string = String::with_capacity(BASE.len() + 5 + clause_string.len());
-        query_string.push_str(BASE);
         query_string.push_str(" AND ");
         query_string.push_str(&clause_string);
-        Ok((query_string, arguments))
-    } else {
-        Ok((BASE.to_string(), arguments))
+    }
+    query_string.push_str(&search_options_to_sql(options)?);
+    Ok((query_string, arguments))
+}
+
+
+// Translates sorting and pagination of search options to SQL
+// Sorting tag name is a blob literal as arguments can't outlive options parsing
+fn search_options_to_sql(options: &SearchOptions) -> IndyResult<String> {
+    let mut s = String::new();
+
+    let order = match options.sort_order {
+        SortOrder::Asc => "ASC",
+        SortOrder::Desc => "DESC",
+    };
+
+    if let Some(tag_name) = options.sort_tag_name()? {
+        let tag_name: String = tag_name.iter().map(|b| format!("{:02x}", b)).collect();
+        s.push_str(&format!(" ORDER BY (SELECT value FROM tags_plaintext WHERE item_id = i.id AND name = X'{}') {}, i.id {}", tag_name, order, order));
+    } else if options.offset.is_some() || options.limit.is_some() {
+        s.push_str(" ORDER BY i.id");
+    }
+
+    match (options.limit, options.offset) {
+        (Some(limit), Some(offset)) => s.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset)),
+        (Some(limit), None) => s.push_str(&format!(" LIMIT {}", limit)),
+        (None, Some(offset)) => s.push_str(&format!(" LIMIT -1 OFFSET {}", offset)),
+        (None, None) => {}
     }
 
+    Ok(s)
 }
 
 
@@ -241,6 +266,35 @@ mod tests {
         ]);
         let query = Operator::Or(vec![condition_1, condition_2]);
         let class = vec![100,100,100];
-        let (_query, _arguments) = wql_to_sql(&class, &query, None).unwrap();
+        let (_query, _arguments) = wql_to_sql(&class, &query, &SearchOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn sort_by_tag_name_with_pagination() {
+        let options: SearchOptions = serde_json::from_value(json!({
+            "sortBy": {"tagName": "~AQID"},
+            "sortOrder": "desc",
+            "offset": 10,
+            "limit": 5
+        })).unwrap();
+
+        let query = Operator::And(vec![]);
+        let class = vec![100, 100, 100];
+        let (query, _arguments) = wql_to_sql(&class, &query, &options).unwrap();
+
+        assert!(query.ends_with(" ORDER BY (SELECT value FROM tags_plaintext WHERE item_id = i.id AND name = X'010203') DESC, i.id DESC LIMIT 5 OFFSET 10"));
+    }
+
+    #[test]
+    fn sort_by_encrypted_tag_name_fails() {
+        let options: SearchOptions = serde_json::from_value(json!({
+            "sortBy": {"tagName": "AQID"}
+        })).unwrap();
+
+        let query = Operator::And(vec![]);
+        let class = vec![100, 100, 100];
+        let res = wql_to_sql(&class, &query, &options);
+
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
     }
 }
\ No newline at end of file
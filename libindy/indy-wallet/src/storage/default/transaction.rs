@@ -1,15 +1,24 @@
 use rusqlite::{TransactionBehavior, Connection, DropBehavior, Result};
 use std::ops::Deref;
 
+const SAVEPOINT_NAME: &str = "indy_wallet_savepoint";
+
 pub struct Transaction<'conn> {
     conn: &'conn Connection,
     drop_behavior: DropBehavior,
     committed: bool,
+    savepoint: bool,
 }
 
 impl<'conn> Transaction<'conn> {
-    /// Begin a new transaction. Cannot be nested; see `savepoint` for nested transactions.
+    /// Begin a new transaction.
+    /// If the connection is already inside a transaction (started by `begin_transaction` of the storage)
+    /// a savepoint is used instead, so the changes become durable only with the outer transaction.
     pub fn new(conn: &Connection, behavior: TransactionBehavior) -> Result<Transaction> {
+        if !conn.is_autocommit() {
+            return Transaction::savepoint(conn);
+        }
+
         let query = match behavior {
             TransactionBehavior::Deferred => "BEGIN DEFERRED",
             TransactionBehavior::Immediate => "BEGIN IMMEDIATE",
@@ -22,6 +31,19 @@ impl<'conn> Transaction<'conn> {
                     conn,
                     drop_behavior: DropBehavior::Rollback,
                     committed: false,
+                    savepoint: false,
+                }
+            })
+    }
+
+    fn savepoint(conn: &Connection) -> Result<Transaction> {
+        conn.execute_batch(&format!("SAVEPOINT {}", SAVEPOINT_NAME))
+            .map(move |_| {
+                Transaction {
+                    conn,
+                    drop_behavior: DropBehavior::Rollback,
+                    committed: false,
+                    savepoint: true,
                 }
             })
     }
@@ -44,7 +66,11 @@ impl<'conn> Transaction<'conn> {
 
     fn commit_(&mut self) -> Result<()> {
         self.committed = true;
-        self.conn.execute_batch("COMMIT")
+        if self.savepoint {
+            self.conn.execute_batch(&format!("RELEASE {}", SAVEPOINT_NAME))
+        } else {
+            self.conn.execute_batch("COMMIT")
+        }
     }
 
     /// A convenience method which consumes and rolls back a transaction.
@@ -55,7 +81,11 @@ impl<'conn> Transaction<'conn> {
 
     fn rollback_(&mut self) -> Result<()> {
         self.committed = true;
-        self.conn.execute_batch("ROLLBACK")
+        if self.savepoint {
+            self.conn.execute_batch(&format!("ROLLBACK TO {0}; RELEASE {0}", SAVEPOINT_NAME))
+        } else {
+            self.conn.execute_batch("ROLLBACK")
+        }
     }
 
     /// Consumes the transaction, committing or rolling back according to the current setting
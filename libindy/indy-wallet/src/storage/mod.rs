@@ -1,5 +1,10 @@
 pub mod default;
 pub mod plugged;
+pub mod sled;
+
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
 
 use indy_api_types::errors::prelude::*;
 use crate::language;
@@ -41,6 +46,31 @@ pub trait StorageIterator {
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
 }
 
+// Iterates over records that were already fetched from the storage
+pub struct StorageRecordsIterator {
+    records: std::vec::IntoIter<StorageRecord>,
+    total_count: Option<usize>,
+}
+
+impl StorageRecordsIterator {
+    pub fn new(records: Vec<StorageRecord>, total_count: Option<usize>) -> Self {
+        StorageRecordsIterator {
+            records: records.into_iter(),
+            total_count,
+        }
+    }
+}
+
+impl StorageIterator for StorageRecordsIterator {
+    fn next(&mut self) -> Result<Option<StorageRecord>, IndyError> {
+        Ok(self.records.next())
+    }
+
+    fn get_total_count(&self) -> Result<Option<usize>, IndyError> {
+        Ok(self.total_count)
+    }
+}
+
 pub trait WalletStorage {
     fn get(&self, type_: &[u8], id: &[u8], options: &str) -> Result<StorageRecord, IndyError>;
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> Result<(), IndyError>;
@@ -53,6 +83,9 @@ pub trait WalletStorage {
     fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> Result<Box<dyn StorageIterator>, IndyError>;
+    fn begin_transaction(&self) -> Result<(), IndyError>;
+    fn commit_transaction(&self) -> Result<(), IndyError>;
+    fn rollback_transaction(&self) -> Result<(), IndyError>;
     fn close(&mut self) -> Result<(), IndyError>;
 }
 
@@ -60,4 +93,47 @@ pub trait WalletStorageType {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), IndyError>;
-}
\ No newline at end of file
+    fn list_storages(&self, config: Option<&str>, credentials: Option<&str>) -> Result<Vec<String>, IndyError>;
+}
+
+impl<T: WalletStorageType> WalletStorageType for Rc<T> {
+    fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError> {
+        (**self).create_storage(id, config, credentials, metadata)
+    }
+
+    fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError> {
+        (**self).open_storage(id, config, credentials)
+    }
+
+    fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), IndyError> {
+        (**self).delete_storage(id, config, credentials)
+    }
+
+    fn list_storages(&self, config: Option<&str>, credentials: Option<&str>) -> Result<Vec<String>, IndyError> {
+        (**self).list_storages(config, credentials)
+    }
+}
+
+// File based storages keep every wallet in {path}/{id}/{db_name}
+fn list_storage_dirs(path: &Path, db_name: &str) -> Result<Vec<String>, IndyError> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut ids = Vec::new();
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+
+        if !entry.path().join(db_name).exists() {
+            continue;
+        }
+
+        if let Some(id) = entry.file_name().to_str() {
+            ids.push(id.to_string());
+        }
+    }
+
+    ids.sort();
+    Ok(ids)
+}
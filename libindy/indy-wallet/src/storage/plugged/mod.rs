@@ -1,4 +1,5 @@
 use std::{slice, str};
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::ffi::{CStr, CString};
 use std::ptr;
@@ -53,6 +54,19 @@ impl Drop for ResourceGuard {
     }
 }
 
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct PluggedTransactionHandlers {
+    pub begin_transaction_handler: WalletBeginTransaction,
+    pub commit_transaction_handler: WalletCommitTransaction,
+    pub rollback_transaction_handler: WalletRollbackTransaction,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct PluggedListHandlers {
+    pub list_storages_handler: WalletListStorages,
+    pub free_storage_list_handler: WalletFreeStorageList,
+}
+
 
 #[derive(PartialEq, Debug)]
 struct PluggedStorageIterator {
@@ -235,6 +249,7 @@ struct PluggedStorage {
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
     close_handler: WalletClose,
+    transaction_handlers: Option<PluggedTransactionHandlers>,
 }
 
 impl PluggedStorage {
@@ -259,7 +274,8 @@ impl PluggedStorage {
            get_search_total_count_handler: WalletGetSearchTotalCount,
            fetch_search_next_record_handler: WalletFetchSearchNextRecord,
            free_search_handler: WalletFreeSearch,
-           close_handler: WalletClose) -> PluggedStorage {
+           close_handler: WalletClose,
+           transaction_handlers: Option<PluggedTransactionHandlers>) -> PluggedStorage {
         PluggedStorage {
             handle,
             add_record_handler,
@@ -283,8 +299,15 @@ impl PluggedStorage {
             fetch_search_next_record_handler,
             free_search_handler,
             close_handler,
+            transaction_handlers,
         }
     }
+
+    fn transaction_handlers(&self) -> IndyResult<&PluggedTransactionHandlers> {
+        self.transaction_handlers
+            .as_ref()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletStorageError, "Wallet storage doesn't support transactions"))
+    }
 }
 
 fn _tags_to_json(tags: &[Tag]) -> IndyResult<String> {
@@ -572,6 +595,7 @@ impl WalletStorage for PluggedStorage {
                     retrieve_type: true,
                     retrieve_value: true,
                     retrieve_tags: true,
+                    ..SearchOptions::default()
                 },
             )
         ))
@@ -606,6 +630,36 @@ impl WalletStorage for PluggedStorage {
         ))
     }
 
+    fn begin_transaction(&self) -> IndyResult<()> {
+        let err = (self.transaction_handlers()?.begin_transaction_handler)(self.handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> IndyResult<()> {
+        let err = (self.transaction_handlers()?.commit_transaction_handler)(self.handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> IndyResult<()> {
+        let err = (self.transaction_handlers()?.rollback_transaction_handler)(self.handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
     fn close(&mut self) -> IndyResult<()> {
         let err = (self.close_handler)(self.handle);
 
@@ -655,6 +709,8 @@ pub struct PluggedStorageType {
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
+    transaction_handlers: Cell<Option<PluggedTransactionHandlers>>,
+    list_handlers: Cell<Option<PluggedListHandlers>>,
 }
 
 
@@ -708,8 +764,18 @@ impl PluggedStorageType {
             get_search_total_count_handler,
             fetch_search_next_record_handler,
             free_search_handler,
+            transaction_handlers: Cell::new(None),
+            list_handlers: Cell::new(None),
         }
     }
+
+    pub fn set_transaction_handlers(&self, transaction_handlers: PluggedTransactionHandlers) {
+        self.transaction_handlers.set(Some(transaction_handlers));
+    }
+
+    pub fn set_list_handlers(&self, list_handlers: PluggedListHandlers) {
+        self.list_handlers.set(Some(list_handlers));
+    }
 }
 
 impl WalletStorageType for PluggedStorageType {
@@ -781,7 +847,8 @@ impl WalletStorageType for PluggedStorageType {
                 self.get_search_total_count_handler,
                 self.fetch_search_next_record_handler,
                 self.free_search_handler,
-                self.close_handler)))
+                self.close_handler,
+                self.transaction_handlers.get())))
     }
 
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> IndyResult<()> {
@@ -805,6 +872,41 @@ impl WalletStorageType for PluggedStorageType {
 
         Ok(())
     }
+
+    fn list_storages(&self, config: Option<&str>, credentials: Option<&str>) -> IndyResult<Vec<String>> {
+        let list_handlers = self.list_handlers.get()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletStorageError, "Wallet storage doesn't support listing of storages"))?;
+
+        let config = config
+            .map(CString::new)
+            .map_or(Ok(None), |r| r.map(Some))?;
+
+        let credentials = credentials
+            .map(CString::new)
+            .map_or(Ok(None), |r| r.map(Some))?;
+
+        let mut ids_ptr: *const c_char = ptr::null_mut();
+        let mut ids_handle = -1;
+
+        let err = (list_handlers.list_storages_handler)(config.as_ref().map_or(ptr::null(), |x| x.as_ptr()),
+                                                        credentials.as_ref().map_or(ptr::null(), |x| x.as_ptr()),
+                                                        &mut ids_ptr,
+                                                        &mut ids_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let ids = unsafe { CStr::from_ptr(ids_ptr) }
+            .to_str()
+            .to_indy(IndyErrorKind::InvalidState, "Storage ids contain non-utf8 symbol")
+            .and_then(|ids| serde_json::from_str::<Vec<String>>(ids)
+                .to_indy(IndyErrorKind::InvalidState, "Invalid storage ids json"));
+
+        (list_handlers.free_storage_list_handler)(ids_handle);
+
+        ids
+    }
 }
 
 #[cfg(test)]
@@ -867,6 +969,11 @@ mod tests {
         GetSearchTotalCountHandler(i32, i32),
         FetchSearchNextRecordHandler(i32, i32),
         FreeSearchHandler(i32, i32),
+        BeginTransactionHandler(i32),
+        CommitTransactionHandler(i32),
+        RollbackTransactionHandler(i32),
+        ListStoragesHandler(Option<String>, Option<String>),
+        FreeStorageListHandler(i32),
     }
 
     fn _random_vector(len: usize) -> Vec<u8> {
@@ -920,6 +1027,8 @@ mod tests {
     static RETURN_SEARCH_HANDLE: i32 = 3i32;
     static RETURN_METADATA_HANDLE: i32 = 4i32;
     static RETURN_SEARCH_TOTAL_COUNT: usize = 1024;
+    static RETURN_STORAGE_LIST_HANDLE: i32 = 5i32;
+    static RETURN_STORAGE_IDS: &str = "[\"wallet1\",\"wallet2\"]\0";
 
     fn _convert_c_string(str: *const c_char) -> Option<String> {
         if str != ptr::null() {
@@ -1340,6 +1449,49 @@ mod tests {
         ErrorCode::Success
     }
 
+    extern "C" fn _mock_begin_transaction_handler(storage_handle: i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(Call::BeginTransactionHandler(storage_handle));
+
+        ErrorCode::Success
+    }
+
+    extern "C" fn _mock_commit_transaction_handler(storage_handle: i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(Call::CommitTransactionHandler(storage_handle));
+
+        ErrorCode::Success
+    }
+
+    extern "C" fn _mock_rollback_transaction_handler(storage_handle: i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(Call::RollbackTransactionHandler(storage_handle));
+
+        ErrorCode::Success
+    }
+
+    extern "C" fn _mock_list_storages_handler(config: *const c_char,
+                                              credentials: *const c_char,
+                                              ids_json_p: *mut *const c_char,
+                                              ids_handle_p: *mut i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(
+            Call::ListStoragesHandler(
+                _convert_c_string(config),
+                _convert_c_string(credentials),
+            )
+        );
+
+        unsafe {
+            *ids_json_p = RETURN_STORAGE_IDS.as_ptr() as *const c_char;
+            *ids_handle_p = RETURN_STORAGE_LIST_HANDLE;
+        }
+
+        ErrorCode::Success
+    }
+
+    extern "C" fn _mock_free_storage_list_handler(ids_handle: i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(Call::FreeStorageListHandler(ids_handle));
+
+        ErrorCode::Success
+    }
+
     fn _create_storage_type() -> PluggedStorageType {
         PluggedStorageType::new(
             _mock_create_handler,
@@ -1495,6 +1647,82 @@ mod tests {
         serde_json::to_string(&map).unwrap()
     }
 
+    #[test]
+    fn plugged_storage_transaction_works() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage_type = _create_storage_type();
+        storage_type.set_transaction_handlers(PluggedTransactionHandlers {
+            begin_transaction_handler: _mock_begin_transaction_handler,
+            commit_transaction_handler: _mock_commit_transaction_handler,
+            rollback_transaction_handler: _mock_rollback_transaction_handler,
+        });
+
+        let storage = storage_type.open_storage("wallet1", None, Some("credentials")).unwrap();
+
+        DEBUG_VEC.write().unwrap().clear();
+
+        storage.begin_transaction().unwrap();
+        storage.commit_transaction().unwrap();
+        storage.begin_transaction().unwrap();
+        storage.rollback_transaction().unwrap();
+
+        let expected_calls = vec![
+            Call::BeginTransactionHandler(RETURN_STORAGE_HANDLE),
+            Call::CommitTransactionHandler(RETURN_STORAGE_HANDLE),
+            Call::BeginTransactionHandler(RETURN_STORAGE_HANDLE),
+            Call::RollbackTransactionHandler(RETURN_STORAGE_HANDLE),
+        ];
+
+        let debug = DEBUG_VEC.read().unwrap();
+        assert_eq!(expected_calls, *debug);
+    }
+
+    #[test]
+    fn plugged_storage_type_list_works() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage_type = _create_storage_type();
+        storage_type.set_list_handlers(PluggedListHandlers {
+            list_storages_handler: _mock_list_storages_handler,
+            free_storage_list_handler: _mock_free_storage_list_handler,
+        });
+
+        let ids = storage_type.list_storages(Some("config"), None).unwrap();
+        assert_eq!(vec!["wallet1".to_string(), "wallet2".to_string()], ids);
+
+        let expected_calls = vec![
+            Call::ListStoragesHandler(Some("config".to_string()), None),
+            Call::FreeStorageListHandler(RETURN_STORAGE_LIST_HANDLE),
+        ];
+
+        let debug = DEBUG_VEC.read().unwrap();
+        assert_eq!(expected_calls, *debug);
+    }
+
+    #[test]
+    fn plugged_storage_type_list_works_for_not_registered_handlers() {
+        let storage_type = _create_storage_type();
+
+        let res = storage_type.list_storages(None, None);
+        assert_kind!(IndyErrorKind::WalletStorageError, res);
+    }
+
+    #[test]
+    fn plugged_storage_transaction_works_for_not_registered_handlers() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage = _open_storage();
+
+        DEBUG_VEC.write().unwrap().clear();
+
+        let res = storage.begin_transaction();
+        assert_kind!(IndyErrorKind::WalletStorageError, res);
+
+        let debug = DEBUG_VEC.read().unwrap();
+        assert!(debug.is_empty());
+    }
+
     #[test]
     fn plugged_storage_add_works() {
         DEBUG_VEC.write().unwrap().clear();
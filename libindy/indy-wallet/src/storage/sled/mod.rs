@@ -0,0 +1,849 @@
+use std;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+
+use rmp_serde;
+use serde_json;
+
+use indy_api_types::errors::prelude::*;
+use crate::language;
+use indy_utils::environment;
+
+use super::{list_storage_dirs, EncryptedValue, StorageIterator, StorageRecord, StorageRecordsIterator, Tag, TagName, WalletStorage, WalletStorageType};
+use super::super::{RecordOptions, SearchOptions, SortOrder};
+
+mod query;
+
+const _SLED_DB: &str = "sled.db";
+const _METADATA_KEY: &[u8] = &[0];
+const _ITEM_KEY_PREFIX: u8 = 1;
+
+// Record is stored as a single value to make every item update atomic.
+// Key of the record is item prefix, length of the type, type and name.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct SledItem {
+    value: Vec<u8>,
+    key: Vec<u8>,
+    tags_encrypted: Vec<(Vec<u8>, Vec<u8>)>,
+    tags_plaintext: Vec<(Vec<u8>, String)>,
+}
+
+impl SledItem {
+    fn new(value: &EncryptedValue, tags: &[Tag]) -> SledItem {
+        let mut item = SledItem {
+            value: value.data.clone(),
+            key: value.key.clone(),
+            tags_encrypted: Vec::new(),
+            tags_plaintext: Vec::new(),
+        };
+
+        item.add_tags(tags);
+        item
+    }
+
+    fn from_bytes(bytes: &[u8]) -> IndyResult<SledItem> {
+        rmp_serde::from_slice(bytes)
+            .to_indy(IndyErrorKind::InvalidState, "Wallet item is malformed")
+    }
+
+    fn to_bytes(&self) -> IndyResult<Vec<u8>> {
+        rmp_serde::to_vec(self)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet item")
+    }
+
+    fn add_tags(&mut self, tags: &[Tag]) {
+        for tag in tags {
+            match *tag {
+                Tag::Encrypted(ref name, ref value) => {
+                    self.tags_encrypted.retain(|&(ref n, _)| n != name);
+                    self.tags_encrypted.push((name.clone(), value.clone()));
+                }
+                Tag::PlainText(ref name, ref value) => {
+                    self.tags_plaintext.retain(|&(ref n, _)| n != name);
+                    self.tags_plaintext.push((name.clone(), value.clone()));
+                }
+            }
+        }
+    }
+
+    fn delete_tags(&mut self, tag_names: &[TagName]) {
+        for tag_name in tag_names {
+            match *tag_name {
+                TagName::OfEncrypted(ref name) => self.tags_encrypted.retain(|&(ref n, _)| n != name),
+                TagName::OfPlain(ref name) => self.tags_plaintext.retain(|&(ref n, _)| n != name),
+            }
+        }
+    }
+
+    fn plain_tag_value(&self, name: &[u8]) -> Option<&str> {
+        self.tags_plaintext
+            .iter()
+            .find(|&&(ref n, _)| n.as_slice() == name)
+            .map(|&(_, ref v)| v.as_str())
+    }
+
+    fn to_storage_record(&self, type_: &[u8], id: &[u8], options: &RecordOptions) -> StorageRecord {
+        let value = if options.retrieve_value {
+            Some(EncryptedValue::new(self.value.clone(), self.key.clone()))
+        } else {
+            None
+        };
+
+        let type_ = if options.retrieve_type { Some(type_.to_vec()) } else { None };
+
+        let tags = if options.retrieve_tags {
+            let mut tags = Vec::new();
+
+            for &(ref name, ref value) in self.tags_encrypted.iter() {
+                tags.push(Tag::Encrypted(name.clone(), value.clone()));
+            }
+
+            for &(ref name, ref value) in self.tags_plaintext.iter() {
+                tags.push(Tag::PlainText(name.clone(), value.clone()));
+            }
+
+            Some(tags)
+        } else {
+            None
+        };
+
+        StorageRecord::new(id.to_vec(), value, type_, tags)
+    }
+}
+
+fn _item_key_prefix(type_: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(5 + type_.len());
+    key.push(_ITEM_KEY_PREFIX);
+    key.extend_from_slice(&(type_.len() as u32).to_be_bytes());
+    key.extend_from_slice(type_);
+    key
+}
+
+fn _item_key(type_: &[u8], id: &[u8]) -> Vec<u8> {
+    let mut key = _item_key_prefix(type_);
+    key.extend_from_slice(id);
+    key
+}
+
+fn _split_item_key(key: &[u8]) -> IndyResult<(&[u8], &[u8])> {
+    if key.len() < 5 || key[0] != _ITEM_KEY_PREFIX {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Wallet item key is malformed"));
+    }
+
+    let mut type_len = [0u8; 4];
+    type_len.copy_from_slice(&key[1..5]);
+    let type_len = u32::from_be_bytes(type_len) as usize;
+
+    if key.len() < 5 + type_len {
+        return Err(err_msg(IndyErrorKind::InvalidState, "Wallet item key is malformed"));
+    }
+
+    Ok((&key[5..5 + type_len], &key[5 + type_len..]))
+}
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    path: Option<String>,
+}
+
+struct SledStorage {
+    db: sled::Db,
+    // Pending changes of the started wallet transaction. None value means deletion.
+    transaction: RefCell<Option<BTreeMap<Vec<u8>, Option<Vec<u8>>>>>,
+}
+
+pub struct SledStorageType {}
+
+
+impl SledStorageType {
+    pub fn new() -> SledStorageType {
+        SledStorageType {}
+    }
+
+    fn _base_path(config: Option<&Config>) -> std::path::PathBuf {
+        match config {
+            Some(Config { path: Some(ref path) }) => std::path::PathBuf::from(path),
+            _ => environment::wallet_home_path()
+        }
+    }
+
+    fn _db_path(id: &str, config: Option<&Config>) -> std::path::PathBuf {
+        let mut path = SledStorageType::_base_path(config);
+
+        path.push(id);
+        path.push(_SLED_DB);
+        path
+    }
+}
+
+impl SledStorage {
+    fn _get(&self, key: &[u8]) -> IndyResult<Option<Vec<u8>>> {
+        if let Some(ref transaction) = *self.transaction.borrow() {
+            if let Some(value) = transaction.get(key) {
+                return Ok(value.clone());
+            }
+        }
+
+        Ok(self.db.get(key)?.map(|value| value.to_vec()))
+    }
+
+    fn _write(&self, key: Vec<u8>, value: Option<Vec<u8>>) -> IndyResult<()> {
+        if let Some(ref mut transaction) = *self.transaction.borrow_mut() {
+            transaction.insert(key, value);
+            return Ok(());
+        }
+
+        match value {
+            Some(value) => self.db.insert(key, value)?,
+            None => self.db.remove(key)?,
+        };
+
+        // Non transactional write is durable once it is reported as successful
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn _scan(&self, prefix: &[u8]) -> IndyResult<Vec<(Vec<u8>, Vec<u8>)>> {
+        let mut items = BTreeMap::new();
+
+        for item in self.db.scan_prefix(prefix) {
+            let (key, value) = item?;
+            items.insert(key.to_vec(), value.to_vec());
+        }
+
+        if let Some(ref transaction) = *self.transaction.borrow() {
+            for (key, value) in transaction.range(prefix.to_vec()..).take_while(|&(key, _)| key.starts_with(prefix)) {
+                match *value {
+                    Some(ref value) => items.insert(key.clone(), value.clone()),
+                    None => items.remove(key),
+                };
+            }
+        }
+
+        Ok(items.into_iter().collect())
+    }
+
+    fn _get_item(&self, type_: &[u8], id: &[u8]) -> IndyResult<SledItem> {
+        match self._get(&_item_key(type_, id))? {
+            Some(bytes) => SledItem::from_bytes(&bytes),
+            None => Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))
+        }
+    }
+
+    fn _put_item(&self, type_: &[u8], id: &[u8], item: &SledItem) -> IndyResult<()> {
+        self._write(_item_key(type_, id), Some(item.to_bytes()?))
+    }
+}
+
+impl WalletStorage for SledStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let options: RecordOptions = if options == "{}" {
+            RecordOptions::default()
+        } else {
+            serde_json::from_str(options)
+                .to_indy(IndyErrorKind::InvalidStructure, "RecordOptions is malformed json")?
+        };
+
+        let item = self._get_item(type_, id)?;
+        Ok(item.to_storage_record(type_, id, &options))
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        if self._get(&_item_key(type_, id))?.is_some() {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists"));
+        }
+
+        self._put_item(type_, id, &SledItem::new(value, tags))
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        let mut item = match self._get(&_item_key(type_, id))? {
+            Some(bytes) => SledItem::from_bytes(&bytes)?,
+            None => return Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found"))
+        };
+
+        item.value = value.data.clone();
+        item.key = value.key.clone();
+
+        self._put_item(type_, id, &item)
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let mut item = self._get_item(type_, id)?;
+        item.add_tags(tags);
+        self._put_item(type_, id, &item)
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let mut item = self._get_item(type_, id)?;
+        item.tags_encrypted.clear();
+        item.tags_plaintext.clear();
+        item.add_tags(tags);
+        self._put_item(type_, id, &item)
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        let mut item = self._get_item(type_, id)?;
+        item.delete_tags(tag_names);
+        self._put_item(type_, id, &item)
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        let key = _item_key(type_, id);
+
+        if self._get(&key)?.is_none() {
+            return Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"));
+        }
+
+        self._write(key, None)
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        self._get(_METADATA_KEY)?
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Wallet metadata not found"))
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self._write(_METADATA_KEY.to_vec(), Some(metadata.to_vec()))
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+        let options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let mut records = Vec::new();
+
+        for (key, value) in self._scan(&[_ITEM_KEY_PREFIX])? {
+            let (type_, id) = _split_item_key(&key)?;
+            records.push(SledItem::from_bytes(&value)?.to_storage_record(type_, id, &options));
+        }
+
+        Ok(Box::new(StorageRecordsIterator::new(records, None)))
+    }
+
+    fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        let sort_tag_name = search_options.sort_tag_name()?;
+
+        let mut items = Vec::new();
+
+        for (key, value) in self._scan(&_item_key_prefix(type_))? {
+            let item = SledItem::from_bytes(&value)?;
+
+            if query::matches(query, &item)? {
+                items.push((key, item));
+            }
+        }
+
+        let total_count = if search_options.retrieve_total_count { Some(items.len()) } else { None };
+
+        if !search_options.retrieve_records {
+            return Ok(Box::new(StorageRecordsIterator::new(Vec::new(), total_count)));
+        }
+
+        // Items are already ordered by key, missing tags are ordered first as SQL NULL values
+        if let Some(ref tag_name) = sort_tag_name {
+            items.sort_by(|a, b| a.1.plain_tag_value(tag_name).cmp(&b.1.plain_tag_value(tag_name)));
+
+            if search_options.sort_order == SortOrder::Desc {
+                items.reverse();
+            }
+        }
+
+        let fetch_options = RecordOptions {
+            retrieve_value: search_options.retrieve_value,
+            retrieve_tags: search_options.retrieve_tags,
+            retrieve_type: search_options.retrieve_type,
+        };
+
+        let mut records = Vec::new();
+
+        for (key, item) in items
+            .into_iter()
+            .skip(search_options.offset.unwrap_or(0))
+            .take(search_options.limit.unwrap_or(usize::max_value())) {
+            let (type_, id) = _split_item_key(&key)?;
+            records.push(item.to_storage_record(type_, id, &fetch_options));
+        }
+
+        Ok(Box::new(StorageRecordsIterator::new(records, total_count)))
+    }
+
+    fn begin_transaction(&self) -> IndyResult<()> {
+        let mut transaction = self.transaction.borrow_mut();
+
+        if transaction.is_some() {
+            return Err(err_msg(IndyErrorKind::WalletStorageError, "Wallet transaction is already started"));
+        }
+
+        *transaction = Some(BTreeMap::new());
+        Ok(())
+    }
+
+    fn commit_transaction(&self) -> IndyResult<()> {
+        let transaction = self.transaction.borrow_mut().take()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletStorageError, "No wallet transaction is started"))?;
+
+        let mut batch = sled::Batch::default();
+
+        for (key, value) in transaction {
+            match value {
+                Some(value) => batch.insert(key, value),
+                None => batch.remove(key),
+            }
+        }
+
+        self.db.apply_batch(batch)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    fn rollback_transaction(&self) -> IndyResult<()> {
+        self.transaction.borrow_mut().take()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletStorageError, "No wallet transaction is started"))?;
+        Ok(())
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        self.transaction.borrow_mut().take();
+        self.db.flush()?;
+        Ok(())
+    }
+}
+
+
+impl WalletStorageType for SledStorageType {
+    ///
+    /// Deletes the sled database with the provided id from the path specified in the config.
+    ///
+    fn delete_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<()> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        let db_path = SledStorageType::_db_path(id, config.as_ref());
+
+        if !db_path.exists() {
+            return Err(err_msg(IndyErrorKind::WalletNotFound, format!("Wallet storage directory isn't found: {:?}", db_path)));
+        }
+
+        fs::remove_dir_all(&db_path)?;
+
+        // wallet directory can contain other storage files with the same id, so it is kept if not empty
+        let _ = fs::remove_dir(db_path.parent().unwrap());
+        Ok(())
+    }
+
+    ///
+    /// Creates the sled database with the provided id in the path specified in the config
+    /// and stores the wallet metadata in it.
+    ///
+    fn create_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        let db_path = SledStorageType::_db_path(id, config.as_ref());
+
+        if db_path.exists() {
+            return Err(err_msg(IndyErrorKind::WalletAlreadyExists, format!("Wallet database already exists: {:?}", db_path)));
+        }
+
+        fs::DirBuilder::new()
+            .recursive(true)
+            .create(db_path.parent().unwrap())?;
+
+        let res = sled::open(&db_path)
+            .and_then(|db| {
+                db.insert(_METADATA_KEY, metadata)?;
+                db.flush()
+            });
+
+        if let Err(err) = res {
+            let _ = fs::remove_dir_all(&db_path);
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Opens the sled database with the provided id located in the path specified in the config.
+    ///
+    fn open_storage(&self, id: &str, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        let db_path = SledStorageType::_db_path(id, config.as_ref());
+
+        if !db_path.exists() {
+            return Err(err_msg(IndyErrorKind::WalletNotFound, "No wallet database exists"));
+        }
+
+        let db = sled::open(&db_path)?;
+
+        Ok(Box::new(SledStorage { db, transaction: RefCell::new(None) }))
+    }
+
+    ///
+    /// Lists ids of sled databases located in the path specified in the config.
+    ///
+    fn list_storages(&self, config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Vec<String>> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?;
+
+        list_storage_dirs(&SledStorageType::_base_path(config.as_ref()), _SLED_DB)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indy_utils::crypto::base64;
+    use indy_utils::test;
+
+    use super::*;
+    use super::super::Tag;
+
+    #[test]
+    fn sled_storage_type_create_works() {
+        _cleanup("sled_storage_type_create_works");
+
+        let storage_type = SledStorageType::new();
+        storage_type.create_storage("sled_storage_type_create_works", None, None, &_metadata()).unwrap();
+
+        _cleanup("sled_storage_type_create_works");
+    }
+
+    #[test]
+    fn sled_storage_type_create_works_for_twice() {
+        _cleanup("sled_storage_type_create_works_for_twice");
+
+        let storage_type = SledStorageType::new();
+        storage_type.create_storage("sled_storage_type_create_works_for_twice", None, None, &_metadata()).unwrap();
+
+        let res = storage_type.create_storage("sled_storage_type_create_works_for_twice", None, None, &_metadata());
+        assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+        _cleanup("sled_storage_type_create_works_for_twice");
+    }
+
+    #[test]
+    fn sled_storage_type_delete_works() {
+        _cleanup("sled_storage_type_delete_works");
+
+        let storage_type = SledStorageType::new();
+        storage_type.create_storage("sled_storage_type_delete_works", None, None, &_metadata()).unwrap();
+        storage_type.delete_storage("sled_storage_type_delete_works", None, None).unwrap();
+
+        let res = storage_type.open_storage("sled_storage_type_delete_works", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+        _cleanup("sled_storage_type_delete_works");
+    }
+
+    #[test]
+    fn sled_storage_get_storage_metadata_works() {
+        _cleanup("sled_storage_get_storage_metadata_works");
+        {
+            let storage = _storage("sled_storage_get_storage_metadata_works");
+            assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+
+            storage.set_storage_metadata(&[1, 2, 3]).unwrap();
+            assert_eq!(storage.get_storage_metadata().unwrap(), vec![1, 2, 3]);
+        }
+        _cleanup("sled_storage_get_storage_metadata_works");
+    }
+
+    #[test]
+    fn sled_storage_add_works_with_reopen() {
+        _cleanup("sled_storage_add_works_with_reopen");
+        {
+            let storage = _storage("sled_storage_add_works_with_reopen");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        }
+        {
+            let storage_type = SledStorageType::new();
+            let storage = storage_type.open_storage("sled_storage_add_works_with_reopen", None, None).unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            assert_eq!(record.type_.unwrap(), _type1());
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+        }
+        _cleanup("sled_storage_add_works_with_reopen");
+    }
+
+    #[test]
+    fn sled_storage_add_works_for_already_existing() {
+        _cleanup("sled_storage_add_works_for_already_existing");
+        {
+            let storage = _storage("sled_storage_add_works_for_already_existing");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            let res = storage.add(&_type1(), &_id1(), &_value2(), &_tags());
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+        }
+        _cleanup("sled_storage_add_works_for_already_existing");
+    }
+
+    #[test]
+    fn sled_storage_update_works() {
+        _cleanup("sled_storage_update_works");
+        {
+            let storage = _storage("sled_storage_update_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            storage.update(&_type1(), &_id1(), &_value2()).unwrap();
+            let record = storage.get(&_type1(), &_id1(), "{}").unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+
+            let res = storage.update(&_type1(), &_id2(), &_value2());
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sled_storage_update_works");
+    }
+
+    #[test]
+    fn sled_storage_tags_works() {
+        _cleanup("sled_storage_tags_works");
+        {
+            let storage = _storage("sled_storage_tags_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            storage.add_tags(&_type1(), &_id1(), &_new_tags()).unwrap();
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+            let mut expected_tags = _tags();
+            expected_tags.extend(_new_tags());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(expected_tags));
+
+            storage.update_tags(&_type1(), &_id1(), &_new_tags()).unwrap();
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+            storage.delete_tags(&_type1(), &_id1(), &[TagName::OfEncrypted(vec![1, 1, 1])]).unwrap();
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+            assert_eq!(record.tags.unwrap(), vec![Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))]);
+        }
+        _cleanup("sled_storage_tags_works");
+    }
+
+    #[test]
+    fn sled_storage_delete_works() {
+        _cleanup("sled_storage_delete_works");
+        {
+            let storage = _storage("sled_storage_delete_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.delete(&_type1(), &_id1()).unwrap();
+
+            let res = storage.get(&_type1(), &_id1(), "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = storage.delete(&_type1(), &_id1());
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sled_storage_delete_works");
+    }
+
+    #[test]
+    fn sled_storage_get_all_works() {
+        _cleanup("sled_storage_get_all_works");
+        {
+            let storage = _storage("sled_storage_get_all_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.add(&_type2(), &_id2(), &_value2(), &_tags()).unwrap();
+
+            let mut storage_iterator = storage.get_all().unwrap();
+
+            let record = storage_iterator.next().unwrap().unwrap();
+            assert_eq!(record.type_.unwrap(), _type1());
+            assert_eq!(record.id, _id1());
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+            let record = storage_iterator.next().unwrap().unwrap();
+            assert_eq!(record.type_.unwrap(), _type2());
+            assert_eq!(record.id, _id2());
+
+            assert!(storage_iterator.next().unwrap().is_none());
+        }
+        _cleanup("sled_storage_get_all_works");
+    }
+
+    #[test]
+    fn sled_storage_search_works() {
+        _cleanup("sled_storage_search_works");
+        {
+            let storage = _storage("sled_storage_search_works");
+            storage.add(&_type1(), &_id(1), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "b".to_string())]).unwrap();
+            storage.add(&_type1(), &_id(2), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "c".to_string())]).unwrap();
+            storage.add(&_type1(), &_id(3), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "a".to_string())]).unwrap();
+            storage.add(&_type2(), &_id(4), &_value1(), &[Tag::PlainText(vec![1, 2, 3], "a".to_string())]).unwrap();
+
+            let query = language::Operator::Gt(language::TagName::PlainTagName(vec![1, 2, 3]), language::TargetValue::Unencrypted("a".to_string()));
+            let mut storage_iterator = storage.search(&_type1(), &query, Some(r##"{"retrieveTotalCount": true}"##)).unwrap();
+
+            assert_eq!(storage_iterator.get_total_count().unwrap(), Some(2));
+            assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id(1));
+            assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id(2));
+            assert!(storage_iterator.next().unwrap().is_none());
+
+            let options = json!({
+                "sortBy": {"tagName": format!("~{}", base64::encode(&[1, 2, 3]))},
+                "sortOrder": "desc",
+                "offset": 1,
+                "limit": 1
+            }).to_string();
+
+            let mut storage_iterator = storage.search(&_type1(), &language::Operator::And(vec![]), Some(&options)).unwrap();
+            assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id(1));
+            assert!(storage_iterator.next().unwrap().is_none());
+        }
+        _cleanup("sled_storage_search_works");
+    }
+
+    #[test]
+    fn sled_storage_transaction_commit_works() {
+        _cleanup("sled_storage_transaction_commit_works");
+        {
+            let storage = _storage("sled_storage_transaction_commit_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            storage.begin_transaction().unwrap();
+            storage.add(&_type1(), &_id2(), &_value2(), &_tags()).unwrap();
+            storage.delete(&_type1(), &_id1()).unwrap();
+
+            let mut storage_iterator = storage.search(&_type1(), &language::Operator::And(vec![]), None).unwrap();
+            assert_eq!(storage_iterator.next().unwrap().unwrap().id, _id2());
+            assert!(storage_iterator.next().unwrap().is_none());
+
+            storage.commit_transaction().unwrap();
+
+            assert!(storage.get(&_type1(), &_id1(), "{}").is_err());
+            storage.get(&_type1(), &_id2(), "{}").unwrap();
+        }
+        _cleanup("sled_storage_transaction_commit_works");
+    }
+
+    #[test]
+    fn sled_storage_transaction_rollback_works() {
+        _cleanup("sled_storage_transaction_rollback_works");
+        {
+            let storage = _storage("sled_storage_transaction_rollback_works");
+
+            storage.begin_transaction().unwrap();
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.rollback_transaction().unwrap();
+
+            let res = storage.get(&_type1(), &_id1(), "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = storage.commit_transaction();
+            assert_kind!(IndyErrorKind::WalletStorageError, res);
+        }
+        _cleanup("sled_storage_transaction_rollback_works");
+    }
+
+    #[test]
+    fn sled_storage_type_list_works() {
+        let mut path = environment::tmp_path();
+        path.push("sled_storage_type_list_works");
+        let _ = fs::remove_dir_all(&path);
+
+        let config = json!({"path": path.to_str().unwrap()}).to_string();
+
+        let storage_type = SledStorageType::new();
+        storage_type.create_storage("wallet1", Some(&config), None, &_metadata()).unwrap();
+
+        let ids = storage_type.list_storages(Some(&config), None).unwrap();
+        assert_eq!(vec!["wallet1".to_string()], ids);
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+
+    fn _cleanup(name: &str) {
+        test::cleanup_storage(name)
+    }
+
+    fn _storage(name: &str) -> Box<dyn WalletStorage> {
+        let storage_type = SledStorageType::new();
+        storage_type.create_storage(name, None, None, &_metadata()).unwrap();
+        storage_type.open_storage(name, None, None).unwrap()
+    }
+
+    fn _metadata() -> Vec<u8> {
+        vec![
+            1, 2, 3, 4, 5, 6, 7, 8,
+            1, 2, 3, 4, 5, 6, 7, 8,
+        ]
+    }
+
+    fn _type(i: u8) -> Vec<u8> {
+        vec![i, 1 + i, 2 + i]
+    }
+
+    fn _type1() -> Vec<u8> {
+        _type(1)
+    }
+
+    fn _type2() -> Vec<u8> {
+        _type(2)
+    }
+
+    fn _id(i: u8) -> Vec<u8> {
+        vec![3 + i, 4 + i, 5 + i]
+    }
+
+    fn _id1() -> Vec<u8> {
+        _id(1)
+    }
+
+    fn _id2() -> Vec<u8> {
+        _id(2)
+    }
+
+    fn _value(i: u8) -> EncryptedValue {
+        EncryptedValue { data: vec![6 + i, 7 + i, 8 + i], key: vec![9 + i, 10 + i, 11 + i] }
+    }
+
+    fn _value1() -> EncryptedValue {
+        _value(1)
+    }
+
+    fn _value2() -> EncryptedValue {
+        _value(2)
+    }
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+            Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string())
+        ]
+    }
+
+    fn _new_tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+            Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))
+        ]
+    }
+
+    fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+        v.sort();
+        v
+    }
+}
@@ -0,0 +1,152 @@
+use indy_api_types::errors::prelude::*;
+use crate::language::{Operator, TagName, TargetValue};
+
+use super::SledItem;
+
+
+// Evaluates Wallet Query Language against tags of the item
+// WQL input is provided as a reference to a top level Operator
+// Semantic is the same as for SQL translation of the default storage
+pub fn matches(op: &Operator, item: &SledItem) -> IndyResult<bool> {
+    match *op {
+        Operator::Eq(ref tag_name, ref target_value) => eq_matches(tag_name, target_value, item),
+        Operator::Neq(ref tag_name, ref target_value) => neq_matches(tag_name, target_value, item),
+        Operator::Gt(ref tag_name, ref target_value) =>
+            plain_cmp_matches(tag_name, target_value, item, "$gt", |value, target| value > target),
+        Operator::Gte(ref tag_name, ref target_value) =>
+            plain_cmp_matches(tag_name, target_value, item, "$gte", |value, target| value >= target),
+        Operator::Lt(ref tag_name, ref target_value) =>
+            plain_cmp_matches(tag_name, target_value, item, "$lt", |value, target| value < target),
+        Operator::Lte(ref tag_name, ref target_value) =>
+            plain_cmp_matches(tag_name, target_value, item, "$lte", |value, target| value <= target),
+        Operator::Like(ref tag_name, ref target_value) =>
+            plain_cmp_matches(tag_name, target_value, item, "$like", like),
+        Operator::In(ref tag_name, ref target_values) => in_matches(tag_name, target_values, item),
+        Operator::And(ref suboperators) => {
+            for suboperator in suboperators {
+                if !matches(suboperator, item)? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        Operator::Or(ref suboperators) => {
+            // empty $or doesn't restrict results as in SQL translation
+            if suboperators.is_empty() {
+                return Ok(true);
+            }
+
+            for suboperator in suboperators {
+                if matches(suboperator, item)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+        Operator::Not(ref suboperator) => Ok(!matches(suboperator, item)?),
+    }
+}
+
+
+fn eq_matches(name: &TagName, value: &TargetValue, item: &SledItem) -> IndyResult<bool> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) =>
+            Ok(item.tags_plaintext.iter().any(|&(ref n, ref v)| n == queried_name && v == queried_value)),
+        (&TagName::EncryptedTagName(ref queried_name), &TargetValue::Encrypted(ref queried_value)) =>
+            Ok(item.tags_encrypted.iter().any(|&(ref n, ref v)| n == queried_name && v == queried_value)),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for equality operator"))
+    }
+}
+
+
+fn neq_matches(name: &TagName, value: &TargetValue, item: &SledItem) -> IndyResult<bool> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) =>
+            Ok(item.tags_plaintext.iter().any(|&(ref n, ref v)| n == queried_name && v != queried_value)),
+        (&TagName::EncryptedTagName(ref queried_name), &TargetValue::Encrypted(ref queried_value)) =>
+            Ok(item.tags_encrypted.iter().any(|&(ref n, ref v)| n == queried_name && v != queried_value)),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for inequality operator"))
+    }
+}
+
+
+fn plain_cmp_matches<F>(name: &TagName, value: &TargetValue, item: &SledItem, op_name: &str, cmp: F) -> IndyResult<bool>
+    where F: Fn(&str, &str) -> bool {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) =>
+            Ok(item.tags_plaintext.iter().any(|&(ref n, ref v)| n == queried_name && cmp(v.as_str(), queried_value.as_str()))),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {} operator", op_name)))
+    }
+}
+
+
+fn in_matches(name: &TagName, values: &[TargetValue], item: &SledItem) -> IndyResult<bool> {
+    let mut res = false;
+
+    for value in values {
+        res |= match (name, value) {
+            (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) |
+            (&TagName::EncryptedTagName(_), &TargetValue::Encrypted(_)) => eq_matches(name, value, item)?,
+            _ => return Err(err_msg(IndyErrorKind::WalletQueryError, "Invalid combination of tag name and value for $in operator"))
+        };
+    }
+
+    Ok(res)
+}
+
+
+// SQL LIKE: % matches any sequence, _ matches any single char, ASCII case is ignored
+fn like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    _like(&value, &pattern)
+}
+
+
+fn _like(value: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((&'%', rest)) => (0..=value.len()).any(|i| _like(&value[i..], rest)),
+        Some((&'_', rest)) => !value.is_empty() && _like(&value[1..], rest),
+        Some((c, rest)) => value.first().map_or(false, |v| v.eq_ignore_ascii_case(c)) && _like(&value[1..], rest),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _item() -> SledItem {
+        SledItem {
+            value: vec![1],
+            key: vec![2],
+            tags_encrypted: vec![(vec![1, 2, 3], vec![4, 5, 6])],
+            tags_plaintext: vec![(vec![7, 8, 9], "spam".to_string())],
+        }
+    }
+
+    #[test]
+    fn matches_works_for_nested() {
+        let query = Operator::And(vec![
+            Operator::Eq(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6])),
+            Operator::Or(vec![
+                Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("SP%".to_string())),
+                Operator::Eq(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("eggs".to_string())),
+            ]),
+            Operator::Not(Box::new(Operator::Gt(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("z".to_string())))),
+        ]);
+
+        assert!(matches(&query, &_item()).unwrap());
+
+        let query = Operator::In(TagName::PlainTagName(vec![7, 8, 9]), vec![TargetValue::Unencrypted("eggs".to_string())]);
+        assert!(!matches(&query, &_item()).unwrap());
+    }
+
+    #[test]
+    fn matches_works_for_gt_encrypted() {
+        let query = Operator::Gt(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6]));
+        let res = matches(&query, &_item());
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use indy_utils::crypto::{hmacsha256, chacha20poly1305_ietf};
+use indy_utils::crypto::{base64, hmacsha256, chacha20poly1305_ietf};
 use indy_utils::wql::Query;
 
 use indy_api_types::errors::prelude::*;
@@ -9,10 +9,12 @@ use indy_api_types::errors::prelude::*;
 use zeroize::Zeroize;
 
 use super::storage;
+use super::storage::{StorageIterator, StorageRecord, StorageRecordsIterator, Tag};
 use super::iterator::WalletIterator;
 use super::encryption::*;
+use super::language::{Operator, TagName};
 use super::query_encryption::encrypt_query;
-use super::WalletRecord;
+use super::{BrokenRecord, WalletRecord, SearchOptions, SortBy, SortOrder};
 
 #[derive(Serialize, Deserialize)]
 pub(super) struct Keys {
@@ -196,11 +198,89 @@ impl Wallet {
 
         let encrypted_query = encrypt_query(parsed_query, &self.keys)?;
         let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
-        let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options)?;
+
+        let search_options: SearchOptions = match options {
+            Some(options) => ::serde_json::from_str(options)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?,
+            None => SearchOptions::default(),
+        };
+
+        let storage_iterator = match search_options.sort_by {
+            None => self.storage.search(&encrypted_type_, &encrypted_query, options)?,
+            Some(SortBy::TagName(ref tag_name)) => {
+                let storage_options = SearchOptions {
+                    sort_by: Some(SortBy::TagName(self._encrypt_sort_tag_name(tag_name)?)),
+                    ..search_options.clone()
+                };
+
+                self.storage.search(&encrypted_type_, &encrypted_query, Some(&_serialize_search_options(&storage_options)?))?
+            }
+            Some(SortBy::Id) => self._search_sorted_by_id(&encrypted_type_, &encrypted_query, &search_options)?,
+        };
+
         let wallet_iterator = WalletIterator::new(storage_iterator, Rc::clone(&self.keys));
         Ok(wallet_iterator)
     }
 
+    fn _encrypt_sort_tag_name(&self, tag_name: &str) -> IndyResult<String> {
+        match TagName::from(tag_name.to_string())? {
+            TagName::PlainTagName(ref name) => {
+                let encrypted_name = encrypt_as_searchable(&name[..], &self.keys.tag_name_key, &self.keys.tags_hmac_key);
+                Ok(format!("~{}", base64::encode(&encrypted_name)))
+            }
+            TagName::EncryptedTagName(_) =>
+                Err(err_msg(IndyErrorKind::WalletQueryError, "Sorting is supported only by unencrypted tags"))
+        }
+    }
+
+    // Record ids are stored encrypted, so storages can't order by them.
+    // Matching records are fetched and ordered by decrypted ids instead.
+    fn _search_sorted_by_id(&self, type_: &[u8], query: &Operator, search_options: &SearchOptions) -> IndyResult<Box<dyn StorageIterator>> {
+        let storage_options = SearchOptions {
+            sort_by: None,
+            offset: None,
+            limit: None,
+            ..search_options.clone()
+        };
+
+        let mut storage_iterator = self.storage.search(type_, query, Some(&_serialize_search_options(&storage_options)?))?;
+        let total_count = storage_iterator.get_total_count()?;
+
+        let mut records = Vec::new();
+
+        while let Some(record) = storage_iterator.next()? {
+            let id = decrypt_merged(&record.id, &self.keys.name_key)?;
+            records.push((id, record));
+        }
+
+        records.sort_by(|a, b| a.0.cmp(&b.0));
+
+        if search_options.sort_order == SortOrder::Desc {
+            records.reverse();
+        }
+
+        let records = records
+            .into_iter()
+            .map(|(_, record)| record)
+            .skip(search_options.offset.unwrap_or(0))
+            .take(search_options.limit.unwrap_or(usize::max_value()))
+            .collect();
+
+        Ok(Box::new(StorageRecordsIterator::new(records, total_count)))
+    }
+
+    pub fn begin_transaction(&self) -> IndyResult<()> {
+        self.storage.begin_transaction()
+    }
+
+    pub fn commit_transaction(&self) -> IndyResult<()> {
+        self.storage.commit_transaction()
+    }
+
+    pub fn rollback_transaction(&self) -> IndyResult<()> {
+        self.storage.rollback_transaction()
+    }
+
     pub fn close(&mut self) -> IndyResult<()> {
         self.storage.close()
             .map_err(IndyError::from)
@@ -214,6 +294,230 @@ impl Wallet {
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
+
+    pub fn get_keys(&self) -> &Keys {
+        &self.keys
+    }
+
+    pub fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.storage.set_storage_metadata(metadata)
+    }
+
+    // Re-encrypts all records with the given keys and starts using them.
+    // Records that are already encrypted with the new keys (left by an interrupted re-key) are skipped,
+    // so the call can be safely repeated until it succeeds.
+    pub fn rekey(&mut self, next_keys: Rc<Keys>, progress: &dyn Fn(usize, usize)) -> IndyResult<()> {
+        // Records are fetched before re-encryption as each of them is replaced in storage
+        let records = {
+            let mut records = Vec::new();
+            let mut all_items = self.storage.get_all()?;
+
+            while let Some(record) = all_items.next()? {
+                records.push(record);
+            }
+
+            records
+        };
+
+        let total = records.len();
+        progress(0, total);
+
+        for (processed, record) in records.iter().enumerate() {
+            if decrypt_merged(&record.id, &self.keys.name_key).is_ok() {
+                self._rekey_record(record, &next_keys)?;
+            } else if decrypt_merged(&record.id, &next_keys.name_key).is_err() {
+                return Err(err_msg(IndyErrorKind::WalletEncryptionError, "Wallet record is encrypted with unknown keys"));
+            }
+
+            progress(processed + 1, total);
+        }
+
+        self.keys = next_keys;
+        Ok(())
+    }
+
+    fn _rekey_record(&self, record: &StorageRecord, next_keys: &Keys) -> IndyResult<()> {
+        let decrypted = decrypt_storage_record(record, &self.keys)?;
+
+        let (etype, value, type_) = match (&record.type_, &record.value, &decrypted.type_) {
+            (&Some(ref etype), &Some(ref value), &Some(ref type_)) => (etype, value, type_),
+            _ => return Err(err_msg(IndyErrorKind::InvalidState, "Wallet record type and value are expected"))
+        };
+
+        let next_etype = encrypt_as_searchable(type_.as_bytes(), &next_keys.type_key, &next_keys.item_hmac_key);
+        let next_ename = encrypt_as_searchable(decrypted.id.as_bytes(), &next_keys.name_key, &next_keys.item_hmac_key);
+
+        // Only the value key is re-wrapped, the value itself stays encrypted with it
+        let next_evalue = {
+            let mut value_key = decrypt_merged(&value.key, &self.keys.value_key)?;
+            let next_evalue = EncryptedValue::new(value.data.clone(), encrypt_as_not_searchable(&value_key, &next_keys.value_key));
+            value_key.zeroize();
+            next_evalue
+        };
+
+        let tags = decrypted.tags.clone().unwrap_or_default();
+        let next_etags = encrypt_tags(&tags, &next_keys.tag_name_key, &next_keys.tag_value_key, &next_keys.tags_hmac_key);
+
+        // The new record may already be stored if the previous attempt was interrupted before removing the old one
+        match self.storage.add(&next_etype, &next_ename, &next_evalue, &next_etags) {
+            Ok(()) => {}
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists => {}
+            Err(err) => return Err(err)
+        }
+
+        self.storage.delete(etype, &record.id)
+    }
+
+    // Verifies that every record can be decrypted with wallet keys and nonces of its searchable parts
+    // match HMAC of decrypted data. Broken records are optionally moved to the quarantine record type.
+    pub fn check(&self, quarantine_type: Option<&str>) -> IndyResult<(usize, Vec<BrokenRecord>)> {
+        // Broken records are collected before quarantining as it modifies the storage
+        let (total, mut broken) = {
+            let mut total = 0;
+            let mut broken = Vec::new();
+            let mut all_items = self.storage.get_all()?;
+
+            while let Some(record) = all_items.next()? {
+                total += 1;
+
+                if let Err(broken_record) = self._check_record(&record) {
+                    broken.push((record, broken_record));
+                }
+            }
+
+            (total, broken)
+        };
+
+        if let Some(quarantine_type) = quarantine_type {
+            for &mut (ref record, ref mut broken_record) in broken.iter_mut() {
+                self._quarantine_record(quarantine_type, record, broken_record)?;
+                broken_record.quarantined = true;
+            }
+        }
+
+        Ok((total, broken.into_iter().map(|(_, broken_record)| broken_record).collect()))
+    }
+
+    fn _check_record(&self, record: &StorageRecord) -> Result<(), BrokenRecord> {
+        let mut broken = BrokenRecord {
+            type_: None,
+            id: None,
+            raw_id: base64::encode(&record.id),
+            reason: String::new(),
+            quarantined: false,
+        };
+
+        match self._check_record_parts(record, &mut broken) {
+            Ok(()) => Ok(()),
+            Err(reason) => {
+                broken.reason = reason;
+                Err(broken)
+            }
+        }
+    }
+
+    // Decrypted type and id are saved to the report as soon as they are verified
+    fn _check_record_parts(&self, record: &StorageRecord, broken: &mut BrokenRecord) -> Result<(), String> {
+        let etype = record.type_.as_ref()
+            .ok_or_else(|| "Record type is missed".to_string())?;
+
+        broken.type_ = Some(_check_searchable(etype, &self.keys.type_key, &self.keys.item_hmac_key)
+            .map_err(|err| format!("Record type {}", err))?);
+
+        broken.id = Some(_check_searchable(&record.id, &self.keys.name_key, &self.keys.item_hmac_key)
+            .map_err(|err| format!("Record id {}", err))?);
+
+        self._check_record_value(record)?;
+        self._check_record_tags(record)
+    }
+
+    fn _check_record_value(&self, record: &StorageRecord) -> Result<(), String> {
+        match record.value {
+            Some(ref value) => {
+                if value.key.len() < chacha20poly1305_ietf::NONCEBYTES || value.data.len() < chacha20poly1305_ietf::NONCEBYTES {
+                    return Err("Record value is too short".to_string());
+                }
+
+                value.decrypt(&self.keys.value_key)
+                    .map(|_| ())
+                    .map_err(|_| "Record value can't be decrypted".to_string())
+            }
+            None => Err("Record value is missed".to_string())
+        }
+    }
+
+    fn _check_record_tags(&self, record: &StorageRecord) -> Result<(), String> {
+        for tag in record.tags.as_ref().map(Vec::as_slice).unwrap_or(&[]) {
+            match *tag {
+                Tag::PlainText(ref ename, _) => {
+                    _check_searchable(ename, &self.keys.tag_name_key, &self.keys.tags_hmac_key)
+                        .map_err(|err| format!("Record tag name {}", err))?;
+                }
+                Tag::Encrypted(ref ename, ref evalue) => {
+                    let name = _check_searchable(ename, &self.keys.tag_name_key, &self.keys.tags_hmac_key)
+                        .map_err(|err| format!("Record tag name {}", err))?;
+                    _check_searchable(evalue, &self.keys.tag_value_key, &self.keys.tags_hmac_key)
+                        .map_err(|err| format!("Record tag {} value {}", name, err))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _quarantine_record(&self, quarantine_type: &str, record: &StorageRecord, broken: &BrokenRecord) -> IndyResult<()> {
+        let etype = record.type_.as_ref()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Can't quarantine wallet record without type"))?;
+
+        // Raw record is kept as is, so it still can be recovered if the cause of damage is found
+        let raw_record = serde_json::json!({
+            "type": base64::encode(etype),
+            "id": broken.raw_id,
+            "value": record.value.as_ref().map(|value| base64::encode(&value.to_bytes())),
+            "tags": record.tags.as_ref().map(|tags| tags.iter().map(|tag| match *tag {
+                Tag::PlainText(ref name, ref value) => serde_json::json!({"name": base64::encode(name), "value": value, "plain": true}),
+                Tag::Encrypted(ref name, ref value) => serde_json::json!({"name": base64::encode(name), "value": base64::encode(value), "plain": false}),
+            }).collect::<Vec<_>>()),
+        }).to_string();
+
+        let mut tags = HashMap::new();
+        tags.insert("reason".to_string(), broken.reason.clone());
+        if let Some(ref type_) = broken.type_ { tags.insert("type".to_string(), type_.clone()); }
+        if let Some(ref id) = broken.id { tags.insert("id".to_string(), id.clone()); }
+
+        // Record with the same id but other type would have the same encrypted id
+        let quarantine_id = format!("{}:{}", base64::encode(etype), broken.raw_id);
+
+        match self.add(quarantine_type, &quarantine_id, &raw_record, &tags) {
+            Ok(()) => {}
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists => {}
+            Err(err) => return Err(err)
+        }
+
+        self.storage.delete(etype, &record.id)
+    }
+}
+
+// Searchable data is encrypted with nonce taken from HMAC of plain data
+fn _check_searchable(data: &[u8], key: &chacha20poly1305_ietf::Key, hmac_key: &hmacsha256::Key) -> Result<String, &'static str> {
+    if data.len() < chacha20poly1305_ietf::NONCEBYTES {
+        return Err("is too short");
+    }
+
+    let plain = decrypt_merged(data, key)
+        .map_err(|_| "can't be decrypted")?;
+
+    if hmacsha256::authenticate(&plain, hmac_key)[..chacha20poly1305_ietf::NONCEBYTES] != data[..chacha20poly1305_ietf::NONCEBYTES] {
+        return Err("doesn't match its HMAC");
+    }
+
+    String::from_utf8(plain)
+        .map_err(|_| "is invalid utf8")
+}
+
+fn _serialize_search_options(search_options: &SearchOptions) -> IndyResult<String> {
+    ::serde_json::to_string(search_options)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize search options")
 }
 
 #[cfg(test)]
@@ -1948,6 +2252,196 @@ mod tests {
         test::cleanup_wallet("wallet_search_works_for_nested_empty");
     }
 
+    #[test]
+    fn wallet_search_works_for_sort_by_id() {
+        test::cleanup_wallet("wallet_search_works_for_sort_by_id");
+        {
+            let mut wallet = _wallet("wallet_search_works_for_sort_by_id");
+            wallet.add(_type1(), _id2(), _value2(), &HashMap::new()).unwrap();
+            wallet.add(_type1(), _id3(), _value3(), &HashMap::new()).unwrap();
+            wallet.add(_type1(), _id1(), _value1(), &HashMap::new()).unwrap();
+
+            let options = jsonstr!({"retrieveTotalCount": true, "sortBy": "id", "sortOrder": "desc", "offset": 1});
+            let mut iterator = wallet.search(_type1(), "{}", Some(&options)).unwrap();
+
+            assert_eq!(_fetch_ids(&mut iterator), vec![_id2(), _id1()]);
+            assert_eq!(iterator.get_total_count().unwrap(), Some(3));
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_search_works_for_sort_by_id");
+    }
+
+    #[test]
+    fn wallet_search_works_for_sort_by_plain_tag() {
+        test::cleanup_wallet("wallet_search_works_for_sort_by_plain_tag");
+        {
+            let mut wallet = _wallet("wallet_search_works_for_sort_by_plain_tag");
+            wallet.add(_type1(), _id1(), _value1(), &jsonmap!({"~tag_name":"2"})).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &jsonmap!({"~tag_name":"3"})).unwrap();
+            wallet.add(_type1(), _id3(), _value3(), &jsonmap!({"~tag_name":"1"})).unwrap();
+
+            let options = jsonstr!({"sortBy": {"tagName": "~tag_name"}, "limit": 2});
+            let mut iterator = wallet.search(_type1(), "{}", Some(&options)).unwrap();
+
+            assert_eq!(_fetch_ids(&mut iterator), vec![_id3(), _id1()]);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_search_works_for_sort_by_plain_tag");
+    }
+
+    #[test]
+    fn wallet_search_works_for_sort_by_encrypted_tag() {
+        test::cleanup_wallet("wallet_search_works_for_sort_by_encrypted_tag");
+        {
+            let mut wallet = _wallet("wallet_search_works_for_sort_by_encrypted_tag");
+
+            let options = jsonstr!({"sortBy": {"tagName": "tag_name"}});
+            let res = wallet.search(_type1(), "{}", Some(&options));
+
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_search_works_for_sort_by_encrypted_tag");
+    }
+
+    #[test]
+    fn wallet_rekey_works() {
+        test::cleanup_wallet("wallet_rekey_works");
+        {
+            let mut wallet = _wallet("wallet_rekey_works");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type2(), _id2(), _value2(), &jsonmap!({"~tag_name":"1"})).unwrap();
+
+            let reported = ::std::cell::RefCell::new(Vec::new());
+            wallet.rekey(Rc::new(Keys::new()), &|processed, total| reported.borrow_mut().push((processed, total))).unwrap();
+
+            assert_eq!(reported.into_inner(), vec![(0, 2), (1, 2), (2, 2)]);
+
+            let record = wallet.get(_type1(), _id1(), &_fetch_options(false, true, true)).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(record.tags.unwrap(), _tags());
+
+            let mut iterator = wallet.search(_type2(), r#"{"~tag_name": "1"}"#, None).unwrap();
+            assert_eq!(_fetch_ids(&mut iterator), vec![_id2()]);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_rekey_works");
+    }
+
+    #[test]
+    fn wallet_rekey_works_for_interrupted() {
+        test::cleanup_wallet("wallet_rekey_works_for_interrupted");
+        {
+            let mut wallet = _wallet("wallet_rekey_works_for_interrupted");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &_tags()).unwrap();
+
+            let next_keys = Rc::new(Keys::new());
+
+            // simulate a crash after the first record was re-encrypted
+            let record = wallet.storage.get_all().unwrap().next().unwrap().unwrap();
+            wallet._rekey_record(&record, &next_keys).unwrap();
+
+            wallet.rekey(next_keys, &|_, _| {}).unwrap();
+
+            let mut iterator = wallet.search(_type1(), "{}", None).unwrap();
+            let mut ids = _fetch_ids(&mut iterator);
+            ids.sort();
+            assert_eq!(ids, vec![_id1(), _id2()]);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_rekey_works_for_interrupted");
+    }
+
+    #[test]
+    fn wallet_check_works() {
+        test::cleanup_wallet("wallet_check_works");
+        {
+            let mut wallet = _wallet("wallet_check_works");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &_tags()).unwrap();
+
+            let (total, broken) = wallet.check(None).unwrap();
+            assert_eq!(total, 2);
+            assert!(broken.is_empty());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_check_works");
+    }
+
+    #[test]
+    fn wallet_check_works_for_broken_value() {
+        test::cleanup_wallet("wallet_check_works_for_broken_value");
+        {
+            let mut wallet = _wallet("wallet_check_works_for_broken_value");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &_tags()).unwrap();
+
+            _break_value(&wallet, _type1(), _id2());
+
+            let (total, broken) = wallet.check(None).unwrap();
+            assert_eq!(total, 2);
+            assert_eq!(broken.len(), 1);
+            assert_eq!(broken[0].type_, Some(_type1().to_string()));
+            assert_eq!(broken[0].id, Some(_id2().to_string()));
+            assert_eq!(broken[0].reason, "Record value can't be decrypted");
+            assert!(!broken[0].quarantined);
+
+            // Broken record is still in place
+            wallet.get(_type1(), _id2(), &_fetch_options(false, false, false)).unwrap();
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_check_works_for_broken_value");
+    }
+
+    #[test]
+    fn wallet_check_works_for_quarantine() {
+        test::cleanup_wallet("wallet_check_works_for_quarantine");
+        {
+            let mut wallet = _wallet("wallet_check_works_for_quarantine");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add(_type1(), _id2(), _value2(), &_tags()).unwrap();
+
+            _break_value(&wallet, _type1(), _id2());
+
+            let (_, broken) = wallet.check(Some(_quarantine_type())).unwrap();
+            assert_eq!(broken.len(), 1);
+            assert!(broken[0].quarantined);
+
+            let res = wallet.get(_type1(), _id2(), &_fetch_options(false, false, false));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let mut iterator = wallet.search(_quarantine_type(), &format!(r#"{{"id": "{}"}}"#, _id2()), Some(&_search_options(true, false, false, true, true))).unwrap();
+            let record = iterator.next().unwrap().unwrap();
+            assert_eq!(record.tags.unwrap()["reason"], "Record value can't be decrypted");
+            assert!(iterator.next().unwrap().is_none());
+
+            let (total, broken) = wallet.check(None).unwrap();
+            assert_eq!(total, 2);
+            assert!(broken.is_empty());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_check_works_for_quarantine");
+    }
+
+    fn _break_value(wallet: &Wallet, type_: &str, id: &str) {
+        let etype = encrypt_as_searchable(type_.as_bytes(), &wallet.keys.type_key, &wallet.keys.item_hmac_key);
+        let eid = encrypt_as_searchable(id.as_bytes(), &wallet.keys.name_key, &wallet.keys.item_hmac_key);
+        let evalue = EncryptedValue::encrypt("other", &chacha20poly1305_ietf::gen_key());
+        wallet.storage.update(&etype, &eid, &evalue).unwrap();
+    }
+
+    fn _quarantine_type() -> &'static str {
+        "quarantine"
+    }
+
     fn _type1() -> &'static str {
         "type1"
     }
@@ -2000,6 +2494,7 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                next_keys: None,
             });
 
             serde_json::to_vec(&metadata).unwrap()
@@ -2080,6 +2575,16 @@ mod tests {
         _sort(v)
     }
 
+    fn _fetch_ids(iterator: &mut WalletIterator) -> Vec<String> {
+        let mut v = Vec::new();
+
+        while let Some(record) = iterator.next().unwrap() {
+            v.push(record.id);
+        }
+
+        v
+    }
+
     fn _sort(mut v: Vec<WalletRecord>) -> Vec<WalletRecord> {
         v.sort();
         v
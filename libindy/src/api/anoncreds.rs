@@ -11,9 +11,11 @@ use crate::domain::anoncreds::credential_definition::{CredentialDefinition, Cred
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialValues};
+use crate::domain::anoncreds::credential::{Credential, CredentialValues, RawCredentialValues};
+use crate::domain::anoncreds::credential_selection::CredentialSelectionPolicy;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::revocation_registry_manager::RevocationRegistryManagerConfig;
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
@@ -711,6 +713,339 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
     res
 }
 
+/// Create a revocation registry manager for the credential definition and its first (active) revocation registry.
+///
+/// The manager keeps a pool of revocation registries for the credential definition.
+/// `indy_issuer_create_credential_with_revoc_reg_manager` selects the active registry automatically,
+/// switches to the next one when the active registry is full and creates the next registry in advance
+/// once usage of the active registry crosses the rollover threshold.
+///
+/// Registries created by the manager are stored in the wallet the same way as `indy_issuer_create_and_store_revoc_reg` does.
+/// Their definitions and initial entries still must be published to the ledger by the issuer
+/// (see `indy_issuer_get_revoc_reg_manager_state`).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// issuer_did: a DID of the issuer
+/// cred_def_id: id of stored in ledger credential definition
+/// config_json: (optional) manager configuration as json:
+/// {
+///     "issuance_type": (optional) type of issuance of managed registries (see `indy_issuer_create_and_store_revoc_reg`),
+///     "max_cred_num": (optional) maximum number of credentials each managed registry can process (default 100000),
+///     "tag_prefix": (optional) prefix of managed registry tags, the registry number is appended to it (default "rev_reg_"),
+///     "rollover_threshold": (optional) part of the active registry capacity to be used before
+///                           the next registry is created, in (0, 1] range (default 0.8)
+/// }
+/// tails_writer_handle: handle of blob storage to store tails (returned by `indy_open_blob_storage_writer`).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_id: identifier of created revocation registry definition
+/// revoc_reg_def_json: public part of revocation registry definition (see `indy_issuer_create_and_store_revoc_reg`)
+/// revoc_reg_entry_json: revocation registry entry that defines initial state of revocation registry
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_create_revoc_reg_manager(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   issuer_did: *const c_char,
+                                                   cred_def_id: *const c_char,
+                                                   config_json: *const c_char,
+                                                   tails_writer_handle: IndyHandle,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                        revoc_reg_id: *const c_char,
+                                                                        revoc_reg_def_json: *const c_char,
+                                                                        revoc_reg_entry_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_revoc_reg_manager: >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config_json: {:?}, tails_writer_handle: {:?}",
+           wallet_handle, issuer_did, cred_def_id, config_json, tails_writer_handle);
+
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam4, CredentialDefinitionId);
+    check_useful_opt_validatable_json!(config_json, ErrorCode::CommonInvalidParam5, RevocationRegistryManagerConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_issuer_create_revoc_reg_manager: entities >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config_json: {:?}, tails_writer_handle: {:?}",
+           wallet_handle, issuer_did, cred_def_id, config_json, tails_writer_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateRevocationRegistryManager(
+                    wallet_handle,
+                    issuer_did,
+                    cred_def_id,
+                    config_json.unwrap_or_default(),
+                    tails_writer_handle,
+                    Box::new(move |result| {
+                        let (err, revoc_reg_id, revoc_reg_def_json, revoc_reg_json) = prepare_result_3!(result, String::new(), String::new(), String::new());
+                        trace!("indy_issuer_create_revoc_reg_manager: revoc_reg_id: {:?}, revoc_reg_def_json: {:?}, revoc_reg_json: {:?}",
+                               revoc_reg_id, revoc_reg_def_json, revoc_reg_json);
+                        let revoc_reg_id = ctypes::string_to_cstring(revoc_reg_id);
+                        let revoc_reg_def_json = ctypes::string_to_cstring(revoc_reg_def_json);
+                        let revoc_reg_json = ctypes::string_to_cstring(revoc_reg_json);
+                        cb(command_handle, err, revoc_reg_id.as_ptr(), revoc_reg_def_json.as_ptr(), revoc_reg_json.as_ptr())
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_revoc_reg_manager: <<< res: {:?}", res);
+
+    res
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request
+/// using the active revocation registry of the revocation registry manager (created by `indy_issuer_create_revoc_reg_manager`)
+/// for the credential definition of the Cred Offer.
+///
+/// If the active registry is full the next registry marked as published (see `indy_issuer_mark_revoc_reg_published`)
+/// becomes active. If there is none, the call fails with AnoncredsRevocationRegistryFullError and the next registry
+/// is created as pending (if not created yet): it has to be published and marked as published before the call is retried.
+/// Once usage of the active registry crosses the rollover threshold, the next registry is created in advance as pending.
+/// Use `indy_issuer_get_revoc_reg_manager_state` to get pending registries which have to be published to the ledger.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_offer_json: a cred offer created by indy_issuer_create_credential_offer
+/// cred_req_json: a credential request created by indy_prover_create_credential_req
+/// cred_values_json: a credential containing attribute values for each of requested attribute names (see `indy_issuer_create_credential`)
+/// tails_writer_handle: handle of blob storage to store tails of new registries (returned by `indy_open_blob_storage_writer`).
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_json: Credential json containing signed credential values (see `indy_issuer_create_credential`)
+/// cred_revoc_id: local id for revocation info (Can be used for revocation of this credential)
+/// revoc_reg_delta_json: Revocation registry delta json with a newly issued credential
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credential_with_revoc_reg_manager(command_handle: CommandHandle,
+                                                                   wallet_handle: WalletHandle,
+                                                                   cred_offer_json: *const c_char,
+                                                                   cred_req_json: *const c_char,
+                                                                   cred_values_json: *const c_char,
+                                                                   tails_writer_handle: IndyHandle,
+                                                                   blob_storage_reader_handle: IndyHandle,
+                                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                                        cred_json: *const c_char,
+                                                                                        cred_revoc_id: *const c_char,
+                                                                                        revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credential_with_revoc_reg_manager: >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, \
+    tails_writer_handle: {:?}, blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, cred_req_json, cred_values_json, tails_writer_handle, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
+    check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, CredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_issuer_create_credential_with_revoc_reg_manager: entities >>> wallet_handle: {:?}, cred_offer_json: {:?}, cred_req_json: {:?}, cred_values_json: {:?}, \
+    tails_writer_handle: {:?}, blob_storage_reader_handle: {:?}", wallet_handle, cred_offer_json, secret!(&cred_req_json), secret!(&cred_values_json), tails_writer_handle, blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentialWithRevocationRegistryManager(
+                    wallet_handle,
+                    cred_offer_json,
+                    cred_req_json,
+                    cred_values_json,
+                    tails_writer_handle,
+                    blob_storage_reader_handle,
+                    Box::new(move |result| {
+                        let (err, cred_json, revoc_id, revoc_reg_delta_json) = prepare_result_3!(result, String::new(), None, None);
+                        trace!("indy_issuer_create_credential_with_revoc_reg_manager: cred_json: {:?}, revoc_id: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(cred_json.as_str()), secret!(&revoc_id), revoc_reg_delta_json);
+                        let cred_json = ctypes::string_to_cstring(cred_json);
+                        let revoc_id = revoc_id.map(ctypes::string_to_cstring);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, cred_json.as_ptr(),
+                           revoc_id.as_ref().map(|id| id.as_ptr()).unwrap_or(ptr::null()),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credential_with_revoc_reg_manager: <<< res: {:?}", res);
+
+    res
+}
+
+/// Get state of revocation registries managed by the revocation registry manager
+/// (created by `indy_issuer_create_revoc_reg_manager`) for the credential definition.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_def_id: id of credential definition the manager is created for
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// registries_json: list of managed revocation registries in order of creation
+/// [{
+///     "rev_reg_id": string, - identifier of revocation registry
+///     "tag": string, - tag of revocation registry
+///     "status": string, - one of
+///         "pending" - created, but not published to the ledger yet,
+///         "ready" - marked as published (see `indy_issuer_mark_revoc_reg_published`) and not used yet,
+///         "active" - used for issuance,
+///         "full" - all indices are issued
+///     "max_cred_num": number, - maximum number of credentials the registry can process
+///     "issued": number, - number of issued credentials
+///     "revoked": number, - number of revoked credentials
+///     "rev_reg_def": (only for "pending" registries) revocation registry definition to be published to the ledger,
+///     "rev_reg": (only for "pending" registries) revocation registry entry that defines initial state of revocation registry
+/// }]
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_get_revoc_reg_manager_state(command_handle: CommandHandle,
+                                                      wallet_handle: WalletHandle,
+                                                      cred_def_id: *const c_char,
+                                                      cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                           registries_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_revoc_reg_manager_state: >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_revoc_reg_manager_state: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetRevocationRegistryManagerState(
+                    wallet_handle,
+                    cred_def_id,
+                    boxed_callback_string!("indy_issuer_get_revoc_reg_manager_state", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_revoc_reg_manager_state: <<< res: {:?}", res);
+
+    res
+}
+
+/// Mark pending revocation registry managed by the revocation registry manager as published to the ledger.
+///
+/// Only registries marked as published can become active for issuance by
+/// `indy_issuer_create_credential_with_revoc_reg_manager`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// rev_reg_id: id of pending revocation registry (see `indy_issuer_get_revoc_reg_manager_state`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_mark_revoc_reg_published(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   rev_reg_id: *const c_char,
+                                                   cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_mark_revoc_reg_published: >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_mark_revoc_reg_published: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::MarkRevocationRegistryPublished(
+                    wallet_handle,
+                    rev_reg_id,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_mark_revoc_reg_published: ");
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_mark_revoc_reg_published: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets credentials issued by the issuer with revocation support.
+///
+/// Issued credentials are tracked starting with this version of libindy.
+/// The status of a credential changes to "revoked" after `indy_issuer_revoke_credential` call.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// query_json: (optional) Wql query filter for issued credentials searching based on tags.
+///     {
+///         "cred_def_id": string, (Optional)
+///         "rev_reg_id": string, (Optional)
+///         "cred_rev_id": string, (Optional)
+///         "status": string, (Optional) "issued" or "revoked"
+///     }
+///     See query syntax in the docs for `indy_prover_search_credentials`.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// issued_credentials_json: list of issued credentials
+/// [{
+///     "cred_def_id": string, - identifier of credential definition
+///     "rev_reg_id": string, - identifier of revocation registry
+///     "cred_rev_id": string, - local id for revocation info
+///     "status": string, - "issued" or "revoked"
+/// }]
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_get_issued_credentials(command_handle: CommandHandle,
+                                                 wallet_handle: WalletHandle,
+                                                 query_json: *const c_char,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                      issued_credentials_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_issued_credentials: >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    check_useful_opt_c_str!(query_json, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_issued_credentials: entities >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetIssuedCredentials(
+                    wallet_handle,
+                    query_json,
+                    boxed_callback_string!("indy_issuer_get_issued_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_issued_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /*/// Recover a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -1512,6 +1847,14 @@ pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandl
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -1535,19 +1878,36 @@ pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandl
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see indy_encode_credential_values)
 ///     }
 /// predicate_referent: Proof-request local identifier of requested attribute predicate
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": int predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<filter_json>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// equality_referent: Proof-request local identifier of requested equality
+/// equality_info: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// membership_referent: Proof-request local identifier of requested membership
+/// membership_info: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// non_revoc_interval: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -1641,6 +2001,14 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -1663,19 +2031,36 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see indy_encode_credential_values)
 ///     }
 /// predicate_referent: Proof-request local identifier of requested attribute predicate
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// equality_referent: Proof-request local identifier of requested equality
+/// equality_info: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// membership_referent: Proof-request local identifier of requested membership
+/// membership_info: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// non_revoc_interval: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -1852,6 +2237,93 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
     res
 }
 
+/// Selects credentials from the wallet to satisfy the given proof request.
+///
+/// For each requested attribute and predicate the most suitable credential is chosen according to the selection policy.
+/// The result can be passed to indy_prover_create_proof as requested_credentials_json
+/// after the listed revocation states are built (see indy_create_revocation_state).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json (see indy_prover_search_credentials_for_proof_req)
+/// policy_json: (Optional) selection policy json. Preferences are applied in the listed order:
+///     {
+///         "prefer_issuers": Optional<[string]>, // issuer DIDs in order of preference
+///         "prefer_non_revocable": Optional<bool>, // (false by default) prefer credentials which can't be revoked
+///         "newest": Optional<bool>, // (false by default) prefer the most recently stored credentials
+///         "minimize_credentials": Optional<bool>, // (false by default) use as few different credentials as possible
+///         "self_attested_attributes": Optional<{"attr_referent": string}>, // values for requested attributes
+///                                     // without restrictions which have no matching credential in the wallet
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// requested_credentials_json: requested credentials json (see indy_prover_create_proof).
+///     All selected attributes are marked as revealed.
+///     {
+///         "self_attested_attributes": {"self_attested_attribute_referent": string},
+///         "requested_attributes": {
+///             "requested_attribute_referent": {"cred_id": string, "timestamp": Optional<number>, revealed: true},
+///         },
+///         "requested_predicates": {
+///             "requested_predicates_referent": {"cred_id": string, "timestamp": Optional<number>},
+///         }
+///     }
+/// rev_states_json: revocation states required to create the proof.
+///     [{
+///         "rev_reg_id": string, // identifier of revocation registry definition
+///         "cred_rev_id": string, // identifier of credential in the revocation registry
+///         "timestamp": int, // timestamp the revocation state must be built for
+///     }]
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+/// WalletItemNotFound - a requested attribute or predicate can't be satisfied by the wallet credentials
+#[no_mangle]
+pub extern fn indy_prover_select_credentials_for_proof_req(command_handle: CommandHandle,
+                                                           wallet_handle: WalletHandle,
+                                                           proof_request_json: *const c_char,
+                                                           policy_json: *const c_char,
+                                                           cb: Option<extern fn(
+                                                               command_handle_: CommandHandle, err: ErrorCode,
+                                                               requested_credentials_json: *const c_char,
+                                                               rev_states_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_select_credentials_for_proof_req: >>> wallet_handle: {:?}, proof_request_json: {:?}, policy_json: {:?}",
+           wallet_handle, proof_request_json, policy_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_opt_validatable_json!(policy_json, ErrorCode::CommonInvalidParam4, CredentialSelectionPolicy);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_select_credentials_for_proof_req: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, policy_json: {:?}",
+           wallet_handle, proof_request_json, policy_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Prover(
+                ProverCommand::SelectCredentialsForProofReq(
+                    wallet_handle,
+                    proof_request_json,
+                    policy_json,
+                    Box::new(move |result| {
+                        let (err, requested_credentials_json, rev_states_json) = prepare_result_2!(result, String::new(), String::new());
+                        trace!("indy_prover_select_credentials_for_proof_req: requested_credentials_json: {:?}, rev_states_json: {:?}", requested_credentials_json, rev_states_json);
+                        let requested_credentials_json = ctypes::string_to_cstring(requested_credentials_json);
+                        let rev_states_json = ctypes::string_to_cstring(rev_states_json);
+                        cb(command_handle, err, requested_credentials_json.as_ptr(), rev_states_json.as_ptr())
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_select_credentials_for_proof_req: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates a proof according to the given proof request
 /// Either a corresponding credential with optionally revealed attributes or self-attested attribute must be provided
 /// for each requested attribute (see indy_prover_get_credentials_for_pool_req).
@@ -1876,6 +2348,14 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -1940,19 +2420,36 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see indy_encode_credential_values)
 ///     }
 /// predicate_referent: Proof-request local identifier of requested attribute predicate
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// equality_referent: Proof-request local identifier of requested equality
+/// equality_info: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// membership_referent: Proof-request local identifier of requested membership
+/// membership_info: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// non_revoc_interval: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -2085,6 +2582,14 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -2172,19 +2677,36 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see indy_encode_credential_values)
 ///     }
 /// predicate_referent: Proof-request local identifier of requested attribute predicate
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (">=", ">", "<=", "<")
-///         "p_value": predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// equality_referent: Proof-request local identifier of requested equality
+/// equality_info: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// membership_referent: Proof-request local identifier of requested membership
+/// membership_info: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// non_revoc_interval: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -2259,6 +2781,114 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     res
 }
 
+/// Verifies a proof (of multiple credential) and reports result of each performed check.
+///
+/// Unlike indy_verifier_verify_proof this function doesn't stop on the first failed check,
+/// so it can be used to find out why a proof is rejected.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json (see indy_verifier_verify_proof)
+/// proof_json: created for request proof json (see indy_verifier_verify_proof)
+/// schemas_json: all schemas participating in the proof (see indy_verifier_verify_proof)
+/// credential_defs_json: all credential definitions participating in the proof (see indy_verifier_verify_proof)
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof (see indy_verifier_verify_proof)
+/// rev_regs_json: all revocation registries participating in the proof (see indy_verifier_verify_proof)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: verification report
+///     {
+///         "valid": bool, // true if all checks are passed (the same as indy_verifier_verify_proof result)
+///         "structure": <check_result>, // received referents correspond to requested ones
+///         "attributes": {
+///             "<attr_referent>": <referent_report>,
+///         },
+///         "predicates": {
+///             "<predicate_referent>": <referent_report>,
+///         },
+///         "equalities": Optional<{
+///             "<equality_referent>": <check_result>,
+///         }>,
+///         "memberships": Optional<{
+///             "<membership_referent>": <check_result>,
+///         }>,
+///         "sub_proofs": [{
+///             "schema_id": string,
+///             "cred_def_id": string,
+///             "rev_reg_id": Optional<string>,
+///             "timestamp": Optional<int>, // timestamp of revocation registry used for non-revocation proof
+///             "resolved": <check_result>, // schema, credential definition and revocation registry are provided
+///         }],
+///         "crypto_proof": Optional<<check_result>>, // primary and non-revocation proofs of all sub proofs
+///                                                   // not set if some sub proof isn't resolved
+///     }
+/// referent_report:
+///     {
+///         "status": "revealed" | "unrevealed" | "self_attested" | "proven" | "missing",
+///         "sub_proof_index": Optional<int>,
+///         "value": Optional<<check_result>>, // revealed value corresponds to crypto proof and requested encoding
+///         "restrictions": Optional<<check_result>>, // requested restrictions are satisfied
+///         "non_revoked": Optional<<check_result>>, // timestamp is provided for requested non-revocation interval
+///     }
+///     Checks that aren't applicable for referent are omitted.
+/// check_result:
+///     {
+///         "valid": bool,
+///         "error": Optional<string>, // description of the failure
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                     proof_request_json: *const c_char,
+                                                     proof_json: *const c_char,
+                                                     schemas_json: *const c_char,
+                                                     credential_defs_json: *const c_char,
+                                                     rev_reg_defs_json: *const c_char,
+                                                     rev_regs_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_report: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_with_report: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithReport(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            Box::new(move |result| {
+                let (err, report_json) = prepare_result_1!(result, String::new());
+                trace!("indy_verifier_verify_proof_with_report: report_json: {:?}", report_json);
+
+                let report_json = ctypes::string_to_cstring(report_json);
+                cb(command_handle, err, report_json.as_ptr())
+            })
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_report: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
 /// Note that revocation delta must cover the whole registry existence time.
@@ -2486,3 +3116,64 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     res
 }
 
+
+/// Encode raw credential attribute values into the format expected by indy_issuer_create_credential.
+///
+/// Values are encoded by standardized schemes, so Verifier is able to check encoding of revealed
+/// attributes (see "encoding" of attr_info in indy_verifier_verify_proof) and build predicates
+/// over dates and decimal numbers.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// cred_values_json: raw attribute values with optional encoding scheme for each attribute.
+///     {
+///         "<attr_name>": <raw value as string> | { // "default" encoding is used for plain string
+///             "raw": string,
+///             "encoding": Optional<"default" | "date" | {"decimal": int}> // "default" if not specified
+///         },
+///     }
+///     Encoding schemes:
+///         "default" - 32-bit integer is encoded as itself, any other value as decimal representation of its SHA-256 hash.
+///         "date" - date in "YYYY-MM-DD" format is encoded as number of days since 1970-01-01 (can be negative).
+///         {"decimal": N} - decimal number with at most N fractional digits is encoded as integer number of 10^-N units.
+///             Encoded value must fit 32-bit signed integer range to be usable in predicates.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// encoded_cred_values_json: credential values in the format of cred_values_json of indy_issuer_create_credential.
+///     {
+///         "<attr_name>": {"raw": string, "encoded": string},
+///     }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_encode_credential_values(command_handle: CommandHandle,
+                                            cred_values_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 encoded_cred_values_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_encode_credential_values: >>> cred_values_json: {:?}", cred_values_json);
+
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam2, RawCredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_encode_credential_values: entities >>> cred_values_json: {:?}", cred_values_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::EncodeCredentialValues(
+            cred_values_json,
+            Box::new(move |result| {
+                let (err, encoded_cred_values_json) = prepare_result_1!(result, String::new());
+                trace!("indy_encode_credential_values: encoded_cred_values_json: {:?}", encoded_cred_values_json);
+                let encoded_cred_values_json = ctypes::string_to_cstring(encoded_cred_values_json);
+                cb(command_handle, err, encoded_cred_values_json.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_encode_credential_values: <<< res: {:?}", res);
+
+    res
+}
@@ -6,6 +6,7 @@ use indy_utils::ctypes;
 use crate::domain::cache::{GetCacheOptions, PurgeOptions};
 use crate::domain::anoncreds::schema::SchemaId;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
 use crate::domain::crypto::did::DidValue;
 use indy_api_types::validation::Validatable;
 use libc::c_char;
@@ -213,3 +214,461 @@ pub extern fn indy_purge_schema_cache(command_handle: CommandHandle,
 
     res
 }
+
+/// Gets NYM data for specified DID.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// target_did: DID to get NYM data for.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// NYM data json (the same as `indy_parse_get_nym_response` returns):
+/// {
+///     did: DID as base58-encoded string for 16 or 32 bit DID value.
+///     verkey: verification key as base58-encoded string.
+///     role: Role associated number
+/// }
+#[no_mangle]
+pub extern fn indy_get_nym(command_handle: CommandHandle,
+                           pool_handle: PoolHandle,
+                           wallet_handle: WalletHandle,
+                           submitter_did: *const c_char,
+                           target_did: *const c_char,
+                           options_json: *const c_char,
+                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                err: ErrorCode,
+                                                nym_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_nym: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, target_did, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(target_did, ErrorCode::CommonInvalidParam5, DidValue);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam6, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_get_nym: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, target_did, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::GetNym(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            target_did,
+            options_json,
+            boxed_callback_string!("indy_get_nym", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_nym: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets ATTRIB data for specified DID and attribute.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// target_did: Target DID as base58-encoded string for 16 or 32 bit DID value.
+/// raw: (Optional) Requested attribute name.
+/// hash: (Optional) Requested attribute hash.
+/// enc: (Optional) Requested attribute encrypted value.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Attribute data as stored on the ledger (for example `{"endpoint":{"ha":"127.0.0.1:5555"}}` for raw attribute).
+#[no_mangle]
+pub extern fn indy_get_attrib(command_handle: CommandHandle,
+                              pool_handle: PoolHandle,
+                              wallet_handle: WalletHandle,
+                              submitter_did: *const c_char,
+                              target_did: *const c_char,
+                              raw: *const c_char,
+                              hash: *const c_char,
+                              enc: *const c_char,
+                              options_json: *const c_char,
+                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                   err: ErrorCode,
+                                                   attrib_data: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_attrib: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}, raw: {:?}, hash: {:?}, enc: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, target_did, raw, hash, enc, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(target_did, ErrorCode::CommonInvalidParam5, DidValue);
+    check_useful_opt_c_str!(raw, ErrorCode::CommonInvalidParam6);
+    check_useful_opt_c_str!(hash, ErrorCode::CommonInvalidParam7);
+    check_useful_opt_c_str!(enc, ErrorCode::CommonInvalidParam8);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam9, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam10);
+
+    trace!("indy_get_attrib: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}, raw: {:?}, hash: {:?}, enc: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, target_did, raw, hash, enc, options_json);
+
+    if raw.is_none() && hash.is_none() && enc.is_none() {
+        return IndyError::from_msg(IndyErrorKind::InvalidStructure, "Either raw or hash or enc must be specified").into();
+    }
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::GetAttrib(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            target_did,
+            raw,
+            hash,
+            enc,
+            options_json,
+            boxed_callback_string!("indy_get_attrib", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_attrib: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets revocation registry definition json data for specified revocation registry definition id.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// id: identifier of revocation registry definition.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_get_revoc_reg_def(command_handle: CommandHandle,
+                                     pool_handle: PoolHandle,
+                                     wallet_handle: WalletHandle,
+                                     submitter_did: *const c_char,
+                                     id: *const c_char,
+                                     options_json: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          revoc_reg_def_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_revoc_reg_def: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, id, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(id, ErrorCode::CommonInvalidParam5, RevocationRegistryId);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam6, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_get_revoc_reg_def: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, id, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::GetRevocRegDef(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            id,
+            options_json,
+            boxed_callback_string!("indy_get_revoc_reg_def", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_revoc_reg_def: <<< res: {:?}", res);
+
+    res
+}
+
+/// Gets revocation registry delta for specified revocation registry definition id and interval.
+/// Deltas are cached per requested interval.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// revoc_reg_def_id: ID of the corresponding Revocation Registry Definition in ledger.
+/// from: Requested time represented as a total number of seconds from Unix Epoch (-1 to get the whole state till `to`).
+/// to: Requested time represented as a total number of seconds from Unix Epoch.
+/// options_json:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Revocation Registry Delta json (the same as `indy_parse_get_revoc_reg_delta_response` returns) and
+/// timestamp of the delta on the ledger.
+#[no_mangle]
+pub extern fn indy_get_revoc_reg_delta(command_handle: CommandHandle,
+                                       pool_handle: PoolHandle,
+                                       wallet_handle: WalletHandle,
+                                       submitter_did: *const c_char,
+                                       revoc_reg_def_id: *const c_char,
+                                       from: i64,
+                                       to: i64,
+                                       options_json: *const c_char,
+                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                            err: ErrorCode,
+                                                            revoc_reg_delta_json: *const c_char,
+                                                            timestamp: u64)>) -> ErrorCode {
+    trace!("indy_get_revoc_reg_delta: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, revoc_reg_def_id: {:?}, from: {:?}, to: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, revoc_reg_def_id, from, to, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(revoc_reg_def_id, ErrorCode::CommonInvalidParam5, RevocationRegistryId);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam8, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam9);
+
+    let from = if from != -1 { Some(from) } else { None };
+
+    trace!("indy_get_revoc_reg_delta: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, revoc_reg_def_id: {:?}, from: {:?}, to: {:?}, options_json: {:?}",
+           pool_handle, wallet_handle, submitter_did, revoc_reg_def_id, from, to, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::GetRevocRegDelta(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            revoc_reg_def_id,
+            from,
+            to,
+            options_json,
+            Box::new(move |result| {
+                let (err, revoc_reg_delta_json, timestamp) = prepare_result_2!(result, String::new(), 0);
+                trace!("indy_get_revoc_reg_delta: revoc_reg_delta_json: {:?}, timestamp: {:?}", revoc_reg_delta_json, timestamp);
+                let revoc_reg_delta_json = ctypes::string_to_cstring(revoc_reg_delta_json);
+                cb(command_handle, err, revoc_reg_delta_json.as_ptr(), timestamp)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_revoc_reg_delta: <<< res: {:?}", res);
+
+    res
+}
+
+/// Purge NYM cache.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_nym_cache(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   options_json: *const c_char,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_nym_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_nym_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::PurgeNymCache(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_nym_cache:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_nym_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Purge ATTRIB cache.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_attrib_cache(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      options_json: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_attrib_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_attrib_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::PurgeAttribCache(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_attrib_cache:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_attrib_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Purge revocation registry definition cache.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_revoc_reg_def_cache(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             options_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_revoc_reg_def_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_revoc_reg_def_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::PurgeRevocRegDefCache(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_revoc_reg_def_cache:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_revoc_reg_def_cache: <<< res: {:?}", res);
+
+    res
+}
+
+/// Purge revocation registry delta cache.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// options_json:
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+/// cb: Callback that takes command result as parameter.
+#[no_mangle]
+pub extern fn indy_purge_revoc_reg_delta_cache(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               options_json: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_purge_revoc_reg_delta_cache: >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam3, PurgeOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_revoc_reg_delta_cache: entities >>> wallet_handle: {:?}, options_json: {:?}",
+           wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Cache(CacheCommand::PurgeRevocRegDeltaCache(
+            wallet_handle,
+            options_json,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_purge_revoc_reg_delta_cache:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_revoc_reg_delta_cache: <<< res: {:?}", res);
+
+    res
+}
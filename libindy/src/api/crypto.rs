@@ -1,8 +1,9 @@
 
 use indy_api_types::{ErrorCode, CommandHandle, WalletHandle};
+use indy_api_types::key_manager::*;
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::crypto::CryptoCommand;
-use crate::domain::crypto::pack::JWE;
+use crate::domain::crypto::pack::{JWE, JWEV2, PackOptionsV2};
 use crate::domain::crypto::key::KeyInfo;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -20,7 +21,9 @@ use libc::c_char;
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and 'p256'.
+///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+///               to create the key in. Secret key never enters the wallet in this case.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -763,3 +766,239 @@ pub extern fn indy_unpack_message(
 
     res
 }
+
+/// Packs a message into DIDComm v2 encrypted envelope (JWE in General JSON Serialization).
+///
+/// Authcrypt (ECDH-1PU+A256KW) is used if sender key id is passed, otherwise anoncrypt (ECDH-ES+A256KW).
+/// Key agreement is done over X25519 keys derived from ed25519 keys stored in the wallet.
+///
+/// Key id (kid) is either a verkey or a DID URL (`<did>#<fragment>`) of my or their DID stored in the wallet.
+/// The DID URL is resolved to the verkey of the DID. Its fragment must identify this key:
+/// `key-1`, multibase key fingerprint (as in `did:key`) or the verkey itself.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// message: a pointer to the first byte of the message to be packed
+/// message_len: the length of the message
+/// receiver_kids: json list of receiver's key ids the message is being encrypted for.
+///     Example: "[<receiver 1 kid>, <receiver 2 kid>]"
+/// sender_kid: (optional) the sender's key id. Key must be stored in the wallet.
+///     When null pointer is used in this parameter, anoncrypt is used.
+/// options_json: (optional) pack options as json:
+/// {
+///     "enc": string, (optional) content encryption algorithm: "XC20P" (default) or "A256GCM"
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// JWE:
+/// {
+///     "protected": b64URLencoded({
+///         "typ": "application/didcomm-encrypted+json",
+///         "alg": "ECDH-1PU+A256KW" | "ECDH-ES+A256KW",
+///         "enc": "XC20P" | "A256GCM",
+///         "epk": {"kty": "OKP", "crv": "X25519", "x": b64URLencode(ephemeral public key)},
+///         "skid": <sender kid>, (authcrypt only)
+///         "apu": b64URLencode(<sender kid>), (authcrypt only)
+///         "apv": b64URLencode(sha256(<sorted receiver kids joined with '.'>))
+///     }),
+///     "recipients": [
+///         {
+///             "header": {"kid": <receiver kid>},
+///             "encrypted_key": b64URLencode(A256KW(kek, cek))
+///         },
+///     ],
+///     "iv": b64URLencode(iv),
+///     "ciphertext": b64URLencode(ciphertext),
+///     "tag": b64URLencode(tag)
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_pack_message_v2(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    message: *const u8,
+    message_len: u32,
+    receiver_kids: *const c_char,
+    sender_kid: *const c_char,
+    options_json: *const c_char,
+    cb: Option<extern fn(xcommand_handle: CommandHandle, err: ErrorCode, jwe_data: *const u8, jwe_len: u32)>,
+) -> ErrorCode {
+    trace!("indy_pack_message_v2: >>> wallet_handle: {:?}, message: {:?}, message_len {:?}, \
+            receiver_kids: {:?}, sender_kid: {:?}, options_json: {:?}", wallet_handle, message, message_len, receiver_kids, sender_kid, options_json);
+
+    check_useful_c_byte_array!(message, message_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_json!(receiver_kids, ErrorCode::CommonInvalidParam4, Vec<String>);
+    check_useful_opt_c_str!(sender_kid, ErrorCode::CommonInvalidParam5);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam6, PackOptionsV2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_pack_message_v2: entities >>> wallet_handle: {:?}, message: {:?}, message_len {:?}, \
+            receiver_kids: {:?}, sender_kid: {:?}, options_json: {:?}", wallet_handle, message, message_len, receiver_kids, sender_kid, options_json);
+
+    if receiver_kids.is_empty() {
+        return IndyError::from_msg(IndyErrorKind::InvalidParam(4), "Empty receiver key ids has been passed").into();
+    }
+
+    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::PackMessageV2(
+        message,
+        receiver_kids,
+        sender_kid,
+        options_json.unwrap_or_default(),
+        wallet_handle,
+        Box::new(move |result| {
+            let (err, jwe) = prepare_result_1!(result, Vec::new());
+            trace!("indy_pack_message_v2: jwe: {:?}", jwe);
+            let (jwe_data, jwe_len) = ctypes::vec_to_pointer(&jwe);
+            cb(command_handle, err, jwe_data, jwe_len)
+        }),
+    )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_pack_message_v2: <<< res: {:?}", res);
+
+    res
+}
+
+/// Unpacks DIDComm v2 encrypted envelope outputted by indy_pack_message_v2.
+///
+/// The first recipient whose key id resolves to a key stored in the wallet is used.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// jwe_data: a pointer to the first byte of the JWE to be unpacked
+/// jwe_len: the length of the JWE message in bytes
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// {
+///     message: <decrypted message>,
+///     recipient_kid: <recipient kid>,
+///     sender_kid: <sender kid> (authcrypt only)
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Crypto*
+#[no_mangle]
+pub extern fn indy_unpack_message_v2(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    jwe_data: *const u8,
+    jwe_len: u32,
+    cb: Option<extern fn(xcommand_handle: CommandHandle, err: ErrorCode, res_json_data: *const u8, res_json_len: u32)>,
+) -> ErrorCode {
+    trace!("indy_unpack_message_v2: >>> wallet_handle: {:?}, jwe_data: {:?}, jwe_len {:?}", wallet_handle, jwe_data, jwe_len);
+
+    check_useful_c_byte_array!(jwe_data, jwe_len, ErrorCode::CommonInvalidParam2, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_unpack_message_v2: entities >>> wallet_handle: {:?}, jwe_data: {:?}, jwe_len {:?}", wallet_handle, jwe_data, jwe_len);
+
+    let jwe: JWEV2 = match serde_json::from_slice(jwe_data.as_slice()) {
+        Ok(x) => x,
+        Err(_) => return IndyError::from_msg(IndyErrorKind::InvalidParam(3), "Invalid DIDComm v2 JWE has been passed").into()
+    };
+
+    let result = CommandExecutor::instance().send(Command::Crypto(CryptoCommand::UnpackMessageV2(
+        jwe,
+        wallet_handle,
+        Box::new(move |result| {
+            let (err, res_json) = prepare_result_1!(result, Vec::new());
+            trace!("indy_unpack_message_v2: cb command_handle: {:?}, err: {:?}, res_json: {:?}", command_handle, err, res_json);
+            let (res_json_data, res_json_len) = ctypes::vec_to_pointer(&res_json);
+            cb(command_handle, err, res_json_data, res_json_len)
+        }),
+    )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_unpack_message_v2: <<< res: {:?}", res);
+
+    res
+}
+
+/// Register external key manager (e.g. KMS or HSM) implementation.
+///
+/// Keys created with "key_manager" option of indy_create_key, indy_create_and_store_my_did
+/// and indy_replace_keys_start are held by the key manager: the wallet stores only verkey
+/// and key reference returned by create_key handler. Signing and decryption with such keys
+/// (indy_crypto_sign, indy_sign_request, indy_pack_message, indy_unpack_message and others)
+/// are delegated to the key manager handlers.
+///
+/// Note that handlers are called synchronously from libindy thread.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// name: Key manager name.
+/// create_key: Key creation handler
+/// sign: Signing handler
+/// crypto_box: Authenticated encryption handler
+/// crypto_box_open: Authenticated decryption handler
+/// crypto_box_seal_open: Anonymous decryption handler
+/// key_agreement: X25519 key agreement handler
+/// free: Handler that allows to de-allocate buffers returned by other handlers (called for every returned buffer, even if the handler failed)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_key_manager(command_handle: CommandHandle,
+                                        name: *const c_char,
+                                        create_key: Option<KeyManagerCreateKey>,
+                                        sign: Option<KeyManagerSign>,
+                                        crypto_box: Option<KeyManagerCryptoBox>,
+                                        crypto_box_open: Option<KeyManagerCryptoBoxOpen>,
+                                        crypto_box_seal_open: Option<KeyManagerCryptoBoxSealOpen>,
+                                        key_agreement: Option<KeyManagerKeyAgreement>,
+                                        free: Option<KeyManagerFree>,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_key_manager: >>> command_handle: {:?}, name: {:?}", command_handle, name);
+
+    check_useful_c_str!(name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(create_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(sign, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(crypto_box, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(crypto_box_open, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(crypto_box_seal_open, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(key_agreement, ErrorCode::CommonInvalidParam8);
+    check_useful_c_callback!(free, ErrorCode::CommonInvalidParam9);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam10);
+
+    trace!("indy_register_key_manager: entities >>> name: {:?}", name);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Crypto(CryptoCommand::RegisterKeyManager(
+            name,
+            create_key,
+            sign,
+            crypto_box,
+            crypto_box_open,
+            crypto_box_seal_open,
+            key_agreement,
+            free,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_register_key_manager: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_key_manager: <<< res: {:?}", res);
+
+    res
+}
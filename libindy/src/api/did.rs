@@ -33,9 +33,20 @@ use crate::domain::ledger::attrib::Endpoint;
 ///     "seed": string, (optional) Seed that allows deterministic did creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values are 'ed25519', 'secp256k1' and 'p256';
+///               verkeys of non-ed25519 keys are suffixed with ':<crypto_type>')
+///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+///               to create the key in. Secret key never enters the wallet in this case.
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///               "key" creates self-certifying `did:key` identifier (Example: `did:key:z6Mk...`);
+///               "peer" creates `did:peer` identifier of numalgo 0 or numalgo 2 if "service" is set.
+///     "service": object, (optional; only for "peer" method) DIDComm service embedded into did:peer:
+///         {
+///             "serviceEndpoint": string,
+///             "routingKeys": [string], (optional)
+///             "accept": [string], (optional)
+///         }
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -98,7 +109,10 @@ pub  extern fn indy_create_and_store_my_did(command_handle: CommandHandle,
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values are 'ed25519', 'secp256k1' and 'p256';
+///               verkeys of non-ed25519 keys are suffixed with ':<crypto_type>')
+///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+///               to create the key in. Secret key never enters the wallet in this case.
 /// }
 /// cb: Callback that takes command result as parameter.
 ///
@@ -264,6 +278,9 @@ pub  extern fn indy_store_their_did(command_handle: CommandHandle,
 /// Note that "indy_create_and_store_my_did" makes similar wallet record as "indy_create_key".
 /// As result we can use returned ver key in all generic crypto and messaging functions.
 ///
+/// Keys of self-certifying `did:key` and `did:peer` DIDs are resolved from the DID itself
+/// without wallet or ledger lookup.
+///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// pool_handle:   Pool handle (created by open_pool).
@@ -323,6 +340,9 @@ pub extern fn indy_key_for_did(command_handle: CommandHandle,
 /// Note that "indy_create_and_store_my_did" makes similar wallet record as "indy_create_key".
 /// As result we can use returned ver key in all generic crypto and messaging functions.
 ///
+/// Keys of self-certifying `did:key` and `did:peer` DIDs are resolved from the DID itself
+/// without wallet or ledger lookup.
+///
 /// #Params
 /// command_handle: Command handle to map callback to caller context.
 /// wallet_handle: Wallet handle (created by open_wallet).
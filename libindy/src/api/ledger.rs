@@ -1,4 +1,4 @@
-use indy_api_types::{CommandHandle, ErrorCode, PoolHandle, WalletHandle};
+use indy_api_types::{CommandHandle, ErrorCode, PoolHandle, SearchHandle, WalletHandle, INVALID_SEARCH_HANDLE};
 use indy_api_types::errors::prelude::*;
 use indy_api_types::validation::Validatable;
 use indy_utils::ctypes;
@@ -14,8 +14,10 @@ use crate::domain::anoncreds::schema::{Schema, SchemaId};
 use crate::domain::crypto::did::DidValue;
 use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
 use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
+use crate::domain::ledger::did_indy::IndyDidUrl;
 use crate::domain::ledger::node::NodeOperationData;
 use crate::domain::ledger::pool::Schedule;
+use crate::domain::ledger::txn::DownloadLedgerTxnsOptions;
 
 /// Signs and submits request message to validator pool.
 ///
@@ -902,6 +904,328 @@ pub extern fn indy_parse_get_cred_def_response(command_handle: CommandHandle,
     res
 }
 
+/// Resolves fully qualified did:indy identifier of a ledger object.
+///
+/// The namespace of the identifier is used to select the opened pool to send request to
+/// (see `namespace` parameter of indy_create_pool_ledger_config).
+/// Then the object is fetched from the ledger and parsed.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// id: fully qualified identifier of the object:
+///     did:indy:<namespace>:<did> - DID
+///     did:indy:<namespace>:<did>/anoncreds/v0/SCHEMA/<name>/<version> - schema
+///     did:indy:<namespace>:<did>/anoncreds/v0/CLAIM_DEF/<schema seq no>/<tag> - credential definition
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// id: requested identifier.
+/// object_json: parsed object.
+///     for DID: {"did": string, "verkey": string (optional), "role": string (optional)}
+///     for schema: schema json (see indy_parse_get_schema_response)
+///     for credential definition: credential definition json (see indy_parse_get_cred_def_response)
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_resolve(command_handle: CommandHandle,
+                           id: *const c_char,
+                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                err: ErrorCode,
+                                                id: *const c_char,
+                                                object_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_resolve: >>> id: {:?}", id);
+
+    check_useful_validatable_string!(id, ErrorCode::CommonInvalidParam2, IndyDidUrl);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_resolve: entities >>> id: {:?}", id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::Resolve(
+            id,
+            Box::new(move |result| {
+                let (err, id, object_json) = prepare_result_2!(result, String::new(), String::new());
+                trace!("indy_resolve: id: {:?}, object_json: {:?}", id, object_json);
+                let id = ctypes::string_to_cstring(id);
+                let object_json = ctypes::string_to_cstring(object_json);
+                cb(command_handle, err, id.as_ptr(), object_json.as_ptr())
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_resolve: <<< res: {:?}", res);
+
+    res
+}
+
+/// Downloads a range of ledger transactions.
+///
+/// Transactions are fetched from the pool nodes in batches using catchup requests
+/// and every batch is verified against the merkle root agreed by f+1 nodes.
+/// Verified transactions are stored in the local append-only replica of the ledger,
+/// so subsequent calls only fetch transactions missing in the replica.
+/// Transactions aren't returned in the callback: they are appended to the file from `file` option
+/// batch by batch or can be read by batches with indy_open_ledger_txns_search.
+///
+/// Pool opened in offline mode serves transactions from the imported ledger snapshot
+/// (DOMAIN ledger) and local replicas (other ledgers).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// ledger_type: (Optional) type of the ledger to download transactions from:
+///     DOMAIN - used default,
+///     POOL,
+///     CONFIG
+///     any number
+/// from: sequence number of the first transaction to download (starting from 1).
+/// to: sequence number of the last transaction to download (inclusive), -1 to download up to the current ledger size.
+/// options_json: (Optional) download options:
+///     {
+///         file: (Optional) path to the file to append downloaded transactions to (one json per line).
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Download result json:
+///     {
+///         from: sequence number of the first downloaded transaction,
+///         to: sequence number of the last downloaded transaction,
+///         file: (Optional) path to the file transactions were appended to (if `file` option is set),
+///     }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_download_ledger_txns(command_handle: CommandHandle,
+                                        pool_handle: PoolHandle,
+                                        ledger_type: *const c_char,
+                                        from: i32,
+                                        to: i32,
+                                        options_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             txns_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_download_ledger_txns: >>> pool_handle: {:?}, ledger_type: {:?}, from: {:?}, to: {:?}, options_json: {:?}",
+           pool_handle, ledger_type, from, to, options_json);
+
+    check_useful_opt_c_str!(ledger_type, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam6, DownloadLedgerTxnsOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_download_ledger_txns: entities >>> pool_handle: {:?}, ledger_type: {:?}, from: {:?}, to: {:?}, options_json: {:?}",
+           pool_handle, ledger_type, from, to, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::DownloadLedgerTxns(
+            pool_handle,
+            ledger_type,
+            from,
+            to,
+            options_json.unwrap_or_default(),
+            boxed_callback_string!("indy_download_ledger_txns", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_download_ledger_txns: <<< res: {:?}", res);
+
+    res
+}
+
+/// Opens search over a range of ledger transactions.
+///
+/// The range is downloaded to the local replica of the ledger like in indy_download_ledger_txns:
+/// every catchup batch is verified against the merkle root agreed by f+1 nodes before appending.
+/// Instead of returning transactions this call returns search handle that can be used
+/// to read them by batches (with indy_fetch_ledger_txns_search_next_batch).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// ledger_type: (Optional) type of the ledger to read transactions from:
+///     DOMAIN - used default,
+///     POOL,
+///     CONFIG
+///     any number
+/// from: sequence number of the first transaction to read (starting from 1).
+/// to: sequence number of the last transaction to read (inclusive), -1 to read up to the current ledger size.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// search_handle: Ledger transactions search handle that can be used later
+///   to fetch transactions by batches (with indy_fetch_ledger_txns_search_next_batch)
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_open_ledger_txns_search(command_handle: CommandHandle,
+                                           pool_handle: PoolHandle,
+                                           ledger_type: *const c_char,
+                                           from: i32,
+                                           to: i32,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                search_handle: SearchHandle)>) -> ErrorCode {
+    trace!("indy_open_ledger_txns_search: >>> pool_handle: {:?}, ledger_type: {:?}, from: {:?}, to: {:?}",
+           pool_handle, ledger_type, from, to);
+
+    check_useful_opt_c_str!(ledger_type, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_open_ledger_txns_search: entities >>> pool_handle: {:?}, ledger_type: {:?}, from: {:?}, to: {:?}",
+           pool_handle, ledger_type, from, to);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::OpenLedgerTxnsSearch(
+            pool_handle,
+            ledger_type,
+            from,
+            to,
+            Box::new(move |result| {
+                let (err, handle) = prepare_result_1!(result, INVALID_SEARCH_HANDLE);
+                trace!("indy_open_ledger_txns_search: handle: {:?}", handle);
+                cb(command_handle, err, handle)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_open_ledger_txns_search: <<< res: {:?}", res);
+
+    res
+}
+
+/// Fetches next batch of transactions of the ledger transactions search.
+/// Batch contains at most 100 transactions (the size of catchup batch).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// search_handle: ledger transactions search handle (created by indy_open_ledger_txns_search)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// txns_json: array of transactions ordered by seqNo, empty array if all transactions of the range are fetched.
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_fetch_ledger_txns_search_next_batch(command_handle: CommandHandle,
+                                                       search_handle: SearchHandle,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                            err: ErrorCode,
+                                                                            txns_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_fetch_ledger_txns_search_next_batch: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_fetch_ledger_txns_search_next_batch: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::FetchLedgerTxnsSearchNextBatch(
+            search_handle,
+            boxed_callback_string!("indy_fetch_ledger_txns_search_next_batch", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_fetch_ledger_txns_search_next_batch: <<< res: {:?}", res);
+
+    res
+}
+
+/// Close ledger transactions search (make search handle invalid)
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// search_handle: ledger transactions search handle (created by indy_open_ledger_txns_search)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_close_ledger_txns_search(command_handle: CommandHandle,
+                                            search_handle: SearchHandle,
+                                            cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_close_ledger_txns_search: >>> search_handle: {:?}", search_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_close_ledger_txns_search: entities >>> search_handle: {:?}", search_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::CloseLedgerTxnsSearch(
+            search_handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_close_ledger_txns_search:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_close_ledger_txns_search: <<< res: {:?}", res);
+
+    res
+}
+
+/// Creates a local snapshot of the domain ledger from catchup of the opened pool
+/// to use by the pool opened in offline mode later.
+///
+/// Current size of the domain ledger and BLS multi-signature of its txn root are requested from the nodes,
+/// then the local replica of the ledger is brought up to this size with catchup requests.
+/// Snapshot is verified like the imported one (txns against signed txn root hash and
+/// multi-signature against BLS keys of the pool nodes) and replaces previously stored snapshot of the pool.
+/// Stored snapshot file (`snapshot.json` in the pool configuration directory) has format
+/// accepted by indy_import_pool_snapshot, so it can be moved to an air-gapped environment.
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context.
+/// pool_handle: pool handle (created by open_pool_ledger). Pool opened in offline mode isn't supported.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Snapshot info json:
+///     {
+///         ledgerSize: number of transactions in the snapshot,
+///         txnRootHash: signed txn root hash of the snapshot transactions,
+///     }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_create_pool_snapshot(command_handle: CommandHandle,
+                                        pool_handle: PoolHandle,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             snapshot_info_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_create_pool_snapshot: >>> pool_handle: {:?}", pool_handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_create_pool_snapshot: entities >>> pool_handle: {:?}", pool_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Ledger(LedgerCommand::CreatePoolSnapshot(
+            pool_handle,
+            boxed_callback_string!("indy_create_pool_snapshot", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_create_pool_snapshot: <<< res: {:?}", res);
+
+    res
+}
+
 /// Builds a NODE request. Request to add a new node to the pool, or updates existing in the pool.
 ///
 /// #Params
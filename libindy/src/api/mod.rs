@@ -30,6 +30,8 @@ use indy_utils::ctypes;
 ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
 ///         Capturing of backtrace can affect library performance.
 ///         NOTE: must be set before invocation of any other API functions.
+///     "ledger_cache_lru_size": Optional<int> - max count of ledger objects kept in memory in front of the wallet-backed
+///         ledger cache (see `indy_get_schema`, `indy_get_nym` and other cache functions). 0 disables in-memory layer. (0 by default)
 /// }
 ///
 /// #Errors
@@ -2,7 +2,7 @@
 use indy_api_types::{ErrorCode, CommandHandle, PoolHandle, INVALID_POOL_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::pool::PoolCommand;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{PoolConfig, PoolOpenConfig, SnapshotImportOptions};
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
 use indy_api_types::validation::Validatable;
@@ -18,6 +18,8 @@ use libc::c_char;
 /// {
 ///     "genesis_txn": string (optional), A path to genesis transaction file. If NULL, then a default one will be used.
 ///                    If file doesn't exists default one will be created.
+///     "namespace": string (optional), did:indy namespace of the network (for example `sovrin:staging`).
+///                  Used by indy_resolve to route requests for `did:indy:<namespace>:...` identifiers to this pool.
 /// }
 ///
 /// #Returns
@@ -35,7 +37,7 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
     trace!("indy_create_pool_ledger_config: >>> config_name: {:?}, config: {:?}", config_name, config);
 
     check_useful_c_str!(config_name, ErrorCode::CommonInvalidParam2);
-    check_useful_opt_json!(config, ErrorCode::CommonInvalidParam3, PoolConfig);
+    check_useful_opt_validatable_json!(config, ErrorCode::CommonInvalidParam3, PoolConfig);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
 
     trace!("indy_create_pool_ledger_config: entities >>> config_name: {:?}, config: {:?}", config_name, config);
@@ -79,6 +81,24 @@ pub extern fn indy_create_pool_ledger_config(command_handle: CommandHandle,
 ///     "number_read_nodes": int (optional) - the number of nodes to send read requests (2 by default)
 ///         By default Libindy sends a read requests to 2 nodes in the pool.
 ///         If response isn't received or `state proof` is invalid Libindy sends the request again but to 2 (`number_read_nodes`) * 2 = 4 nodes and so far until completion.
+///     "transport": object (optional) - transport used to send requests to the nodes ({"type": "zmq"} by default):
+///         {
+///             "type": string - one of:
+///                 "zmq" - direct CurveZMQ connections to the node client ports
+///                 "http" - HTTP(S) requests to the node-side gateways
+///                 "ws" - WebSocket connections to the node-side gateways
+///             "gateways": object - (required for "http" and "ws") gateway url for every node alias:
+///                 {"Node1": "https://gateway.example.com/Node1", ...}
+///                 Nodes without gateway are not used.
+///             "proxy": string - (optional, "http" only) proxy to send gateway requests through.
+///         }
+///     "offline": bool (optional) - open pool in read-only offline mode (false by default).
+///         No nodes connections are established. Read requests are answered from the ledger snapshot
+///         imported with indy_import_pool_snapshot and write requests are refused with CommonInvalidState error.
+///         Can't be combined with gateway transports.
+///     "verify_write_replies": bool (optional) - verify replies on write requests (false by default).
+///         A reply is accepted from a single node if the audit path of the written transaction leads to the reply root hash
+///         and the root hash is signed by BLS multi-signature of the pool. Replies failed verification are discarded.
 /// }
 ///
 /// #Returns
@@ -267,6 +287,127 @@ pub extern fn indy_delete_pool_ledger_config(command_handle: CommandHandle,
     res
 }
 
+/// Imports a local snapshot of the domain ledger transactions to use by pools opened in offline mode.
+///
+/// Snapshot is verified before saving: txns are checked against signed txn root hash
+/// and the root multi-signature is checked against BLS keys of the pool nodes
+/// known from genesis transactions and catchup cache.
+/// Snapshot without multi-signature is refused.
+/// Previously imported snapshot of the pool is replaced.
+///
+/// Snapshot can be created from catchup of the opened pool with indy_create_pool_snapshot.
+///
+/// #Params
+/// config_name: Name of the pool ledger configuration.
+/// snapshot_path: Path to the snapshot json file:
+/// {
+///     "ledgerId": int - id of the ledger (only domain ledger 1 is supported),
+///     "txns": array<object> - all ledger transactions ordered by seqNo starting from 1
+///                             (as returned by the nodes for protocol version 2),
+///     "multiSignature": object - pool BLS multi-signature of the ledger state:
+///         {
+///             "signature": string,
+///             "participants": array<string>,
+///             "value": {"ledger_id": int, "txn_root_hash": string, "state_root_hash": string,
+///                       "pool_state_root_hash": string, "timestamp": int}
+///         }
+/// }
+/// options (optional): Import options json. Reserved for the future use, only empty object is accepted.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_import_pool_snapshot(command_handle: CommandHandle,
+                                        config_name: *const c_char,
+                                        snapshot_path: *const c_char,
+                                        options: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_pool_snapshot: >>> config_name: {:?}, snapshot_path: {:?}, options: {:?}", config_name, snapshot_path, options);
+
+    check_useful_c_str!(config_name, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(snapshot_path, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_json!(options, ErrorCode::CommonInvalidParam4, SnapshotImportOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_import_pool_snapshot: entities >>> config_name: {:?}, snapshot_path: {:?}, options: {:?}", config_name, snapshot_path, options);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::ImportSnapshot(
+            config_name,
+            snapshot_path,
+            options,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_pool_snapshot:");
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_import_pool_snapshot: <<< res: {:?}", res);
+
+    res
+}
+
+/// Returns health statistics of the nodes of opened pool ledger.
+///
+/// Statistics are collected since pool opening and are dropped on pool closing.
+/// Nodes that haven't been requested yet are omitted.
+///
+/// #Params
+/// handle: pool handle returned by indy_open_pool_ledger.
+///
+/// #Returns
+/// Pool status json:
+/// {
+///     "nodes": {
+///         <node alias>: {
+///             "lastReplyTime": int (optional) - unix timestamp of the last reply received from the node,
+///             "latencyMs": int (optional) - average latency of the last node replies in milliseconds,
+///             "replies": int - count of successful replies,
+///             "timeouts": int - count of requests without reply in time,
+///             "nacks": int - count of REQNACK replies,
+///             "rejects": int - count of REJECT replies,
+///             "blsFailures": int - count of replies with state proof failed BLS verification,
+///         },
+///         ...
+///     }
+/// }
+///
+/// #Errors
+/// Common*
+/// Ledger*
+#[no_mangle]
+pub extern fn indy_get_pool_status(command_handle: CommandHandle,
+                                   handle: PoolHandle,
+                                   cb: Option<extern fn(command_handle_: CommandHandle,
+                                                        err: ErrorCode,
+                                                        status_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_pool_status: >>> handle: {:?}", handle);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_get_pool_status: entities >>> handle: {:?}", handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Pool(PoolCommand::GetStatus(
+            handle,
+            boxed_callback_string!("indy_get_pool_status", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_get_pool_status: <<< res: {:?}", res);
+
+    res
+}
+
 /// Set PROTOCOL_VERSION to specific version.
 ///
 /// There is a global property PROTOCOL_VERSION that used in every request to the pool and
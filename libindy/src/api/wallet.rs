@@ -1,8 +1,8 @@
 
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, INVALID_WALLET_HANDLE};
+use indy_api_types::{ErrorCode, CommandHandle, IndyHandle, WalletHandle, INVALID_WALLET_HANDLE};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::wallet::WalletCommand;
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{CheckOptions, Config, Credentials, ExportConfig, KeyConfig};
 use indy_api_types::wallet::*;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -143,6 +143,106 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
     res
 }
 
+/// Register transaction handlers for custom wallet storage implementation.
+/// Wallet storage type must be previously registered with indy_register_wallet_storage call.
+/// Storages without registered transaction handlers don't support wallet transactions.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Storage type name.
+/// begin_transaction: WalletType begin transaction operation handler
+/// commit_transaction: WalletType commit transaction operation handler
+/// rollback_transaction: WalletType rollback transaction operation handler
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_transactions(command_handle: CommandHandle,
+                                                        type_: *const c_char,
+                                                        begin_transaction: Option<WalletBeginTransaction>,
+                                                        commit_transaction: Option<WalletCommitTransaction>,
+                                                        rollback_transaction: Option<WalletRollbackTransaction>,
+                                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                             err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_transactions: >>> command_handle: {:?}, type_: {:?}, begin_transaction: {:?}, \
+            commit_transaction: {:?}, rollback_transaction: {:?}, cb: {:?}",
+           command_handle, type_, begin_transaction, commit_transaction, rollback_transaction, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(begin_transaction, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(commit_transaction, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(rollback_transaction, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_register_wallet_storage_transactions: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterWalletStorageTransactions(
+                type_,
+                begin_transaction,
+                commit_transaction,
+                rollback_transaction,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_storage_transactions: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_storage_transactions: <<< res: {:?}", res);
+    res
+}
+
+/// Register storages listing handlers for custom wallet storage implementation.
+/// Wallet storage type must be previously registered with indy_register_wallet_storage call.
+/// Wallets of storages without registered listing handlers can't be listed with indy_list_wallets call.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Storage type name.
+/// list_storages: WalletType list storages operation handler
+/// free_storage_list: WalletType free storage list operation handler
+///
+/// #Returns
+/// Error code
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_list(command_handle: CommandHandle,
+                                                type_: *const c_char,
+                                                list_storages: Option<WalletListStorages>,
+                                                free_storage_list: Option<WalletFreeStorageList>,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_list: >>> command_handle: {:?}, type_: {:?}, list_storages: {:?}, \
+            free_storage_list: {:?}, cb: {:?}",
+           command_handle, type_, list_storages, free_storage_list, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(list_storages, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(free_storage_list, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_register_wallet_storage_list: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterWalletStorageList(
+                type_,
+                list_storages,
+                free_storage_list,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_storage_list: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_storage_list: <<< res: {:?}", res);
+    res
+}
+
 /// Create a new secure wallet.
 ///
 /// #Params
@@ -152,6 +252,8 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+///                  and accepts the same configuration as 'default' one.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -225,6 +327,8 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///             Configured storage uses this identifier to lookup exact wallet data placement.
 ///       "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                       'Default' storage type allows to store wallet data in the local file.
+///                       'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+///                       and accepts the same configuration as 'default' one.
 ///                       Custom storage types can be registered with indy_register_wallet_storage call.
 ///       "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                         Can be optional if storage supports default configuration.
@@ -311,6 +415,15 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "version": optional<int> Export format version:
+///                 0 - single export file (used by default)
+///                 1 - "path" is a manifest file and encrypted records are stored in "chunks"
+///                     subdirectory of manifest directory. Records already stored there are not rewritten.
+///     "base_manifest": optional<string> Path of the manifest of previous version 1 export in the same directory
+///                      to make incremental backup to. The key derivation of the base export is used
+///                      and "key" has to match the key of the base export.
+///     "verkey": optional<string> Verkey of the wallet key (see indy_create_key) the manifest of version 1 export
+///               is signed with. Required for version 1. Base manifest has to be signed with the same key.
 ///   }
 ///
 /// #Returns
@@ -349,6 +462,135 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 }
 
 
+/// Rotates keys of opened wallet without export/import cycle.
+///
+/// New wallet keys are generated and all wallet records are re-encrypted with them in place.
+/// The keys are protected with a master key derived from the new credentials.
+/// Progress of records re-encryption is reported with progress_cb.
+///
+/// Operation is crash-safe: if it is interrupted after the new credentials were applied,
+/// the wallet can be opened with the new credentials only and re-encryption of the rest of records
+/// will be finished by indy_open_wallet.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// new_credentials: New wallet credentials json
+///   {
+///       "key": string, New key or passphrase used for wallet key derivation.
+///                      Look to key_derivation_method param for information about supported key derivation methods.
+///       "key_derivation_method": optional<string> Algorithm to use for wallet key derivation:
+///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+///                          RAW - raw wallet key master provided (skip derivation).
+///                                RAW keys can be generated with indy_generate_wallet_key call
+///   }
+///   Storage credentials can't be changed with this call, "storage_credentials" and "rekey" fields are ignored.
+/// progress_cb: optional callback called with amount of processed and total amount of wallet records.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_rekey_wallet(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                new_credentials: *const c_char,
+                                progress_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              processed: u32,
+                                                              total: u32)>,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_rekey_wallet: >>> wallet_handle: {:?}, new_credentials: {:?}", wallet_handle, new_credentials);
+
+    check_useful_json!(new_credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_rekey_wallet: params wallet_handle: {:?}, new_credentials: {:?}", wallet_handle, secret!(&new_credentials));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Rekey(
+            wallet_handle,
+            new_credentials,
+            Box::new(move |processed, total| {
+                if let Some(progress_cb) = progress_cb {
+                    progress_cb(command_handle, processed as u32, total as u32)
+                }
+            }),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_rekey_wallet: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_rekey_wallet: <<< res: {:?}", res);
+    res
+}
+
+/// Checks integrity of opened wallet.
+///
+/// Every record of the wallet is verified to be decryptable with the wallet keys.
+/// Encrypted type, id and tag names/values are additionally verified to match HMAC of their decrypted content,
+/// so records damaged by a partial write or by a buggy storage plugin are detected.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// options_json: (optional) check options json
+///   {
+///       "quarantine": optional<bool>, Move broken records to "Indy::Quarantine" record type. false by default.
+///                     Quarantined record keeps raw (base64 encoded) content of the broken record as its value
+///                     and has "reason" tag, as well as "type" and "id" tags if they could be decrypted.
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json:
+///   {
+///       "total": int, amount of checked records
+///       "broken": [{
+///           "type": optional<string>, type of the broken record (absent if it can't be decrypted)
+///           "id": optional<string>, id of the broken record (absent if it can't be decrypted)
+///           "raw_id": string, base64 encoded id of the record as it is stored in the storage
+///           "reason": string, description of the damage
+///           "quarantined": bool, whether the record was moved to quarantine
+///       }]
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_check_wallet(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                options_json: *const c_char,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode,
+                                                     report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_wallet: >>> command_handle: {:?}, wallet_handle: {:?}, options_json: {:?}, cb: {:?}",
+           command_handle, wallet_handle, options_json, cb);
+
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam3, CheckOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_check_wallet: params wallet_handle: {:?}, options_json: {:?}", wallet_handle, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Check(
+            wallet_handle,
+            options_json.unwrap_or_default(),
+            boxed_callback_string!("indy_check_wallet", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_check_wallet: <<< res: {:?}", res);
+    res
+}
+
+
 /// Creates a new secure wallet and then imports its content
 /// according to fields provided in import_config
 /// This can be seen as an indy_create_wallet call with additional content import
@@ -360,6 +602,8 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+///                  and accepts the same configuration as 'default' one.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -386,7 +630,10 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 /// import_config: Import settings json.
 /// {
 ///   "path": <string>, path of the file that contains exported wallet content
+///           or of the manifest file of version 1 export (format is detected automatically)
 ///   "key": <string>, key used for export of the wallet
+///   "verkey": optional<string>, verkey the manifest of version 1 export is signed with.
+///             Required for version 1. Signatures of the manifest and of all its base manifests are verified.
 /// }
 ///
 /// #Returns
@@ -478,6 +725,8 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'sled' storage type stores wallet data in the embedded key-value database {path}/{id}/sled.db
+///                  and accepts the same configuration as 'default' one.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -539,6 +788,281 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Lists wallets existing in the wallet storage.
+///
+/// #Params
+/// storage_type: (optional) Type of the wallet storage. Defaults to 'default'.
+///               Custom storage types must register listing handlers with indy_register_wallet_storage_list call.
+/// storage_config: (optional) Storage configuration json. The same as "storage_config" of indy_create_wallet config.
+///                 For 'default' and 'sled' storage types wallets are looked up in {path}
+///                 ($HOME/.indy_client/wallet by default).
+/// storage_credentials: (optional) Credentials for wallet storage. The same as "storage_credentials" of indy_create_wallet credentials.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// wallets_json: sorted list of ids of existing wallets
+///   ["id1", "id2", ...]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_list_wallets(command_handle: CommandHandle,
+                                storage_type: *const c_char,
+                                storage_config: *const c_char,
+                                storage_credentials: *const c_char,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode,
+                                                     wallets_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_list_wallets: >>> command_handle: {:?}, storage_type: {:?}, storage_config: {:?}, storage_credentials: {:?}, cb: {:?}",
+           command_handle, storage_type, storage_config, storage_credentials, cb);
+
+    check_useful_opt_c_str!(storage_type, ErrorCode::CommonInvalidParam2);
+    check_useful_opt_c_str!(storage_config, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_c_str!(storage_credentials, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_list_wallets: params storage_type: {:?}, storage_config: {:?}, storage_credentials: {:?}",
+           storage_type, storage_config, secret!(&storage_credentials));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::List(
+            storage_type,
+            storage_config,
+            storage_credentials,
+            boxed_callback_string!("indy_list_wallets", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_list_wallets: <<< res: {:?}", res);
+    res
+}
+
+/// Begin a transaction on the opened wallet.
+/// All following changes of wallet records (including non-secrets, DIDs, keys, credentials and etc.)
+/// are applied atomically: they become visible for other wallet users
+/// and durable only after indy_wallet_commit_transaction call.
+///
+/// Note that transactions can't be nested and the wallet storage must support transactions
+/// ('default' storage does, custom storages can register handlers with indy_register_wallet_storage_transactions call).
+/// If the wallet is closed before commit all changes made in the transaction are discarded.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_wallet_begin_transaction(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_wallet_begin_transaction: >>> command_handle: {:?}, wallet_handle: {:?}, cb: {:?}",
+           command_handle, wallet_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_wallet_begin_transaction: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::BeginTransaction(
+            wallet_handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_wallet_begin_transaction: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_wallet_begin_transaction: <<< res: {:?}", res);
+    res
+}
+
+/// Commit the transaction started with indy_wallet_begin_transaction call.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_wallet_commit_transaction(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_wallet_commit_transaction: >>> command_handle: {:?}, wallet_handle: {:?}, cb: {:?}",
+           command_handle, wallet_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_wallet_commit_transaction: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::CommitTransaction(
+            wallet_handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_wallet_commit_transaction: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_wallet_commit_transaction: <<< res: {:?}", res);
+    res
+}
+
+/// Rollback the transaction started with indy_wallet_begin_transaction call.
+/// All changes made in the transaction are discarded.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_wallet_rollback_transaction(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                    err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_wallet_rollback_transaction: >>> command_handle: {:?}, wallet_handle: {:?}, cb: {:?}",
+           command_handle, wallet_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_wallet_rollback_transaction: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::RollbackTransaction(
+            wallet_handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_wallet_rollback_transaction: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_wallet_rollback_transaction: <<< res: {:?}", res);
+    res
+}
+
+/// Subscribes to changes of records of opened wallet.
+///
+/// Events are produced for records added, updated or deleted through any libindy call
+/// working with the wallet (non_secrets, did, anoncreds, etc.).
+/// Changes made inside of a wallet transaction are reported after the transaction is committed.
+/// Subscription is dropped with indy_unsubscribe_wallet_events call or when the wallet is closed.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+/// type_filter: (optional) type of records to receive events for. Events for all types are delivered if not set.
+///              Note that records created by libindy itself have "Indy::" prefix (for example "Indy::Credential").
+/// event_cb: Callback called with command_handle of this call and event json for each change:
+///   {
+///       "event": string, one of "add", "update" (value or tags of the record were changed), "delete"
+///       "type": string, type of the record
+///       "id": string, id of the record
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// subscription_handle: handle to pass to indy_unsubscribe_wallet_events
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_subscribe_wallet_events(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           type_filter: *const c_char,
+                                           event_cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                      event_json: *const c_char)>,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                subscription_handle: IndyHandle)>) -> ErrorCode {
+    trace!("indy_subscribe_wallet_events: >>> command_handle: {:?}, wallet_handle: {:?}, type_filter: {:?}, event_cb: {:?}, cb: {:?}",
+           command_handle, wallet_handle, type_filter, event_cb, cb);
+
+    check_useful_opt_c_str!(type_filter, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(event_cb, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_subscribe_wallet_events: params wallet_handle: {:?}, type_filter: {:?}", wallet_handle, type_filter);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::SubscribeEvents(
+            wallet_handle,
+            type_filter,
+            Box::new(move |event_json| {
+                trace!("indy_subscribe_wallet_events: event_cb command_handle: {:?}, event_json: {:?}", command_handle, event_json);
+                let event_json = ctypes::string_to_cstring(event_json);
+                event_cb(command_handle, event_json.as_ptr())
+            }),
+            Box::new(move |result| {
+                let (err, subscription_handle) = prepare_result_1!(result, 0);
+                trace!("indy_subscribe_wallet_events: cb command_handle: {:?}, err: {:?}, subscription_handle: {:?}", command_handle, err, subscription_handle);
+                cb(command_handle, err, subscription_handle)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_subscribe_wallet_events: <<< res: {:?}", res);
+    res
+}
+
+/// Cancels subscription to wallet records changes made with indy_subscribe_wallet_events call.
+///
+/// #Params
+/// subscription_handle: subscription handle returned by indy_subscribe_wallet_events.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_unsubscribe_wallet_events(command_handle: CommandHandle,
+                                             subscription_handle: IndyHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_unsubscribe_wallet_events: >>> command_handle: {:?}, subscription_handle: {:?}, cb: {:?}",
+           command_handle, subscription_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_unsubscribe_wallet_events: params subscription_handle: {:?}", subscription_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::UnsubscribeEvents(
+            subscription_handle,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_unsubscribe_wallet_events: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_unsubscribe_wallet_events: <<< res: {:?}", res);
+    res
+}
+
 /// Generate wallet master key.
 /// Returned key is compatible with "RAW" key derivation method.
 /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
@@ -40,6 +40,15 @@ use crate::domain::anoncreds::revocation_registry_definition::{
     RevocationRegistryInfo,
     RevocationRegistryId
 };
+use crate::domain::anoncreds::revocation_registry_manager::{
+    IssuedCredential,
+    IssuedCredentialStatus,
+    ManagedRevocationRegistry,
+    ManagedRevocationRegistryInfo,
+    ManagedRevocationRegistryStatus,
+    RevocationRegistryManager,
+    RevocationRegistryManagerConfig,
+};
 use crate::domain::anoncreds::revocation_registry_delta::{
     RevocationRegistryDelta,
     RevocationRegistryDeltaV1,
@@ -53,7 +62,7 @@ use crate::services::anoncreds::helpers::parse_cred_rev_id;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::pool::PoolService;
-use indy_wallet::{RecordOptions, WalletService};
+use indy_wallet::{RecordOptions, SearchOptions, WalletService};
 
 use super::tails::{SDKTailsAccessor, store_tails_from_generator};
 use indy_api_types::{WalletHandle, CommandHandle};
@@ -131,6 +140,33 @@ pub enum IssuerCommand {
         RevocationRegistryId, //revocation registry id
         String, //credential revoc id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    CreateRevocationRegistryManager(
+        WalletHandle,
+        DidValue, // issuer did
+        CredentialDefinitionId, // credential definition id
+        RevocationRegistryManagerConfig, // config
+        i32, // tails writer handle
+        Box<dyn Fn(IndyResult<(String, String, String)>) + Send>),
+    CreateCredentialWithRevocationRegistryManager(
+        WalletHandle,
+        CredentialOffer, // credential offer
+        CredentialRequest, // credential request
+        CredentialValues, // credential values
+        i32, // tails writer handle
+        i32, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    GetRevocationRegistryManagerState(
+        WalletHandle,
+        CredentialDefinitionId, // credential definition id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    MarkRevocationRegistryPublished(
+        WalletHandle,
+        RevocationRegistryId, // revocation registry id
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    GetIssuedCredentials(
+        WalletHandle,
+        Option<String>, // query
+        Box<dyn Fn(IndyResult<String>) + Send>),
     /*    RecoverCredential(
             WalletHandle,
             i32, // blob storage reader config handle
@@ -220,6 +256,26 @@ impl IssuerCommandExecutor {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
             }
+            IssuerCommand::CreateRevocationRegistryManager(wallet_handle, issuer_did, cred_def_id, config, tails_writer_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateRevocationRegistryManager command received");
+                cb(self.create_revocation_registry_manager(wallet_handle, &issuer_did, &cred_def_id, &config, tails_writer_handle));
+            }
+            IssuerCommand::CreateCredentialWithRevocationRegistryManager(wallet_handle, cred_offer, cred_req, cred_values, tails_writer_handle, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentialWithRevocationRegistryManager command received");
+                cb(self.new_credential_with_revocation_registry_manager(wallet_handle, &cred_offer, &cred_req, &cred_values, tails_writer_handle, blob_storage_reader_handle));
+            }
+            IssuerCommand::GetRevocationRegistryManagerState(wallet_handle, cred_def_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetRevocationRegistryManagerState command received");
+                cb(self.get_revocation_registry_manager_state(wallet_handle, &cred_def_id));
+            }
+            IssuerCommand::MarkRevocationRegistryPublished(wallet_handle, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "MarkRevocationRegistryPublished command received");
+                cb(self.mark_revocation_registry_published(wallet_handle, &rev_reg_id));
+            }
+            IssuerCommand::GetIssuedCredentials(wallet_handle, query_json, cb) => {
+                debug!(target: "issuer_command_executor", "GetIssuedCredentials command received");
+                cb(self.get_issued_credentials(wallet_handle, query_json.as_ref().map(String::as_str)));
+            }
             /*            IssuerCommand::RecoverCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                             debug!(target: "issuer_command_executor", "RecoverCredential command received");
                             cb(self.recovery_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
@@ -787,6 +843,8 @@ impl IssuerCommandExecutor {
 
             self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
             self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
+
+            self._wallet_add_issued_credential(wallet_handle, &cred_def_id, r_reg_id, &r_reg_info.curr_id.to_string())?;
         };
 
         let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
@@ -850,11 +908,257 @@ impl IssuerCommandExecutor {
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
         self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
 
+        self._wallet_revoke_issued_credential(wallet_handle, rev_reg_id, &cred_revoc_id.to_string())?;
+
         debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
 
         Ok(rev_reg_delta_json)
     }
 
+    fn create_revocation_registry_manager(&self,
+                                          wallet_handle: WalletHandle,
+                                          issuer_did: &DidValue,
+                                          cred_def_id: &CredentialDefinitionId,
+                                          config: &RevocationRegistryManagerConfig,
+                                          tails_writer_handle: i32) -> IndyResult<(String, String, String)> {
+        debug!("create_revocation_registry_manager >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config: {:?}, tails_writer_handle: {:?}",
+               wallet_handle, issuer_did, cred_def_id, config, tails_writer_handle);
+
+        if self.wallet_service.record_exists::<RevocationRegistryManager>(wallet_handle, &cred_def_id.0)? {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists,
+                               format!("Revocation registry manager already exists for credential definition: {}", cred_def_id.0)));
+        }
+
+        let mut manager = RevocationRegistryManager {
+            issuer_did: issuer_did.clone(),
+            cred_def_id: cred_def_id.clone(),
+            config: config.clone(),
+            registries: Vec::new(),
+        };
+
+        let (rev_reg_id, rev_reg_def_json, rev_reg_json) =
+            self._add_managed_revocation_registry(wallet_handle, &mut manager, ManagedRevocationRegistryStatus::Active, tails_writer_handle)?;
+
+        self.wallet_service.add_indy_object(wallet_handle, &cred_def_id.0, &manager, &HashMap::new())?;
+
+        debug!("create_revocation_registry_manager <<< rev_reg_id: {:?}, rev_reg_def_json: {:?}, rev_reg_json: {:?}", rev_reg_id, rev_reg_def_json, rev_reg_json);
+
+        Ok((rev_reg_id, rev_reg_def_json, rev_reg_json))
+    }
+
+    fn new_credential_with_revocation_registry_manager(&self,
+                                                       wallet_handle: WalletHandle,
+                                                       cred_offer: &CredentialOffer,
+                                                       cred_request: &CredentialRequest,
+                                                       cred_values: &CredentialValues,
+                                                       tails_writer_handle: i32,
+                                                       blob_storage_reader_handle: i32) -> IndyResult<(String, Option<String>, Option<String>)> {
+        debug!("new_credential_with_revocation_registry_manager >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, \
+               tails_writer_handle: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), tails_writer_handle, blob_storage_reader_handle);
+
+        let cred_def_id = match cred_offer.method_name {
+            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
+            None => cred_offer.cred_def_id.clone()
+        };
+
+        let mut manager: RevocationRegistryManager =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        if let Some(active_rev_reg_id) = manager.active().map(|registry| registry.rev_reg_id.clone()) {
+            let (issued, max_cred_num) = self._revocation_registry_usage(wallet_handle, &active_rev_reg_id)?;
+
+            if issued >= max_cred_num {
+                manager.set_status(&active_rev_reg_id, ManagedRevocationRegistryStatus::Full);
+            }
+        }
+
+        let active_rev_reg_id = manager.active().map(|registry| registry.rev_reg_id.clone());
+        let ready_rev_reg_id = manager.ready().map(|registry| registry.rev_reg_id.clone());
+        let pending_rev_reg_id = manager.pending().map(|registry| registry.rev_reg_id.0.clone());
+
+        // Only registries marked as published can be promoted to active
+        let rev_reg_id = match (active_rev_reg_id, ready_rev_reg_id, pending_rev_reg_id) {
+            (Some(active_rev_reg_id), _, _) => active_rev_reg_id,
+            (None, Some(ready_rev_reg_id), _) => {
+                manager.set_status(&ready_rev_reg_id, ManagedRevocationRegistryStatus::Active);
+                ready_rev_reg_id
+            }
+            (None, None, pending_rev_reg_id) => {
+                let next_rev_reg_id = match pending_rev_reg_id {
+                    Some(pending_rev_reg_id) => pending_rev_reg_id,
+                    None => self._add_managed_revocation_registry(wallet_handle, &mut manager, ManagedRevocationRegistryStatus::Pending, tails_writer_handle)?.0
+                };
+
+                self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &manager)?;
+
+                return Err(err_msg(IndyErrorKind::RevocationRegistryFull,
+                                   format!("Revocation registries of credential definition {} are full, the next registry {} must be published before issuance", cred_def_id.0, next_rev_reg_id)));
+            }
+        };
+
+        let res = match self.new_credential(wallet_handle, cred_offer, cred_request, cred_values, Some(&rev_reg_id), Some(blob_storage_reader_handle)) {
+            Ok(res) => res,
+            Err(err) => {
+                // Registry statuses changed above must not be lost with failed issuance
+                self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &manager)?;
+                return Err(err);
+            }
+        };
+
+        // The credential is already issued, so failed preparation of the next registry is retried on the next issuance
+        if manager.ready().is_none() && manager.pending().is_none() {
+            let (issued, max_cred_num) = self._revocation_registry_usage(wallet_handle, &rev_reg_id)?;
+
+            if f64::from(issued) >= manager.config.rollover_threshold() * f64::from(max_cred_num) {
+                if let Err(err) = self._add_managed_revocation_registry(wallet_handle, &mut manager, ManagedRevocationRegistryStatus::Pending, tails_writer_handle) {
+                    warn!("Cannot create next revocation registry for credential definition {}: {:?}", cred_def_id.0, err);
+                }
+            }
+        }
+
+        self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &manager)?;
+
+        debug!("new_credential_with_revocation_registry_manager <<< cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&res.1), res.2);
+
+        Ok(res)
+    }
+
+    fn get_revocation_registry_manager_state(&self,
+                                             wallet_handle: WalletHandle,
+                                             cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
+        debug!("get_revocation_registry_manager_state >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+        let manager: RevocationRegistryManager =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        let mut registries: Vec<ManagedRevocationRegistryInfo> = Vec::new();
+
+        for registry in manager.registries {
+            let rev_reg_def = RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, &registry.rev_reg_id)?);
+            let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &registry.rev_reg_id)?;
+
+            let issued = rev_reg_info.curr_id;
+            let revoked = match rev_reg_def.value.issuance_type {
+                IssuanceType::ISSUANCE_ON_DEMAND => issued.saturating_sub(rev_reg_info.used_ids.len() as u32),
+                IssuanceType::ISSUANCE_BY_DEFAULT => rev_reg_info.used_ids.len() as u32
+            };
+
+            let max_cred_num = rev_reg_def.value.max_cred_num;
+
+            let (rev_reg_def, rev_reg) = if registry.status == ManagedRevocationRegistryStatus::Pending {
+                (Some(RevocationRegistryDefinition::RevocationRegistryDefinitionV1(rev_reg_def)),
+                 Some(self._wallet_get_rev_reg(wallet_handle, &registry.rev_reg_id)?))
+            } else {
+                (None, None)
+            };
+
+            registries.push(ManagedRevocationRegistryInfo {
+                rev_reg_id: registry.rev_reg_id,
+                tag: registry.tag,
+                status: registry.status,
+                max_cred_num,
+                issued,
+                revoked,
+                rev_reg_def,
+                rev_reg,
+            });
+        }
+
+        let registries_json = serde_json::to_string(&registries)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize managed revocation registries")?;
+
+        debug!("get_revocation_registry_manager_state <<< registries_json: {:?}", registries_json);
+
+        Ok(registries_json)
+    }
+
+    fn mark_revocation_registry_published(&self,
+                                          wallet_handle: WalletHandle,
+                                          rev_reg_id: &RevocationRegistryId) -> IndyResult<()> {
+        debug!("mark_revocation_registry_published >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+        let rev_reg_def = RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?);
+
+        let mut manager: RevocationRegistryManager =
+            self.wallet_service.get_indy_object(wallet_handle, &rev_reg_def.cred_def_id.0, &RecordOptions::id_value())?;
+
+        let status = manager.get(rev_reg_id)
+            .map(|registry| registry.status)
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound,
+                                   format!("Revocation registry {} is not managed by revocation registry manager", rev_reg_id.0)))?;
+
+        if status != ManagedRevocationRegistryStatus::Pending {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Revocation registry {} is not pending publication", rev_reg_id.0)));
+        }
+
+        manager.set_status(rev_reg_id, ManagedRevocationRegistryStatus::Ready);
+
+        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_def.cred_def_id.0, &manager)?;
+
+        debug!("mark_revocation_registry_published <<<");
+
+        Ok(())
+    }
+
+    fn get_issued_credentials(&self,
+                              wallet_handle: WalletHandle,
+                              query_json: Option<&str>) -> IndyResult<String> {
+        debug!("get_issued_credentials >>> wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+        let mut issued_credentials_search =
+            self.wallet_service.search_indy_records::<IssuedCredential>(wallet_handle, query_json.unwrap_or("{}"), &SearchOptions::id_value())?;
+
+        let mut issued_credentials: Vec<IssuedCredential> = Vec::new();
+
+        while let Some(issued_credential_record) = issued_credentials_search.fetch_next_record()? {
+            let issued_credential_json = issued_credential_record.get_value()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("Issued credential not found for id: {}", issued_credential_record.get_id())))?;
+
+            let issued_credential: IssuedCredential = serde_json::from_str(issued_credential_json)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize IssuedCredential")?;
+
+            issued_credentials.push(issued_credential);
+        }
+
+        let issued_credentials_json = serde_json::to_string(&issued_credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize IssuedCredential list")?;
+
+        debug!("get_issued_credentials <<< issued_credentials_json: {:?}", issued_credentials_json);
+
+        Ok(issued_credentials_json)
+    }
+
+    fn _add_managed_revocation_registry(&self,
+                                        wallet_handle: WalletHandle,
+                                        manager: &mut RevocationRegistryManager,
+                                        status: ManagedRevocationRegistryStatus,
+                                        tails_writer_handle: i32) -> IndyResult<(String, String, String)> {
+        let tag = manager.next_tag();
+
+        let config = RevocationRegistryConfig {
+            issuance_type: manager.config.issuance_type.clone(),
+            max_cred_num: manager.config.max_cred_num,
+        };
+
+        let (_, rev_reg_def_json, rev_reg_json) =
+            self.create_and_store_revocation_registry(wallet_handle, &manager.issuer_did, None, &tag, &manager.cred_def_id, &config, tails_writer_handle)?;
+
+        let rev_reg_id = RevocationRegistryId::new(&manager.issuer_did, &manager.cred_def_id, RegistryType::CL_ACCUM.to_str(), &tag);
+
+        manager.registries.push(ManagedRevocationRegistry { rev_reg_id: rev_reg_id.clone(), tag, status });
+
+        Ok((rev_reg_id.0, rev_reg_def_json, rev_reg_json))
+    }
+
+    fn _revocation_registry_usage(&self, wallet_handle: WalletHandle, rev_reg_id: &RevocationRegistryId) -> IndyResult<(u32, u32)> {
+        let rev_reg_def = RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?);
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?;
+
+        Ok((rev_reg_info.curr_id, rev_reg_def.value.max_cred_num))
+    }
+
     fn _recovery_credential(&self,
                             wallet_handle: WalletHandle,
                             blob_storage_reader_handle: i32,
@@ -956,4 +1260,53 @@ impl IssuerCommandExecutor {
     fn _wallet_get_rev_reg_info(&self, wallet_handle: WalletHandle, key: &RevocationRegistryId) -> IndyResult<RevocationRegistryInfo> {
         self.wallet_service.get_indy_object(wallet_handle, &key.0, &RecordOptions::id_value())
     }
+
+    fn _wallet_add_issued_credential(&self,
+                                     wallet_handle: WalletHandle,
+                                     cred_def_id: &CredentialDefinitionId,
+                                     rev_reg_id: &RevocationRegistryId,
+                                     cred_rev_id: &str) -> IndyResult<()> {
+        let issued_credential = IssuedCredential {
+            cred_def_id: cred_def_id.clone(),
+            rev_reg_id: rev_reg_id.clone(),
+            cred_rev_id: cred_rev_id.to_string(),
+            status: IssuedCredentialStatus::Issued,
+        };
+
+        self.wallet_service.add_indy_object(wallet_handle,
+                                            &IssuedCredential::id(rev_reg_id, cred_rev_id),
+                                            &issued_credential,
+                                            &IssuerCommandExecutor::_issued_credential_tags(&issued_credential))?;
+        Ok(())
+    }
+
+    // Credentials issued before tracking was introduced have no records
+    fn _wallet_revoke_issued_credential(&self,
+                                        wallet_handle: WalletHandle,
+                                        rev_reg_id: &RevocationRegistryId,
+                                        cred_rev_id: &str) -> IndyResult<()> {
+        let id = IssuedCredential::id(rev_reg_id, cred_rev_id);
+
+        if !self.wallet_service.record_exists::<IssuedCredential>(wallet_handle, &id)? {
+            return Ok(());
+        }
+
+        let mut issued_credential: IssuedCredential = self.wallet_service.get_indy_object(wallet_handle, &id, &RecordOptions::id_value())?;
+        issued_credential.status = IssuedCredentialStatus::Revoked;
+
+        self.wallet_service.update_indy_object(wallet_handle, &id, &issued_credential)?;
+        self.wallet_service.update_record_tags(wallet_handle,
+                                               &self.wallet_service.add_prefix("IssuedCredential"),
+                                               &id,
+                                               &IssuerCommandExecutor::_issued_credential_tags(&issued_credential))
+    }
+
+    fn _issued_credential_tags(issued_credential: &IssuedCredential) -> Tags {
+        let mut tags = Tags::new();
+        tags.insert("cred_def_id".to_string(), issued_credential.cred_def_id.0.to_string());
+        tags.insert("rev_reg_id".to_string(), issued_credential.rev_reg_id.0.to_string());
+        tags.insert("cred_rev_id".to_string(), issued_credential.cred_rev_id.to_string());
+        tags.insert("status".to_string(), issued_credential.status.to_str().to_string());
+        tags
+    }
 }
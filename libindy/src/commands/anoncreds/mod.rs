@@ -12,7 +12,8 @@ use crate::services::blob_storage::BlobStorageService;
 use crate::services::pool::PoolService;
 use indy_wallet::WalletService;
 use crate::services::crypto::CryptoService;
-use crate::services::anoncreds::helpers::to_unqualified;
+use crate::services::anoncreds::helpers::{encode_credential_values, to_unqualified};
+use crate::domain::anoncreds::credential::{CredentialValues, RawCredentialValues};
 
 use indy_api_types::errors::prelude::*;
 
@@ -24,6 +25,9 @@ pub enum AnoncredsCommand {
     Verifier(VerifierCommand),
     ToUnqualified(
         String, // entity
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeCredentialValues(
+        RawCredentialValues, // raw credential values
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
@@ -68,6 +72,23 @@ impl AnoncredsCommandExecutor {
                 debug!("ToUnqualified command received");
                 cb(to_unqualified(&entity));
             }
+            AnoncredsCommand::EncodeCredentialValues(cred_values, cb) => {
+                debug!("EncodeCredentialValues command received");
+                cb(self.encode_credential_values(&cred_values));
+            }
         };
     }
+
+    fn encode_credential_values(&self, cred_values: &RawCredentialValues) -> IndyResult<String> {
+        trace!("encode_credential_values >>> cred_values: {:?}", cred_values);
+
+        let cred_values = CredentialValues(encode_credential_values(cred_values)?);
+
+        let res = serde_json::to_string(&cred_values)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialValues")?;
+
+        trace!("encode_credential_values <<< res: {:?}", res);
+
+        Ok(res)
+    }
 }
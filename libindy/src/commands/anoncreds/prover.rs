@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use ursa::cl::{new_nonce, RevocationRegistry, Witness};
 
@@ -12,6 +13,7 @@ use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs
 use crate::domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest, RequestedCredential};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
+use crate::domain::anoncreds::credential_selection::CredentialSelectionPolicy;
 use crate::domain::anoncreds::master_secret::MasterSecret;
 use crate::domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
@@ -33,6 +35,8 @@ use super::tails::SDKTailsAccessor;
 use indy_api_types::{WalletHandle, SearchHandle};
 use crate::commands::BoxedCallbackStringStringSend;
 
+const STORED_AT_TAG: &str = "~stored_at";
+
 pub enum ProverCommand {
     CreateMasterSecret(
         WalletHandle,
@@ -103,6 +107,11 @@ pub enum ProverCommand {
     CloseCredentialsSearchForProofReq(
         SearchHandle,
         Box<dyn Fn(IndyResult<()>) + Send>),
+    SelectCredentialsForProofReq(
+        WalletHandle,
+        ProofRequest, // proof request
+        Option<CredentialSelectionPolicy>, // selection policy
+        BoxedCallbackStringStringSend),
     CreateProof(
         WalletHandle,
         ProofRequest, // proof request
@@ -238,6 +247,10 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "CloseCredentialsSearchForProofReq command received");
                 cb(self.close_credentials_search_for_proof_req(search_handle));
             }
+            ProverCommand::SelectCredentialsForProofReq(wallet_handle, proof_req, policy, cb) => {
+                debug!(target: "prover_command_executor", "SelectCredentialsForProofReq command received");
+                cb(self.select_credentials_for_proof_req(wallet_handle, &proof_req, policy.as_ref()));
+            }
             ProverCommand::CreateProof(wallet_handle, proof_req, requested_credentials, master_secret_name,
                                        schemas, cred_defs, rev_states, cb) => {
                 debug!(target: "prover_command_executor", "CreateProof command received");
@@ -347,11 +360,22 @@ impl ProverCommandExecutor {
         // Cascade whether we updated policy or not: could be a retroactive cred attr tags reset to existing policy
         if retroactive {
             let query_json = format!(r#"{{"cred_def_id": "{}"}}"#, cred_def_id.0);
-            let mut credentials_search = self.wallet_service.search_indy_records::<Credential>(wallet_handle, query_json.as_str(), &SearchOptions::id_value())?;
+            let options_json = json!({
+                "retrieveRecords": true,
+                "retrieveValue": true,
+                "retrieveTags": true,
+            }).to_string();
+
+            let mut credentials_search = self.wallet_service.search_indy_records::<Credential>(wallet_handle, query_json.as_str(), &options_json)?;
 
             while let Some(credential_record) = credentials_search.fetch_next_record()? {
                 let (_, credential) = self._get_credential(&credential_record)?;
-                let cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol)?;
+                let mut cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol)?;
+
+                if let Some(stored_at) = credential_record.get_tags().and_then(|tags| tags.get(STORED_AT_TAG)) {
+                    cred_tags.insert(STORED_AT_TAG.to_string(), stored_at.to_string());
+                }
+
                 self.wallet_service.update_record_tags(wallet_handle, self.wallet_service.add_prefix("Credential").as_str(), credential_record.get_id(), &cred_tags)?;
             }
         }
@@ -409,7 +433,12 @@ impl ProverCommandExecutor {
             None
         };
 
-        let cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol.as_ref())?;
+        let mut cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol.as_ref())?;
+
+        let stored_at = SystemTime::now().duration_since(UNIX_EPOCH)
+            .to_indy(IndyErrorKind::InvalidState, "Time has gone backwards")?;
+        cred_tags.insert(STORED_AT_TAG.to_string(), stored_at.as_millis().to_string());
+
         self.wallet_service.add_indy_object(wallet_handle, &out_cred_id, credential, &cred_tags)?;
 
         debug!("store_credential <<< out_cred_id: {:?}", out_cred_id);
@@ -526,6 +555,58 @@ impl ProverCommandExecutor {
                                      proof_request: &ProofRequest) -> IndyResult<String> {
         debug!("get_credentials_for_proof_req >>> wallet_handle: {:?}, proof_request: {:?}", wallet_handle, proof_request);
 
+        let credentials_for_proof_request = self._get_credentials_for_proof_req(wallet_handle, proof_request)?;
+
+        let credentials_for_proof_request_json = serde_json::to_string(&credentials_for_proof_request)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialsForProofRequest")?;
+
+        debug!("get_credentials_for_proof_req <<< credentials_for_proof_request_json: {:?}", credentials_for_proof_request_json);
+
+        Ok(credentials_for_proof_request_json)
+    }
+
+    fn select_credentials_for_proof_req(&self,
+                                        wallet_handle: WalletHandle,
+                                        proof_request: &ProofRequest,
+                                        policy: Option<&CredentialSelectionPolicy>) -> IndyResult<(String, String)> {
+        debug!("select_credentials_for_proof_req >>> wallet_handle: {:?}, proof_request: {:?}, policy: {:?}", wallet_handle, proof_request, policy);
+
+        let default_policy = CredentialSelectionPolicy::default();
+        let policy = policy.unwrap_or(&default_policy);
+
+        let credentials_for_proof_request = self._get_credentials_for_proof_req(wallet_handle, proof_request)?;
+
+        let stored_at = if policy.newest {
+            self._get_credentials_stored_at(wallet_handle, &credentials_for_proof_request)?
+        } else {
+            HashMap::new()
+        };
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)
+            .to_indy(IndyErrorKind::InvalidState, "Time has gone backwards")?
+            .as_secs();
+
+        let (requested_credentials, rev_states) =
+            self.anoncreds_service.prover.select_credentials_for_proof_req(proof_request.value(),
+                                                                           &credentials_for_proof_request,
+                                                                           &stored_at,
+                                                                           policy,
+                                                                           now)?;
+
+        let requested_credentials_json = serde_json::to_string(&requested_credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RequestedCredentials")?;
+
+        let rev_states_json = serde_json::to_string(&rev_states)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize required revocation states")?;
+
+        debug!("select_credentials_for_proof_req <<< requested_credentials_json: {:?}, rev_states_json: {:?}", requested_credentials_json, rev_states_json);
+
+        Ok((requested_credentials_json, rev_states_json))
+    }
+
+    fn _get_credentials_for_proof_req(&self,
+                                      wallet_handle: WalletHandle,
+                                      proof_request: &ProofRequest) -> IndyResult<CredentialsForProofRequest> {
         let proof_req = proof_request.value();
         let proof_req_version = proof_request.version();
 
@@ -561,12 +642,38 @@ impl ProverCommandExecutor {
             credentials_for_proof_request.predicates.insert(predicate_id.to_string(), credentials_for_predicate);
         }
 
-        let credentials_for_proof_request_json = serde_json::to_string(&credentials_for_proof_request)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialsForProofRequest")?;
+        Ok(credentials_for_proof_request)
+    }
 
-        debug!("get_credentials_for_proof_req <<< credentials_for_proof_request_json: {:?}", credentials_for_proof_request_json);
+    fn _get_credentials_stored_at(&self,
+                                  wallet_handle: WalletHandle,
+                                  credentials_for_proof_request: &CredentialsForProofRequest) -> IndyResult<HashMap<String, u64>> {
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": false,
+            "retrieveTags": true,
+        }).to_string();
 
-        Ok(credentials_for_proof_request_json)
+        let mut stored_at: HashMap<String, u64> = HashMap::new();
+
+        for credential in credentials_for_proof_request.attrs.values().chain(credentials_for_proof_request.predicates.values()).flatten() {
+            let cred_id = &credential.cred_info.referent;
+
+            if stored_at.contains_key(cred_id) {
+                continue;
+            }
+
+            // Credentials stored before the tag was introduced are considered the oldest ones
+            let record = self.wallet_service.get_indy_record::<Credential>(wallet_handle, cred_id, &options_json)?;
+            let timestamp = record.get_tags()
+                .and_then(|tags| tags.get(STORED_AT_TAG))
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0);
+
+            stored_at.insert(cred_id.to_string(), timestamp);
+        }
+
+        Ok(stored_at)
     }
 
     fn search_credentials_for_proof_req(&self,
@@ -6,18 +6,27 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use indy_api_types::domain::wallet::Tags;
 use crate::domain::anoncreds::schema::SchemaId;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
 use indy_api_types::errors::prelude::*;
-use indy_wallet::{WalletService, WalletRecord};
+use indy_wallet::WalletService;
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::ledger::LedgerCommand;
 use crate::domain::cache::{GetCacheOptions, PurgeOptions};
 use crate::domain::crypto::did::DidValue;
+use crate::services::cache::{CacheRecord, CacheService};
 
 use indy_utils::next_command_handle;
 
 const CRED_DEF_CACHE: &str = "cred_def_cache";
 const SCHEMA_CACHE: &str = "schema_cache";
+const NYM_CACHE: &str = "nym_cache";
+const ATTRIB_CACHE: &str = "attrib_cache";
+const REVOC_REG_DEF_CACHE: &str = "revoc_reg_def_cache";
+const REVOC_REG_DELTA_CACHE: &str = "revoc_reg_delta_cache";
+
+const TIMESTAMP_TAG: &str = "timestamp";
+const TXN_TIME_TAG: &str = "txnTime";
 
 pub enum CacheCommand {
     GetSchema(PoolHandle,
@@ -44,46 +53,109 @@ pub enum CacheCommand {
         GetCacheOptions,              // options
         CommandHandle,                          // cb_id
     ),
+    GetNym(PoolHandle,
+           WalletHandle,
+           DidValue, // submitter_did
+           DidValue, // target_did
+           GetCacheOptions, // options
+           Box<dyn Fn(IndyResult<String>) + Send>),
+    GetNymContinue(
+        WalletHandle,
+        String,                       // cache id
+        IndyResult<String>,           // ledger_response
+        GetCacheOptions,              // options
+        CommandHandle,                // cb_id
+    ),
+    GetAttrib(PoolHandle,
+              WalletHandle,
+              DidValue, // submitter_did
+              DidValue, // target_did
+              Option<String>, // raw
+              Option<String>, // hash
+              Option<String>, // enc
+              GetCacheOptions, // options
+              Box<dyn Fn(IndyResult<String>) + Send>),
+    GetAttribContinue(
+        WalletHandle,
+        String,                       // cache id
+        IndyResult<String>,           // ledger_response
+        GetCacheOptions,              // options
+        CommandHandle,                // cb_id
+    ),
+    GetRevocRegDef(PoolHandle,
+                   WalletHandle,
+                   DidValue, // submitter_did
+                   RevocationRegistryId, // id
+                   GetCacheOptions, // options
+                   Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocRegDefContinue(
+        WalletHandle,
+        IndyResult<(String, String)>, // ledger_response
+        GetCacheOptions,              // options
+        CommandHandle,                // cb_id
+    ),
+    GetRevocRegDelta(PoolHandle,
+                     WalletHandle,
+                     DidValue, // submitter_did
+                     RevocationRegistryId, // id
+                     Option<i64>, // from
+                     i64, // to
+                     GetCacheOptions, // options
+                     Box<dyn Fn(IndyResult<(String, u64)>) + Send>),
+    GetRevocRegDeltaContinue(
+        WalletHandle,
+        String,                               // cache id
+        IndyResult<(String, String, u64)>,    // ledger_response
+        GetCacheOptions,                      // options
+        CommandHandle,                        // cb_id
+    ),
     PurgeSchemaCache(WalletHandle,
                      PurgeOptions, // options
                      Box<dyn Fn(IndyResult<()>) + Send>),
     PurgeCredDefCache(WalletHandle,
                       PurgeOptions, // options
                       Box<dyn Fn(IndyResult<()>) + Send>),
+    PurgeNymCache(WalletHandle,
+                  PurgeOptions, // options
+                  Box<dyn Fn(IndyResult<()>) + Send>),
+    PurgeAttribCache(WalletHandle,
+                     PurgeOptions, // options
+                     Box<dyn Fn(IndyResult<()>) + Send>),
+    PurgeRevocRegDefCache(WalletHandle,
+                          PurgeOptions, // options
+                          Box<dyn Fn(IndyResult<()>) + Send>),
+    PurgeRevocRegDeltaCache(WalletHandle,
+                            PurgeOptions, // options
+                            Box<dyn Fn(IndyResult<()>) + Send>),
 }
 
 pub struct CacheCommandExecutor {
     wallet_service: Rc<WalletService>,
+    cache_service: Rc<CacheService>,
 
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    pending_delta_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, u64)>)>>>,
 }
 
 macro_rules! check_cache {
     ($cache: ident, $options: ident, $cb: ident) => {
-    if let Some(cache) = $cache {
-            let min_fresh = $options.min_fresh.unwrap_or(-1);
-            if min_fresh >= 0 {
-                let ts = match CacheCommandExecutor::get_seconds_since_epoch() {
-                    Ok(ts) => ts,
-                    Err(err) => {
-                        return $cb(Err(err))
-                    }
-                };
-                if ts - min_fresh <= cache.get_tags().unwrap_or(&Tags::new()).get("timestamp").unwrap_or(&"-1".to_string()).parse().unwrap_or(-1) {
-                    return $cb(Ok(cache.get_value().unwrap_or("").to_string()))
-                }
-            } else {
-                return $cb(Ok(cache.get_value().unwrap_or("").to_string()))
-            }
+        if let Some(cache) = $cache {
+            return $cb(Ok(cache.value));
+        }
+
+        if $options.no_update.unwrap_or(false) {
+            return $cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
         }
     };
 }
 
 impl CacheCommandExecutor {
-    pub fn new(wallet_service: Rc<WalletService>) -> CacheCommandExecutor {
+    pub fn new(wallet_service: Rc<WalletService>, cache_service: Rc<CacheService>) -> CacheCommandExecutor {
         CacheCommandExecutor {
             wallet_service,
+            cache_service,
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_delta_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -105,13 +177,61 @@ impl CacheCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "GetCredDefContinue command received");
                 self._get_cred_def_continue(wallet_handle, ledger_response, options, cb_id);
             }
+            CacheCommand::GetNym(pool_handle, wallet_handle, submitter_did, target_did, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetNym command received");
+                self.get_nym(pool_handle, wallet_handle, &submitter_did, &target_did, options, cb);
+            }
+            CacheCommand::GetNymContinue(wallet_handle, id, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetNymContinue command received");
+                self._get_nym_continue(wallet_handle, &id, ledger_response, options, cb_id);
+            }
+            CacheCommand::GetAttrib(pool_handle, wallet_handle, submitter_did, target_did, raw, hash, enc, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetAttrib command received");
+                self.get_attrib(pool_handle, wallet_handle, &submitter_did, &target_did, raw, hash, enc, options, cb);
+            }
+            CacheCommand::GetAttribContinue(wallet_handle, id, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetAttribContinue command received");
+                self._get_attrib_continue(wallet_handle, &id, ledger_response, options, cb_id);
+            }
+            CacheCommand::GetRevocRegDef(pool_handle, wallet_handle, submitter_did, id, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetRevocRegDef command received");
+                self.get_revoc_reg_def(pool_handle, wallet_handle, &submitter_did, &id, options, cb);
+            }
+            CacheCommand::GetRevocRegDefContinue(wallet_handle, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetRevocRegDefContinue command received");
+                self._get_revoc_reg_def_continue(wallet_handle, ledger_response, options, cb_id);
+            }
+            CacheCommand::GetRevocRegDelta(pool_handle, wallet_handle, submitter_did, id, from, to, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetRevocRegDelta command received");
+                self.get_revoc_reg_delta(pool_handle, wallet_handle, &submitter_did, &id, from, to, options, cb);
+            }
+            CacheCommand::GetRevocRegDeltaContinue(wallet_handle, id, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetRevocRegDeltaContinue command received");
+                self._get_revoc_reg_delta_continue(wallet_handle, &id, ledger_response, options, cb_id);
+            }
             CacheCommand::PurgeSchemaCache(wallet_handle, options, cb) => {
                 debug!(target: "non_secrets_command_executor", "PurgeSchemaCache command received");
-                cb(self.purge_schema_cache(wallet_handle, options));
+                cb(self.purge_cache(wallet_handle, options, SCHEMA_CACHE));
             }
             CacheCommand::PurgeCredDefCache(wallet_handle, options, cb) => {
                 debug!(target: "non_secrets_command_executor", "PurgeCredDefCache command received");
-                cb(self.purge_cred_def_cache(wallet_handle, options));
+                cb(self.purge_cache(wallet_handle, options, CRED_DEF_CACHE));
+            }
+            CacheCommand::PurgeNymCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeNymCache command received");
+                cb(self.purge_cache(wallet_handle, options, NYM_CACHE));
+            }
+            CacheCommand::PurgeAttribCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeAttribCache command received");
+                cb(self.purge_cache(wallet_handle, options, ATTRIB_CACHE));
+            }
+            CacheCommand::PurgeRevocRegDefCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeRevocRegDefCache command received");
+                cb(self.purge_cache(wallet_handle, options, REVOC_REG_DEF_CACHE));
+            }
+            CacheCommand::PurgeRevocRegDeltaCache(wallet_handle, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeRevocRegDeltaCache command received");
+                cb(self.purge_cache(wallet_handle, options, REVOC_REG_DELTA_CACHE));
             }
         }
     }
@@ -131,10 +251,6 @@ impl CacheCommandExecutor {
 
         check_cache!(cache, options, cb);
 
-        if options.no_update.unwrap_or(false) {
-            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
-        }
-
         let cb_id = next_command_handle();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
 
@@ -164,12 +280,12 @@ impl CacheCommandExecutor {
     fn _delete_and_add_record(&self,
                               wallet_handle: WalletHandle,
                               options: GetCacheOptions,
-                              schema_id: &str,
-                              schema_json: &str,
+                              id: &str,
+                              value: &str,
+                              mut tags: Tags,
                               which_cache: &str) -> IndyResult<()>
     {
         if !options.no_store.unwrap_or(false) {
-            let mut tags = Tags::new();
             let ts = match SystemTime::now().duration_since(UNIX_EPOCH) {
                 Ok(ts) => ts.as_secs() as i32,
                 Err(err) => {
@@ -177,9 +293,10 @@ impl CacheCommandExecutor {
                     0
                 }
             };
-            tags.insert("timestamp".to_string(), ts.to_string());
-            let _ignore = self.wallet_service.delete_record(wallet_handle, which_cache, &schema_id);
-            self.wallet_service.add_record(wallet_handle, which_cache, &schema_id, &schema_json, &tags)?
+            tags.insert(TIMESTAMP_TAG.to_string(), ts.to_string());
+            let _ignore = self.wallet_service.delete_record(wallet_handle, which_cache, &id);
+            self.wallet_service.add_record(wallet_handle, which_cache, &id, &value, &tags)?;
+            self.cache_service.put(wallet_handle, which_cache, id, CacheRecord::new(value.to_string(), tags));
         }
         Ok(())
     }
@@ -192,7 +309,7 @@ impl CacheCommandExecutor {
 
         let (schema_id, schema_json) = try_cb!(ledger_response, cb);
 
-        match self._delete_and_add_record(wallet_handle, options, &schema_id, &schema_json, SCHEMA_CACHE) {
+        match self._delete_and_add_record(wallet_handle, options, &schema_id, &schema_json, Tags::new(), SCHEMA_CACHE) {
             Ok(_) => cb(Ok(schema_json)),
             Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_schema_continue failed: {:?}", err))))
         }
@@ -213,10 +330,6 @@ impl CacheCommandExecutor {
 
         check_cache!(cache, options, cb);
 
-        if options.no_update.unwrap_or(false) {
-            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
-        }
-
         let cb_id = next_command_handle();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
 
@@ -243,18 +356,48 @@ impl CacheCommandExecutor {
         ).unwrap();
     }
 
-    fn get_record_from_cache(&self, wallet_handle: WalletHandle, id: &str, options: &GetCacheOptions, which_cache: &str) -> Result<Option<WalletRecord>, IndyError> {
-        if !options.no_cache.unwrap_or(false) {
-            let options_json = json!({
-                "retrieveType": false,
-                "retrieveValue": true,
-                "retrieveTags": true,
-            }).to_string();
-            match self.wallet_service.get_record(wallet_handle, which_cache, &id, &options_json) {
-                Ok(record) => Ok(Some(record)),
-                Err(err) => if err.kind() == IndyErrorKind::WalletItemNotFound { Ok(None) } else { Err(err) }
+    /// Looks up fresh enough record in the in-memory layer first and then in the wallet.
+    fn get_record_from_cache(&self, wallet_handle: WalletHandle, id: &str, options: &GetCacheOptions, which_cache: &str) -> IndyResult<Option<CacheRecord>> {
+        if options.no_cache.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let min_fresh = options.min_fresh.unwrap_or(-1);
+        let min_timestamp = if min_fresh >= 0 {
+            Some(CacheCommandExecutor::get_seconds_since_epoch()? - min_fresh)
+        } else { None };
+        let is_fresh = |record: &CacheRecord| min_timestamp.map(|ts| ts <= record.timestamp()).unwrap_or(true);
+
+        if let Some(record) = self.cache_service.get(wallet_handle, which_cache, id) {
+            if is_fresh(&record) {
+                self.cache_service.lru_hit(which_cache);
+                return Ok(Some(record));
+            }
+        }
+
+        let options_json = json!({
+            "retrieveType": false,
+            "retrieveValue": true,
+            "retrieveTags": true,
+        }).to_string();
+
+        let record = match self.wallet_service.get_record(wallet_handle, which_cache, &id, &options_json) {
+            Ok(record) => Some(CacheRecord::new(record.get_value().unwrap_or("").to_string(),
+                                                record.get_tags().cloned().unwrap_or_else(Tags::new))),
+            Err(err) => if err.kind() == IndyErrorKind::WalletItemNotFound { None } else { return Err(err) }
+        };
+
+        match record {
+            Some(record) if is_fresh(&record) => {
+                self.cache_service.wallet_hit(which_cache);
+                self.cache_service.put(wallet_handle, which_cache, id, record.clone());
+                Ok(Some(record))
+            }
+            _ => {
+                self.cache_service.miss(which_cache);
+                Ok(None)
             }
-        } else { Ok(None) }
+        }
     }
 
     fn _get_cred_def_continue(&self, wallet_handle: WalletHandle, ledger_response: IndyResult<(String, String)>, options: GetCacheOptions, cb_id: CommandHandle) {
@@ -262,12 +405,257 @@ impl CacheCommandExecutor {
 
         let (cred_def_id, cred_def_json) = try_cb!(ledger_response, cb);
 
-        match self._delete_and_add_record(wallet_handle, options, &cred_def_id, &cred_def_json, CRED_DEF_CACHE) {
+        match self._delete_and_add_record(wallet_handle, options, &cred_def_id, &cred_def_json, Tags::new(), CRED_DEF_CACHE) {
             Ok(_) => cb(Ok(cred_def_json)),
             Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_cred_def_continue failed: {:?}", err))))
         }
     }
 
+    fn get_nym(&self,
+               pool_handle: PoolHandle,
+               wallet_handle: WalletHandle,
+               submitter_did: &DidValue,
+               target_did: &DidValue,
+               options: GetCacheOptions,
+               cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("get_nym >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, target_did, options);
+
+        let id = target_did.0.clone();
+
+        let cache = self.get_record_from_cache(wallet_handle, &id, &options, NYM_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        check_cache!(cache, options, cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetNym(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    target_did.clone(),
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetNymContinue(
+                                    wallet_handle,
+                                    id.clone(),
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_nym_continue(&self, wallet_handle: WalletHandle, id: &str, ledger_response: IndyResult<String>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let nym_json = try_cb!(ledger_response, cb);
+
+        match self._delete_and_add_record(wallet_handle, options, id, &nym_json, Tags::new(), NYM_CACHE) {
+            Ok(_) => cb(Ok(nym_json)),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_nym_continue failed: {:?}", err))))
+        }
+    }
+
+    fn get_attrib(&self,
+                  pool_handle: PoolHandle,
+                  wallet_handle: WalletHandle,
+                  submitter_did: &DidValue,
+                  target_did: &DidValue,
+                  raw: Option<String>,
+                  hash: Option<String>,
+                  enc: Option<String>,
+                  options: GetCacheOptions,
+                  cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("get_attrib >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, target_did: {:?}, raw: {:?}, hash: {:?}, enc: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, target_did, raw, hash, enc, options);
+
+        let id = match (raw.as_ref(), hash.as_ref(), enc.as_ref()) {
+            (Some(raw), _, _) => format!("{}:raw:{}", target_did.0, raw),
+            (None, Some(hash), _) => format!("{}:hash:{}", target_did.0, hash),
+            (None, None, Some(enc)) => format!("{}:enc:{}", target_did.0, enc),
+            (None, None, None) => return cb(Err(err_msg(IndyErrorKind::InvalidStructure, "Either raw or hash or enc must be specified")))
+        };
+
+        let cache = self.get_record_from_cache(wallet_handle, &id, &options, ATTRIB_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        check_cache!(cache, options, cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetAttrib(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    target_did.clone(),
+                    raw,
+                    hash,
+                    enc,
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetAttribContinue(
+                                    wallet_handle,
+                                    id.clone(),
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_attrib_continue(&self, wallet_handle: WalletHandle, id: &str, ledger_response: IndyResult<String>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let attrib_data = try_cb!(ledger_response, cb);
+
+        match self._delete_and_add_record(wallet_handle, options, id, &attrib_data, Tags::new(), ATTRIB_CACHE) {
+            Ok(_) => cb(Ok(attrib_data)),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_attrib_continue failed: {:?}", err))))
+        }
+    }
+
+    fn get_revoc_reg_def(&self,
+                         pool_handle: PoolHandle,
+                         wallet_handle: WalletHandle,
+                         submitter_did: &DidValue,
+                         id: &RevocationRegistryId,
+                         options: GetCacheOptions,
+                         cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("get_revoc_reg_def >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, id, options);
+
+        let cache = self.get_record_from_cache(wallet_handle, &id.0, &options, REVOC_REG_DEF_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        check_cache!(cache, options, cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetRevocRegDef(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    id.clone(),
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetRevocRegDefContinue(
+                                    wallet_handle,
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_revoc_reg_def_continue(&self, wallet_handle: WalletHandle, ledger_response: IndyResult<(String, String)>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let (revoc_reg_def_id, revoc_reg_def_json) = try_cb!(ledger_response, cb);
+
+        match self._delete_and_add_record(wallet_handle, options, &revoc_reg_def_id, &revoc_reg_def_json, Tags::new(), REVOC_REG_DEF_CACHE) {
+            Ok(_) => cb(Ok(revoc_reg_def_json)),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_revoc_reg_def_continue failed: {:?}", err))))
+        }
+    }
+
+    fn get_revoc_reg_delta(&self,
+                           pool_handle: PoolHandle,
+                           wallet_handle: WalletHandle,
+                           submitter_did: &DidValue,
+                           id: &RevocationRegistryId,
+                           from: Option<i64>,
+                           to: i64,
+                           options: GetCacheOptions,
+                           cb: Box<dyn Fn(IndyResult<(String, u64)>) + Send>) {
+        trace!("get_revoc_reg_delta >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, from: {:?}, to: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, id, from, to, options);
+
+        // deltas are cached per requested interval
+        let cache_id = format!("{}:{}:{}", id.0, from.unwrap_or(-1), to);
+
+        let cache = self.get_record_from_cache(wallet_handle, &cache_id, &options, REVOC_REG_DELTA_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        if let Some(cache) = cache {
+            let txn_time = cache.tags.get(TXN_TIME_TAG)
+                .and_then(|txn_time| txn_time.parse().ok())
+                .unwrap_or(0);
+            return cb(Ok((cache.value, txn_time)));
+        }
+
+        if options.no_update.unwrap_or(false) {
+            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
+        }
+
+        let cb_id = next_command_handle();
+        self.pending_delta_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetRevocRegDelta(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    id.clone(),
+                    from,
+                    to,
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetRevocRegDeltaContinue(
+                                    wallet_handle,
+                                    cache_id.clone(),
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_revoc_reg_delta_continue(&self, wallet_handle: WalletHandle, id: &str, ledger_response: IndyResult<(String, String, u64)>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_delta_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let (_, revoc_reg_delta_json, txn_time) = try_cb!(ledger_response, cb);
+
+        let mut tags = Tags::new();
+        tags.insert(TXN_TIME_TAG.to_string(), txn_time.to_string());
+
+        match self._delete_and_add_record(wallet_handle, options, id, &revoc_reg_delta_json, tags, REVOC_REG_DELTA_CACHE) {
+            Ok(_) => cb(Ok((revoc_reg_delta_json, txn_time))),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_revoc_reg_delta_continue failed: {:?}", err))))
+        }
+    }
+
     fn get_seconds_since_epoch() -> Result<i32, IndyError> {
         match SystemTime::now().duration_since(UNIX_EPOCH) {
             Ok(ts) => Ok(ts.as_secs() as i32),
@@ -287,43 +675,19 @@ impl CacheCommandExecutor {
         }
     }
 
-    fn purge_schema_cache(&self,
-                          wallet_handle: WalletHandle,
-                          options: PurgeOptions) -> IndyResult<()> {
-        trace!("purge_schema_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
+    fn purge_cache(&self,
+                   wallet_handle: WalletHandle,
+                   options: PurgeOptions,
+                   which_cache: &str) -> IndyResult<()> {
+        trace!("purge_cache >>> wallet_handle: {:?}, options: {:?}, which_cache: {:?}", wallet_handle, options, which_cache);
 
         let max_age = options.max_age.unwrap_or(-1);
         let query_json = CacheCommandExecutor::build_query_json(max_age)?;
 
-        let options_json = json!({
-            "retrieveType": false,
-            "retrieveValue": false,
-            "retrieveTags": false,
-        }).to_string();
-
-        let mut search = self.wallet_service.search_records(
-            wallet_handle,
-            SCHEMA_CACHE,
-            &query_json,
-            &options_json,
-        )?;
-
-        while let Some(record) = search.fetch_next_record()? {
-            self.wallet_service.delete_record(wallet_handle, SCHEMA_CACHE, record.get_id())?;
-        }
-
-        trace!("purge_schema_cache <<< res: ()");
-
-        Ok(())
-    }
-
-    fn purge_cred_def_cache(&self,
-                            wallet_handle: WalletHandle,
-                            options: PurgeOptions) -> IndyResult<()> {
-        trace!("purge_cred_def_cache >>> wallet_handle: {:?}, options: {:?}", wallet_handle, options);
-
-        let max_age = options.max_age.unwrap_or(-1);
-        let query_json = CacheCommandExecutor::build_query_json(max_age)?;
+        let older_than = if max_age >= 0 {
+            Some(CacheCommandExecutor::get_seconds_since_epoch()? - max_age)
+        } else { None };
+        self.cache_service.purge(wallet_handle, which_cache, older_than);
 
         let options_json = json!({
             "retrieveType": false,
@@ -333,16 +697,16 @@ impl CacheCommandExecutor {
 
         let mut search = self.wallet_service.search_records(
             wallet_handle,
-            CRED_DEF_CACHE,
+            which_cache,
             &query_json,
             &options_json,
         )?;
 
         while let Some(record) = search.fetch_next_record()? {
-            self.wallet_service.delete_record(wallet_handle, CRED_DEF_CACHE, record.get_id())?;
+            self.wallet_service.delete_record(wallet_handle, which_cache, record.get_id())?;
         }
 
-        trace!("purge_cred_def_cache <<< res: ()");
+        trace!("purge_cache <<< res: ()");
 
         Ok(())
     }
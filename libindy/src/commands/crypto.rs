@@ -1,22 +1,33 @@
 use std::collections::HashMap;
 
+use crate::domain::crypto::did::{Did, DidValue, TheirDid};
 use crate::domain::crypto::key::{Key, KeyInfo, KeyMetadata};
 use crate::domain::crypto::pack::*;
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
+use crate::services::crypto::key_manager::PluggedKeyManager;
 use indy_wallet::{RecordOptions, WalletService};
 
 use std::rc::Rc;
 use std::str;
 use indy_utils::crypto::base64;
 use indy_utils::crypto::chacha20poly1305_ietf;
+use indy_utils::crypto::ed25519_box;
 use crate::domain::crypto::combo_box::ComboBox;
+use crate::utils::crypto::did_key;
+use crate::utils::crypto::jose;
 use indy_api_types::WalletHandle;
+use indy_api_types::key_manager::*;
+use sha2::{Digest, Sha256};
 
 pub const PROTECTED_HEADER_ENC: &str = "xchacha20poly1305_ietf";
 pub const PROTECTED_HEADER_TYP: &str = "JWM/1.0";
 pub const PROTECTED_HEADER_ALG_AUTH: &str = "Authcrypt";
 pub const PROTECTED_HEADER_ALG_ANON: &str = "Anoncrypt";
+pub const DIDCOMM_V2_ENCRYPTED_TYP: &str = "application/didcomm-encrypted+json";
+
+// Id of the only key of DID in DID URL key ids
+const KEY_ID_FRAGMENT: &str = "key-1";
 
 pub enum CryptoCommand {
     CreateKey(
@@ -83,6 +94,30 @@ pub enum CryptoCommand {
         WalletHandle,
         Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
     ),
+    PackMessageV2(
+        Vec<u8>, // plaintext message
+        Vec<String>,  // list of receiver's kids
+        Option<String>,  // sender's kid
+        PackOptionsV2,
+        WalletHandle,
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    UnpackMessageV2(
+        JWEV2,
+        WalletHandle,
+        Box<dyn Fn(IndyResult<Vec<u8>>) + Send>,
+    ),
+    RegisterKeyManager(
+        String, // name
+        KeyManagerCreateKey,
+        KeyManagerSign,
+        KeyManagerCryptoBox,
+        KeyManagerCryptoBoxOpen,
+        KeyManagerCryptoBoxSealOpen,
+        KeyManagerKeyAgreement,
+        KeyManagerFree,
+        Box<dyn Fn(IndyResult<()>) + Send>,
+    ),
 }
 
 pub struct CryptoCommandExecutor {
@@ -147,9 +182,42 @@ impl CryptoCommandExecutor {
                 debug!("UnpackMessage command received");
                 cb(self.unpack_msg(jwe_json, wallet_handle));
             }
+            CryptoCommand::PackMessageV2(message, receivers, sender_kid, options, wallet_handle, cb) => {
+                debug!("PackMessageV2 command received");
+                cb(self.pack_msg_v2(message, receivers, sender_kid, options, wallet_handle));
+            }
+            CryptoCommand::UnpackMessageV2(jwe, wallet_handle, cb) => {
+                debug!("UnpackMessageV2 command received");
+                cb(self.unpack_msg_v2(jwe, wallet_handle));
+            }
+            CryptoCommand::RegisterKeyManager(name, create_key, sign, crypto_box, crypto_box_open, crypto_box_seal_open, key_agreement, free, cb) => {
+                debug!("RegisterKeyManager command received");
+                cb(self.register_key_manager(&name, create_key, sign, crypto_box, crypto_box_open, crypto_box_seal_open, key_agreement, free));
+            }
         };
     }
 
+    fn register_key_manager(&self,
+                            name: &str,
+                            create_key: KeyManagerCreateKey,
+                            sign: KeyManagerSign,
+                            crypto_box: KeyManagerCryptoBox,
+                            crypto_box_open: KeyManagerCryptoBoxOpen,
+                            crypto_box_seal_open: KeyManagerCryptoBoxSealOpen,
+                            key_agreement: KeyManagerKeyAgreement,
+                            free: KeyManagerFree) -> IndyResult<()> {
+        debug!("register_key_manager >>> name: {:?}", name);
+
+        let key_manager = PluggedKeyManager::new(create_key, sign, crypto_box, crypto_box_open,
+                                                 crypto_box_seal_open, key_agreement, free);
+
+        self.crypto_service.register_key_manager(name, Box::new(key_manager))?;
+
+        debug!("register_key_manager <<<");
+
+        Ok(())
+    }
+
     fn create_key(&self, wallet_handle: WalletHandle, key_info: &KeyInfo) -> IndyResult<String> {
         debug!(
             "create_key >>> wallet_handle: {:?}, key_info: {:?}",
@@ -621,4 +689,202 @@ impl CryptoCommandExecutor {
         Ok((None, cek))
     }
 
+
+    pub fn pack_msg_v2(
+        &self,
+        message: Vec<u8>,
+        receiver_kids: Vec<String>,
+        sender_kid: Option<String>,
+        options: PackOptionsV2,
+        wallet_handle: WalletHandle,
+    ) -> IndyResult<Vec<u8>> {
+        trace!("pack_msg_v2 >>> receiver_kids: {:?}, sender_kid: {:?}, options: {:?}, wallet_handle: {:?}",
+               receiver_kids, sender_kid, options, wallet_handle);
+
+        if receiver_kids.is_empty() {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "No receiver keys found"));
+        }
+
+        let receivers = receiver_kids
+            .iter()
+            .map(|kid| {
+                let vk = self._resolve_kid(wallet_handle, kid)?;
+                Ok((kid.to_string(), self.crypto_service.vk_to_x25519(&vk)?))
+            })
+            .collect::<IndyResult<Vec<(String, ed25519_box::PublicKey)>>>()?;
+
+        let sender_key: Option<Key> = match sender_kid {
+            Some(ref sender_kid) => {
+                let sender_vk = self._resolve_kid(wallet_handle, sender_kid)?;
+                Some(self.wallet_service.get_indy_object(wallet_handle, &sender_vk, &RecordOptions::id_value())?)
+            }
+            None => None
+        };
+
+        let alg = if sender_kid.is_some() { KeyAgreementAlgorithm::Ecdh1puA256kw } else { KeyAgreementAlgorithm::EcdhEsA256kw };
+        let (epk, esk) = ed25519_box::gen_keypair();
+
+        let apu = sender_kid.as_ref().map(|kid| kid.as_bytes().to_vec()).unwrap_or_default();
+        let apv = _apv(&receiver_kids);
+
+        let protected = ProtectedV2 {
+            typ: DIDCOMM_V2_ENCRYPTED_TYP.to_string(),
+            alg,
+            enc: options.enc,
+            epk: EphemeralPublicKey {
+                kty: "OKP".to_string(),
+                crv: "X25519".to_string(),
+                x: base64::encode_urlsafe_nopad(&epk[..]),
+            },
+            apu: sender_kid.as_ref().map(|_| base64::encode_urlsafe_nopad(&apu)),
+            skid: sender_kid,
+            apv: base64::encode_urlsafe_nopad(&apv),
+        };
+
+        let protected = serde_json::to_string(&protected)
+            .to_indy(IndyErrorKind::InvalidState, "Failed to serialize protected header")?;
+        let protected = base64::encode_urlsafe_nopad(protected.as_bytes());
+
+        let cek = jose::gen_cek();
+        let (iv, ciphertext, tag) = options.enc.encrypt(&cek, &message, protected.as_bytes())?;
+
+        let mut recipients = Vec::with_capacity(receivers.len());
+
+        for (kid, pk) in receivers {
+            let mut z = ed25519_box::scalarmult(&esk, &pk)?;
+            if let Some(ref sender_key) = sender_key {
+                z.extend(self.crypto_service.key_agreement(sender_key, &pk)?);
+            }
+
+            let kek = jose::concat_kdf(&z, alg.name(), &apu, &apv, _cc_tag(alg, &tag));
+
+            recipients.push(RecipientV2 {
+                header: HeaderV2 { kid },
+                encrypted_key: base64::encode_urlsafe_nopad(&jose::aes_key_wrap(&kek, &cek)?),
+            });
+        }
+
+        let jwe = JWEV2 {
+            protected,
+            recipients,
+            iv: base64::encode_urlsafe_nopad(&iv),
+            ciphertext: base64::encode_urlsafe_nopad(&ciphertext),
+            tag: base64::encode_urlsafe_nopad(&tag),
+        };
+
+        let res = serde_json::to_vec(&jwe)
+            .to_indy(IndyErrorKind::InvalidState, "Failed to serialize JWE")?;
+
+        trace!("pack_msg_v2 <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    pub fn unpack_msg_v2(&self, jwe: JWEV2, wallet_handle: WalletHandle) -> IndyResult<Vec<u8>> {
+        trace!("unpack_msg_v2 >>> jwe: {:?}, wallet_handle: {:?}", jwe, wallet_handle);
+
+        let protected: ProtectedV2 = serde_json::from_slice(&base64::decode_urlsafe(&jwe.protected)?)
+            .to_indy(IndyErrorKind::InvalidStructure, "Failed to deserialize protected header")?;
+
+        if protected.epk.kty != "OKP" || protected.epk.crv != "X25519" {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported ephemeral key: {} {}", protected.epk.kty, protected.epk.crv)));
+        }
+
+        let epk = ed25519_box::PublicKey::from_slice(&base64::decode_urlsafe(&protected.epk.x)?)?;
+
+        let (recipient, my_key) = jwe.recipients
+            .iter()
+            .filter_map(|recipient| {
+                self._resolve_kid(wallet_handle, &recipient.header.kid).ok()
+                    .and_then(|vk| self.wallet_service.get_indy_object::<Key>(wallet_handle, &vk, &RecordOptions::id_value()).ok())
+                    .map(|key| (recipient, key))
+            })
+            .next()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "No recipient key found in the wallet"))?;
+
+        let mut z = self.crypto_service.key_agreement(&my_key, &epk)?;
+
+        match (protected.alg, protected.skid.as_ref()) {
+            (KeyAgreementAlgorithm::Ecdh1puA256kw, Some(skid)) => {
+                let sender_vk = self._resolve_kid(wallet_handle, skid)?;
+                let sender_pk = self.crypto_service.vk_to_x25519(&sender_vk)?;
+                z.extend(self.crypto_service.key_agreement(&my_key, &sender_pk)?);
+            }
+            (KeyAgreementAlgorithm::Ecdh1puA256kw, None) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Sender key id (skid) is required for ECDH-1PU")),
+            // Anonymous encryption doesn't authenticate the sender, so any sender key id would be forged
+            (KeyAgreementAlgorithm::EcdhEsA256kw, Some(_)) =>
+                return Err(err_msg(IndyErrorKind::InvalidStructure, "Sender key id (skid) is not allowed for ECDH-ES")),
+            (KeyAgreementAlgorithm::EcdhEsA256kw, None) => {}
+        }
+
+        let apu = match protected.apu {
+            Some(ref apu) => base64::decode_urlsafe(apu)?,
+            None => Vec::new()
+        };
+        let apv = base64::decode_urlsafe(&protected.apv)?;
+        let tag = base64::decode_urlsafe(&jwe.tag)?;
+
+        let kek = jose::concat_kdf(&z, protected.alg.name(), &apu, &apv, _cc_tag(protected.alg, &tag));
+        let cek = jose::aes_key_unwrap(&kek, &base64::decode_urlsafe(&recipient.encrypted_key)?)?;
+
+        let message = protected.enc.decrypt(&cek,
+                                            &base64::decode_urlsafe(&jwe.iv)?,
+                                            &base64::decode_urlsafe(&jwe.ciphertext)?,
+                                            &tag,
+                                            jwe.protected.as_bytes())?;
+
+        let res = UnpackMessageV2 {
+            message: String::from_utf8(message)
+                .to_indy(IndyErrorKind::InvalidStructure, "Failed to convert message to UTF-8")?,
+            recipient_kid: recipient.header.kid.clone(),
+            sender_kid: protected.skid,
+        };
+
+        let res = serde_json::to_vec(&res)
+            .to_indy(IndyErrorKind::InvalidState, "Failed to serialize message")?;
+
+        trace!("unpack_msg_v2 <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    // Resolves key id to verkey. Key id is either verkey or DID URL of my or their DID stored in the wallet.
+    // As DIDs have the only key, DID URL fragment must be `key-1`, multibase fingerprint or verkey of this key.
+    fn _resolve_kid(&self, wallet_handle: WalletHandle, kid: &str) -> IndyResult<String> {
+        let (did, fragment) = match kid.find('#') {
+            Some(pos) => (&kid[..pos], &kid[pos + 1..]),
+            None => return Ok(kid.to_string())
+        };
+
+        let verkey = match did_key::resolve_verkey(&DidValue(did.to_string())) {
+            Some(verkey) => verkey?,
+            None => match self.wallet_service.get_indy_object::<Did>(wallet_handle, did, &RecordOptions::id_value()) {
+                Ok(my_did) => my_did.verkey,
+                Err(_) => self.wallet_service.get_indy_object::<TheirDid>(wallet_handle, did, &RecordOptions::id_value())?.verkey
+            }
+        };
+
+        if fragment != KEY_ID_FRAGMENT && fragment != verkey && fragment != did_key::fingerprint(&verkey)? {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("DID URL fragment doesn't identify the key of DID: {}", kid)));
+        }
+
+        Ok(verkey)
+    }
+}
+
+// DIDComm v2 sets apv to hash of sorted recipient key ids joined with '.'
+fn _apv(receiver_kids: &[String]) -> Vec<u8> {
+    let mut kids = receiver_kids.to_vec();
+    kids.sort();
+
+    Sha256::digest(kids.join(".").as_bytes()).to_vec()
+}
+
+// ECDH-1PU in key wrapping mode binds the content authentication tag into key derivation
+fn _cc_tag(alg: KeyAgreementAlgorithm, tag: &[u8]) -> Option<&[u8]> {
+    match alg {
+        KeyAgreementAlgorithm::Ecdh1puA256kw => Some(tag),
+        KeyAgreementAlgorithm::EcdhEsA256kw => None,
+    }
 }
@@ -15,6 +15,7 @@ use crate::domain::pairwise::Pairwise;
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
+use crate::utils::crypto::did_key;
 use indy_wallet::{RecordOptions, SearchOptions, WalletService};
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
 use indy_utils::next_command_handle;
@@ -231,6 +232,10 @@ impl DidCommandExecutor {
 
         self.crypto_service.validate_did(my_did)?;
 
+        if did_key::is_self_certifying(my_did) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Keys of self-certifying DID can't be replaced: {}", my_did.0)));
+        }
+
         let my_did = self._wallet_get_my_did(wallet_handle, my_did)?;
 
         let temporary_key = self.crypto_service.create_key(&key_info)?;
@@ -376,6 +381,12 @@ impl DidCommandExecutor {
 
         try_cb!(self.crypto_service.validate_did(&did), cb);
 
+        // did:key and did:peer contain the key itself
+        if let Some(res) = did_key::resolve_verkey(&did) {
+            debug!("key_for_did <<< res: {:?}", res);
+            return cb(res);
+        }
+
         // Look to my did
         match self._wallet_get_my_did(wallet_handle, &did) {
             Ok(my_did) => return cb(Ok(my_did.verkey)),
@@ -409,6 +420,12 @@ impl DidCommandExecutor {
 
         self.crypto_service.validate_did(&did)?;
 
+        // did:key and did:peer contain the key itself
+        if let Some(res) = did_key::resolve_verkey(did) {
+            debug!("key_for_local_did <<< res: {:?}", res);
+            return res;
+        }
+
         // Look to my did
         match self._wallet_get_my_did(wallet_handle, did) {
             Ok(my_did) => return Ok(my_did.verkey),
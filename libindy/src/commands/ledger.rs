@@ -1,11 +1,13 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::rc::Rc;
 use std::string::ToString;
 
-use indy_api_types::{CommandHandle, PoolHandle, WalletHandle};
+use indy_api_types::{CommandHandle, PoolHandle, SearchHandle, WalletHandle};
 use indy_api_types::errors::prelude::*;
-use indy_utils::next_command_handle;
+use indy_utils::{next_command_handle, next_search_handle};
 use rust_base58::ToBase58;
 use serde_json;
 use serde_json::Value;
@@ -21,14 +23,17 @@ use crate::domain::anoncreds::schema::{Schema, SchemaId, SchemaV1};
 use crate::domain::crypto::did::{Did, DidValue};
 use crate::domain::crypto::key::Key;
 use crate::domain::ledger::auth_rule::{AuthRules, Constraint};
+use crate::domain::ledger::did_indy::{IndyDidUrl, IndyObjectId};
 use crate::domain::ledger::author_agreement::{AcceptanceMechanisms, GetTxnAuthorAgreementData};
 use crate::domain::ledger::node::NodeOperationData;
 use crate::domain::ledger::pool::Schedule;
 use crate::domain::ledger::request::Request;
+use crate::domain::ledger::txn::DownloadLedgerTxnsOptions;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
 use crate::services::pool::{
     parse_response_metadata,
+    LedgerTxnsReader,
     PoolService
 };
 use crate::utils::crypto::signature_serializer::serialize_signature;
@@ -239,6 +244,111 @@ pub enum LedgerCommand {
         IndyResult<String>,
         CommandHandle,
     ),
+    GetNym(
+        PoolHandle,
+        Option<DidValue>,
+        DidValue, // target did
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    GetNymContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    GetAttrib(
+        PoolHandle,
+        Option<DidValue>,
+        DidValue, // target did
+        Option<String>, // raw
+        Option<String>, // hash
+        Option<String>, // enc
+        Box<dyn Fn(IndyResult<String>) + Send>,
+    ),
+    GetAttribContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    GetRevocRegDef(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        BoxedCallbackStringStringSend,
+    ),
+    GetRevocRegDefContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    GetRevocRegDelta(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        Option<i64>, // from
+        i64, // to
+        Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>,
+    ),
+    GetRevocRegDeltaContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    Resolve(
+        IndyDidUrl, // id
+        BoxedCallbackStringStringSend,
+    ),
+    ResolveContinue(
+        IndyDidUrl, // id
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    DownloadLedgerTxns(
+        PoolHandle,
+        Option<String>, // ledger type
+        i32, // from
+        i32, // to
+        DownloadLedgerTxnsOptions,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    DownloadLedgerTxnsContinue(
+        PoolHandle,
+        u8, // ledger id
+        i32, // from
+        i32, // to
+        DownloadLedgerTxnsOptions,
+        IndyResult<String>, // ledger sync result
+        CommandHandle,
+    ),
+    OpenLedgerTxnsSearch(
+        PoolHandle,
+        Option<String>, // ledger type
+        i32, // from
+        i32, // to
+        Box<dyn Fn(IndyResult<SearchHandle>) + Send>),
+    OpenLedgerTxnsSearchContinue(
+        PoolHandle,
+        u8, // ledger id
+        i32, // from
+        i32, // to
+        IndyResult<String>, // ledger sync result
+        CommandHandle,
+    ),
+    FetchLedgerTxnsSearchNextBatch(
+        SearchHandle,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CloseLedgerTxnsSearch(
+        SearchHandle,
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    CreatePoolSnapshot(
+        PoolHandle,
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CreatePoolSnapshotContinue(
+        PoolHandle,
+        IndyResult<String>, // GET_TXN response
+        CommandHandle,
+    ),
+    CreatePoolSnapshotSynced(
+        PoolHandle,
+        usize, // signed ledger size
+        Value, // multi-signature
+        IndyResult<String>, // ledger sync result
+        CommandHandle,
+    ),
     BuildTxnAuthorAgreementRequest(
         DidValue, // submitter did
         Option<String>, // text
@@ -293,6 +403,10 @@ pub struct LedgerCommandExecutor {
 
     send_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String)>)>>>,
+    pending_string_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
+    pending_revoc_reg_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String, u64)>)>>>,
+    pending_search_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<SearchHandle>)>>>,
+    ledger_txns_searches: RefCell<HashMap<SearchHandle, LedgerTxnsReader>>,
 }
 
 impl LedgerCommandExecutor {
@@ -307,6 +421,10 @@ impl LedgerCommandExecutor {
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_string_callbacks: RefCell::new(HashMap::new()),
+            pending_revoc_reg_callbacks: RefCell::new(HashMap::new()),
+            pending_search_callbacks: RefCell::new(HashMap::new()),
+            ledger_txns_searches: RefCell::new(HashMap::new()),
         }
     }
 
@@ -499,6 +617,86 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "GetCredDefContinue command received");
                 self._get_cred_def_continue(id, pool_response, cb_id);
             }
+            LedgerCommand::GetNym(pool_handle, submitter_did, target_did, cb) => {
+                debug!(target: "ledger_command_executor", "GetNym command received");
+                self.get_nym(pool_handle, submitter_did.as_ref(), &target_did, cb);
+            }
+            LedgerCommand::GetNymContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetNymContinue command received");
+                self._get_nym_continue(pool_response, cb_id);
+            }
+            LedgerCommand::GetAttrib(pool_handle, submitter_did, target_did, raw, hash, enc, cb) => {
+                debug!(target: "ledger_command_executor", "GetAttrib command received");
+                self.get_attrib(pool_handle, submitter_did.as_ref(), &target_did,
+                                raw.as_ref().map(String::as_str),
+                                hash.as_ref().map(String::as_str),
+                                enc.as_ref().map(String::as_str),
+                                cb);
+            }
+            LedgerCommand::GetAttribContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetAttribContinue command received");
+                self._get_attrib_continue(pool_response, cb_id);
+            }
+            LedgerCommand::GetRevocRegDef(pool_handle, submitter_did, id, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDef command received");
+                self.get_revoc_reg_def(pool_handle, submitter_did.as_ref(), &id, cb);
+            }
+            LedgerCommand::GetRevocRegDefContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDefContinue command received");
+                self._get_revoc_reg_def_continue(pool_response, cb_id);
+            }
+            LedgerCommand::GetRevocRegDelta(pool_handle, submitter_did, id, from, to, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDelta command received");
+                self.get_revoc_reg_delta(pool_handle, submitter_did.as_ref(), &id, from, to, cb);
+            }
+            LedgerCommand::GetRevocRegDeltaContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDeltaContinue command received");
+                self._get_revoc_reg_delta_continue(pool_response, cb_id);
+            }
+            LedgerCommand::Resolve(id, cb) => {
+                debug!(target: "ledger_command_executor", "Resolve command received");
+                self.resolve(id, cb);
+            }
+            LedgerCommand::ResolveContinue(id, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "ResolveContinue command received");
+                self._resolve_continue(id, pool_response, cb_id);
+            }
+            LedgerCommand::DownloadLedgerTxns(pool_handle, ledger_type, from, to, options, cb) => {
+                debug!(target: "ledger_command_executor", "DownloadLedgerTxns command received");
+                self.download_ledger_txns(pool_handle, ledger_type.as_ref().map(String::as_str), from, to, options, cb);
+            }
+            LedgerCommand::DownloadLedgerTxnsContinue(pool_handle, ledger_id, from, to, options, sync_result, cb_id) => {
+                debug!(target: "ledger_command_executor", "DownloadLedgerTxnsContinue command received");
+                self._download_ledger_txns_continue(pool_handle, ledger_id, from, to, &options, sync_result, cb_id);
+            }
+            LedgerCommand::OpenLedgerTxnsSearch(pool_handle, ledger_type, from, to, cb) => {
+                debug!(target: "ledger_command_executor", "OpenLedgerTxnsSearch command received");
+                self.open_ledger_txns_search(pool_handle, ledger_type.as_ref().map(String::as_str), from, to, cb);
+            }
+            LedgerCommand::OpenLedgerTxnsSearchContinue(pool_handle, ledger_id, from, to, sync_result, cb_id) => {
+                debug!(target: "ledger_command_executor", "OpenLedgerTxnsSearchContinue command received");
+                self._open_ledger_txns_search_continue(pool_handle, ledger_id, from, to, sync_result, cb_id);
+            }
+            LedgerCommand::FetchLedgerTxnsSearchNextBatch(search_handle, cb) => {
+                debug!(target: "ledger_command_executor", "FetchLedgerTxnsSearchNextBatch command received");
+                cb(self.fetch_ledger_txns_search_next_batch(search_handle));
+            }
+            LedgerCommand::CloseLedgerTxnsSearch(search_handle, cb) => {
+                debug!(target: "ledger_command_executor", "CloseLedgerTxnsSearch command received");
+                cb(self.close_ledger_txns_search(search_handle));
+            }
+            LedgerCommand::CreatePoolSnapshot(pool_handle, cb) => {
+                debug!(target: "ledger_command_executor", "CreatePoolSnapshot command received");
+                self.create_pool_snapshot(pool_handle, cb);
+            }
+            LedgerCommand::CreatePoolSnapshotContinue(pool_handle, pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "CreatePoolSnapshotContinue command received");
+                self._create_pool_snapshot_continue(pool_handle, pool_response, cb_id);
+            }
+            LedgerCommand::CreatePoolSnapshotSynced(pool_handle, ledger_size, multi_signature, sync_result, cb_id) => {
+                debug!(target: "ledger_command_executor", "CreatePoolSnapshotSynced command received");
+                self._create_pool_snapshot_synced(pool_handle, ledger_size, multi_signature, sync_result, cb_id);
+            }
             LedgerCommand::BuildTxnAuthorAgreementRequest(submitter_did, text, version, ratification_ts, retirement_ts, cb) => {
                 debug!(target: "ledger_command_executor", "BuildTxnAuthorAgreementRequest command received");
                 cb(self.build_txn_author_agreement_request(&submitter_did, text.as_ref().map(String::as_str), &version, ratification_ts, retirement_ts));
@@ -1324,6 +1522,434 @@ impl LedgerCommandExecutor {
         cb(self.ledger_service.parse_get_cred_def_response(&pool_response, id.get_method().as_ref().map(String::as_str)))
     }
 
+    fn get_nym(&self, pool_handle: PoolHandle, submitter_did: Option<&DidValue>, target_did: &DidValue, cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        let request_json = try_cb!(self.build_get_nym_request(submitter_did, target_did), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_string_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetNymContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_nym_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_string_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.parse_get_nym_response(&pool_response))
+    }
+
+    fn get_attrib(&self,
+                  pool_handle: PoolHandle,
+                  submitter_did: Option<&DidValue>,
+                  target_did: &DidValue,
+                  raw: Option<&str>,
+                  hash: Option<&str>,
+                  enc: Option<&str>,
+                  cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        let request_json = try_cb!(self.build_get_attrib_request(submitter_did, target_did, raw, hash, enc), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_string_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetAttribContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_attrib_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_string_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.ledger_service.parse_get_attrib_response(&pool_response))
+    }
+
+    fn get_revoc_reg_def(&self, pool_handle: PoolHandle, submitter_did: Option<&DidValue>, id: &RevocationRegistryId, cb: BoxedCallbackStringStringSend) {
+        let request_json = try_cb!(self.build_get_revoc_reg_def_request(submitter_did, id), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegDefContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_def_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.parse_revoc_reg_def_response(&pool_response))
+    }
+
+    fn get_revoc_reg_delta(&self,
+                           pool_handle: PoolHandle,
+                           submitter_did: Option<&DidValue>,
+                           id: &RevocationRegistryId,
+                           from: Option<i64>,
+                           to: i64,
+                           cb: Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>) {
+        let request_json = try_cb!(self.build_get_revoc_reg_delta_request(submitter_did, id, from, to), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_revoc_reg_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegDeltaContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_delta_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_revoc_reg_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.parse_revoc_reg_delta_response(&pool_response))
+    }
+
+    fn resolve(&self, id: IndyDidUrl, cb: BoxedCallbackStringStringSend) {
+        debug!("resolve >>> id: {:?}", id);
+
+        let (namespace, object_id) = match (id.namespace(), id.object_id()) {
+            (Some(namespace), Some(object_id)) => (namespace, object_id),
+            _ => return cb(Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported did:indy identifier: {}", id.0))))
+        };
+
+        let pool_handle = try_cb!(self.pool_service.get_handle_by_namespace(&namespace), cb);
+
+        let request_json = match object_id {
+            IndyObjectId::Nym(ref did) => self.build_get_nym_request(None, did),
+            IndyObjectId::Schema(ref schema_id) => self.build_get_schema_request(None, schema_id),
+            IndyObjectId::CredDef(ref cred_def_id) => self.build_get_cred_def_request(None, cred_def_id),
+        };
+        let request_json = try_cb!(request_json, cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::ResolveContinue(
+                        id.clone(),
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _resolve_continue(&self, id: IndyDidUrl, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+
+        let object_json = match id.object_id() {
+            Some(IndyObjectId::Nym(_)) => self.parse_get_nym_response(&pool_response),
+            Some(IndyObjectId::Schema(_)) => self.ledger_service.parse_get_schema_response(&pool_response, None).map(|(_, json)| json),
+            Some(IndyObjectId::CredDef(_)) => self.ledger_service.parse_get_cred_def_response(&pool_response, None).map(|(_, json)| json),
+            None => Err(err_msg(IndyErrorKind::InvalidState, "Resolved object type is unknown")),
+        };
+
+        debug!("resolve <<< id: {:?}, object_json: {:?}", id, object_json);
+
+        cb(object_json.map(|object_json| (id.0, object_json)))
+    }
+
+    fn download_ledger_txns(&self,
+                            pool_handle: PoolHandle,
+                            ledger_type: Option<&str>,
+                            from: i32,
+                            to: i32,
+                            options: DownloadLedgerTxnsOptions,
+                            cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("download_ledger_txns >>> pool_handle: {:?}, ledger_type: {:?}, from: {:?}, to: {:?}, options: {:?}",
+               pool_handle, ledger_type, from, to, options);
+
+        let (ledger_id, cmd_id) = try_cb!(self._sync_ledger_range(pool_handle, ledger_type, from, to), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_string_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.send_callbacks.borrow_mut().insert(cmd_id, Box::new(move |sync_result| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::DownloadLedgerTxnsContinue(
+                        pool_handle,
+                        ledger_id,
+                        from,
+                        to,
+                        options.clone(),
+                        sync_result,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _download_ledger_txns_continue(&self,
+                                      pool_handle: PoolHandle,
+                                      ledger_id: u8,
+                                      from: i32,
+                                      to: i32,
+                                      options: &DownloadLedgerTxnsOptions,
+                                      sync_result: IndyResult<String>,
+                                      cb_id: CommandHandle) {
+        let cb = self.pending_string_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let sync_result = try_cb!(sync_result, cb);
+
+        let res = self._download_ledger_txns(pool_handle, ledger_id, from, to, options, &sync_result);
+
+        debug!("download_ledger_txns <<< res: {:?}", res);
+
+        cb(res)
+    }
+
+    fn _download_ledger_txns(&self,
+                             pool_handle: PoolHandle,
+                             ledger_id: u8,
+                             from: i32,
+                             to: i32,
+                             options: &DownloadLedgerTxnsOptions,
+                             sync_result: &str) -> IndyResult<String> {
+        let (from, to) = _synced_ledger_range(ledger_id, from, to, sync_result)?;
+
+        let res = match options.file {
+            Some(ref file) => {
+                let mut reader = self.pool_service.open_ledger_txns_reader(pool_handle, ledger_id, from, to)?;
+
+                let mut file_ = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(file)
+                    .to_indy(IndyErrorKind::IOError, format!("Can't open file {}", file))?;
+
+                loop {
+                    let txns = self.pool_service.read_ledger_txns(&mut reader)?;
+
+                    if txns.is_empty() {
+                        break;
+                    }
+
+                    for txn in txns.iter() {
+                        writeln!(file_, "{}", txn)
+                            .to_indy(IndyErrorKind::IOError, format!("Can't write to file {}", file))?;
+                    }
+                }
+
+                json!({"from": from, "to": to, "file": file})
+            }
+            None => json!({"from": from, "to": to})
+        };
+
+        Ok(res.to_string())
+    }
+
+    fn open_ledger_txns_search(&self,
+                               pool_handle: PoolHandle,
+                               ledger_type: Option<&str>,
+                               from: i32,
+                               to: i32,
+                               cb: Box<dyn Fn(IndyResult<SearchHandle>) + Send>) {
+        debug!("open_ledger_txns_search >>> pool_handle: {:?}, ledger_type: {:?}, from: {:?}, to: {:?}",
+               pool_handle, ledger_type, from, to);
+
+        let (ledger_id, cmd_id) = try_cb!(self._sync_ledger_range(pool_handle, ledger_type, from, to), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_search_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.send_callbacks.borrow_mut().insert(cmd_id, Box::new(move |sync_result| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::OpenLedgerTxnsSearchContinue(
+                        pool_handle,
+                        ledger_id,
+                        from,
+                        to,
+                        sync_result,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _open_ledger_txns_search_continue(&self,
+                                         pool_handle: PoolHandle,
+                                         ledger_id: u8,
+                                         from: i32,
+                                         to: i32,
+                                         sync_result: IndyResult<String>,
+                                         cb_id: CommandHandle) {
+        let cb = self.pending_search_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let sync_result = try_cb!(sync_result, cb);
+
+        let res = self._open_ledger_txns_search(pool_handle, ledger_id, from, to, &sync_result);
+
+        debug!("open_ledger_txns_search <<< res: {:?}", res);
+
+        cb(res)
+    }
+
+    fn _open_ledger_txns_search(&self,
+                                pool_handle: PoolHandle,
+                                ledger_id: u8,
+                                from: i32,
+                                to: i32,
+                                sync_result: &str) -> IndyResult<SearchHandle> {
+        let (from, to) = _synced_ledger_range(ledger_id, from, to, sync_result)?;
+
+        let reader = self.pool_service.open_ledger_txns_reader(pool_handle, ledger_id, from, to)?;
+
+        let search_handle = next_search_handle();
+        self.ledger_txns_searches.borrow_mut().insert(search_handle, reader);
+
+        Ok(search_handle)
+    }
+
+    fn fetch_ledger_txns_search_next_batch(&self, search_handle: SearchHandle) -> IndyResult<String> {
+        debug!("fetch_ledger_txns_search_next_batch >>> search_handle: {:?}", search_handle);
+
+        let mut searches = self.ledger_txns_searches.borrow_mut();
+        let reader = searches.get_mut(&search_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Unknown LedgerTxnsSearch handle: {:?}", search_handle)))?;
+
+        let txns = self.pool_service.read_ledger_txns(reader)?;
+
+        let res = json!(txns).to_string();
+
+        debug!("fetch_ledger_txns_search_next_batch <<< txns: {:?}", txns.len());
+
+        Ok(res)
+    }
+
+    fn close_ledger_txns_search(&self, search_handle: SearchHandle) -> IndyResult<()> {
+        debug!("close_ledger_txns_search >>> search_handle: {:?}", search_handle);
+
+        self.ledger_txns_searches.borrow_mut().remove(&search_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Unknown LedgerTxnsSearch handle: {:?}", search_handle)))?;
+
+        debug!("close_ledger_txns_search <<< res: ()");
+
+        Ok(())
+    }
+
+    /// Validates requested range and brings local replica of the ledger up to its end.
+    fn _sync_ledger_range(&self, pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32) -> IndyResult<(u8, CommandHandle)> {
+        let ledger_id = self.ledger_service.parse_ledger_id(ledger_type)?;
+
+        if ledger_id < 0 || ledger_id > i32::from(u8::max_value()) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid Ledger type: {:?}", ledger_type)));
+        }
+
+        if from < 1 || (to != -1 && to < from) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid range of transactions: from {} to {}", from, to)));
+        }
+
+        let ledger_id = ledger_id as u8;
+        let till = if to == -1 { None } else { Some(to as usize) };
+
+        let cmd_id = self.pool_service.sync_ledger(pool_handle, ledger_id, till)?;
+
+        Ok((ledger_id, cmd_id))
+    }
+
+    fn create_pool_snapshot(&self,
+                            pool_handle: PoolHandle,
+                            cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("create_pool_snapshot >>> pool_handle: {:?}", pool_handle);
+
+        try_cb!(self.pool_service.get_online_pool_name(pool_handle), cb);
+
+        // GET_TXN reply contains the current size of the domain ledger and multi-signature of its txn root
+        let request_json = try_cb!(self.ledger_service.build_get_txn_request(None, None, 1), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_string_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::CreatePoolSnapshotContinue(
+                        pool_handle,
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _create_pool_snapshot_continue(&self,
+                                      pool_handle: PoolHandle,
+                                      pool_response: IndyResult<String>,
+                                      cb_id: CommandHandle) {
+        let cb = self.pending_string_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+
+        let (ledger_size, multi_signature) = try_cb!(_parse_signed_ledger_size(&pool_response), cb);
+
+        let cmd_id = try_cb!(self.pool_service.sync_ledger(pool_handle, DOMAIN_LEDGER_ID, Some(ledger_size)), cb);
+
+        self.pending_string_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.send_callbacks.borrow_mut().insert(cmd_id, Box::new(move |sync_result| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::CreatePoolSnapshotSynced(
+                        pool_handle,
+                        ledger_size,
+                        multi_signature.clone(),
+                        sync_result,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _create_pool_snapshot_synced(&self,
+                                    pool_handle: PoolHandle,
+                                    ledger_size: usize,
+                                    multi_signature: Value,
+                                    sync_result: IndyResult<String>,
+                                    cb_id: CommandHandle) {
+        let cb = self.pending_string_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        try_cb!(sync_result, cb);
+
+        let res = self.pool_service.create_snapshot(pool_handle, ledger_size, multi_signature);
+
+        debug!("create_pool_snapshot <<< res: {:?}", res);
+
+        cb(res)
+    }
+
     fn build_ledgers_freeze_request(&self, submitter_did: &DidValue, ledgers_ids: Vec<u64>) -> IndyResult<String>{
         debug!("build_ledgers_freeze_request >>> submitter_did: {:?}, ledgers_ids: {:?}", submitter_did, ledgers_ids);
 
@@ -1349,6 +1975,42 @@ impl LedgerCommandExecutor {
     }
 }
 
+const DOMAIN_LEDGER_ID: u8 = 1;
+
+fn _synced_ledger_range(ledger_id: u8, from: i32, to: i32, sync_result: &str) -> IndyResult<(usize, usize)> {
+    let sync_result: serde_json::Value = serde_json::from_str(sync_result)
+        .to_indy(IndyErrorKind::InvalidState, "Invalid ledger sync result")?;
+
+    let ledger_size = sync_result["ledgerSize"].as_u64()
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Ledger size not found in ledger sync result"))? as usize;
+
+    let from = from as usize;
+    let to = if to == -1 { ledger_size } else { to as usize };
+
+    if from > to || to > ledger_size {
+        return Err(err_msg(IndyErrorKind::LedgerItemNotFound,
+                           format!("Ledger {} contains only {} transactions", ledger_id, ledger_size)));
+    }
+
+    Ok((from, to))
+}
+
+fn _parse_signed_ledger_size(response: &str) -> IndyResult<(usize, Value)> {
+    let response: Value = serde_json::from_str(response)
+        .to_indy(IndyErrorKind::InvalidTransaction, "Invalid GET_TXN response")?;
+
+    let ledger_size = response["result"]["data"]["ledgerSize"].as_u64()
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidTransaction, "GET_TXN response doesn't contain ledger size"))?;
+
+    let multi_signature = &response["result"]["state_proof"]["multi_signature"];
+
+    if !multi_signature.is_object() {
+        return Err(err_msg(IndyErrorKind::InvalidTransaction, "GET_TXN response doesn't contain multi-signature"));
+    }
+
+    Ok((ledger_size as usize, multi_signature.clone()))
+}
+
 enum SignatureType {
     Single,
     Multi
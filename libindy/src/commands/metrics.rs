@@ -1,5 +1,7 @@
 use crate::services::metrics::models::MetricsValue;
 use crate::services::metrics::MetricsService;
+use crate::services::cache::CacheService;
+use crate::services::pool::PoolService;
 use indy_api_types::errors::prelude::*;
 use indy_wallet::WalletService;
 use serde_json::{Map, Value};
@@ -14,6 +16,16 @@ const OPENED_WALLETS_COUNT: &str = "opened";
 const OPENED_WALLET_IDS_COUNT: &str = "opened_ids";
 const PENDING_FOR_IMPORT_WALLETS_COUNT: &str = "pending_for_import";
 const PENDING_FOR_OPEN_WALLETS_COUNT: &str = "pending_for_open";
+const POOL_NODE_REPLIES_COUNT: &str = "replies";
+const POOL_NODE_TIMEOUTS_COUNT: &str = "timeouts";
+const POOL_NODE_NACKS_COUNT: &str = "nacks";
+const POOL_NODE_REJECTS_COUNT: &str = "rejects";
+const POOL_NODE_BLS_FAILURES_COUNT: &str = "bls_failures";
+const POOL_NODE_LATENCY_MS: &str = "latency_ms";
+const LEDGER_CACHE_LRU_HITS_COUNT: &str = "lru_hits";
+const LEDGER_CACHE_WALLET_HITS_COUNT: &str = "wallet_hits";
+const LEDGER_CACHE_MISSES_COUNT: &str = "misses";
+const LEDGER_CACHE_LRU_SIZE: &str = "lru_size";
 
 pub enum MetricsCommand {
     CollectMetrics(Box<dyn Fn(IndyResult<String>) + Send>),
@@ -22,16 +34,19 @@ pub enum MetricsCommand {
 pub struct MetricsCommandExecutor {
     wallet_service: Rc<WalletService>,
     metrics_service: Rc<MetricsService>,
+    cache_service: Rc<CacheService>,
 }
 
 impl MetricsCommandExecutor {
     pub fn new(
         wallet_service: Rc<WalletService>,
         metrics_service: Rc<MetricsService>,
+        cache_service: Rc<CacheService>,
     ) -> MetricsCommandExecutor {
         MetricsCommandExecutor {
             wallet_service,
             metrics_service,
+            cache_service,
         }
     }
 
@@ -49,6 +64,8 @@ impl MetricsCommandExecutor {
         let mut metrics_map = serde_json::Map::new();
         self.append_threapool_metrics(&mut metrics_map)?;
         self.append_wallet_metrics(&mut metrics_map)?;
+        self.append_pool_metrics(&mut metrics_map)?;
+        self.append_ledger_cache_metrics(&mut metrics_map)?;
         self.metrics_service
             .append_command_metrics(&mut metrics_map)?;
         let res = serde_json::to_string(&metrics_map)
@@ -133,6 +150,75 @@ impl MetricsCommandExecutor {
         Ok(())
     }
 
+    fn append_pool_metrics(&self, metrics_map: &mut Map<String, Value>) -> IndyResult<()> {
+        let mut pool_nodes = Vec::new();
+
+        for (pool_name, nodes) in PoolService::get_all_status() {
+            for (node_alias, status) in nodes {
+                let values = [
+                    (POOL_NODE_REPLIES_COUNT, status.replies),
+                    (POOL_NODE_TIMEOUTS_COUNT, status.timeouts),
+                    (POOL_NODE_NACKS_COUNT, status.nacks),
+                    (POOL_NODE_REJECTS_COUNT, status.rejects),
+                    (POOL_NODE_BLS_FAILURES_COUNT, status.bls_failures),
+                    (POOL_NODE_LATENCY_MS, status.latency_ms.unwrap_or(0) as usize),
+                ];
+
+                for (label, value) in values.iter() {
+                    let mut tags = HashMap::<String, String>::new();
+                    tags.insert(String::from("pool"), pool_name.clone());
+                    tags.insert(String::from("node"), node_alias.clone());
+                    tags.insert(String::from("label"), String::from(*label));
+
+                    pool_nodes.push(serde_json::to_value(MetricsValue::new(*value, tags))
+                        .to_indy(IndyErrorKind::IOError, "Unable to convert json")?);
+                }
+            }
+        }
+
+        metrics_map.insert(
+            String::from("pool_nodes_status"),
+            serde_json::to_value(pool_nodes)
+                .to_indy(IndyErrorKind::IOError, "Unable to convert json")?,
+        );
+
+        Ok(())
+    }
+
+    fn append_ledger_cache_metrics(&self, metrics_map: &mut Map<String, Value>) -> IndyResult<()> {
+        let mut ledger_cache = Vec::new();
+
+        ledger_cache.push(self.get_metric_json(
+            LEDGER_CACHE_LRU_SIZE,
+            self.cache_service.size()
+        )?);
+
+        for (which_cache, counters) in self.cache_service.get_counters() {
+            let values = [
+                (LEDGER_CACHE_LRU_HITS_COUNT, counters.lru_hits),
+                (LEDGER_CACHE_WALLET_HITS_COUNT, counters.wallet_hits),
+                (LEDGER_CACHE_MISSES_COUNT, counters.misses),
+            ];
+
+            for (label, value) in values.iter() {
+                let mut tags = HashMap::<String, String>::new();
+                tags.insert(String::from("cache"), which_cache.clone());
+                tags.insert(String::from("label"), String::from(*label));
+
+                ledger_cache.push(serde_json::to_value(MetricsValue::new(*value, tags))
+                    .to_indy(IndyErrorKind::IOError, "Unable to convert json")?);
+            }
+        }
+
+        metrics_map.insert(
+            String::from("ledger_cache"),
+            serde_json::to_value(ledger_cache)
+                .to_indy(IndyErrorKind::IOError, "Unable to convert json")?,
+        );
+
+        Ok(())
+    }
+
     fn get_metric_json(&self, label: &str, value: usize) -> IndyResult<Value> {
         let mut tag = HashMap::<String, String>::new();
         tag.insert(String::from("label"), String::from(label));
@@ -28,6 +28,7 @@ use crate::services::ledger::LedgerService;
 use crate::services::payments::PaymentsService;
 use crate::services::pool::{PoolService, set_freshness_threshold};
 use crate::services::metrics::MetricsService;
+use crate::services::cache::{CacheService, set_lru_capacity};
 use crate::services::metrics::command_metrics::CommandMetric;
 use indy_wallet::WalletService;
 
@@ -96,6 +97,9 @@ pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(threshold) = config.freshness_threshold {
         set_freshness_threshold(threshold);
     }
+    if let Some(lru_size) = config.ledger_cache_lru_size {
+        set_lru_capacity(lru_size);
+    }
 }
 
 fn get_cur_time() -> u128 {
@@ -134,6 +138,7 @@ impl CommandExecutor {
                 let pool_service = Rc::new(PoolService::new());
                 let wallet_service = Rc::new(WalletService::new());
                 let metrics_service = Rc::new(MetricsService::new());
+                let cache_service = Rc::new(CacheService::new());
 
                 let anoncreds_command_executor = AnoncredsCommandExecutor::new(anoncreds_service.clone(), blob_storage_service.clone(), pool_service.clone(), wallet_service.clone(), crypto_service.clone());
                 let crypto_command_executor = CryptoCommandExecutor::new(wallet_service.clone(), crypto_service.clone());
@@ -145,8 +150,8 @@ impl CommandExecutor {
                 let blob_storage_command_executor = BlobStorageCommandExecutor::new(blob_storage_service.clone());
                 let non_secret_command_executor = NonSecretsCommandExecutor::new(wallet_service.clone());
                 let payments_command_executor = PaymentsCommandExecutor::new(payments_service.clone(), wallet_service.clone(), crypto_service.clone(), ledger_service.clone());
-                let cache_command_executor = CacheCommandExecutor::new(wallet_service.clone());
-                let metrics_command_executor = MetricsCommandExecutor::new(wallet_service.clone(), metrics_service.clone());
+                let cache_command_executor = CacheCommandExecutor::new(wallet_service.clone(), cache_service.clone());
+                let metrics_command_executor = MetricsCommandExecutor::new(wallet_service.clone(), metrics_service.clone(), cache_service.clone());
 
                 loop {
                     let instrumented_cmd = match receiver.recv() {
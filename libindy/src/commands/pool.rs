@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use crate::domain::ledger::request::ProtocolVersion;
-use crate::domain::pool::{PoolConfig, PoolOpenConfig};
+use crate::domain::pool::{PoolConfig, PoolOpenConfig, SnapshotImportOptions};
 use indy_api_types::errors::prelude::*;
 use crate::services::pool::PoolService;
 use indy_api_types::{PoolHandle, CommandHandle};
@@ -38,6 +38,14 @@ pub enum PoolCommand {
     SetProtocolVersion(
         usize, // protocol version
         Box<dyn Fn(IndyResult<()>) + Send>),
+    ImportSnapshot(
+        String, // name
+        String, // snapshot path
+        Option<SnapshotImportOptions>, // options
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    GetStatus(
+        PoolHandle, // pool handle
+        Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
 pub struct PoolCommandExecutor {
@@ -132,6 +140,14 @@ impl PoolCommandExecutor {
                 debug!(target: "pool_command_executor", "SetProtocolVersion command received");
                 cb(self.set_protocol_version(protocol_version));
             }
+            PoolCommand::ImportSnapshot(name, snapshot_path, options, cb) => {
+                debug!(target: "pool_command_executor", "ImportSnapshot command received");
+                cb(self.import_snapshot(&name, &snapshot_path, options));
+            }
+            PoolCommand::GetStatus(handle, cb) => {
+                debug!(target: "pool_command_executor", "GetStatus command received");
+                cb(self.get_status(handle));
+            }
         };
     }
 
@@ -221,6 +237,28 @@ impl PoolCommandExecutor {
         debug!("refresh <<<");
     }
 
+    fn import_snapshot(&self, name: &str, snapshot_path: &str, options: Option<SnapshotImportOptions>) -> IndyResult<()> {
+        debug!("import_snapshot >>> name: {:?}, snapshot_path: {:?}, options: {:?}", name, snapshot_path, options);
+
+        self.pool_service.import_snapshot(name, snapshot_path, options)?;
+
+        debug!("import_snapshot << res: ()");
+
+        Ok(())
+    }
+
+    fn get_status(&self, handle: PoolHandle) -> IndyResult<String> {
+        debug!("get_status >>> handle: {:?}", handle);
+
+        let res = self.pool_service
+            .get_status(handle)
+            .and_then(|status| ::serde_json::to_string(&json!({ "nodes": status }))
+                .to_indy(IndyErrorKind::InvalidState, "Can't serialize pool status"))?;
+
+        debug!("get_status << res: {:?}", res);
+        Ok(res)
+    }
+
     fn set_protocol_version(&self, version: usize) -> IndyResult<()> {
         debug!("set_protocol_version >>> version: {:?}", version);
 
@@ -4,13 +4,14 @@ use std::rc::Rc;
 
 use indy_api_types::wallet::*;
 use crate::commands::{Command, CommandExecutor};
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{CheckOptions, Config, Credentials, ExportConfig, KeyConfig};
 use indy_api_types::errors::prelude::*;
+use crate::domain::crypto::key::Key;
 use crate::services::crypto::CryptoService;
-use indy_wallet::{KeyDerivationData, WalletService, Metadata};
+use indy_wallet::{KeyDerivationData, WalletService, Metadata, RecordOptions};
 use indy_utils::crypto::{chacha20poly1305_ietf, randombytes};
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
-use indy_api_types::{WalletHandle, CallbackHandle};
+use indy_api_types::{WalletHandle, CallbackHandle, IndyHandle};
 use rust_base58::ToBase58;
 
 type DeriveKeyResult<T> = IndyResult<T>;
@@ -42,6 +43,19 @@ pub enum WalletCommand {
                        WalletFetchSearchNextRecord, // fetch search next record
                        WalletFreeSearch, // free search
                        Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterWalletStorageTransactions(String, // type_
+                                      WalletBeginTransaction, // begin transaction
+                                      WalletCommitTransaction, // commit transaction
+                                      WalletRollbackTransaction, // rollback transaction
+                                      Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterWalletStorageList(String, // type_
+                              WalletListStorages, // list storages
+                              WalletFreeStorageList, // free storage list
+                              Box<dyn Fn(IndyResult<()>) + Send>),
+    List(Option<String>, // storage type
+         Option<String>, // storage config
+         Option<String>, // storage credentials
+         Box<dyn Fn(IndyResult<String>) + Send>),
     Create(Config, // config
            Credentials, // credentials
            Box<dyn Fn(IndyResult<()>) + Send>),
@@ -74,6 +88,14 @@ pub enum WalletCommand {
                    KeyDerivationData,
                    DeriveKeyResult<MasterKey>,
                    CallbackHandle),
+    Rekey(WalletHandle,
+          Credentials, // new credentials
+          Box<dyn Fn(usize, usize) + Send>, // progress
+          Box<dyn Fn(IndyResult<()>) + Send>),
+    RekeyContinue(WalletHandle,
+                  KeyDerivationData,
+                  DeriveKeyResult<MasterKey>,
+                  CallbackHandle),
     Import(Config, // config
            Credentials, // credentials
            ExportConfig, // import config
@@ -84,6 +106,21 @@ pub enum WalletCommand {
                    WalletHandle,
                    CallbackHandle
     ),
+    Check(WalletHandle,
+          CheckOptions, // check options
+          Box<dyn Fn(IndyResult<String>) + Send>),
+    BeginTransaction(WalletHandle,
+                     Box<dyn Fn(IndyResult<()>) + Send>),
+    CommitTransaction(WalletHandle,
+                      Box<dyn Fn(IndyResult<()>) + Send>),
+    RollbackTransaction(WalletHandle,
+                        Box<dyn Fn(IndyResult<()>) + Send>),
+    SubscribeEvents(WalletHandle,
+                    Option<String>, // type filter
+                    Box<dyn Fn(String) + Send>, // event listener
+                    Box<dyn Fn(IndyResult<IndyHandle>) + Send>),
+    UnsubscribeEvents(IndyHandle, // subscription handle
+                      Box<dyn Fn(IndyResult<()>) + Send>),
     GenerateKey(Option<KeyConfig>, // config
                 Box<dyn Fn(IndyResult<String>) + Send>),
     DeriveKey(KeyDerivationData,
@@ -101,7 +138,8 @@ pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
-    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
+    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>,
+    rekey_progress_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(usize, usize) + Send>>>,
 }
 
 impl WalletCommandExecutor {
@@ -110,7 +148,8 @@ impl WalletCommandExecutor {
             wallet_service,
             crypto_service,
             open_callbacks: RefCell::new(HashMap::new()),
-            pending_callbacks: RefCell::new(HashMap::new())
+            pending_callbacks: RefCell::new(HashMap::new()),
+            rekey_progress_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -130,6 +169,20 @@ impl WalletCommandExecutor {
                                        free_storage_metadata, search_records, search_all_records, get_search_total_count,
                                        fetch_search_next_record, free_search));
             }
+            WalletCommand::RegisterWalletStorageTransactions(type_, begin_transaction, commit_transaction, rollback_transaction, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterWalletStorageTransactions command received");
+                cb(self._register_storage_transactions(&type_, begin_transaction, commit_transaction, rollback_transaction));
+            }
+            WalletCommand::RegisterWalletStorageList(type_, list_storages, free_storage_list, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterWalletStorageList command received");
+                cb(self._register_storage_list(&type_, list_storages, free_storage_list));
+            }
+            WalletCommand::List(storage_type, storage_config, storage_credentials, cb) => {
+                debug!(target: "wallet_command_executor", "List command received");
+                cb(self._list(storage_type.as_ref().map(String::as_str),
+                              storage_config.as_ref().map(String::as_str),
+                              storage_credentials.as_ref().map(String::as_str)));
+            }
             WalletCommand::Create(config, credentials, cb) => {
                 debug!(target: "wallet_command_executor", "Create command received");
                 self._create(&config, &credentials, cb)
@@ -166,6 +219,14 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "ExportContinue command received");
                 self._export_continue(cb_id, wallet_handle, &export_config, key_data, key_result)
             }
+            WalletCommand::Rekey(wallet_handle, credentials, progress_cb, cb) => {
+                debug!(target: "wallet_command_executor", "Rekey command received");
+                self._rekey(wallet_handle, &credentials, progress_cb, cb)
+            }
+            WalletCommand::RekeyContinue(wallet_handle, key_data, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "RekeyContinue command received");
+                self._rekey_continue(cb_id, wallet_handle, key_data, key_result)
+            }
             WalletCommand::Import(config, credentials, import_config, cb) => {
                 debug!(target: "wallet_command_executor", "Import command received");
                 self._import(&config, &credentials, &import_config, cb);
@@ -174,6 +235,30 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "ImportContinue command received");
                 self._import_continue(cb_id, wallet_handle, &config, &credential, key_result);
             }
+            WalletCommand::Check(wallet_handle, options, cb) => {
+                debug!(target: "wallet_command_executor", "Check command received");
+                cb(self._check(wallet_handle, &options));
+            }
+            WalletCommand::BeginTransaction(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "BeginTransaction command received");
+                cb(self._begin_transaction(wallet_handle));
+            }
+            WalletCommand::CommitTransaction(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "CommitTransaction command received");
+                cb(self._commit_transaction(wallet_handle));
+            }
+            WalletCommand::RollbackTransaction(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "RollbackTransaction command received");
+                cb(self._rollback_transaction(wallet_handle));
+            }
+            WalletCommand::SubscribeEvents(wallet_handle, type_filter, listener, cb) => {
+                debug!(target: "wallet_command_executor", "SubscribeEvents command received");
+                cb(self._subscribe_events(wallet_handle, type_filter, listener));
+            }
+            WalletCommand::UnsubscribeEvents(subscription_handle, cb) => {
+                debug!(target: "wallet_command_executor", "UnsubscribeEvents command received");
+                cb(self._unsubscribe_events(subscription_handle));
+            }
             WalletCommand::GenerateKey(config, cb) => {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 cb(self._generate_key(config.as_ref()));
@@ -226,6 +311,51 @@ impl WalletCommandExecutor {
         Ok(())
     }
 
+    fn _register_storage_transactions(&self,
+                                      type_: &str,
+                                      begin_transaction: WalletBeginTransaction,
+                                      commit_transaction: WalletCommitTransaction,
+                                      rollback_transaction: WalletRollbackTransaction) -> IndyResult<()> {
+        trace!("_register_storage_transactions >>> type_: {:?}", type_);
+
+        self
+            .wallet_service
+            .register_wallet_storage_transactions(type_, begin_transaction, commit_transaction, rollback_transaction)?;
+
+        trace!("_register_storage_transactions <<< res: ()");
+        Ok(())
+    }
+
+    fn _register_storage_list(&self,
+                              type_: &str,
+                              list_storages: WalletListStorages,
+                              free_storage_list: WalletFreeStorageList) -> IndyResult<()> {
+        trace!("_register_storage_list >>> type_: {:?}", type_);
+
+        self
+            .wallet_service
+            .register_wallet_storage_list(type_, list_storages, free_storage_list)?;
+
+        trace!("_register_storage_list <<< res: ()");
+        Ok(())
+    }
+
+    fn _list(&self,
+             storage_type: Option<&str>,
+             storage_config: Option<&str>,
+             storage_credentials: Option<&str>) -> IndyResult<String> {
+        trace!("_list >>> storage_type: {:?}, storage_config: {:?}, storage_credentials: {:?}",
+               storage_type, storage_config, secret!(storage_credentials));
+
+        let ids = self.wallet_service.list_wallets(storage_type, storage_config, storage_credentials)?;
+
+        let res = serde_json::to_string(&ids)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet ids")?;
+
+        trace!("_list <<< res: {:?}", res);
+        Ok(res)
+    }
+
     fn _create(&self,
                config: &Config,
                credentials: &Credentials,
@@ -393,7 +523,7 @@ impl WalletCommandExecutor {
                cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         trace!("_export >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
 
-        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method);
+        let key_data = try_cb!(self.wallet_service.export_wallet_prepare(export_config), cb);
 
         let cb_id = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
@@ -425,8 +555,64 @@ impl WalletCommandExecutor {
                         key_data: KeyDerivationData,
                         key_result: DeriveKeyResult<MasterKey>) {
         let cb = get_cb!(self, cb_id);
+
+        let sign = |manifest: &[u8]| -> IndyResult<Vec<u8>> {
+            let verkey = export_config.verkey.as_ref()
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Verkey to sign backup manifest is required for version 1"))?;
+
+            let key: Key = self.wallet_service.get_indy_object(wallet_handle, verkey, &RecordOptions::id_value())?;
+            self.crypto_service.sign(&key, manifest)
+        };
+
+        let verify = |verkey: &str, manifest: &[u8], signature: &[u8]| self.crypto_service.verify(verkey, manifest, signature);
+
         cb(key_result
-            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key)))) // TODO - later add proper versioning
+            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, export_config.version, (&key_data, &key), &sign, &verify)))
+    }
+
+    fn _rekey(&self,
+              wallet_handle: WalletHandle,
+              credentials: &Credentials,
+              progress_cb: Box<dyn Fn(usize, usize) + Send>,
+              cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_rekey >>> handle: {:?}, credentials: {:?}", wallet_handle, secret!(credentials));
+
+        let key_data = try_cb!(self.wallet_service.rekey_wallet_prepare(wallet_handle, credentials), cb);
+
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+        self.rekey_progress_callbacks.borrow_mut().insert(cb_id, progress_cb);
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_data.clone(),
+                Box::new(move |master_key_res| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::RekeyContinue(
+                        wallet_handle,
+                        key_data.clone(),
+                        master_key_res,
+                        cb_id,
+                    ))).unwrap();
+                })
+            ))
+        ).unwrap();
+
+        trace!("_rekey <<<");
+    }
+
+    fn _rekey_continue(&self,
+                       cb_id: CallbackHandle,
+                       wallet_handle: WalletHandle,
+                       key_data: KeyDerivationData,
+                       key_result: DeriveKeyResult<MasterKey>) {
+        let cb = get_cb!(self, cb_id);
+        let progress_cb = match self.rekey_progress_callbacks.borrow_mut().remove(&cb_id) {
+            Some(val) => val,
+            None => return error!("No pending rekey progress callback for id: {}", cb_id)
+        };
+
+        cb(key_result
+            .and_then(|key| self.wallet_service.rekey_wallet_continue(wallet_handle, (&key_data, &key), &*progress_cb)))
     }
 
     fn _import(&self,
@@ -481,8 +667,82 @@ impl WalletCommandExecutor {
                         credential: &Credentials,
                         key_result: DeriveKeyResult<(MasterKey, MasterKey)>) {
         let cb = get_cb!(self, cb_id);
+
+        let verify = |verkey: &str, manifest: &[u8], signature: &[u8]| self.crypto_service.verify(verkey, manifest, signature);
+
         cb(key_result
-            .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key)))
+            .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key, &verify)))
+    }
+
+    fn _check(&self,
+              wallet_handle: WalletHandle,
+              options: &CheckOptions) -> IndyResult<String> {
+        trace!("_check >>> handle: {:?}, options: {:?}", wallet_handle, options);
+
+        let report = self.wallet_service.check_wallet(wallet_handle, options)?;
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize wallet check report")?;
+
+        trace!("_check <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _begin_transaction(&self,
+                          wallet_handle: WalletHandle) -> IndyResult<()> {
+        trace!("_begin_transaction >>> handle: {:?}", wallet_handle);
+
+        self.wallet_service.begin_transaction(wallet_handle)?;
+
+        trace!("_begin_transaction <<< res: ()");
+        Ok(())
+    }
+
+    fn _commit_transaction(&self,
+                           wallet_handle: WalletHandle) -> IndyResult<()> {
+        trace!("_commit_transaction >>> handle: {:?}", wallet_handle);
+
+        self.wallet_service.commit_transaction(wallet_handle)?;
+
+        trace!("_commit_transaction <<< res: ()");
+        Ok(())
+    }
+
+    fn _rollback_transaction(&self,
+                             wallet_handle: WalletHandle) -> IndyResult<()> {
+        trace!("_rollback_transaction >>> handle: {:?}", wallet_handle);
+
+        self.wallet_service.rollback_transaction(wallet_handle)?;
+
+        trace!("_rollback_transaction <<< res: ()");
+        Ok(())
+    }
+
+    fn _subscribe_events(&self,
+                         wallet_handle: WalletHandle,
+                         type_filter: Option<String>,
+                         listener: Box<dyn Fn(String) + Send>) -> IndyResult<IndyHandle> {
+        trace!("_subscribe_events >>> handle: {:?}, type_filter: {:?}", wallet_handle, type_filter);
+
+        let res = self.wallet_service.subscribe_events(wallet_handle, type_filter, Box::new(move |event| {
+            match serde_json::to_string(event) {
+                Ok(event_json) => listener(event_json),
+                Err(err) => error!("Can't serialize wallet event: {:?}", err)
+            }
+        }))?;
+
+        trace!("_subscribe_events <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _unsubscribe_events(&self,
+                           subscription_handle: IndyHandle) -> IndyResult<()> {
+        trace!("_unsubscribe_events >>> subscription_handle: {:?}", subscription_handle);
+
+        self.wallet_service.unsubscribe_events(subscription_handle)?;
+
+        trace!("_unsubscribe_events <<< res: ()");
+        Ok(())
     }
 
     fn _generate_key(&self,
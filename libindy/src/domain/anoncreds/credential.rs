@@ -65,6 +65,48 @@ impl Validatable for CredentialValues {
     }
 }
 
+/// Scheme of encoding raw attribute value into the number signed by Issuer.
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeEncoding {
+    /// 32-bit integer in canonical form is encoded as itself, any other value as SHA-256 hash of it.
+    Default,
+    /// Date in "YYYY-MM-DD" format is encoded as number of days since 1970-01-01.
+    Date,
+    /// Decimal number is encoded as number of units with the given count of digits after the point.
+    Decimal(u8),
+}
+
+impl Default for AttributeEncoding {
+    fn default() -> Self {
+        AttributeEncoding::Default
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(untagged)]
+pub enum RawAttributeValue {
+    Raw(String),
+    Encoding {
+        raw: String,
+        #[serde(default)]
+        encoding: AttributeEncoding,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct RawCredentialValues(pub HashMap<String, RawAttributeValue>);
+
+impl Validatable for RawCredentialValues {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("RawCredentialValues validation failed: empty list has been passed"));
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable for Credential {
     fn validate(&self) -> Result<(), String> {
         self.schema_id.validate()?;
@@ -0,0 +1,41 @@
+use std::collections::HashMap;
+
+use indy_api_types::validation::Validatable;
+
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::super::crypto::did::DidValue;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct CredentialSelectionPolicy {
+    /// Issuer DIDs in order of preference.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefer_issuers: Vec<DidValue>,
+    #[serde(default)]
+    pub prefer_non_revocable: bool,
+    #[serde(default)]
+    pub newest: bool,
+    #[serde(default)]
+    pub minimize_credentials: bool,
+    /// Values for requested attributes which can be self attested.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub self_attested_attributes: HashMap<String, String>,
+}
+
+impl Validatable for CredentialSelectionPolicy {
+    fn validate(&self) -> Result<(), String> {
+        for did in self.prefer_issuers.iter() {
+            did.validate()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct RequiredRevocationState {
+    pub rev_reg_id: RevocationRegistryId,
+    pub cred_rev_id: String,
+    pub timestamp: u64,
+}
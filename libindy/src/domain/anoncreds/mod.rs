@@ -2,6 +2,7 @@ pub mod credential;
 pub mod credential_attr_tag_policy;
 pub mod credential_definition;
 pub mod credential_for_proof_request;
+pub mod credential_selection;
 pub mod credential_offer;
 pub mod credential_request;
 pub mod proof;
@@ -10,8 +11,10 @@ pub mod requested_credential;
 pub mod revocation_registry_definition;
 pub mod revocation_registry_delta;
 pub mod revocation_registry;
+pub mod revocation_registry_manager;
 pub mod revocation_state;
 pub mod schema;
+pub mod verification_report;
 pub mod master_secret;
 
 pub const DELIMITER: &str = ":";
@@ -8,13 +8,16 @@ use serde::{de, Deserialize, Deserializer, ser, Serialize, Serializer};
 use serde_json::Value;
 use crate::utils::wql::Query;
 
-use super::credential::Credential;
+use super::credential::{AttributeEncoding, Credential};
 use super::super::crypto::did::DidValue;
 use super::credential_definition::CredentialDefinitionId;
 use super::revocation_registry_definition::RevocationRegistryId;
 use super::schema::SchemaId;
 use crate::utils::qualifier;
 
+/// Maximal number of values of requested membership set.
+const MAX_MEMBERSHIP_VALUES: usize = 100;
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ProofRequestPayload {
     pub nonce: Nonce,
@@ -24,6 +27,12 @@ pub struct ProofRequestPayload {
     pub requested_attributes: HashMap<String, AttributeInfo>,
     #[serde(default)]
     pub requested_predicates: HashMap<String, PredicateInfo>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub requested_equalities: HashMap<String, EqualityInfo>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub requested_memberships: HashMap<String, MembershipInfo>,
     pub non_revoked: Option<NonRevocedInterval>
 }
 
@@ -136,13 +145,17 @@ pub struct AttributeInfo {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub names: Option<Vec<String>>,
     pub restrictions: Option<Query>,
-    pub non_revoked: Option<NonRevocedInterval>
+    pub non_revoked: Option<NonRevocedInterval>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<AttributeEncoding>
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 pub struct PredicateInfo {
     pub name: String,
     pub p_type: PredicateTypes,
+    /// CL predicates are defined over 32-bit signed integers, 64-bit values are not supported.
     pub p_value: i32,
     pub restrictions: Option<Query>,
     pub non_revoked: Option<NonRevocedInterval>
@@ -171,6 +184,20 @@ impl fmt::Display for PredicateTypes {
     }
 }
 
+/// Constraint that values of several requested attributes are equal.
+/// Unrevealed attributes are proven to be equal without disclosing the value.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct EqualityInfo {
+    pub attr_referents: Vec<String>
+}
+
+/// Constraint that revealed value of the requested attribute belongs to the set of raw values.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct MembershipInfo {
+    pub attr_referent: String,
+    pub values: Vec<String>
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RequestedAttributeInfo {
     pub attr_referent: String,
@@ -218,10 +245,28 @@ impl Validatable for ProofRequest {
             }
         }
 
+        for (referent, requested_equality) in value.requested_equalities.iter() {
+            _check_equality(referent, requested_equality, &value.requested_attributes)?;
+        }
+
+        for (referent, requested_membership) in value.requested_memberships.iter() {
+            _check_membership(referent, requested_membership, &value.requested_attributes)?;
+        }
+
         Ok(())
     }
 }
 
+impl ProofRequestPayload {
+    /// Returns attribute name of the requested attribute in the common view
+    /// (as it is used by CL proofs).
+    pub fn attr_name(&self, attr_referent: &str) -> Option<String> {
+        self.requested_attributes.get(attr_referent)
+            .and_then(|attr_info| attr_info.name.as_ref())
+            .map(|name| _attr_common_view(name))
+    }
+}
+
 impl ProofRequest {
     pub fn to_unqualified(self) -> ProofRequest {
         let convert = |proof_request: &mut ProofRequestPayload| {
@@ -330,6 +375,42 @@ fn _check_restriction(tag_name: &str, tag_value: &str, version: &ProofRequestsVe
     Ok(())
 }
 
+fn _check_equality(referent: &str, equality: &EqualityInfo, requested_attributes: &HashMap<String, AttributeInfo>) -> Result<(), String> {
+    if equality.attr_referents.len() < 2 {
+        return Err(format!("Proof Request validation failed: requested equality \"{}\" must contain at least two attribute referents", referent));
+    }
+
+    for attr_referent in equality.attr_referents.iter() {
+        requested_attributes.get(attr_referent)
+            .ok_or_else(|| format!("Proof Request validation failed: requested equality \"{}\" refers to unknown attribute \"{}\"", referent, attr_referent))?
+            .name.as_ref()
+            .ok_or_else(|| format!("Proof Request validation failed: requested equality \"{}\" refers to attribute \"{}\" without \"name\"", referent, attr_referent))?;
+    }
+
+    Ok(())
+}
+
+fn _check_membership(referent: &str, membership: &MembershipInfo, requested_attributes: &HashMap<String, AttributeInfo>) -> Result<(), String> {
+    if membership.values.is_empty() {
+        return Err(format!("Proof Request validation failed: requested membership \"{}\" must contain at least one value", referent));
+    }
+
+    if membership.values.len() > MAX_MEMBERSHIP_VALUES {
+        return Err(format!("Proof Request validation failed: requested membership \"{}\" must contain at most {} values", referent, MAX_MEMBERSHIP_VALUES));
+    }
+
+    requested_attributes.get(&membership.attr_referent)
+        .ok_or_else(|| format!("Proof Request validation failed: requested membership \"{}\" refers to unknown attribute \"{}\"", referent, membership.attr_referent))?
+        .name.as_ref()
+        .ok_or_else(|| format!("Proof Request validation failed: requested membership \"{}\" refers to attribute \"{}\" without \"name\"", referent, membership.attr_referent))?;
+
+    Ok(())
+}
+
+fn _attr_common_view(attr: &str) -> String {
+    attr.replace(" ", "").to_lowercase()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +475,7 @@ mod tests {
                     Query::Eq("cred_def_id".to_string(), CRED_DEF_ID_QUALIFIED.to_string()),
                 ])),
                 non_revoked: None,
+                encoding: None,
             });
 
             let mut requested_predicates: HashMap<String, PredicateInfo> = HashMap::new();
@@ -414,6 +496,8 @@ mod tests {
                 version: "1.0".to_string(),
                 requested_attributes,
                 requested_predicates,
+                requested_equalities: HashMap::new(),
+                requested_memberships: HashMap::new(),
                 non_revoked: None,
             });
 
@@ -427,6 +511,7 @@ mod tests {
                     Query::Eq("cred_def_id".to_string(), CRED_DEF_ID_UNQUALIFIED.to_string()),
                 ])),
                 non_revoked: None,
+                encoding: None,
             });
 
 
@@ -448,4 +533,108 @@ mod tests {
             assert_eq!(ProofRequestsVersion::V2, proof_request.version());
         }
     }
+
+    mod requested_equalities {
+        use super::*;
+
+        fn _proof_request(requested_equalities: serde_json::Value) -> ProofRequest {
+            serde_json::from_value(json!({
+                "nonce": "123456",
+                "name": "name",
+                "version": "1.0",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "Name"},
+                    "attr2_referent": {"name": "name"},
+                    "attr3_referent": {"name": "sex"},
+                    "attr4_referent": {"names": ["name", "sex"]},
+                },
+                "requested_equalities": requested_equalities,
+            })).unwrap()
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_equality() {
+            let proof_request = _proof_request(json!({"eq1_referent": {"attr_referents": ["attr1_referent", "attr2_referent"]}}));
+            proof_request.validate().unwrap();
+
+            assert_eq!(Some("name".to_string()), proof_request.value().attr_name("attr1_referent"));
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_equality_of_single_attribute() {
+            _proof_request(json!({"eq1_referent": {"attr_referents": ["attr1_referent"]}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_equality_of_unknown_attribute() {
+            _proof_request(json!({"eq1_referent": {"attr_referents": ["attr1_referent", "unknown_referent"]}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_equality_of_attribute_group() {
+            _proof_request(json!({"eq1_referent": {"attr_referents": ["attr1_referent", "attr4_referent"]}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_equality_of_different_attributes() {
+            _proof_request(json!({"eq1_referent": {"attr_referents": ["attr1_referent", "attr3_referent"]}})).validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_serialization_skips_empty_equalities() {
+            let proof_request = _proof_request(json!({}));
+            let proof_request_json = serde_json::to_value(&proof_request).unwrap();
+            assert!(proof_request_json.get("requested_equalities").is_none());
+        }
+    }
+
+    mod requested_memberships {
+        use super::*;
+
+        fn _proof_request(requested_memberships: serde_json::Value) -> ProofRequest {
+            serde_json::from_value(json!({
+                "nonce": "123456",
+                "name": "name",
+                "version": "1.0",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "country"},
+                    "attr2_referent": {"names": ["country", "city"]},
+                },
+                "requested_memberships": requested_memberships,
+            })).unwrap()
+        }
+
+        #[test]
+        fn proof_request_validate_works_for_membership() {
+            _proof_request(json!({"mem1_referent": {"attr_referent": "attr1_referent", "values": ["DE", "FR"]}})).validate().unwrap();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_membership_of_empty_set() {
+            _proof_request(json!({"mem1_referent": {"attr_referent": "attr1_referent", "values": []}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_membership_of_too_big_set() {
+            let values: Vec<String> = (0..=MAX_MEMBERSHIP_VALUES).map(|value| value.to_string()).collect();
+            _proof_request(json!({"mem1_referent": {"attr_referent": "attr1_referent", "values": values}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_membership_of_unknown_attribute() {
+            _proof_request(json!({"mem1_referent": {"attr_referent": "unknown_referent", "values": ["DE"]}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_validate_fails_for_membership_of_attribute_group() {
+            _proof_request(json!({"mem1_referent": {"attr_referent": "attr2_referent", "values": ["DE"]}})).validate().unwrap_err();
+        }
+
+        #[test]
+        fn proof_request_serialization_skips_empty_memberships() {
+            let proof_request = _proof_request(json!({}));
+            let proof_request_json = serde_json::to_value(&proof_request).unwrap();
+            assert!(proof_request_json.get("requested_memberships").is_none());
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,234 @@
+use indy_api_types::validation::Validatable;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry::RevocationRegistry;
+use super::revocation_registry_definition::{IssuanceType, RevocationRegistryDefinition, RevocationRegistryId};
+use super::super::crypto::did::DidValue;
+
+pub const DEFAULT_TAG_PREFIX: &str = "rev_reg_";
+pub const DEFAULT_ROLLOVER_THRESHOLD: f64 = 0.8;
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RevocationRegistryManagerConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuance_type: Option<IssuanceType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_cred_num: Option<u32>,
+    /// Tags of managed registries are built as `<tag_prefix><registry number>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_prefix: Option<String>,
+    /// Part of the active registry capacity to be used before the next registry is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rollover_threshold: Option<f64>,
+}
+
+impl RevocationRegistryManagerConfig {
+    pub fn tag_prefix(&self) -> &str {
+        self.tag_prefix.as_ref().map(String::as_str).unwrap_or(DEFAULT_TAG_PREFIX)
+    }
+
+    pub fn rollover_threshold(&self) -> f64 {
+        self.rollover_threshold.unwrap_or(DEFAULT_ROLLOVER_THRESHOLD)
+    }
+}
+
+impl Validatable for RevocationRegistryManagerConfig {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(0) = self.max_cred_num {
+            return Err(String::from("RevocationRegistryManagerConfig validation failed: `max_cred_num` must be greater than 0"));
+        }
+
+        if let Some(threshold) = self.rollover_threshold {
+            if threshold <= 0.0 || threshold > 1.0 {
+                return Err(String::from("RevocationRegistryManagerConfig validation failed: `rollover_threshold` must be in (0, 1] range"));
+            }
+        }
+
+        if let Some(ref tag_prefix) = self.tag_prefix {
+            if tag_prefix.is_empty() || tag_prefix.contains(super::DELIMITER) {
+                return Err(String::from("RevocationRegistryManagerConfig validation failed: `tag_prefix` must be non-empty and must not contain ':'"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ManagedRevocationRegistryStatus {
+    /// Created, but not published to the ledger yet.
+    Pending,
+    /// Published in advance, becomes active when the current one is full.
+    Ready,
+    /// Used for issuance.
+    Active,
+    Full,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ManagedRevocationRegistry {
+    pub rev_reg_id: RevocationRegistryId,
+    pub tag: String,
+    pub status: ManagedRevocationRegistryStatus,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevocationRegistryManager {
+    pub issuer_did: DidValue,
+    pub cred_def_id: CredentialDefinitionId,
+    pub config: RevocationRegistryManagerConfig,
+    pub registries: Vec<ManagedRevocationRegistry>,
+}
+
+impl RevocationRegistryManager {
+    pub fn active(&self) -> Option<&ManagedRevocationRegistry> {
+        self.registries.iter().find(|registry| registry.status == ManagedRevocationRegistryStatus::Active)
+    }
+
+    pub fn ready(&self) -> Option<&ManagedRevocationRegistry> {
+        self.registries.iter().find(|registry| registry.status == ManagedRevocationRegistryStatus::Ready)
+    }
+
+    pub fn pending(&self) -> Option<&ManagedRevocationRegistry> {
+        self.registries.iter().find(|registry| registry.status == ManagedRevocationRegistryStatus::Pending)
+    }
+
+    pub fn get(&self, rev_reg_id: &RevocationRegistryId) -> Option<&ManagedRevocationRegistry> {
+        self.registries.iter().find(|registry| registry.rev_reg_id == *rev_reg_id)
+    }
+
+    pub fn next_tag(&self) -> String {
+        format!("{}{}", self.config.tag_prefix(), self.registries.len() + 1)
+    }
+
+    pub fn set_status(&mut self, rev_reg_id: &RevocationRegistryId, status: ManagedRevocationRegistryStatus) {
+        if let Some(registry) = self.registries.iter_mut().find(|registry| registry.rev_reg_id == *rev_reg_id) {
+            registry.status = status;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ManagedRevocationRegistryInfo {
+    pub rev_reg_id: RevocationRegistryId,
+    pub tag: String,
+    pub status: ManagedRevocationRegistryStatus,
+    pub max_cred_num: u32,
+    pub issued: u32,
+    pub revoked: u32,
+    /// Definition and initial entry to be published for pending registries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_def: Option<RevocationRegistryDefinition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg: Option<RevocationRegistry>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssuedCredentialStatus {
+    Issued,
+    Revoked,
+}
+
+impl IssuedCredentialStatus {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            IssuedCredentialStatus::Issued => "issued",
+            IssuedCredentialStatus::Revoked => "revoked",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IssuedCredential {
+    pub cred_def_id: CredentialDefinitionId,
+    pub rev_reg_id: RevocationRegistryId,
+    pub cred_rev_id: String,
+    pub status: IssuedCredentialStatus,
+}
+
+impl IssuedCredential {
+    pub fn id(rev_reg_id: &RevocationRegistryId, cred_rev_id: &str) -> String {
+        format!("{}{}{}", rev_reg_id.0, super::DELIMITER, cred_rev_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _rev_reg_id(tag: &str) -> RevocationRegistryId {
+        RevocationRegistryId(format!("NcYxiDXkpYi6ov5FcYDi1e:4:NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag:CL_ACCUM:{}", tag))
+    }
+
+    fn _manager() -> RevocationRegistryManager {
+        RevocationRegistryManager {
+            issuer_did: DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string()),
+            cred_def_id: CredentialDefinitionId("NcYxiDXkpYi6ov5FcYDi1e:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag".to_string()),
+            config: RevocationRegistryManagerConfig::default(),
+            registries: vec![
+                ManagedRevocationRegistry { rev_reg_id: _rev_reg_id("rev_reg_1"), tag: "rev_reg_1".to_string(), status: ManagedRevocationRegistryStatus::Full },
+                ManagedRevocationRegistry { rev_reg_id: _rev_reg_id("rev_reg_2"), tag: "rev_reg_2".to_string(), status: ManagedRevocationRegistryStatus::Active },
+            ],
+        }
+    }
+
+    mod validate {
+        use super::*;
+
+        #[test]
+        fn validate_config_works_for_default() {
+            RevocationRegistryManagerConfig::default().validate().unwrap();
+        }
+
+        #[test]
+        fn validate_config_works_for_zero_max_cred_num() {
+            let config = RevocationRegistryManagerConfig { max_cred_num: Some(0), ..RevocationRegistryManagerConfig::default() };
+            config.validate().unwrap_err();
+        }
+
+        #[test]
+        fn validate_config_works_for_rollover_threshold_out_of_range() {
+            let config = RevocationRegistryManagerConfig { rollover_threshold: Some(0.0), ..RevocationRegistryManagerConfig::default() };
+            config.validate().unwrap_err();
+
+            let config = RevocationRegistryManagerConfig { rollover_threshold: Some(1.5), ..RevocationRegistryManagerConfig::default() };
+            config.validate().unwrap_err();
+        }
+
+        #[test]
+        fn validate_config_works_for_tag_prefix_with_delimiter() {
+            let config = RevocationRegistryManagerConfig { tag_prefix: Some("rev:reg".to_string()), ..RevocationRegistryManagerConfig::default() };
+            config.validate().unwrap_err();
+        }
+    }
+
+    #[test]
+    fn manager_next_tag_works() {
+        assert_eq!("rev_reg_3", _manager().next_tag());
+    }
+
+    #[test]
+    fn manager_set_status_works() {
+        let mut manager = _manager();
+        assert!(manager.ready().is_none());
+
+        manager.set_status(&_rev_reg_id("rev_reg_2"), ManagedRevocationRegistryStatus::Ready);
+
+        assert!(manager.active().is_none());
+        assert_eq!(_rev_reg_id("rev_reg_2"), manager.ready().unwrap().rev_reg_id);
+    }
+
+    #[test]
+    fn manager_pending_works() {
+        let mut manager = _manager();
+        assert!(manager.pending().is_none());
+
+        manager.registries.push(ManagedRevocationRegistry { rev_reg_id: _rev_reg_id("rev_reg_3"), tag: "rev_reg_3".to_string(), status: ManagedRevocationRegistryStatus::Pending });
+
+        assert_eq!(_rev_reg_id("rev_reg_3"), manager.pending().unwrap().rev_reg_id);
+        assert!(manager.ready().is_none());
+        assert_eq!(ManagedRevocationRegistryStatus::Pending, manager.get(&_rev_reg_id("rev_reg_3")).unwrap().status);
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+use super::schema::SchemaId;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct CheckResult {
+    pub valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    pub fn valid() -> CheckResult {
+        CheckResult { valid: true, error: None }
+    }
+
+    pub fn invalid(error: String) -> CheckResult {
+        CheckResult { valid: false, error: Some(error) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferentStatus {
+    Revealed,
+    Unrevealed,
+    SelfAttested,
+    Proven,
+    Missing,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ReferentReport {
+    pub status: ReferentStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_proof_index: Option<u32>,
+    /// Revealed values correspond to the crypto proof and requested encoding.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<CheckResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<CheckResult>,
+    /// Timestamp is provided for the requested non-revocation interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<CheckResult>,
+}
+
+impl ReferentReport {
+    pub fn is_valid(&self) -> bool {
+        self.status != ReferentStatus::Missing &&
+            self.value.iter()
+                .chain(self.restrictions.iter())
+                .chain(self.non_revoked.iter())
+                .all(|check| check.valid)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct SubProofReport {
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// Schema, credential definition and revocation registry of sub proof are provided and consistent.
+    pub resolved: CheckResult,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct VerificationReport {
+    pub valid: bool,
+    /// Received referents correspond to requested ones.
+    pub structure: CheckResult,
+    pub attributes: HashMap<String, ReferentReport>,
+    pub predicates: HashMap<String, ReferentReport>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub equalities: HashMap<String, CheckResult>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub memberships: HashMap<String, CheckResult>,
+    pub sub_proofs: Vec<SubProofReport>,
+    /// Primary and non-revocation proofs of all sub proofs. Not set if sub proofs can't be resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto_proof: Option<CheckResult>,
+}
+
+impl VerificationReport {
+    pub fn is_valid(&self) -> bool {
+        self.structure.valid &&
+            self.attributes.values().all(ReferentReport::is_valid) &&
+            self.predicates.values().all(ReferentReport::is_valid) &&
+            self.equalities.values().all(|check| check.valid) &&
+            self.memberships.values().all(|check| check.valid) &&
+            self.sub_proofs.iter().all(|sub_proof| sub_proof.resolved.valid) &&
+            self.crypto_proof.as_ref().map(|check| check.valid).unwrap_or(false)
+    }
+}
@@ -26,6 +26,8 @@ pub struct MyDidInfo {
     pub crypto_type: Option<String>,
     pub cid: Option<bool>,
     pub method_name: Option<DidMethod>,
+    pub service: Option<DidService>,
+    pub key_manager: Option<String>,
 }
 
 impl Validatable for MyDidInfo {
@@ -36,6 +38,33 @@ impl Validatable for MyDidInfo {
         if let Some(ref name) = self.method_name {
             name.validate()?
         }
+        if let Some(ref service) = self.service {
+            match self.method_name {
+                Some(ref name) if name.0 == "peer" => service.validate()?,
+                _ => return Err(String::from("Service can be embedded only into DID of \"peer\" method"))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// DIDComm service endpoint embedded into `did:peer:2` identifier.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DidService {
+    pub service_endpoint: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub routing_keys: Vec<String>,
+    #[serde(default)]
+    pub accept: Vec<String>,
+}
+
+impl Validatable for DidService {
+    fn validate(&self) -> Result<(), String> {
+        if self.service_endpoint.is_empty() {
+            return Err(String::from("Empty service endpoint"));
+        }
         Ok(())
     }
 }
@@ -12,6 +12,10 @@ pub struct Key {
     pub signkey: String,
     #[cfg(test)]
     pub signkey: String,
+    /// Name of external key manager holding the key. Signkey contains key reference in this case.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_manager: Option<String>,
 }
 
 impl Key {
@@ -19,6 +23,15 @@ impl Key {
         Key {
             verkey,
             signkey,
+            key_manager: None,
+        }
+    }
+
+    pub fn new_managed(verkey: String, key_ref: String, key_manager: String) -> Key {
+        Key {
+            verkey,
+            signkey: key_ref,
+            key_manager: Some(key_manager),
         }
     }
 }
@@ -39,6 +52,7 @@ impl Drop for Key {
 pub struct KeyInfo {
     pub seed: Option<String>,
     pub crypto_type: Option<String>,
+    pub key_manager: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
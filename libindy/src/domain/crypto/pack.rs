@@ -38,4 +38,93 @@ pub struct UnpackMessage {
     pub recipient_verkey: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sender_verkey: Option<String>
-}
\ No newline at end of file
+}
+
+/// DIDComm v2 JWE in General JSON Serialization.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct JWEV2 {
+    pub protected: String,
+    pub recipients: Vec<RecipientV2>,
+    pub iv: String,
+    pub ciphertext: String,
+    pub tag: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct RecipientV2 {
+    pub header: HeaderV2,
+    pub encrypted_key: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct HeaderV2 {
+    pub kid: String
+}
+
+/// Content encryption algorithm of the JWE.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ContentEncryption {
+    #[serde(rename = "XC20P")]
+    XC20P,
+    #[serde(rename = "A256GCM")]
+    A256GCM,
+}
+
+impl Default for ContentEncryption {
+    fn default() -> Self {
+        ContentEncryption::XC20P
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum KeyAgreementAlgorithm {
+    #[serde(rename = "ECDH-ES+A256KW")]
+    EcdhEsA256kw,
+    #[serde(rename = "ECDH-1PU+A256KW")]
+    Ecdh1puA256kw,
+}
+
+impl KeyAgreementAlgorithm {
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyAgreementAlgorithm::EcdhEsA256kw => "ECDH-ES+A256KW",
+            KeyAgreementAlgorithm::Ecdh1puA256kw => "ECDH-1PU+A256KW",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct EphemeralPublicKey {
+    pub kty: String,
+    pub crv: String,
+    pub x: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ProtectedV2 {
+    pub typ: String,
+    pub alg: KeyAgreementAlgorithm,
+    pub enc: ContentEncryption,
+    pub epk: EphemeralPublicKey,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skid: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apu: Option<String>,
+    pub apv: String
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct PackOptionsV2 {
+    #[serde(default)]
+    pub enc: ContentEncryption
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct UnpackMessageV2 {
+    pub message: String,
+    pub recipient_kid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_kid: Option<String>
+}
@@ -0,0 +1,91 @@
+use indy_api_types::validation::Validatable;
+
+use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::schema::SchemaId;
+use crate::domain::crypto::did::DidValue;
+use crate::utils::qualifier;
+
+pub const CL_SIGNATURE_TYPE: &str = "CL";
+
+/// Fully qualified `did:indy` identifier of a ledger object:
+///     `did:indy:<namespace>:<did>` - DID
+///     `did:indy:<namespace>:<did>/anoncreds/v0/SCHEMA/<name>/<version>` - schema
+///     `did:indy:<namespace>:<did>/anoncreds/v0/CLAIM_DEF/<schema seq no>/<tag>` - credential definition
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IndyDidUrl(pub String);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum IndyObjectId {
+    Nym(DidValue),
+    Schema(SchemaId),
+    CredDef(CredentialDefinitionId),
+}
+
+impl IndyDidUrl {
+    pub fn namespace(&self) -> Option<String> {
+        qualifier::indy_namespace(&self.0)
+    }
+
+    pub fn object_id(&self) -> Option<IndyObjectId> {
+        let caps = qualifier::INDY_REGEX.captures(&self.0)?;
+        let did = DidValue(caps[2].to_string());
+
+        let path = match caps.get(3) {
+            Some(path) => path.as_str(),
+            None => return Some(IndyObjectId::Nym(did))
+        };
+
+        match path[1..].split('/').collect::<Vec<&str>>().as_slice() {
+            ["anoncreds", "v0", "SCHEMA", name, version] =>
+                Some(IndyObjectId::Schema(SchemaId::new(&did, name, version))),
+            ["anoncreds", "v0", "CLAIM_DEF", seq_no, tag] if seq_no.parse::<u64>().is_ok() =>
+                Some(IndyObjectId::CredDef(CredentialDefinitionId(format!("{}:{}:{}:{}:{}", did.0, CredentialDefinitionId::MARKER, CL_SIGNATURE_TYPE, seq_no, tag)))),
+            _ => None
+        }
+    }
+}
+
+impl Validatable for IndyDidUrl {
+    fn validate(&self) -> Result<(), String> {
+        if self.object_id().is_none() {
+            return Err(format!("Unsupported did:indy identifier: {}. \
+                               Only DIDs, schema and credential definition identifiers can be resolved", self.0));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DID: &str = "NcYxiDXkpYi6ov5FcYDi1e";
+
+    #[test]
+    fn object_id_works_for_did() {
+        let id = IndyDidUrl(format!("did:indy:sovrin:staging:{}", DID));
+        assert_eq!(Some("sovrin:staging".to_string()), id.namespace());
+        assert_eq!(Some(IndyObjectId::Nym(DidValue(DID.to_string()))), id.object_id());
+    }
+
+    #[test]
+    fn object_id_works_for_schema() {
+        let id = IndyDidUrl(format!("did:indy:sovrin:{}/anoncreds/v0/SCHEMA/gvt/1.0", DID));
+        assert_eq!(Some("sovrin".to_string()), id.namespace());
+        assert_eq!(Some(IndyObjectId::Schema(SchemaId(format!("{}:2:gvt:1.0", DID)))), id.object_id());
+    }
+
+    #[test]
+    fn object_id_works_for_cred_def() {
+        let id = IndyDidUrl(format!("did:indy:sovrin:{}/anoncreds/v0/CLAIM_DEF/14/TAG_1", DID));
+        assert_eq!(Some(IndyObjectId::CredDef(CredentialDefinitionId(format!("{}:3:CL:14:TAG_1", DID)))), id.object_id());
+    }
+
+    #[test]
+    fn validate_works_for_unsupported() {
+        IndyDidUrl(format!("did:sov:{}", DID)).validate().unwrap_err();
+        IndyDidUrl(format!("did:indy:{}", DID)).validate().unwrap_err();
+        IndyDidUrl(format!("did:indy:sovrin:{}/anoncreds/v0/REV_REG_DEF/14/TAG_1", DID)).validate().unwrap_err();
+        IndyDidUrl(format!("did:indy:sovrin:{}/anoncreds/v0/CLAIM_DEF/gvt/TAG_1", DID)).validate().unwrap_err();
+    }
+}
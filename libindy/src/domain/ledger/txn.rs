@@ -35,3 +35,10 @@ impl LedgerType {
         }
     }
 }
+
+/// Options of ledger transactions download
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct DownloadLedgerTxnsOptions {
+    /// Path to the file downloaded transactions are appended to (one json per line)
+    pub file: Option<String>,
+}
@@ -11,7 +11,8 @@ use indy_api_types::validation::Validatable;
 pub struct IndyConfig {
     pub crypto_thread_pool_size: Option<usize>,
     pub collect_backtrace: Option<bool>,
-    pub freshness_threshold: Option<u64>
+    pub freshness_threshold: Option<u64>,
+    pub ledger_cache_lru_size: Option<usize>,
 }
 
 impl Validatable for IndyConfig {}
\ No newline at end of file
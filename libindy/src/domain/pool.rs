@@ -1,5 +1,9 @@
+use std::collections::HashMap;
+
 use indy_api_types::validation::Validatable;
 
+use crate::utils::qualifier;
+
 pub const POOL_CON_ACTIVE_TO: i64 = 5;
 pub const POOL_ACK_TIMEOUT: i64 = 20;
 pub const POOL_REPLY_TIMEOUT: i64 = 60;
@@ -8,14 +12,26 @@ pub const NUMBER_READ_NODES: u8 = 2;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PoolConfig {
-    pub genesis_txn: String
+    pub genesis_txn: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 impl PoolConfig {
     pub fn default_for_name(name: &str) -> PoolConfig {
         let mut txn = name.to_string();
         txn += ".txn";
-        PoolConfig { genesis_txn: txn }
+        PoolConfig { genesis_txn: txn, namespace: None }
+    }
+}
+
+impl Validatable for PoolConfig {
+    fn validate(&self) -> Result<(), String> {
+        match self.namespace {
+            Some(ref namespace) if !qualifier::is_valid_namespace(namespace) =>
+                Err(format!("Invalid `namespace`: {}. It must consist of colon separated lowercase alphanumeric, `-` and `_` parts", namespace)),
+            _ => Ok(())
+        }
     }
 }
 
@@ -33,6 +49,64 @@ pub struct PoolOpenConfig {
     pub preordered_nodes: Vec<String>,
     #[serde(default = "PoolOpenConfig::default_number_read_nodes")]
     pub number_read_nodes: u8,
+    #[serde(default)]
+    pub transport: PoolTransport,
+    #[serde(default)]
+    pub offline: bool,
+    #[serde(default)]
+    pub verify_write_replies: bool,
+}
+
+/// Transport used to deliver requests to pool nodes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum PoolTransport {
+    /// Direct CurveZMQ connections to node client ports
+    Zmq,
+    /// Node-side gateways reached with HTTP(S) requests
+    Http(GatewayConfig),
+    /// Node-side gateways reached through WebSocket connections
+    Ws(GatewayConfig),
+}
+
+impl Default for PoolTransport {
+    fn default() -> Self {
+        PoolTransport::Zmq
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GatewayConfig {
+    /// Gateway url for every node alias
+    pub gateways: HashMap<String, String>,
+    /// Proxy to send gateway requests through (HTTP transport only)
+    pub proxy: Option<String>,
+}
+
+impl Validatable for PoolTransport {
+    fn validate(&self) -> Result<(), String> {
+        let (config, schemes) = match self {
+            PoolTransport::Zmq => return Ok(()),
+            PoolTransport::Http(config) => (config, ["http://", "https://"]),
+            PoolTransport::Ws(config) => {
+                if config.proxy.is_some() {
+                    return Err(String::from("`proxy` is supported only by `http` transport"));
+                }
+                (config, ["ws://", "wss://"])
+            }
+        };
+
+        if config.gateways.is_empty() {
+            return Err(String::from("`gateways` must not be empty"));
+        }
+
+        if let Some((node, url)) = config.gateways.iter()
+            .find(|(_, url)| !schemes.iter().any(|scheme| url.starts_with(scheme))) {
+            return Err(format!("Invalid gateway url for node {}: {}", node, url));
+        }
+
+        Ok(())
+    }
 }
 
 impl Validatable for PoolOpenConfig {
@@ -52,6 +126,10 @@ impl Validatable for PoolOpenConfig {
         if self.number_read_nodes == 0 {
             return Err(String::from("`number_read_nodes` must be greater than 0"));
         }
+        self.transport.validate()?;
+        if self.offline && self.transport != PoolTransport::Zmq {
+            return Err(String::from("`offline` pool can't be combined with a gateway `transport`"));
+        }
         Ok(())
     }
 }
@@ -65,6 +143,9 @@ impl Default for PoolOpenConfig {
             conn_active_timeout: PoolOpenConfig::default_conn_active_timeout(),
             preordered_nodes: PoolOpenConfig::default_preordered_nodes(),
             number_read_nodes: PoolOpenConfig::default_number_read_nodes(),
+            transport: PoolTransport::default(),
+            offline: false,
+            verify_write_replies: false,
         }
     }
 }
@@ -92,3 +173,11 @@ impl PoolOpenConfig {
 
     fn default_number_read_nodes() -> u8 { NUMBER_READ_NODES }
 }
+
+/// Options of ledger snapshot import.
+///
+/// Reserved for the future use: multi-signature of the snapshot is always verified,
+/// so unknown options (like former `verify_signature`) are rejected.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SnapshotImportOptions {}
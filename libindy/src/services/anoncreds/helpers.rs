@@ -1,6 +1,6 @@
 use indy_api_types::errors::prelude::*;
 
-use crate::domain::anoncreds::credential::AttributeValues;
+use crate::domain::anoncreds::credential::{AttributeEncoding, AttributeValues, RawAttributeValue, RawCredentialValues};
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, NonRevocedInterval};
 use ursa::cl::{issuer, verifier, CredentialSchema, NonCredentialSchema, MasterSecret, CredentialValues, SubProofRequest};
 
@@ -15,7 +15,12 @@ use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequest;
 use crate::domain::anoncreds::proof_request::ProofRequest;
 
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use ursa::bn::BigNumber;
+
 use std::collections::{HashSet, HashMap};
+use std::convert::TryFrom;
 
 pub fn attr_common_view(attr: &str) -> String {
     attr.replace(" ", "").to_lowercase()
@@ -157,6 +162,113 @@ pub fn to_unqualified(entity: &str) -> IndyResult<String> {
     Ok(entity.to_string())
 }
 
+pub fn encode_attribute_value(raw: &str, encoding: &AttributeEncoding) -> IndyResult<String> {
+    trace!("encode_attribute_value >>> raw: {:?}, encoding: {:?}", raw, encoding);
+
+    let res = match encoding {
+        AttributeEncoding::Default => _encode_default(raw)?,
+        AttributeEncoding::Date => _encode_date(raw)?.to_string(),
+        AttributeEncoding::Decimal(scale) => _encode_decimal(raw, *scale)?.to_string(),
+    };
+
+    trace!("encode_attribute_value <<< res: {:?}", res);
+
+    Ok(res)
+}
+
+pub fn encode_credential_values(values: &RawCredentialValues) -> IndyResult<HashMap<String, AttributeValues>> {
+    trace!("encode_credential_values >>> values: {:?}", values);
+
+    let res = values.0
+        .iter()
+        .map(|(attr, value)| {
+            let (raw, encoding) = match value {
+                RawAttributeValue::Raw(raw) => (raw, AttributeEncoding::Default),
+                RawAttributeValue::Encoding { raw, encoding } => (raw, encoding.clone()),
+            };
+
+            let encoded = encode_attribute_value(raw, &encoding)
+                .map_err(|err| err.extend(format!("Attribute \"{}\" can't be encoded", attr)))?;
+
+            Ok((attr.to_string(), AttributeValues { raw: raw.to_string(), encoded }))
+        })
+        .collect::<IndyResult<HashMap<String, AttributeValues>>>()?;
+
+    trace!("encode_credential_values <<< res: {:?}", res);
+
+    Ok(res)
+}
+
+fn _encode_default(raw: &str) -> IndyResult<String> {
+    // only canonical decimal integers are passed through so that "5", "+5" and "05" stay distinct
+    if let Ok(value) = raw.parse::<i32>() {
+        if value.to_string() == raw {
+            return Ok(value.to_string());
+        }
+    }
+
+    Ok(BigNumber::from_bytes(&Sha256::digest(raw.as_bytes()))?.to_dec()?)
+}
+
+fn _encode_date(raw: &str) -> IndyResult<i64> {
+    lazy_static! {
+        static ref DATE_RE: Regex = Regex::new("^(\\d{4})-(\\d{2})-(\\d{2})$").unwrap();
+    }
+
+    let invalid_date = || err_msg(IndyErrorKind::InvalidStructure, format!("Invalid date \"{}\": expected YYYY-MM-DD", raw));
+
+    let caps = DATE_RE.captures(raw).ok_or_else(invalid_date)?;
+    let (year, month, day) = (caps[1].parse::<i64>().map_err(|_| invalid_date())?,
+                              caps[2].parse::<i64>().map_err(|_| invalid_date())?,
+                              caps[3].parse::<i64>().map_err(|_| invalid_date())?);
+
+    let is_leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap => 29,
+        2 => 28,
+        _ => return Err(invalid_date())
+    };
+
+    if day < 1 || day > days_in_month {
+        return Err(invalid_date());
+    }
+
+    // days since 1970-01-01 in proleptic Gregorian calendar
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = (if year >= 0 { year } else { year - 399 }) / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    Ok(era * 146097 + day_of_era - 719468)
+}
+
+fn _encode_decimal(raw: &str, scale: u8) -> IndyResult<i32> {
+    lazy_static! {
+        static ref DECIMAL_RE: Regex = Regex::new("^(-?)(\\d+)(?:\\.(\\d+))?$").unwrap();
+    }
+
+    let invalid_decimal = || err_msg(IndyErrorKind::InvalidStructure,
+                                     format!("Invalid decimal \"{}\": expected number with at most {} fractional digits fitting 32-bit range after scaling", raw, scale));
+
+    let caps = DECIMAL_RE.captures(raw).ok_or_else(invalid_decimal)?;
+
+    let fraction = caps.get(3).map(|m| m.as_str()).unwrap_or("");
+    if fraction.len() > scale as usize {
+        return Err(invalid_decimal());
+    }
+
+    let digits = format!("{}{:0<width$}", &caps[2], fraction, width = scale as usize);
+    let mut value = digits.parse::<i64>().map_err(|_| invalid_decimal())?;
+    if &caps[1] == "-" {
+        value = -value;
+    }
+
+    i32::try_from(value).map_err(|_| invalid_decimal())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +336,81 @@ mod tests {
             assert_eq!(REV_REG_ID_UNQUALIFIED, to_unqualified(REV_REG_ID_UNQUALIFIED).unwrap());
         }
     }
-}
\ No newline at end of file
+
+    mod encode_attribute_value {
+        use super::*;
+
+        #[test]
+        fn encode_attribute_value_works_for_default_integer() {
+            assert_eq!("28", encode_attribute_value("28", &AttributeEncoding::Default).unwrap());
+            assert_eq!("-5", encode_attribute_value("-5", &AttributeEncoding::Default).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_default_non_canonical_integer() {
+            let canonical = encode_attribute_value("5", &AttributeEncoding::Default).unwrap();
+
+            for raw in &["+5", "05", "-0"] {
+                let encoded = encode_attribute_value(raw, &AttributeEncoding::Default).unwrap();
+                assert_ne!(canonical, encoded);
+                assert_eq!(BigNumber::from_bytes(&Sha256::digest(raw.as_bytes())).unwrap().to_dec().unwrap(), encoded);
+            }
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_default_string() {
+            assert_eq!("99262857098057710338306967609588410025648622308394250666849665532448612202874",
+                       encode_attribute_value("Alex", &AttributeEncoding::Default).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_date() {
+            assert_eq!("0", encode_attribute_value("1970-01-01", &AttributeEncoding::Date).unwrap());
+            assert_eq!("10957", encode_attribute_value("2000-01-01", &AttributeEncoding::Date).unwrap());
+            assert_eq!("11016", encode_attribute_value("2000-02-29", &AttributeEncoding::Date).unwrap());
+            assert_eq!("-1", encode_attribute_value("1969-12-31", &AttributeEncoding::Date).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_invalid_date() {
+            for raw in &["2001-02-29", "2000-13-01", "2000-00-10", "2000-1-1", "01.01.2000", "2000-01-01T00:00:00"] {
+                let res = encode_attribute_value(raw, &AttributeEncoding::Date);
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+            }
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_decimal() {
+            assert_eq!("12345", encode_attribute_value("123.45", &AttributeEncoding::Decimal(2)).unwrap());
+            assert_eq!("12340", encode_attribute_value("123.4", &AttributeEncoding::Decimal(2)).unwrap());
+            assert_eq!("12300", encode_attribute_value("123", &AttributeEncoding::Decimal(2)).unwrap());
+            assert_eq!("-150", encode_attribute_value("-1.5", &AttributeEncoding::Decimal(2)).unwrap());
+            assert_eq!("7", encode_attribute_value("7", &AttributeEncoding::Decimal(0)).unwrap());
+        }
+
+        #[test]
+        fn encode_attribute_value_works_for_invalid_decimal() {
+            for raw in &["123.456", "12a", "1e5", ".5", "21474836.48"] {
+                let res = encode_attribute_value(raw, &AttributeEncoding::Decimal(2));
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+            }
+        }
+
+        #[test]
+        fn encode_credential_values_works() {
+            let values: RawCredentialValues = serde_json::from_value(json!({
+                "name": "Alex",
+                "age": {"raw": "28"},
+                "birthdate": {"raw": "2000-01-01", "encoding": "date"},
+                "salary": {"raw": "100.5", "encoding": {"decimal": 2}}
+            })).unwrap();
+
+            let res = encode_credential_values(&values).unwrap();
+            assert_eq!("28", res["age"].encoded);
+            assert_eq!("10957", res["birthdate"].encoded);
+            assert_eq!("2000-01-01", res["birthdate"].raw);
+            assert_eq!("10050", res["salary"].encoded);
+            assert_eq!(encode_attribute_value("Alex", &AttributeEncoding::Default).unwrap(), res["name"].encoded);
+        }
+    }
+}
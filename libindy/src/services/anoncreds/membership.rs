@@ -0,0 +1,44 @@
+//! Membership of revealed attribute value in the requested set of values.
+//!
+//! Membership of hidden value isn't supported: CL proofs can't express disjunction of values.
+use ursa::bn::BigNumber;
+
+use crate::domain::anoncreds::proof_request::{MembershipInfo, ProofRequestPayload};
+use indy_api_types::errors::prelude::*;
+use crate::services::anoncreds::helpers::encode_attribute_value;
+
+/// Encodes raw values of the requested set by encoding of the requested attribute.
+pub fn encode_set(membership_referent: &str, membership: &MembershipInfo, proof_req: &ProofRequestPayload) -> IndyResult<Vec<String>> {
+    let encoding = proof_req.requested_attributes.get(&membership.attr_referent)
+        .and_then(|attr_info| attr_info.encoding.clone())
+        .unwrap_or_default();
+
+    membership.values
+        .iter()
+        .map(|value| encode_attribute_value(value, &encoding)
+            .map_err(|err| err.extend(format!("Value \"{}\" of membership \"{}\" can't be encoded as {:?}", value, membership_referent, encoding))))
+        .collect()
+}
+
+/// Checks that encoded `value` belongs to the encoded set.
+pub fn contains(set: &[String], value: &str) -> IndyResult<bool> {
+    let value = BigNumber::from_dec(value)?;
+    for item in set {
+        if BigNumber::from_dec(item)? == value {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contains_works() {
+        let set = vec!["10".to_string(), "20".to_string()];
+        assert!(contains(&set, "20").unwrap());
+        assert!(!contains(&set, "25").unwrap());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod helpers;
 pub mod issuer;
+pub mod membership;
 pub mod prover;
 pub mod verifier;
 
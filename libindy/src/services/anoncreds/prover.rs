@@ -1,6 +1,8 @@
+use std::cmp::Reverse;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
+use ursa::bn::BigNumber;
 use ursa::cl::{
     BlindedCredentialSecrets,
     BlindedCredentialSecretsCorrectnessProof,
@@ -16,22 +18,29 @@ use ursa::cl::verifier::Verifier as CryptoVerifier;
 use crate::domain::anoncreds::credential::{AttributeValues, Credential};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1 as CredentialDefinition, CredentialDefinitionId};
+use crate::domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest, RequestedCredential};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequestMetadata;
+use crate::domain::anoncreds::credential_selection::{CredentialSelectionPolicy, RequiredRevocationState};
 use crate::domain::anoncreds::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo, SubProofReferent, RevealedAttributeGroupInfo, AttributeValue};
-use crate::domain::anoncreds::proof_request::{PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload, ProofRequestsVersion, RequestedAttributeInfo, RequestedPredicateInfo, ProofRequestExtraQuery};
-use crate::domain::anoncreds::requested_credential::ProvingCredentialKey;
+use crate::domain::anoncreds::proof_request::{EqualityInfo, MembershipInfo, PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload, ProofRequestsVersion, RequestedAttributeInfo, RequestedPredicateInfo, ProofRequestExtraQuery};
+use crate::domain::anoncreds::requested_credential::{ProvingCredentialKey, RequestedAttribute};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
 use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinitionV1;
 use crate::domain::anoncreds::revocation_state::RevocationState;
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
+use crate::domain::crypto::did::DidValue;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
+use crate::services::anoncreds::membership;
 use crate::utils::wql::Query;
 use crate::services::anoncreds::verifier::Verifier;
 
 const ATTRIBUTE_EXISTENCE_MARKER: &str = "1";
 
+/// Size of blinding factors of hidden attributes in CL proofs.
+const LARGE_MVECT: usize = 592;
+
 pub struct Prover {}
 
 impl Prover {
@@ -117,6 +126,16 @@ impl Prover {
         let mut proof_builder = CryptoProver::new_proof_builder()?;
         proof_builder.add_common_attribute("master_secret")?;
 
+        for (equality_referent, equality) in proof_req_val.requested_equalities.iter() {
+            self._check_equality(equality_referent, equality, proof_req_val, credentials, requested_credentials)?;
+        }
+
+        for (membership_referent, membership) in proof_req_val.requested_memberships.iter() {
+            self._check_membership(membership_referent, membership, proof_req_val, credentials, requested_credentials)?;
+        }
+
+        let linked_attrs = Prover::_link_hidden_attributes(proof_req_val, requested_credentials)?;
+
         let mut requested_proof = RequestedProof::default();
 
         requested_proof.self_attested_attrs = requested_credentials.self_attested_attributes.clone();
@@ -155,6 +174,15 @@ impl Prover {
             let credential_values = build_credential_values(&credential.values.0, Some(master_secret))?;
             let sub_proof_request = Prover::_build_sub_proof_request(&req_attrs_for_cred, &req_predicates_for_cred)?;
 
+            // blinding factors of linked hidden attributes are applied to this sub proof only
+            let mut common_attrs: Vec<&String> = Vec::new();
+            for ((key, attr_name), blinding) in linked_attrs.iter() {
+                if *key == cred_key {
+                    proof_builder.common_attributes.insert(attr_name.to_string(), blinding.try_clone()?);
+                    common_attrs.push(attr_name);
+                }
+            }
+
             proof_builder.add_sub_proof_request(&sub_proof_request,
                                                 &credential_schema,
                                                 &non_credential_schema,
@@ -164,6 +192,10 @@ impl Prover {
                                                 rev_state.as_ref().map(|r_info| &r_info.rev_reg),
                                                 rev_state.as_ref().map(|r_info| &r_info.witness))?;
 
+            for attr_name in common_attrs {
+                proof_builder.common_attributes.remove(attr_name);
+            }
+
 
             let identifier = match proof_req {
                 ProofRequest::ProofRequestV1(_) => {
@@ -208,6 +240,131 @@ impl Prover {
         Ok(full_proof)
     }
 
+    fn _check_equality(&self,
+                       equality_referent: &str,
+                       equality: &EqualityInfo,
+                       proof_req: &ProofRequestPayload,
+                       credentials: &HashMap<String, Credential>,
+                       requested_credentials: &RequestedCredentials) -> IndyResult<()> {
+        trace!("_check_equality >>> equality_referent: {:?}, equality: {:?}, requested_credentials: {:?}",
+               equality_referent, equality, requested_credentials);
+
+        let mut revealed: Option<bool> = None;
+        let mut encoded: Option<String> = None;
+
+        for attr_referent in equality.attr_referents.iter() {
+            let attr_name = proof_req.attr_name(attr_referent)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Attribute name not found for \"{}\" of equality \"{}\"", attr_referent, equality_referent)))?;
+
+            let requested_attr = requested_credentials.requested_attributes.get(attr_referent)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure,
+                                       format!("Credential is not provided for attribute \"{}\" of equality \"{}\"", attr_referent, equality_referent)))?;
+
+            if *revealed.get_or_insert(requested_attr.revealed) != requested_attr.revealed {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Attributes of equality \"{}\" must be either all revealed or all unrevealed", equality_referent)));
+            }
+
+            let credential: &Credential = credentials.get(requested_attr.cred_id.as_str())
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id: {:?}", requested_attr.cred_id)))?;
+
+            let attr_values = self.get_credential_values_for_attribute(&credential.values.0, &attr_name)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential value not found for attribute {:?}", attr_name)))?;
+
+            if *encoded.get_or_insert(attr_values.encoded.clone()) != attr_values.encoded {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Credentials provided for equality \"{}\" contain different values of \"{}\"", equality_referent, attr_name)));
+            }
+        }
+
+        trace!("_check_equality <<<");
+
+        Ok(())
+    }
+
+    // Hidden values of attributes linked by equalities share the blinding factor, so CL proof responses (m^)
+    // of the sub proofs are the same only if the values are equal.
+    fn _link_hidden_attributes(proof_req: &ProofRequestPayload,
+                               requested_credentials: &RequestedCredentials) -> IndyResult<HashMap<(ProvingCredentialKey, String), BigNumber>> {
+        let hidden_attr = |attr_referent: &str| -> Option<(ProvingCredentialKey, String)> {
+            let requested_attr = requested_credentials.requested_attributes.get(attr_referent)?;
+            if requested_attr.revealed {
+                return None;
+            }
+            let attr_name = proof_req.attr_name(attr_referent)?;
+            Some((ProvingCredentialKey { cred_id: requested_attr.cred_id.clone(), timestamp: requested_attr.timestamp }, attr_name))
+        };
+
+        let linked = proof_req.requested_equalities
+            .values()
+            .map(|equality| equality.attr_referents.iter().filter_map(|attr_referent| hidden_attr(attr_referent)).collect::<HashSet<_>>());
+
+        // groups sharing an attribute are merged
+        let mut groups: Vec<HashSet<(ProvingCredentialKey, String)>> = Vec::new();
+        for mut group in linked {
+            let (overlapping, rest): (Vec<_>, Vec<_>) = groups.into_iter().partition(|other| !other.is_disjoint(&group));
+            for other in overlapping {
+                group.extend(other);
+            }
+            groups = rest;
+            if !group.is_empty() {
+                groups.push(group);
+            }
+        }
+
+        let mut blindings: HashMap<(ProvingCredentialKey, String), BigNumber> = HashMap::new();
+        for group in groups {
+            let blinding = BigNumber::rand(LARGE_MVECT)?;
+            for attr in group {
+                blindings.insert(attr, blinding.try_clone()?);
+            }
+        }
+
+        Ok(blindings)
+    }
+
+    fn _check_membership(&self,
+                         membership_referent: &str,
+                         membership: &MembershipInfo,
+                         proof_req: &ProofRequestPayload,
+                         credentials: &HashMap<String, Credential>,
+                         requested_credentials: &RequestedCredentials) -> IndyResult<()> {
+        trace!("_check_membership >>> membership_referent: {:?}, membership: {:?}, requested_credentials: {:?}",
+               membership_referent, membership, requested_credentials);
+
+        let attr_referent = &membership.attr_referent;
+
+        let requested_attr = requested_credentials.requested_attributes.get(attr_referent)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Credential is not provided for attribute \"{}\" of membership \"{}\"", attr_referent, membership_referent)))?;
+
+        // only revealed value can be checked against the set
+        if !requested_attr.revealed {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Attribute \"{}\" of membership \"{}\" must be revealed", attr_referent, membership_referent)));
+        }
+
+        let attr_name = proof_req.attr_name(attr_referent)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Attribute name not found for membership \"{}\"", membership_referent)))?;
+
+        let credential: &Credential = credentials.get(requested_attr.cred_id.as_str())
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential not found by id: {:?}", requested_attr.cred_id)))?;
+
+        let attr_values = self.get_credential_values_for_attribute(&credential.values.0, &attr_name)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Credential value not found for attribute {:?}", attr_name)))?;
+
+        let set = membership::encode_set(membership_referent, membership, proof_req)?;
+
+        if !membership::contains(&set, &attr_values.encoded)? {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Credential provided for membership \"{}\" contains value of \"{}\" out of the requested set", membership_referent, attr_name)));
+        }
+
+        trace!("_check_membership <<<");
+
+        Ok(())
+    }
+
     pub fn _prepare_credentials_for_proving(requested_credentials: &RequestedCredentials,
                                             proof_req: &ProofRequestPayload) -> IndyResult<HashMap<ProvingCredentialKey, (Vec<RequestedAttributeInfo>, Vec<RequestedPredicateInfo>)>> {
         trace!("_prepare_credentials_for_proving >>> requested_credentials: {:?}, proof_req: {:?}", requested_credentials, proof_req);
@@ -357,6 +514,244 @@ impl Prover {
         res
     }
 
+    pub fn select_credentials_for_proof_req(&self,
+                                            proof_req: &ProofRequestPayload,
+                                            credentials_for_proof_req: &CredentialsForProofRequest,
+                                            stored_at: &HashMap<String, u64>,
+                                            policy: &CredentialSelectionPolicy,
+                                            now: u64) -> IndyResult<(RequestedCredentials, Vec<RequiredRevocationState>)> {
+        trace!("select_credentials_for_proof_req >>> proof_req: {:?}, credentials_for_proof_req: {:?}, stored_at: {:?}, policy: {:?}, now: {:?}",
+               proof_req, credentials_for_proof_req, stored_at, policy, now);
+
+        let prefer_issuers: Vec<DidValue> = policy.prefer_issuers.iter().map(DidValue::to_unqualified).collect();
+
+        let rank = |credential: &RequestedCredential| {
+            let issuer_rank = credential.cred_info.cred_def_id.issuer_did()
+                .and_then(|did| prefer_issuers.iter().position(|preferred| *preferred == did.to_unqualified()))
+                .unwrap_or(prefer_issuers.len());
+            let revocable = policy.prefer_non_revocable && credential.cred_info.rev_reg_id.is_some();
+            let stored_time = if policy.newest { stored_at.get(&credential.cred_info.referent).cloned().unwrap_or(0) } else { 0 };
+
+            (issuer_rank, revocable, Reverse(stored_time))
+        };
+
+        let mut self_attested_attributes: HashMap<String, String> = HashMap::new();
+        let mut attrs: BTreeMap<&str, Vec<&RequestedCredential>> = BTreeMap::new();
+        let mut predicates: BTreeMap<&str, Vec<&RequestedCredential>> = BTreeMap::new();
+
+        for (attr_referent, attr_info) in proof_req.requested_attributes.iter() {
+            let mut candidates: Vec<&RequestedCredential> = credentials_for_proof_req.attrs.get(attr_referent)
+                .map(|credentials| credentials.iter().collect())
+                .unwrap_or_default();
+
+            if candidates.is_empty() {
+                match policy.self_attested_attributes.get(attr_referent) {
+                    Some(value) if attr_info.name.is_some() && attr_info.restrictions.is_none() => {
+                        self_attested_attributes.insert(attr_referent.to_string(), value.to_string());
+                        continue;
+                    }
+                    _ => return Err(err_msg(IndyErrorKind::ItemNotFound,
+                                            format!("No credential found for requested attribute \"{}\"", attr_referent)))
+                }
+            }
+
+            candidates.sort_by_key(|credential| rank(*credential));
+            attrs.insert(attr_referent.as_str(), candidates);
+        }
+
+        for predicate_referent in proof_req.requested_predicates.keys() {
+            let mut candidates: Vec<&RequestedCredential> = credentials_for_proof_req.predicates.get(predicate_referent)
+                .map(|credentials| credentials.iter().collect())
+                .unwrap_or_default();
+
+            if candidates.is_empty() {
+                return Err(err_msg(IndyErrorKind::ItemNotFound,
+                                   format!("No credential found for requested predicate \"{}\"", predicate_referent)));
+            }
+
+            candidates.sort_by_key(|credential| rank(*credential));
+            predicates.insert(predicate_referent.as_str(), candidates);
+        }
+
+        let memberships: BTreeMap<&String, &MembershipInfo> = proof_req.requested_memberships.iter().collect();
+
+        for (membership_referent, membership) in memberships {
+            Prover::_select_member_credentials(membership_referent, membership, proof_req, &mut attrs)?;
+        }
+
+        let equalities: BTreeMap<&String, &EqualityInfo> = proof_req.requested_equalities.iter().collect();
+
+        for (equality_referent, equality) in equalities {
+            Prover::_select_equal_credentials(equality_referent, equality, proof_req, &mut attrs)?;
+        }
+
+        let (selected_attrs, selected_predicates) = if policy.minimize_credentials {
+            Prover::_select_minimal_credentials(&attrs, &predicates)
+        } else {
+            (Prover::_select_first_credentials(&attrs), Prover::_select_first_credentials(&predicates))
+        };
+
+        let mut rev_states: Vec<RequiredRevocationState> = Vec::new();
+
+        let mut requested_attributes: HashMap<String, RequestedAttribute> = HashMap::new();
+        for (attr_referent, credential) in selected_attrs {
+            let timestamp = Prover::_require_revocation_state(&credential, now, &mut rev_states)?;
+            requested_attributes.insert(attr_referent, RequestedAttribute { cred_id: credential.cred_info.referent, timestamp, revealed: true });
+        }
+
+        let mut requested_predicates: HashMap<String, ProvingCredentialKey> = HashMap::new();
+        for (predicate_referent, credential) in selected_predicates {
+            let timestamp = Prover::_require_revocation_state(&credential, now, &mut rev_states)?;
+            requested_predicates.insert(predicate_referent, ProvingCredentialKey { cred_id: credential.cred_info.referent, timestamp });
+        }
+
+        let requested_credentials = RequestedCredentials { self_attested_attributes, requested_attributes, requested_predicates };
+
+        trace!("select_credentials_for_proof_req <<< requested_credentials: {:?}, rev_states: {:?}", requested_credentials, rev_states);
+
+        Ok((requested_credentials, rev_states))
+    }
+
+    fn _select_equal_credentials(equality_referent: &str,
+                                 equality: &EqualityInfo,
+                                 proof_req: &ProofRequestPayload,
+                                 attrs: &mut BTreeMap<&str, Vec<&RequestedCredential>>) -> IndyResult<()> {
+        let not_found = || err_msg(IndyErrorKind::ItemNotFound,
+                                   format!("No credentials with equal values found for requested equality \"{}\"", equality_referent));
+
+        let mut attr_names: Vec<String> = Vec::new();
+        let mut groups: Vec<&Vec<&RequestedCredential>> = Vec::new();
+        for attr_referent in equality.attr_referents.iter() {
+            attr_names.push(proof_req.attr_name(attr_referent)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Requested equality \"{}\" is invalid", equality_referent)))?);
+            groups.push(attrs.get(attr_referent.as_str()).ok_or_else(not_found)?);
+        }
+
+        // Values are tried in the preferred order of the first attribute candidates
+        let value = groups[0].iter()
+            .filter_map(|credential| Prover::_raw_value(credential, &attr_names[0]))
+            .find(|value| groups.iter().zip(attr_names.iter())
+                .all(|(candidates, attr_name)| candidates.iter().any(|credential| Prover::_raw_value(credential, attr_name).as_ref() == Some(value))))
+            .ok_or_else(not_found)?;
+
+        for (attr_referent, attr_name) in equality.attr_referents.iter().zip(attr_names.iter()) {
+            if let Some(candidates) = attrs.get_mut(attr_referent.as_str()) {
+                candidates.retain(|credential| Prover::_raw_value(credential, attr_name).as_ref() == Some(&value));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _select_member_credentials(membership_referent: &str,
+                                  membership: &MembershipInfo,
+                                  proof_req: &ProofRequestPayload,
+                                  attrs: &mut BTreeMap<&str, Vec<&RequestedCredential>>) -> IndyResult<()> {
+        let attr_name = proof_req.attr_name(&membership.attr_referent)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Requested membership \"{}\" is invalid", membership_referent)))?;
+
+        let set = membership::encode_set(membership_referent, membership, proof_req)?;
+        let encoding = proof_req.requested_attributes.get(&membership.attr_referent)
+            .and_then(|attr_info| attr_info.encoding.clone())
+            .unwrap_or_default();
+
+        // values that can't be encoded by the requested encoding don't belong to the set
+        let is_member = |credential: &RequestedCredential| Prover::_raw_value(credential, &attr_name)
+            .and_then(|raw| encode_attribute_value(&raw, &encoding).ok())
+            .map(|encoded| membership::contains(&set, &encoded).unwrap_or(false))
+            .unwrap_or(false);
+
+        if let Some(candidates) = attrs.get_mut(membership.attr_referent.as_str()) {
+            candidates.retain(|credential| is_member(*credential));
+            if candidates.is_empty() {
+                return Err(err_msg(IndyErrorKind::ItemNotFound,
+                                   format!("No credentials with value from the requested set found for requested membership \"{}\"", membership_referent)));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _raw_value(credential: &RequestedCredential, attr_name: &str) -> Option<String> {
+        credential.cred_info.attrs.iter()
+            .find(|&(ref key, _)| attr_common_view(key) == attr_common_view(attr_name))
+            .map(|(_, value)| value.to_string())
+    }
+
+    fn _select_first_credentials(candidates: &BTreeMap<&str, Vec<&RequestedCredential>>) -> BTreeMap<String, RequestedCredential> {
+        candidates.iter()
+            .map(|(referent, credentials)| (referent.to_string(), credentials[0].clone()))
+            .collect()
+    }
+
+    // Greedy set cover: each step takes the credential satisfying the most of still uncovered referents.
+    fn _select_minimal_credentials(attrs: &BTreeMap<&str, Vec<&RequestedCredential>>,
+                                   predicates: &BTreeMap<&str, Vec<&RequestedCredential>>) -> (BTreeMap<String, RequestedCredential>, BTreeMap<String, RequestedCredential>) {
+        let mut uncovered: Vec<(bool, &str, &Vec<&RequestedCredential>)> =
+            attrs.iter().map(|(referent, candidates)| (false, *referent, candidates))
+                .chain(predicates.iter().map(|(referent, candidates)| (true, *referent, candidates)))
+                .collect();
+
+        let mut selected_attrs: BTreeMap<String, RequestedCredential> = BTreeMap::new();
+        let mut selected_predicates: BTreeMap<String, RequestedCredential> = BTreeMap::new();
+
+        while !uncovered.is_empty() {
+            let mut best: Option<(&str, usize)> = None;
+
+            for (_, _, candidates) in uncovered.iter() {
+                for &credential in candidates.iter() {
+                    let cred_id = credential.cred_info.referent.as_str();
+                    let count = uncovered.iter()
+                        .filter(|(_, _, candidates)| candidates.iter().any(|candidate| candidate.cred_info.referent == cred_id))
+                        .count();
+
+                    if best.map(|(_, best_count)| count > best_count).unwrap_or(true) {
+                        best = Some((cred_id, count));
+                    }
+                }
+            }
+
+            let cred_id = match best {
+                Some((cred_id, _)) => cred_id,
+                None => break
+            };
+
+            uncovered.retain(|(is_predicate, referent, candidates)| {
+                match candidates.iter().find(|candidate| candidate.cred_info.referent == cred_id) {
+                    Some(credential) => {
+                        let selected = if *is_predicate { &mut selected_predicates } else { &mut selected_attrs };
+                        selected.insert(referent.to_string(), (*credential).clone());
+                        false
+                    }
+                    None => true
+                }
+            });
+        }
+
+        (selected_attrs, selected_predicates)
+    }
+
+    fn _require_revocation_state(credential: &RequestedCredential,
+                                 now: u64,
+                                 rev_states: &mut Vec<RequiredRevocationState>) -> IndyResult<Option<u64>> {
+        let (rev_reg_id, interval) = match (credential.cred_info.rev_reg_id.as_ref(), credential.interval.as_ref()) {
+            (Some(rev_reg_id), Some(interval)) => (rev_reg_id, interval),
+            _ => return Ok(None)
+        };
+
+        let cred_rev_id = credential.cred_info.cred_rev_id.clone()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, format!("Revocation index not found for credential \"{}\"", credential.cred_info.referent)))?;
+
+        let timestamp = interval.to.or(interval.from).unwrap_or(now);
+
+        let rev_state = RequiredRevocationState { rev_reg_id: rev_reg_id.clone(), cred_rev_id, timestamp };
+        if !rev_states.contains(&rev_state) {
+            rev_states.push(rev_state);
+        }
+
+        Ok(Some(timestamp))
+    }
+
     fn _update_requested_proof(&self, req_attrs_for_credential: Vec<RequestedAttributeInfo>,
                                req_predicates_for_credential: Vec<RequestedPredicateInfo>,
                                proof_req: &ProofRequestPayload,
@@ -795,6 +1190,7 @@ mod tests {
                 names: None,
                 restrictions: None,
                 non_revoked: None,
+                encoding: None,
             }
         }
 
@@ -819,6 +1215,8 @@ mod tests {
                 requested_predicates: hashmap!(
                     PREDICATE_REFERENT.to_string() => _predicate_info()
                 ),
+                requested_equalities: HashMap::new(),
+                requested_memberships: HashMap::new(),
                 non_revoked: None,
             }
         }
@@ -871,6 +1269,7 @@ mod tests {
                 names: None,
                 restrictions: None,
                 non_revoked: None,
+                encoding: None,
             });
 
             let res = Prover::_prepare_credentials_for_proving(&req_cred, &proof_req).unwrap();
@@ -906,6 +1305,154 @@ mod tests {
         }
     }
 
+    mod check_equality {
+        use crate::domain::anoncreds::proof_request::AttributeInfo;
+        use crate::domain::anoncreds::requested_credential::RequestedAttribute;
+
+        use super::*;
+
+        const CRED_ID_1: &str = "cred_1";
+        const CRED_ID_2: &str = "cred_2";
+        const EQUALITY_REFERENT: &str = "equality_referent";
+
+        fn _credential(name: &str) -> Credential {
+            let mut attr_values: HashMap<String, AttributeValues> = HashMap::new();
+            attr_values.insert("name".to_string(), AttributeValues { raw: name.to_string(), encoded: format!("{}", name.len()) });
+            attr_values.insert("nickname".to_string(), AttributeValues { raw: name.to_string(), encoded: format!("{}", name.len()) });
+
+            serde_json::from_value::<Credential>(json!({
+                "schema_id": SCHEMA_ID,
+                "cred_def_id": CRED_DEF_ID,
+                "values": attr_values,
+                "signature": {"p_credential": {"m_2": "0","a": "0","e": "0","v": "0"}},
+                "signature_correctness_proof": {"se":"0", "c":"0"}
+            })).unwrap()
+        }
+
+        fn _attr_info() -> AttributeInfo {
+            AttributeInfo {
+                name: Some("Name".to_string()),
+                names: None,
+                restrictions: None,
+                non_revoked: None,
+                encoding: None,
+            }
+        }
+
+        fn _equality() -> EqualityInfo {
+            EqualityInfo { attr_referents: vec!["attr_1".to_string(), "attr_2".to_string()] }
+        }
+
+        fn _proof_req() -> ProofRequestPayload {
+            ProofRequestPayload {
+                nonce: ursa::cl::new_nonce().unwrap(),
+                name: "Job-Application".to_string(),
+                version: "0.1".to_string(),
+                requested_attributes: hashmap!(
+                    "attr_1".to_string() => _attr_info(),
+                    "attr_2".to_string() => _attr_info()
+                ),
+                requested_predicates: HashMap::new(),
+                requested_equalities: hashmap!(
+                    EQUALITY_REFERENT.to_string() => _equality()
+                ),
+                requested_memberships: HashMap::new(),
+                non_revoked: None,
+            }
+        }
+
+        fn _req_cred(revealed_2: bool) -> RequestedCredentials {
+            RequestedCredentials {
+                self_attested_attributes: HashMap::new(),
+                requested_attributes: hashmap!(
+                    "attr_1".to_string() => RequestedAttribute { cred_id: CRED_ID_1.to_string(), timestamp: None, revealed: false },
+                    "attr_2".to_string() => RequestedAttribute { cred_id: CRED_ID_2.to_string(), timestamp: None, revealed: revealed_2 }
+                ),
+                requested_predicates: HashMap::new(),
+            }
+        }
+
+        #[test]
+        fn check_equality_works() {
+            let credentials = hashmap!(
+                CRED_ID_1.to_string() => _credential("Alex"),
+                CRED_ID_2.to_string() => _credential("Alex")
+            );
+
+            Prover::new()._check_equality(EQUALITY_REFERENT, &_equality(), &_proof_req(), &credentials, &_req_cred(false)).unwrap();
+        }
+
+        #[test]
+        fn check_equality_fails_for_different_values() {
+            let credentials = hashmap!(
+                CRED_ID_1.to_string() => _credential("Alex"),
+                CRED_ID_2.to_string() => _credential("Alexander")
+            );
+
+            let res = Prover::new()._check_equality(EQUALITY_REFERENT, &_equality(), &_proof_req(), &credentials, &_req_cred(false));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_equality_fails_for_partially_revealed_attributes() {
+            let credentials = hashmap!(
+                CRED_ID_1.to_string() => _credential("Alex"),
+                CRED_ID_2.to_string() => _credential("Alex")
+            );
+
+            let res = Prover::new()._check_equality(EQUALITY_REFERENT, &_equality(), &_proof_req(), &credentials, &_req_cred(true));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn check_equality_works_for_different_attributes() {
+            let credentials = hashmap!(
+                CRED_ID_1.to_string() => _credential("Alex"),
+                CRED_ID_2.to_string() => _credential("Alex")
+            );
+
+            let mut proof_req = _proof_req();
+            proof_req.requested_attributes.get_mut("attr_2").unwrap().name = Some("Nick Name".to_string());
+
+            Prover::new()._check_equality(EQUALITY_REFERENT, &_equality(), &proof_req, &credentials, &_req_cred(false)).unwrap();
+        }
+
+        #[test]
+        fn check_membership_fails_for_hidden_attribute() {
+            let credentials = hashmap!(
+                CRED_ID_1.to_string() => _credential("Alex")
+            );
+            let membership = MembershipInfo { attr_referent: "attr_1".to_string(), values: vec!["Alex".to_string()] };
+
+            let res = Prover::new()._check_membership("membership_referent", &membership, &_proof_req(), &credentials, &_req_cred(false));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+
+        #[test]
+        fn link_hidden_attributes_works() {
+            let mut proof_req = _proof_req();
+            proof_req.requested_attributes.insert("attr_3".to_string(), _attr_info());
+            proof_req.requested_equalities.insert("equality_2".to_string(), EqualityInfo { attr_referents: vec!["attr_2".to_string(), "attr_3".to_string()] });
+
+            let mut req_cred = _req_cred(false);
+            req_cred.requested_attributes.insert("attr_3".to_string(), RequestedAttribute { cred_id: "cred_3".to_string(), timestamp: None, revealed: false });
+
+            let blindings = Prover::_link_hidden_attributes(&proof_req, &req_cred).unwrap();
+            assert_eq!(3, blindings.len());
+
+            let blinding = |cred_id: &str| blindings[&(ProvingCredentialKey { cred_id: cred_id.to_string(), timestamp: None }, "name".to_string())].to_dec().unwrap();
+            assert_eq!(blinding(CRED_ID_1), blinding(CRED_ID_2));
+            assert_eq!(blinding(CRED_ID_1), blinding("cred_3"));
+        }
+
+        #[test]
+        fn link_hidden_attributes_works_for_revealed_attributes() {
+            let blindings = Prover::_link_hidden_attributes(&_proof_req(), &_req_cred(true)).unwrap();
+            assert_eq!(1, blindings.len());
+            assert!(blindings.contains_key(&(ProvingCredentialKey { cred_id: CRED_ID_1.to_string(), timestamp: None }, "name".to_string())));
+        }
+    }
+
     mod get_credential_values_for_attribute {
         use super::*;
 
@@ -972,6 +1519,311 @@ mod tests {
         }
     }
 
+    mod select_credentials_for_proof_req {
+        use super::*;
+        use crate::domain::anoncreds::credential::CredentialInfo;
+        use crate::domain::anoncreds::proof_request::NonRevocedInterval;
+        use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
+
+        const OTHER_ISSUER_DID: &str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW";
+        const OTHER_CRED_DEF_ID: &str = "CnEDk9HrMnmiHXEV1WFgbVCRteYnPqsJwrTdcZaNhFVW:3:CL:NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0:tag";
+        const NOW: u64 = 100;
+
+        fn _proof_req(non_revoked: Option<serde_json::Value>) -> ProofRequestPayload {
+            serde_json::from_value(json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name"},
+                    "attr2_referent": {"name": "sex"}
+                },
+                "requested_predicates": {
+                    "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18}
+                },
+                "non_revoked": non_revoked
+            })).unwrap()
+        }
+
+        fn _credential(cred_id: &str, cred_def_id: &str, revocable: bool, name: &str, interval: Option<NonRevocedInterval>) -> RequestedCredential {
+            RequestedCredential {
+                cred_info: CredentialInfo {
+                    referent: cred_id.to_string(),
+                    attrs: hashmap!(
+                        "name".to_string() => name.to_string(),
+                        "sex".to_string() => "male".to_string(),
+                        "age".to_string() => "28".to_string()
+                    ),
+                    schema_id: SchemaId(SCHEMA_ID.to_string()),
+                    cred_def_id: CredentialDefinitionId(cred_def_id.to_string()),
+                    rev_reg_id: if revocable { Some(RevocationRegistryId(REV_REG_ID.to_string())) } else { None },
+                    cred_rev_id: if revocable { Some("1".to_string()) } else { None },
+                },
+                interval,
+            }
+        }
+
+        fn _credentials(attr1: Vec<RequestedCredential>, attr2: Vec<RequestedCredential>, predicate1: Vec<RequestedCredential>) -> CredentialsForProofRequest {
+            CredentialsForProofRequest {
+                attrs: hashmap!(
+                    "attr1_referent".to_string() => attr1,
+                    "attr2_referent".to_string() => attr2
+                ),
+                predicates: hashmap!(
+                    "predicate1_referent".to_string() => predicate1
+                ),
+            }
+        }
+
+        fn _cred_ids(requested_credentials: &RequestedCredentials) -> (String, String, String) {
+            (requested_credentials.requested_attributes["attr1_referent"].cred_id.clone(),
+             requested_credentials.requested_attributes["attr2_referent"].cred_id.clone(),
+             requested_credentials.requested_predicates["predicate1_referent"].cred_id.clone())
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_default_policy() {
+            let ps = Prover::new();
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("2", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None)]);
+
+            let (requested_credentials, rev_states) =
+                ps.select_credentials_for_proof_req(&_proof_req(None), &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW).unwrap();
+
+            assert_eq!(("1".to_string(), "2".to_string(), "1".to_string()), _cred_ids(&requested_credentials));
+            assert!(requested_credentials.requested_attributes["attr1_referent"].revealed);
+            assert!(rev_states.is_empty());
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_newest() {
+            let ps = Prover::new();
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None)]);
+
+            let stored_at = hashmap!("1".to_string() => 10, "2".to_string() => 20);
+            let policy = CredentialSelectionPolicy { newest: true, ..CredentialSelectionPolicy::default() };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&_proof_req(None), &credentials, &stored_at, &policy, NOW).unwrap();
+
+            assert_eq!(("2".to_string(), "2".to_string(), "1".to_string()), _cred_ids(&requested_credentials));
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_prefer_issuers() {
+            let ps = Prover::new();
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", OTHER_CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", OTHER_CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", OTHER_CRED_DEF_ID, false, "Alex", None)]);
+
+            let policy = CredentialSelectionPolicy { prefer_issuers: vec![DidValue(OTHER_ISSUER_DID.to_string()), DidValue(ISSUER_DID.to_string())], ..CredentialSelectionPolicy::default() };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&_proof_req(None), &credentials, &HashMap::new(), &policy, NOW).unwrap();
+
+            assert_eq!(("2".to_string(), "2".to_string(), "2".to_string()), _cred_ids(&requested_credentials));
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_prefer_non_revocable() {
+            let ps = Prover::new();
+
+            let interval = Some(NonRevocedInterval { from: None, to: Some(50) });
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, true, "Alex", interval.clone()), _credential("2", CRED_DEF_ID, false, "Alex", interval.clone())],
+                                           vec![_credential("1", CRED_DEF_ID, true, "Alex", interval.clone())],
+                                           vec![_credential("1", CRED_DEF_ID, true, "Alex", interval.clone()), _credential("2", CRED_DEF_ID, false, "Alex", interval.clone())]);
+
+            let policy = CredentialSelectionPolicy { prefer_non_revocable: true, ..CredentialSelectionPolicy::default() };
+
+            let (requested_credentials, rev_states) =
+                ps.select_credentials_for_proof_req(&_proof_req(Some(json!({"to": 50}))), &credentials, &HashMap::new(), &policy, NOW).unwrap();
+
+            assert_eq!(("2".to_string(), "1".to_string(), "2".to_string()), _cred_ids(&requested_credentials));
+            assert_eq!(None, requested_credentials.requested_attributes["attr1_referent"].timestamp);
+            assert_eq!(Some(50), requested_credentials.requested_attributes["attr2_referent"].timestamp);
+            assert_eq!(vec![RequiredRevocationState { rev_reg_id: RevocationRegistryId(REV_REG_ID.to_string()), cred_rev_id: "1".to_string(), timestamp: 50 }], rev_states);
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_revocable_and_interval_without_bounds() {
+            let ps = Prover::new();
+
+            let interval = Some(NonRevocedInterval { from: None, to: None });
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, true, "Alex", interval.clone())],
+                                           vec![_credential("1", CRED_DEF_ID, true, "Alex", interval.clone())],
+                                           vec![_credential("1", CRED_DEF_ID, true, "Alex", interval.clone())]);
+
+            let (requested_credentials, rev_states) =
+                ps.select_credentials_for_proof_req(&_proof_req(Some(json!({}))), &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW).unwrap();
+
+            assert_eq!(Some(NOW), requested_credentials.requested_predicates["predicate1_referent"].timestamp);
+            assert_eq!(1, rev_states.len());
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_minimize_credentials() {
+            let ps = Prover::new();
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("3", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Alex", None)]);
+
+            let policy = CredentialSelectionPolicy { minimize_credentials: true, ..CredentialSelectionPolicy::default() };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&_proof_req(None), &credentials, &HashMap::new(), &policy, NOW).unwrap();
+
+            assert_eq!(("2".to_string(), "2".to_string(), "2".to_string()), _cred_ids(&requested_credentials));
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_self_attested_attribute() {
+            let ps = Prover::new();
+
+            let credentials = _credentials(vec![],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None)]);
+
+            let policy = CredentialSelectionPolicy { self_attested_attributes: hashmap!("attr1_referent".to_string() => "Alex".to_string()), ..CredentialSelectionPolicy::default() };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&_proof_req(None), &credentials, &HashMap::new(), &policy, NOW).unwrap();
+
+            assert_eq!(hashmap!("attr1_referent".to_string() => "Alex".to_string()), requested_credentials.self_attested_attributes);
+            assert!(!requested_credentials.requested_attributes.contains_key("attr1_referent"));
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_missing_credential() {
+            let ps = Prover::new();
+
+            let credentials = _credentials(vec![_credential("1", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![_credential("1", CRED_DEF_ID, false, "Alex", None)],
+                                           vec![]);
+
+            let res = ps.select_credentials_for_proof_req(&_proof_req(None), &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW);
+            assert_kind!(IndyErrorKind::ItemNotFound, res);
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_requested_equality() {
+            let ps = Prover::new();
+
+            let proof_req: ProofRequestPayload = serde_json::from_value(json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name"},
+                    "attr2_referent": {"name": "name"}
+                },
+                "requested_equalities": {
+                    "equality1_referent": {"attr_referents": ["attr1_referent", "attr2_referent"]}
+                }
+            })).unwrap();
+
+            let credentials = CredentialsForProofRequest {
+                attrs: hashmap!(
+                    "attr1_referent".to_string() => vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Bob", None)],
+                    "attr2_referent".to_string() => vec![_credential("3", OTHER_CRED_DEF_ID, false, "Bob", None)]
+                ),
+                predicates: HashMap::new(),
+            };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&proof_req, &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW).unwrap();
+
+            assert_eq!("2", requested_credentials.requested_attributes["attr1_referent"].cred_id);
+            assert_eq!("3", requested_credentials.requested_attributes["attr2_referent"].cred_id);
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_requested_equality_of_different_attributes() {
+            let ps = Prover::new();
+
+            let proof_req: ProofRequestPayload = serde_json::from_value(json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name"},
+                    "attr2_referent": {"name": "sex"}
+                },
+                "requested_equalities": {
+                    "equality1_referent": {"attr_referents": ["attr1_referent", "attr2_referent"]}
+                }
+            })).unwrap();
+
+            let credentials = CredentialsForProofRequest {
+                attrs: hashmap!(
+                    "attr1_referent".to_string() => vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "male", None)],
+                    "attr2_referent".to_string() => vec![_credential("3", OTHER_CRED_DEF_ID, false, "Bob", None)]
+                ),
+                predicates: HashMap::new(),
+            };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&proof_req, &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW).unwrap();
+
+            assert_eq!("2", requested_credentials.requested_attributes["attr1_referent"].cred_id);
+            assert_eq!("3", requested_credentials.requested_attributes["attr2_referent"].cred_id);
+        }
+
+        fn _proof_req_with_membership(values: serde_json::Value) -> ProofRequestPayload {
+            serde_json::from_value(json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name"}
+                },
+                "requested_memberships": {
+                    "membership1_referent": {"attr_referent": "attr1_referent", "values": values}
+                }
+            })).unwrap()
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_requested_membership() {
+            let ps = Prover::new();
+
+            let credentials = CredentialsForProofRequest {
+                attrs: hashmap!(
+                    "attr1_referent".to_string() => vec![_credential("1", CRED_DEF_ID, false, "Alex", None), _credential("2", CRED_DEF_ID, false, "Bob", None)]
+                ),
+                predicates: HashMap::new(),
+            };
+
+            let (requested_credentials, _) =
+                ps.select_credentials_for_proof_req(&_proof_req_with_membership(json!(["Bob", "Carol"])), &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW).unwrap();
+
+            assert_eq!("2", requested_credentials.requested_attributes["attr1_referent"].cred_id);
+        }
+
+        #[test]
+        fn select_credentials_for_proof_req_works_for_requested_membership_without_member_credentials() {
+            let ps = Prover::new();
+
+            let credentials = CredentialsForProofRequest {
+                attrs: hashmap!(
+                    "attr1_referent".to_string() => vec![_credential("1", CRED_DEF_ID, false, "Alex", None)]
+                ),
+                predicates: HashMap::new(),
+            };
+
+            let res = ps.select_credentials_for_proof_req(&_proof_req_with_membership(json!(["Bob", "Carol"])), &credentials, &HashMap::new(), &CredentialSelectionPolicy::default(), NOW);
+            assert_kind!(IndyErrorKind::ItemNotFound, res);
+        }
+    }
+
     mod extend_operator {
         use super::*;
 
@@ -1,17 +1,20 @@
 use std::collections::{HashMap, HashSet};
 
+use crate::domain::anoncreds::credential::AttributeEncoding;
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1, CredentialDefinitionId};
 use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo};
-use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
+use crate::domain::anoncreds::proof_request::{AttributeInfo, EqualityInfo, MembershipInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistryV1;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
+use crate::domain::anoncreds::verification_report::{CheckResult, ReferentReport, ReferentStatus, SubProofReport, VerificationReport};
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::helpers::*;
+use crate::services::anoncreds::membership;
 
 use ursa::bn::BigNumber;
-use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
-use ursa::cl::verifier::Verifier as CryptoVerifier;
+use ursa::cl::{CredentialPublicKey, NonCredentialSchema, new_nonce, Nonce};
+use ursa::cl::verifier::{ProofVerifier, Verifier as CryptoVerifier};
 use crate::utils::wql::Query;
 use regex::Regex;
 
@@ -60,6 +63,10 @@ impl Verifier {
 
         Verifier::_verify_revealed_attribute_values(&proof_req, &full_proof)?;
 
+        Verifier::_verify_requested_equalities(&proof_req, &full_proof)?;
+
+        Verifier::_verify_requested_memberships(&proof_req, &full_proof)?;
+
         Verifier::_verify_requested_restrictions(&proof_req,
                                                  &full_proof.requested_proof,
                                                  &received_revealed_attrs,
@@ -77,56 +84,155 @@ impl Verifier {
         let non_credential_schema = build_non_credential_schema()?;
 
         for sub_proof_index in 0..full_proof.identifiers.len() {
-            let identifier = full_proof.identifiers[sub_proof_index].clone();
+            Verifier::_add_sub_proof_request(&mut proof_verifier, &non_credential_schema, sub_proof_index,
+                                             full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs)?;
+        }
 
-            let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
+        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
 
-            let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+        trace!("verify <<< valid: {:?}", valid);
 
-            let (rev_reg_def, rev_reg) =
-                if let Some(timestamp) = identifier.timestamp {
-                    let rev_reg_id = identifier.rev_reg_id
-                        .clone()
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
+        Ok(valid)
+    }
 
-                    let rev_reg_def = Some(rev_reg_defs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+    pub fn verify_with_report(&self,
+                              full_proof: &Proof,
+                              proof_req: &ProofRequestPayload,
+                              schemas: &HashMap<SchemaId, SchemaV1>,
+                              cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                              rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                              rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<VerificationReport> {
+        trace!("verify_with_report >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
+               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
 
-                    let rev_regs_for_cred = rev_regs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+        let received_revealed_attrs: HashMap<String, Identifier> = Verifier::_received_revealed_attrs(&full_proof)?;
+        let received_unrevealed_attrs: HashMap<String, Identifier> = Verifier::_received_unrevealed_attrs(&full_proof)?;
+        let received_predicates: HashMap<String, Identifier> = Verifier::_received_predicates(&full_proof)?;
+        let received_self_attested_attrs: HashSet<String> = Verifier::_received_self_attested_attrs(&full_proof);
+
+        let proof_attr_identifiers: HashMap<String, Identifier> = received_revealed_attrs
+            .iter()
+            .chain(&received_unrevealed_attrs)
+            .map(|(r, id)| (r.to_string(), id.clone()))
+            .collect();
 
-                    let rev_reg = Some(rev_regs_for_cred
-                        .get(&timestamp)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
+        let requested_proof = &full_proof.requested_proof;
+
+        let structure = _check_result(
+            Verifier::_compare_attr_from_proof_and_request(proof_req,
+                                                           &received_revealed_attrs,
+                                                           &received_unrevealed_attrs,
+                                                           &received_self_attested_attrs,
+                                                           &received_predicates));
+
+        let mut attributes: HashMap<String, ReferentReport> = HashMap::new();
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            let (status, sub_proof_index) =
+                if let Some(attr) = requested_proof.revealed_attrs.get(referent) {
+                    (ReferentStatus::Revealed, Some(attr.sub_proof_index))
+                } else if let Some(attrs) = requested_proof.revealed_attr_groups.get(referent) {
+                    (ReferentStatus::Revealed, Some(attrs.sub_proof_index))
+                } else if let Some(attr) = requested_proof.unrevealed_attrs.get(referent) {
+                    (ReferentStatus::Unrevealed, Some(attr.sub_proof_index))
+                } else if received_self_attested_attrs.contains(referent) {
+                    (ReferentStatus::SelfAttested, None)
+                } else {
+                    (ReferentStatus::Missing, None)
+                };
 
-                    (rev_reg_def, rev_reg)
-                } else { (None, None) };
+            let value = if status == ReferentStatus::Revealed {
+                Some(_check_result(Verifier::_verify_revealed_attribute_referent(referent, proof_req, full_proof)))
+            } else { None };
 
-            let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
-            let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
+            let restrictions = if info.restrictions.is_some() && status != ReferentStatus::Missing &&
+                !Verifier::_is_self_attested(referent, info, &received_self_attested_attrs) {
+                Some(_check_result(Verifier::_verify_attr_restrictions(referent, info, proof_req, requested_proof, &proof_attr_identifiers)))
+            } else { None };
 
-            let credential_schema = build_credential_schema(&schema.attr_names.0)?;
-            let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
+            let non_revoked = if get_non_revoc_interval(&proof_req.non_revoked, &info.non_revoked).is_some() {
+                Some(_check_result(
+                    Verifier::_validate_attr_timestamp(proof_req, referent, info, &received_revealed_attrs, &received_unrevealed_attrs, &received_self_attested_attrs)
+                        .map_err(|err| err.extend("Timestamp is not provided for requested non-revocation interval"))))
+            } else { None };
 
-            let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+            attributes.insert(referent.to_string(), ReferentReport { status, sub_proof_index, value, restrictions, non_revoked });
+        }
 
-            proof_verifier.add_sub_proof_request(&sub_proof_request,
-                                                 &credential_schema,
-                                                 &non_credential_schema,
-                                                 &credential_pub_key,
-                                                 rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
-                                                 rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
+        let mut predicates: HashMap<String, ReferentReport> = HashMap::new();
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            let (status, sub_proof_index) = match requested_proof.predicates.get(referent) {
+                Some(predicate) => (ReferentStatus::Proven, Some(predicate.sub_proof_index)),
+                None => (ReferentStatus::Missing, None)
+            };
+
+            let restrictions = if info.restrictions.is_some() && status != ReferentStatus::Missing {
+                Some(_check_result(Verifier::_verify_predicate_restrictions(referent, info, proof_req, requested_proof, &received_predicates)))
+            } else { None };
+
+            let non_revoked = if get_non_revoc_interval(&proof_req.non_revoked, &info.non_revoked).is_some() {
+                Some(_check_result(
+                    Verifier::_validate_timestamp(&received_predicates, referent, &proof_req.non_revoked, &info.non_revoked)
+                        .map_err(|err| err.extend("Timestamp is not provided for requested non-revocation interval"))))
+            } else { None };
+
+            predicates.insert(referent.to_string(), ReferentReport { status, sub_proof_index, value: None, restrictions, non_revoked });
         }
 
-        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
+        let crypto_proof = serde_json::to_value(&full_proof.proof)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CryptoProof")?;
 
-        trace!("verify <<< valid: {:?}", valid);
+        let equalities: HashMap<String, CheckResult> = proof_req.requested_equalities
+            .iter()
+            .map(|(referent, equality)|
+                (referent.to_string(), _check_result(Verifier::_verify_requested_equality(referent, equality, proof_req, full_proof, &crypto_proof))))
+            .collect();
 
-        Ok(valid)
+        let memberships: HashMap<String, CheckResult> = proof_req.requested_memberships
+            .iter()
+            .map(|(referent, membership)|
+                (referent.to_string(), _check_result(Verifier::_verify_requested_membership(referent, membership, proof_req, full_proof))))
+            .collect();
+
+        let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
+        let non_credential_schema = build_non_credential_schema()?;
+
+        let sub_proofs: Vec<SubProofReport> = full_proof.identifiers
+            .iter()
+            .enumerate()
+            .map(|(sub_proof_index, identifier)| SubProofReport {
+                schema_id: identifier.schema_id.clone(),
+                cred_def_id: identifier.cred_def_id.clone(),
+                rev_reg_id: identifier.rev_reg_id.clone(),
+                timestamp: identifier.timestamp,
+                resolved: _check_result(
+                    Verifier::_add_sub_proof_request(&mut proof_verifier, &non_credential_schema, sub_proof_index,
+                                                     full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs)),
+            })
+            .collect();
+
+        let crypto_proof = if sub_proofs.iter().all(|sub_proof| sub_proof.resolved.valid) {
+            Some(match proof_verifier.verify(&full_proof.proof, &proof_req.nonce) {
+                Ok(true) => CheckResult::valid(),
+                Ok(false) => CheckResult::invalid("Primary or non-revocation proof is invalid".to_string()),
+                Err(err) => _check_result(Err(IndyError::from(err)))
+            })
+        } else { None };
+
+        let mut report = VerificationReport {
+            valid: false,
+            structure,
+            attributes,
+            predicates,
+            equalities,
+            memberships,
+            sub_proofs,
+            crypto_proof,
+        };
+        report.valid = report.is_valid();
+
+        trace!("verify_with_report <<< report: {:?}", report);
+
+        Ok(report)
     }
 
     pub fn generate_nonce(&self) -> IndyResult<Nonce> {
@@ -139,6 +245,62 @@ impl Verifier {
         Ok(nonce)
     }
 
+    fn _add_sub_proof_request(proof_verifier: &mut ProofVerifier,
+                              non_credential_schema: &NonCredentialSchema,
+                              sub_proof_index: usize,
+                              full_proof: &Proof,
+                              proof_req: &ProofRequestPayload,
+                              schemas: &HashMap<SchemaId, SchemaV1>,
+                              cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                              rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                              rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<()> {
+        let identifier = full_proof.identifiers[sub_proof_index].clone();
+
+        let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
+
+        let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+
+        let (rev_reg_def, rev_reg) =
+            if let Some(timestamp) = identifier.timestamp {
+                let rev_reg_id = identifier.rev_reg_id
+                    .clone()
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
+
+                let rev_reg_def = Some(rev_reg_defs
+                    .get(&rev_reg_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+
+                let rev_regs_for_cred = rev_regs
+                    .get(&rev_reg_id)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+
+                let rev_reg = Some(rev_regs_for_cred
+                    .get(&timestamp)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
+
+                (rev_reg_def, rev_reg)
+            } else { (None, None) };
+
+        let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
+        let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
+
+        let credential_schema = build_credential_schema(&schema.attr_names.0)?;
+        let sub_proof_request = build_sub_proof_request(&attrs_for_credential, &predicates_for_credential)?;
+
+        let credential_pub_key = CredentialPublicKey::build_from_parts(&cred_def.value.primary, cred_def.value.revocation.as_ref())?;
+
+        proof_verifier.add_sub_proof_request(&sub_proof_request,
+                                             &credential_schema,
+                                             non_credential_schema,
+                                             &credential_pub_key,
+                                             rev_reg_def.as_ref().map(|r_reg_def| &r_reg_def.value.public_keys.accum_key),
+                                             rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
+
+        Ok(())
+    }
+
     fn _get_revealed_attributes_for_credential(sub_proof_index: usize,
                                                requested_proof: &RequestedProof,
                                                proof_req: &ProofRequestPayload) -> IndyResult<Vec<AttributeInfo>> {
@@ -235,9 +397,7 @@ impl Verifier {
         proof_req.requested_attributes
             .iter()
             .map(|(referent, info)|
-                Verifier::_validate_timestamp(&received_revealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked)
-                    .or_else(|_| Verifier::_validate_timestamp(&received_unrevealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked))
-                    .or_else(|_| received_self_attested_attrs.get(referent).map(|_| ()).ok_or_else(|| IndyError::from(IndyErrorKind::InvalidStructure)))
+                Verifier::_validate_attr_timestamp(proof_req, referent, info, received_revealed_attrs, received_unrevealed_attrs, received_self_attested_attrs)
             )
             .collect::<IndyResult<Vec<()>>>()?;
 
@@ -250,6 +410,17 @@ impl Verifier {
         Ok(())
     }
 
+    fn _validate_attr_timestamp(proof_req: &ProofRequestPayload,
+                                referent: &str,
+                                info: &AttributeInfo,
+                                received_revealed_attrs: &HashMap<String, Identifier>,
+                                received_unrevealed_attrs: &HashMap<String, Identifier>,
+                                received_self_attested_attrs: &HashSet<String>) -> IndyResult<()> {
+        Verifier::_validate_timestamp(&received_revealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked)
+            .or_else(|_| Verifier::_validate_timestamp(&received_unrevealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked))
+            .or_else(|_| received_self_attested_attrs.get(referent).map(|_| ()).ok_or_else(|| IndyError::from(IndyErrorKind::InvalidStructure)))
+    }
+
     fn _validate_timestamp(received_: &HashMap<String, Identifier>, referent: &str,
                            global_interval: &Option<NonRevocedInterval>, local_interval: &Option<NonRevocedInterval>) -> IndyResult<()> {
         if get_non_revoc_interval(global_interval, local_interval).is_none() {
@@ -324,20 +495,27 @@ impl Verifier {
 
     fn _verify_revealed_attribute_values(proof_req: &ProofRequestPayload,
                                          proof: &Proof) -> IndyResult<()> {
-        for (attr_referent, attr_info) in proof.requested_proof.revealed_attrs.iter() {
+        for attr_referent in proof.requested_proof.revealed_attrs.keys().chain(proof.requested_proof.revealed_attr_groups.keys()) {
+            Verifier::_verify_revealed_attribute_referent(attr_referent, proof_req, proof)?;
+        }
+        Ok(())
+    }
 
-            let attr_name = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
+    fn _verify_revealed_attribute_referent(attr_referent: &str,
+                                           proof_req: &ProofRequestPayload,
+                                           proof: &Proof) -> IndyResult<()> {
+        let requested_attr = proof_req.requested_attributes.get(attr_referent)
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
+
+        if let Some(attr_info) = proof.requested_proof.revealed_attrs.get(attr_referent) {
+            let attr_name = requested_attr
                 .name.as_ref()
                 .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info)?;
+            Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info, requested_attr.encoding.as_ref())?;
         }
 
-        for (attr_referent, attr_infos) in proof.requested_proof.revealed_attr_groups.iter() {
-            let attr_names = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
+        if let Some(attr_infos) = proof.requested_proof.revealed_attr_groups.get(attr_referent) {
+            let attr_names = requested_attr
                 .names.as_ref()
                 .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
             if attr_infos.values.len() != attr_names.len() {
@@ -351,7 +529,7 @@ impl Verifier {
                     sub_proof_index: attr_infos.sub_proof_index,
                     raw: attr_info.raw.clone(),
                     encoded: attr_info.encoded.clone()
-                })?;
+                }, requested_attr.encoding.as_ref())?;
             }
         }
         Ok(())
@@ -359,8 +537,20 @@ impl Verifier {
 
     fn _verify_revealed_attribute_value(attr_name: &str,
                                         proof: &Proof,
-                                        attr_info: &RevealedAttributeInfo) -> IndyResult<()> {
+                                        attr_info: &RevealedAttributeInfo,
+                                        encoding: Option<&AttributeEncoding>) -> IndyResult<()> {
         let reveal_attr_encoded = &attr_info.encoded;
+
+        if let Some(encoding) = encoding {
+            let expected_encoded = encode_attribute_value(&attr_info.raw, encoding)
+                .map_err(|err| IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                                   format!("Raw value for \"{}\" doesn't match requested encoding: {}", attr_name, err)))?;
+
+            if BigNumber::from_dec(reveal_attr_encoded)? != BigNumber::from_dec(&expected_encoded)? {
+                return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                               format!("Encoded Value for \"{}\" \"{}\" doesn't match raw value \"{}\" encoded as {:?}", attr_name, reveal_attr_encoded, attr_info.raw, encoding)));
+            }
+        }
         let sub_proof_index = attr_info.sub_proof_index as usize;
 
         let crypto_proof_encoded = proof.proof.proofs
@@ -380,6 +570,89 @@ impl Verifier {
         Ok(())
     }
 
+    fn _verify_requested_equalities(proof_req: &ProofRequestPayload,
+                                    proof: &Proof) -> IndyResult<()> {
+        if proof_req.requested_equalities.is_empty() {
+            return Ok(());
+        }
+
+        let crypto_proof = serde_json::to_value(&proof.proof)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CryptoProof")?;
+
+        for (equality_referent, equality) in proof_req.requested_equalities.iter() {
+            Verifier::_verify_requested_equality(equality_referent, equality, proof_req, proof, &crypto_proof)?;
+        }
+
+        Ok(())
+    }
+
+    fn _verify_requested_equality(equality_referent: &str,
+                                  equality: &EqualityInfo,
+                                  proof_req: &ProofRequestPayload,
+                                  proof: &Proof,
+                                  crypto_proof: &serde_json::Value) -> IndyResult<()> {
+        // Revealed values are compared by encoding. Hidden values are linked by common blinding factor
+        // so the prover responses (m^) of all sub proofs are the same only if the values are equal.
+        let mut values: HashSet<(bool, String)> = HashSet::new();
+
+        for attr_referent in equality.attr_referents.iter() {
+            let attr_name = proof_req.attr_name(attr_referent)
+                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute name not found for \"{}\" of equality \"{}\"", attr_referent, equality_referent)))?;
+
+            let value = if let Some(attr_info) = proof.requested_proof.revealed_attrs.get(attr_referent) {
+                (true, BigNumber::from_dec(&attr_info.encoded)?.to_dec()?)
+            } else if let Some(sub_proof_referent) = proof.requested_proof.unrevealed_attrs.get(attr_referent) {
+                let m_hat = crypto_proof["proofs"][sub_proof_referent.sub_proof_index as usize]["primary_proof"]["eq_proof"]["m"][&attr_name]
+                    .as_str()
+                    .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Hidden value of \"{}\" not found in CryptoProof", attr_name)))?;
+
+                (false, BigNumber::from_dec(m_hat)?.to_dec()?)
+            } else {
+                return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                               format!("Attribute \"{}\" of equality \"{}\" not found in Proof", attr_referent, equality_referent)));
+            };
+
+            values.insert(value);
+        }
+
+        if values.len() != 1 {
+            return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                           format!("Values are not proven to be equal for equality \"{}\"", equality_referent)));
+        }
+
+        Ok(())
+    }
+
+    fn _verify_requested_memberships(proof_req: &ProofRequestPayload,
+                                     proof: &Proof) -> IndyResult<()> {
+        for (membership_referent, membership) in proof_req.requested_memberships.iter() {
+            Verifier::_verify_requested_membership(membership_referent, membership, proof_req, proof)?;
+        }
+
+        Ok(())
+    }
+
+    // Only revealed values are checked: membership of hidden values isn't supported
+    fn _verify_requested_membership(membership_referent: &str,
+                                    membership: &MembershipInfo,
+                                    proof_req: &ProofRequestPayload,
+                                    proof: &Proof) -> IndyResult<()> {
+        let attr_referent = &membership.attr_referent;
+
+        let attr_info = proof.requested_proof.revealed_attrs.get(attr_referent)
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                       format!("Attribute \"{}\" of membership \"{}\" is not revealed in Proof", attr_referent, membership_referent)))?;
+
+        let set = membership::encode_set(membership_referent, membership, proof_req)?;
+
+        if !membership::contains(&set, &attr_info.encoded)? {
+            return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                           format!("Revealed value of \"{}\" doesn't belong to the set of membership \"{}\"", attr_referent, membership_referent)));
+        }
+
+        Ok(())
+    }
+
     fn _verify_requested_restrictions(proof_req: &ProofRequestPayload,
                                       requested_proof: &RequestedProof,
                                       received_revealed_attrs: &HashMap<String, Identifier>,
@@ -392,77 +665,90 @@ impl Verifier {
             .map(|(r, id)| (r.to_string(), id.clone()))
             .collect();
 
-        let requested_attrs: HashMap<String, AttributeInfo> = proof_req.requested_attributes
-            .iter()
-            .filter(|&(referent, info)| !Verifier::_is_self_attested(&referent, &info, self_attested_attrs))
-            .map(|(referent, info)| (referent.to_string(), info.clone()))
-            .collect();
-
-        for (referent, info) in requested_attrs.clone() {
-            if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, &proof_attr_identifiers)?;
-
-                let name_value_map: HashMap<String, Option<&str>> = if let Some(name) = info.name {
-                    let mut map = HashMap::new();
-                    map.insert(name.clone(), requested_proof.revealed_attrs.get(&referent).map(|attr| attr.raw.as_str()));
-                    map
-                } else if let Some(names) = info.names {
-                    let mut map = HashMap::new();
-                    let attrs = requested_proof.revealed_attr_groups.get(&referent)
-                        .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
-                    for name in names {
-                        let val = attrs.values.get(&name).map(|attr| attr.raw.as_str());
-                        map.insert(name, val);
-                    }
-                    map
-                } else {
-                    error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
-                    return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
-                };
-
-                Verifier::_do_process_operator(&name_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            if !Verifier::_is_self_attested(&referent, &info, self_attested_attrs) {
+                Verifier::_verify_attr_restrictions(referent, info, proof_req, requested_proof, &proof_attr_identifiers)?;
             }
         }
 
         for (referent, info) in proof_req.requested_predicates.iter() {
-            if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
-
-                // start with the predicate requested attribute, which is un-revealed
-                let mut attr_value_map = HashMap::new();
-                attr_value_map.insert(info.name.to_string(), None);
-
-                // include any revealed attributes for the same credential (based on sub_proof_index)
-                let pred_sub_proof_index = requested_proof.predicates.get(referent).unwrap().sub_proof_index;
-                for attr_referent in requested_proof.revealed_attrs.keys() {
-                    let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        let attr_name = requested_attrs.get(attr_referent).unwrap().name.clone();
-                        if let Some(name) = attr_name {
-                            attr_value_map.insert(name, Some(attr_info.raw.as_str()));
-                        }
+            Verifier::_verify_predicate_restrictions(referent, info, proof_req, requested_proof, received_predicates)?;
+        }
+
+        Ok(())
+    }
+
+    fn _verify_attr_restrictions(referent: &str,
+                                 info: &AttributeInfo,
+                                 proof_req: &ProofRequestPayload,
+                                 requested_proof: &RequestedProof,
+                                 proof_attr_identifiers: &HashMap<String, Identifier>) -> IndyResult<()> {
+        if let Some(ref query) = info.restrictions {
+            let filter = Verifier::_gather_filter_info(&referent, proof_attr_identifiers)?;
+
+            let name_value_map: HashMap<String, Option<&str>> = if let Some(ref name) = info.name {
+                let mut map = HashMap::new();
+                map.insert(name.clone(), requested_proof.revealed_attrs.get(referent).map(|attr| attr.raw.as_str()));
+                map
+            } else if let Some(ref names) = info.names {
+                let mut map = HashMap::new();
+                let attrs = requested_proof.revealed_attr_groups.get(referent)
+                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
+                for name in names {
+                    let val = attrs.values.get(name).map(|attr| attr.raw.as_str());
+                    map.insert(name.clone(), val);
+                }
+                map
+            } else {
+                error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
+                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
+            };
+
+            Verifier::_do_process_operator(&name_value_map, &query, &filter)
+                .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
+        }
+
+        Ok(())
+    }
+
+    fn _verify_predicate_restrictions(referent: &str,
+                                      info: &PredicateInfo,
+                                      proof_req: &ProofRequestPayload,
+                                      requested_proof: &RequestedProof,
+                                      received_predicates: &HashMap<String, Identifier>) -> IndyResult<()> {
+        if let Some(ref query) = info.restrictions {
+            let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
+
+            // start with the predicate requested attribute, which is un-revealed
+            let mut attr_value_map = HashMap::new();
+            attr_value_map.insert(info.name.to_string(), None);
+
+            // include any revealed attributes for the same credential (based on sub_proof_index)
+            let pred_sub_proof_index = requested_proof.predicates.get(referent)
+                .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?
+                .sub_proof_index;
+            for (attr_referent, attr_info) in requested_proof.revealed_attrs.iter() {
+                if pred_sub_proof_index == attr_info.sub_proof_index {
+                    let attr_name = proof_req.requested_attributes.get(attr_referent).and_then(|attr| attr.name.clone());
+                    if let Some(name) = attr_name {
+                        attr_value_map.insert(name, Some(attr_info.raw.as_str()));
                     }
                 }
-                for attr_referent in requested_proof.revealed_attr_groups.keys() {
-                    let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        for name in attr_info.values.keys() {
-                            let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
-                            attr_value_map.insert(name.clone(), Some(raw_val.clone()));
-                        }
+            }
+            for attr_info in requested_proof.revealed_attr_groups.values() {
+                if pred_sub_proof_index == attr_info.sub_proof_index {
+                    for (name, value) in attr_info.values.iter() {
+                        attr_value_map.insert(name.clone(), Some(value.raw.as_str()));
                     }
                 }
+            }
 
-                Verifier::_do_process_operator(&attr_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
+            Verifier::_do_process_operator(&attr_value_map, &query, &filter)
+                .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
 
-                // old style :-/ which fails for attribute restrictions on predicates
-                //Verifier::_process_operator(&info.name, &query, &filter, None)
-                //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
-            }
+            // old style :-/ which fails for attribute restrictions on predicates
+            //Verifier::_process_operator(&info.name, &query, &filter, None)
+            //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
         }
 
         Ok(())
@@ -654,6 +940,13 @@ impl Verifier {
     }
 }
 
+fn _check_result(res: IndyResult<()>) -> CheckResult {
+    match res {
+        Ok(()) => CheckResult::valid(),
+        Err(err) => CheckResult::invalid(err.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
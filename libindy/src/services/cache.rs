@@ -0,0 +1,284 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use indy_api_types::domain::wallet::Tags;
+use indy_api_types::WalletHandle;
+
+lazy_static! {
+    static ref LRU_CAPACITY: Mutex<usize> = Mutex::new(0);
+}
+
+/// Sets max count of ledger objects kept in memory in front of wallet-backed cache.
+/// 0 disables in-memory layer.
+pub fn set_lru_capacity(capacity: usize) {
+    let mut lru_capacity = LRU_CAPACITY.lock().unwrap();
+    *lru_capacity = capacity;
+}
+
+fn lru_capacity() -> usize {
+    *LRU_CAPACITY.lock().unwrap()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CacheRecord {
+    pub value: String,
+    pub tags: Tags,
+}
+
+impl CacheRecord {
+    pub fn new(value: String, tags: Tags) -> CacheRecord {
+        CacheRecord { value, tags }
+    }
+
+    /// Time (in seconds since epoch) when the record was stored or -1 if unknown.
+    pub fn timestamp(&self) -> i32 {
+        self.tags.get("timestamp")
+            .and_then(|ts| ts.parse().ok())
+            .unwrap_or(-1)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CacheCounters {
+    pub lru_hits: usize,
+    pub wallet_hits: usize,
+    pub misses: usize,
+}
+
+type CacheKey = (WalletHandle, String, String); // wallet handle, cache type, id
+
+pub struct CacheService {
+    records: RefCell<HashMap<CacheKey, (CacheRecord, u64)>>,
+    usage: RefCell<BTreeMap<u64, CacheKey>>,
+    tick: Cell<u64>,
+    counters: RefCell<HashMap<String, CacheCounters>>,
+}
+
+impl CacheService {
+    pub fn new() -> CacheService {
+        CacheService {
+            records: RefCell::new(HashMap::new()),
+            usage: RefCell::new(BTreeMap::new()),
+            tick: Cell::new(0),
+            counters: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, wallet_handle: WalletHandle, which_cache: &str, id: &str) -> Option<CacheRecord> {
+        if lru_capacity() == 0 {
+            return None;
+        }
+
+        let key = (wallet_handle, which_cache.to_string(), id.to_string());
+        let tick = self._next_tick();
+
+        let mut records = self.records.borrow_mut();
+        let (record, last_used) = records.get_mut(&key)?;
+
+        let mut usage = self.usage.borrow_mut();
+        usage.remove(&*last_used);
+        usage.insert(tick, key.clone());
+        *last_used = tick;
+
+        Some(record.clone())
+    }
+
+    pub fn put(&self, wallet_handle: WalletHandle, which_cache: &str, id: &str, record: CacheRecord) {
+        let capacity = lru_capacity();
+
+        if capacity == 0 {
+            self.records.borrow_mut().clear();
+            self.usage.borrow_mut().clear();
+            return;
+        }
+
+        self.remove(wallet_handle, which_cache, id);
+
+        let key = (wallet_handle, which_cache.to_string(), id.to_string());
+        let tick = self._next_tick();
+
+        let mut records = self.records.borrow_mut();
+        let mut usage = self.usage.borrow_mut();
+
+        records.insert(key.clone(), (record, tick));
+        usage.insert(tick, key);
+
+        while records.len() > capacity {
+            let oldest = match usage.keys().next() {
+                Some(oldest) => *oldest,
+                None => break
+            };
+
+            if let Some(key) = usage.remove(&oldest) {
+                records.remove(&key);
+            }
+        }
+    }
+
+    pub fn remove(&self, wallet_handle: WalletHandle, which_cache: &str, id: &str) {
+        let key = (wallet_handle, which_cache.to_string(), id.to_string());
+
+        if let Some((_, last_used)) = self.records.borrow_mut().remove(&key) {
+            self.usage.borrow_mut().remove(&last_used);
+        }
+    }
+
+    /// Removes records of `which_cache` stored before `older_than` (seconds since epoch) or all of them if `None`.
+    pub fn purge(&self, wallet_handle: WalletHandle, which_cache: &str, older_than: Option<i32>) {
+        let mut records = self.records.borrow_mut();
+        let mut usage = self.usage.borrow_mut();
+
+        records.retain(|(handle, cache, _), (record, last_used)| {
+            let purge = *handle == wallet_handle && cache == which_cache &&
+                older_than.map(|ts| record.timestamp() < ts).unwrap_or(true);
+
+            if purge {
+                usage.remove(&*last_used);
+            }
+
+            !purge
+        });
+    }
+
+    pub fn size(&self) -> usize {
+        self.records.borrow().len()
+    }
+
+    pub fn lru_hit(&self, which_cache: &str) {
+        self._counters(which_cache, |counters| counters.lru_hits += 1);
+    }
+
+    pub fn wallet_hit(&self, which_cache: &str) {
+        self._counters(which_cache, |counters| counters.wallet_hits += 1);
+    }
+
+    pub fn miss(&self, which_cache: &str) {
+        self._counters(which_cache, |counters| counters.misses += 1);
+    }
+
+    pub fn get_counters(&self) -> HashMap<String, CacheCounters> {
+        self.counters.borrow().clone()
+    }
+
+    fn _counters<F>(&self, which_cache: &str, f: F) where F: FnOnce(&mut CacheCounters) {
+        let mut counters = self.counters.borrow_mut();
+        f(counters.entry(which_cache.to_string()).or_insert_with(CacheCounters::default));
+    }
+
+    fn _next_tick(&self) -> u64 {
+        let tick = self.tick.get() + 1;
+        self.tick.set(tick);
+        tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALLET_HANDLE: WalletHandle = WalletHandle(1);
+    const CACHE: &str = "test_cache";
+
+    lazy_static! {
+        // LRU capacity is a global setting, so tests changing it must not run concurrently
+        static ref CAPACITY_GUARD: Mutex<()> = Mutex::new(());
+    }
+
+    fn _record(value: &str, timestamp: i32) -> CacheRecord {
+        let mut tags = Tags::new();
+        tags.insert("timestamp".to_string(), timestamp.to_string());
+        CacheRecord::new(value.to_string(), tags)
+    }
+
+    #[test]
+    fn cache_service_get_works_for_disabled_lru() {
+        let _guard = CAPACITY_GUARD.lock().unwrap();
+        set_lru_capacity(0);
+
+        let cache_service = CacheService::new();
+        cache_service.put(WALLET_HANDLE, CACHE, "id", _record("value", 1));
+
+        assert_eq!(None, cache_service.get(WALLET_HANDLE, CACHE, "id"));
+        assert_eq!(0, cache_service.size());
+    }
+
+    #[test]
+    fn cache_service_get_works() {
+        let _guard = CAPACITY_GUARD.lock().unwrap();
+        set_lru_capacity(2);
+
+        let cache_service = CacheService::new();
+        cache_service.put(WALLET_HANDLE, CACHE, "id", _record("value", 1));
+
+        assert_eq!(Some(_record("value", 1)), cache_service.get(WALLET_HANDLE, CACHE, "id"));
+        assert_eq!(None, cache_service.get(WalletHandle(2), CACHE, "id"));
+        assert_eq!(None, cache_service.get(WALLET_HANDLE, "other_cache", "id"));
+    }
+
+    #[test]
+    fn cache_service_put_works_for_eviction_of_least_recently_used() {
+        let _guard = CAPACITY_GUARD.lock().unwrap();
+        set_lru_capacity(2);
+
+        let cache_service = CacheService::new();
+        cache_service.put(WALLET_HANDLE, CACHE, "id1", _record("value1", 1));
+        cache_service.put(WALLET_HANDLE, CACHE, "id2", _record("value2", 1));
+        cache_service.get(WALLET_HANDLE, CACHE, "id1").unwrap();
+        cache_service.put(WALLET_HANDLE, CACHE, "id3", _record("value3", 1));
+
+        assert_eq!(2, cache_service.size());
+        assert!(cache_service.get(WALLET_HANDLE, CACHE, "id1").is_some());
+        assert!(cache_service.get(WALLET_HANDLE, CACHE, "id2").is_none());
+        assert!(cache_service.get(WALLET_HANDLE, CACHE, "id3").is_some());
+    }
+
+    #[test]
+    fn cache_service_put_works_for_replace() {
+        let _guard = CAPACITY_GUARD.lock().unwrap();
+        set_lru_capacity(2);
+
+        let cache_service = CacheService::new();
+        cache_service.put(WALLET_HANDLE, CACHE, "id", _record("value1", 1));
+        cache_service.put(WALLET_HANDLE, CACHE, "id", _record("value2", 2));
+
+        assert_eq!(1, cache_service.size());
+        assert_eq!(Some(_record("value2", 2)), cache_service.get(WALLET_HANDLE, CACHE, "id"));
+    }
+
+    #[test]
+    fn cache_service_purge_works() {
+        let _guard = CAPACITY_GUARD.lock().unwrap();
+        set_lru_capacity(10);
+
+        let cache_service = CacheService::new();
+        cache_service.put(WALLET_HANDLE, CACHE, "id1", _record("value1", 10));
+        cache_service.put(WALLET_HANDLE, CACHE, "id2", _record("value2", 20));
+        cache_service.put(WALLET_HANDLE, "other_cache", "id1", _record("value1", 10));
+
+        cache_service.purge(WALLET_HANDLE, CACHE, Some(15));
+
+        assert!(cache_service.get(WALLET_HANDLE, CACHE, "id1").is_none());
+        assert!(cache_service.get(WALLET_HANDLE, CACHE, "id2").is_some());
+        assert!(cache_service.get(WALLET_HANDLE, "other_cache", "id1").is_some());
+
+        cache_service.purge(WALLET_HANDLE, CACHE, None);
+
+        assert!(cache_service.get(WALLET_HANDLE, CACHE, "id2").is_none());
+        assert_eq!(1, cache_service.size());
+    }
+
+    #[test]
+    fn cache_service_counters_work() {
+        let cache_service = CacheService::new();
+        cache_service.lru_hit(CACHE);
+        cache_service.wallet_hit(CACHE);
+        cache_service.wallet_hit(CACHE);
+        cache_service.miss("other_cache");
+
+        let counters = cache_service.get_counters();
+
+        assert_eq!(CacheCounters { lru_hits: 1, wallet_hits: 2, misses: 0 }, counters[CACHE]);
+        assert_eq!(CacheCounters { lru_hits: 0, wallet_hits: 0, misses: 1 }, counters["other_cache"]);
+    }
+}
@@ -0,0 +1,222 @@
+//! Box and sealed box constructions shared by the ECDH based crypto types.
+//!
+//! The ECDH shared secret is hashed with SHA-256 and the result is used as chacha20poly1305_ietf key.
+//! Sealed boxes are prefixed with the ephemeral public key and use a nonce derived from both public keys.
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::chacha20poly1305_ietf;
+use sha2::{Digest, Sha256};
+
+pub fn gen_nonce() -> Vec<u8> {
+    chacha20poly1305_ietf::gen_nonce()[..].to_vec()
+}
+
+pub fn encrypt(shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+    let key = _derive_key(shared_secret)?;
+    let nonce = chacha20poly1305_ietf::Nonce::from_slice(nonce)?;
+
+    Ok(chacha20poly1305_ietf::encrypt(doc, &key, &nonce))
+}
+
+pub fn decrypt(shared_secret: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+    let key = _derive_key(shared_secret)?;
+    let nonce = chacha20poly1305_ietf::Nonce::from_slice(nonce)?;
+
+    chacha20poly1305_ietf::decrypt(doc, &key, &nonce)
+}
+
+pub fn seal(shared_secret: &[u8], ephemeral_vk: &[u8], vk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+    let nonce = _seal_nonce(ephemeral_vk, vk);
+
+    let mut res = ephemeral_vk.to_vec();
+    res.extend(encrypt(shared_secret, doc, &nonce)?);
+
+    Ok(res)
+}
+
+pub fn split_sealed(doc: &[u8], vk_len: usize) -> IndyResult<(&[u8], &[u8])> {
+    if doc.len() <= vk_len {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Sealed message is too short"));
+    }
+
+    Ok(doc.split_at(vk_len))
+}
+
+pub fn seal_open(shared_secret: &[u8], ephemeral_vk: &[u8], vk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+    let nonce = _seal_nonce(ephemeral_vk, vk);
+
+    decrypt(shared_secret, doc, &nonce)
+}
+
+fn _derive_key(shared_secret: &[u8]) -> IndyResult<chacha20poly1305_ietf::Key> {
+    chacha20poly1305_ietf::Key::from_slice(&Sha256::digest(shared_secret))
+}
+
+fn _seal_nonce(ephemeral_vk: &[u8], vk: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(ephemeral_vk);
+    hasher.update(vk);
+
+    hasher.finalize()[..chacha20poly1305_ietf::NONCEBYTES].to_vec()
+}
+
+/// Implements `CryptoType` with ECDSA (SHA-256) signatures and the ECDH boxes above
+/// for the curve of the given RustCrypto crate.
+///
+/// The calling module provides curve key handling: `VERKEYBYTES`, `_secret_key`,
+/// `_public_key`, `_verkey` and `_shared_secret`.
+macro_rules! ecdsa_crypto_type {
+    ($(#[$meta:meta])* $name:ident, $curve:ident, $curve_name:literal) => {
+        $(#[$meta])*
+        pub struct $name {}
+
+        impl $name {
+            pub fn new() -> $name {
+                $name {}
+            }
+        }
+
+        impl CryptoType for $name {
+            fn crypto_box(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+                ecdh::encrypt(&_shared_secret(&_secret_key(sk)?, &_public_key(vk)?), doc, nonce)
+            }
+
+            fn crypto_box_open(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+                ecdh::decrypt(&_shared_secret(&_secret_key(sk)?, &_public_key(vk)?), doc, nonce)
+            }
+
+            fn gen_nonce(&self) -> Vec<u8> {
+                ecdh::gen_nonce()
+            }
+
+            fn create_key(&self, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, Vec<u8>)> {
+                let sk = match seed {
+                    Some(seed) => _secret_key(seed)?,
+                    None => $curve::SecretKey::random(rand::rngs::OsRng)
+                };
+
+                Ok((_verkey(&sk.public_key()), sk.to_bytes().to_vec()))
+            }
+
+            fn validate_key(&self, vk: &[u8]) -> IndyResult<()> {
+                _public_key(vk).map(|_| ())
+            }
+
+            fn sign(&self, sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+                use $curve::ecdsa::signature::{Signature as _, Signer};
+
+                let signing_key = $curve::ecdsa::SigningKey::from_bytes(sk)
+                    .to_indy(IndyErrorKind::InvalidStructure, concat!("Invalid ", $curve_name, " signkey"))?;
+
+                let signature: $curve::ecdsa::Signature = signing_key.try_sign(doc)
+                    .to_indy(IndyErrorKind::InvalidState, concat!("Unable to sign message with ", $curve_name, " key"))?;
+
+                Ok(signature.as_bytes().to_vec())
+            }
+
+            fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool> {
+                use $curve::ecdsa::signature::{Signature as _, Verifier};
+
+                let verifying_key = $curve::ecdsa::VerifyingKey::from_sec1_bytes(vk)
+                    .to_indy(IndyErrorKind::InvalidStructure, concat!("Invalid ", $curve_name, " verkey"))?;
+
+                let signature = $curve::ecdsa::Signature::from_bytes(signature)
+                    .to_indy(IndyErrorKind::InvalidStructure, concat!("Invalid ", $curve_name, " signature"))?;
+
+                Ok(verifying_key.verify(doc, &signature).is_ok())
+            }
+
+            fn crypto_box_seal(&self, vk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+                let ephemeral_sk = $curve::SecretKey::random(rand::rngs::OsRng);
+                let ephemeral_vk = _verkey(&ephemeral_sk.public_key());
+
+                ecdh::seal(&_shared_secret(&ephemeral_sk, &_public_key(vk)?), &ephemeral_vk, vk, doc)
+            }
+
+            fn crypto_box_seal_open(&self, vk: &[u8], sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>> {
+                let (ephemeral_vk, doc) = ecdh::split_sealed(doc, VERKEYBYTES)?;
+
+                ecdh::seal_open(&_shared_secret(&_secret_key(sk)?, &_public_key(ephemeral_vk)?), ephemeral_vk, vk, doc)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            #[test]
+            fn create_key_works_for_seed() {
+                let crypto_type = $name::new();
+                let seed = [1u8; 32];
+
+                let (vk, sk) = crypto_type.create_key(Some(&seed)).unwrap();
+                assert_eq!(VERKEYBYTES, vk.len());
+                assert_eq!(seed.to_vec(), sk);
+
+                assert_eq!((vk, sk), crypto_type.create_key(Some(&seed)).unwrap());
+            }
+
+            #[test]
+            fn create_key_fails_for_zero_seed() {
+                let crypto_type = $name::new();
+                assert!(crypto_type.create_key(Some(&[0u8; 32])).is_err());
+            }
+
+            #[test]
+            fn sign_verify_works() {
+                let crypto_type = $name::new();
+                let (vk, sk) = crypto_type.create_key(None).unwrap();
+
+                let signature = crypto_type.sign(&sk, b"message").unwrap();
+                assert_eq!(64, signature.len());
+
+                assert!(crypto_type.verify(&vk, b"message", &signature).unwrap());
+                assert!(!crypto_type.verify(&vk, b"other message", &signature).unwrap());
+            }
+
+            #[test]
+            fn crypto_box_works() {
+                let crypto_type = $name::new();
+                let (alice_vk, alice_sk) = crypto_type.create_key(None).unwrap();
+                let (bob_vk, bob_sk) = crypto_type.create_key(None).unwrap();
+
+                let nonce = crypto_type.gen_nonce();
+                let encrypted = crypto_type.crypto_box(&alice_sk, &bob_vk, b"message", &nonce).unwrap();
+                let decrypted = crypto_type.crypto_box_open(&bob_sk, &alice_vk, &encrypted, &nonce).unwrap();
+
+                assert_eq!(b"message".to_vec(), decrypted);
+            }
+
+            #[test]
+            fn crypto_box_seal_works() {
+                let crypto_type = $name::new();
+                let (vk, sk) = crypto_type.create_key(None).unwrap();
+
+                let encrypted = crypto_type.crypto_box_seal(&vk, b"message").unwrap();
+                let decrypted = crypto_type.crypto_box_seal_open(&vk, &sk, &encrypted).unwrap();
+
+                assert_eq!(b"message".to_vec(), decrypted);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_decrypt_works() {
+        let nonce = gen_nonce();
+        let encrypted = encrypt(b"secret", b"message", &nonce).unwrap();
+
+        assert_eq!(b"message".to_vec(), decrypt(b"secret", &encrypted, &nonce).unwrap());
+        assert!(decrypt(b"other", &encrypted, &nonce).is_err());
+    }
+
+    #[test]
+    fn split_sealed_works_for_short_message() {
+        assert!(split_sealed(&[1, 2, 3], 33).is_err());
+    }
+}
+
@@ -14,43 +14,69 @@ impl ED25519CryptoType {
 }
 
 impl CryptoType for ED25519CryptoType {
-    fn crypto_box(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> Result<Vec<u8>, IndyError> {
-        ed25519_box::encrypt(&ed25519_sign::sk_to_curve25519(sk)?,
-                           &ed25519_sign::vk_to_curve25519(vk)?, doc, nonce)
+    fn crypto_box(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, IndyError> {
+        let sk = ed25519_sign::SecretKey::from_slice(sk)?;
+        let vk = ed25519_sign::PublicKey::from_slice(vk)?;
+        let nonce = ed25519_box::Nonce::from_slice(nonce)?;
+
+        ed25519_box::encrypt(&ed25519_sign::sk_to_curve25519(&sk)?,
+                           &ed25519_sign::vk_to_curve25519(&vk)?, doc, &nonce)
     }
 
-    fn crypto_box_open(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> Result<Vec<u8>, IndyError> {
-        ed25519_box::decrypt(&ed25519_sign::sk_to_curve25519(sk)?,
-                           &ed25519_sign::vk_to_curve25519(vk)?, doc, nonce)
+    fn crypto_box_open(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &[u8]) -> Result<Vec<u8>, IndyError> {
+        let sk = ed25519_sign::SecretKey::from_slice(sk)?;
+        let vk = ed25519_sign::PublicKey::from_slice(vk)?;
+        let nonce = ed25519_box::Nonce::from_slice(nonce)?;
+
+        ed25519_box::decrypt(&ed25519_sign::sk_to_curve25519(&sk)?,
+                           &ed25519_sign::vk_to_curve25519(&vk)?, doc, &nonce)
     }
 
-    fn gen_nonce(&self) -> ed25519_box::Nonce {
-        ed25519_box::gen_nonce()
+    fn gen_nonce(&self) -> Vec<u8> {
+        ed25519_box::gen_nonce()[..].to_vec()
     }
 
-    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> Result<(ed25519_sign::PublicKey, ed25519_sign::SecretKey), IndyError> {
-        ed25519_sign::create_key_pair_for_signature(seed)
+    fn create_key(&self, seed: Option<&[u8]>) -> Result<(Vec<u8>, Vec<u8>), IndyError> {
+        let seed = match seed {
+            Some(seed) => Some(ed25519_sign::Seed::from_slice(seed)?),
+            None => None
+        };
+
+        let (vk, sk) = ed25519_sign::create_key_pair_for_signature(seed.as_ref())?;
+
+        Ok((vk[..].to_vec(), sk[..].to_vec()))
     }
 
-    fn sign(&self, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> Result<ed25519_sign::Signature, IndyError> {
-        ed25519_sign::sign(sk, doc)
+    fn sign(&self, sk: &[u8], doc: &[u8]) -> Result<Vec<u8>, IndyError> {
+        let sk = ed25519_sign::SecretKey::from_slice(sk)?;
+
+        Ok(ed25519_sign::sign(&sk, doc)?[..].to_vec())
     }
 
-    fn verify(&self, vk: &ed25519_sign::PublicKey, doc: &[u8], signature: &ed25519_sign::Signature) -> Result<bool, IndyError> {
-        ed25519_sign::verify(vk, doc, signature)
+    fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> Result<bool, IndyError> {
+        let vk = ed25519_sign::PublicKey::from_slice(vk)?;
+        let signature = ed25519_sign::Signature::from_slice(signature)?;
+
+        ed25519_sign::verify(&vk, doc, &signature)
     }
 
-    fn crypto_box_seal(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> Result<Vec<u8>, IndyError> {
-        sealedbox::encrypt(&ed25519_sign::vk_to_curve25519(vk)?, doc)
+    fn crypto_box_seal(&self, vk: &[u8], doc: &[u8]) -> Result<Vec<u8>, IndyError> {
+        let vk = ed25519_sign::PublicKey::from_slice(vk)?;
+
+        sealedbox::encrypt(&ed25519_sign::vk_to_curve25519(&vk)?, doc)
     }
 
-    fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> Result<Vec<u8>, IndyError> {
-        sealedbox::decrypt(&ed25519_sign::vk_to_curve25519(vk)?,
-                         &ed25519_sign::sk_to_curve25519(sk)?, doc)
+    fn crypto_box_seal_open(&self, vk: &[u8], sk: &[u8], doc: &[u8]) -> Result<Vec<u8>, IndyError> {
+        let vk = ed25519_sign::PublicKey::from_slice(vk)?;
+        let sk = ed25519_sign::SecretKey::from_slice(sk)?;
+
+        sealedbox::decrypt(&ed25519_sign::vk_to_curve25519(&vk)?,
+                         &ed25519_sign::sk_to_curve25519(&sk)?, doc)
     }
 
-    fn validate_key(&self, _vk: &ed25519_sign::PublicKey) -> Result<(), IndyError> {
+    fn validate_key(&self, vk: &[u8]) -> Result<(), IndyError> {
         // TODO: FIXME: Validate key
+        let _ = ed25519_sign::PublicKey::from_slice(vk)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,196 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+use std::slice;
+
+use libc::c_char;
+
+use indy_api_types::ErrorCode;
+use indy_api_types::errors::prelude::*;
+use indy_api_types::key_manager::*;
+
+/// External storage of secret keys (e.g. KMS or HSM).
+///
+/// Keys are held by reference: the wallet stores only verkey and key reference,
+/// all operations requiring secret key are performed by key manager.
+pub trait KeyManager {
+    /// Returns raw verkey and reference of the created key.
+    fn create_key(&self, crypto_type: &str, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, String)>;
+    fn sign(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box(&self, key_ref: &str, their_vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box_open(&self, key_ref: &str, their_vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box_seal_open(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn key_agreement(&self, key_ref: &str, their_pk: &[u8]) -> IndyResult<Vec<u8>>;
+}
+
+/// Key manager implemented by application through C callbacks.
+pub struct PluggedKeyManager {
+    create_key_handler: KeyManagerCreateKey,
+    sign_handler: KeyManagerSign,
+    crypto_box_handler: KeyManagerCryptoBox,
+    crypto_box_open_handler: KeyManagerCryptoBoxOpen,
+    crypto_box_seal_open_handler: KeyManagerCryptoBoxSealOpen,
+    key_agreement_handler: KeyManagerKeyAgreement,
+    free_handler: KeyManagerFree,
+}
+
+impl PluggedKeyManager {
+    pub fn new(create_key_handler: KeyManagerCreateKey,
+               sign_handler: KeyManagerSign,
+               crypto_box_handler: KeyManagerCryptoBox,
+               crypto_box_open_handler: KeyManagerCryptoBoxOpen,
+               crypto_box_seal_open_handler: KeyManagerCryptoBoxSealOpen,
+               key_agreement_handler: KeyManagerKeyAgreement,
+               free_handler: KeyManagerFree) -> PluggedKeyManager {
+        PluggedKeyManager {
+            create_key_handler,
+            sign_handler,
+            crypto_box_handler,
+            crypto_box_open_handler,
+            crypto_box_seal_open_handler,
+            key_agreement_handler,
+            free_handler,
+        }
+    }
+
+    // Copies and frees buffer returned by key manager. Buffer is freed even if the call failed.
+    fn _take_buffer(&self, err: ErrorCode, buffer: *const u8, buffer_len: usize) -> IndyResult<Vec<u8>> {
+        if buffer.is_null() {
+            return Err(if err != ErrorCode::Success {
+                err.into()
+            } else {
+                err_msg(IndyErrorKind::InvalidState, "Key manager returned null buffer")
+            });
+        }
+
+        let res = unsafe { slice::from_raw_parts(buffer, buffer_len) }.to_vec();
+        (self.free_handler)(buffer);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(res)
+    }
+
+    // Copies and frees key reference returned by key manager.
+    fn _take_key_ref(&self, key_ref: *const c_char) -> IndyResult<String> {
+        if key_ref.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Key manager returned null key reference"));
+        }
+
+        let res = unsafe { CStr::from_ptr(key_ref) }
+            .to_str()
+            .map(str::to_string)
+            .to_indy(IndyErrorKind::InvalidState, "Key reference contains non-utf8 symbol");
+        (self.free_handler)(key_ref as *const u8);
+
+        res
+    }
+}
+
+impl KeyManager for PluggedKeyManager {
+    fn create_key(&self, crypto_type: &str, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, String)> {
+        let crypto_type = CString::new(crypto_type)?;
+        let (seed, seed_len) = seed.map(|seed| (seed.as_ptr(), seed.len())).unwrap_or((ptr::null(), 0));
+
+        let mut verkey: *const u8 = ptr::null();
+        let mut verkey_len: usize = 0;
+        let mut key_ref: *const c_char = ptr::null();
+
+        let err = (self.create_key_handler)(crypto_type.as_ptr(),
+                                            seed,
+                                            seed_len,
+                                            &mut verkey,
+                                            &mut verkey_len,
+                                            &mut key_ref);
+
+        // Both outputs are taken before checking results to free them on every path
+        let key_ref = self._take_key_ref(key_ref);
+        let verkey = self._take_buffer(err, verkey, verkey_len)?;
+
+        Ok((verkey, key_ref?))
+    }
+
+    fn sign(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_ref = CString::new(key_ref)?;
+
+        let mut signature: *const u8 = ptr::null();
+        let mut signature_len: usize = 0;
+
+        let err = (self.sign_handler)(key_ref.as_ptr(),
+                                      doc.as_ptr(),
+                                      doc.len(),
+                                      &mut signature,
+                                      &mut signature_len);
+
+        self._take_buffer(err, signature, signature_len)
+    }
+
+    fn crypto_box(&self, key_ref: &str, their_vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_ref = CString::new(key_ref)?;
+
+        let mut encrypted: *const u8 = ptr::null();
+        let mut encrypted_len: usize = 0;
+
+        let err = (self.crypto_box_handler)(key_ref.as_ptr(),
+                                            their_vk.as_ptr(),
+                                            their_vk.len(),
+                                            doc.as_ptr(),
+                                            doc.len(),
+                                            nonce.as_ptr(),
+                                            nonce.len(),
+                                            &mut encrypted,
+                                            &mut encrypted_len);
+
+        self._take_buffer(err, encrypted, encrypted_len)
+    }
+
+    fn crypto_box_open(&self, key_ref: &str, their_vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_ref = CString::new(key_ref)?;
+
+        let mut decrypted: *const u8 = ptr::null();
+        let mut decrypted_len: usize = 0;
+
+        let err = (self.crypto_box_open_handler)(key_ref.as_ptr(),
+                                                 their_vk.as_ptr(),
+                                                 their_vk.len(),
+                                                 doc.as_ptr(),
+                                                 doc.len(),
+                                                 nonce.as_ptr(),
+                                                 nonce.len(),
+                                                 &mut decrypted,
+                                                 &mut decrypted_len);
+
+        self._take_buffer(err, decrypted, decrypted_len)
+    }
+
+    fn crypto_box_seal_open(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_ref = CString::new(key_ref)?;
+
+        let mut decrypted: *const u8 = ptr::null();
+        let mut decrypted_len: usize = 0;
+
+        let err = (self.crypto_box_seal_open_handler)(key_ref.as_ptr(),
+                                                      doc.as_ptr(),
+                                                      doc.len(),
+                                                      &mut decrypted,
+                                                      &mut decrypted_len);
+
+        self._take_buffer(err, decrypted, decrypted_len)
+    }
+
+    fn key_agreement(&self, key_ref: &str, their_pk: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_ref = CString::new(key_ref)?;
+
+        let mut shared_secret: *const u8 = ptr::null();
+        let mut shared_secret_len: usize = 0;
+
+        let err = (self.key_agreement_handler)(key_ref.as_ptr(),
+                                               their_pk.as_ptr(),
+                                               their_pk.len(),
+                                               &mut shared_secret,
+                                               &mut shared_secret_len);
+
+        self._take_buffer(err, shared_secret, shared_secret_len)
+    }
+}
@@ -1,5 +1,6 @@
 extern crate hex;
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::str;
 
@@ -8,72 +9,85 @@ use crate::domain::crypto::did::{Did, DidValue, MyDidInfo, TheirDid, TheirDidInf
 use crate::domain::crypto::key::{Key, KeyInfo};
 use indy_api_types::errors::prelude::*;
 use indy_utils::crypto::base64;
-use indy_utils::crypto::ed25519_box;
 use indy_utils::crypto::chacha20poly1305_ietf;
+use indy_utils::crypto::ed25519_box;
 use indy_utils::crypto::chacha20poly1305_ietf::gen_nonce_and_encrypt_detached;
 use indy_utils::crypto::ed25519_sign;
+use crate::utils::crypto::did_key;
 use crate::utils::crypto::verkey_builder::{build_full_verkey, split_verkey, verkey_get_cryptoname};
 
 use self::ed25519::ED25519CryptoType;
+use self::key_manager::KeyManager;
+use self::p256::P256CryptoType;
+use self::secp256k1::Secp256k1CryptoType;
 use self::hex::FromHex;
 use rust_base58::{FromBase58, ToBase58};
 
+#[macro_use]
+mod ecdh;
 mod ed25519;
+pub mod key_manager;
+mod p256;
+mod secp256k1;
 
 pub const DEFAULT_CRYPTO_TYPE: &str = "ed25519";
+pub const SECP256K1_CRYPTO_TYPE: &str = "secp256k1";
+pub const P256_CRYPTO_TYPE: &str = "p256";
 
-//TODO fix this crypto trait so it matches the functions below
 //TODO create a second crypto trait for additional functions
+// Keys, nonces and signatures are passed as raw bytes as their sizes differ between crypto types
 trait CryptoType {
-    fn crypto_box(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
-    fn crypto_box_open(&self, sk: &ed25519_sign::SecretKey, vk: &ed25519_sign::PublicKey, doc: &[u8], nonce: &ed25519_box::Nonce) -> IndyResult<Vec<u8>>;
-    fn gen_nonce(&self) -> ed25519_box::Nonce;
-    fn create_key(&self, seed: Option<&ed25519_sign::Seed>) -> IndyResult<(ed25519_sign::PublicKey, ed25519_sign::SecretKey)>;
-    fn validate_key(&self, _vk: &ed25519_sign::PublicKey) -> IndyResult<()>;
-    fn sign(&self, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<ed25519_sign::Signature>;
-    fn verify(&self, vk: &ed25519_sign::PublicKey, doc: &[u8], signature: &ed25519_sign::Signature) -> IndyResult<bool>;
-    fn crypto_box_seal(&self, vk: &ed25519_sign::PublicKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
-    fn crypto_box_seal_open(&self, vk: &ed25519_sign::PublicKey, sk: &ed25519_sign::SecretKey, doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box_open(&self, sk: &[u8], vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>>;
+    fn gen_nonce(&self) -> Vec<u8>;
+    fn create_key(&self, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, Vec<u8>)>;
+    fn validate_key(&self, vk: &[u8]) -> IndyResult<()>;
+    fn sign(&self, sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn verify(&self, vk: &[u8], doc: &[u8], signature: &[u8]) -> IndyResult<bool>;
+    fn crypto_box_seal(&self, vk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>>;
+    fn crypto_box_seal_open(&self, vk: &[u8], sk: &[u8], doc: &[u8]) -> IndyResult<Vec<u8>>;
 }
 
 pub struct CryptoService {
-    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>
+    crypto_types: HashMap<&'static str, Box<dyn CryptoType>>,
+    key_managers: RefCell<HashMap<String, Box<dyn KeyManager>>>,
 }
 
 impl CryptoService {
     pub fn new() -> CryptoService {
         let mut crypto_types: HashMap<&str, Box<dyn CryptoType>> = HashMap::new();
         crypto_types.insert(DEFAULT_CRYPTO_TYPE, Box::new(ED25519CryptoType::new()));
+        crypto_types.insert(SECP256K1_CRYPTO_TYPE, Box::new(Secp256k1CryptoType::new()));
+        crypto_types.insert(P256_CRYPTO_TYPE, Box::new(P256CryptoType::new()));
 
         CryptoService {
-            crypto_types
+            crypto_types,
+            key_managers: RefCell::new(HashMap::new()),
         }
     }
 
-    pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
-        trace!("create_key >>> key_info: {:?}", secret!(key_info));
+    pub fn register_key_manager(&self, name: &str, key_manager: Box<dyn KeyManager>) -> IndyResult<()> {
+        trace!("register_key_manager >>> name: {:?}", name);
 
-        let crypto_type_name = key_info.crypto_type
-            .as_ref()
-            .map(String::as_str)
-            .unwrap_or(DEFAULT_CRYPTO_TYPE);
+        let mut key_managers = self.key_managers.borrow_mut();
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
-            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("KeyInfo contains unknown crypto: {}", crypto_type_name)));
+        if key_managers.contains_key(name) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Key manager is already registered for name: {}", name)));
         }
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        key_managers.insert(name.to_string(), key_manager);
 
-        let seed = self.convert_seed(key_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
-        let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
-        if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
-            // Use suffix with crypto type name to store crypto type inside of vk
-            vk = format!("{}:{}", vk, crypto_type_name);
-        }
+        trace!("register_key_manager <<<");
 
-        let key = Key::new(vk, sk);
+        Ok(())
+    }
+
+    pub fn create_key(&self, key_info: &KeyInfo) -> IndyResult<Key> {
+        trace!("create_key >>> key_info: {:?}", secret!(key_info));
+
+        let (_, key) = self._create_key(key_info.crypto_type.as_ref().map(String::as_str),
+                                        key_info.seed.as_ref().map(String::as_str),
+                                        key_info.key_manager.as_ref().map(String::as_str))?;
 
         trace!("create_key <<< key: {:?}", key);
 
@@ -83,40 +97,78 @@ impl CryptoService {
     pub fn create_my_did(&self, my_did_info: &MyDidInfo) -> IndyResult<(Did, Key)> {
         trace!("create_my_did >>> my_did_info: {:?}", secret!(my_did_info));
 
-        let crypto_type_name = my_did_info.crypto_type
-            .as_ref()
-            .map(String::as_str)
-            .unwrap_or(DEFAULT_CRYPTO_TYPE);
+        let (vk_bytes, key) = self._create_key(my_did_info.crypto_type.as_ref().map(String::as_str),
+                                               my_did_info.seed.as_ref().map(String::as_str),
+                                               my_did_info.key_manager.as_ref().map(String::as_str))?;
 
-        if !self.crypto_types.contains_key(crypto_type_name) {
-            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("MyDidInfo contains unknown crypto: {}", crypto_type_name)));
-        }
+        let vk = key.verkey.clone();
 
-        let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
+        let method_name = my_did_info.method_name.as_ref().map(|method| method.0.as_str());
 
-        let seed = self.convert_seed(my_did_info.seed.as_ref().map(String::as_ref))?;
-        let (vk, sk) = crypto_type.create_key(seed.as_ref())?;
-        let did = match my_did_info.did {
-            Some(ref did) => did.clone(),
+        let did = match (my_did_info.did.as_ref(), method_name) {
+            (Some(did), _) => did.clone(),
+            (None, Some(did_key::KEY_METHOD)) => did_key::did_key(&vk)?,
+            (None, Some(did_key::PEER_METHOD)) => match my_did_info.service {
+                Some(ref service) => did_key::did_peer_2(&vk, service)?,
+                None => did_key::did_peer_0(&vk)?
+            },
             _ if my_did_info.cid == Some(true) =>
-                DidValue::new(&vk[..].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str())),
+                DidValue::new(&vk_bytes[..].to_vec().to_base58(), method_name),
             _ =>
-                DidValue::new(&vk[0..16].to_vec().to_base58(), my_did_info.method_name.as_ref().map(|method| method.0.as_str()))
+                DidValue::new(&vk_bytes[0..16].to_vec().to_base58(), method_name)
         };
 
-        let mut vk = vk[..].to_base58();
-        let sk = sk[..].to_base58();
+        let did = (Did::new(did, vk), key);
+
+        trace!("create_my_did <<< did: {:?}", did);
+
+        Ok(did)
+    }
+
+    // Returns raw verkey bytes along with the key
+    fn _create_key(&self, crypto_type_name: Option<&str>, seed: Option<&str>, key_manager: Option<&str>) -> IndyResult<(Vec<u8>, Key)> {
+        let crypto_type_name = crypto_type_name.unwrap_or(DEFAULT_CRYPTO_TYPE);
+
+        let crypto_type = self.crypto_types.get(crypto_type_name)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Trying to create key with unknown crypto: {}", crypto_type_name)))?;
+
+        let seed = self.convert_seed(seed)?;
+        let seed = seed.as_ref().map(|seed| &seed[..]);
+
+        let (vk, sk) = match key_manager {
+            Some(key_manager) => {
+                let (vk, key_ref) = self._with_key_manager(key_manager, |km| km.create_key(crypto_type_name, seed))?;
+                crypto_type.validate_key(&vk)?;
+                (vk, key_ref)
+            }
+            None => {
+                let (vk, sk) = crypto_type.create_key(seed)?;
+                (vk, sk[..].to_base58())
+            }
+        };
+
+        let mut verkey = vk[..].to_base58();
 
         if !crypto_type_name.eq(DEFAULT_CRYPTO_TYPE) {
             // Use suffix with crypto type name to store crypto type inside of vk
-            vk = format!("{}:{}", vk, crypto_type_name);
+            verkey = format!("{}:{}", verkey, crypto_type_name);
         }
 
-        let did = (Did::new(did, vk.clone()), Key::new(vk, sk));
+        let key = match key_manager {
+            Some(key_manager) => Key::new_managed(verkey, sk, key_manager.to_string()),
+            None => Key::new(verkey, sk)
+        };
 
-        trace!("create_my_did <<< did: {:?}", did);
+        Ok((vk, key))
+    }
 
-        Ok(did)
+    fn _with_key_manager<T, F>(&self, name: &str, f: F) -> IndyResult<T> where F: FnOnce(&dyn KeyManager) -> IndyResult<T> {
+        let key_managers = self.key_managers.borrow();
+
+        let key_manager = key_managers.get(name)
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownCrypto, format!("Unknown key manager: {}", name)))?;
+
+        f(key_manager.as_ref())
     }
 
     pub fn create_their_did(&self, their_did_info: &TheirDidInfo) -> IndyResult<TheirDid> {
@@ -125,8 +177,11 @@ impl CryptoService {
         // Check did is correct Base58
         let _ = self.validate_did(&their_did_info.did)?;
 
-        let verkey = build_full_verkey(&their_did_info.did.to_unqualified().0,
-                                       their_did_info.verkey.as_ref().map(String::as_str))?;
+        let verkey = match (their_did_info.verkey.as_ref(), did_key::resolve_verkey(&their_did_info.did)) {
+            (None, Some(verkey)) => verkey?,
+            (verkey, _) => build_full_verkey(&their_did_info.did.to_unqualified().0,
+                                             verkey.map(String::as_str))?
+        };
 
         self.validate_key(&verkey)?;
 
@@ -148,8 +203,10 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let signature = crypto_type.sign(&my_sk, doc)?[..].to_vec();
+        let signature = match my_key.key_manager {
+            Some(ref key_manager) => self._with_key_manager(key_manager, |km| km.sign(&my_key.signkey, doc))?,
+            None => crypto_type.sign(&my_key.signkey.as_str().from_base58()?, doc)?
+        };
 
         trace!("sign <<< signature: {:?}", signature);
 
@@ -167,10 +224,9 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(&their_vk.from_base58()?)?;
-        let signature = ed25519_sign::Signature::from_slice(&signature)?;
+        let their_vk = their_vk.from_base58()?;
 
-        let valid = crypto_type.verify(&their_vk, msg, &signature)?;
+        let valid = crypto_type.verify(&their_vk, msg, signature)?;
 
         trace!("verify <<< valid: {:?}", valid);
 
@@ -214,12 +270,13 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(&crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let their_vk = their_vk.from_base58()?;
         let nonce = crypto_type.gen_nonce();
 
-        let encrypted_doc = crypto_type.crypto_box(&my_sk, &their_vk, doc, &nonce)?;
-        let nonce = nonce[..].to_vec();
+        let encrypted_doc = match my_key.key_manager {
+            Some(ref key_manager) => self._with_key_manager(key_manager, |km| km.crypto_box(&my_key.signkey, &their_vk, doc, &nonce))?,
+            None => crypto_type.crypto_box(&my_key.signkey.as_str().from_base58()?, &their_vk, doc, &nonce)?
+        };
 
         trace!("crypto_box <<< encrypted_doc: {:?}, nonce: {:?}", encrypted_doc, nonce);
 
@@ -248,11 +305,12 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_sk = ed25519_sign::SecretKey::from_slice(&my_key.signkey.from_base58()?.as_slice())?;
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
-        let nonce = ed25519_box::Nonce::from_slice(&nonce)?;
+        let their_vk = their_vk.from_base58()?;
 
-        let decrypted_doc = crypto_type.crypto_box_open(&my_sk, &their_vk, &doc, &nonce)?;
+        let decrypted_doc = match my_key.key_manager {
+            Some(ref key_manager) => self._with_key_manager(key_manager, |km| km.crypto_box_open(&my_key.signkey, &their_vk, doc, nonce))?,
+            None => crypto_type.crypto_box_open(&my_key.signkey.from_base58()?, &their_vk, &doc, nonce)?
+        };
 
         trace!("crypto_box_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -270,7 +328,7 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let their_vk = ed25519_sign::PublicKey::from_slice(their_vk.from_base58()?.as_slice())?;
+        let their_vk = their_vk.from_base58()?;
 
         let encrypted_doc = crypto_type.crypto_box_seal(&their_vk, doc)?;
 
@@ -291,10 +349,10 @@ impl CryptoService {
 
         let crypto_type = self.crypto_types.get(crypto_type_name).unwrap();
 
-        let my_vk = ed25519_sign::PublicKey::from_slice(my_vk.from_base58()?.as_slice())?;
-        let my_sk = ed25519_sign::SecretKey::from_slice(my_key.signkey.as_str().from_base58()?.as_slice())?;
-
-        let decrypted_doc = crypto_type.crypto_box_seal_open(&my_vk, &my_sk, doc)?;
+        let decrypted_doc = match my_key.key_manager {
+            Some(ref key_manager) => self._with_key_manager(key_manager, |km| km.crypto_box_seal_open(&my_key.signkey, doc))?,
+            None => crypto_type.crypto_box_seal_open(&my_vk.from_base58()?, &my_key.signkey.as_str().from_base58()?, doc)?
+        };
 
         trace!("crypto_box_seal_open <<< decrypted_doc: {:?}", decrypted_doc);
 
@@ -356,7 +414,7 @@ impl CryptoService {
         if vk.starts_with('~') {
             let _ = vk[1..].from_base58()?; // TODO: proper validate abbreviated verkey
         } else {
-            let vk = ed25519_sign::PublicKey::from_slice(vk.from_base58()?.as_slice())?;
+            let vk = vk.from_base58()?;
             crypto_type.validate_key(&vk)?;
         };
 
@@ -374,6 +432,60 @@ impl CryptoService {
         Ok(())
     }
 
+    pub fn vk_to_x25519(&self, vk: &str) -> IndyResult<ed25519_box::PublicKey> {
+        trace!("vk_to_x25519 >>> vk: {:?}", vk);
+
+        let (vk, crypto_type_name) = split_verkey(vk);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("X25519 key agreement isn't supported for crypto: {}", crypto_type_name)));
+        }
+
+        let vk = ed25519_sign::PublicKey::from_slice(&vk.from_base58()?)?;
+        let res = ed25519_sign::vk_to_curve25519(&vk)?;
+
+        trace!("vk_to_x25519 <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    /// X25519 shared secret of the key (converted to X25519) and their X25519 public key.
+    pub fn key_agreement(&self, my_key: &Key, their_pk: &ed25519_box::PublicKey) -> IndyResult<Vec<u8>> {
+        trace!("key_agreement >>> my_key: {:?}, their_pk: {:?}", my_key, their_pk);
+
+        let crypto_type_name = verkey_get_cryptoname(&my_key.verkey);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("X25519 key agreement isn't supported for crypto: {}", crypto_type_name)));
+        }
+
+        let res = match my_key.key_manager {
+            Some(ref key_manager) => self._with_key_manager(key_manager, |km| km.key_agreement(&my_key.signkey, &their_pk[..]))?,
+            None => ed25519_box::scalarmult(&self.sk_to_x25519(my_key)?, their_pk)?
+        };
+
+        trace!("key_agreement <<<");
+
+        Ok(res)
+    }
+
+    pub fn sk_to_x25519(&self, key: &Key) -> IndyResult<ed25519_box::SecretKey> {
+        trace!("sk_to_x25519 >>> key: {:?}", key);
+
+        let crypto_type_name = verkey_get_cryptoname(&key.verkey);
+
+        if crypto_type_name != DEFAULT_CRYPTO_TYPE {
+            return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("X25519 key agreement isn't supported for crypto: {}", crypto_type_name)));
+        }
+
+        let sk = ed25519_sign::SecretKey::from_slice(&key.signkey.from_base58()?)?;
+        let res = ed25519_sign::sk_to_curve25519(&sk)?;
+
+        trace!("sk_to_x25519 <<<");
+
+        Ok(res)
+    }
+
     pub fn encrypt_plaintext(&self,
                              plaintext: Vec<u8>,
                              aad: &str,
@@ -445,7 +557,7 @@ impl CryptoService {
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::crypto::did::MyDidInfo;
+    use crate::domain::crypto::did::{DidMethod, MyDidInfo};
     use indy_utils::crypto::chacha20poly1305_ietf::gen_key;
 
     use super::*;
@@ -453,7 +565,7 @@ mod tests {
     #[test]
     fn create_my_did_with_works_for_empty_info() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let my_did = service.create_my_did(&did_info);
         assert!(my_did.is_ok());
     }
@@ -463,7 +575,7 @@ mod tests {
         let service = CryptoService::new();
 
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
-        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
 
         let (my_did, _) = service.create_my_did(&did_info).unwrap();
         assert_eq!(did, my_did.did);
@@ -476,7 +588,7 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let crypto_type = Some("type".to_string());
 
-        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None };
+        let did_info = MyDidInfo { did: Some(did), cid: None, seed: None, crypto_type, method_name: None, service: None, key_manager: None };
 
         assert!(service.create_my_did(&did_info).is_err());
     }
@@ -488,8 +600,8 @@ mod tests {
         let did = DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string());
         let seed = Some("00000000000000000000000000000My1".to_string());
 
-        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None };
-        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info_with_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed, crypto_type: None, method_name: None, service: None, key_manager: None };
+        let did_info_without_seed = MyDidInfo { did: Some(did.clone()), cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
 
         let (did_with_seed, _) = service.create_my_did(&did_info_with_seed).unwrap();
         let (did_without_seed, _) = service.create_my_did(&did_info_without_seed).unwrap();
@@ -497,6 +609,28 @@ mod tests {
         assert_ne!(did_with_seed.verkey, did_without_seed.verkey)
     }
 
+    #[test]
+    fn create_my_did_works_for_key_method() {
+        let service = CryptoService::new();
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: Some(DidMethod(did_key::KEY_METHOD.to_string())), service: None, key_manager: None };
+
+        let (my_did, _) = service.create_my_did(&did_info).unwrap();
+        assert!(my_did.did.0.starts_with("did:key:z6Mk"));
+        assert_eq!(my_did.verkey, did_key::resolve_verkey(&my_did.did).unwrap().unwrap());
+    }
+
+    #[test]
+    fn create_their_did_works_for_did_key_without_verkey() {
+        let service = CryptoService::new();
+        let did = DidValue("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK".to_string());
+
+        let their_did_info = TheirDidInfo::new(did.clone(), None);
+        let their_did = service.create_their_did(&their_did_info).unwrap();
+
+        assert_eq!(did, their_did.did);
+        assert_eq!("48GdbJyVULjHDaBNS6ct9oAGtckZUS5v8asrPzvZ7R1w", their_did.verkey);
+    }
+
     #[test]
     fn create_their_did_works_without_verkey() {
         let service = CryptoService::new();
@@ -536,7 +670,7 @@ mod tests {
     #[test]
     fn sign_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
 
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
@@ -552,10 +686,109 @@ mod tests {
         assert!(service.sign(&my_key, message.as_bytes()).is_err());
     }
 
+    struct TestKeyManager {
+        keys: RefCell<HashMap<String, Vec<u8>>>
+    }
+
+    impl TestKeyManager {
+        fn new() -> TestKeyManager {
+            TestKeyManager { keys: RefCell::new(HashMap::new()) }
+        }
+
+        fn sk(&self, key_ref: &str) -> IndyResult<Vec<u8>> {
+            self.keys.borrow().get(key_ref).cloned()
+                .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Unknown key"))
+        }
+    }
+
+    impl KeyManager for TestKeyManager {
+        fn create_key(&self, _crypto_type: &str, seed: Option<&[u8]>) -> IndyResult<(Vec<u8>, String)> {
+            let (vk, sk) = ED25519CryptoType::new().create_key(seed)?;
+            let key_ref = format!("ref-{}", vk.to_base58());
+            self.keys.borrow_mut().insert(key_ref.clone(), sk);
+            Ok((vk, key_ref))
+        }
+
+        fn sign(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+            ED25519CryptoType::new().sign(&self.sk(key_ref)?, doc)
+        }
+
+        fn crypto_box(&self, key_ref: &str, their_vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+            ED25519CryptoType::new().crypto_box(&self.sk(key_ref)?, their_vk, doc, nonce)
+        }
+
+        fn crypto_box_open(&self, key_ref: &str, their_vk: &[u8], doc: &[u8], nonce: &[u8]) -> IndyResult<Vec<u8>> {
+            ED25519CryptoType::new().crypto_box_open(&self.sk(key_ref)?, their_vk, doc, nonce)
+        }
+
+        fn crypto_box_seal_open(&self, key_ref: &str, doc: &[u8]) -> IndyResult<Vec<u8>> {
+            let sk = self.sk(key_ref)?;
+            // ed25519 secret key contains public key in the last 32 bytes
+            ED25519CryptoType::new().crypto_box_seal_open(&sk[32..], &sk, doc)
+        }
+
+        fn key_agreement(&self, key_ref: &str, their_pk: &[u8]) -> IndyResult<Vec<u8>> {
+            let sk = ed25519_sign::sk_to_curve25519(&ed25519_sign::SecretKey::from_slice(&self.sk(key_ref)?)?)?;
+            ed25519_box::scalarmult(&sk, &ed25519_box::PublicKey::from_slice(their_pk)?)
+        }
+    }
+
+    fn _managed_key_info() -> KeyInfo {
+        KeyInfo { seed: None, crypto_type: None, key_manager: Some("test".to_string()) }
+    }
+
+    #[test]
+    fn register_key_manager_fails_for_duplicate() {
+        let service = CryptoService::new();
+        service.register_key_manager("test", Box::new(TestKeyManager::new())).unwrap();
+        assert!(service.register_key_manager("test", Box::new(TestKeyManager::new())).is_err());
+    }
+
+    #[test]
+    fn create_key_fails_for_unknown_key_manager() {
+        let service = CryptoService::new();
+        assert!(service.create_key(&_managed_key_info()).is_err());
+    }
+
+    #[test]
+    fn sign_verify_works_for_managed_key() {
+        let service = CryptoService::new();
+        service.register_key_manager("test", Box::new(TestKeyManager::new())).unwrap();
+
+        let key = service.create_key(&_managed_key_info()).unwrap();
+        assert_eq!(Some("test".to_string()), key.key_manager);
+        assert_eq!(format!("ref-{}", key.verkey), key.signkey);
+
+        let signature = service.sign(&key, b"message").unwrap();
+        assert!(service.verify(&key.verkey, b"message", &signature).unwrap());
+    }
+
+    #[test]
+    fn crypto_box_works_for_managed_key() {
+        let service = CryptoService::new();
+        service.register_key_manager("test", Box::new(TestKeyManager::new())).unwrap();
+
+        let managed_key = service.create_key(&_managed_key_info()).unwrap();
+        let local_key = service.create_key(&KeyInfo { seed: None, crypto_type: None, key_manager: None }).unwrap();
+
+        let (encrypted, nonce) = service.crypto_box(&managed_key, &local_key.verkey, b"message").unwrap();
+        assert_eq!(b"message".to_vec(), service.crypto_box_open(&local_key, &managed_key.verkey, &encrypted, &nonce).unwrap());
+
+        let (encrypted, nonce) = service.crypto_box(&local_key, &managed_key.verkey, b"message").unwrap();
+        assert_eq!(b"message".to_vec(), service.crypto_box_open(&managed_key, &local_key.verkey, &encrypted, &nonce).unwrap());
+
+        let sealed = service.crypto_box_seal(&managed_key.verkey, b"message").unwrap();
+        assert_eq!(b"message".to_vec(), service.crypto_box_seal_open(&managed_key, &sealed).unwrap());
+
+        let their_pk = service.vk_to_x25519(&local_key.verkey).unwrap();
+        let my_pk = service.vk_to_x25519(&managed_key.verkey).unwrap();
+        assert_eq!(service.key_agreement(&managed_key, &their_pk).unwrap(), service.key_agreement(&local_key, &my_pk).unwrap());
+    }
+
     #[test]
     fn sign_verify_works() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -563,10 +796,35 @@ mod tests {
         assert!(valid);
     }
 
+    #[test]
+    fn sign_verify_works_for_ec_crypto_types() {
+        let service = CryptoService::new();
+        let message = r#"message"#;
+
+        for crypto_type in &[SECP256K1_CRYPTO_TYPE, P256_CRYPTO_TYPE] {
+            let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: Some(crypto_type.to_string()), method_name: None, service: None, key_manager: None };
+            let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
+            assert_eq!(*crypto_type, verkey_get_cryptoname(&my_did.verkey));
+
+            let signature = service.sign(&my_key, message.as_bytes()).unwrap();
+            let valid = service.verify(&my_did.verkey, message.as_bytes(), &signature).unwrap();
+            assert!(valid);
+        }
+    }
+
+    #[test]
+    fn crypto_box_works_for_incompatible_crypto_types() {
+        let service = CryptoService::new();
+        let my_key = service.create_key(&KeyInfo { seed: None, crypto_type: Some(SECP256K1_CRYPTO_TYPE.to_string()), key_manager: None }).unwrap();
+        let their_key = service.create_key(&KeyInfo { seed: None, crypto_type: Some(P256_CRYPTO_TYPE.to_string()), key_manager: None }).unwrap();
+
+        assert!(service.crypto_box(&my_key, &their_key.verkey, b"message").is_err());
+    }
+
     #[test]
     fn sign_verify_works_for_verkey_contained_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -579,7 +837,7 @@ mod tests {
     #[test]
     fn sign_verify_works_for_verkey_contained_invalid_crypto_type() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let message = r#"message"#;
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -590,7 +848,7 @@ mod tests {
     #[test]
     fn verify_not_works_for_invalid_verkey() {
         let service = CryptoService::new();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let message = r#"message"#;
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let signature = service.sign(&my_key, message.as_bytes()).unwrap();
@@ -603,7 +861,7 @@ mod tests {
     fn crypto_box_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let (_, my_key) = service.create_my_did(&did_info).unwrap();
         let (their_did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let their_did = Did::new(their_did.did, their_did.verkey);
@@ -617,7 +875,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -645,7 +903,7 @@ mod tests {
 
         let msg = "some message";
 
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
 
         let (my_did, my_key) = service.create_my_did(&did_info).unwrap();
 
@@ -671,7 +929,7 @@ mod tests {
     fn crypto_box_seal_works() {
         let service = CryptoService::new();
         let msg = "some message";
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let (did, _) = service.create_my_did(&did_info.clone()).unwrap();
         let did = Did::new(did.did, did.verkey);
         let encrypted_message = service.crypto_box_seal(&did.verkey, msg.as_bytes());
@@ -682,7 +940,7 @@ mod tests {
     fn crypto_box_seal_and_crypto_box_seal_open_works() {
         let service = CryptoService::new();
         let msg = "some message".as_bytes();
-        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None };
+        let did_info = MyDidInfo { did: None, cid: None, seed: None, crypto_type: None, method_name: None, service: None, key_manager: None };
         let (did, key) = service.create_my_did(&did_info.clone()).unwrap();
         let encrypt_did = Did::new(did.did.clone(), did.verkey.clone());
         let encrypted_message = service.crypto_box_seal(&encrypt_did.verkey, msg).unwrap();
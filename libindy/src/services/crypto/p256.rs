@@ -0,0 +1,36 @@
+use indy_api_types::errors::prelude::*;
+use super::CryptoType;
+use super::ecdh;
+
+use p256::{PublicKey, SecretKey};
+use p256::ecdh::diffie_hellman;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+/// Length of SEC1 compressed public key.
+const VERKEYBYTES: usize = 33;
+
+ecdsa_crypto_type!(
+    /// P-256 keys with ECDSA (SHA-256) signatures.
+    ///
+    /// Verkey is SEC1 compressed public key, signkey is 32 bytes secret scalar
+    /// and signature is 64 bytes `r || s` pair.
+    P256CryptoType, p256, "P-256"
+);
+
+fn _secret_key(sk: &[u8]) -> IndyResult<SecretKey> {
+    SecretKey::from_bytes(sk)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid P-256 signkey")
+}
+
+fn _public_key(vk: &[u8]) -> IndyResult<PublicKey> {
+    PublicKey::from_sec1_bytes(vk)
+        .to_indy(IndyErrorKind::InvalidStructure, "Invalid P-256 verkey")
+}
+
+fn _verkey(vk: &PublicKey) -> Vec<u8> {
+    vk.to_encoded_point(true).as_bytes().to_vec()
+}
+
+fn _shared_secret(sk: &SecretKey, vk: &PublicKey) -> Vec<u8> {
+    diffie_hellman(sk.secret_scalar(), vk.as_affine()).as_bytes().to_vec()
+}
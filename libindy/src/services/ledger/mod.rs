@@ -11,7 +11,7 @@ use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistr
 use crate::domain::anoncreds::revocation_registry_delta::{RevocationRegistryDelta, RevocationRegistryDeltaV1};
 use crate::domain::anoncreds::schema::{Schema, SchemaV1, SchemaId};
 use crate::domain::crypto::did::DidValue;
-use crate::domain::ledger::attrib::{AttribOperation, GetAttribOperation};
+use crate::domain::ledger::attrib::{AttribOperation, GetAttribOperation, GetAttrReplyResult};
 use crate::domain::ledger::constants::{GET_VALIDATOR_INFO, POOL_RESTART, ROLE_REMOVE, STEWARD, ENDORSER, TRUSTEE, NETWORK_MONITOR, ROLES, txn_name_to_code};
 use crate::domain::ledger::cred_def::{CredDefOperation, GetCredDefOperation, GetCredDefReplyResult};
 use crate::domain::ledger::ddo::GetDdoOperation;
@@ -141,6 +141,18 @@ impl LedgerService {
         build_result!(GetAttribOperation, identifier, dest.to_short(), raw, hash, enc)
     }
 
+    #[logfn(Info)]
+    pub fn parse_get_attrib_response(&self, get_attrib_response: &str) -> IndyResult<String> {
+        let reply: Reply<GetAttrReplyResult> = LedgerService::parse_response(get_attrib_response)?;
+
+        let raw = match reply.result() {
+            GetAttrReplyResult::GetAttrReplyResultV0(res) => res.data,
+            GetAttrReplyResult::GetAttrReplyResultV1(res) => res.txn.data.raw,
+        };
+
+        Ok(raw)
+    }
+
     #[logfn(Info)]
     pub fn build_schema_request(&self, identifier: &DidValue, schema: SchemaV1) -> IndyResult<String> {
         let schema_data = SchemaOperationData::new(schema.name, schema.version, schema.attr_names.into());
@@ -197,16 +209,20 @@ impl LedgerService {
 
     #[logfn(Info)]
     pub fn build_get_txn_request(&self, identifier: Option<&DidValue>, ledger_type: Option<&str>, seq_no: i32) -> IndyResult<String> {
-        let ledger_id = match ledger_type {
+        let ledger_id = self.parse_ledger_id(ledger_type)?;
+
+        build_result!(GetTxnOperation, identifier, seq_no, ledger_id)
+    }
+
+    pub fn parse_ledger_id(&self, ledger_type: Option<&str>) -> IndyResult<i32> {
+        match ledger_type {
             Some(type_) =>
                 serde_json::from_str::<LedgerType>(&format!(r#""{}""#, type_))
                     .map(|type_| type_.to_id())
                     .or_else(|_| type_.parse::<i32>())
-                    .to_indy(IndyErrorKind::InvalidStructure, format!("Invalid Ledger type: {}", type_))?,
-            None => LedgerType::DOMAIN.to_id()
-        };
-
-        build_result!(GetTxnOperation, identifier, seq_no, ledger_id)
+                    .to_indy(IndyErrorKind::InvalidStructure, format!("Invalid Ledger type: {}", type_)),
+            None => Ok(LedgerType::DOMAIN.to_id())
+        }
     }
 
     #[logfn(Info)]
@@ -712,6 +728,44 @@ mod tests {
         check_request(&request, expected_result);
     }
 
+    #[test]
+    fn parse_get_attrib_response_works() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({
+            "op": "REPLY",
+            "result": {
+                "type": GET_ATTR,
+                "identifier": IDENTIFIER,
+                "dest": DEST,
+                "raw": "endpoint",
+                "data": r#"{"endpoint":{"ha":"127.0.0.1:5555"}}"#
+            }
+        }).to_string();
+
+        let raw = ledger_service.parse_get_attrib_response(&response).unwrap();
+        assert_eq!(r#"{"endpoint":{"ha":"127.0.0.1:5555"}}"#, raw);
+    }
+
+    #[test]
+    fn parse_get_attrib_response_works_for_not_found() {
+        let ledger_service = LedgerService::new();
+
+        let response = json!({
+            "op": "REPLY",
+            "result": {
+                "type": GET_ATTR,
+                "identifier": IDENTIFIER,
+                "dest": DEST,
+                "raw": "endpoint",
+                "data": null
+            }
+        }).to_string();
+
+        let res = ledger_service.parse_get_attrib_response(&response);
+        assert_eq!(IndyErrorKind::LedgerItemNotFound, res.unwrap_err().kind());
+    }
+
     #[test]
     fn build_schema_request_works() {
         let ledger_service = LedgerService::new();
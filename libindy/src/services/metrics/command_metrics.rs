@@ -67,6 +67,21 @@ impl From<&IssuerCommand> for CommandMetric {
             IssuerCommand::RevokeCredential(_, _, _, _, _) => {
                 CommandMetric::IssuerCommandRevokeCredential
             }
+            IssuerCommand::CreateRevocationRegistryManager(_, _, _, _, _, _) => {
+                CommandMetric::IssuerCommandCreateRevocationRegistryManager
+            }
+            IssuerCommand::CreateCredentialWithRevocationRegistryManager(_, _, _, _, _, _, _) => {
+                CommandMetric::IssuerCommandCreateCredentialWithRevocationRegistryManager
+            }
+            IssuerCommand::GetRevocationRegistryManagerState(_, _, _) => {
+                CommandMetric::IssuerCommandGetRevocationRegistryManagerState
+            }
+            IssuerCommand::MarkRevocationRegistryPublished(_, _, _) => {
+                CommandMetric::IssuerCommandMarkRevocationRegistryPublished
+            }
+            IssuerCommand::GetIssuedCredentials(_, _, _) => {
+                CommandMetric::IssuerCommandGetIssuedCredentials
+            }
             IssuerCommand::MergeRevocationRegistryDeltas(_, _, _) => {
                 CommandMetric::IssuerCommandMergeRevocationRegistryDeltas
             }
@@ -92,6 +107,7 @@ impl From<&ProverCommand> for CommandMetric {
             ProverCommand::SearchCredentialsForProofReq(_, _, _, _) => { CommandMetric::ProverCommandSearchCredentialsForProofReq }
             ProverCommand::FetchCredentialForProofReq(_, _, _, _) => { CommandMetric::ProverCommandFetchCredentialForProofReq }
             ProverCommand::CloseCredentialsSearchForProofReq(_, _) => { CommandMetric::ProverCommandCloseCredentialsSearchForProofReq }
+            ProverCommand::SelectCredentialsForProofReq(_, _, _, _) => { CommandMetric::ProverCommandSelectCredentialsForProofReq }
             ProverCommand::CreateProof(_, _, _, _, _, _, _, _) => { CommandMetric::ProverCommandCreateProof }
             ProverCommand::CreateRevocationState(_, _, _, _, _, _) => { CommandMetric::ProverCommandCreateRevocationState }
             ProverCommand::UpdateRevocationState(_, _, _, _, _, _, _) => { CommandMetric::ProverCommandUpdateRevocationState }
@@ -103,6 +119,7 @@ impl From<&VerifierCommand> for CommandMetric {
     fn from(cmd: &VerifierCommand) -> Self {
         match cmd {
             VerifierCommand::VerifyProof(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProof }
+            VerifierCommand::VerifyProofWithReport(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProofWithReport }
             VerifierCommand::GenerateNonce(_) => { CommandMetric::VerifierCommandGenerateNonce }
         }
     }
@@ -118,6 +135,7 @@ impl From<&Command> for CommandMetric {
                     AnoncredsCommand::Prover(cmd) => { cmd.into() }
                     AnoncredsCommand::Verifier(cmd) => { cmd.into() }
                     AnoncredsCommand::ToUnqualified(_, _) => { CommandMetric::AnoncredsCommandToUnqualified }
+                    AnoncredsCommand::EncodeCredentialValues(_, _) => { CommandMetric::AnoncredsCommandEncodeCredentialValues }
                 }
             }
             Command::BlobStorage(cmd) => {
@@ -139,6 +157,9 @@ impl From<&Command> for CommandMetric {
                     CryptoCommand::AnonymousDecrypt(_, _, _, _) => { CommandMetric::CryptoCommandAnonymousDecrypt }
                     CryptoCommand::PackMessage(_, _, _, _, _) => { CommandMetric::CryptoCommandPackMessage }
                     CryptoCommand::UnpackMessage(_, _, _) => { CommandMetric::CryptoCommandUnpackMessage }
+                    CryptoCommand::PackMessageV2(_, _, _, _, _, _) => { CommandMetric::CryptoCommandPackMessageV2 }
+                    CryptoCommand::UnpackMessageV2(_, _, _) => { CommandMetric::CryptoCommandUnpackMessageV2 }
+                    CryptoCommand::RegisterKeyManager(_, _, _, _, _, _, _, _, _) => { CommandMetric::CryptoCommandRegisterKeyManager }
                 }
             }
             Command::Ledger(cmd) => {
@@ -184,6 +205,25 @@ impl From<&Command> for CommandMetric {
                     LedgerCommand::GetSchemaContinue(_, _, _) => { CommandMetric::LedgerCommandGetSchemaContinue }
                     LedgerCommand::GetCredDef(_, _, _, _) => { CommandMetric::LedgerCommandGetCredDef }
                     LedgerCommand::GetCredDefContinue(_, _, _) => { CommandMetric::LedgerCommandGetCredDefContinue }
+                    LedgerCommand::GetNym(_, _, _, _) => { CommandMetric::LedgerCommandGetNym }
+                    LedgerCommand::GetNymContinue(_, _) => { CommandMetric::LedgerCommandGetNymContinue }
+                    LedgerCommand::GetAttrib(_, _, _, _, _, _, _) => { CommandMetric::LedgerCommandGetAttrib }
+                    LedgerCommand::GetAttribContinue(_, _) => { CommandMetric::LedgerCommandGetAttribContinue }
+                    LedgerCommand::GetRevocRegDef(_, _, _, _) => { CommandMetric::LedgerCommandGetRevocRegDef }
+                    LedgerCommand::GetRevocRegDefContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegDefContinue }
+                    LedgerCommand::GetRevocRegDelta(_, _, _, _, _, _) => { CommandMetric::LedgerCommandGetRevocRegDelta }
+                    LedgerCommand::GetRevocRegDeltaContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegDeltaContinue }
+                    LedgerCommand::Resolve(_, _) => { CommandMetric::LedgerCommandResolve }
+                    LedgerCommand::ResolveContinue(_, _, _) => { CommandMetric::LedgerCommandResolveContinue }
+                    LedgerCommand::DownloadLedgerTxns(_, _, _, _, _, _) => { CommandMetric::LedgerCommandDownloadLedgerTxns }
+                    LedgerCommand::DownloadLedgerTxnsContinue(_, _, _, _, _, _, _) => { CommandMetric::LedgerCommandDownloadLedgerTxnsContinue }
+                    LedgerCommand::OpenLedgerTxnsSearch(_, _, _, _, _) => { CommandMetric::LedgerCommandOpenLedgerTxnsSearch }
+                    LedgerCommand::OpenLedgerTxnsSearchContinue(_, _, _, _, _, _) => { CommandMetric::LedgerCommandOpenLedgerTxnsSearchContinue }
+                    LedgerCommand::FetchLedgerTxnsSearchNextBatch(_, _) => { CommandMetric::LedgerCommandFetchLedgerTxnsSearchNextBatch }
+                    LedgerCommand::CloseLedgerTxnsSearch(_, _) => { CommandMetric::LedgerCommandCloseLedgerTxnsSearch }
+                    LedgerCommand::CreatePoolSnapshot(_, _) => { CommandMetric::LedgerCommandCreatePoolSnapshot }
+                    LedgerCommand::CreatePoolSnapshotContinue(_, _, _) => { CommandMetric::LedgerCommandCreatePoolSnapshotContinue }
+                    LedgerCommand::CreatePoolSnapshotSynced(_, _, _, _, _) => { CommandMetric::LedgerCommandCreatePoolSnapshotSynced }
                     LedgerCommand::BuildTxnAuthorAgreementRequest(_, _, _, _, _, _) => { CommandMetric::LedgerCommandBuildTxnAuthorAgreementRequest }
                     LedgerCommand::BuildDisableAllTxnAuthorAgreementsRequest(_, _) => { CommandMetric::LedgerCommandBuildDisableAllTxnAuthorAgreementsRequest }
                     LedgerCommand::BuildGetTxnAuthorAgreementRequest(_, _, _) => { CommandMetric::LedgerCommandBuildGetTxnAuthorAgreementRequest }
@@ -207,6 +247,8 @@ impl From<&Command> for CommandMetric {
                     PoolCommand::Refresh(_, _) => { CommandMetric::PoolCommandRefresh }
                     PoolCommand::RefreshAck(_, _) => { CommandMetric::PoolCommandRefreshAck }
                     PoolCommand::SetProtocolVersion(_, _) => { CommandMetric::PoolCommandSetProtocolVersion }
+                    PoolCommand::ImportSnapshot(_, _, _, _) => { CommandMetric::PoolCommandImportSnapshot }
+                    PoolCommand::GetStatus(_, _) => { CommandMetric::PoolCommandGetStatus }
                 }
             }
             Command::Did(cmd) => {
@@ -232,6 +274,9 @@ impl From<&Command> for CommandMetric {
             Command::Wallet(cmd) => {
                 match cmd {
                     WalletCommand::RegisterWalletType(_, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) => { CommandMetric::WalletCommandRegisterWalletType }
+                    WalletCommand::RegisterWalletStorageTransactions(_, _, _, _, _) => { CommandMetric::WalletCommandRegisterWalletStorageTransactions }
+                    WalletCommand::RegisterWalletStorageList(_, _, _, _) => { CommandMetric::WalletCommandRegisterWalletStorageList }
+                    WalletCommand::List(_, _, _, _) => { CommandMetric::WalletCommandList }
                     WalletCommand::Create(_, _, _) => { CommandMetric::WalletCommandCreate }
                     WalletCommand::CreateContinue(_, _, _, _, _) => { CommandMetric::WalletCommandCreateContinue }
                     WalletCommand::Open(_, _, _) => { CommandMetric::WalletCommandOpen }
@@ -241,8 +286,16 @@ impl From<&Command> for CommandMetric {
                     WalletCommand::DeleteContinue(_, _, _, _, _) => { CommandMetric::WalletCommandDeleteContinue }
                     WalletCommand::Export(_, _, _) => { CommandMetric::WalletCommandExport }
                     WalletCommand::ExportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandExportContinue }
+                    WalletCommand::Rekey(_, _, _, _) => { CommandMetric::WalletCommandRekey }
+                    WalletCommand::RekeyContinue(_, _, _, _) => { CommandMetric::WalletCommandRekeyContinue }
                     WalletCommand::Import(_, _, _, _) => { CommandMetric::WalletCommandImport }
                     WalletCommand::ImportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandImportContinue }
+                    WalletCommand::Check(_, _, _) => { CommandMetric::WalletCommandCheck }
+                    WalletCommand::BeginTransaction(_, _) => { CommandMetric::WalletCommandBeginTransaction }
+                    WalletCommand::CommitTransaction(_, _) => { CommandMetric::WalletCommandCommitTransaction }
+                    WalletCommand::RollbackTransaction(_, _) => { CommandMetric::WalletCommandRollbackTransaction }
+                    WalletCommand::SubscribeEvents(_, _, _, _) => { CommandMetric::WalletCommandSubscribeEvents }
+                    WalletCommand::UnsubscribeEvents(_, _) => { CommandMetric::WalletCommandUnsubscribeEvents }
                     WalletCommand::GenerateKey(_, _) => { CommandMetric::WalletCommandGenerateKey }
                     WalletCommand::DeriveKey(_, _) => { CommandMetric::WalletCommandDeriveKey }
                 }
@@ -314,8 +367,20 @@ impl From<&Command> for CommandMetric {
                     CacheCommand::GetSchemaContinue(_, _, _, _) => { CommandMetric::CacheCommandGetSchemaContinue }
                     CacheCommand::GetCredDef(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetCredDef }
                     CacheCommand::GetCredDefContinue(_, _, _, _) => { CommandMetric::CacheCommandGetCredDefContinue }
+                    CacheCommand::GetNym(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetNym }
+                    CacheCommand::GetNymContinue(_, _, _, _, _) => { CommandMetric::CacheCommandGetNymContinue }
+                    CacheCommand::GetAttrib(_, _, _, _, _, _, _, _, _) => { CommandMetric::CacheCommandGetAttrib }
+                    CacheCommand::GetAttribContinue(_, _, _, _, _) => { CommandMetric::CacheCommandGetAttribContinue }
+                    CacheCommand::GetRevocRegDef(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetRevocRegDef }
+                    CacheCommand::GetRevocRegDefContinue(_, _, _, _) => { CommandMetric::CacheCommandGetRevocRegDefContinue }
+                    CacheCommand::GetRevocRegDelta(_, _, _, _, _, _, _, _) => { CommandMetric::CacheCommandGetRevocRegDelta }
+                    CacheCommand::GetRevocRegDeltaContinue(_, _, _, _, _) => { CommandMetric::CacheCommandGetRevocRegDeltaContinue }
                     CacheCommand::PurgeSchemaCache(_, _, _) => { CommandMetric::CacheCommandPurgeSchemaCache }
                     CacheCommand::PurgeCredDefCache(_, _, _) => { CommandMetric::CacheCommandPurgeCredDefCache }
+                    CacheCommand::PurgeNymCache(_, _, _) => { CommandMetric::CacheCommandPurgeNymCache }
+                    CacheCommand::PurgeAttribCache(_, _, _) => { CommandMetric::CacheCommandPurgeAttribCache }
+                    CacheCommand::PurgeRevocRegDefCache(_, _, _) => { CommandMetric::CacheCommandPurgeRevocRegDefCache }
+                    CacheCommand::PurgeRevocRegDeltaCache(_, _, _) => { CommandMetric::CacheCommandPurgeRevocRegDeltaCache }
                 }
             }
             Command::Metrics(cmd) => {
@@ -340,6 +405,11 @@ pub enum CommandMetric {
     IssuerCommandCreateCredentialOffer,
     IssuerCommandCreateCredential,
     IssuerCommandRevokeCredential,
+    IssuerCommandCreateRevocationRegistryManager,
+    IssuerCommandCreateCredentialWithRevocationRegistryManager,
+    IssuerCommandGetRevocationRegistryManagerState,
+    IssuerCommandMarkRevocationRegistryPublished,
+    IssuerCommandGetIssuedCredentials,
     IssuerCommandMergeRevocationRegistryDeltas,
     // ProverCommand
     ProverCommandCreateMasterSecret,
@@ -357,14 +427,17 @@ pub enum CommandMetric {
     ProverCommandSearchCredentialsForProofReq,
     ProverCommandFetchCredentialForProofReq,
     ProverCommandCloseCredentialsSearchForProofReq,
+    ProverCommandSelectCredentialsForProofReq,
     ProverCommandCreateProof,
     ProverCommandCreateRevocationState,
     ProverCommandUpdateRevocationState,
     // VerifierCommand
     VerifierCommandVerifyProof,
+    VerifierCommandVerifyProofWithReport,
     VerifierCommandGenerateNonce,
     // AnoncredsCommand
     AnoncredsCommandToUnqualified,
+    AnoncredsCommandEncodeCredentialValues,
     // BlobStorage
     BlobStorageCommandOpenReader,
     BlobStorageCommandOpenWriter,
@@ -380,6 +453,9 @@ pub enum CommandMetric {
     CryptoCommandAnonymousDecrypt,
     CryptoCommandPackMessage,
     CryptoCommandUnpackMessage,
+    CryptoCommandPackMessageV2,
+    CryptoCommandUnpackMessageV2,
+    CryptoCommandRegisterKeyManager,
     LedgerCommandSignAndSubmitRequest,
     // LedgerCommand
     LedgerCommandSubmitRequest,
@@ -422,6 +498,25 @@ pub enum CommandMetric {
     LedgerCommandGetSchemaContinue,
     LedgerCommandGetCredDef,
     LedgerCommandGetCredDefContinue,
+    LedgerCommandGetNym,
+    LedgerCommandGetNymContinue,
+    LedgerCommandGetAttrib,
+    LedgerCommandGetAttribContinue,
+    LedgerCommandGetRevocRegDef,
+    LedgerCommandGetRevocRegDefContinue,
+    LedgerCommandGetRevocRegDelta,
+    LedgerCommandGetRevocRegDeltaContinue,
+    LedgerCommandResolve,
+    LedgerCommandResolveContinue,
+    LedgerCommandDownloadLedgerTxns,
+    LedgerCommandDownloadLedgerTxnsContinue,
+    LedgerCommandOpenLedgerTxnsSearch,
+    LedgerCommandOpenLedgerTxnsSearchContinue,
+    LedgerCommandFetchLedgerTxnsSearchNextBatch,
+    LedgerCommandCloseLedgerTxnsSearch,
+    LedgerCommandCreatePoolSnapshot,
+    LedgerCommandCreatePoolSnapshotContinue,
+    LedgerCommandCreatePoolSnapshotSynced,
     LedgerCommandBuildTxnAuthorAgreementRequest,
     LedgerCommandBuildDisableAllTxnAuthorAgreementsRequest,
     LedgerCommandBuildGetTxnAuthorAgreementRequest,
@@ -442,6 +537,8 @@ pub enum CommandMetric {
     PoolCommandRefresh,
     PoolCommandRefreshAck,
     PoolCommandSetProtocolVersion,
+    PoolCommandImportSnapshot,
+    PoolCommandGetStatus,
     // DidCommand
     DidCommandCreateAndStoreMyDid,
     DidCommandReplaceKeysStart,
@@ -461,6 +558,9 @@ pub enum CommandMetric {
     DidCommandQualifyDid,
     // WalletCommand
     WalletCommandRegisterWalletType,
+    WalletCommandRegisterWalletStorageTransactions,
+    WalletCommandRegisterWalletStorageList,
+    WalletCommandList,
     WalletCommandCreate,
     WalletCommandCreateContinue,
     WalletCommandOpen,
@@ -470,8 +570,16 @@ pub enum CommandMetric {
     WalletCommandDeleteContinue,
     WalletCommandExport,
     WalletCommandExportContinue,
+    WalletCommandRekey,
+    WalletCommandRekeyContinue,
     WalletCommandImport,
     WalletCommandImportContinue,
+    WalletCommandCheck,
+    WalletCommandBeginTransaction,
+    WalletCommandCommitTransaction,
+    WalletCommandRollbackTransaction,
+    WalletCommandSubscribeEvents,
+    WalletCommandUnsubscribeEvents,
     WalletCommandGenerateKey,
     WalletCommandDeriveKey,
     // PairwiseCommand
@@ -531,8 +639,20 @@ pub enum CommandMetric {
     CacheCommandGetSchemaContinue,
     CacheCommandGetCredDef,
     CacheCommandGetCredDefContinue,
+    CacheCommandGetNym,
+    CacheCommandGetNymContinue,
+    CacheCommandGetAttrib,
+    CacheCommandGetAttribContinue,
+    CacheCommandGetRevocRegDef,
+    CacheCommandGetRevocRegDefContinue,
+    CacheCommandGetRevocRegDelta,
+    CacheCommandGetRevocRegDeltaContinue,
     CacheCommandPurgeSchemaCache,
     CacheCommandPurgeCredDefCache,
+    CacheCommandPurgeNymCache,
+    CacheCommandPurgeAttribCache,
+    CacheCommandPurgeRevocRegDefCache,
+    CacheCommandPurgeRevocRegDeltaCache,
     // MetricsCommand
     MetricsCommandCollectMetrics,
     // Exit
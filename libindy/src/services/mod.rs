@@ -4,4 +4,5 @@ pub mod crypto;
 pub mod ledger;
 pub mod payments;
 pub mod pool;
-pub mod metrics;
\ No newline at end of file
+pub mod metrics;
+pub mod cache;
\ No newline at end of file
@@ -29,20 +29,32 @@ pub fn build_catchup_req(merkle: &MerkleTree, target_mt_size: usize) -> IndyResu
     let seq_no_start = merkle.count() + 1;
     let seq_no_end = target_mt_size;
 
+    let req_id = format!("{}{}", seq_no_start, seq_no_end);
+
+    let req_json = _build_catchup_req(0, seq_no_start, seq_no_end, target_mt_size)?;
+
+    Ok(Some((req_id, req_json)))
+}
+
+/// Builds CATCHUP_REQ for next part of the ledger with given id.
+/// Nodes reply with transactions up to `seq_no_end` and consistency proof of them with `target_mt_size` ledger.
+pub fn build_ledger_catchup_req(ledger_id: u8, merkle: &MerkleTree, seq_no_end: usize, target_mt_size: usize) -> IndyResult<String> {
+    _build_catchup_req(ledger_id as usize, merkle.count() + 1, seq_no_end, target_mt_size)
+}
+
+fn _build_catchup_req(ledger_id: usize, seq_no_start: usize, seq_no_end: usize, catchup_till: usize) -> IndyResult<String> {
     let cr = CatchupReq {
-        ledgerId: 0,
+        ledgerId: ledger_id,
         seqNoStart: seq_no_start,
         seqNoEnd: seq_no_end,
-        catchupTill: target_mt_size,
+        catchupTill: catchup_till,
     };
 
-    let req_id = format!("{}{}", seq_no_start, seq_no_end);
-
     let req_json = serde_json::to_string(&Message::CatchupReq(cr))
         .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CatchupRequest")?;
 
     trace!("catchup_req msg: {:?}", req_json);
-    Ok(Some((req_id, req_json)))
+    Ok(req_json)
 }
 
 pub fn check_nodes_responses_on_status(nodes_votes: &HashMap<(String, usize, Option<Vec<String>>), HashSet<String>>,
@@ -86,6 +98,46 @@ pub fn check_nodes_responses_on_status(nodes_votes: &HashMap<(String, usize, Opt
     Ok(CatchupProgress::InProgress)
 }
 
+/// Looks for the target of local ledger replica sync.
+///
+/// In contrast to pool ledger catchup the replica is dropped only if it isn't consistent with the ledger,
+/// lack of consensus is returned as an error.
+pub fn check_nodes_responses_on_ledger_status(nodes_votes: &HashMap<(String, usize, Option<Vec<String>>), HashSet<String>>,
+                                              merkle_tree: &MerkleTree,
+                                              node_cnt: usize,
+                                              f: usize,
+                                              pool_name: &str,
+                                              ledger_id: u8) -> IndyResult<CatchupProgress> {
+    let most_popular_not_timeout =
+        nodes_votes
+            .iter()
+            .filter(|((key, _, _), _)| key != "timeout")
+            .max_by_key(|(_, nodes)| nodes.len());
+
+    let votes_cnt = most_popular_not_timeout.map(|(_, nodes)| nodes.len()).unwrap_or(0);
+
+    if let Some((most_popular_not_timeout_vote, _)) = most_popular_not_timeout {
+        if votes_cnt == f + 1 {
+            return _try_to_catch_up(most_popular_not_timeout_vote, merkle_tree).or_else(|err| {
+                if merkle_tree.count() == 0 {
+                    return Err(err);
+                }
+                merkle_tree_factory::drop_replica(pool_name, ledger_id)?;
+                Ok(CatchupProgress::Restart(MerkleTree::from_vec(Vec::new())?))
+            });
+        }
+    }
+
+    let reps_cnt: usize = nodes_votes.values().map(HashSet::len).sum();
+
+    if votes_cnt + node_cnt.saturating_sub(reps_cnt) < f + 1 {
+        //TODO: maybe we should change the error, but it was made to escape changing of ErrorCode returned to client
+        Err(err_msg(IndyErrorKind::PoolTimeout, "No consensus possible"))
+    } else {
+        Ok(CatchupProgress::InProgress)
+    }
+}
+
 fn _if_consensus_reachable(nodes_votes: &HashMap<(String, usize, Option<Vec<String>>), HashSet<String>>,
                            node_cnt: usize,
                            votes_cnt: usize,
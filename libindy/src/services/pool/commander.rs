@@ -5,7 +5,7 @@ use super::zmq;
 
 use byteorder::{ByteOrder, LittleEndian};
 use indy_api_types::INVALID_COMMAND_HANDLE;
-use crate::services::pool::{COMMAND_CONNECT, COMMAND_EXIT, COMMAND_REFRESH};
+use crate::services::pool::{COMMAND_CONNECT, COMMAND_EXIT, COMMAND_REFRESH, COMMAND_SYNC_LEDGER};
 
 pub struct Commander {
     cmd_socket: zmq::Socket,
@@ -39,6 +39,12 @@ impl Commander {
             Some(PoolEvent::Refresh(id))
         } else if COMMAND_CONNECT.eq(cmd_s.as_str()) {
             Some(PoolEvent::CheckCache(id))
+        } else if COMMAND_SYNC_LEDGER.eq(cmd_s.as_str()) {
+            let ledger_id = LittleEndian::read_i32(cmd_parts[2].as_slice()) as u8;
+            let till = LittleEndian::read_i32(cmd_parts[3].as_slice());
+            let till = if till == -1 { None } else { Some(till as usize) };
+
+            Some(PoolEvent::SyncLedger(id, ledger_id, till))
         } else {
             let timeout = LittleEndian::read_i32(cmd_parts[2].as_slice());
             let timeout = if timeout == -1 { None } else { Some(timeout) };
@@ -65,7 +71,7 @@ mod commander_tests {
     use super::*;
     use indy_api_types::{CommandHandle};
     use indy_utils::next_command_handle;
-    use crate::services::pool::{COMMAND_REFRESH, COMMAND_EXIT, pool_create_pair_of_sockets};
+    use crate::services::pool::{COMMAND_REFRESH, COMMAND_EXIT, COMMAND_SYNC_LEDGER, pool_create_pair_of_sockets};
 
     fn new_commander() -> Commander {
         let zmq_ctx = zmq::Context::new();
@@ -156,4 +162,20 @@ mod commander_tests {
                       msg_, msg);
     }
 
+    #[test]
+    pub fn commander_fetch_sync_ledger_event_works() {
+        let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("sync_ledger");
+
+        let cmd = Commander::new(recv_cmd_sock);
+
+        let cmd_id: CommandHandle = next_command_handle();
+        let mut buf = [0u8; 4];
+        LittleEndian::write_i32(&mut buf, cmd_id);
+        let mut buf_ledger_id = [0u8; 4];
+        LittleEndian::write_i32(&mut buf_ledger_id, 1);
+        let mut buf_till = [0u8; 4];
+        LittleEndian::write_i32(&mut buf_till, 10);
+        send_cmd_sock.send_multipart(&[COMMAND_SYNC_LEDGER.as_bytes(), &buf, &buf_ledger_id, &buf_till], zmq::DONTWAIT).expect("FIXME");
+        assert_match!(Some(PoolEvent::SyncLedger(cmd_id_, 1, Some(10))), cmd.fetch_events(), cmd_id_, cmd_id);
+    }
 }
\ No newline at end of file
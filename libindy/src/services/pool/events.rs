@@ -72,6 +72,7 @@ pub enum NetworkerEvent {
 pub const COMMAND_EXIT : &str = "exit";
 pub const COMMAND_CONNECT : &str = "connect";
 pub const COMMAND_REFRESH : &str = "refresh";
+pub const COMMAND_SYNC_LEDGER : &str = "sync_ledger";
 
 #[derive(Clone, Debug)]
 pub enum PoolEvent {
@@ -112,6 +113,11 @@ pub enum PoolEvent {
         String, //req_id
         String, //node alias
     ),
+    SyncLedger(
+        CommandHandle,
+        u8, // ledger id
+        Option<usize>, // seq no to sync till
+    ),
 }
 
 #[derive(Clone, Debug)]
@@ -178,12 +184,22 @@ pub enum RequestEvent {
         String, //node alias
         String, //req_id
     ),
+    SyncLedger(
+        u8, // ledger id
+        Option<usize>, // seq no to sync till
+    ),
     PoolLedgerTxns,
     Ping,
     Pong,
     Terminate,
 }
 
+/// Catchup messages don't contain request id, so during local ledger replica sync
+/// all of them are routed to the single request handler of the ledger.
+pub fn ledger_sync_req_id(ledger_id: usize) -> String {
+    format!("ledger_sync_{}", ledger_id)
+}
+
 impl RequestEvent {
     pub fn get_req_id(&self) -> String {
         match *self {
@@ -194,6 +210,10 @@ impl RequestEvent {
             RequestEvent::ReqACK(_, _, _, ref id) => id.to_string(),
             RequestEvent::ReqNACK(_, _, _, ref id) => id.to_string(),
             RequestEvent::Reject(_, _, _, ref id) => id.to_string(),
+            RequestEvent::SyncLedger(ledger_id, _) => ledger_sync_req_id(ledger_id as usize),
+            RequestEvent::LedgerStatus(ref ls, Some(_), _) => ledger_sync_req_id(ls.ledgerId as usize),
+            RequestEvent::ConsistencyProof(ref cp, _) => ledger_sync_req_id(cp.ledgerId),
+            RequestEvent::CatchupRep(ref cr, _) => ledger_sync_req_id(cr.ledgerId),
             _ => "".to_string()
         }
     }
@@ -256,6 +276,7 @@ impl Into<Option<RequestEvent>> for PoolEvent {
                 }
             }
             PoolEvent::Timeout(req_id, node_alias) => Some(RequestEvent::Timeout(req_id, node_alias)),
+            PoolEvent::SyncLedger(_, ledger_id, till) => Some(RequestEvent::SyncLedger(ledger_id, till)),
             _ => None
         }
     }
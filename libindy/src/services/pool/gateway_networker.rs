@@ -0,0 +1,497 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration as StdDuration;
+
+use rand::thread_rng;
+use rand::prelude::SliceRandom;
+use serde_json::Value;
+use threadpool::ThreadPool;
+use time::Tm;
+use tungstenite::{Message as WsMessage, WebSocket};
+use tungstenite::client::AutoStream;
+use tungstenite::stream::Stream as WsStream;
+
+use indy_api_types::errors::prelude::*;
+use crate::domain::pool::{GatewayConfig, PoolTransport};
+use crate::services::pool::events::*;
+use crate::services::pool::networker::Networker;
+use crate::services::pool::status;
+use crate::services::pool::types::*;
+use indy_utils::sequence;
+
+use super::time::Duration;
+
+use super::zmq::PollItem;
+use super::zmq::Socket as ZSocket;
+
+const WS_READ_TIMEOUT_MS: u64 = 100;
+const HTTP_WORKERS_COUNT: usize = 4;
+// Requests over this limit are dropped and resent by timeout as for unreachable node
+const HTTP_QUEUE_LIMIT: usize = 256;
+
+enum GatewayKind {
+    Http(ureq::Agent),
+    Ws,
+}
+
+/// Networker that talks to node-side gateways over HTTP(S) or WebSocket instead of CurveZMQ.
+///
+/// Gateway replies are received by worker threads and forwarded into inproc zmq socket,
+/// so the pool thread polls them the same way as ZMQ node sockets. HTTP requests are
+/// processed by fixed pool of workers, WebSocket connection has single worker per node.
+pub struct GatewayNetworker {
+    kind: GatewayKind,
+    http_workers: ThreadPool,
+    gateways: HashMap<String, String>,
+    nodes: Vec<RemoteNode>,
+    preordered_nodes: Vec<String>,
+    ctx: zmq::Context,
+    replies: ZSocket,
+    replies_addr: String,
+    ws_connections: RefCell<HashMap<String, Sender<String>>>,
+    resend: RefCell<HashMap<String, (usize, String)>>,
+    timeouts: RefCell<HashMap<(String, String), Tm>>,
+}
+
+impl Networker for GatewayNetworker {
+    fn new(_active_timeout: i64, _conn_limit: usize, preordered_nodes: Vec<String>, transport: PoolTransport) -> IndyResult<Self> {
+        let (kind, config) = match transport {
+            PoolTransport::Http(config) => (GatewayKind::Http(_http_agent(&config)), config),
+            PoolTransport::Ws(config) => (GatewayKind::Ws, config),
+            PoolTransport::Zmq => return Err(err_msg(IndyErrorKind::InvalidStructure, "Gateway networker requires http or ws pool transport")),
+        };
+
+        let ctx = zmq::Context::new();
+        let replies_addr = format!("inproc://gateway-networker-{}", sequence::get_next_id());
+        let replies = ctx.socket(zmq::SocketType::PULL)?;
+        replies.bind(&replies_addr)?;
+
+        Ok(GatewayNetworker {
+            kind,
+            http_workers: ThreadPool::with_name("gateway-http".to_string(), HTTP_WORKERS_COUNT),
+            gateways: config.gateways,
+            nodes: Vec::new(),
+            preordered_nodes,
+            ctx,
+            replies,
+            replies_addr,
+            ws_connections: RefCell::new(HashMap::new()),
+            resend: RefCell::new(HashMap::new()),
+            timeouts: RefCell::new(HashMap::new()),
+        })
+    }
+
+    fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent> {
+        let mut events = Vec::new();
+
+        if !poll_items.first().map(PollItem::is_readable).unwrap_or(false) {
+            return events;
+        }
+
+        while let Ok(parts) = self.replies.recv_multipart(zmq::DONTWAIT) {
+            match parts.as_slice() {
+                [node_alias, reply] => {
+                    match (String::from_utf8(node_alias.to_vec()), String::from_utf8(reply.to_vec())) {
+                        (Ok(node_alias), Ok(reply)) => events.push(PoolEvent::NodeReply(reply, node_alias)),
+                        _ => warn!("Gateway reply isn't valid utf8 string"),
+                    }
+                }
+                _ => warn!("Unexpected gateway reply parts count: {}", parts.len()),
+            }
+        }
+
+        events
+    }
+
+    fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent> {
+        match pe {
+            Some(NetworkerEvent::SendOneRequest(msg, req_id, timeout)) => {
+                self._send_msg_to_one_node(0, req_id.clone(), msg.clone(), timeout);
+                self.resend.borrow_mut().insert(req_id, (0, msg));
+            }
+            Some(NetworkerEvent::SendAllRequest(msg, req_id, timeout, nodes_to_send)) => {
+                for idx in 0..self.nodes.len() {
+                    if nodes_to_send.as_ref().map(|nodes| nodes.contains(&self.nodes[idx].name)).unwrap_or(true) {
+                        self._send_msg_to_one_node(idx, req_id.clone(), msg.clone(), timeout);
+                    }
+                }
+            }
+            Some(NetworkerEvent::Resend(req_id, timeout)) => {
+                let nodes_cnt = self.nodes.len();
+                let resend = if let Some(&mut (ref mut cnt, ref req)) = self.resend.borrow_mut().get_mut(&req_id) {
+                    *cnt += 1;
+                    Some((*cnt % ::std::cmp::max(nodes_cnt, 1), req.clone()))
+                } else {
+                    error!("Unknown req_id for resending {}", req_id);
+                    None
+                };
+                if let Some((idx, req)) = resend {
+                    self._send_msg_to_one_node(idx, req_id, req, timeout);
+                }
+            }
+            Some(NetworkerEvent::NodesStateUpdated(nodes)) => {
+                trace!("GatewayNetworker::process_event: nodes_updated {:?}", nodes);
+                self._update_nodes(nodes);
+            }
+            Some(NetworkerEvent::ExtendTimeout(req_id, node_alias, timeout)) => {
+                if let Some(value) = self.timeouts.borrow_mut().get_mut(&(req_id.clone(), node_alias.clone())) {
+                    *value = time::now() + Duration::seconds(timeout);
+                } else {
+                    debug!("late REQACK for req_id {}, node {}", req_id, node_alias);
+                }
+            }
+            Some(NetworkerEvent::CleanTimeout(req_id, node_alias)) => {
+                match node_alias {
+                    Some(node_alias) => {
+                        self.timeouts.borrow_mut().remove(&(req_id, node_alias));
+                    }
+                    None => {
+                        status::request_finished(&req_id);
+                        self.timeouts.borrow_mut().retain(|(req_id_timeout, _), _| *req_id_timeout != req_id);
+                        self.resend.borrow_mut().remove(&req_id);
+                    }
+                }
+            }
+            _ => ()
+        }
+        None
+    }
+
+    fn get_timeout(&self) -> ((String, String), i64) {
+        self.timeouts.borrow().iter()
+            .map(|(key, value)| (key.clone(), (*value - time::now()).num_milliseconds()))
+            .min_by(|&(_, val1), &(_, val2)| val1.cmp(&val2))
+            .unwrap_or((("".to_string(), "".to_string()), ::std::i64::MAX))
+    }
+
+    fn get_poll_items(&self) -> Vec<PollItem> {
+        vec![self.replies.as_poll_item(zmq::POLLIN)]
+    }
+}
+
+impl GatewayNetworker {
+    fn _update_nodes(&mut self, nodes: Vec<RemoteNode>) {
+        let gateways = &self.gateways;
+
+        let (mut nodes, skipped): (Vec<RemoteNode>, Vec<RemoteNode>) = nodes.into_iter()
+            .partition(|node| gateways.contains_key(&node.name));
+
+        for node in skipped {
+            warn!("Gateway isn't configured for node {}, node will be skipped", node.name);
+        }
+
+        nodes.shuffle(&mut thread_rng());
+
+        if !self.preordered_nodes.is_empty() {
+            let preordered_nodes = &self.preordered_nodes;
+            nodes.sort_by_key(|node: &RemoteNode| -> usize {
+                preordered_nodes.iter()
+                    .position(|name| node.name.eq(name))
+                    .unwrap_or(usize::max_value())
+            });
+        }
+
+        self.nodes = nodes;
+    }
+
+    fn _send_msg_to_one_node(&self, idx: usize, req_id: String, req: String, timeout: i64) {
+        trace!("_send_msg_to_one_node >> idx {}, req_id {}, req {}", idx, req_id, req);
+
+        let node_alias = match self.nodes.get(idx) {
+            Some(node) => node.name.clone(),
+            None => {
+                error!("There are no nodes reachable through gateways to send request {}", req_id);
+                return;
+            }
+        };
+
+        let url = self.gateways[&node_alias].clone();
+
+        let res = match self.kind {
+            GatewayKind::Http(ref agent) => self._send_http(agent.clone(), url, node_alias.clone(), req, timeout),
+            GatewayKind::Ws => self._send_ws(url, node_alias.clone(), req),
+        };
+
+        if let Err(err) = res {
+            warn!("Can't send request {} to gateway of node {}: {:?}", req_id, node_alias, err);
+        }
+
+        status::request_sent(&req_id, &node_alias);
+        self.timeouts.borrow_mut().insert((req_id, node_alias), time::now() + Duration::seconds(timeout));
+
+        trace!("_send_msg_to_one_node <<");
+    }
+
+    fn _send_http(&self, agent: ureq::Agent, url: String, node_alias: String, req: String, timeout: i64) -> IndyResult<()> {
+        if self.http_workers.queued_count() >= HTTP_QUEUE_LIMIT {
+            return Err(err_msg(IndyErrorKind::IOError, "Gateway request queue is full"));
+        }
+
+        let replies = self._replies_sender()?;
+
+        self.http_workers.execute(move || {
+            let res = agent.post(&url)
+                .timeout(StdDuration::from_secs(timeout as u64))
+                .set("Content-Type", "application/json")
+                .send_string(&req)
+                .map_err(|err| err.to_string())
+                .and_then(|response| response.into_string().map_err(|err| err.to_string()));
+
+            match res {
+                Ok(body) => {
+                    for reply in _parse_gateway_replies(&body) {
+                        _forward_reply(&replies, &node_alias, &reply);
+                    }
+                }
+                Err(err) => warn!("Gateway request to node {} failed: {}", node_alias, err),
+            }
+        });
+
+        Ok(())
+    }
+
+    fn _send_ws(&self, url: String, node_alias: String, req: String) -> IndyResult<()> {
+        let mut connections = self.ws_connections.borrow_mut();
+
+        // connection worker finishes on any websocket error, so reconnect if request can't be passed to it
+        let req = match connections.get(&node_alias) {
+            Some(sender) => match sender.send(req) {
+                Ok(()) => return Ok(()),
+                Err(mpsc::SendError(req)) => req,
+            },
+            None => req,
+        };
+
+        let replies = self._replies_sender()?;
+        let (sender, receiver) = mpsc::channel();
+
+        let worker_alias = node_alias.clone();
+        thread::spawn(move || _ws_worker(url, worker_alias, receiver, replies));
+
+        sender.send(req)
+            .map_err(|_| err_msg(IndyErrorKind::IOError, "Gateway connection worker isn't available"))?;
+
+        connections.insert(node_alias, sender);
+        Ok(())
+    }
+
+    fn _replies_sender(&self) -> IndyResult<ZSocket> {
+        let s = self.ctx.socket(zmq::SocketType::PUSH)?;
+        s.set_linger(0)?;
+        s.connect(&self.replies_addr)?;
+        Ok(s)
+    }
+}
+
+fn _http_agent(config: &GatewayConfig) -> ureq::Agent {
+    let mut builder = ureq::AgentBuilder::new();
+
+    if let Some(ref proxy) = config.proxy {
+        match ureq::Proxy::new(proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(err) => warn!("Invalid gateway proxy {}, requests will be sent directly: {}", proxy, err),
+        }
+    }
+
+    builder.build()
+}
+
+fn _ws_worker(url: String, node_alias: String, requests: Receiver<String>, replies: ZSocket) {
+    let mut socket = match tungstenite::connect(url.as_str()) {
+        Ok((socket, _)) => socket,
+        Err(err) => {
+            warn!("Can't connect to gateway of node {}: {}", node_alias, err);
+            return;
+        }
+    };
+
+    if let Err(err) = _set_read_timeout(&socket, StdDuration::from_millis(WS_READ_TIMEOUT_MS)) {
+        warn!("Can't set read timeout for gateway connection of node {}: {}", node_alias, err);
+        return;
+    }
+
+    loop {
+        loop {
+            match requests.try_recv() {
+                Ok(req) => {
+                    if let Err(err) = socket.write_message(WsMessage::Text(req)) {
+                        warn!("Can't send request to gateway of node {}: {}", node_alias, err);
+                        return;
+                    }
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    let _ = socket.close(None);
+                    return;
+                }
+            }
+        }
+
+        match socket.read_message() {
+            Ok(WsMessage::Text(reply)) => _forward_reply(&replies, &node_alias, &reply),
+            Ok(WsMessage::Binary(reply)) => match String::from_utf8(reply) {
+                Ok(reply) => _forward_reply(&replies, &node_alias, &reply),
+                Err(_) => warn!("Gateway reply of node {} isn't valid utf8 string", node_alias),
+            },
+            Ok(_) => (),
+            Err(tungstenite::Error::Io(ref err)) if err.kind() == io::ErrorKind::WouldBlock || err.kind() == io::ErrorKind::TimedOut => (),
+            Err(err) => {
+                warn!("Gateway connection of node {} is closed: {}", node_alias, err);
+                return;
+            }
+        }
+    }
+}
+
+fn _set_read_timeout(socket: &WebSocket<AutoStream>, timeout: StdDuration) -> io::Result<()> {
+    match socket.get_ref() {
+        WsStream::Plain(stream) => stream.set_read_timeout(Some(timeout)),
+        WsStream::Tls(stream) => stream.get_ref().set_read_timeout(Some(timeout)),
+    }
+}
+
+// Gateway answers HTTP request with json array of node messages, string or object each.
+fn _parse_gateway_replies(body: &str) -> Vec<String> {
+    if body.trim().is_empty() {
+        return Vec::new();
+    }
+
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Array(replies)) => replies.into_iter()
+            .map(|reply| match reply {
+                Value::String(reply) => reply,
+                reply => reply.to_string(),
+            })
+            .collect(),
+        _ => vec![body.to_string()],
+    }
+}
+
+fn _forward_reply(replies: &ZSocket, node_alias: &str, reply: &str) {
+    if let Err(err) = replies.send_multipart(vec![node_alias.as_bytes(), reply.as_bytes()], zmq::DONTWAIT) {
+        warn!("Can't forward gateway reply of node {}: {}", node_alias, err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use super::*;
+
+    const REQ_ID: &str = "1";
+    const MESSAGE: &str = r#"{"reqId":1}"#;
+    const NODE_NAME: &str = "n1";
+
+    fn _remote_node(name: &str) -> RemoteNode {
+        RemoteNode {
+            name: name.to_string(),
+            public_key: Vec::new(),
+            zaddr: String::new(),
+            is_blacklisted: false,
+        }
+    }
+
+    fn _transport(url: &str) -> PoolTransport {
+        let mut gateways = HashMap::new();
+        gateways.insert(NODE_NAME.to_string(), url.to_string());
+        PoolTransport::Http(GatewayConfig { gateways, proxy: None })
+    }
+
+    // Answers single HTTP request with given body and returns gateway url
+    fn _gateway(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !String::from_utf8_lossy(&request).contains(MESSAGE) {
+                let read = stream.read(&mut buf).unwrap();
+                if read == 0 { break; }
+                request.extend_from_slice(&buf[..read]);
+            }
+
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+        });
+
+        url
+    }
+
+    #[test]
+    fn gateway_networker_new_works() {
+        let networker = GatewayNetworker::new(0, 0, vec![], _transport("http://127.0.0.1:1/")).unwrap();
+        assert!(networker.nodes.is_empty());
+        assert_eq!(1, networker.get_poll_items().len());
+    }
+
+    #[test]
+    fn gateway_networker_new_works_for_zmq_transport() {
+        let res = GatewayNetworker::new(0, 0, vec![], PoolTransport::Zmq);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn gateway_networker_process_nodes_updated_skips_nodes_without_gateway() {
+        let mut networker = GatewayNetworker::new(0, 0, vec![], _transport("http://127.0.0.1:1/")).unwrap();
+        networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![_remote_node(NODE_NAME), _remote_node("n2")])));
+
+        assert_eq!(vec![_remote_node(NODE_NAME)], networker.nodes);
+    }
+
+    #[test]
+    fn gateway_networker_send_request_over_http_works() {
+        let url = _gateway(r#"["{\"op\":\"REQACK\",\"reqId\":1}",{"op":"REPLY","result":{"reqId":1}}]"#);
+
+        let mut networker = GatewayNetworker::new(0, 0, vec![], _transport(&url)).unwrap();
+        networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![_remote_node(NODE_NAME)])));
+        networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), 10)));
+
+        let mut events = Vec::new();
+        while events.len() < 2 {
+            let mut poll_items = networker.get_poll_items();
+            assert_eq!(1, zmq::poll(&mut poll_items, 5000).unwrap());
+            events.extend(networker.fetch_events(&poll_items));
+        }
+
+        let replies: Vec<(String, String)> = events.into_iter()
+            .map(|event| match event {
+                PoolEvent::NodeReply(reply, node_alias) => (reply, node_alias),
+                _ => panic!("Unexpected event"),
+            })
+            .collect();
+
+        assert_eq!((r#"{"op":"REQACK","reqId":1}"#.to_string(), NODE_NAME.to_string()), replies[0]);
+        assert_eq!((r#"{"op":"REPLY","result":{"reqId":1}}"#.to_string(), NODE_NAME.to_string()), replies[1]);
+    }
+
+    #[test]
+    fn gateway_networker_get_timeout_works() {
+        let mut networker = GatewayNetworker::new(0, 0, vec![], _transport("http://127.0.0.1:1/")).unwrap();
+        assert_eq!(::std::i64::MAX, networker.get_timeout().1);
+
+        networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![_remote_node(NODE_NAME)])));
+        networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), 10)));
+
+        let ((req_id, node_alias), timeout) = networker.get_timeout();
+        assert_eq!(REQ_ID, req_id);
+        assert_eq!(NODE_NAME, node_alias);
+        assert!(timeout > 0 && timeout <= 10_000);
+
+        networker.process_event(Some(NetworkerEvent::CleanTimeout(REQ_ID.to_string(), None)));
+        assert_eq!(::std::i64::MAX, networker.get_timeout().1);
+    }
+
+    #[test]
+    fn parse_gateway_replies_works() {
+        assert_eq!(Vec::<String>::new(), _parse_gateway_replies(""));
+        assert_eq!(vec!["po".to_string()], _parse_gateway_replies("po"));
+        assert_eq!(vec![r#"{"op":"REPLY"}"#.to_string()], _parse_gateway_replies(r#"[{"op":"REPLY"}]"#));
+    }
+}
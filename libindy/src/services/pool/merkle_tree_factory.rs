@@ -1,6 +1,6 @@
 use std::{fs, io};
 use std::collections::HashMap;
-use std::io::{BufRead, Read, Write};
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
 use std::path::PathBuf;
 
 use serde_json;
@@ -15,6 +15,8 @@ use crate::utils::environment;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 const POOL_EXT : &str = "txn";
+const REPLICA_PREFIX: &str = "ledger_";
+const REPLICA_EXT: &str = "btxn";
 
 pub fn create(pool_name: &str) -> IndyResult<MerkleTree> {
     let mut p = environment::pool_path(pool_name);
@@ -60,28 +62,43 @@ fn _from_cache(file_name: &PathBuf) -> IndyResult<MerkleTree> {
 
     trace!("Start recover from cache");
 
-    loop {
-        let bytes = match f.read_u64::<LittleEndian>() {
-            Ok(bytes) => bytes,
-            Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
-            Err(e) => return Err(e.to_indy(IndyErrorKind::IOError, "Can't read from pool ledger cache file"))
-        };
+    while let Some(buf) = _read_record(&mut f)? {
+        mt.append(buf)?;
+    }
 
-        trace!("bytes: {:?}", bytes);
-        let mut buf = vec![0; bytes as usize];
+    Ok(mt)
+}
 
-        match f.read_exact(buf.as_mut()) {
-            Ok(()) => (),
-            Err(e) => match e.kind() {
-                    io::ErrorKind::UnexpectedEof => return Err(e.to_indy(IndyErrorKind::InvalidState, "Malformed pool ledger cache file")),
-                    _  => return Err(e.to_indy(IndyErrorKind::IOError, "Can't read from pool ledger cache file"))
-            }
+fn _read_record(f: &mut fs::File) -> IndyResult<Option<Vec<u8>>> {
+    let bytes = match f.read_u64::<LittleEndian>() {
+        Ok(bytes) => bytes,
+        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.to_indy(IndyErrorKind::IOError, "Can't read from pool ledger cache file"))
+    };
+
+    trace!("bytes: {:?}", bytes);
+    let mut buf = vec![0; bytes as usize];
+
+    match f.read_exact(buf.as_mut()) {
+        Ok(()) => Ok(Some(buf)),
+        Err(e) => match e.kind() {
+            io::ErrorKind::UnexpectedEof => Err(e.to_indy(IndyErrorKind::InvalidState, "Malformed pool ledger cache file")),
+            _ => Err(e.to_indy(IndyErrorKind::IOError, "Can't read from pool ledger cache file"))
         }
-
-        mt.append(buf.to_vec())?;
     }
+}
 
-    Ok(mt)
+fn _skip_record(f: &mut fs::File) -> IndyResult<bool> {
+    let bytes = match f.read_u64::<LittleEndian>() {
+        Ok(bytes) => bytes,
+        Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+        Err(e) => return Err(e.to_indy(IndyErrorKind::IOError, "Can't read from pool ledger cache file"))
+    };
+
+    f.seek(SeekFrom::Current(bytes as i64))
+        .to_indy(IndyErrorKind::IOError, "Can't read from pool ledger cache file")?;
+
+    Ok(true)
 }
 
 fn _from_genesis(file_name: &PathBuf) -> IndyResult<MerkleTree> {
@@ -131,6 +148,136 @@ pub fn dump_new_txns(pool_name: &str, txns: &[Vec<u8>]) -> IndyResult<()> {
     _dump_vec_to_file(txns, &mut file)
 }
 
+/// Restores merkle tree of the local replica of the ledger.
+///
+/// Replica is an append-only copy of the ledger transactions starting from seqNo 1
+/// stored in the same format as pool ledger cache. Only transactions verified against
+/// the ledger merkle root are appended to it.
+pub fn create_replica(pool_name: &str, ledger_id: u8) -> IndyResult<MerkleTree> {
+    let p = get_replica_path(pool_name, ledger_id, false);
+
+    if p.exists() {
+        trace!("Restoring merkle tree from ledger {} replica", ledger_id);
+        _from_cache(&p)
+    } else {
+        MerkleTree::from_vec(Vec::new())
+    }
+}
+
+pub fn drop_replica(pool_name: &str, ledger_id: u8) -> IndyResult<()> {
+    let p = get_replica_path(pool_name, ledger_id, false);
+
+    if p.exists() {
+        warn!("Ledger {} replica is invalid -- dropping it!", ledger_id);
+        fs::remove_file(p)
+            .to_indy(IndyErrorKind::IOError, "Can't drop ledger replica file")?;
+    }
+
+    Ok(())
+}
+
+pub fn dump_replica_txns(pool_name: &str, ledger_id: u8, txns: &[Vec<u8>]) -> IndyResult<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_replica_path(pool_name, ledger_id, true))
+        .to_indy(IndyErrorKind::IOError, "Can't open ledger replica file")?;
+
+    _dump_vec_to_file(txns, &mut file)
+}
+
+pub fn replica_size(pool_name: &str, ledger_id: u8) -> IndyResult<usize> {
+    let p = get_replica_path(pool_name, ledger_id, false);
+
+    if !p.exists() {
+        return Ok(0);
+    }
+
+    let mut f = fs::File::open(p)
+        .to_indy(IndyErrorKind::IOError, "Can't open ledger replica file")?;
+
+    let mut size = 0;
+    while _read_record(&mut f)?.is_some() {
+        size += 1;
+    }
+
+    Ok(size)
+}
+
+/// Reads transactions with seqNo in [from, to] range from the local replica of the ledger.
+pub fn read_replica_txns(pool_name: &str, ledger_id: u8, from: usize, to: usize) -> IndyResult<Vec<SJsonValue>> {
+    open_replica_reader(pool_name, ledger_id, from)?
+        .read((to + 1).saturating_sub(from))
+}
+
+/// Sequential reader of the local replica of the ledger.
+/// Only the current position is kept in memory, transactions are read from the file on demand.
+pub struct ReplicaReader {
+    ledger_id: u8,
+    file: Option<fs::File>,
+    seq_no: usize,
+}
+
+impl ReplicaReader {
+    /// Reads next `count` transactions of the replica.
+    pub fn read(&mut self, count: usize) -> IndyResult<Vec<SJsonValue>> {
+        let mut txns = Vec::with_capacity(count);
+
+        while txns.len() < count {
+            let buf = match self.file {
+                Some(ref mut file) => _read_record(file)?,
+                None => None
+            };
+
+            let buf = buf.ok_or_else(|| self._replica_too_short())?;
+            self.seq_no += 1;
+
+            txns.push(rmp_serde::decode::from_slice(&buf)
+                .to_indy(IndyErrorKind::InvalidState, "Ledger replica contains invalid transaction")?);
+        }
+
+        Ok(txns)
+    }
+
+    fn _replica_too_short(&self) -> IndyError {
+        err_msg(IndyErrorKind::InvalidState,
+                format!("Ledger {} replica contains only {} transactions", self.ledger_id, self.seq_no))
+    }
+}
+
+/// Opens the local replica of the ledger to read transactions starting from `from` seqNo.
+pub fn open_replica_reader(pool_name: &str, ledger_id: u8, from: usize) -> IndyResult<ReplicaReader> {
+    let p = get_replica_path(pool_name, ledger_id, false);
+
+    let file = if p.exists() {
+        Some(fs::File::open(p)
+            .to_indy(IndyErrorKind::IOError, "Can't open ledger replica file")?)
+    } else {
+        None
+    };
+
+    let mut reader = ReplicaReader { ledger_id, file, seq_no: 0 };
+
+    while reader.seq_no + 1 < from {
+        let skipped = match reader.file {
+            Some(ref mut file) => _skip_record(file)?,
+            None => false
+        };
+
+        if !skipped {
+            return Err(reader._replica_too_short());
+        }
+
+        reader.seq_no += 1;
+    }
+
+    Ok(reader)
+}
+
+fn get_replica_path(pool_name: &str, ledger_id: u8, create_dir: bool) -> PathBuf {
+    get_pool_stored_path_base(pool_name, create_dir, &format!("{}{}", REPLICA_PREFIX, ledger_id), REPLICA_EXT)
+}
+
 fn _dump_genesis_to_stored(p: &PathBuf, pool_name: &str) -> IndyResult<()> {
     let p_genesis = get_pool_stored_path_base(pool_name, false, pool_name, POOL_EXT);
 
@@ -402,4 +549,50 @@ mod tests {
 
         test::cleanup_storage("pool_worker_build_node_state_works_for_old_txns_format_and_2_protocol_version");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn replica_works() {
+        let pool_name = "merkle_tree_factory_replica_works";
+        test::cleanup_storage(pool_name);
+
+        let txns: Vec<serde_json::Value> = (1..4).map(|seq_no| json!({"txnMetadata": {"seqNo": seq_no}})).collect();
+        let bytes: Vec<Vec<u8>> = txns.iter().map(|txn| rmp_serde::to_vec_named(txn).unwrap()).collect();
+
+        assert_eq!(0, super::create_replica(pool_name, 1).unwrap().count());
+
+        super::dump_replica_txns(pool_name, 1, &bytes[0..2]).unwrap();
+        super::dump_replica_txns(pool_name, 1, &bytes[2..]).unwrap();
+
+        assert_eq!(3, super::create_replica(pool_name, 1).unwrap().count());
+        assert_eq!(3, super::replica_size(pool_name, 1).unwrap());
+        assert_eq!(txns[1..].to_vec(), super::read_replica_txns(pool_name, 1, 2, 3).unwrap());
+        assert_kind!(IndyErrorKind::InvalidState, super::read_replica_txns(pool_name, 1, 2, 4));
+        assert_eq!(0, super::replica_size(pool_name, 2).unwrap());
+
+        super::drop_replica(pool_name, 1).unwrap();
+        assert_eq!(0, super::replica_size(pool_name, 1).unwrap());
+
+        test::cleanup_storage(pool_name);
+    }
+
+    #[test]
+    fn replica_reader_works() {
+        let pool_name = "merkle_tree_factory_replica_reader_works";
+        test::cleanup_storage(pool_name);
+
+        let txns: Vec<serde_json::Value> = (1..6).map(|seq_no| json!({"txnMetadata": {"seqNo": seq_no}})).collect();
+        let bytes: Vec<Vec<u8>> = txns.iter().map(|txn| rmp_serde::to_vec_named(txn).unwrap()).collect();
+
+        super::dump_replica_txns(pool_name, 1, &bytes).unwrap();
+
+        let mut reader = super::open_replica_reader(pool_name, 1, 2).unwrap();
+        assert_eq!(txns[1..3].to_vec(), reader.read(2).unwrap());
+        assert_eq!(txns[3..5].to_vec(), reader.read(2).unwrap());
+        assert_kind!(IndyErrorKind::InvalidState, reader.read(1));
+
+        assert_kind!(IndyErrorKind::InvalidState, super::open_replica_reader(pool_name, 1, 7));
+        assert_kind!(IndyErrorKind::InvalidState, super::open_replica_reader(pool_name, 2, 1).unwrap().read(1));
+
+        test::cleanup_storage(pool_name);
+    }
+}
@@ -8,7 +8,7 @@ extern crate zmq;
 use byteorder::{ByteOrder, LittleEndian};
 use self::zmq::Socket;
 
-use std::{fs, io};
+use std::{cmp, fs, io};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::Write;
@@ -18,8 +18,11 @@ use serde_json;
 use serde::de::DeserializeOwned;
 
 use crate::api::ledger::{CustomFree, CustomTransactionParser};
+use crate::commands::{Command, CommandExecutor};
+use crate::commands::ledger::LedgerCommand;
+use crate::commands::pool::PoolCommand;
 use crate::domain::{
-    pool::{PoolConfig, PoolOpenConfig},
+    pool::{PoolConfig, PoolOpenConfig, SnapshotImportOptions},
     ledger::response::{
         Message,
         Reply,
@@ -27,9 +30,13 @@ use crate::domain::{
     }
 };
 use indy_api_types::errors::*;
+use crate::services::pool::merkle_tree_factory::ReplicaReader;
 use crate::services::pool::pool::{Pool, ZMQPool};
+use crate::services::pool::request_handler::LEDGER_SYNC_BATCH_SIZE;
+use crate::services::pool::snapshot::OfflinePool;
+use crate::services::pool::status::NodeStatus;
 use crate::utils::environment;
-use crate::services::pool::events::{COMMAND_EXIT, COMMAND_CONNECT, COMMAND_REFRESH};
+use crate::services::pool::events::{COMMAND_EXIT, COMMAND_CONNECT, COMMAND_REFRESH, COMMAND_SYNC_LEDGER};
 use indy_api_types::{CommandHandle, PoolHandle};
 use indy_utils::{next_command_handle, next_pool_handle};
 use ursa::bls::VerKey;
@@ -37,10 +44,13 @@ use ursa::bls::VerKey;
 mod catchup;
 mod commander;
 mod events;
+mod gateway_networker;
 mod merkle_tree_factory;
 mod networker;
 mod pool;
 mod request_handler;
+mod snapshot;
+mod status;
 mod state_proof;
 mod types;
 
@@ -50,9 +60,21 @@ lazy_static! {
 
 type Nodes = HashMap<String, Option<VerKey>>;
 
+/// Reader of a range of ledger transactions returning them by batches.
+/// Only the position in the local replica of the ledger is kept in memory.
+pub struct LedgerTxnsReader {
+    pool_handle: PoolHandle,
+    ledger_id: u8,
+    next: usize,
+    to: usize,
+    replica: Option<ReplicaReader>,
+}
+
 pub struct PoolService {
     open_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
     pending_pools: RefCell<HashMap<PoolHandle, ZMQPool>>,
+    offline_pools: RefCell<HashMap<PoolHandle, OfflinePool>>,
+    namespaces: RefCell<HashMap<PoolHandle, String>>,
 }
 
 impl PoolService {
@@ -60,6 +82,8 @@ impl PoolService {
         PoolService {
             open_pools: RefCell::new(HashMap::new()),
             pending_pools: RefCell::new(HashMap::new()),
+            offline_pools: RefCell::new(HashMap::new()),
+            namespaces: RefCell::new(HashMap::new()),
         }
     }
 
@@ -137,6 +161,10 @@ impl PoolService {
             }
         }
 
+        if self.offline_pools.try_borrow()?.values().any(|pool| pool.name.eq(name)) {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Can't delete pool config - pool is open now"));
+        }
+
         let path = environment::pool_path(name);
 
         fs::remove_dir_all(path)
@@ -151,14 +179,38 @@ impl PoolService {
             }
         }
 
+        if self.offline_pools.try_borrow()?.values().any(|pool| pool.name.eq(name)) {
+            return Err(err_msg(IndyErrorKind::InvalidPoolHandle, "Pool with the same name is already opened"));
+        }
+
+        let namespace = _read_pool_namespace(name)?;
+
+        if let Some(ref namespace) = namespace {
+            if self._find_by_namespace(namespace)?.is_some() {
+                return Err(err_msg(IndyErrorKind::InvalidState, format!("Pool with the same namespace {} is already opened", namespace)));
+            }
+        }
+
         let config = config.unwrap_or_default();
 
         let pool_handle: PoolHandle = next_pool_handle();
+
+        if let Some(namespace) = namespace {
+            self.namespaces.try_borrow_mut()?.insert(pool_handle, namespace);
+        }
+
+        if config.offline {
+            let pool = OfflinePool::new(name)?;
+            self.offline_pools.try_borrow_mut()?.insert(pool_handle, pool);
+            _send_pool_command(PoolCommand::OpenAck(pool_handle, pool_handle, Ok(())));
+            return Ok(pool_handle);
+        }
+
         let mut new_pool = Pool::new(name, pool_handle, config);
 
         let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets(&format!("pool_{}", name));
 
-        new_pool.work(recv_cmd_sock);
+        new_pool.work(recv_cmd_sock)?;
         self._send_msg(pool_handle, COMMAND_CONNECT, &send_cmd_sock, None, None)?;
 
         self.pending_pools.try_borrow_mut()?
@@ -167,6 +219,10 @@ impl PoolService {
     }
 
     pub fn add_open_pool(&self, pool_id: PoolHandle) -> IndyResult<PoolHandle> {
+        if self.offline_pools.try_borrow()?.contains_key(&pool_id) {
+            return Ok(pool_id);
+        }
+
         let pool = self.pending_pools.try_borrow_mut()?
             .remove(&pool_id)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {:?}", pool_id)))?;
@@ -182,6 +238,13 @@ impl PoolService {
     }
 
     pub fn send_action(&self, handle: PoolHandle, msg: &str, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<CommandHandle> {
+        if let Some(pool) = self.offline_pools.try_borrow()?.get(&handle) {
+            let cmd_id: CommandHandle = next_command_handle();
+            let res = pool.answer(msg);
+            _send_ledger_command(LedgerCommand::SubmitAck(cmd_id, res));
+            return Ok(cmd_id);
+        }
+
         let pools = self.open_pools.try_borrow()?;
 
         if let Some(ref pool) = pools.get(&handle) {
@@ -217,10 +280,20 @@ impl PoolService {
     pub fn close(&self, handle: PoolHandle) -> IndyResult<CommandHandle> {
         let cmd_id: CommandHandle = next_command_handle();
 
+        self.namespaces.try_borrow_mut()?.remove(&handle);
+
+        if self.offline_pools.try_borrow_mut()?.remove(&handle).is_some() {
+            _send_pool_command(PoolCommand::CloseAck(cmd_id, Ok(())));
+            return Ok(cmd_id);
+        }
+
         let mut pools = self.open_pools.try_borrow_mut()?;
 
         match pools.remove(&handle) {
-            Some(ref pool) => self._send_msg(cmd_id, COMMAND_EXIT, &pool.cmd_socket, None, None)?,
+            Some(ref pool) => {
+                status::clear(pool.pool.get_name());
+                self._send_msg(cmd_id, COMMAND_EXIT, &pool.cmd_socket, None, None)?
+            }
             None => return Err(err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))
         }
 
@@ -228,9 +301,174 @@ impl PoolService {
     }
 
     pub fn refresh(&self, handle: PoolHandle) -> IndyResult<i32> {
+        if let Some(pool) = self.offline_pools.try_borrow_mut()?.get_mut(&handle) {
+            let cmd_id: CommandHandle = next_command_handle();
+            _send_pool_command(PoolCommand::RefreshAck(cmd_id, pool.refresh()));
+            return Ok(cmd_id);
+        }
+
         self.send_action(handle, COMMAND_REFRESH, None, None)
     }
 
+    pub fn get_handle_by_namespace(&self, namespace: &str) -> IndyResult<PoolHandle> {
+        self._find_by_namespace(namespace)?
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("There is no opened pool for namespace {}", namespace)))
+    }
+
+    fn _find_by_namespace(&self, namespace: &str) -> IndyResult<Option<PoolHandle>> {
+        let open_pools = self.open_pools.try_borrow()?;
+        let offline_pools = self.offline_pools.try_borrow()?;
+
+        let handle = self.namespaces.try_borrow()?.iter()
+            .find(|&(handle, ns)| ns == namespace && (open_pools.contains_key(handle) || offline_pools.contains_key(handle)))
+            .map(|(handle, _)| *handle);
+
+        Ok(handle)
+    }
+
+    pub fn get_status(&self, handle: PoolHandle) -> IndyResult<HashMap<String, NodeStatus>> {
+        trace!("PoolService::get_status >> handle: {}", handle);
+
+        if self.offline_pools.try_borrow()?.contains_key(&handle) {
+            return Ok(HashMap::new());
+        }
+
+        let pools = self.open_pools.try_borrow()?;
+
+        let pool = pools.get(&handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))?;
+
+        let res = status::get(pool.pool.get_name());
+
+        trace!("PoolService::get_status << res: {:?}", res);
+        Ok(res)
+    }
+
+    pub fn get_all_status() -> HashMap<String, HashMap<String, NodeStatus>> {
+        status::get_all()
+    }
+
+    pub fn import_snapshot(&self, name: &str, snapshot_path: &str, options: Option<SnapshotImportOptions>) -> IndyResult<()> {
+        trace!("PoolService::import_snapshot {} from {}", name, snapshot_path);
+
+        if self.offline_pools.try_borrow()?.values().any(|pool| pool.name.eq(name)) {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Can't import ledger snapshot - pool is open in offline mode now"));
+        }
+
+        snapshot::import(name, snapshot_path, options.unwrap_or_default())
+    }
+
+    /// Returns name of the pool connected to the nodes. Pools opened in offline mode are refused.
+    pub fn get_online_pool_name(&self, handle: PoolHandle) -> IndyResult<String> {
+        if self.offline_pools.try_borrow()?.contains_key(&handle) {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Pool is opened in offline mode"));
+        }
+
+        let pools = self.open_pools.try_borrow()?;
+
+        let pool = pools.get(&handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))?;
+
+        Ok(pool.pool.get_name().to_string())
+    }
+
+    /// Creates ledger snapshot of the pool from the first `ledger_size` domain ledger txns of the local replica.
+    /// Returns `{"ledgerSize": <snapshot size>, "txnRootHash": <signed txn root hash>}` json.
+    pub fn create_snapshot(&self, handle: PoolHandle, ledger_size: usize, multi_signature: serde_json::Value) -> IndyResult<String> {
+        trace!("PoolService::create_snapshot >> handle: {}, ledger_size: {}", handle, ledger_size);
+
+        let name = self.get_online_pool_name(handle)?;
+
+        let snapshot = snapshot::create(&name, ledger_size, multi_signature)?;
+
+        let res = json!({"ledgerSize": snapshot.txns.len(), "txnRootHash": snapshot.root_hash()?}).to_string();
+
+        trace!("PoolService::create_snapshot << res: {}", res);
+        Ok(res)
+    }
+
+    /// Brings local replica of the ledger up to `till` seqNo (current ledger size if absent).
+    /// Result is delivered as `LedgerCommand::SubmitAck` with `{"ledgerSize": <replica size>}` json.
+    pub fn sync_ledger(&self, handle: PoolHandle, ledger_id: u8, till: Option<usize>) -> IndyResult<CommandHandle> {
+        trace!("PoolService::sync_ledger >> handle: {}, ledger_id: {}, till: {:?}", handle, ledger_id, till);
+
+        let cmd_id: CommandHandle = next_command_handle();
+
+        if let Some(pool) = self.offline_pools.try_borrow()?.get(&handle) {
+            let res = pool.ledger_size(ledger_id)
+                .map(|size| json!({"ledgerSize": size}).to_string());
+            _send_ledger_command(LedgerCommand::SubmitAck(cmd_id, res));
+            return Ok(cmd_id);
+        }
+
+        let pools = self.open_pools.try_borrow()?;
+
+        let pool = pools.get(&handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))?;
+
+        let mut buf = [0u8; 4];
+        let mut buf_ledger_id = [0u8; 4];
+        let mut buf_till = [0u8; 4];
+        LittleEndian::write_i32(&mut buf, cmd_id);
+        LittleEndian::write_i32(&mut buf_ledger_id, i32::from(ledger_id));
+        LittleEndian::write_i32(&mut buf_till, till.map(|till| till as i32).unwrap_or(-1));
+
+        pool.cmd_socket.send_multipart(&[COMMAND_SYNC_LEDGER.as_bytes(), &buf, &buf_ledger_id, &buf_till], zmq::DONTWAIT)?;
+
+        trace!("PoolService::sync_ledger << cmd_id: {}", cmd_id);
+        Ok(cmd_id)
+    }
+
+    /// Opens reader of transactions `from..=to` of the ledger synced to the local replica.
+    pub fn open_ledger_txns_reader(&self, handle: PoolHandle, ledger_id: u8, from: usize, to: usize) -> IndyResult<LedgerTxnsReader> {
+        trace!("PoolService::open_ledger_txns_reader >> handle: {}, ledger_id: {}, from: {}, to: {}", handle, ledger_id, from, to);
+
+        let replica = if let Some(pool) = self.offline_pools.try_borrow()?.get(&handle) {
+            match u64::from(ledger_id) {
+                // Domain ledger of offline pool is served from the snapshot
+                snapshot::DOMAIN_LEDGER_ID => None,
+                _ => Some(merkle_tree_factory::open_replica_reader(&pool.name, ledger_id, from)?)
+            }
+        } else {
+            let pools = self.open_pools.try_borrow()?;
+
+            let pool = pools.get(&handle)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", handle)))?;
+
+            Some(merkle_tree_factory::open_replica_reader(pool.pool.get_name(), ledger_id, from)?)
+        };
+
+        Ok(LedgerTxnsReader { pool_handle: handle, ledger_id, next: from, to, replica })
+    }
+
+    /// Reads next batch of at most `LEDGER_SYNC_BATCH_SIZE` transactions. Empty batch means the range is read.
+    pub fn read_ledger_txns(&self, reader: &mut LedgerTxnsReader) -> IndyResult<Vec<serde_json::Value>> {
+        trace!("PoolService::read_ledger_txns >> ledger_id: {}, next: {}, to: {}", reader.ledger_id, reader.next, reader.to);
+
+        let count = cmp::min(LEDGER_SYNC_BATCH_SIZE, (reader.to + 1).saturating_sub(reader.next));
+
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        let txns = match reader.replica {
+            Some(ref mut replica) => replica.read(count)?,
+            None => {
+                let pools = self.offline_pools.try_borrow()?;
+
+                let pool = pools.get(&reader.pool_handle)
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidPoolHandle, format!("No pool with requested handle {}", reader.pool_handle)))?;
+
+                pool.ledger_txns(reader.ledger_id, reader.next, reader.next + count - 1)?
+            }
+        };
+
+        reader.next += count;
+
+        trace!("PoolService::read_ledger_txns << count: {}", txns.len());
+        Ok(txns)
+    }
+
     fn _send_msg(&self, cmd_id: CommandHandle, msg: &str, socket: &Socket, nodes: Option<&str>, timeout: Option<i32>) -> IndyResult<()> {
         let mut buf = [0u8; 4];
         let mut buf_to = [0u8; 4];
@@ -325,6 +563,32 @@ fn _parse_transaction_metadata_v1(message: &serde_json::Value) -> ResponseMetada
     }
 }
 
+fn _read_pool_namespace(name: &str) -> IndyResult<Option<String>> {
+    let mut path = environment::pool_path(name);
+    path.push("config");
+    path.set_extension("json");
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let config = fs::read_to_string(&path)
+        .to_indy(IndyErrorKind::IOError, "Can't read pool config file")?;
+
+    let config: PoolConfig = serde_json::from_str(&config)
+        .to_indy(IndyErrorKind::InvalidState, "Can't deserialize pool config")?;
+
+    Ok(config.namespace)
+}
+
+fn _send_pool_command(pc: PoolCommand) {
+    CommandExecutor::instance().send(Command::Pool(pc)).unwrap();
+}
+
+fn _send_ledger_command(lc: LedgerCommand) {
+    CommandExecutor::instance().send(Command::Ledger(lc)).unwrap();
+}
+
 pub fn pool_create_pair_of_sockets(addr: &str) -> (zmq::Socket, zmq::Socket) {
     let zmq_ctx = zmq::Context::new();
     let send_cmd_sock = zmq_ctx.socket(zmq::SocketType::PAIR).unwrap();
@@ -407,6 +671,30 @@ mod tests {
             assert_eq!(cmd_id, LittleEndian::read_i32(recv[1].as_slice()));
         }
 
+        #[test]
+        fn pool_service_sync_ledger_works() {
+            test::cleanup_storage("pool_service_sync_ledger_works");
+
+            let ps = PoolService::new();
+            let pool_id = next_pool_handle();
+            let (send_cmd_sock, recv_cmd_sock) = pool_create_pair_of_sockets("pool_service_sync_ledger_works");
+            ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(Pool::new("", pool_id, PoolOpenConfig::default()), send_cmd_sock));
+            let cmd_id = ps.sync_ledger(pool_id, 1, None).unwrap();
+            let recv = recv_cmd_sock.recv_multipart(zmq::DONTWAIT).unwrap();
+            assert_eq!(recv.len(), 4);
+            assert_eq!(COMMAND_SYNC_LEDGER, String::from_utf8(recv[0].clone()).unwrap());
+            assert_eq!(cmd_id, LittleEndian::read_i32(recv[1].as_slice()));
+            assert_eq!(1, LittleEndian::read_i32(recv[2].as_slice()));
+            assert_eq!(-1, LittleEndian::read_i32(recv[3].as_slice()));
+        }
+
+        #[test]
+        fn pool_service_sync_ledger_works_for_invalid_handle() {
+            let ps = PoolService::new();
+            let res = ps.sync_ledger(INVALID_POOL_HANDLE, 1, None);
+            assert_eq!(IndyErrorKind::InvalidPoolHandle, res.unwrap_err().kind());
+        }
+
         #[test]
         fn pool_service_delete_works() {
             test::cleanup_storage("pool_service_delete_works");
@@ -593,7 +881,7 @@ mod tests {
 
             let pool_id = next_pool_handle();
             let mut pool = Pool::new(pool_name, pool_id, PoolOpenConfig::default());
-            pool.work(recv_cmd_sock);
+            pool.work(recv_cmd_sock).unwrap();
             ps.open_pools.borrow_mut().insert(pool_id, ZMQPool::new(pool, send_cmd_sock));
             thread::sleep(time::Duration::from_secs(1));
             ps.close(pool_id).unwrap();
@@ -6,7 +6,10 @@ use rand::prelude::SliceRandom;
 use time::Tm;
 
 use indy_api_types::errors::prelude::*;
+use crate::domain::pool::PoolTransport;
 use crate::services::pool::events::*;
+use crate::services::pool::gateway_networker::GatewayNetworker;
+use crate::services::pool::status;
 use crate::services::pool::types::*;
 use indy_utils::sequence;
 use indy_utils::crypto::base64;
@@ -17,13 +20,59 @@ use super::zmq::PollItem;
 use super::zmq::Socket as ZSocket;
 
 pub trait Networker {
-    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>) -> Self;
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, transport: PoolTransport) -> IndyResult<Self> where Self: Sized;
     fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent>;
     fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent>;
     fn get_timeout(&self) -> ((String, String), i64);
     fn get_poll_items(&self) -> Vec<PollItem>;
 }
 
+/// Networker selected by the transport of the pool open config.
+pub enum PoolNetworker {
+    Zmq(ZMQNetworker),
+    Gateway(GatewayNetworker),
+}
+
+impl Networker for PoolNetworker {
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, transport: PoolTransport) -> IndyResult<Self> {
+        let networker = match transport {
+            PoolTransport::Zmq =>
+                PoolNetworker::Zmq(ZMQNetworker::new(active_timeout, conn_limit, preordered_nodes, transport)?),
+            PoolTransport::Http(_) | PoolTransport::Ws(_) =>
+                PoolNetworker::Gateway(GatewayNetworker::new(active_timeout, conn_limit, preordered_nodes, transport)?),
+        };
+        Ok(networker)
+    }
+
+    fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent> {
+        match self {
+            PoolNetworker::Zmq(networker) => networker.fetch_events(poll_items),
+            PoolNetworker::Gateway(networker) => networker.fetch_events(poll_items),
+        }
+    }
+
+    fn process_event(&mut self, pe: Option<NetworkerEvent>) -> Option<RequestEvent> {
+        match self {
+            PoolNetworker::Zmq(networker) => networker.process_event(pe),
+            PoolNetworker::Gateway(networker) => networker.process_event(pe),
+        }
+    }
+
+    fn get_timeout(&self) -> ((String, String), i64) {
+        match self {
+            PoolNetworker::Zmq(networker) => networker.get_timeout(),
+            PoolNetworker::Gateway(networker) => networker.get_timeout(),
+        }
+    }
+
+    fn get_poll_items(&self) -> Vec<PollItem> {
+        match self {
+            PoolNetworker::Zmq(networker) => networker.get_poll_items(),
+            PoolNetworker::Gateway(networker) => networker.get_poll_items(),
+        }
+    }
+}
+
 pub struct ZMQNetworker {
     req_id_mappings: HashMap<String, i32>,
     pool_connections: BTreeMap<i32, PoolConnection>,
@@ -34,15 +83,15 @@ pub struct ZMQNetworker {
 }
 
 impl Networker for ZMQNetworker {
-    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>) -> Self {
-        ZMQNetworker {
+    fn new(active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, _transport: PoolTransport) -> IndyResult<Self> {
+        Ok(ZMQNetworker {
             req_id_mappings: HashMap::new(),
             pool_connections: BTreeMap::new(),
             nodes: Vec::new(),
             active_timeout,
             conn_limit,
             preordered_nodes,
-        }
+        })
     }
 
     fn fetch_events(&self, poll_items: &[PollItem]) -> Vec<PoolEvent> {
@@ -302,6 +351,7 @@ impl PoolConnection {
                 self.timeouts.borrow_mut().remove(&(req_id.to_string(), node_alias));
             }
             None => {
+                status::request_finished(req_id);
                 let keys_to_remove: Vec<(String, String)> = self.timeouts.borrow().keys()
                     .cloned().filter(|&(ref req_id_timeout, _)| req_id == req_id_timeout).collect();
                 keys_to_remove.iter().for_each(|key| { self.timeouts.borrow_mut().remove(key); });
@@ -323,6 +373,7 @@ impl PoolConnection {
             let s = self._get_socket(idx)?;
             s.send(&req, zmq::DONTWAIT)?;
         }
+        status::request_sent(&req_id, &self.nodes[idx].name);
         self.timeouts.borrow_mut().insert((req_id, self.nodes[idx].name.clone()), time::now() + Duration::seconds(timeout));
         trace!("_send_msg_to_one_node <<");
         Ok(())
@@ -360,10 +411,10 @@ pub struct MockNetworker {
 
 #[cfg(test)]
 impl Networker for MockNetworker {
-    fn new(_active_timeout: i64, _conn_limit: usize, _preordered_nodes: Vec<String>) -> Self {
-        MockNetworker {
+    fn new(_active_timeout: i64, _conn_limit: usize, _preordered_nodes: Vec<String>, _transport: PoolTransport) -> IndyResult<Self> {
+        Ok(MockNetworker {
             events: Vec::new(),
-        }
+        })
     }
 
     fn fetch_events(&self, _poll_items: &[zmq::PollItem]) -> Vec<PoolEvent> {
@@ -418,12 +469,12 @@ pub mod networker_tests {
 
         #[test]
         pub fn networker_new_works() {
-            ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
         }
 
         #[test]
         pub fn networker_process_event_works() {
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
             networker.process_event(None);
         }
 
@@ -432,7 +483,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             assert_eq!(0, networker.nodes.len());
 
@@ -447,7 +498,7 @@ pub mod networker_tests {
             let handle = nodes_emulator::start(&mut txn);
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             assert!(networker.pool_connections.is_empty());
@@ -473,7 +524,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
             networker.process_event(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, None)));
@@ -496,7 +547,7 @@ pub mod networker_tests {
 
             let send_cnt = 2;
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec!["n2".to_string(), "n1".to_string()]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec!["n2".to_string(), "n1".to_string()], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
 
@@ -526,7 +577,7 @@ pub mod networker_tests {
             let handle_2 = nodes_emulator::start(&mut txn_2);
             let rn_2 = _remote_node(&txn_2);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn_1, rn_2])));
             networker.process_event(Some(NetworkerEvent::SendAllRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT, Some(vec![NODE_NAME.to_string()]))));
@@ -542,7 +593,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -565,7 +616,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -588,7 +639,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -617,7 +668,7 @@ pub mod networker_tests {
             let rn = _remote_node(&txn);
             let conn = PoolConnection::new(vec![rn.clone()], POOL_CON_ACTIVE_TO, vec![]);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.pool_connections.insert(1, conn);
@@ -634,7 +685,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
 
@@ -650,7 +701,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -668,7 +719,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
             networker.process_event(Some(NetworkerEvent::SendOneRequest(MESSAGE.to_string(), REQ_ID.to_string(), POOL_ACK_TIMEOUT)));
@@ -687,7 +738,7 @@ pub mod networker_tests {
             let txn = nodes_emulator::node();
             let rn = _remote_node(&txn);
 
-            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![]);
+            let mut networker = ZMQNetworker::new(POOL_CON_ACTIVE_TO, MAX_REQ_PER_POOL_CON, vec![], PoolTransport::Zmq).unwrap();
 
             networker.process_event(Some(NetworkerEvent::NodesStateUpdated(vec![rn])));
 
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::sync::mpsc;
 use std::thread;
 use std::thread::JoinHandle;
 
@@ -13,13 +14,13 @@ use crate::commands::CommandExecutor;
 use crate::commands::ledger::LedgerCommand;
 use crate::commands::pool::PoolCommand;
 use crate::domain::ledger::request::ProtocolVersion;
-use crate::domain::pool::PoolOpenConfig;
+use crate::domain::pool::{PoolOpenConfig, PoolTransport};
 use indy_api_types::errors::prelude::*;
 use crate::services::ledger::merkletree::merkletree::MerkleTree;
 use crate::services::pool::commander::Commander;
 use crate::services::pool::events::*;
 use crate::services::pool::{merkle_tree_factory, Nodes};
-use crate::services::pool::networker::{Networker, ZMQNetworker};
+use crate::services::pool::networker::{Networker, PoolNetworker};
 use crate::services::pool::request_handler::{RequestHandler, RequestHandlerImpl};
 use rust_base58::{FromBase58, ToBase58};
 use crate::services::pool::types::{LedgerStatus, RemoteNode};
@@ -35,6 +36,7 @@ struct PoolSM<T: Networker, R: RequestHandler<T>> {
     timeout: i64,
     extended_timeout: i64,
     number_read_nodes: u8,
+    verify_write_replies: bool,
     state: PoolState<T, R>,
 }
 
@@ -85,21 +87,22 @@ struct TerminatedState<T: Networker> {
 struct ClosedState {}
 
 impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
-    pub fn new(networker: Rc<RefCell<T>>, pname: &str, id: PoolHandle, timeout: i64, extended_timeout: i64, number_read_nodes: u8) -> PoolSM<T, R> {
+    pub fn new(networker: Rc<RefCell<T>>, pname: &str, id: PoolHandle, timeout: i64, extended_timeout: i64, number_read_nodes: u8, verify_write_replies: bool) -> PoolSM<T, R> {
         PoolSM {
             pool_name: pname.to_string(),
             id,
             timeout,
             extended_timeout,
             number_read_nodes,
+            verify_write_replies,
             state: PoolState::Initialization(InitializationState {
                 networker
             }),
         }
     }
 
-    pub fn step(pool_name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, number_read_nodes: u8, state: PoolState<T, R>) -> Self {
-        PoolSM { pool_name, id, timeout, extended_timeout, number_read_nodes, state }
+    pub fn step(pool_name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, number_read_nodes: u8, verify_write_replies: bool, state: PoolState<T, R>) -> Self {
+        PoolSM { pool_name, id, timeout, extended_timeout, number_read_nodes, verify_write_replies, state }
     }
 }
 
@@ -268,7 +271,7 @@ impl<T: Networker> From<TerminatedState<T>> for ClosedState {
 
 impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
     pub fn handle_event(self, pe: PoolEvent) -> Self {
-        let PoolSM { pool_name, id, state, timeout, extended_timeout, number_read_nodes } = self;
+        let PoolSM { pool_name, id, state, timeout, extended_timeout, number_read_nodes, verify_write_replies } = self;
         let state = match state {
             PoolState::Initialization(state) => match pe {
                 PoolEvent::CheckCache(cmd_id) => {
@@ -278,7 +281,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         //                        PoolWrapper::Active(pool.into())
                         unimplemented!()
                     } else {
-                        match _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
+                        match _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies) {
                             Ok(request_handler) => PoolState::GettingCatchupTarget((request_handler, cmd_id, state).into()),
                             Err(err) => {
                                 CommandExecutor::instance().send(
@@ -310,8 +313,8 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                     PoolEvent::CatchupRestart(merkle_tree) => {
                         if let Ok((nodes, remotes)) = _get_nodes_and_remotes(&merkle_tree) {
                             state.networker.borrow_mut().process_event(Some(NetworkerEvent::NodesStateUpdated(remotes)));
-                            state.request_handler = R::new(state.networker.clone(), _get_f(nodes.len()), &[], &nodes, &pool_name, timeout, extended_timeout, number_read_nodes);
-                            let ls = _ledger_status(&merkle_tree);
+                            state.request_handler = R::new(state.networker.clone(), _get_f(nodes.len()), &[], &nodes, &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies);
+                            let ls = _ledger_status(&merkle_tree, 0);
                             state.request_handler.process_event(Some(RequestEvent::LedgerStatus(ls, None, Some(merkle_tree))));
                             PoolState::GettingCatchupTarget(state)
                         } else {
@@ -321,7 +324,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                     PoolEvent::CatchupTargetFound(target_mt_root, target_mt_size, merkle_tree) => {
                         if let Ok((nodes, remotes)) = _get_nodes_and_remotes(&merkle_tree) {
                             state.networker.borrow_mut().process_event(Some(NetworkerEvent::NodesStateUpdated(remotes)));
-                            let mut request_handler = R::new(state.networker.clone(), _get_f(nodes.len()), &[], &nodes, &pool_name, timeout, extended_timeout, number_read_nodes);
+                            let mut request_handler = R::new(state.networker.clone(), _get_f(nodes.len()), &[], &nodes, &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies);
                             request_handler.process_event(Some(RequestEvent::CatchupReq(merkle_tree, target_mt_size, target_mt_root)));
                             PoolState::SyncCatchup((request_handler, state).into())
                         } else {
@@ -347,7 +350,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         PoolState::Closed(state.into())
                     }
                     PoolEvent::Refresh(cmd_id) => {
-                        if let Ok(request_handler) = _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
+                        if let Ok(request_handler) = _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies) {
                             PoolState::GettingCatchupTarget((state, request_handler, cmd_id).into())
                         } else {
                             PoolState::Terminated(state)
@@ -373,7 +376,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         PoolState::Closed(state.into())
                     }
                     PoolEvent::Refresh(cmd_id) => {
-                        if let Ok(request_handler) = _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes) {
+                        if let Ok(request_handler) = _get_request_handler_with_ledger_status_sent(state.networker.clone(), &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies) {
                             PoolState::GettingCatchupTarget((state, request_handler, cmd_id).into())
                         } else {
                             PoolState::Terminated(state.into())
@@ -384,7 +387,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                         let re: Option<RequestEvent> = pe.into();
                         match re.as_ref().map(|r| r.get_req_id()) {
                             Some(req_id) => {
-                                let mut request_handler = R::new(state.networker.clone(), _get_f(state.nodes.len()), &[cmd_id], &state.nodes, &pool_name, timeout, extended_timeout, number_read_nodes);
+                                let mut request_handler = R::new(state.networker.clone(), _get_f(state.nodes.len()), &[cmd_id], &state.nodes, &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies);
                                 request_handler.process_event(re);
                                 state.request_handlers.insert(req_id.to_string(), request_handler); //FIXME check already exists
                             }
@@ -415,6 +418,19 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
 
                         PoolState::Active(state)
                     }
+                    PoolEvent::SyncLedger(cmd_id, ledger_id, _) => {
+                        trace!("received request to sync ledger {}", ledger_id);
+                        let req_id = ledger_sync_req_id(ledger_id as usize);
+                        if state.request_handlers.contains_key(&req_id) {
+                            let res = Err(err_msg(IndyErrorKind::InvalidState, format!("Ledger {} is being synced already", ledger_id)));
+                            _send_submit_ack(cmd_id, res)
+                        } else {
+                            let mut request_handler = R::new(state.networker.clone(), _get_f(state.nodes.len()), &[cmd_id], &state.nodes, &pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies);
+                            request_handler.process_event(pe.into());
+                            state.request_handlers.insert(req_id, request_handler);
+                        }
+                        PoolState::Active(state)
+                    }
                     PoolEvent::Timeout(req_id, node_alias) => {
                         if let Some(rh) = state.request_handlers.get_mut(&req_id) {
                             rh.process_event(pe.into());
@@ -449,7 +465,7 @@ impl<T: Networker, R: RequestHandler<T>> PoolSM<T, R> {
                 }
             }
         };
-        PoolSM::step(pool_name, id, timeout, extended_timeout, number_read_nodes, state)
+        PoolSM::step(pool_name, id, timeout, extended_timeout, number_read_nodes, verify_write_replies, state)
     }
 
     pub fn is_terminal(&self) -> bool {
@@ -475,6 +491,8 @@ pub struct Pool<S: Networker, R: RequestHandler<S>> {
     conn_limit: usize,
     preordered_nodes: Vec<String>,
     number_read_nodes: u8,
+    verify_write_replies: bool,
+    transport: PoolTransport,
 }
 
 impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
@@ -491,10 +509,12 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
             conn_limit: config.conn_limit,
             preordered_nodes: config.preordered_nodes,
             number_read_nodes: config.number_read_nodes,
+            verify_write_replies: config.verify_write_replies,
+            transport: config.transport,
         }
     }
 
-    pub fn work(&mut self, cmd_socket: zmq::Socket) {
+    pub fn work(&mut self, cmd_socket: zmq::Socket) -> IndyResult<()> {
         let name = self.name.as_str().to_string();
         let id = self.id;
         let timeout = self.timeout;
@@ -503,14 +523,31 @@ impl<S: Networker, R: RequestHandler<S>> Pool<S, R> {
         let conn_limit = self.conn_limit;
         let preordered_nodes = self.preordered_nodes.clone();
         let number_read_nodes = self.number_read_nodes;
+        let verify_write_replies = self.verify_write_replies;
+        let transport = self.transport.clone();
+        let (started_sender, started_receiver) = mpsc::channel();
         self.worker = Some(thread::spawn(move || {
-            let mut pool_thread: PoolThread<S, R> = PoolThread::new(cmd_socket, name, id,
-                                                                    timeout, extended_timeout,
-                                                                    active_timeout, conn_limit,
-                                                                    preordered_nodes,
-                                                                    number_read_nodes);
-            pool_thread.work();
+            let pool_thread: IndyResult<PoolThread<S, R>> = PoolThread::new(cmd_socket, name, id,
+                                                                            timeout, extended_timeout,
+                                                                            active_timeout, conn_limit,
+                                                                            preordered_nodes,
+                                                                            number_read_nodes,
+                                                                            verify_write_replies,
+                                                                            transport);
+            match pool_thread {
+                Ok(mut pool_thread) => {
+                    let _ = started_sender.send(Ok(()));
+                    pool_thread.work();
+                }
+                Err(err) => {
+                    let _ = started_sender.send(Err(err));
+                }
+            }
         }));
+
+        // Networker can't be created for invalid transport config, so pool thread reports its start
+        started_receiver.recv()
+            .to_indy(IndyErrorKind::InvalidState, "Pool thread is terminated on start")?
     }
 
     pub fn get_name(&self) -> &str {
@@ -530,14 +567,14 @@ struct PoolThread<S: Networker, R: RequestHandler<S>> {
 }
 
 impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
-    pub fn new(cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, number_read_nodes: u8) -> Self {
-        let networker = Rc::new(RefCell::new(S::new(active_timeout, conn_limit, preordered_nodes)));
-        PoolThread {
-            pool_sm: Some(PoolSM::new(networker.clone(), &name, id, timeout, extended_timeout, number_read_nodes)),
+    pub fn new(cmd_socket: zmq::Socket, name: String, id: PoolHandle, timeout: i64, extended_timeout: i64, active_timeout: i64, conn_limit: usize, preordered_nodes: Vec<String>, number_read_nodes: u8, verify_write_replies: bool, transport: PoolTransport) -> IndyResult<Self> {
+        let networker = Rc::new(RefCell::new(S::new(active_timeout, conn_limit, preordered_nodes, transport)?));
+        Ok(PoolThread {
+            pool_sm: Some(PoolSM::new(networker.clone(), &name, id, timeout, extended_timeout, number_read_nodes, verify_write_replies)),
             events: VecDeque::new(),
             commander: Commander::new(cmd_socket),
             networker,
-        }
+        })
     }
 
     pub fn work(&mut self) {
@@ -598,7 +635,7 @@ impl<S: Networker, R: RequestHandler<S>> PoolThread<S, R> {
     }
 }
 
-fn _get_f(cnt: usize) -> usize {
+pub(super) fn _get_f(cnt: usize) -> usize {
     if cnt < 4 {
         return 0;
     }
@@ -610,7 +647,8 @@ fn _get_request_handler_with_ledger_status_sent<T: Networker, R: RequestHandler<
     pool_name: &str,
     timeout: i64,
     extended_timeout: i64,
-    number_read_nodes: u8) -> IndyResult<R>
+    number_read_nodes: u8,
+    verify_write_replies: bool) -> IndyResult<R>
 {
     let mut merkle = merkle_tree_factory::create(pool_name)?;
 
@@ -627,26 +665,26 @@ fn _get_request_handler_with_ledger_status_sent<T: Networker, R: RequestHandler<
         }
     };
     networker.borrow_mut().process_event(Some(NetworkerEvent::NodesStateUpdated(remotes)));
-    let mut request_handler = R::new(networker.clone(), _get_f(nodes.len()), &[], &nodes, pool_name, timeout, extended_timeout, number_read_nodes);
-    let ls = _ledger_status(&merkle);
+    let mut request_handler = R::new(networker.clone(), _get_f(nodes.len()), &[], &nodes, pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies);
+    let ls = _ledger_status(&merkle, 0);
     request_handler.process_event(Some(RequestEvent::LedgerStatus(ls, None, Some(merkle))));
     Ok(request_handler)
 }
 
-fn _ledger_status(merkle: &MerkleTree) -> LedgerStatus {
+pub(super) fn _ledger_status(merkle: &MerkleTree, ledger_id: u8) -> LedgerStatus {
     let protocol_version = ProtocolVersion::get();
 
     LedgerStatus {
         txnSeqNo: merkle.count(),
         merkleRoot: merkle.root_hash().as_slice().to_base58(),
-        ledgerId: 0,
+        ledgerId: ledger_id,
         ppSeqNo: None,
         viewNo: None,
         protocolVersion: if protocol_version > 1 { Some(protocol_version) } else { None },
     }
 }
 
-fn _get_nodes_and_remotes(merkle: &MerkleTree) -> IndyResult<(Nodes, Vec<RemoteNode>)> {
+pub(super) fn _get_nodes_and_remotes(merkle: &MerkleTree) -> IndyResult<(Nodes, Vec<RemoteNode>)> {
     let nodes = merkle_tree_factory::build_node_state(merkle)?;
 
     Ok(nodes.iter().map(|(_, txn)| {
@@ -731,12 +769,12 @@ fn _send_open_refresh_ack(cmd_id: CommandHandle, id: PoolHandle, is_refresh: boo
 }
 
 pub struct ZMQPool {
-    pub(super) pool: Pool<ZMQNetworker, RequestHandlerImpl<ZMQNetworker>>,
+    pub(super) pool: Pool<PoolNetworker, RequestHandlerImpl<PoolNetworker>>,
     pub(super) cmd_socket: zmq::Socket,
 }
 
 impl ZMQPool {
-    pub fn new(pool: Pool<ZMQNetworker, RequestHandlerImpl<ZMQNetworker>>, cmd_socket: zmq::Socket) -> ZMQPool {
+    pub fn new(pool: Pool<PoolNetworker, RequestHandlerImpl<PoolNetworker>>, cmd_socket: zmq::Socket) -> ZMQPool {
         ZMQPool {
             pool,
             cmd_socket,
@@ -809,7 +847,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_new_initialization_works() {
-            let _p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "name", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let _p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "name", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
         }
 
         #[test]
@@ -819,7 +857,7 @@ mod tests {
             ProtocolVersion::set(2);
             _write_genesis_txns("pool_wrapper_check_cache_works");
 
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_check_cache_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_check_cache_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             assert_match!(PoolState::GettingCatchupTarget(_), p.state);
@@ -830,8 +868,8 @@ mod tests {
         #[test]
         pub fn pool_wrapper_check_cache_works_for_no_pool_created() {
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))),
-                            "pool_wrapper_check_cache_works_for_no_pool_created", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())),
+                            "pool_wrapper_check_cache_works_for_no_pool_created", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             assert_match!(PoolState::Terminated(_), p.state);
@@ -839,7 +877,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_terminated_close_works() {
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_terminated_close_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_terminated_close_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let cmd_id: CommandHandle = next_command_handle();
@@ -850,7 +888,7 @@ mod tests {
         #[test]
         pub fn pool_wrapper_terminated_refresh_works() {
             test::cleanup_pool("pool_wrapper_terminated_refresh_works");
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_terminated_refresh_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_terminated_refresh_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
 
@@ -869,11 +907,12 @@ mod tests {
                 pool_name: "pool_wrapper_terminated_timeout_works".to_string(),
                 id: next_pool_handle(),
                 state: PoolState::Terminated(TerminatedState {
-                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))),
+                    networker: Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())),
                 }),
                 timeout: 0,
                 extended_timeout: 0,
                 number_read_nodes: NUMBER_READ_NODES,
+                verify_write_replies: false,
             };
 
             let p = p.handle_event(PoolEvent::Timeout("".to_string(), "".to_string()));
@@ -890,7 +929,7 @@ mod tests {
 
         #[test]
         pub fn pool_wrapper_cloe_works_from_initialization() {
-            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_cloe_works_from_initialization", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_cloe_works_from_initialization", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::Close(cmd_id));
             assert_match!(PoolState::Closed(_), p.state);
@@ -904,7 +943,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_close_works_from_getting_catchup_target");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_close_works_from_getting_catchup_target", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_close_works_from_getting_catchup_target", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let cmd_id: CommandHandle = next_command_handle();
@@ -922,7 +961,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_catchup_target_not_found_works");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_catchup_target_not_found_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_catchup_target_not_found_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetNotFound(err_msg(IndyErrorKind::PoolTimeout, "Pool timeout")));
@@ -939,7 +978,7 @@ mod tests {
             _write_genesis_txns("pool_wrapper_getting_catchup_target_synced_works");
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
-                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![]))), "pool_wrapper_getting_catchup_target_synced_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())), "pool_wrapper_getting_catchup_target_synced_works", next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -958,11 +997,11 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
-                                       0, vec![]))),
+                                       0, vec![], PoolTransport::Zmq).unwrap())),
                 "pool_wrapper_getting_catchup_target_synced_works_for_node_state_error",
                 next_pool_handle(),
                 0,
-                0, NUMBER_READ_NODES);
+                0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             ProtocolVersion::set(1);
@@ -985,11 +1024,11 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], PoolTransport::Zmq).unwrap())),
                 "pool_wrapper_getting_catchup_target_catchup_target_found_works",
                 next_pool_handle(),
                 0,
-                0, NUMBER_READ_NODES);
+                0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetFound(mt.root_hash().to_vec(), mt.count, mt));
@@ -1009,11 +1048,11 @@ mod tests {
 
             let p: PoolSM<MockNetworker, MockRequestHandler> =
                 PoolSM::new(Rc::new(RefCell::new(
-                    MockNetworker::new(0, 0, vec![]))),
+                    MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap())),
                             "pool_wrapper_getting_catchup_target_catchup_target_found_works_for_node_state_error",
                             next_pool_handle(),
                             0,
-                            0, NUMBER_READ_NODES);
+                            0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             ProtocolVersion::set(1);
@@ -1037,11 +1076,11 @@ mod tests {
                     RefCell::new(
                         MockNetworker::new(0,
                                            0,
-                                           vec![]))),
+                                           vec![], PoolTransport::Zmq).unwrap())),
                             "pool_wrapper_sync_catchup_close_works",
                             next_pool_handle(),
                             0,
-                            0, NUMBER_READ_NODES);
+                            0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetFound(mt.root_hash().to_vec(), mt.count, mt));
@@ -1065,11 +1104,11 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], PoolTransport::Zmq).unwrap())),
                 "pool_wrapper_sync_catchup_synced_works",
                 next_pool_handle(),
                 0,
-                0, NUMBER_READ_NODES);
+                0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetFound(mt.root_hash().to_vec(), mt.count, mt));
@@ -1092,11 +1131,11 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], PoolTransport::Zmq).unwrap())),
                 "pool_wrapper_sync_catchup_synced_works_for_node_state_error",
                 next_pool_handle(),
                 0,
-                0, NUMBER_READ_NODES);
+                0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::CatchupTargetFound(mt.root_hash().to_vec(), mt.count, mt));
@@ -1124,11 +1163,11 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
                 RefCell::new(MockNetworker::new(0,
                                                 0,
-                                                vec![]))),
+                                                vec![], PoolTransport::Zmq).unwrap())),
                                                                            "pool_wrapper_active_send_request_works",
                                                                            next_pool_handle(),
                                                                            0,
-                                                                           0, NUMBER_READ_NODES);
+                                                                           0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -1146,6 +1185,38 @@ mod tests {
             test::cleanup_storage("pool_wrapper_active_send_request_works");
         }
 
+        #[test]
+        pub fn pool_wrapper_active_sync_ledger_works() {
+            test::cleanup_storage("pool_wrapper_active_sync_ledger_works");
+
+            ProtocolVersion::set(2);
+            _write_genesis_txns("pool_wrapper_active_sync_ledger_works");
+
+            let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
+                RefCell::new(MockNetworker::new(0,
+                                                0,
+                                                vec![], PoolTransport::Zmq).unwrap())),
+                                                                           "pool_wrapper_active_sync_ledger_works",
+                                                                           next_pool_handle(),
+                                                                           0,
+                                                                           0, NUMBER_READ_NODES, false);
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
+            let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
+            let cmd_id: CommandHandle = next_command_handle();
+            let p = p.handle_event(PoolEvent::SyncLedger(cmd_id, 1, Some(10)));
+            assert_match!(PoolState::Active(_), p.state);
+            match p.state {
+                PoolState::Active(state) => {
+                    assert_eq!(state.request_handlers.len(), 1);
+                    assert!(state.request_handlers.contains_key("ledger_sync_1"));
+                }
+                _ => assert!(false)
+            };
+
+            test::cleanup_storage("pool_wrapper_active_sync_ledger_works");
+        }
+
         #[test]
         pub fn pool_wrapper_active_send_request_works_for_no_req_id() {
             test::cleanup_storage("pool_wrapper_active_send_request_works_for_no_req_id");
@@ -1164,11 +1235,11 @@ mod tests {
                     MockNetworker::new(
                         0,
                         0,
-                        vec![]))),
+                        vec![], PoolTransport::Zmq).unwrap())),
                             "pool_wrapper_active_send_request_works_for_no_req_id",
                             next_pool_handle(),
                             0,
-                            0, NUMBER_READ_NODES);
+                            0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -1217,11 +1288,11 @@ mod tests {
                 Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], PoolTransport::Zmq).unwrap())),
                 "pool_wrapper_active_node_reply_works",
                 next_pool_handle(),
                 0,
-                0, NUMBER_READ_NODES);
+                0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -1257,9 +1328,9 @@ mod tests {
                 PoolSM::new(Rc::new(RefCell::new(
                     MockNetworker::new(0,
                                        0,
-                                       vec![]))),
+                                       vec![], PoolTransport::Zmq).unwrap())),
                             "pool_wrapper_sends_requests_to_two_nodes",
-                            next_pool_handle(), 0, 0, NUMBER_READ_NODES);
+                            next_pool_handle(), 0, 0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -1307,11 +1378,11 @@ mod tests {
             let p: PoolSM<MockNetworker, MockRequestHandler> = PoolSM::new(Rc::new(
                 RefCell::new(MockNetworker::new(0,
                                                 0,
-                                                vec![]))),
+                                                vec![], PoolTransport::Zmq).unwrap())),
                                                                            "pool_wrapper_active_node_reply_works_for_no_request",
                                                                            next_pool_handle(),
                                                                            0,
-                                                                           0, NUMBER_READ_NODES);
+                                                                           0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
@@ -1350,11 +1421,11 @@ mod tests {
                 PoolSM::new(Rc::new(RefCell::new(MockNetworker::new(
                     0,
                     0,
-                    vec![]))),
+                    vec![], PoolTransport::Zmq).unwrap())),
                             "pool_wrapper_active_node_reply_works_for_invalid_reply",
                             next_pool_handle(),
                             0,
-                            0, NUMBER_READ_NODES);
+                            0, NUMBER_READ_NODES, false);
             let cmd_id: CommandHandle = next_command_handle();
             let p = p.handle_event(PoolEvent::CheckCache(cmd_id));
             let p = p.handle_event(PoolEvent::Synced(MerkleTree::from_vec(vec![]).unwrap()));
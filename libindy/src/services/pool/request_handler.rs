@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::cmp;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::FromIterator;
@@ -16,16 +17,20 @@ use crate::commands::CommandExecutor;
 use crate::commands::ledger::LedgerCommand;
 use indy_api_types::errors::prelude::*;
 use crate::services::ledger::merkletree::merkletree::MerkleTree;
-use crate::services::pool::catchup::{build_catchup_req, CatchupProgress, check_cons_proofs, check_nodes_responses_on_status};
+use crate::services::pool::catchup::{build_catchup_req, build_ledger_catchup_req, CatchupProgress, check_cons_proofs, check_nodes_responses_on_ledger_status, check_nodes_responses_on_status};
+use crate::services::pool::events::ledger_sync_req_id;
 use crate::services::pool::events::NetworkerEvent;
 use crate::services::pool::events::PoolEvent;
 use crate::services::pool::events::RequestEvent;
 use crate::services::pool::{get_last_signed_time, Nodes};
 use crate::services::pool::merkle_tree_factory;
 use crate::services::pool::networker::Networker;
+use crate::services::pool::pool::_ledger_status;
 use crate::services::pool::state_proof;
+use crate::services::pool::status;
 use crate::services::pool::types::CatchupRep;
 use crate::services::pool::types::HashableValue;
+use crate::services::pool::types::Message;
 
 use super::ursa::bls::Generator;
 
@@ -43,15 +48,18 @@ struct RequestSM<T: Networker> {
     timeout: i64,
     extended_timeout: i64,
     number_read_nodes: u8,
+    verify_write_replies: bool,
     state: RequestState<T>,
 }
 
 /// Transitions of request state
-/// Start -> Start, Single, Consensus, CatchupSingle, CatchupConsensus, Full, Finish
+/// Start -> Start, Single, Consensus, CatchupSingle, CatchupConsensus, LedgerSyncConsensus, Full, Finish
 /// Single -> Single, Finish
 /// Consensus -> Consensus, Finish
 /// CatchupSingle -> CatchupSingle, Finish
 /// CatchupConsensus -> CatchupConsensus, Finish
+/// LedgerSyncConsensus -> LedgerSyncConsensus, LedgerSyncSingle, Finish
+/// LedgerSyncSingle -> LedgerSyncSingle, Finish
 /// Full -> Full, Finish
 /// Finish -> Finish
 enum RequestState<T: Networker> {
@@ -60,10 +68,15 @@ enum RequestState<T: Networker> {
     Consensus(ConsensusState<T>),
     CatchupSingle(CatchupSingleState<T>),
     CatchupConsensus(CatchupConsensusState<T>),
+    LedgerSyncConsensus(LedgerSyncConsensusState<T>),
+    LedgerSyncSingle(LedgerSyncSingleState<T>),
     Full(FullState<T>),
     Finish(FinishState),
 }
 
+/// Max number of transactions requested from a node by one CATCHUP_REQ during local ledger replica sync
+pub const LEDGER_SYNC_BATCH_SIZE: usize = 100;
+
 /*
  The Generator is used for multi-signature verification.
  It must be the same as on the Ledger side otherwise signatures verification will fail.
@@ -75,7 +88,7 @@ impl<T: Networker> RequestSM<T> {
                f: usize,
                cmd_ids: &[CommandHandle],
                nodes: &Nodes,
-               pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8) -> Self {
+               pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8, verify_write_replies: bool) -> Self {
         let generator: Generator = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
         RequestSM {
             f,
@@ -86,6 +99,7 @@ impl<T: Networker> RequestSM<T> {
             timeout,
             extended_timeout,
             number_read_nodes,
+            verify_write_replies,
             state: RequestState::Start(StartState {
                 networker
             }),
@@ -100,6 +114,7 @@ impl<T: Networker> RequestSM<T> {
                 timeout: i64,
                 extended_timeout: i64,
                 number_read_nodes: u8,
+                verify_write_replies: bool,
                 state: RequestState<T>) -> Self {
         RequestSM {
             f,
@@ -110,6 +125,7 @@ impl<T: Networker> RequestSM<T> {
             timeout,
             extended_timeout,
             number_read_nodes,
+            verify_write_replies,
             state,
         }
     }
@@ -120,6 +136,7 @@ struct StartState<T: Networker> {
 }
 
 struct ConsensusState<T: Networker> {
+    request: SJsonValue,
     denied_nodes: HashSet<String> /* FIXME should be map, may be merged with replies */,
     replies: HashMap<HashableValue, HashSet<String>>,
     timeout_nodes: HashSet<String>,
@@ -140,6 +157,24 @@ struct CatchupSingleState<T: Networker> {
     req_id: String,
 }
 
+struct LedgerSyncConsensusState<T: Networker> {
+    ledger_id: u8,
+    till: Option<usize>,
+    replies: HashMap<(String, usize, Option<Vec<String>>), HashSet<String>>,
+    networker: Rc<RefCell<T>>,
+    merkle_tree: MerkleTree,
+}
+
+struct LedgerSyncSingleState<T: Networker> {
+    ledger_id: u8,
+    till: usize,
+    target_mt_root: Vec<u8>,
+    target_mt_size: usize,
+    merkle_tree: MerkleTree,
+    resends: usize,
+    networker: Rc<RefCell<T>>,
+}
+
 struct SingleState<T: Networker> {
     denied_nodes: HashSet<String> /* FIXME should be map, may be merged with replies */,
     replies: HashMap<HashableValue, HashSet<NodeResponse>>,
@@ -170,9 +205,10 @@ impl<T: Networker> From<(StartState<T>, Option<Vec<u8>>, (Option<u64>, Option<u6
     }
 }
 
-impl<T: Networker> From<StartState<T>> for ConsensusState<T> {
-    fn from(state: StartState<T>) -> Self {
+impl<T: Networker> From<(SJsonValue, StartState<T>)> for ConsensusState<T> {
+    fn from((request, state): (SJsonValue, StartState<T>)) -> Self {
         ConsensusState {
+            request,
             denied_nodes: HashSet::new(),
             replies: HashMap::new(),
             timeout_nodes: HashSet::new(),
@@ -203,6 +239,32 @@ impl<T: Networker> From<(MerkleTree, StartState<T>, Vec<u8>, usize, String)> for
     }
 }
 
+impl<T: Networker> From<(u8, Option<usize>, MerkleTree, StartState<T>)> for LedgerSyncConsensusState<T> {
+    fn from((ledger_id, till, merkle_tree, state): (u8, Option<usize>, MerkleTree, StartState<T>)) -> Self {
+        LedgerSyncConsensusState {
+            ledger_id,
+            till,
+            replies: HashMap::new(),
+            networker: state.networker.clone(),
+            merkle_tree,
+        }
+    }
+}
+
+impl<T: Networker> From<(LedgerSyncConsensusState<T>, MerkleTree, Vec<u8>, usize, usize)> for LedgerSyncSingleState<T> {
+    fn from((state, merkle_tree, target_mt_root, target_mt_size, till): (LedgerSyncConsensusState<T>, MerkleTree, Vec<u8>, usize, usize)) -> Self {
+        LedgerSyncSingleState {
+            ledger_id: state.ledger_id,
+            till,
+            target_mt_root,
+            target_mt_size,
+            merkle_tree,
+            resends: 0,
+            networker: state.networker.clone(),
+        }
+    }
+}
+
 impl<T: Networker> From<StartState<T>> for FullState<T> {
     fn from(state: StartState<T>) -> Self {
         FullState {
@@ -251,7 +313,8 @@ impl Hash for NodeResponse {
 
 impl<T: Networker> RequestSM<T> {
     fn handle_event(self, re: RequestEvent) -> (Self, Option<PoolEvent>) {
-        let RequestSM { state, f, cmd_ids, nodes, generator, pool_name, timeout, extended_timeout, number_read_nodes } = self;
+        let RequestSM { state, f, cmd_ids, nodes, generator, pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies } = self;
+        status::record_event(&pool_name, &re);
         let (state, event) = match state {
             RequestState::Start(state) => {
                 match re {
@@ -279,6 +342,19 @@ impl<T: Networker> RequestSM<T> {
                             }
                         }
                     }
+                    RequestEvent::SyncLedger(ledger_id, till) => {
+                        match merkle_tree_factory::create_replica(&pool_name, ledger_id) {
+                            Ok(merkle) => {
+                                let state: LedgerSyncConsensusState<T> = (ledger_id, till, merkle, state).into();
+                                state.send_ledger_status(extended_timeout);
+                                (RequestState::LedgerSyncConsensus(state), None)
+                            }
+                            Err(err) => {
+                                _send_replies(&cmd_ids, Err(err));
+                                (RequestState::finish(), None)
+                            }
+                        }
+                    }
                     RequestEvent::CustomSingleRequest(msg, req_id, sp_key, timestamps) => {
                         state.networker.borrow_mut().process_event(Some(NetworkerEvent::SendOneRequest(msg.clone(), req_id.clone(), timeout)));
 
@@ -316,8 +392,9 @@ impl<T: Networker> RequestSM<T> {
                         }
                     }
                     RequestEvent::CustomConsensusRequest(msg, req_id) => {
+                        let request = serde_json::from_str::<SJsonValue>(&msg).unwrap_or(SJsonValue::Null);
                         state.networker.borrow_mut().process_event(Some(NetworkerEvent::SendAllRequest(msg, req_id, timeout, None)));
-                        (RequestState::Consensus(state.into()), None)
+                        (RequestState::Consensus((request, state).into()), None)
                     }
                     _ => {
                         (RequestState::Start(state), None)
@@ -326,42 +403,28 @@ impl<T: Networker> RequestSM<T> {
             }
             RequestState::Consensus(mut state) => {
                 match re {
-                    RequestEvent::Reply(_, raw_msg, node_alias, req_id) |
-                    RequestEvent::ReqNACK(_, raw_msg, node_alias, req_id) |
-                    RequestEvent::Reject(_, raw_msg, node_alias, req_id)
-                    => {
-                        if let Ok((_, result_without_proof)) = _get_msg_result_without_state_proof(&raw_msg) {
-                            let hashable = HashableValue { inner: result_without_proof };
-
-                            let cnt = {
-                                let set = state.replies.entry(hashable).or_insert_with(HashSet::new);
-                                set.insert(node_alias.clone());
-                                set.len()
-                            };
-
-                            if cnt > f {
+                    RequestEvent::Reply(_, raw_msg, node_alias, req_id) if verify_write_replies => {
+                        match _check_write_reply(&raw_msg, &state.request, f, &generator, &nodes) {
+                            Some(true) => {
                                 _send_ok_replies(&cmd_ids, &raw_msg);
                                 state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
                                 (RequestState::finish(), None)
-                            } else if state.is_consensus_reachable(f, nodes.len()) {
-                                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias))));
-                                (RequestState::Consensus(state), None)
-                            } else {
-                                //TODO: maybe we should change the error, but it was made to escape changing of ErrorCode returned to client
-                                _send_replies(&cmd_ids, Err(err_msg(IndyErrorKind::PoolTimeout, "Consensus is impossible")));
-                                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
-                                (RequestState::finish(), None)
                             }
-                        } else {
-                            state.denied_nodes.insert(node_alias.clone());
-                            if state.denied_nodes.len() + state.replies.len() == nodes.len() {
-                                _send_replies(&cmd_ids, Err(err_msg(IndyErrorKind::PoolTimeout, "Consensus is impossible")));
-                                (RequestState::finish(), None)
-                            } else {
-                                (RequestState::Consensus(state), None)
+                            Some(false) => {
+                                warn!("Write reply from node {} hasn't passed verification", node_alias);
+                                status::bls_verification_failed(&pool_name, &node_alias);
+                                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias.clone()))));
+                                (state.deny_node(node_alias, &cmd_ids, nodes.len()), None)
                             }
+                            None => (state.process_reply(&raw_msg, node_alias, req_id, &cmd_ids, f, nodes.len()), None)
                         }
                     }
+                    RequestEvent::Reply(_, raw_msg, node_alias, req_id) |
+                    RequestEvent::ReqNACK(_, raw_msg, node_alias, req_id) |
+                    RequestEvent::Reject(_, raw_msg, node_alias, req_id)
+                    => {
+                        (state.process_reply(&raw_msg, node_alias, req_id, &cmd_ids, f, nodes.len()), None)
+                    }
                     RequestEvent::ReqACK(_, _, node_alias, req_id) => {
                         state.networker.borrow_mut().process_event(Some(NetworkerEvent::ExtendTimeout(req_id, node_alias, extended_timeout)));
                         (RequestState::Consensus(state), None)
@@ -407,7 +470,7 @@ impl<T: Networker> RequestSM<T> {
                             };
 
                             if cnt > f
-                                || _check_state_proof(&result, f, &generator, &nodes, &raw_msg, state.sp_key.as_ref().map(Vec::as_slice), state.timestamps, last_write_time) {
+                                || _check_state_proof(&result, f, &generator, &nodes, &raw_msg, state.sp_key.as_ref().map(Vec::as_slice), state.timestamps, last_write_time, &pool_name, &node_alias) {
                                 state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
                                 _send_ok_replies(&cmd_ids, if cnt > f { &soonest } else { &raw_msg });
                                 (RequestState::finish(), None)
@@ -489,6 +552,73 @@ impl<T: Networker> RequestSM<T> {
                     _ => (RequestState::CatchupSingle(state), None)
                 }
             }
+            RequestState::LedgerSyncConsensus(state) => {
+                match re {
+                    RequestEvent::LedgerStatus(ls, Some(node_alias), _) => {
+                        (RequestSM::_ledger_sync_handle_consensus_state(
+                            state,
+                            ls.merkleRoot, ls.txnSeqNo, None,
+                            node_alias, &cmd_ids, f, &nodes, &pool_name, timeout, extended_timeout), None)
+                    }
+                    RequestEvent::ConsistencyProof(cp, node_alias) => {
+                        (RequestSM::_ledger_sync_handle_consensus_state(
+                            state,
+                            cp.newMerkleRoot, cp.seqNoEnd, Some(cp.hashes),
+                            node_alias, &cmd_ids, f, &nodes, &pool_name, timeout, extended_timeout), None)
+                    }
+                    RequestEvent::Timeout(_, node_alias) => {
+                        (RequestSM::_ledger_sync_handle_consensus_state(
+                            state,
+                            "timeout".to_string(), 0, None,
+                            node_alias, &cmd_ids, f, &nodes, &pool_name, timeout, extended_timeout), None)
+                    }
+                    RequestEvent::Terminate => {
+                        _finish_request(&cmd_ids);
+                        (RequestState::finish(), None)
+                    }
+                    _ => (RequestState::LedgerSyncConsensus(state), None)
+                }
+            }
+            RequestState::LedgerSyncSingle(mut state) => {
+                match re {
+                    RequestEvent::CatchupRep(mut cr, node_alias) => {
+                        match _process_ledger_catchup_reply(&mut cr, &mut state.merkle_tree, &state.target_mt_root, state.target_mt_size, state.ledger_id, &pool_name) {
+                            Ok(true) => {
+                                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(ledger_sync_req_id(state.ledger_id as usize), None)));
+                                state.resends = 0;
+                                if state.merkle_tree.count() >= state.till {
+                                    _send_ok_replies(&cmd_ids, &_ledger_sync_result(state.target_mt_size));
+                                    (RequestState::finish(), None)
+                                } else {
+                                    (RequestSM::_send_ledger_catchup_req(state, &cmd_ids, timeout), None)
+                                }
+                            }
+                            Ok(false) => (RequestState::LedgerSyncSingle(state), None),
+                            Err(_) => {
+                                match merkle_tree_factory::create_replica(&pool_name, state.ledger_id) {
+                                    Ok(merkle) => {
+                                        state.merkle_tree = merkle;
+                                        (RequestSM::_resend_ledger_catchup_req(state, node_alias, &cmd_ids, nodes.len(), timeout), None)
+                                    }
+                                    Err(err) => {
+                                        state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(ledger_sync_req_id(state.ledger_id as usize), None)));
+                                        _send_replies(&cmd_ids, Err(err));
+                                        (RequestState::finish(), None)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    RequestEvent::Timeout(_, node_alias) => {
+                        (RequestSM::_resend_ledger_catchup_req(state, node_alias, &cmd_ids, nodes.len(), timeout), None)
+                    }
+                    RequestEvent::Terminate => {
+                        _finish_request(&cmd_ids);
+                        (RequestState::finish(), None)
+                    }
+                    _ => (RequestState::LedgerSyncSingle(state), None)
+                }
+            }
             RequestState::Full(state) => {
                 match re {
                     RequestEvent::Reply(_, raw_msg, node_alias, req_id) |
@@ -509,7 +639,7 @@ impl<T: Networker> RequestSM<T> {
             }
             RequestState::Finish(state) => (RequestState::Finish(state), None)
         };
-        (RequestSM::step(f, cmd_ids, nodes, generator, pool_name, timeout, extended_timeout, number_read_nodes, state), event)
+        (RequestSM::step(f, cmd_ids, nodes, generator, pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies, state), event)
     }
 
     fn is_terminal(&self) -> bool {
@@ -519,6 +649,8 @@ impl<T: Networker> RequestSM<T> {
             RequestState::Single(_) |
             RequestState::CatchupSingle(_) |
             RequestState::CatchupConsensus(_) |
+            RequestState::LedgerSyncConsensus(_) |
+            RequestState::LedgerSyncSingle(_) |
             RequestState::Full(_) => false,
             RequestState::Finish(_) => true
         }
@@ -555,6 +687,85 @@ impl<T: Networker> RequestSM<T> {
         }
     }
 
+    fn _ledger_sync_handle_consensus_state(mut state: LedgerSyncConsensusState<T>,
+                                           mt_root: String, sz: usize, cons_proof: Option<Vec<String>>,
+                                           node_alias: String,
+                                           cmd_ids: &[CommandHandle],
+                                           f: usize, nodes: &Nodes,
+                                           pool_name: &str,
+                                           timeout: i64, extended_timeout: i64) -> RequestState<T> {
+        let req_id = ledger_sync_req_id(state.ledger_id as usize);
+
+        state.replies.entry((mt_root, sz, cons_proof))
+            .or_insert_with(HashSet::new)
+            .insert(node_alias.clone());
+
+        match check_nodes_responses_on_ledger_status(&state.replies, &state.merkle_tree, nodes.len(), f, pool_name, state.ledger_id) {
+            Ok(CatchupProgress::InProgress) => {
+                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias))));
+                RequestState::LedgerSyncConsensus(state)
+            }
+            Ok(CatchupProgress::Restart(merkle_tree)) => {
+                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+                state.replies.clear();
+                state.merkle_tree = merkle_tree;
+                state.send_ledger_status(extended_timeout);
+                RequestState::LedgerSyncConsensus(state)
+            }
+            Ok(CatchupProgress::NotNeeded(merkle_tree)) => {
+                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+                _send_ok_replies(cmd_ids, &_ledger_sync_result(merkle_tree.count()));
+                RequestState::finish()
+            }
+            Ok(CatchupProgress::ShouldBeStarted(target_mt_root, target_mt_size, merkle_tree)) => {
+                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+                let till = state.till.map(|till| cmp::min(till, target_mt_size)).unwrap_or(target_mt_size);
+
+                if merkle_tree.count() >= till {
+                    _send_ok_replies(cmd_ids, &_ledger_sync_result(target_mt_size));
+                    RequestState::finish()
+                } else {
+                    RequestSM::_send_ledger_catchup_req((state, merkle_tree, target_mt_root, target_mt_size, till).into(), cmd_ids, timeout)
+                }
+            }
+            Err(err) => {
+                state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+                _send_replies(cmd_ids, Err(err));
+                RequestState::finish()
+            }
+        }
+    }
+
+    fn _send_ledger_catchup_req(state: LedgerSyncSingleState<T>, cmd_ids: &[CommandHandle], timeout: i64) -> RequestState<T> {
+        let seq_no_end = cmp::min(state.merkle_tree.count() + LEDGER_SYNC_BATCH_SIZE, state.till);
+
+        match build_ledger_catchup_req(state.ledger_id, &state.merkle_tree, seq_no_end, state.target_mt_size) {
+            Ok(req_json) => {
+                state.networker.borrow_mut().process_event(Some(NetworkerEvent::SendOneRequest(req_json, ledger_sync_req_id(state.ledger_id as usize), timeout)));
+                RequestState::LedgerSyncSingle(state)
+            }
+            Err(err) => {
+                _send_replies(cmd_ids, Err(err));
+                RequestState::finish()
+            }
+        }
+    }
+
+    fn _resend_ledger_catchup_req(mut state: LedgerSyncSingleState<T>, node_alias: String, cmd_ids: &[CommandHandle], nodes_cnt: usize, timeout: i64) -> RequestState<T> {
+        let req_id = ledger_sync_req_id(state.ledger_id as usize);
+        state.resends += 1;
+
+        if state.resends < nodes_cnt {
+            state.networker.borrow_mut().process_event(Some(NetworkerEvent::Resend(req_id.clone(), timeout)));
+            state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias))));
+            RequestState::LedgerSyncSingle(state)
+        } else {
+            _send_replies(cmd_ids, Err(err_msg(IndyErrorKind::PoolTimeout, "No node returned valid ledger transactions")));
+            state.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+            RequestState::finish()
+        }
+    }
+
     fn _catchup_target_handle_consensus_state(mut state: CatchupConsensusState<T>,
                                               mt_root: String, sz: usize, cons_proof: Option<Vec<String>>,
                                               node_alias: String, req_id: String,
@@ -612,7 +823,7 @@ impl<T: Networker> RequestSM<T> {
 }
 
 pub trait RequestHandler<T: Networker> {
-    fn new(networker: Rc<RefCell<T>>, f: usize, cmd_ids: &[CommandHandle], nodes: &Nodes, pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8) -> Self;
+    fn new(networker: Rc<RefCell<T>>, f: usize, cmd_ids: &[CommandHandle], nodes: &Nodes, pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8, verify_write_replies: bool) -> Self;
     fn process_event(&mut self, ore: Option<RequestEvent>) -> Option<PoolEvent>;
     fn is_terminal(&self) -> bool;
 }
@@ -622,9 +833,9 @@ pub struct RequestHandlerImpl<T: Networker> {
 }
 
 impl<T: Networker> RequestHandler<T> for RequestHandlerImpl<T> {
-    fn new(networker: Rc<RefCell<T>>, f: usize, cmd_ids: &[CommandHandle], nodes: &Nodes, pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8) -> Self {
+    fn new(networker: Rc<RefCell<T>>, f: usize, cmd_ids: &[CommandHandle], nodes: &Nodes, pool_name: &str, timeout: i64, extended_timeout: i64, number_read_nodes: u8, verify_write_replies: bool) -> Self {
         RequestHandlerImpl {
-            request_wrapper: Some(RequestSM::new(networker, f, cmd_ids, nodes, pool_name, timeout, extended_timeout, number_read_nodes)),
+            request_wrapper: Some(RequestSM::new(networker, f, cmd_ids, nodes, pool_name, timeout, extended_timeout, number_read_nodes, verify_write_replies)),
         }
     }
 
@@ -669,12 +880,60 @@ impl<T: Networker> SingleState<T> {
     }
 }
 
+impl<T: Networker> LedgerSyncConsensusState<T> {
+    fn send_ledger_status(&self, timeout: i64) {
+        let ls = _ledger_status(&self.merkle_tree, self.ledger_id);
+        let ne = Some(NetworkerEvent::SendAllRequest(serde_json::to_string(&Message::LedgerStatus(ls)).expect("FIXME"),
+                                                     ledger_sync_req_id(self.ledger_id as usize), timeout, None));
+        trace!("start ledger {} sync, ne: {:?}", self.ledger_id, ne);
+        self.networker.borrow_mut().process_event(ne);
+    }
+}
+
 impl<T: Networker> ConsensusState<T> {
     fn is_consensus_reachable(&self, f: usize, total_nodes_cnt: usize) -> bool {
         let rep_no: usize = self.replies.values().map(|set| set.len()).sum();
         let max_no = self.replies.values().map(|set| set.len()).max().unwrap_or(0);
         max_no + total_nodes_cnt - rep_no - self.timeout_nodes.len() - self.denied_nodes.len() > f
     }
+
+    fn process_reply(mut self, raw_msg: &str, node_alias: String, req_id: String, cmd_ids: &[CommandHandle], f: usize, nodes_cnt: usize) -> RequestState<T> {
+        if let Ok((_, result_without_proof)) = _get_msg_result_without_state_proof(raw_msg) {
+            let hashable = HashableValue { inner: result_without_proof };
+
+            let cnt = {
+                let set = self.replies.entry(hashable).or_insert_with(HashSet::new);
+                set.insert(node_alias.clone());
+                set.len()
+            };
+
+            if cnt > f {
+                _send_ok_replies(cmd_ids, raw_msg);
+                self.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+                RequestState::finish()
+            } else if self.is_consensus_reachable(f, nodes_cnt) {
+                self.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, Some(node_alias))));
+                RequestState::Consensus(self)
+            } else {
+                //TODO: maybe we should change the error, but it was made to escape changing of ErrorCode returned to client
+                _send_replies(cmd_ids, Err(err_msg(IndyErrorKind::PoolTimeout, "Consensus is impossible")));
+                self.networker.borrow_mut().process_event(Some(NetworkerEvent::CleanTimeout(req_id, None)));
+                RequestState::finish()
+            }
+        } else {
+            self.deny_node(node_alias, cmd_ids, nodes_cnt)
+        }
+    }
+
+    fn deny_node(mut self, node_alias: String, cmd_ids: &[CommandHandle], nodes_cnt: usize) -> RequestState<T> {
+        self.denied_nodes.insert(node_alias);
+        if self.denied_nodes.len() + self.replies.len() == nodes_cnt {
+            _send_replies(cmd_ids, Err(err_msg(IndyErrorKind::PoolTimeout, "Consensus is impossible")));
+            RequestState::finish()
+        } else {
+            RequestState::Consensus(self)
+        }
+    }
 }
 
 fn _parse_nack(denied_nodes: &mut HashSet<String>, f: usize, raw_msg: &str, cmd_ids: &[CommandHandle], node_alias: &str) -> bool {
@@ -688,8 +947,31 @@ fn _parse_nack(denied_nodes: &mut HashSet<String>, f: usize, raw_msg: &str, cmd_
 }
 
 fn _process_catchup_reply(rep: &mut CatchupRep, merkle: &MerkleTree, target_mt_root: &Vec<u8>, target_mt_size: usize, pool_name: &str) -> IndyResult<MerkleTree> {
-    let mut txns_to_drop = vec![];
     let mut merkle = merkle.clone();
+    let txns_to_drop = _append_catchup_txns(rep, &mut merkle)?;
+
+    check_cons_proofs(&merkle, &rep.consProof, target_mt_root, target_mt_size)?;
+    merkle_tree_factory::dump_new_txns(pool_name, &txns_to_drop)?;
+    Ok(merkle)
+}
+
+/// Appends transactions of the reply to the ledger replica.
+/// Returns false if the reply doesn't continue the replica (it's a late reply on previous CATCHUP_REQ).
+/// Merkle tree is left modified on error and must be restored from the replica.
+fn _process_ledger_catchup_reply(rep: &mut CatchupRep, merkle: &mut MerkleTree, target_mt_root: &Vec<u8>, target_mt_size: usize, ledger_id: u8, pool_name: &str) -> IndyResult<bool> {
+    if rep.min_tx()? != merkle.count() + 1 {
+        return Ok(false);
+    }
+
+    let txns_to_drop = _append_catchup_txns(rep, merkle)?;
+
+    check_cons_proofs(merkle, &rep.consProof, target_mt_root, target_mt_size)?;
+    merkle_tree_factory::dump_replica_txns(pool_name, ledger_id, &txns_to_drop)?;
+    Ok(true)
+}
+
+fn _append_catchup_txns(rep: &mut CatchupRep, merkle: &mut MerkleTree) -> IndyResult<Vec<Vec<u8>>> {
+    let mut txns = vec![];
 
     while !rep.txns.is_empty() {
         let key = rep.min_tx()?;
@@ -699,12 +981,14 @@ fn _process_catchup_reply(rep: &mut CatchupRep, merkle: &MerkleTree, target_mt_r
             .to_indy(IndyErrorKind::InvalidStructure, "Invalid transaction -- can not transform to bytes")?;
 
         merkle.append(txn.clone())?;
-        txns_to_drop.push(txn);
+        txns.push(txn);
     }
 
-    check_cons_proofs(&merkle, &rep.consProof, target_mt_root, target_mt_size)?;
-    merkle_tree_factory::dump_new_txns(pool_name, &txns_to_drop)?;
-    Ok(merkle)
+    Ok(txns)
+}
+
+fn _ledger_sync_result(ledger_size: usize) -> String {
+    json!({"ledgerSize": ledger_size}).to_string()
 }
 
 fn _send_ok_replies(cmd_ids: &[CommandHandle], msg: &str) {
@@ -740,13 +1024,18 @@ fn _get_msg_result_without_state_proof(msg: &str) -> IndyResult<(SJsonValue, SJs
     Ok((msg_result, msg_result_without_proof))
 }
 
-fn _check_state_proof(msg_result: &SJsonValue, f: usize, gen: &Generator, bls_keys: &Nodes, raw_msg: &str, sp_key: Option<&[u8]>, requested_timestamps: (Option<u64>, Option<u64>), last_write_time: u64) -> bool {
+fn _check_state_proof(msg_result: &SJsonValue, f: usize, gen: &Generator, bls_keys: &Nodes, raw_msg: &str, sp_key: Option<&[u8]>, requested_timestamps: (Option<u64>, Option<u64>), last_write_time: u64,
+                      pool_name: &str, node_alias: &str) -> bool {
     debug!("TransactionHandler::process_reply: Try to verify proof and signature >>");
 
     let proof_checking_res = match state_proof::parse_generic_reply_for_proof_checking(&msg_result, raw_msg, sp_key) {
         Some(parsed_sps) => {
             debug!("TransactionHandler::process_reply: Proof and signature are present");
-            state_proof::verify_parsed_sp(parsed_sps, bls_keys, f, gen)
+            let res = state_proof::verify_parsed_sp(parsed_sps, bls_keys, f, gen);
+            if !res {
+                status::bls_verification_failed(pool_name, node_alias);
+            }
+            res
         }
         None => false
     };
@@ -757,6 +1046,12 @@ fn _check_state_proof(msg_result: &SJsonValue, f: usize, gen: &Generator, bls_ke
     res
 }
 
+/// Returns None if the reply isn't a reply on a written transaction or can't be verified without consensus
+fn _check_write_reply(raw_msg: &str, request: &SJsonValue, f: usize, gen: &Generator, bls_keys: &Nodes) -> Option<bool> {
+    let (msg_result, _) = _get_msg_result_without_state_proof(raw_msg).ok()?;
+    state_proof::verify_write_reply(&msg_result, request, bls_keys, f, gen)
+}
+
 fn _check_freshness(msg_result: &SJsonValue, requested_timestamps: (Option<u64>, Option<u64>), last_write_time: u64) -> bool {
     debug!("TransactionHandler::_check_freshness: requested_timestamps: {:?} >>", requested_timestamps);
 
@@ -843,7 +1138,7 @@ pub mod tests {
     use crate::services::pool::types::{ConsistencyProof, LedgerStatus, Reply, ReplyResultV1, ReplyTxnV1, ReplyV1, Response, ResponseMetadata, ResponseV1};
     use crate::utils::test;
     use crate::utils::test::test_pool_create_poolfile;
-    use crate::domain::pool::NUMBER_READ_NODES;
+    use crate::domain::pool::{NUMBER_READ_NODES, PoolTransport};
 
     use super::*;
     use std::io::Write;
@@ -857,12 +1152,14 @@ pub mod tests {
     const SIMPLE_REPLY: &str = r#"{"result":{}}"#;
     const REJECT_REPLY: &str = r#"{"op":"REJECT", "result": {"reason": "reject"}}"#;
     const NACK_REPLY: &str = r#"{"op":"REQNACK", "result": {"reason": "reqnack"}}"#;
+    const WRITE_REPLY: &str = r#"{"op":"REPLY", "result": {"txn": {"type": "1"}, "txnMetadata": {"seqNo": 1}, "ver": "1", "rootHash": "", "auditPath": [], "multiSignature": {"signature": "", "participants": [], "value": {"ledger_id": 1, "txn_root_hash": ""}}}}"#;
+    const WRITE_REPLY_WITHOUT_MULTI_SIGNATURE: &str = r#"{"op":"REPLY", "result": {"txn": {"type": "1"}, "txnMetadata": {"seqNo": 1}, "ver": "1"}}"#;
 
     #[derive(Debug)]
     pub struct MockRequestHandler {}
 
     impl<T: Networker> RequestHandler<T> for MockRequestHandler {
-        fn new(_networker: Rc<RefCell<T>>, _f: usize, _cmd_ids: &[CommandHandle], _nodes: &Nodes, _pool_name: &str, _timeout: i64, _extended_timeout: i64, _number_read_nodes: u8) -> Self {
+        fn new(_networker: Rc<RefCell<T>>, _f: usize, _cmd_ids: &[CommandHandle], _nodes: &Nodes, _pool_name: &str, _timeout: i64, _extended_timeout: i64, _number_read_nodes: u8, _verify_write_replies: bool) -> Self {
             MockRequestHandler {}
         }
 
@@ -924,7 +1221,11 @@ pub mod tests {
     }
 
     fn _request_handler(pool_name: &str, f: usize, nodes_cnt: usize) -> RequestHandlerImpl<MockNetworker> {
-        let networker = Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![])));
+        _request_handler_with_write_verification(pool_name, f, nodes_cnt, false)
+    }
+
+    fn _request_handler_with_write_verification(pool_name: &str, f: usize, nodes_cnt: usize, verify_write_replies: bool) -> RequestHandlerImpl<MockNetworker> {
+        let networker = Rc::new(RefCell::new(MockNetworker::new(0, 0, vec![], PoolTransport::Zmq).unwrap()));
 
         let mut default_nodes: Nodes = HashMap::new();
         default_nodes.insert(NODE.to_string(), None);
@@ -943,7 +1244,8 @@ pub mod tests {
                                 pool_name,
                                 0,
                                 0,
-                                NUMBER_READ_NODES)
+                                NUMBER_READ_NODES,
+                                verify_write_replies)
     }
 
     // required because of dumping txns to cache
@@ -1100,6 +1402,66 @@ pub mod tests {
             assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
         }
 
+        #[test]
+        fn request_handler_process_reply_event_from_consensus_state_works_for_write_verification_and_not_written_txn() {
+            let mut request_handler = _request_handler_with_write_verification("request_handler_process_reply_event_from_consensus_state_works_for_write_verification_and_not_written_txn", 0, 1, true);
+            request_handler.process_event(Some(RequestEvent::CustomConsensusRequest(MESSAGE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), SIMPLE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_consensus_state_works_for_write_verification_failed() {
+            let mut request_handler = _request_handler_with_write_verification("request_handler_process_reply_event_from_consensus_state_works_for_write_verification_failed", 0, 1, true);
+            request_handler.process_event(Some(RequestEvent::CustomConsensusRequest(MESSAGE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_consensus_state_works_for_write_verification_failed_on_some_nodes() {
+            let mut request_handler = _request_handler_with_write_verification("request_handler_process_reply_event_from_consensus_state_works_for_write_verification_failed_on_some_nodes", 1, 4, true);
+            request_handler.process_event(Some(RequestEvent::CustomConsensusRequest(MESSAGE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY.to_string(), NODE_2.to_string(), REQ_ID.to_string())));
+
+            let request_handler = request_handler.request_wrapper.unwrap();
+            match request_handler.state {
+                RequestState::Consensus(state) => {
+                    assert_eq!(2, state.denied_nodes.len());
+                    assert!(state.replies.is_empty());
+                }
+                _ => assert!(false)
+            }
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_consensus_state_works_for_write_verification_and_missed_multi_signature() {
+            let mut request_handler = _request_handler_with_write_verification("request_handler_process_reply_event_from_consensus_state_works_for_write_verification_and_missed_multi_signature", 1, 4, true);
+            request_handler.process_event(Some(RequestEvent::CustomConsensusRequest(MESSAGE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY_WITHOUT_MULTI_SIGNATURE.to_string(), NODE.to_string(), REQ_ID.to_string())));
+
+            match request_handler.request_wrapper.as_ref().unwrap().state {
+                RequestState::Consensus(ref state) => {
+                    assert!(state.denied_nodes.is_empty());
+                    assert_eq!(1, state.replies.len());
+                }
+                _ => assert!(false)
+            }
+
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY_WITHOUT_MULTI_SIGNATURE.to_string(), NODE_2.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_reply_event_from_consensus_state_works_for_write_reply_without_verification() {
+            let mut request_handler = _request_handler("request_handler_process_reply_event_from_consensus_state_works_for_write_reply_without_verification", 1, 4);
+            request_handler.process_event(Some(RequestEvent::CustomConsensusRequest(MESSAGE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY.to_string(), NODE.to_string(), REQ_ID.to_string())));
+            request_handler.process_event(Some(RequestEvent::Reply(Reply::default(), WRITE_REPLY.to_string(), NODE_2.to_string(), REQ_ID.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
         #[test]
         fn request_handler_process_reply_event_from_consensus_state_works_for_consensus_reachable() {
             let mut request_handler = _request_handler("request_handler_process_reply_event_from_consensus_state_works_for_consensus_reachable", 1, 2);
@@ -1664,6 +2026,136 @@ pub mod tests {
         }
     }
 
+    mod ledger_sync {
+        use super::*;
+        use rust_base58::ToBase58;
+
+        const LEDGER_ID: u8 = 1;
+
+        fn _txns() -> Vec<SJsonValue> {
+            vec![
+                json!({"txn": {"type": "1", "data": {"dest": "V4SGRU86Z58d6TV7PBUe6f"}}, "txnMetadata": {"seqNo": 1}}),
+                json!({"txn": {"type": "1", "data": {"dest": "7wY2iDEbVRWqjiJDVKKUBX"}}, "txnMetadata": {"seqNo": 2}}),
+            ]
+        }
+
+        fn _consistency_proof() -> ConsistencyProof {
+            let mut mt = MerkleTree::from_vec(Vec::new()).unwrap();
+            for txn in _txns() {
+                mt.append(rmp_serde::to_vec_named(&txn).unwrap()).unwrap();
+            }
+
+            ConsistencyProof {
+                seqNoEnd: 2,
+                ledgerId: LEDGER_ID as usize,
+                newMerkleRoot: mt.root_hash().as_slice().to_base58(),
+                ..ConsistencyProof::default()
+            }
+        }
+
+        fn _catchup_rep(txns: Vec<(usize, SJsonValue)>) -> CatchupRep {
+            CatchupRep {
+                ledgerId: LEDGER_ID as usize,
+                consProof: Vec::new(),
+                txns: txns.into_iter().map(|(seq_no, txn)| (seq_no.to_string(), txn)).collect(),
+            }
+        }
+
+        fn _request_handler_in_single_state(pool_name: &str, nodes_cnt: usize) -> RequestHandlerImpl<MockNetworker> {
+            let mut request_handler = _request_handler(pool_name, 0, nodes_cnt);
+            request_handler.process_event(Some(RequestEvent::SyncLedger(LEDGER_ID, None)));
+            request_handler.process_event(Some(RequestEvent::ConsistencyProof(_consistency_proof(), NODE.to_string())));
+            request_handler
+        }
+
+        #[test]
+        fn request_handler_process_sync_ledger_event_from_start_works() {
+            let mut request_handler = _request_handler("request_handler_process_sync_ledger_event_from_start_works", 0, 1);
+            request_handler.process_event(Some(RequestEvent::SyncLedger(LEDGER_ID, None)));
+            assert_match!(RequestState::LedgerSyncConsensus(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_consistency_proof_event_from_ledger_sync_consensus_state_works() {
+            let request_handler = _request_handler_in_single_state("request_handler_process_consistency_proof_event_from_ledger_sync_consensus_state_works", 1);
+            assert_match!(RequestState::LedgerSyncSingle(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_consistency_proof_event_from_ledger_sync_consensus_state_works_for_not_completed() {
+            let mut request_handler = _request_handler("request_handler_process_consistency_proof_event_from_ledger_sync_consensus_state_works_for_not_completed", 1, 4);
+            request_handler.process_event(Some(RequestEvent::SyncLedger(LEDGER_ID, None)));
+            request_handler.process_event(Some(RequestEvent::ConsistencyProof(_consistency_proof(), NODE.to_string())));
+            assert_match!(RequestState::LedgerSyncConsensus(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_ledger_status_event_from_ledger_sync_consensus_state_works_for_synced_ledger() {
+            let mut request_handler = _request_handler("request_handler_process_ledger_status_event_from_ledger_sync_consensus_state_works_for_synced_ledger", 0, 1);
+            request_handler.process_event(Some(RequestEvent::SyncLedger(LEDGER_ID, None)));
+
+            let ls = LedgerStatus {
+                ledgerId: LEDGER_ID,
+                merkleRoot: MerkleTree::from_vec(Vec::new()).unwrap().root_hash().as_slice().to_base58(),
+                ..LedgerStatus::default()
+            };
+            request_handler.process_event(Some(RequestEvent::LedgerStatus(ls, Some(NODE.to_string()), None)));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_timeout_event_from_ledger_sync_consensus_state_works() {
+            let mut request_handler = _request_handler("request_handler_process_timeout_event_from_ledger_sync_consensus_state_works", 0, 1);
+            request_handler.process_event(Some(RequestEvent::SyncLedger(LEDGER_ID, None)));
+            request_handler.process_event(Some(RequestEvent::Timeout(ledger_sync_req_id(LEDGER_ID as usize), NODE.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_catchup_reply_event_from_ledger_sync_single_state_works() {
+            let pool_name = "request_handler_process_catchup_reply_event_from_ledger_sync_single_state_works";
+            test::cleanup_pool(pool_name);
+
+            let mut request_handler = _request_handler_in_single_state(pool_name, 1);
+
+            let txns = _txns();
+            request_handler.process_event(Some(RequestEvent::CatchupRep(_catchup_rep(vec![(1, txns[0].clone()), (2, txns[1].clone())]), NODE.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+
+            assert_eq!(txns, merkle_tree_factory::read_replica_txns(pool_name, LEDGER_ID, 1, 2).unwrap());
+
+            test::cleanup_pool(pool_name);
+        }
+
+        #[test]
+        fn request_handler_process_catchup_reply_event_from_ledger_sync_single_state_works_for_late_reply() {
+            let mut request_handler = _request_handler_in_single_state("request_handler_process_catchup_reply_event_from_ledger_sync_single_state_works_for_late_reply", 1);
+            request_handler.process_event(Some(RequestEvent::CatchupRep(_catchup_rep(vec![(2, _txns()[1].clone())]), NODE.to_string())));
+            assert_match!(RequestState::LedgerSyncSingle(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_catchup_reply_event_from_ledger_sync_single_state_works_for_invalid_txns() {
+            let mut request_handler = _request_handler_in_single_state("request_handler_process_catchup_reply_event_from_ledger_sync_single_state_works_for_invalid_txns", 2);
+            request_handler.process_event(Some(RequestEvent::CatchupRep(_catchup_rep(vec![(1, _txns()[1].clone()), (2, _txns()[0].clone())]), NODE.to_string())));
+            assert_match!(RequestState::LedgerSyncSingle(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_timeout_event_from_ledger_sync_single_state_works_for_all_nodes_timed_out() {
+            let mut request_handler = _request_handler_in_single_state("request_handler_process_timeout_event_from_ledger_sync_single_state_works_for_all_nodes_timed_out", 1);
+            request_handler.process_event(Some(RequestEvent::Timeout(ledger_sync_req_id(LEDGER_ID as usize), NODE.to_string())));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+
+        #[test]
+        fn request_handler_process_terminate_event_from_ledger_sync_single_state_works() {
+            let mut request_handler = _request_handler_in_single_state("request_handler_process_terminate_event_from_ledger_sync_single_state_works", 1);
+            request_handler.process_event(Some(RequestEvent::Terminate));
+            assert_match!(RequestState::Finish(_), request_handler.request_wrapper.unwrap().state);
+        }
+    }
+
     mod full {
         use super::*;
 
@@ -0,0 +1,698 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+use rust_base58::{FromBase58, ToBase58};
+use serde_json;
+use serde_json::Value as SJsonValue;
+use ursa::bls::Generator;
+
+use crate::domain::ledger::constants;
+use crate::domain::pool::SnapshotImportOptions;
+use indy_api_types::errors::prelude::*;
+use crate::services::ledger::merkletree::merkletree::MerkleTree;
+use crate::services::pool::{merkle_tree_factory, Nodes};
+use crate::services::pool::pool::{_get_f, _get_nodes_and_remotes};
+use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+use crate::services::pool::state_proof;
+use crate::utils::environment;
+
+const SNAPSHOT_FILE: &str = "snapshot";
+const SNAPSHOT_EXT: &str = "json";
+
+const POOL_LEDGER_ID: u64 = 0;
+pub const DOMAIN_LEDGER_ID: u64 = 1;
+
+const READ_REQUESTS: [&str; 13] = [constants::GET_TXN, constants::GET_ATTR, constants::GET_NYM, constants::GET_SCHEMA,
+    constants::GET_CRED_DEF, constants::GET_REVOC_REG_DEF, constants::GET_REVOC_REG, constants::GET_REVOC_REG_DELTA,
+    constants::GET_VALIDATOR_INFO, constants::GET_AUTH_RULE, constants::GET_TXN_AUTHR_AGRMT,
+    constants::GET_TXN_AUTHR_AGRMT_AML, constants::GET_FROZEN_LEDGERS];
+
+/// Locally stored copy of the domain ledger transactions.
+///
+/// Snapshot contains the whole ledger starting from seqNo 1 in protocol version 2 format
+/// and multi-signature of the pool for the ledger txn root.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerSnapshot {
+    pub ledger_id: u64,
+    pub txns: Vec<SJsonValue>,
+    pub multi_signature: Option<SJsonValue>,
+}
+
+impl LedgerSnapshot {
+    pub fn from_json(json: &str) -> IndyResult<LedgerSnapshot> {
+        let snapshot: LedgerSnapshot = serde_json::from_str(json)
+            .to_indy(IndyErrorKind::InvalidStructure, "Invalid ledger snapshot json")?;
+
+        if snapshot.ledger_id != DOMAIN_LEDGER_ID {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Unsupported snapshot ledger id: {}", snapshot.ledger_id)));
+        }
+
+        for (idx, txn) in snapshot.txns.iter().enumerate() {
+            if txn["txnMetadata"]["seqNo"].as_u64() != Some(idx as u64 + 1) {
+                return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                   format!("Snapshot txns must be ordered by seqNo without gaps starting from 1, invalid txn at position {}", idx)));
+            }
+        }
+
+        Ok(snapshot)
+    }
+
+    pub fn load(pool_name: &str) -> IndyResult<LedgerSnapshot> {
+        let path = _snapshot_path(pool_name);
+
+        if !path.exists() {
+            return Err(err_msg(IndyErrorKind::InvalidState,
+                               format!("No ledger snapshot imported for pool {:?}", pool_name)));
+        }
+
+        let json = fs::read_to_string(&path)
+            .to_indy(IndyErrorKind::IOError, "Can't read ledger snapshot file")?;
+
+        LedgerSnapshot::from_json(&json)
+    }
+
+    pub fn store(&self, pool_name: &str) -> IndyResult<()> {
+        let json = serde_json::to_string(self)
+            .to_indy(IndyErrorKind::InvalidState, "Can't serialize ledger snapshot")?;
+
+        let mut f = fs::File::create(_snapshot_path(pool_name))
+            .to_indy(IndyErrorKind::IOError, "Can't create ledger snapshot file")?;
+
+        f.write_all(json.as_bytes())
+            .to_indy(IndyErrorKind::IOError, "Can't write ledger snapshot file")?;
+
+        f.flush()
+            .to_indy(IndyErrorKind::IOError, "Can't write ledger snapshot file")
+    }
+
+    /// Checks that snapshot is signed by the pool nodes known from the genesis transactions and catchup cache.
+    pub fn verify(&self, pool_name: &str) -> IndyResult<()> {
+        self.verify_root()?;
+
+        let merkle = merkle_tree_factory::create(pool_name)?;
+        let (nodes, _) = _get_nodes_and_remotes(&merkle)?;
+        self.verify_signature(&nodes, _get_f(nodes.len()))
+    }
+
+    /// Checks that snapshot txns match txn root hash of the multi-signature.
+    pub fn verify_root(&self) -> IndyResult<()> {
+        let multi_signature = self.multi_signature.as_ref()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Snapshot doesn't contain multi-signature"))?;
+
+        if multi_signature["value"]["ledger_id"].as_u64() != Some(self.ledger_id) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Snapshot multi-signature is for another ledger"));
+        }
+
+        let expected_root = multi_signature["value"]["txn_root_hash"].as_str()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Snapshot multi-signature doesn't contain txn_root_hash"))?;
+
+        let root = self.root_hash()?;
+
+        if root != expected_root {
+            return Err(err_msg(IndyErrorKind::InvalidStructure,
+                               format!("Snapshot txns root hash {} doesn't match signed root hash {}", root, expected_root)));
+        }
+
+        Ok(())
+    }
+
+    /// Checks multi-signature of the snapshot against BLS keys of the pool nodes.
+    pub fn verify_signature(&self, nodes: &Nodes, f: usize) -> IndyResult<()> {
+        let multi_signature = self.multi_signature.as_ref()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Snapshot doesn't contain multi-signature"))?;
+
+        let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidState, "Invalid default generator"))?)?;
+
+        if !state_proof::verify_multi_signature(multi_signature, nodes, f, &gen) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Snapshot multi-signature verification failed"));
+        }
+
+        Ok(())
+    }
+
+    pub fn root_hash(&self) -> IndyResult<String> {
+        let mut mt = MerkleTree::from_vec(Vec::new())?;
+
+        for txn in &self.txns {
+            mt.append(rmp_serde::encode::to_vec_named(txn)
+                .to_indy(IndyErrorKind::InvalidState, "Can't encode snapshot txn as message pack")?)?;
+        }
+
+        Ok(mt.root_hash().to_base58())
+    }
+
+    /// Builds node-like reply for read request. Write requests are refused.
+    pub fn answer(&self, pool_name: &str, request: &str) -> IndyResult<String> {
+        let request: SJsonValue = serde_json::from_str(request)
+            .to_indy(IndyErrorKind::InvalidStructure, "Request is invalid json")?;
+
+        let operation = &request["operation"];
+        let type_ = operation["type"].as_str()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Request doesn't contain operation type"))?;
+
+        let mut result = match type_ {
+            constants::GET_NYM => self._get_nym(operation),
+            constants::GET_ATTR => self._get_attr(operation),
+            constants::GET_SCHEMA => self._get_schema(operation),
+            constants::GET_CRED_DEF => self._get_cred_def(operation),
+            constants::GET_REVOC_REG_DEF => self._get_revoc_reg_def(operation),
+            constants::GET_REVOC_REG => self._get_revoc_reg(operation),
+            constants::GET_REVOC_REG_DELTA => self._get_revoc_reg_delta(operation),
+            constants::GET_TXN => self._get_txn(pool_name, operation)?,
+            type_ if _is_write(type_) =>
+                return Err(err_msg(IndyErrorKind::InvalidState,
+                                   format!("Pool is opened in offline mode, write request of type {} is refused", type_))),
+            type_ => return Err(err_msg(IndyErrorKind::InvalidState,
+                                        format!("Request of type {} can't be answered from ledger snapshot", type_)))
+        };
+
+        result["type"] = json!(type_);
+        result["reqId"] = request["reqId"].clone();
+        result["identifier"] = request["identifier"].clone();
+
+        Ok(json!({"op": "REPLY", "result": result}).to_string())
+    }
+
+    fn _txns_of_type<'a>(&'a self, type_: &'a str) -> impl DoubleEndedIterator<Item=&'a SJsonValue> + 'a {
+        self.txns.iter().filter(move |txn| txn["txn"]["type"].as_str() == Some(type_))
+    }
+
+    fn _get_nym(&self, operation: &SJsonValue) -> SJsonValue {
+        let dest = &operation["dest"];
+
+        let mut nym: Option<(SJsonValue, &SJsonValue)> = None;
+
+        for txn in self._txns_of_type(constants::NYM).filter(|txn| txn["txn"]["data"]["dest"] == *dest) {
+            let data = &txn["txn"]["data"];
+
+            let state = nym.get_or_insert_with(|| (json!({
+                "identifier": txn["txn"]["metadata"]["from"],
+                "dest": dest,
+                "role": null,
+                "verkey": null,
+            }), txn));
+
+            if let Some(verkey) = data.get("verkey") {
+                state.0["verkey"] = verkey.clone();
+            }
+            if let Some(role) = data.get("role") {
+                state.0["role"] = role.clone();
+            }
+            state.1 = txn;
+        }
+
+        match nym {
+            Some((data, txn)) => _result(Some(txn), json!(data.to_string())),
+            None => _result(None, SJsonValue::Null)
+        }
+    }
+
+    fn _get_attr(&self, operation: &SJsonValue) -> SJsonValue {
+        let dest = &operation["dest"];
+
+        let (key, name) = ["raw", "hash", "enc"].iter()
+            .find_map(|key| operation[*key].as_str().map(|name| (*key, name)))
+            .unwrap_or(("raw", ""));
+
+        let txn = self._txns_of_type(constants::ATTRIB)
+            .rev()
+            .find(|txn| {
+                let data = &txn["txn"]["data"];
+                data["dest"] == *dest && match key {
+                    "raw" => data["raw"].as_str()
+                        .and_then(|raw| serde_json::from_str::<SJsonValue>(raw).ok())
+                        .map(|raw| raw.get(name).is_some())
+                        .unwrap_or(false),
+                    key => data[key].as_str() == Some(name),
+                }
+            });
+
+        let mut result = _result(txn, txn.map(|txn| txn["txn"]["data"][key].clone()).unwrap_or(SJsonValue::Null));
+        result["dest"] = dest.clone();
+        result[key] = json!(name);
+        result
+    }
+
+    fn _get_schema(&self, operation: &SJsonValue) -> SJsonValue {
+        let dest = &operation["dest"];
+        let name = &operation["data"]["name"];
+        let version = &operation["data"]["version"];
+
+        let txn = self._txns_of_type(constants::SCHEMA)
+            .find(|txn| {
+                let data = &txn["txn"]["data"]["data"];
+                txn["txn"]["metadata"]["from"] == *dest && data["name"] == *name && data["version"] == *version
+            });
+
+        let data = match txn {
+            Some(txn) => txn["txn"]["data"]["data"].clone(),
+            None => json!({"name": name, "version": version})
+        };
+
+        let mut result = _result(txn, data);
+        result["dest"] = dest.clone();
+        result
+    }
+
+    fn _get_cred_def(&self, operation: &SJsonValue) -> SJsonValue {
+        let origin = &operation["origin"];
+
+        let txn = self._txns_of_type(constants::CRED_DEF)
+            .find(|txn| {
+                let data = &txn["txn"]["data"];
+                txn["txn"]["metadata"]["from"] == *origin
+                    && data["ref"] == operation["ref"]
+                    && data["signature_type"] == operation["signature_type"]
+                    && data["tag"] == operation["tag"]
+            });
+
+        let mut result = _result(txn, txn.map(|txn| txn["txn"]["data"]["data"].clone()).unwrap_or(SJsonValue::Null));
+        result["identifier"] = origin.clone();
+        result["origin"] = origin.clone();
+        result["ref"] = operation["ref"].clone();
+        result["signature_type"] = operation["signature_type"].clone();
+        result["tag"] = operation["tag"].clone();
+        result
+    }
+
+    fn _get_revoc_reg_def(&self, operation: &SJsonValue) -> SJsonValue {
+        let txn = self._txns_of_type(constants::REVOC_REG_DEF)
+            .find(|txn| txn["txn"]["data"]["id"] == operation["id"]);
+
+        let data = txn.map(|txn| {
+            let mut data = txn["txn"]["data"].clone();
+            data["ver"] = json!("1.0");
+            data
+        }).unwrap_or(SJsonValue::Null);
+
+        _result(txn, data)
+    }
+
+    fn _revoc_reg_entries(&self, rev_reg_def_id: &SJsonValue) -> Vec<&SJsonValue> {
+        self._txns_of_type(constants::REVOC_REG_ENTRY)
+            .filter(|txn| txn["txn"]["data"]["revocRegDefId"] == *rev_reg_def_id)
+            .collect()
+    }
+
+    fn _revoc_reg_entry_at(&self, rev_reg_def_id: &SJsonValue, timestamp: u64) -> Option<&SJsonValue> {
+        self._revoc_reg_entries(rev_reg_def_id)
+            .into_iter()
+            .rev()
+            .find(|txn| _txn_time(txn).map(|time| time <= timestamp).unwrap_or(false))
+    }
+
+    fn _get_revoc_reg(&self, operation: &SJsonValue) -> SJsonValue {
+        let rev_reg_def_id = &operation["revocRegDefId"];
+        let timestamp = operation["timestamp"].as_u64().unwrap_or(0);
+
+        let txn = self._revoc_reg_entry_at(rev_reg_def_id, timestamp);
+
+        let mut result = _result(txn, txn.map(_accum_value).unwrap_or(SJsonValue::Null));
+        result["revocRegDefId"] = rev_reg_def_id.clone();
+        result
+    }
+
+    fn _get_revoc_reg_delta(&self, operation: &SJsonValue) -> SJsonValue {
+        let rev_reg_def_id = &operation["revocRegDefId"];
+        let from = operation["from"].as_u64();
+        let to = operation["to"].as_u64().unwrap_or(0);
+
+        let txn_to = self._revoc_reg_entry_at(rev_reg_def_id, to);
+        let txn_from = from.and_then(|from| self._revoc_reg_entry_at(rev_reg_def_id, from));
+
+        let data = match txn_to {
+            Some(txn_to) => {
+                let mut issued: BTreeSet<u64> = BTreeSet::new();
+                let mut revoked: BTreeSet<u64> = BTreeSet::new();
+
+                let from_time = txn_from.and_then(_txn_time);
+                let to_time = _txn_time(txn_to);
+
+                for txn in self._revoc_reg_entries(rev_reg_def_id)
+                    .into_iter()
+                    .filter(|txn| _txn_time(txn) > from_time && _txn_time(txn) <= to_time) {
+                    let value = &txn["txn"]["data"]["value"];
+
+                    for idx in value["issued"].as_array().into_iter().flatten().filter_map(SJsonValue::as_u64) {
+                        revoked.remove(&idx);
+                        issued.insert(idx);
+                    }
+                    for idx in value["revoked"].as_array().into_iter().flatten().filter_map(SJsonValue::as_u64) {
+                        issued.remove(&idx);
+                        revoked.insert(idx);
+                    }
+                }
+
+                json!({
+                    "value": {
+                        "accum_from": txn_from.map(|txn| json!({"value": _accum_value(txn)["value"], "txnTime": _txn_time(txn)})),
+                        "accum_to": {"value": _accum_value(txn_to)["value"], "txnTime": to_time},
+                        "issued": issued,
+                        "revoked": revoked,
+                    }
+                })
+            }
+            None => SJsonValue::Null
+        };
+
+        let mut result = _result(txn_to, data);
+        result["revocRegDefId"] = rev_reg_def_id.clone();
+        result
+    }
+
+    fn _get_txn(&self, pool_name: &str, operation: &SJsonValue) -> IndyResult<SJsonValue> {
+        let seq_no = operation["data"].as_u64().unwrap_or(0);
+
+        let txn: Option<SJsonValue> = match operation["ledgerId"].as_u64().unwrap_or(DOMAIN_LEDGER_ID) {
+            DOMAIN_LEDGER_ID => seq_no.checked_sub(1)
+                .and_then(|idx| self.txns.get(idx as usize))
+                .cloned(),
+            POOL_LEDGER_ID => {
+                // Pool ledger is available from genesis and catchup cache
+                let merkle = merkle_tree_factory::create(pool_name)?;
+                match seq_no.checked_sub(1).and_then(|idx| merkle.iter().nth(idx as usize)) {
+                    Some(txn) => Some(rmp_serde::decode::from_slice(txn.as_slice())
+                        .to_indy(IndyErrorKind::InvalidState, "MerkleTree contains invalid item")?),
+                    None => None
+                }
+            }
+            ledger_id => return Err(err_msg(IndyErrorKind::InvalidState,
+                                            format!("Ledger {} isn't available in offline mode", ledger_id)))
+        };
+
+        Ok(json!({
+            "seqNo": txn.as_ref().map(|_| seq_no),
+            "data": txn,
+        }))
+    }
+}
+
+/// Pool opened in offline mode
+pub struct OfflinePool {
+    pub name: String,
+    pub snapshot: LedgerSnapshot,
+}
+
+impl OfflinePool {
+    pub fn new(name: &str) -> IndyResult<OfflinePool> {
+        Ok(OfflinePool {
+            name: name.to_string(),
+            snapshot: OfflinePool::_load(name)?,
+        })
+    }
+
+    pub fn refresh(&mut self) -> IndyResult<()> {
+        self.snapshot = OfflinePool::_load(&self.name)?;
+        Ok(())
+    }
+
+    // Snapshot file can be replaced after import, so it is verified again before serving any read
+    fn _load(name: &str) -> IndyResult<LedgerSnapshot> {
+        let snapshot = LedgerSnapshot::load(name)?;
+        snapshot.verify(name)?;
+        Ok(snapshot)
+    }
+
+    pub fn answer(&self, request: &str) -> IndyResult<String> {
+        self.snapshot.answer(&self.name, request)
+    }
+
+    pub fn ledger_size(&self, ledger_id: u8) -> IndyResult<usize> {
+        match u64::from(ledger_id) {
+            DOMAIN_LEDGER_ID => Ok(self.snapshot.txns.len()),
+            _ => merkle_tree_factory::replica_size(&self.name, ledger_id)
+        }
+    }
+
+    pub fn ledger_txns(&self, ledger_id: u8, from: usize, to: usize) -> IndyResult<Vec<SJsonValue>> {
+        match u64::from(ledger_id) {
+            DOMAIN_LEDGER_ID => from.checked_sub(1)
+                .and_then(|from| self.snapshot.txns.get(from..to))
+                .map(<[SJsonValue]>::to_vec)
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState,
+                                       format!("Ledger snapshot contains only {} transactions", self.snapshot.txns.len()))),
+            _ => merkle_tree_factory::read_replica_txns(&self.name, ledger_id, from, to)
+        }
+    }
+}
+
+pub fn import(pool_name: &str, snapshot_path: &str, _options: SnapshotImportOptions) -> IndyResult<()> {
+    let pool_path = environment::pool_path(pool_name);
+
+    if !pool_path.exists() {
+        return Err(err_msg(IndyErrorKind::PoolNotCreated, format!("Pool is not created for name: {:?}", pool_name)));
+    }
+
+    let json = fs::read_to_string(snapshot_path)
+        .to_indy(IndyErrorKind::IOError, format!("Can't read ledger snapshot file {:?}", snapshot_path))?;
+
+    let snapshot = LedgerSnapshot::from_json(&json)?;
+
+    snapshot.verify(pool_name)?;
+
+    snapshot.store(pool_name)
+}
+
+/// Creates snapshot of the domain ledger from the first `ledger_size` txns of the local replica
+/// filled by catchup. `multi_signature` must sign the txn root of exactly these txns.
+pub fn create(pool_name: &str, ledger_size: usize, multi_signature: SJsonValue) -> IndyResult<LedgerSnapshot> {
+    let snapshot = LedgerSnapshot {
+        ledger_id: DOMAIN_LEDGER_ID,
+        txns: merkle_tree_factory::read_replica_txns(pool_name, DOMAIN_LEDGER_ID as u8, 1, ledger_size)?,
+        multi_signature: Some(multi_signature),
+    };
+
+    snapshot.verify(pool_name)?;
+    snapshot.store(pool_name)?;
+
+    Ok(snapshot)
+}
+
+fn _snapshot_path(pool_name: &str) -> PathBuf {
+    let mut path = environment::pool_path(pool_name);
+    path.push(SNAPSHOT_FILE);
+    path.set_extension(SNAPSHOT_EXT);
+    path
+}
+
+fn _is_write(type_: &str) -> bool {
+    constants::REQUESTS.contains(&type_) && !READ_REQUESTS.contains(&type_)
+}
+
+fn _txn_time(txn: &SJsonValue) -> Option<u64> {
+    txn["txnMetadata"]["txnTime"].as_u64()
+}
+
+fn _accum_value(txn: &SJsonValue) -> SJsonValue {
+    json!({"value": {"accum": txn["txn"]["data"]["value"]["accum"]}})
+}
+
+fn _result(txn: Option<&SJsonValue>, data: SJsonValue) -> SJsonValue {
+    json!({
+        "seqNo": txn.map(|txn| &txn["txnMetadata"]["seqNo"]),
+        "txnTime": txn.and_then(_txn_time),
+        "data": data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ursa::bls::{Bls, MultiSignature, SignKey, VerKey};
+
+    use super::*;
+
+    const DID: &str = "VsKV7grR1BUE29mG2Fm2kX";
+    const TRUSTEE_DID: &str = "V4SGRU86Z58d6TV7PBUe6f";
+    const REV_REG_DEF_ID: &str = "V4SGRU86Z58d6TV7PBUe6f:4:V4SGRU86Z58d6TV7PBUe6f:3:CL:1:TAG_1:CL_ACCUM:TAG_1";
+
+    fn _txn(seq_no: u64, txn_time: u64, type_: &str, data: SJsonValue) -> SJsonValue {
+        json!({
+            "txn": {"type": type_, "data": data, "metadata": {"from": TRUSTEE_DID, "reqId": seq_no}},
+            "txnMetadata": {"seqNo": seq_no, "txnTime": txn_time},
+            "reqSignature": {},
+            "ver": "1"
+        })
+    }
+
+    fn _snapshot() -> LedgerSnapshot {
+        LedgerSnapshot {
+            ledger_id: DOMAIN_LEDGER_ID,
+            txns: vec![
+                _txn(1, 100, constants::NYM, json!({"dest": DID, "verkey": "~NcYxiDXkpYi6ov5FcYDi1e"})),
+                _txn(2, 110, constants::SCHEMA, json!({"data": {"name": "gvt", "version": "1.0", "attr_names": ["name"]}})),
+                _txn(3, 120, constants::NYM, json!({"dest": DID, "role": "101"})),
+                _txn(4, 130, constants::REVOC_REG_ENTRY, json!({"revocRegDefId": REV_REG_DEF_ID, "value": {"accum": "1", "issued": [1, 2, 3]}})),
+                _txn(5, 140, constants::REVOC_REG_ENTRY, json!({"revocRegDefId": REV_REG_DEF_ID, "value": {"prevAccum": "1", "accum": "2", "revoked": [2]}})),
+            ],
+            multi_signature: None,
+        }
+    }
+
+    fn _request(operation: SJsonValue) -> String {
+        json!({"reqId": 1, "identifier": TRUSTEE_DID, "operation": operation, "protocolVersion": 2}).to_string()
+    }
+
+    fn _answer(snapshot: &LedgerSnapshot, operation: SJsonValue) -> SJsonValue {
+        let reply = snapshot.answer("", &_request(operation)).unwrap();
+        let reply: SJsonValue = serde_json::from_str(&reply).unwrap();
+        assert_eq!("REPLY", reply["op"]);
+        reply["result"].clone()
+    }
+
+    fn _signed_value(snapshot: &LedgerSnapshot) -> SJsonValue {
+        json!({
+            "ledger_id": DOMAIN_LEDGER_ID,
+            "txn_root_hash": snapshot.root_hash().unwrap(),
+            "state_root_hash": "",
+            "pool_state_root_hash": "",
+            "timestamp": 140
+        })
+    }
+
+    #[test]
+    fn ledger_snapshot_from_json_works_for_gaps() {
+        let mut snapshot = _snapshot();
+        snapshot.txns.remove(1);
+
+        let res = LedgerSnapshot::from_json(&serde_json::to_string(&snapshot).unwrap());
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn ledger_snapshot_verify_root_works() {
+        let mut snapshot = _snapshot();
+        snapshot.multi_signature = Some(json!({"signature": "", "participants": [], "value": _signed_value(&snapshot)}));
+
+        snapshot.verify_root().unwrap();
+    }
+
+    #[test]
+    fn ledger_snapshot_verify_root_works_for_missed_signature() {
+        let res = _snapshot().verify_root();
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn ledger_snapshot_verify_root_works_for_modified_txn() {
+        let mut snapshot = _snapshot();
+        snapshot.multi_signature = Some(json!({"signature": "", "participants": [], "value": _signed_value(&snapshot)}));
+        snapshot.txns[0]["txn"]["data"]["verkey"] = json!("~HYwqs2vrTc8Tn4uBV7NBTe");
+
+        let res = snapshot.verify_root();
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn ledger_snapshot_verify_signature_works() {
+        let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+        let mut snapshot = _snapshot();
+        let value = _signed_value(&snapshot);
+        let message = rmp_serde::to_vec_named(&value).unwrap();
+
+        let mut nodes: Nodes = HashMap::new();
+        let mut signatures = Vec::new();
+
+        for alias in &["Node1", "Node2", "Node3", "Node4"] {
+            let sign_key = SignKey::new(None).unwrap();
+            nodes.insert(alias.to_string(), Some(VerKey::new(&gen, &sign_key).unwrap()));
+            if *alias != "Node4" {
+                signatures.push(Bls::sign(&message, &sign_key).unwrap());
+            }
+        }
+
+        let signature = MultiSignature::new(signatures.iter().collect::<Vec<_>>().as_slice()).unwrap();
+
+        snapshot.multi_signature = Some(json!({
+            "signature": signature.as_bytes().to_base58(),
+            "participants": ["Node1", "Node2", "Node3"],
+            "value": value
+        }));
+
+        snapshot.verify_signature(&nodes, 1).unwrap();
+
+        let res = snapshot.verify_signature(&nodes, 0);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn ledger_snapshot_verify_signature_works_for_missed_signature() {
+        let res = _snapshot().verify_signature(&HashMap::new(), 0);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_get_nym() {
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_NYM, "dest": DID}));
+
+        assert_eq!(constants::GET_NYM, result["type"]);
+        assert_eq!(3, result["seqNo"]);
+        assert_eq!(120, result["txnTime"]);
+
+        let data: SJsonValue = serde_json::from_str(result["data"].as_str().unwrap()).unwrap();
+        assert_eq!(json!({"identifier": TRUSTEE_DID, "dest": DID, "role": "101", "verkey": "~NcYxiDXkpYi6ov5FcYDi1e"}), data);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_unknown_nym() {
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_NYM, "dest": TRUSTEE_DID}));
+
+        assert_eq!(SJsonValue::Null, result["data"]);
+        assert_eq!(SJsonValue::Null, result["seqNo"]);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_get_schema() {
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_SCHEMA, "dest": TRUSTEE_DID, "data": {"name": "gvt", "version": "1.0"}}));
+
+        assert_eq!(2, result["seqNo"]);
+        assert_eq!(TRUSTEE_DID, result["dest"]);
+        assert_eq!(json!(["name"]), result["data"]["attr_names"]);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_get_revoc_reg() {
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_REVOC_REG, "revocRegDefId": REV_REG_DEF_ID, "timestamp": 135}));
+
+        assert_eq!(4, result["seqNo"]);
+        assert_eq!(json!({"value": {"accum": "1"}}), result["data"]);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_get_revoc_reg_delta() {
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_REVOC_REG_DELTA, "revocRegDefId": REV_REG_DEF_ID, "to": 150}));
+
+        assert_eq!(5, result["seqNo"]);
+        assert_eq!(json!([1, 3]), result["data"]["value"]["issued"]);
+        assert_eq!(json!([2]), result["data"]["value"]["revoked"]);
+        assert_eq!(SJsonValue::Null, result["data"]["value"]["accum_from"]);
+        assert_eq!(json!({"value": {"accum": "2"}, "txnTime": 140}), result["data"]["value"]["accum_to"]);
+
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_REVOC_REG_DELTA, "revocRegDefId": REV_REG_DEF_ID, "from": 135, "to": 150}));
+
+        assert_eq!(json!([]), result["data"]["value"]["issued"]);
+        assert_eq!(json!([2]), result["data"]["value"]["revoked"]);
+        assert_eq!(json!({"value": {"accum": "1"}, "txnTime": 130}), result["data"]["value"]["accum_from"]);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_get_txn() {
+        let result = _answer(&_snapshot(), json!({"type": constants::GET_TXN, "ledgerId": DOMAIN_LEDGER_ID, "data": 2}));
+
+        assert_eq!(2, result["seqNo"]);
+        assert_eq!(constants::SCHEMA, result["data"]["txn"]["type"]);
+    }
+
+    #[test]
+    fn ledger_snapshot_answer_works_for_write_request() {
+        let res = _snapshot().answer("", &_request(json!({"type": constants::NYM, "dest": TRUSTEE_DID})));
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+}
@@ -12,7 +12,7 @@ use serde_json;
 use serde_json::Value as SJsonValue;
 
 use indy_api_types::ErrorCode;
-use crate::domain::ledger::{constants, request::ProtocolVersion};
+use crate::domain::ledger::{constants, request::ProtocolVersion, txn::LedgerType};
 use indy_api_types::errors::prelude::*;
 use crate::services::pool::events::{REQUESTS_FOR_STATE_PROOFS, REQUESTS_FOR_MULTI_STATE_PROOFS};
 use indy_utils::crypto::hash::hash as openssl_hash;
@@ -25,6 +25,7 @@ use ursa::bls::{Bls, Generator, MultiSignature, VerKey};
 use self::node::{Node, TrieDB};
 use rust_base58::FromBase58;
 use crate::services::pool::Nodes;
+use crate::utils::crypto::signature_serializer::serialize_signature;
 
 mod node;
 
@@ -145,6 +146,133 @@ pub fn verify_parsed_sp(parsed_sps: Vec<ParsedSP>,
     true
 }
 
+pub fn verify_multi_signature(multi_signature: &SJsonValue,
+                              nodes: &Nodes,
+                              f: usize,
+                              gen: &Generator) -> bool {
+    let data_to_check_proof_signature =
+        _parse_reply_for_proof_signature_checking(multi_signature);
+    let (signature, participants, value) = unwrap_opt_or_return!(data_to_check_proof_signature, false);
+
+    _verify_proof_signature(signature,
+                            participants.as_slice(),
+                            &value,
+                            nodes, f, gen)
+        .map_err(|err| warn!("{:?}", err)).unwrap_or(false)
+}
+
+/// Checks that the reply on a write request proves the submitted transaction is committed:
+///  - transaction of the reply is the submitted request (author, reqId and payload digest)
+///  - audit path of the transaction leads to the `rootHash` of the reply
+///  - `rootHash` is the txn root hash of the transaction ledger signed by BLS multi-signature of the pool
+/// Returns None if the reply doesn't contain a written transaction or BLS multi-signature.
+pub fn verify_write_reply(msg_result: &SJsonValue,
+                          request: &SJsonValue,
+                          nodes: &Nodes,
+                          f: usize,
+                          gen: &Generator) -> Option<bool> {
+    let seq_no = msg_result["txnMetadata"]["seqNo"].as_u64()?;
+
+    let multi_signature = if msg_result["multiSignature"].is_object() {
+        &msg_result["multiSignature"]
+    } else if msg_result["state_proof"]["multi_signature"].is_object() {
+        &msg_result["state_proof"]["multi_signature"]
+    } else {
+        debug!("verify_write_reply: <<< No multi-signature for txn with seqNo {}", seq_no);
+        return None;
+    };
+
+    let res = _verify_write_reply(msg_result, request, seq_no, multi_signature, nodes, f, gen);
+
+    trace!("verify_write_reply: <<< seq_no: {}, res: {}", seq_no, res);
+    Some(res)
+}
+
+fn _verify_write_reply(msg_result: &SJsonValue, request: &SJsonValue, seq_no: u64, multi_signature: &SJsonValue, nodes: &Nodes, f: usize, gen: &Generator) -> bool {
+    let root_hash = unwrap_opt_or_return!(msg_result["rootHash"].as_str(), false);
+    let audit_path = unwrap_opt_or_return!(msg_result["auditPath"].as_array(), false);
+    let ledger_size = msg_result["ledgerSize"].as_u64().unwrap_or(seq_no);
+
+    if seq_no == 0 || seq_no > ledger_size {
+        error!("Transaction seqNo {} is out of ledger size {}", seq_no, ledger_size);
+        return false;
+    }
+
+    if !_check_write_reply_txn(&msg_result["txn"], request) {
+        return false;
+    }
+
+    let ledger_id = _txn_ledger_id(msg_result["txn"]["type"].as_str());
+    if multi_signature["value"]["ledger_id"].as_u64() != Some(ledger_id) {
+        error!("Given signature is not for ledger {}, aborting", ledger_id);
+        return false;
+    }
+
+    if multi_signature["value"]["txn_root_hash"].as_str() != Some(root_hash) {
+        error!("Given signature is not for transaction root hash, aborting");
+        return false;
+    }
+
+    if !verify_multi_signature(multi_signature, nodes, f, gen) {
+        return false;
+    }
+
+    let mut txn = msg_result.clone();
+    if let Some(txn) = txn.as_object_mut() {
+        for field in &["rootHash", "auditPath", "ledgerSize", "multiSignature", "state_proof"] {
+            txn.remove(*field);
+        }
+    }
+
+    let proof_nodes = json!(audit_path).to_string();
+    let root_hash = unwrap_or_return!(root_hash.from_base58(), false);
+    let kvs = vec![(base64::encode(seq_no.to_string().as_bytes()), Some(txn.to_string()))];
+
+    _verify_merkle_tree(proof_nodes.as_bytes(), root_hash.as_slice(), kvs.as_slice(), ledger_size)
+}
+
+/// Checks that the transaction of the reply was written for the submitted request
+fn _check_write_reply_txn(txn: &SJsonValue, request: &SJsonValue) -> bool {
+    let metadata = &txn["metadata"];
+
+    if metadata["from"].is_null() || metadata["from"] != request["identifier"] || metadata["reqId"] != request["reqId"] {
+        error!("Transaction wasn't written for the submitted request, aborting");
+        return false;
+    }
+
+    if let Some(payload_digest) = metadata["payloadDigest"].as_str() {
+        let digest = unwrap_or_return!(_request_payload_digest(request), false);
+
+        if payload_digest != digest {
+            error!("Transaction payload digest doesn't match the submitted request, aborting");
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Payload digest as computed by the pool: hash of the serialized request without signatures
+fn _request_payload_digest(request: &SJsonValue) -> IndyResult<String> {
+    let payload = serialize_signature(request.clone())?;
+    Ok(hex::encode(openssl_hash(payload.as_bytes())?))
+}
+
+fn _txn_ledger_id(txn_type: Option<&str>) -> u64 {
+    match txn_type {
+        Some(constants::NODE) => LedgerType::POOL as u64,
+        Some(constants::TXN_AUTHR_AGRMT) |
+        Some(constants::TXN_AUTHR_AGRMT_AML) |
+        Some(constants::DISABLE_ALL_TXN_AUTHR_AGRMTS) |
+        Some(constants::LEDGERS_FREEZE) |
+        Some(constants::POOL_UPGRADE) |
+        Some(constants::POOL_CONFIG) |
+        Some(constants::AUTH_RULE) |
+        Some(constants::AUTH_RULES) => LedgerType::CONFIG as u64,
+        _ => LedgerType::DOMAIN as u64
+    }
+}
+
 #[logfn(Trace)]
 pub fn parse_key_from_request_for_builtin_sp(json_msg: &SJsonValue) -> Option<Vec<u8>> {
     let type_ = json_msg["operation"]["type"].as_str()?;
@@ -905,6 +1033,184 @@ mod tests {
     ///  /  \   /  \
     /// 1   2  3   4
 
+    fn _write_request(seq_no: u64) -> SJsonValue {
+        json!({
+            "identifier": "V4SGRU86Z58d6TV7PBUe6f",
+            "reqId": seq_no,
+            "operation": {"type": "1", "dest": "VsKV7grR1BUE29mG2Fm2kX"},
+            "protocolVersion": 2,
+            "signature": "4Dq7x4v6kAWtSHLwbrRgXmRrpbU7djSgpvmuRM1aFkFwsmpqsBajDYJ7YgYcFjYAT5V7W4CbHEoJBmVTqqdWbXBk"
+        })
+    }
+
+    fn _write_reply_txn(seq_no: u64) -> SJsonValue {
+        let payload_digest = _request_payload_digest(&_write_request(seq_no)).unwrap();
+
+        json!({
+            "txn": {"type": "1", "data": {"dest": "VsKV7grR1BUE29mG2Fm2kX"}, "metadata": {"from": "V4SGRU86Z58d6TV7PBUe6f", "reqId": seq_no, "payloadDigest": payload_digest}},
+            "txnMetadata": {"seqNo": seq_no, "txnTime": 1600000000},
+            "reqSignature": {},
+            "ver": "1"
+        })
+    }
+
+    fn _write_reply_nodes_and_multi_signature(txn_root_hash: &str) -> (Nodes, Generator, SJsonValue) {
+        use ursa::bls::SignKey;
+        use crate::services::pool::request_handler::DEFAULT_GENERATOR;
+
+        let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+        let value = json!({
+            "ledger_id": 1,
+            "txn_root_hash": txn_root_hash,
+            "state_root_hash": "",
+            "pool_state_root_hash": "",
+            "timestamp": 1600000000
+        });
+        let message = rmp_serde::to_vec_named(&value).unwrap();
+
+        let mut nodes: Nodes = HashMap::new();
+        let mut signatures = Vec::new();
+
+        for alias in &["Node1", "Node2", "Node3", "Node4"] {
+            let sign_key = SignKey::new(None).unwrap();
+            nodes.insert(alias.to_string(), Some(VerKey::new(&gen, &sign_key).unwrap()));
+            if *alias != "Node4" {
+                signatures.push(Bls::sign(&message, &sign_key).unwrap());
+            }
+        }
+
+        let signature = MultiSignature::new(signatures.iter().collect::<Vec<_>>().as_slice()).unwrap();
+
+        let multi_signature = json!({
+            "signature": signature.as_bytes().to_base58(),
+            "participants": ["Node1", "Node2", "Node3"],
+            "value": value
+        });
+
+        (nodes, gen, multi_signature)
+    }
+
+    /// Reply on writing of the second transaction of the ledger
+    fn _write_reply() -> (SJsonValue, Nodes, Generator) {
+        let leaf_1 = Hash::hash_leaf(&rmp_serde::to_vec(&_write_reply_txn(1)).unwrap()).unwrap();
+        let leaf_2 = Hash::hash_leaf(&rmp_serde::to_vec(&_write_reply_txn(2)).unwrap()).unwrap();
+        let root_hash = Hash::hash_nodes(&leaf_1, &leaf_2).unwrap().to_base58();
+
+        let (nodes, gen, multi_signature) = _write_reply_nodes_and_multi_signature(&root_hash);
+
+        let mut reply = _write_reply_txn(2);
+        reply["rootHash"] = json!(root_hash);
+        reply["auditPath"] = json!([leaf_1.to_base58()]);
+        reply["multiSignature"] = multi_signature;
+
+        (reply, nodes, gen)
+    }
+
+    #[test]
+    fn verify_write_reply_works() {
+        let (reply, nodes, gen) = _write_reply();
+        assert_eq!(Some(true), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_multi_signature_in_state_proof() {
+        let (mut reply, nodes, gen) = _write_reply();
+        let multi_signature = reply.as_object_mut().unwrap().remove("multiSignature").unwrap();
+        reply["state_proof"] = json!({"multi_signature": multi_signature});
+
+        assert_eq!(Some(true), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_not_written_txn() {
+        let (nodes, gen, _) = _write_reply_nodes_and_multi_signature("");
+        let reply = json!({"type": "105", "data": null});
+
+        assert_eq!(None, verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_missed_multi_signature() {
+        let (mut reply, nodes, gen) = _write_reply();
+        reply.as_object_mut().unwrap().remove("multiSignature");
+
+        assert_eq!(None, verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_other_request() {
+        let (reply, nodes, gen) = _write_reply();
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(1), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_other_request_payload() {
+        let (reply, nodes, gen) = _write_reply();
+
+        let mut request = _write_request(2);
+        request["operation"]["dest"] = json!("V4SGRU86Z58d6TV7PBUe6f");
+
+        assert_eq!(Some(false), verify_write_reply(&reply, &request, &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_request_signatures() {
+        let (reply, nodes, gen) = _write_reply();
+
+        let mut request = _write_request(2);
+        request["signature"] = json!("3YVzDpu7gqWTC87ntZqGZTFdXuULoLVMy4pVk2ApiUWcmPv6eHHfy1JpcDbvpzYPvGnBwZC1aL1X1vXBDuRZEFEn");
+
+        assert_eq!(Some(true), verify_write_reply(&reply, &request, &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_signature_of_other_ledger() {
+        let (mut reply, nodes, gen) = _write_reply();
+        reply["multiSignature"]["value"]["ledger_id"] = json!(0);
+
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_modified_txn() {
+        let (mut reply, nodes, gen) = _write_reply();
+        reply["txn"]["data"]["dest"] = json!("V4SGRU86Z58d6TV7PBUe6f");
+
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_invalid_audit_path() {
+        let (mut reply, nodes, gen) = _write_reply();
+        reply["auditPath"] = json!([]);
+
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_root_hash_not_signed() {
+        let (mut reply, nodes, gen) = _write_reply();
+        reply["multiSignature"]["value"]["txn_root_hash"] = json!("G9QooEDKSmEtLGNyTwafQiPfGHMqw3A3Fjcj2eLRG4G1");
+
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_not_enough_participants() {
+        let (reply, nodes, gen) = _write_reply();
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(2), &nodes, 0, &gen));
+    }
+
+    #[test]
+    fn verify_write_reply_works_for_no_proof() {
+        let (nodes, gen, multi_signature) = _write_reply_nodes_and_multi_signature("");
+        let mut reply = _write_reply_txn(2);
+        reply["multiSignature"] = multi_signature;
+
+        assert_eq!(Some(false), verify_write_reply(&reply, &_write_request(2), &nodes, 1, &gen));
+    }
+
     #[test]
     fn audit_proof_verify_works() {
         let nodes = json!(
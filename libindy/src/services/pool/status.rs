@@ -0,0 +1,201 @@
+//! Per-node health statistics of opened pools.
+//!
+//! Networkers report the moment a request was sent to a node, request handlers report
+//! what the node answered. Both run on the pool thread, but the statistics are read
+//! from the command thread, so they are kept in a global registry keyed by pool name.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::services::pool::events::RequestEvent;
+
+const LATENCY_WINDOW: usize = 20;
+
+lazy_static! {
+    static ref NODES_STATUS: Mutex<HashMap<String, HashMap<String, NodeStatus>>> = Mutex::new(HashMap::new());
+    static ref SENT_REQUESTS: Mutex<HashMap<(String, String), Instant>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatus {
+    /// Unix timestamp (in seconds) of the last reply received from the node
+    pub last_reply_time: Option<u64>,
+    /// Average latency (in milliseconds) of the last replies
+    pub latency_ms: Option<u64>,
+    pub replies: usize,
+    pub timeouts: usize,
+    pub nacks: usize,
+    pub rejects: usize,
+    pub bls_failures: usize,
+    #[serde(skip)]
+    latencies: VecDeque<Duration>,
+}
+
+impl NodeStatus {
+    fn reply(&mut self, latency: Option<Duration>) {
+        self.replies += 1;
+        self.last_reply_time = Some(SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0));
+
+        if let Some(latency) = latency {
+            if self.latencies.len() == LATENCY_WINDOW {
+                self.latencies.pop_front();
+            }
+            self.latencies.push_back(latency);
+
+            let total: Duration = self.latencies.iter().sum();
+            self.latency_ms = Some((total / self.latencies.len() as u32).as_millis() as u64);
+        }
+    }
+}
+
+pub fn request_sent(req_id: &str, node_alias: &str) {
+    SENT_REQUESTS.lock().unwrap().insert((req_id.to_string(), node_alias.to_string()), Instant::now());
+}
+
+pub fn request_finished(req_id: &str) {
+    SENT_REQUESTS.lock().unwrap().retain(|(id, _), _| id != req_id);
+}
+
+pub fn bls_verification_failed(pool_name: &str, node_alias: &str) {
+    _update(pool_name, node_alias, |status| status.bls_failures += 1);
+}
+
+pub fn record_event(pool_name: &str, event: &RequestEvent) {
+    match *event {
+        RequestEvent::Reply(_, _, ref node_alias, ref req_id) => {
+            let latency = _take_latency(req_id, node_alias);
+            _update(pool_name, node_alias, |status| status.reply(latency));
+        }
+        RequestEvent::ReqNACK(_, _, ref node_alias, ref req_id) => {
+            _take_latency(req_id, node_alias);
+            _update(pool_name, node_alias, |status| status.nacks += 1);
+        }
+        RequestEvent::Reject(_, _, ref node_alias, ref req_id) => {
+            _take_latency(req_id, node_alias);
+            _update(pool_name, node_alias, |status| status.rejects += 1);
+        }
+        RequestEvent::Timeout(ref req_id, ref node_alias) if !node_alias.is_empty() => {
+            _take_latency(req_id, node_alias);
+            _update(pool_name, node_alias, |status| status.timeouts += 1);
+        }
+        RequestEvent::LedgerStatus(_, Some(ref node_alias), _) |
+        RequestEvent::ConsistencyProof(_, ref node_alias) |
+        RequestEvent::CatchupRep(_, ref node_alias) => {
+            _update(pool_name, node_alias, |status| status.reply(None));
+        }
+        _ => ()
+    }
+}
+
+pub fn get(pool_name: &str) -> HashMap<String, NodeStatus> {
+    NODES_STATUS.lock().unwrap().get(pool_name).cloned().unwrap_or_default()
+}
+
+pub fn get_all() -> HashMap<String, HashMap<String, NodeStatus>> {
+    NODES_STATUS.lock().unwrap().clone()
+}
+
+pub fn clear(pool_name: &str) {
+    NODES_STATUS.lock().unwrap().remove(pool_name);
+}
+
+fn _take_latency(req_id: &str, node_alias: &str) -> Option<Duration> {
+    SENT_REQUESTS.lock().unwrap()
+        .remove(&(req_id.to_string(), node_alias.to_string()))
+        .map(|sent| sent.elapsed())
+}
+
+fn _update<F: FnOnce(&mut NodeStatus)>(pool_name: &str, node_alias: &str, f: F) {
+    let mut nodes_status = NODES_STATUS.lock().unwrap();
+    let status = nodes_status
+        .entry(pool_name.to_string()).or_insert_with(HashMap::new)
+        .entry(node_alias.to_string()).or_insert_with(NodeStatus::default);
+    f(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::services::pool::types::Response;
+
+    fn _reply(node_alias: &str, req_id: &str) -> RequestEvent {
+        RequestEvent::Reply(serde_json::from_str(r#"{"result":{"reqId":1}}"#).unwrap(), String::new(), node_alias.to_string(), req_id.to_string())
+    }
+
+    fn _response() -> Response {
+        serde_json::from_str(r#"{"reqId":1}"#).unwrap()
+    }
+
+    #[test]
+    fn record_event_works_for_reply() {
+        let pool_name = "record_event_works_for_reply";
+
+        request_sent("status_req_1", "Node1");
+        record_event(pool_name, &_reply("Node1", "status_req_1"));
+
+        let status = get(pool_name);
+        let node = &status["Node1"];
+        assert_eq!(1, node.replies);
+        assert!(node.last_reply_time.is_some());
+        assert!(node.latency_ms.is_some());
+
+        clear(pool_name);
+    }
+
+    #[test]
+    fn record_event_works_for_failures() {
+        let pool_name = "record_event_works_for_failures";
+
+        record_event(pool_name, &RequestEvent::ReqNACK(_response(), String::new(), "Node1".to_string(), "status_req_2".to_string()));
+        record_event(pool_name, &RequestEvent::Reject(_response(), String::new(), "Node1".to_string(), "status_req_3".to_string()));
+        record_event(pool_name, &RequestEvent::Timeout("status_req_4".to_string(), "Node2".to_string()));
+        bls_verification_failed(pool_name, "Node2");
+
+        let status = get(pool_name);
+        assert_eq!(1, status["Node1"].nacks);
+        assert_eq!(1, status["Node1"].rejects);
+        assert_eq!(0, status["Node1"].replies);
+        assert_eq!(1, status["Node2"].timeouts);
+        assert_eq!(1, status["Node2"].bls_failures);
+        assert_eq!(None, status["Node2"].last_reply_time);
+
+        clear(pool_name);
+        assert!(get(pool_name).is_empty());
+    }
+
+    #[test]
+    fn record_event_skips_timeout_without_node() {
+        let pool_name = "record_event_skips_timeout_without_node";
+
+        record_event(pool_name, &RequestEvent::Timeout("status_req_5".to_string(), String::new()));
+
+        assert!(get(pool_name).is_empty());
+    }
+
+    #[test]
+    fn node_status_keeps_latency_window() {
+        let mut status = NodeStatus::default();
+
+        for _ in 0..LATENCY_WINDOW + 5 {
+            status.reply(Some(Duration::from_millis(10)));
+        }
+
+        assert_eq!(LATENCY_WINDOW, status.latencies.len());
+        assert_eq!(Some(10), status.latency_ms);
+        assert_eq!(LATENCY_WINDOW + 5, status.replies);
+    }
+
+    #[test]
+    fn request_finished_drops_sent_requests() {
+        request_sent("status_req_6", "Node1");
+        request_sent("status_req_6", "Node2");
+
+        request_finished("status_req_6");
+
+        assert!(_take_latency("status_req_6", "Node1").is_none());
+        assert!(_take_latency("status_req_6", "Node2").is_none());
+    }
+}
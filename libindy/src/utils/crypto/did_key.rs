@@ -0,0 +1,210 @@
+//! Self-certifying `did:key` and `did:peer` (numalgo 0 and 2) identifiers.
+//!
+//! Keys are encoded as multibase (base58btc) multicodec fingerprints, e.g. `z6Mk...` for ed25519 keys.
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::base64;
+use indy_utils::crypto::ed25519_sign;
+use rust_base58::{FromBase58, ToBase58};
+
+use crate::domain::crypto::did::{DidService, DidValue};
+use crate::services::crypto::{DEFAULT_CRYPTO_TYPE, P256_CRYPTO_TYPE, SECP256K1_CRYPTO_TYPE};
+use crate::utils::crypto::verkey_builder::split_verkey;
+
+pub const KEY_METHOD: &str = "key";
+pub const PEER_METHOD: &str = "peer";
+
+const MULTIBASE_BASE58BTC: char = 'z';
+
+// Unsigned varint encoded multicodec prefixes
+const ED25519_CODEC: [u8; 2] = [0xed, 0x01];
+const X25519_CODEC: [u8; 2] = [0xec, 0x01];
+const SECP256K1_CODEC: [u8; 2] = [0xe7, 0x01];
+const P256_CODEC: [u8; 2] = [0x80, 0x24];
+
+/// Builds multibase multicodec fingerprint of verkey (optionally suffixed with crypto type).
+pub fn fingerprint(verkey: &str) -> IndyResult<String> {
+    let (verkey, crypto_type) = split_verkey(verkey);
+
+    let codec = match crypto_type {
+        DEFAULT_CRYPTO_TYPE => ED25519_CODEC,
+        SECP256K1_CRYPTO_TYPE => SECP256K1_CODEC,
+        P256_CRYPTO_TYPE => P256_CODEC,
+        _ => return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unable to build key fingerprint for crypto: {}", crypto_type)))
+    };
+
+    Ok(_fingerprint(&codec, &verkey.from_base58()?))
+}
+
+/// Restores verkey (suffixed with crypto type for non-default types) from multibase multicodec fingerprint.
+pub fn verkey_from_fingerprint(fingerprint: &str) -> IndyResult<String> {
+    let bytes = if fingerprint.starts_with(MULTIBASE_BASE58BTC) {
+        fingerprint[1..].from_base58()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, format!("Invalid base58 in key fingerprint: {}", fingerprint)))?
+    } else {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported multibase encoding of key fingerprint: {}", fingerprint)));
+    };
+
+    if bytes.len() < 2 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Key fingerprint is too short: {}", fingerprint)));
+    }
+
+    let (codec, key) = bytes.split_at(2);
+
+    let crypto_type = match [codec[0], codec[1]] {
+        ED25519_CODEC => DEFAULT_CRYPTO_TYPE,
+        SECP256K1_CODEC => SECP256K1_CRYPTO_TYPE,
+        P256_CODEC => P256_CRYPTO_TYPE,
+        _ => return Err(err_msg(IndyErrorKind::UnknownCrypto, format!("Unsupported key type of fingerprint: {}", fingerprint)))
+    };
+
+    let verkey = key.to_base58();
+
+    if crypto_type == DEFAULT_CRYPTO_TYPE {
+        Ok(verkey)
+    } else {
+        Ok(format!("{}:{}", verkey, crypto_type))
+    }
+}
+
+pub fn did_key(verkey: &str) -> IndyResult<DidValue> {
+    Ok(DidValue(format!("did:{}:{}", KEY_METHOD, fingerprint(verkey)?)))
+}
+
+pub fn did_peer_0(verkey: &str) -> IndyResult<DidValue> {
+    Ok(DidValue(format!("did:{}:0{}", PEER_METHOD, fingerprint(verkey)?)))
+}
+
+/// Builds `did:peer:2` with verification key, key agreement key (ed25519 only) and DIDComm service.
+pub fn did_peer_2(verkey: &str, service: &DidService) -> IndyResult<DidValue> {
+    let mut did = format!("did:{}:2", PEER_METHOD);
+
+    let (vk, crypto_type) = split_verkey(verkey);
+
+    if crypto_type == DEFAULT_CRYPTO_TYPE {
+        let vk = ed25519_sign::PublicKey::from_slice(&vk.from_base58()?)?;
+        let pk = ed25519_sign::vk_to_curve25519(&vk)?;
+        did.push_str(&format!(".E{}", _fingerprint(&X25519_CODEC, &pk[..])));
+    }
+
+    did.push_str(&format!(".V{}", fingerprint(verkey)?));
+
+    let service = json!({
+        "t": "dm",
+        "s": service.service_endpoint,
+        "r": service.routing_keys,
+        "a": service.accept,
+    });
+
+    did.push_str(&format!(".S{}", base64::encode_urlsafe_nopad(service.to_string().as_bytes())));
+
+    Ok(DidValue(did))
+}
+
+/// Resolves verkey of `did:key` or `did:peer` DID.
+///
+/// Returns None for DIDs of other methods as they can't be resolved from identifier itself.
+pub fn resolve_verkey(did: &DidValue) -> Option<IndyResult<String>> {
+    match did.get_method().as_ref().map(String::as_str) {
+        Some(KEY_METHOD) => Some(_resolve_did_key(did)),
+        Some(PEER_METHOD) => Some(_resolve_did_peer(did)),
+        _ => None
+    }
+}
+
+pub fn is_self_certifying(did: &DidValue) -> bool {
+    match did.get_method().as_ref().map(String::as_str) {
+        Some(KEY_METHOD) | Some(PEER_METHOD) => true,
+        _ => false
+    }
+}
+
+fn _resolve_did_key(did: &DidValue) -> IndyResult<String> {
+    verkey_from_fingerprint(&did.to_unqualified().0)
+}
+
+fn _resolve_did_peer(did: &DidValue) -> IndyResult<String> {
+    let id = did.to_unqualified().0;
+
+    if id.starts_with('0') {
+        return verkey_from_fingerprint(&id[1..]);
+    }
+
+    if id.starts_with("2.") {
+        return id[2..]
+            .split('.')
+            .find(|element| element.starts_with('V'))
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("DID doesn't contain verification key: {}", did.0)))
+            .and_then(|element| verkey_from_fingerprint(&element[1..]));
+    }
+
+    Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported did:peer numalgo: {}", did.0)))
+}
+
+fn _fingerprint(codec: &[u8], key: &[u8]) -> String {
+    let mut bytes = codec.to_vec();
+    bytes.extend_from_slice(key);
+
+    format!("{}{}", MULTIBASE_BASE58BTC, bytes.to_base58())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // did:key test vector from W3C CCG did:key specification
+    const DID_KEY: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+    const VERKEY: &str = "48GdbJyVULjHDaBNS6ct9oAGtckZUS5v8asrPzvZ7R1w";
+
+    #[test]
+    fn did_key_works() {
+        assert_eq!(DidValue(DID_KEY.to_string()), did_key(VERKEY).unwrap());
+    }
+
+    #[test]
+    fn resolve_verkey_works_for_did_key() {
+        assert_eq!(VERKEY, resolve_verkey(&DidValue(DID_KEY.to_string())).unwrap().unwrap());
+    }
+
+    #[test]
+    fn fingerprint_works_for_ec_crypto_types() {
+        for crypto_type in &[SECP256K1_CRYPTO_TYPE, P256_CRYPTO_TYPE] {
+            let verkey = format!("{}:{}", [2u8; 33].to_base58(), crypto_type);
+            assert_eq!(verkey, verkey_from_fingerprint(&fingerprint(&verkey).unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn resolve_verkey_works_for_did_peer_0() {
+        let did = did_peer_0(VERKEY).unwrap();
+        assert!(did.0.starts_with("did:peer:0z6Mk"));
+        assert_eq!(VERKEY, resolve_verkey(&did).unwrap().unwrap());
+    }
+
+    #[test]
+    fn resolve_verkey_works_for_did_peer_2() {
+        let service = DidService {
+            service_endpoint: "https://example.com/endpoint".to_string(),
+            routing_keys: vec![],
+            accept: vec!["didcomm/v2".to_string()],
+        };
+
+        let did = did_peer_2(VERKEY, &service).unwrap();
+        assert!(did.0.starts_with("did:peer:2.Ez6LS"));
+        assert!(did.0.contains(".Vz6Mk"));
+        assert!(did.0.contains(".S"));
+
+        assert_eq!(VERKEY, resolve_verkey(&did).unwrap().unwrap());
+    }
+
+    #[test]
+    fn resolve_verkey_works_for_other_methods() {
+        assert!(resolve_verkey(&DidValue("did:sov:NcYxiDXkpYi6ov5FcYDi1e".to_string())).is_none());
+        assert!(resolve_verkey(&DidValue("NcYxiDXkpYi6ov5FcYDi1e".to_string())).is_none());
+    }
+
+    #[test]
+    fn resolve_verkey_fails_for_unsupported_numalgo() {
+        assert!(resolve_verkey(&DidValue("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa".to_string())).unwrap().is_err());
+    }
+}
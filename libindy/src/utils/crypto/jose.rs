@@ -0,0 +1,223 @@
+//! JOSE primitives used by DIDComm v2 envelopes (RFC 7516, RFC 7518).
+
+use aes::Aes256;
+use aes::cipher::{BlockCipher, NewBlockCipher};
+use aes::cipher::generic_array::GenericArray;
+use aes_gcm::Aes256Gcm;
+use aes_gcm::aead::{AeadInPlace, NewAead};
+use chacha20poly1305::XChaCha20Poly1305;
+use crate::domain::crypto::pack::ContentEncryption;
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::randombytes::randombytes;
+use sha2::{Digest, Sha256};
+use sodiumoxide::utils::memcmp;
+
+pub const CEK_BYTES: usize = 32;
+const TAG_BYTES: usize = 16;
+const KW_IV: [u8; 8] = [0xA6; 8];
+
+impl ContentEncryption {
+    fn iv_len(&self) -> usize {
+        match self {
+            ContentEncryption::XC20P => 24,
+            ContentEncryption::A256GCM => 12,
+        }
+    }
+
+    /// Encrypts `plaintext` with `aad` as associated data. Returns (iv, ciphertext, tag).
+    pub fn encrypt(&self, cek: &[u8], plaintext: &[u8], aad: &[u8]) -> IndyResult<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let iv = randombytes(self.iv_len());
+        let mut buffer = plaintext.to_vec();
+
+        let tag = match self {
+            ContentEncryption::XC20P =>
+                XChaCha20Poly1305::new(_cek(cek)?)
+                    .encrypt_in_place_detached(GenericArray::from_slice(&iv), aad, &mut buffer)
+                    .map(|tag| tag.to_vec()),
+            ContentEncryption::A256GCM =>
+                Aes256Gcm::new(_cek(cek)?)
+                    .encrypt_in_place_detached(GenericArray::from_slice(&iv), aad, &mut buffer)
+                    .map(|tag| tag.to_vec()),
+        }.map_err(|_| err_msg(IndyErrorKind::InvalidState, "Unable to encrypt JWE content"))?;
+
+        Ok((iv, buffer, tag))
+    }
+
+    pub fn decrypt(&self, cek: &[u8], iv: &[u8], ciphertext: &[u8], tag: &[u8], aad: &[u8]) -> IndyResult<Vec<u8>> {
+        if iv.len() != self.iv_len() || tag.len() != TAG_BYTES {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid JWE iv or tag length"));
+        }
+
+        let mut buffer = ciphertext.to_vec();
+
+        match self {
+            ContentEncryption::XC20P =>
+                XChaCha20Poly1305::new(_cek(cek)?)
+                    .decrypt_in_place_detached(GenericArray::from_slice(iv), aad, &mut buffer, GenericArray::from_slice(tag)),
+            ContentEncryption::A256GCM =>
+                Aes256Gcm::new(_cek(cek)?)
+                    .decrypt_in_place_detached(GenericArray::from_slice(iv), aad, &mut buffer, GenericArray::from_slice(tag)),
+        }.map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Unable to decrypt JWE content"))?;
+
+        Ok(buffer)
+    }
+}
+
+pub fn gen_cek() -> Vec<u8> {
+    randombytes(CEK_BYTES)
+}
+
+/// Concat KDF with SHA-256 (RFC 7518 4.6.2) producing 256 bits key.
+///
+/// `cc_tag` is appended to SuppPubInfo as required by ECDH-1PU key wrapping mode.
+pub fn concat_kdf(z: &[u8], alg: &str, apu: &[u8], apv: &[u8], cc_tag: Option<&[u8]>) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+
+    hasher.update(&1u32.to_be_bytes());
+    hasher.update(z);
+    _update_with_len(&mut hasher, alg.as_bytes());
+    _update_with_len(&mut hasher, apu);
+    _update_with_len(&mut hasher, apv);
+    hasher.update(&((CEK_BYTES * 8) as u32).to_be_bytes());
+    if let Some(cc_tag) = cc_tag {
+        _update_with_len(&mut hasher, cc_tag);
+    }
+
+    hasher.finalize().to_vec()
+}
+
+/// AES-256 key wrap (RFC 3394).
+pub fn aes_key_wrap(kek: &[u8], key: &[u8]) -> IndyResult<Vec<u8>> {
+    if key.len() % 8 != 0 || key.len() < 16 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid length of key to wrap"));
+    }
+
+    let cipher = _kek(kek)?;
+    let n = key.len() / 8;
+
+    let mut a = KW_IV;
+    let mut r: Vec<[u8; 8]> = key.chunks(8).map(_block).collect();
+
+    for j in 0..6 {
+        for i in 0..n {
+            let mut block = GenericArray::clone_from_slice(&[&a[..], &r[i][..]].concat());
+            cipher.encrypt_block(&mut block);
+
+            let t = (n * j + i + 1) as u64;
+            a = _block(&block[..8]);
+            for (a_byte, t_byte) in a.iter_mut().zip(t.to_be_bytes().iter()) {
+                *a_byte ^= t_byte;
+            }
+            r[i] = _block(&block[8..]);
+        }
+    }
+
+    let mut res = a.to_vec();
+    for block in r {
+        res.extend_from_slice(&block);
+    }
+
+    Ok(res)
+}
+
+/// AES-256 key unwrap (RFC 3394).
+pub fn aes_key_unwrap(kek: &[u8], wrapped: &[u8]) -> IndyResult<Vec<u8>> {
+    if wrapped.len() % 8 != 0 || wrapped.len() < 24 {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid length of wrapped key"));
+    }
+
+    let cipher = _kek(kek)?;
+    let n = wrapped.len() / 8 - 1;
+
+    let mut a = _block(&wrapped[..8]);
+    let mut r: Vec<[u8; 8]> = wrapped[8..].chunks(8).map(_block).collect();
+
+    for j in (0..6).rev() {
+        for i in (0..n).rev() {
+            let t = (n * j + i + 1) as u64;
+            for (a_byte, t_byte) in a.iter_mut().zip(t.to_be_bytes().iter()) {
+                *a_byte ^= t_byte;
+            }
+
+            let mut block = GenericArray::clone_from_slice(&[&a[..], &r[i][..]].concat());
+            cipher.decrypt_block(&mut block);
+
+            a = _block(&block[..8]);
+            r[i] = _block(&block[8..]);
+        }
+    }
+
+    if !memcmp(&a, &KW_IV) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Unable to unwrap key: integrity check failed"));
+    }
+
+    Ok(r.concat())
+}
+
+fn _update_with_len(hasher: &mut Sha256, data: &[u8]) {
+    hasher.update(&(data.len() as u32).to_be_bytes());
+    hasher.update(data);
+}
+
+fn _block(bytes: &[u8]) -> [u8; 8] {
+    let mut block = [0u8; 8];
+    block.copy_from_slice(bytes);
+    block
+}
+
+fn _kek(kek: &[u8]) -> IndyResult<Aes256> {
+    if kek.len() != CEK_BYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid length of key encryption key"));
+    }
+
+    Ok(Aes256::new(GenericArray::from_slice(kek)))
+}
+
+fn _cek(cek: &[u8]) -> IndyResult<&GenericArray<u8, aes_gcm::aead::consts::U32>> {
+    if cek.len() != CEK_BYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid length of content encryption key"));
+    }
+
+    Ok(GenericArray::from_slice(cek))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aes_key_wrap_works_for_rfc3394_vector() {
+        let kek = hex::decode("000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F").unwrap();
+        let key = hex::decode("00112233445566778899AABBCCDDEEFF000102030405060708090A0B0C0D0E0F").unwrap();
+        let expected = hex::decode("28C9F404C4B810F4CBCCB35CFB87F8263F5786E2D80ED326CBC7F0E71A99F43BFB988B9B7A02DD21").unwrap();
+
+        let wrapped = aes_key_wrap(&kek, &key).unwrap();
+        assert_eq!(expected, wrapped);
+
+        assert_eq!(key, aes_key_unwrap(&kek, &wrapped).unwrap());
+    }
+
+    #[test]
+    fn aes_key_unwrap_fails_for_other_kek() {
+        let wrapped = aes_key_wrap(&[1u8; 32], &[2u8; 32]).unwrap();
+        assert!(aes_key_unwrap(&[3u8; 32], &wrapped).is_err());
+    }
+
+    #[test]
+    fn content_encryption_works() {
+        for enc in &[ContentEncryption::XC20P, ContentEncryption::A256GCM] {
+            let cek = gen_cek();
+            let (iv, ciphertext, tag) = enc.encrypt(&cek, b"message", b"aad").unwrap();
+
+            assert_eq!(b"message".to_vec(), enc.decrypt(&cek, &iv, &ciphertext, &tag, b"aad").unwrap());
+            assert!(enc.decrypt(&cek, &iv, &ciphertext, &tag, b"other aad").is_err());
+        }
+    }
+
+    #[test]
+    fn concat_kdf_depends_on_cc_tag() {
+        let key = concat_kdf(&[1u8; 32], "ECDH-1PU+A256KW", b"alice", b"bob", None);
+        assert_eq!(CEK_BYTES, key.len());
+        assert_ne!(key, concat_kdf(&[1u8; 32], "ECDH-1PU+A256KW", b"alice", b"bob", Some(b"tag")));
+    }
+}
@@ -1,2 +1,4 @@
+pub mod did_key;
+pub mod jose;
 pub mod verkey_builder;
 pub mod signature_serializer;
@@ -2,6 +2,9 @@ use regex::Regex;
 
 lazy_static! {
     pub static ref REGEX: Regex = Regex::new("^[a-z0-9]+:([a-z0-9]+):(.*)$").unwrap();
+    // did:indy:<namespace>:<id>[/<path>], namespace may consist of several colon separated parts
+    pub static ref INDY_REGEX: Regex = Regex::new("^did:indy:((?:[a-z0-9_-]+:)*[a-z0-9_-]+):([1-9A-HJ-NP-Za-km-z]{21,22})(/.*)?$").unwrap();
+    pub static ref NAMESPACE_REGEX: Regex = Regex::new("^(?:[a-z0-9_-]+:)*[a-z0-9_-]+$").unwrap();
 }
 
 pub fn qualify(entity: &str, prefix: &str, method: &str) -> String {
@@ -9,6 +12,10 @@ pub fn qualify(entity: &str, prefix: &str, method: &str) -> String {
 }
 
 pub fn to_unqualified(entity: &str) -> String {
+    if let Some(caps) = INDY_REGEX.captures(entity) {
+        return format!("{}{}", &caps[2], caps.get(3).map(|m| m.as_str()).unwrap_or(""));
+    }
+
     match REGEX.captures(entity) {
         None => entity.to_string(),
         Some(caps) => {
@@ -30,6 +37,16 @@ pub fn is_fully_qualified(entity: &str) -> bool {
     REGEX.is_match(&entity)
 }
 
+pub fn indy_namespace(entity: &str) -> Option<String> {
+    INDY_REGEX.captures(entity)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str().to_string())
+}
+
+pub fn is_valid_namespace(namespace: &str) -> bool {
+    NAMESPACE_REGEX.is_match(namespace)
+}
+
 macro_rules! qualifiable_type (($newtype:ident) => (
 
     #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
@@ -51,4 +68,41 @@ macro_rules! qualifiable_type (($newtype:ident) => (
             self.0.starts_with($newtype::PREFIX) && qualifier::is_fully_qualified(&self.0)
         }
     }
-));
\ No newline at end of file
+));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_unqualified_works() {
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn to_unqualified_works_for_did_indy() {
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e", to_unqualified("did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!("NcYxiDXkpYi6ov5FcYDi1e/anoncreds/v0/SCHEMA/gvt/1.0",
+                   to_unqualified("did:indy:sovrin:NcYxiDXkpYi6ov5FcYDi1e/anoncreds/v0/SCHEMA/gvt/1.0"));
+    }
+
+    #[test]
+    fn indy_namespace_works() {
+        assert_eq!(Some("sovrin".to_string()), indy_namespace("did:indy:sovrin:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(Some("sovrin:staging".to_string()), indy_namespace("did:indy:sovrin:staging:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(Some("idunion:test".to_string()), indy_namespace("did:indy:idunion:test:NcYxiDXkpYi6ov5FcYDi1e/anoncreds/v0/SCHEMA/gvt/1.0"));
+        assert_eq!(None, indy_namespace("did:sov:NcYxiDXkpYi6ov5FcYDi1e"));
+        assert_eq!(None, indy_namespace("did:indy:NcYxiDXkpYi6ov5FcYDi1e"));
+    }
+
+    #[test]
+    fn is_valid_namespace_works() {
+        assert!(is_valid_namespace("sovrin"));
+        assert!(is_valid_namespace("sovrin:staging"));
+        assert!(is_valid_namespace("indy_test-1"));
+        assert!(!is_valid_namespace(""));
+        assert!(!is_valid_namespace("Sovrin"));
+        assert!(!is_valid_namespace("sovrin:"));
+        assert!(!is_valid_namespace("sovrin::staging"));
+    }
+}
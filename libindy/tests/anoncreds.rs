@@ -3086,6 +3086,97 @@ mod high_cases {
         }
     }
 
+    mod prover_select_credentials_for_proof_req {
+        use super::*;
+        use crate::utils::anoncreds::{CREDENTIAL2_ID, CREDENTIAL3_ID};
+
+        fn _proof_req() -> String {
+            json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"name"
+                   })
+               }),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({
+                       "name":"age",
+                       "p_type":">=",
+                       "p_value":18
+                   })
+               }),
+            }).to_string()
+        }
+
+        #[test]
+        fn prover_select_credentials_for_proof_req_works_for_prefer_issuers() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            for (issuer_did, cred_id) in vec![(ISSUER_DID, CREDENTIAL1_ID), (ISSUER_DID_2, CREDENTIAL3_ID)] {
+                let policy = json!({"prefer_issuers": [issuer_did]}).to_string();
+
+                let (requested_credentials_json, rev_states_json) =
+                    anoncreds::prover_select_credentials_for_proof_req(wallet_handle, &_proof_req(), Some(&policy)).unwrap();
+
+                let requested_credentials: serde_json::Value = serde_json::from_str(&requested_credentials_json).unwrap();
+                assert_eq!(cred_id, requested_credentials["requested_attributes"]["attr1_referent"]["cred_id"].as_str().unwrap());
+                assert_eq!(true, requested_credentials["requested_attributes"]["attr1_referent"]["revealed"].as_bool().unwrap());
+                assert_eq!(cred_id, requested_credentials["requested_predicates"]["predicate1_referent"]["cred_id"].as_str().unwrap());
+
+                assert_eq!("[]", rev_states_json);
+            }
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_select_credentials_for_proof_req_works_for_minimize_credentials_and_self_attested_attr() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"status"
+                   }),
+                   "attr2_referent": json!({
+                       "name":"phone"
+                   })
+               }),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({
+                       "name":"period",
+                       "p_type":">=",
+                       "p_value":5
+                   })
+               }),
+            }).to_string();
+
+            let policy = json!({
+                "minimize_credentials": true,
+                "self_attested_attributes": {"attr2_referent": "8-800-300"}
+            }).to_string();
+
+            let (requested_credentials_json, _) =
+                anoncreds::prover_select_credentials_for_proof_req(wallet_handle, &proof_req, Some(&policy)).unwrap();
+
+            let requested_credentials: serde_json::Value = serde_json::from_str(&requested_credentials_json).unwrap();
+            assert_eq!(CREDENTIAL2_ID, requested_credentials["requested_attributes"]["attr1_referent"]["cred_id"].as_str().unwrap());
+            assert_eq!(CREDENTIAL2_ID, requested_credentials["requested_predicates"]["predicate1_referent"]["cred_id"].as_str().unwrap());
+            assert_eq!("8-800-300", requested_credentials["self_attested_attributes"]["attr2_referent"].as_str().unwrap());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_create_proof_works {
         use super::*;
 
@@ -3797,6 +3888,90 @@ mod high_cases {
             assert_eq!(anoncreds::local_gvt_cred_def_id(), cred_req.cred_def_id.0);
         }
     }
+
+    mod encode_credential_values {
+        use super::*;
+
+        #[test]
+        fn encode_credential_values_works() {
+            let cred_values_json = json!({
+                "name": "Alex",
+                "age": {"raw": "28"},
+                "birthdate": {"raw": "1992-03-15", "encoding": "date"},
+                "salary": {"raw": "1500.75", "encoding": {"decimal": 2}}
+            }).to_string();
+
+            let encoded_cred_values_json = anoncreds::encode_credential_values(&cred_values_json).unwrap();
+            let encoded_cred_values: serde_json::Value = serde_json::from_str(&encoded_cred_values_json).unwrap();
+
+            assert_eq!(json!({"raw": "28", "encoded": "28"}), encoded_cred_values["age"]);
+            assert_eq!(json!({"raw": "1992-03-15", "encoded": "8109"}), encoded_cred_values["birthdate"]);
+            assert_eq!(json!({"raw": "1500.75", "encoded": "150075"}), encoded_cred_values["salary"]);
+            assert_eq!(json!("Alex"), encoded_cred_values["name"]["raw"]);
+        }
+
+        #[test]
+        fn encode_credential_values_works_for_issuance() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let cred_values_json = anoncreds::encode_credential_values(&json!({
+                "sex": "male",
+                "name": "Alex",
+                "height": {"raw": "175.5", "encoding": {"decimal": 1}},
+                "age": {"raw": "28"}
+            }).to_string()).unwrap();
+
+            let (credential_json, _, _) = anoncreds::issuer_create_credential(wallet_handle,
+                                                                              &credential_offer,
+                                                                              &credential_req,
+                                                                              &cred_values_json,
+                                                                              None,
+                                                                              None).unwrap();
+
+            let credential: serde_json::Value = serde_json::from_str(&credential_json).unwrap();
+            assert_eq!(json!({"raw": "175.5", "encoded": "1755"}), credential["values"]["height"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod verifier_verify_proof_with_report {
+        use super::*;
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_correct_proof() {
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(true), report["valid"]);
+            assert_eq!(json!({"valid": true}), report["structure"]);
+            assert_eq!(json!("revealed"), report["attributes"]["attr1_referent"]["status"]);
+            assert_eq!(json!({"valid": true}), report["attributes"]["attr1_referent"]["value"]);
+            assert_eq!(json!({"valid": true}), report["sub_proofs"][0]["resolved"]);
+            assert_eq!(json!({"valid": true}), report["crypto_proof"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_restrictions() {
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_restrictions(),
+                                                                           &anoncreds::proof_json_restrictions(),
+                                                                           &anoncreds::schemas_for_proof_restrictions(),
+                                                                           &anoncreds::cred_defs_for_proof_restrictions(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(true), report["valid"]);
+            assert_eq!(json!({"valid": true}), report["attributes"]["attr1_referent"]["restrictions"]);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -4237,6 +4412,81 @@ mod medium_cases {
         }
     }
 
+    mod issuer_revoc_reg_manager {
+        use super::*;
+
+        #[test]
+        fn issuer_create_revoc_reg_manager_works_for_invalid_rollover_threshold() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_revoc_reg_manager(wallet_handle,
+                                                                 ISSUER_DID,
+                                                                 &anoncreds::issuer_1_gvt_cred_def_id(),
+                                                                 Some(r#"{"rollover_threshold":1.5}"#),
+                                                                 -1);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_get_revoc_reg_manager_state_works_for_not_created_manager() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_get_revoc_reg_manager_state(wallet_handle, &anoncreds::issuer_1_gvt_cred_def_id());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_mark_revoc_reg_published_works_for_unknown_rev_reg() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_mark_revoc_reg_published(wallet_handle, &anoncreds::gvt_rev_reg_id());
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_create_credential_with_revoc_reg_manager_works_for_not_created_manager() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credential_with_revoc_reg_manager(wallet_handle,
+                                                                                 &credential_offer,
+                                                                                 &credential_req,
+                                                                                 &anoncreds::gvt_credential_values_json(),
+                                                                                 -1,
+                                                                                 -1);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn issuer_get_issued_credentials_works_for_unknown_cred_def() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let query_json = json!({ "cred_def_id": "unknown_cred_def_id" }).to_string();
+
+            let issued_credentials_json = anoncreds::issuer_get_issued_credentials(wallet_handle, Some(&query_json)).unwrap();
+            assert_eq!("[]", issued_credentials_json);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -4405,6 +4655,89 @@ mod medium_cases {
         }
     }
 
+    mod prover_select_credentials_for_proof_req {
+        use super::*;
+
+        #[test]
+        fn prover_select_credentials_for_proof_req_works_for_not_satisfiable_predicate() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({}),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({
+                       "name":"age",
+                       "p_type":">=",
+                       "p_value":58
+                   })
+               }),
+            }).to_string();
+
+            let res = anoncreds::prover_select_credentials_for_proof_req(wallet_handle, &proof_req, None);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_select_credentials_for_proof_req_works_for_self_attested_attr_with_restrictions() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"phone",
+                       "restrictions": json!({ "issuer_did": ISSUER_DID })
+                   })
+               }),
+               "requested_predicates": json!({}),
+            }).to_string();
+
+            let policy = json!({"self_attested_attributes": {"attr1_referent": "8-800-300"}}).to_string();
+
+            let res = anoncreds::prover_select_credentials_for_proof_req(wallet_handle, &proof_req, Some(&policy));
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_select_credentials_for_proof_req_works_for_invalid_policy() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"name"
+                   })
+               }),
+               "requested_predicates": json!({}),
+            }).to_string();
+
+            let policy = json!({"prefer_issuers": ["invalid_base58_did"]}).to_string();
+
+            let res = anoncreds::prover_select_credentials_for_proof_req(wallet_handle, &proof_req, Some(&policy));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_create_proof_works {
         use super::*;
 
@@ -4692,6 +5025,7 @@ mod medium_cases {
                             ] }
                     })).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let res = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4720,6 +5054,7 @@ mod medium_cases {
                             ] }
                     })).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4750,6 +5085,7 @@ mod medium_cases {
                         }
                     ])).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4780,6 +5116,7 @@ mod medium_cases {
                         }
                     ])).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4806,6 +5143,7 @@ mod medium_cases {
                             ]
                         })).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4832,6 +5170,7 @@ mod medium_cases {
                             ]
                         })).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4858,6 +5197,7 @@ mod medium_cases {
                             ]
                         })).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4884,6 +5224,7 @@ mod medium_cases {
                             ]
                         })).unwrap(),
                     non_revoked: None,
+                    encoding: None,
                 },
             );
             let valid = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
@@ -4895,4 +5236,143 @@ mod medium_cases {
             assert_code!(ErrorCode::AnoncredsProofRejected , valid);
         }
     }
+
+    mod encode_credential_values {
+        use super::*;
+
+        #[test]
+        fn encode_credential_values_works_for_invalid_date() {
+            let res = anoncreds::encode_credential_values(&json!({
+                "birthdate": {"raw": "1992-02-30", "encoding": "date"}
+            }).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn encode_credential_values_works_for_decimal_out_of_range() {
+            let res = anoncreds::encode_credential_values(&json!({
+                "salary": {"raw": "99999999.99", "encoding": {"decimal": 2}}
+            }).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn encode_credential_values_works_for_unknown_encoding() {
+            let res = anoncreds::encode_credential_values(&json!({
+                "name": {"raw": "Alex", "encoding": "base64"}
+            }).to_string());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn encode_credential_values_works_for_empty() {
+            let res = anoncreds::encode_credential_values("{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod verifier_verify_proof_with_encoding {
+        use super::*;
+        use crate::utils::domain::anoncreds::credential::AttributeEncoding;
+
+        #[test]
+        fn verifier_verify_proof_fails_for_not_matching_encoding() {
+            let mut proof_req: ProofRequestPayload = serde_json::from_str(&anoncreds::proof_request_restrictions()).unwrap();
+            proof_req.requested_attributes.get_mut("attr1_referent").unwrap().encoding = Some(AttributeEncoding::Default);
+
+            let res = anoncreds::verifier_verify_proof(&serde_json::to_string(&proof_req).unwrap(),
+                                                       &anoncreds::proof_json_restrictions(),
+                                                       &anoncreds::schemas_for_proof_restrictions(),
+                                                       &anoncreds::cred_defs_for_proof_restrictions(),
+                                                       "{}",
+                                                       "{}");
+            assert_code!(ErrorCode::AnoncredsProofRejected, res);
+        }
+    }
+
+    mod verifier_verify_proof_with_report {
+        use super::*;
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_failed_restriction() {
+            let proof_req = json!({
+                "nonce":"123432421212",
+                "name":"proof_req_1",
+                "version":"0.1",
+                "requested_attributes": {
+                    "attr1_referent": {
+                        "name":"name",
+                        "restrictions": { "issuer_did": "Not Here" }
+                    }
+                },
+                "requested_predicates": {},
+            }).to_string();
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&proof_req,
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(false), report["valid"]);
+            assert_eq!(json!(false), report["attributes"]["attr1_referent"]["restrictions"]["valid"]);
+            assert!(report["attributes"]["attr1_referent"]["restrictions"]["error"].as_str().unwrap().contains("issuer_did"));
+            assert_eq!(json!({"valid": true}), report["crypto_proof"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missing_referent() {
+            let proof_req = json!({
+                "nonce":"123432421212",
+                "name":"proof_req_1",
+                "version":"0.1",
+                "requested_attributes": {
+                    "attr1_referent": { "name":"name" },
+                    "attr2_referent": { "name":"sex" }
+                },
+                "requested_predicates": {},
+            }).to_string();
+
+            let report_json = anoncreds::verifier_verify_proof_with_report(&proof_req,
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           &anoncreds::cred_defs_for_proof(),
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(false), report["valid"]);
+            assert_eq!(json!(false), report["structure"]["valid"]);
+            assert_eq!(json!("missing"), report["attributes"]["attr2_referent"]["status"]);
+            assert_eq!(json!("revealed"), report["attributes"]["attr1_referent"]["status"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missing_cred_def() {
+            let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                           &anoncreds::proof_json(),
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           "{}",
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!(false), report["valid"]);
+            assert_eq!(json!(false), report["sub_proofs"][0]["resolved"]["valid"]);
+            assert_eq!(serde_json::Value::Null, report["crypto_proof"]);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_invalid_proof_json() {
+            let res = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                   r#"{"proof": {}}"#,
+                                                                   &anoncreds::schemas_for_proof(),
+                                                                   &anoncreds::cred_defs_for_proof(),
+                                                                   "{}",
+                                                                   "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
 }
@@ -10,7 +10,7 @@ extern crate indyrs as api;
 mod demos {
     use super::*;
 
-    use crate::utils::{wallet, anoncreds};
+    use crate::utils::{wallet, anoncreds, blob_storage};
     use crate::utils::anoncreds::{COMMON_MASTER_SECRET, CREDENTIAL1_ID, CREDENTIAL2_ID, CREDENTIAL3_ID};
 
     use indy::ErrorCode;
@@ -27,6 +27,7 @@ mod demos {
     use crate::utils::domain::anoncreds::revocation_state::RevocationState;
     use crate::utils::domain::anoncreds::revocation_registry::RevocationRegistry;
     use crate::utils::domain::anoncreds::credential_offer::CredentialOffer;
+    use crate::utils::domain::anoncreds::revocation_registry_manager::{IssuedCredential, IssuedCredentialStatus, ManagedRevocationRegistryInfo, ManagedRevocationRegistryStatus};
 
     #[test]
     fn anoncreds_works_for_single_issuer_single_prover() {
@@ -1602,6 +1603,398 @@ mod demos {
         wallet::close_and_delete_wallet(issuer_abc_wallet_handle, &issuer_abc_wallet_config).unwrap();
     }
 
+    #[test]
+    fn anoncreds_works_for_attribute_equality_from_different_credentials() {
+        Setup::empty();
+
+        //1. Issuer1 creates wallet, gets wallet handles
+        let (issuer_gvt_wallet_handle, issuer_gvt_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_attribute_equality_from_different_credentials").unwrap();
+
+        //2. Issuer2 creates wallet, gets wallet handles
+        let (issuer_abc_wallet_handle, issuer_abc_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_attribute_equality_from_different_credentials").unwrap();
+
+        //3. Prover creates wallet, gets wallet handles
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_attribute_equality_from_different_credentials").unwrap();
+
+        //4. Issuer creates Schema and Credential Definition
+        let (gvt_schema_id, gvt_schema, gvt_cred_def_id, gvt_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_gvt_wallet_handle,
+                                                                                                                        ISSUER_DID,
+                                                                                                                        GVT_SCHEMA_NAME,
+                                                                                                                        GVT_SCHEMA_ATTRIBUTES);
+
+        //5. Issuer creates Schema and Credential Definition
+        let (abc_schema_id, abc_schema, abc_cred_def_id, abc_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_abc_wallet_handle,
+                                                                                                                        ISSUER_DID,
+                                                                                                                        "abc",
+                                                                                                                        r#"["name", "second_name", "experience"]"#);
+
+        //6. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //7. Issuer1 issue GVT Credential for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_gvt_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &gvt_cred_def_id,
+                                                 &gvt_cred_def_json);
+
+        //8. Issuer2 issue two ABC Credentials for Prover: with the same and with different name
+        let abc_cred_values_same = r#"{
+            "name": {"raw":"Alex", "encoded": "1139481716457488690172217916278103335"},
+            "second_name": {"raw":"Park", "encoded": "42935129364832492914638245934"},
+            "experience": {"raw":"5", "encoded": "5"}
+        }"#;
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_abc_wallet_handle,
+                                                 CREDENTIAL2_ID,
+                                                 abc_cred_values_same,
+                                                 &abc_cred_def_id,
+                                                 &abc_cred_def_json);
+
+        let abc_cred_values_different = r#"{
+            "name": {"raw":"Alexander", "encoded": "126328542632549235769221"},
+            "second_name": {"raw":"Park", "encoded": "42935129364832492914638245934"},
+            "experience": {"raw":"5", "encoded": "5"}
+        }"#;
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_abc_wallet_handle,
+                                                 CREDENTIAL3_ID,
+                                                 abc_cred_values_different,
+                                                 &abc_cred_def_id,
+                                                 &abc_cred_def_json);
+
+        //9. Verifier asks to prove that hidden names from different Credentials are equal
+        let proof_req = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name",
+                   "restrictions": json!({ "cred_def_id": gvt_cred_def_id })
+               }),
+               "attr2_referent": json!({
+                   "name":"name",
+                   "restrictions": json!({ "cred_def_id": abc_cred_def_id })
+               })
+           }),
+           "requested_predicates": json!({}),
+        });
+
+        let mut proof_req_with_equality = proof_req.clone();
+        proof_req_with_equality["requested_equalities"] = json!({
+            "equality1_referent": json!({ "attr_referents": ["attr1_referent", "attr2_referent"] })
+        });
+
+        let proof_req_json = proof_req.to_string();
+        let proof_req_with_equality_json = proof_req_with_equality.to_string();
+
+        let schemas_json = json!({
+            gvt_schema_id: serde_json::from_str::<Schema>(&gvt_schema).unwrap(),
+            abc_schema_id: serde_json::from_str::<Schema>(&abc_schema).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            gvt_cred_def_id: serde_json::from_str::<CredentialDefinition>(&gvt_cred_def_json).unwrap(),
+            abc_cred_def_id: serde_json::from_str::<CredentialDefinition>(&abc_cred_def_json).unwrap()
+        }).to_string();
+        let rev_states_json = json!({}).to_string();
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let requested_credentials_json = |abc_cred_id: &str| json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":false }),
+                "attr2_referent": json!({ "cred_id": abc_cred_id, "revealed":false })
+             }),
+             "requested_predicates": json!({})
+        }).to_string();
+
+        //10. Prover creates Proof for Credentials with the same name
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_with_equality_json,
+                                                        &requested_credentials_json(CREDENTIAL2_ID),
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+        assert!(proof.requested_proof.revealed_attrs.is_empty());
+
+        //11. Verifier verifies proof
+        let valid = anoncreds::verifier_verify_proof(&proof_req_with_equality_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //12. Prover can not create Proof for Credentials with different names
+        let res = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                 &proof_req_with_equality_json,
+                                                 &requested_credentials_json(CREDENTIAL3_ID),
+                                                 COMMON_MASTER_SECRET,
+                                                 &schemas_json,
+                                                 &credential_defs_json,
+                                                 &rev_states_json);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        //13. Verifier rejects Proof created without equality for Credentials with different names
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json(CREDENTIAL3_ID),
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        let res = anoncreds::verifier_verify_proof(&proof_req_with_equality_json,
+                                                   &proof_json,
+                                                   &schemas_json,
+                                                   &credential_defs_json,
+                                                   &rev_reg_defs_json,
+                                                   &rev_regs_json);
+        assert_code!(ErrorCode::AnoncredsProofRejected, res);
+
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_gvt_wallet_handle, &issuer_gvt_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_abc_wallet_handle, &issuer_abc_wallet_config).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_attribute_equality_of_different_attributes() {
+        Setup::empty();
+
+        //1. Issuers and Prover create wallets, gets wallet handles
+        let (issuer_gvt_wallet_handle, issuer_gvt_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_attribute_equality_of_different_attributes").unwrap();
+        let (issuer_abc_wallet_handle, issuer_abc_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_attribute_equality_of_different_attributes").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_attribute_equality_of_different_attributes").unwrap();
+
+        //2. Issuers create Schemas and Credential Definitions
+        let (gvt_schema_id, gvt_schema, gvt_cred_def_id, gvt_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_gvt_wallet_handle,
+                                                                                                                        ISSUER_DID,
+                                                                                                                        GVT_SCHEMA_NAME,
+                                                                                                                        GVT_SCHEMA_ATTRIBUTES);
+
+        let (abc_schema_id, abc_schema, abc_cred_def_id, abc_cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_abc_wallet_handle,
+                                                                                                                        ISSUER_DID,
+                                                                                                                        "abc",
+                                                                                                                        r#"["first_name", "experience"]"#);
+
+        //3. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //4. Issuers issue Credentials for Prover: GVT "name" and ABC "first_name" have the same value
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_gvt_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &gvt_cred_def_id,
+                                                 &gvt_cred_def_json);
+
+        let abc_cred_values = r#"{
+            "first_name": {"raw":"Alex", "encoded": "1139481716457488690172217916278103335"},
+            "experience": {"raw":"5", "encoded": "5"}
+        }"#;
+
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_abc_wallet_handle,
+                                                 CREDENTIAL2_ID,
+                                                 abc_cred_values,
+                                                 &abc_cred_def_id,
+                                                 &abc_cred_def_json);
+
+        //5. Verifier asks to prove that hidden "name" and "first_name" are equal
+        let proof_req_json = json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({
+                   "name":"name",
+                   "restrictions": json!({ "cred_def_id": gvt_cred_def_id })
+               }),
+               "attr2_referent": json!({
+                   "name":"first_name",
+                   "restrictions": json!({ "cred_def_id": abc_cred_def_id })
+               })
+           }),
+           "requested_predicates": json!({}),
+           "requested_equalities": json!({
+               "equality1_referent": json!({ "attr_referents": ["attr1_referent", "attr2_referent"] })
+           }),
+        }).to_string();
+
+        let schemas_json = json!({
+            gvt_schema_id: serde_json::from_str::<Schema>(&gvt_schema).unwrap(),
+            abc_schema_id: serde_json::from_str::<Schema>(&abc_schema).unwrap()
+        }).to_string();
+
+        let credential_defs_json = json!({
+            gvt_cred_def_id: serde_json::from_str::<CredentialDefinition>(&gvt_cred_def_json).unwrap(),
+            abc_cred_def_id: serde_json::from_str::<CredentialDefinition>(&abc_cred_def_json).unwrap()
+        }).to_string();
+        let rev_states_json = json!({}).to_string();
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let requested_credentials_json = json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":false }),
+                "attr2_referent": json!({ "cred_id": CREDENTIAL2_ID, "revealed":false })
+             }),
+             "requested_predicates": json!({})
+        }).to_string();
+
+        //6. Prover creates Proof
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+
+        //7. Verifier verifies proof
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_gvt_wallet_handle, &issuer_gvt_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_abc_wallet_handle, &issuer_abc_wallet_config).unwrap();
+    }
+
+    #[test]
+    fn anoncreds_works_for_revealed_attribute_set_membership() {
+        Setup::empty();
+
+        //1. Issuer and Prover create wallets, gets wallet handles
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revealed_attribute_set_membership").unwrap();
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revealed_attribute_set_membership").unwrap();
+
+        //2. Issuer creates Schema and Credential Definition
+        let (schema_id, schema_json, cred_def_id, cred_def_json) = anoncreds::multi_steps_issuer_preparation(issuer_wallet_handle,
+                                                                                                             ISSUER_DID,
+                                                                                                             GVT_SCHEMA_NAME,
+                                                                                                             GVT_SCHEMA_ATTRIBUTES);
+
+        //3. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //4. Issuer issues GVT Credential with age 28 for Prover
+        anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                 prover_wallet_handle,
+                                                 issuer_wallet_handle,
+                                                 CREDENTIAL1_ID,
+                                                 &anoncreds::gvt_credential_values_json(),
+                                                 &cred_def_id,
+                                                 &cred_def_json);
+
+        //5. Verifier asks to reveal age from the set
+        let proof_req = |values: serde_json::Value| json!({
+           "nonce":"123432421212",
+           "name":"proof_req_1",
+           "version":"0.1",
+           "requested_attributes": json!({
+               "attr1_referent": json!({ "name":"age" })
+           }),
+           "requested_predicates": json!({}),
+           "requested_memberships": json!({
+               "membership1_referent": json!({ "attr_referent": "attr1_referent", "values": values })
+           }),
+        }).to_string();
+
+        let proof_req_json = proof_req(json!(["25", "28", "30"]));
+
+        let schemas_json = json!({
+            schema_id: serde_json::from_str::<Schema>(&schema_json).unwrap(),
+        }).to_string();
+
+        let credential_defs_json = json!({
+            cred_def_id: serde_json::from_str::<CredentialDefinition>(&cred_def_json).unwrap(),
+        }).to_string();
+        let rev_states_json = json!({}).to_string();
+        let rev_reg_defs_json = json!({}).to_string();
+        let rev_regs_json = json!({}).to_string();
+
+        let requested_credentials = |revealed: bool| json!({
+             "self_attested_attributes": json!({}),
+             "requested_attributes": json!({
+                "attr1_referent": json!({ "cred_id": CREDENTIAL1_ID, "revealed":revealed })
+             }),
+             "requested_predicates": json!({})
+        }).to_string();
+        let requested_credentials_json = requested_credentials(true);
+
+        //6. Prover creates Proof
+        let proof_json = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                        &proof_req_json,
+                                                        &requested_credentials_json,
+                                                        COMMON_MASTER_SECRET,
+                                                        &schemas_json,
+                                                        &credential_defs_json,
+                                                        &rev_states_json).unwrap();
+        let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+        assert_eq!("28", proof.requested_proof.revealed_attrs.get("attr1_referent").unwrap().raw);
+
+        //7. Verifier verifies proof
+        let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                     &proof_json,
+                                                     &schemas_json,
+                                                     &credential_defs_json,
+                                                     &rev_reg_defs_json,
+                                                     &rev_regs_json).unwrap();
+        assert!(valid);
+
+        //8. Verifier rejects Proof for other set
+        let res = anoncreds::verifier_verify_proof(&proof_req(json!(["25", "29", "30"])),
+                                                   &proof_json,
+                                                   &schemas_json,
+                                                   &credential_defs_json,
+                                                   &rev_reg_defs_json,
+                                                   &rev_regs_json);
+        assert_code!(ErrorCode::AnoncredsProofRejected, res);
+
+        //9. Prover can not create Proof for set without its value
+        let res = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                 &proof_req(json!(["25", "30"])),
+                                                 &requested_credentials_json,
+                                                 COMMON_MASTER_SECRET,
+                                                 &schemas_json,
+                                                 &credential_defs_json,
+                                                 &rev_states_json);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        //10. Prover can not create Proof for hidden value
+        let res = anoncreds::prover_create_proof(prover_wallet_handle,
+                                                 &proof_req_json,
+                                                 &requested_credentials(false),
+                                                 COMMON_MASTER_SECRET,
+                                                 &schemas_json,
+                                                 &credential_defs_json,
+                                                 &rev_states_json);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_twice_entry_of_credential_for_different_witness() {
         Setup::empty();
@@ -4085,4 +4478,236 @@ mod demos {
         wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
         wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
     }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_manager_rollover() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_manager_rollover").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_manager_rollover").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+        //4. Issuer creates Revocation Registry Manager with the first Revocation Registry
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let tails_writer_handle = blob_storage::open_writer("default", &tails_writer_config).unwrap();
+
+        let (rev_reg_id_1, _, _) = anoncreds::issuer_create_revoc_reg_manager(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              &cred_def_id,
+                                                                              Some(r#"{"max_cred_num":2, "rollover_threshold":0.5}"#),
+                                                                              tails_writer_handle).unwrap();
+
+        let res = anoncreds::issuer_create_revoc_reg_manager(issuer_wallet_handle, ISSUER_DID, &cred_def_id, None, tails_writer_handle);
+        assert_code!(ErrorCode::WalletItemAlreadyExists, res);
+
+        let blob_storage_reader_handle = blob_storage::open_reader("default", &tails_writer_config).unwrap();
+
+        //5. Issuer issues three Credentials
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let issue_credential = || {
+            let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+            let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                             DID_MY1,
+                                                                             &cred_offer_json,
+                                                                             &cred_def_json,
+                                                                             COMMON_MASTER_SECRET).unwrap();
+
+            let (cred_json, cred_rev_id, _) =
+                anoncreds::issuer_create_credential_with_revoc_reg_manager(issuer_wallet_handle,
+                                                                           &cred_offer_json,
+                                                                           &cred_req_json,
+                                                                           &anoncreds::gvt_credential_values_json(),
+                                                                           tails_writer_handle,
+                                                                           blob_storage_reader_handle).unwrap();
+
+            let credential: serde_json::Value = serde_json::from_str(&cred_json).unwrap();
+            (credential["rev_reg_id"].as_str().unwrap().to_string(), cred_rev_id.unwrap())
+        };
+
+        let get_state = || {
+            let registries_json = anoncreds::issuer_get_revoc_reg_manager_state(issuer_wallet_handle, &cred_def_id).unwrap();
+            serde_json::from_str::<Vec<ManagedRevocationRegistryInfo>>(&registries_json).unwrap()
+        };
+
+        // The next registry is created in advance once the threshold is crossed
+        let (cred_1_rev_reg_id, cred_1_rev_id) = issue_credential();
+        assert_eq!(rev_reg_id_1, cred_1_rev_reg_id);
+
+        let registries = get_state();
+        assert_eq!(2, registries.len());
+        assert_eq!(ManagedRevocationRegistryStatus::Active, registries[0].status);
+        assert_eq!(ManagedRevocationRegistryStatus::Pending, registries[1].status);
+        assert!(registries[0].rev_reg_def.is_none());
+        assert!(registries[1].rev_reg_def.is_some());
+        assert!(registries[1].rev_reg.is_some());
+
+        // The next registry can be used once it is published to the ledger
+        anoncreds::issuer_mark_revoc_reg_published(issuer_wallet_handle, &registries[1].rev_reg_id.0).unwrap();
+        assert_eq!(ManagedRevocationRegistryStatus::Ready, get_state()[1].status);
+
+        let (cred_2_rev_reg_id, _) = issue_credential();
+        assert_eq!(rev_reg_id_1, cred_2_rev_reg_id);
+
+        // The full registry is replaced by the ready one
+        let (cred_3_rev_reg_id, _) = issue_credential();
+        assert_eq!(registries[1].rev_reg_id.0, cred_3_rev_reg_id);
+
+        let registries = get_state();
+        assert_eq!(3, registries.len());
+        assert_eq!(ManagedRevocationRegistryStatus::Full, registries[0].status);
+        assert_eq!(ManagedRevocationRegistryStatus::Active, registries[1].status);
+        assert_eq!(ManagedRevocationRegistryStatus::Pending, registries[2].status);
+        assert_eq!(2, registries[0].issued);
+        assert_eq!(1, registries[1].issued);
+
+        //6. Issuer revokes the first Credential
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle,
+                                            blob_storage_reader_handle,
+                                            &rev_reg_id_1,
+                                            &cred_1_rev_id).unwrap();
+
+        let registries = get_state();
+        assert_eq!(1, registries[0].revoked);
+        assert_eq!(0, registries[1].revoked);
+
+        //7. Issuer gets issued and revoked Credentials
+        let issued_credentials_json = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, None).unwrap();
+        let issued_credentials: Vec<IssuedCredential> = serde_json::from_str(&issued_credentials_json).unwrap();
+        assert_eq!(3, issued_credentials.len());
+
+        let query_json = json!({ "cred_def_id": cred_def_id, "status": "revoked" }).to_string();
+        let revoked_credentials_json = anoncreds::issuer_get_issued_credentials(issuer_wallet_handle, Some(&query_json)).unwrap();
+        let revoked_credentials: Vec<IssuedCredential> = serde_json::from_str(&revoked_credentials_json).unwrap();
+        assert_eq!(1, revoked_credentials.len());
+        assert_eq!(rev_reg_id_1, revoked_credentials[0].rev_reg_id.0);
+        assert_eq!(cred_1_rev_id, revoked_credentials[0].cred_rev_id);
+        assert_eq!(IssuedCredentialStatus::Revoked, revoked_credentials[0].status);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_manager_rollover_without_ready_registry() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_manager_rollover_without_ready_registry").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_manager_rollover_without_ready_registry").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+        //4. Issuer creates Revocation Registry Manager with the first Revocation Registry
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let tails_writer_handle = blob_storage::open_writer("default", &tails_writer_config).unwrap();
+        let invalid_tails_writer_handle = tails_writer_handle + 100;
+
+        let (rev_reg_id_1, _, _) = anoncreds::issuer_create_revoc_reg_manager(issuer_wallet_handle,
+                                                                              ISSUER_DID,
+                                                                              &cred_def_id,
+                                                                              Some(r#"{"max_cred_num":1}"#),
+                                                                              tails_writer_handle).unwrap();
+
+        let blob_storage_reader_handle = blob_storage::open_reader("default", &tails_writer_config).unwrap();
+
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let issue_credential = |tails_writer_handle: i32| {
+            let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+            let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                             DID_MY1,
+                                                                             &cred_offer_json,
+                                                                             &cred_def_json,
+                                                                             COMMON_MASTER_SECRET).unwrap();
+
+            anoncreds::issuer_create_credential_with_revoc_reg_manager(issuer_wallet_handle,
+                                                                       &cred_offer_json,
+                                                                       &cred_req_json,
+                                                                       &anoncreds::gvt_credential_values_json(),
+                                                                       tails_writer_handle,
+                                                                       blob_storage_reader_handle)
+                .map(|(cred_json, _, _)| {
+                    let credential: serde_json::Value = serde_json::from_str(&cred_json).unwrap();
+                    credential["rev_reg_id"].as_str().unwrap().to_string()
+                })
+        };
+
+        let get_state = || {
+            let registries_json = anoncreds::issuer_get_revoc_reg_manager_state(issuer_wallet_handle, &cred_def_id).unwrap();
+            serde_json::from_str::<Vec<ManagedRevocationRegistryInfo>>(&registries_json).unwrap()
+        };
+
+        //5. Issuer issues Credential, but the next Revocation Registry can't be created in advance
+        assert_eq!(rev_reg_id_1, issue_credential(invalid_tails_writer_handle).unwrap());
+        assert_eq!(1, get_state().len());
+
+        //6. Issuance fails while the next Revocation Registry can't be created
+        assert!(issue_credential(invalid_tails_writer_handle).is_err());
+
+        let registries = get_state();
+        assert_eq!(1, registries.len());
+        assert_eq!(ManagedRevocationRegistryStatus::Active, registries[0].status);
+
+        //7. Issuance fails until the created Revocation Registry is published
+        let res = issue_credential(tails_writer_handle);
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+
+        let registries = get_state();
+        assert_eq!(2, registries.len());
+        assert_eq!(ManagedRevocationRegistryStatus::Full, registries[0].status);
+        assert_eq!(ManagedRevocationRegistryStatus::Pending, registries[1].status);
+        assert!(registries[1].rev_reg_def.is_some());
+        assert!(registries[1].rev_reg.is_some());
+
+        let res = issue_credential(tails_writer_handle);
+        assert_code!(ErrorCode::AnoncredsRevocationRegistryFullError, res);
+        assert_eq!(2, get_state().len());
+
+        //8. Issuer issues Credential using the published Revocation Registry
+        anoncreds::issuer_mark_revoc_reg_published(issuer_wallet_handle, &registries[1].rev_reg_id.0).unwrap();
+
+        let res = anoncreds::issuer_mark_revoc_reg_published(issuer_wallet_handle, &registries[1].rev_reg_id.0);
+        assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+        assert_eq!(registries[1].rev_reg_id.0, issue_credential(tails_writer_handle).unwrap());
+
+        let registries = get_state();
+        assert_eq!(ManagedRevocationRegistryStatus::Active, registries[1].status);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
 }
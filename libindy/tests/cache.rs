@@ -426,4 +426,323 @@ mod high_cases {
             purge_cred_def_cache(setup.wallet_handle, &json!({"minFresh": 1000}).to_string()).unwrap();
         }
     }
-}
\ No newline at end of file
+
+    mod nym_cache {
+        use super::*;
+        use crate::utils::domain::ledger::nym::NymData;
+        use crate::utils::constants::*;
+
+        #[test]
+        fn indy_get_nym_cache_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let options_json = json!({}).to_string();
+            let nym_json1 = get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &options_json
+            ).unwrap();
+            let nym: NymData = serde_json::from_str(&nym_json1).unwrap();
+            assert_eq!(DID_TRUSTEE, nym.did.0);
+
+            // now retrieve it from cache
+            let options_json = json!({"noUpdate": true}).to_string();
+            let nym_json2 = get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &options_json
+            ).unwrap();
+
+            assert_eq!(nym_json1, nym_json2);
+        }
+
+        #[test]
+        fn indy_get_nym_only_cache_no_cached_data() {
+            let setup = Setup::wallet_and_pool();
+
+            let options_json = json!({"noUpdate": true}).to_string();
+
+            let res = get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &options_json);
+
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_get_nym_no_store_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let options_json = json!({"noStore": true}).to_string();
+            get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &options_json
+            ).unwrap();
+
+            // it should not be present inside of cache, because of noStore option in previous request.
+            let options_json = json!({"noUpdate": true}).to_string();
+            let res = get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &options_json
+            );
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_nym_cache_works() {
+            let setup = Setup::wallet_and_pool();
+
+            get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &json!({}).to_string()
+            ).unwrap();
+
+            purge_nym_cache(setup.wallet_handle, "{}").unwrap();
+
+            let res = get_nym_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                &json!({"noUpdate": true}).to_string()
+            );
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+    }
+
+    mod attrib_cache {
+        use super::*;
+        use crate::utils::constants::*;
+        use crate::utils::types::ResponseType;
+
+        #[test]
+        fn indy_get_attrib_cache_works() {
+            let setup = Setup::new_identity();
+
+            let attrib_request = utils::ledger::build_attrib_request(&setup.did,
+                                                                     &setup.did,
+                                                                     None,
+                                                                     Some(ATTRIB_RAW_DATA),
+                                                                     None).unwrap();
+            let attrib_response = utils::ledger::sign_and_submit_request(setup.pool_handle, setup.wallet_handle, &setup.did, &attrib_request).unwrap();
+            utils::pool::check_response_type(&attrib_response, ResponseType::REPLY);
+
+            let get_attrib_request = utils::ledger::build_get_attrib_request(Some(&setup.did), &setup.did, Some("endpoint"), None, None).unwrap();
+            utils::ledger::submit_request_with_retries(setup.pool_handle, &get_attrib_request, &attrib_response).unwrap();
+
+            let attrib_data = get_attrib_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                &setup.did,
+                &setup.did,
+                Some("endpoint"),
+                None,
+                None,
+                &json!({}).to_string()
+            ).unwrap();
+            assert_eq!(ATTRIB_RAW_DATA, attrib_data);
+
+            // now retrieve it from cache
+            let attrib_data = get_attrib_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                &setup.did,
+                &setup.did,
+                Some("endpoint"),
+                None,
+                None,
+                &json!({"noUpdate": true}).to_string()
+            ).unwrap();
+            assert_eq!(ATTRIB_RAW_DATA, attrib_data);
+        }
+
+        #[test]
+        fn indy_get_attrib_cache_works_for_missed_attribute() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = get_attrib_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                None,
+                None,
+                None,
+                &json!({}).to_string()
+            );
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_get_attrib_only_cache_no_cached_data() {
+            let setup = Setup::wallet_and_pool();
+
+            let res = get_attrib_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                DID_TRUSTEE,
+                Some("endpoint"),
+                None,
+                None,
+                &json!({"noUpdate": true}).to_string()
+            );
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_attrib_cache_no_options() {
+            let setup = Setup::wallet();
+            purge_attrib_cache(setup.wallet_handle, "{}").unwrap();
+        }
+    }
+
+    mod revoc_reg_def_cache {
+        use super::*;
+        use crate::utils::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinitionV1;
+        use crate::utils::constants::*;
+
+        #[test]
+        fn indy_get_revoc_reg_def_cache_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            let revoc_reg_def_json1 = get_revoc_reg_def_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                &json!({}).to_string()
+            ).unwrap();
+            let revoc_reg_def: RevocationRegistryDefinitionV1 = serde_json::from_str(&revoc_reg_def_json1).unwrap();
+            assert_eq!(rev_reg_id, revoc_reg_def.id.0);
+
+            // now retrieve it from cache
+            let revoc_reg_def_json2 = get_revoc_reg_def_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                &json!({"noUpdate": true}).to_string()
+            ).unwrap();
+
+            assert_eq!(revoc_reg_def_json1, revoc_reg_def_json2);
+        }
+
+        #[test]
+        fn indy_get_revoc_reg_def_only_cache_no_cached_data() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            let res = get_revoc_reg_def_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                &json!({"noUpdate": true}).to_string()
+            );
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_revoc_reg_def_cache_no_options() {
+            let setup = Setup::wallet();
+            purge_revoc_reg_def_cache(setup.wallet_handle, "{}").unwrap();
+        }
+    }
+
+    mod revoc_reg_delta_cache {
+        use super::*;
+        use crate::utils::domain::anoncreds::revocation_registry_delta::RevocationRegistryDeltaV1;
+        use crate::utils::constants::*;
+
+        #[test]
+        fn indy_get_revoc_reg_delta_cache_works() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            let to = time::get_time().sec + 300;
+
+            let (revoc_reg_delta_json1, timestamp1) = get_revoc_reg_delta_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                None,
+                to,
+                &json!({}).to_string()
+            ).unwrap();
+            let _revoc_reg_delta: RevocationRegistryDeltaV1 = serde_json::from_str(&revoc_reg_delta_json1).unwrap();
+
+            // now retrieve it from cache
+            let (revoc_reg_delta_json2, timestamp2) = get_revoc_reg_delta_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                None,
+                to,
+                &json!({"noUpdate": true}).to_string()
+            ).unwrap();
+
+            assert_eq!(revoc_reg_delta_json1, revoc_reg_delta_json2);
+            assert_eq!(timestamp1, timestamp2);
+        }
+
+        #[test]
+        fn indy_get_revoc_reg_delta_cache_works_for_other_interval() {
+            let setup = Setup::wallet_and_pool();
+
+            let (_, _, rev_reg_id) = utils::ledger::post_entities();
+
+            let to = time::get_time().sec + 300;
+
+            get_revoc_reg_delta_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                None,
+                to,
+                &json!({}).to_string()
+            ).unwrap();
+
+            // deltas are cached per interval
+            let res = get_revoc_reg_delta_cache(
+                setup.pool_handle,
+                setup.wallet_handle,
+                DID_MY1,
+                rev_reg_id,
+                Some(0),
+                to,
+                &json!({"noUpdate": true}).to_string()
+            );
+            assert_code!(ErrorCode::LedgerNotFound, res);
+        }
+
+        #[test]
+        fn indy_purge_revoc_reg_delta_cache_no_options() {
+            let setup = Setup::wallet();
+            purge_revoc_reg_delta_cache(setup.wallet_handle, "{}").unwrap();
+        }
+    }
+}
@@ -326,6 +326,397 @@ mod high_cases {
             assert_code!(ErrorCode::WalletItemNotFound, res);
         }
     }
+
+    mod pack_message_v2 {
+        use super::*;
+        use crate::utils::did;
+
+        #[derive(Deserialize, Debug)]
+        pub struct UnpackMessageV2 {
+            pub message: String,
+            pub recipient_kid: String,
+            pub sender_kid: Option<String>,
+        }
+
+        // Builds ECDH-ES+A256KW/A256GCM JWE the way any party knowing only receiver verkey can do it
+        fn _anoncrypt_jwe(receiver_verkey: &str, skid: Option<&str>) -> Vec<u8> {
+            use indy_utils::crypto::{base64, ed25519_box, ed25519_sign};
+            use openssl::aes::{AesKey, wrap_key};
+            use openssl::sha::Sha256;
+            use openssl::symm::{Cipher, encrypt_aead};
+            use rust_base58::FromBase58;
+
+            fn update_with_len(hasher: &mut Sha256, data: &[u8]) {
+                hasher.update(&(data.len() as u32).to_be_bytes());
+                hasher.update(data);
+            }
+
+            let alg = "ECDH-ES+A256KW";
+
+            let receiver_vk = ed25519_sign::PublicKey::from_slice(&receiver_verkey.from_base58().unwrap()).unwrap();
+            let receiver_pk = ed25519_sign::vk_to_curve25519(&receiver_vk).unwrap();
+            let (epk, esk) = ed25519_box::gen_keypair();
+
+            let mut apv = Sha256::new();
+            apv.update(receiver_verkey.as_bytes());
+            let apv = apv.finish();
+
+            let mut protected = json!({
+                "typ": "application/didcomm-encrypted+json",
+                "alg": alg,
+                "enc": "A256GCM",
+                "epk": {"kty": "OKP", "crv": "X25519", "x": base64::encode_urlsafe_nopad(&epk[..])},
+                "apv": base64::encode_urlsafe_nopad(&apv),
+            });
+            if let Some(skid) = skid {
+                protected["skid"] = json!(skid);
+            }
+            let protected = base64::encode_urlsafe_nopad(protected.to_string().as_bytes());
+
+            let cek = [7u8; 32];
+            let iv = [9u8; 12];
+            let mut tag = [0u8; 16];
+            let ciphertext = encrypt_aead(Cipher::aes_256_gcm(), &cek, Some(&iv), protected.as_bytes(), AGENT_MESSAGE.as_bytes(), &mut tag).unwrap();
+
+            let mut kek = Sha256::new();
+            kek.update(&1u32.to_be_bytes());
+            kek.update(&ed25519_box::scalarmult(&esk, &receiver_pk).unwrap());
+            update_with_len(&mut kek, alg.as_bytes());
+            update_with_len(&mut kek, &[]);
+            update_with_len(&mut kek, &apv);
+            kek.update(&256u32.to_be_bytes());
+            let kek = kek.finish();
+
+            let mut encrypted_key = [0u8; 40];
+            wrap_key(&AesKey::new_encrypt(&kek).unwrap(), None, &mut encrypted_key, &cek).unwrap();
+
+            json!({
+                "protected": protected,
+                "recipients": [{"header": {"kid": receiver_verkey}, "encrypted_key": base64::encode_urlsafe_nopad(&encrypted_key)}],
+                "iv": base64::encode_urlsafe_nopad(&iv),
+                "ciphertext": base64::encode_urlsafe_nopad(&ciphertext),
+                "tag": base64::encode_urlsafe_nopad(&tag),
+            }).to_string().into_bytes()
+        }
+
+        #[test]
+        fn indy_pack_message_v2_anoncrypt_works() {
+            let sender_setup = Setup::wallet();
+            let receiver_setup = Setup::key();
+
+            let receiver_kids = json!([VERKEY_TRUSTEE, receiver_setup.verkey]).to_string();
+            let jwe = crypto::pack_message_v2(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, None, None).unwrap();
+
+            let jwe_json: serde_json::Value = serde_json::from_slice(&jwe).unwrap();
+            assert_eq!(2, jwe_json["recipients"].as_array().unwrap().len());
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, &jwe).unwrap();
+            let res: UnpackMessageV2 = serde_json::from_slice(&res).unwrap();
+
+            assert_eq!(AGENT_MESSAGE, res.message);
+            assert_eq!(receiver_setup.verkey, res.recipient_kid);
+            assert_eq!(None, res.sender_kid);
+        }
+
+        #[test]
+        fn indy_pack_message_v2_authcrypt_works_for_did_urls() {
+            let sender_setup = Setup::did();
+            let receiver_setup = Setup::did();
+
+            let sender_kid = format!("{}#key-1", sender_setup.did);
+            let receiver_kid = format!("{}#key-1", receiver_setup.did);
+
+            did::store_their_did_from_parts(sender_setup.wallet_handle, &receiver_setup.did, &receiver_setup.verkey).unwrap();
+            did::store_their_did_from_parts(receiver_setup.wallet_handle, &sender_setup.did, &sender_setup.verkey).unwrap();
+
+            let receiver_kids = json!([receiver_kid]).to_string();
+            let options = json!({"enc": "A256GCM"}).to_string();
+            let jwe = crypto::pack_message_v2(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, Some(&sender_kid), Some(&options)).unwrap();
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, &jwe).unwrap();
+            let res: UnpackMessageV2 = serde_json::from_slice(&res).unwrap();
+
+            assert_eq!(AGENT_MESSAGE, res.message);
+            assert_eq!(receiver_kid, res.recipient_kid);
+            assert_eq!(Some(sender_kid), res.sender_kid);
+        }
+
+        #[test]
+        fn indy_pack_message_v2_works_for_did_key_url_with_fingerprint() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::wallet();
+
+            let (receiver_did, _) = did::create_my_did(receiver_setup.wallet_handle, &json!({"method_name": "key"}).to_string()).unwrap();
+            let receiver_kid = format!("{}#{}", receiver_did, &receiver_did["did:key:".len()..]);
+
+            let receiver_kids = json!([receiver_kid]).to_string();
+            let jwe = crypto::pack_message_v2(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, None, None).unwrap();
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, &jwe).unwrap();
+            let res: UnpackMessageV2 = serde_json::from_slice(&res).unwrap();
+
+            assert_eq!(AGENT_MESSAGE, res.message);
+            assert_eq!(receiver_kid, res.recipient_kid);
+        }
+
+        #[test]
+        fn indy_pack_message_v2_fails_for_did_url_with_unknown_fragment() {
+            let setup = Setup::did();
+
+            let receiver_kids = json!([format!("{}#key-2", setup.did)]).to_string();
+            let res = crypto::pack_message_v2(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, None, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_unpack_message_v2_fails_for_tampered_ciphertext() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_kids = json!([receiver_setup.verkey]).to_string();
+            let jwe = crypto::pack_message_v2(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, Some(&sender_setup.verkey), None).unwrap();
+
+            let mut jwe_json: serde_json::Value = serde_json::from_slice(&jwe).unwrap();
+            jwe_json["ciphertext"] = json!("AAAA");
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, jwe_json.to_string().as_bytes());
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_unpack_message_v2_works_for_anoncrypt_jwe_built_by_third_party() {
+            let receiver_setup = Setup::key();
+
+            let jwe = _anoncrypt_jwe(&receiver_setup.verkey, None);
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, &jwe).unwrap();
+            let res: UnpackMessageV2 = serde_json::from_slice(&res).unwrap();
+
+            assert_eq!(AGENT_MESSAGE, res.message);
+            assert_eq!(None, res.sender_kid);
+        }
+
+        #[test]
+        fn indy_unpack_message_v2_fails_for_anoncrypt_jwe_with_forged_sender_kid() {
+            let receiver_setup = Setup::key();
+
+            let jwe = _anoncrypt_jwe(&receiver_setup.verkey, Some(VERKEY_TRUSTEE));
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, &jwe);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_unpack_message_v2_fails_no_matching_key() {
+            let sender_setup = Setup::key();
+            let receiver_setup = Setup::key();
+
+            let receiver_kids = json!([VERKEY_TRUSTEE]).to_string();
+            let jwe = crypto::pack_message_v2(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, Some(&sender_setup.verkey), None).unwrap();
+
+            let res = crypto::unpack_message_v2(receiver_setup.wallet_handle, &jwe);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+
+        #[test]
+        fn indy_pack_message_v2_fails_for_empty_receivers() {
+            let setup = Setup::key();
+
+            let res = crypto::pack_message_v2(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), "[]", None, None);
+            assert_code!(ErrorCode::CommonInvalidParam4, res);
+        }
+    }
+
+    mod ec_crypto_types {
+        use super::*;
+        use rust_base58::FromBase58;
+
+        const EC_SEED: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+        fn _sign_verify_works(crypto_type: &str) {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_with_crypto_type(setup.wallet_handle, Some(EC_SEED), crypto_type).unwrap();
+            assert!(verkey.ends_with(&format!(":{}", crypto_type)));
+            assert_eq!(33, verkey.split(':').next().unwrap().from_base58().unwrap().len());
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+            assert!(!crypto::verify(&verkey, b"other message", &signature).unwrap());
+        }
+
+        fn _auth_crypt_works(crypto_type: &str) {
+            let setup = Setup::wallet();
+
+            let sender_vk = crypto::create_key_with_crypto_type(setup.wallet_handle, None, crypto_type).unwrap();
+            let recipient_vk = crypto::create_key_with_crypto_type(setup.wallet_handle, None, crypto_type).unwrap();
+
+            let encrypted_msg = crypto::auth_crypt(setup.wallet_handle, &sender_vk, &recipient_vk, MESSAGE.as_bytes()).unwrap();
+
+            let (vk, msg) = crypto::auth_decrypt(setup.wallet_handle, &recipient_vk, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+            assert_eq!(sender_vk, vk);
+        }
+
+        fn _anon_crypt_works(crypto_type: &str) {
+            let setup = Setup::wallet();
+
+            let verkey = crypto::create_key_with_crypto_type(setup.wallet_handle, None, crypto_type).unwrap();
+
+            let encrypted_msg = crypto::anon_crypt(&verkey, MESSAGE.as_bytes()).unwrap();
+
+            let msg = crypto::anon_decrypt(setup.wallet_handle, &verkey, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+        }
+
+        #[test]
+        fn indy_crypto_sign_verify_works_for_secp256k1() {
+            _sign_verify_works("secp256k1");
+        }
+
+        #[test]
+        fn indy_crypto_sign_verify_works_for_p256() {
+            _sign_verify_works("p256");
+        }
+
+        #[test]
+        fn indy_crypto_auth_crypt_works_for_secp256k1() {
+            _auth_crypt_works("secp256k1");
+        }
+
+        #[test]
+        fn indy_crypto_auth_crypt_works_for_p256() {
+            _auth_crypt_works("p256");
+        }
+
+        #[test]
+        fn indy_crypto_anon_crypt_works_for_secp256k1() {
+            _anon_crypt_works("secp256k1");
+        }
+
+        #[test]
+        fn indy_crypto_anon_crypt_works_for_p256() {
+            _anon_crypt_works("p256");
+        }
+
+        #[test]
+        fn indy_crypto_auth_crypt_works_for_different_crypto_types() {
+            let setup = Setup::wallet();
+
+            let sender_vk = crypto::create_key_with_crypto_type(setup.wallet_handle, None, "secp256k1").unwrap();
+            let recipient_vk = crypto::create_key_with_crypto_type(setup.wallet_handle, None, "p256").unwrap();
+
+            let res = crypto::auth_crypt(setup.wallet_handle, &sender_vk, &recipient_vk, MESSAGE.as_bytes());
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+    }
+
+    mod key_manager {
+        use super::*;
+        use crate::utils::{did, ledger};
+
+        const KEY_MANAGER: &str = "file";
+
+        #[test]
+        fn indy_create_key_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let verkey = crypto::create_key_with_key_manager(setup.wallet_handle, Some(MY1_SEED), KEY_MANAGER).unwrap();
+            assert_eq!(VERKEY_MY1, verkey);
+        }
+
+        #[test]
+        fn indy_crypto_sign_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let verkey = crypto::create_key_with_key_manager(setup.wallet_handle, Some(MY1_SEED), KEY_MANAGER).unwrap();
+
+            let signature = crypto::sign(setup.wallet_handle, &verkey, MESSAGE.as_bytes()).unwrap();
+            assert_eq!(SIGNATURE.to_vec(), signature);
+            assert!(crypto::verify(&verkey, MESSAGE.as_bytes(), &signature).unwrap());
+        }
+
+        #[test]
+        fn indy_crypto_auth_crypt_works_for_key_manager() {
+            let setup = Setup::key();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let verkey = crypto::create_key_with_key_manager(setup.wallet_handle, None, KEY_MANAGER).unwrap();
+
+            let encrypted_msg = crypto::auth_crypt(setup.wallet_handle, &verkey, &setup.verkey, MESSAGE.as_bytes()).unwrap();
+            let (vk, msg) = crypto::auth_decrypt(setup.wallet_handle, &setup.verkey, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+            assert_eq!(verkey, vk);
+
+            let encrypted_msg = crypto::auth_crypt(setup.wallet_handle, &setup.verkey, &verkey, MESSAGE.as_bytes()).unwrap();
+            let (vk, msg) = crypto::auth_decrypt(setup.wallet_handle, &verkey, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+            assert_eq!(setup.verkey, vk);
+        }
+
+        #[test]
+        fn indy_crypto_anon_decrypt_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let verkey = crypto::create_key_with_key_manager(setup.wallet_handle, None, KEY_MANAGER).unwrap();
+
+            let encrypted_msg = crypto::anon_crypt(&verkey, MESSAGE.as_bytes()).unwrap();
+            let msg = crypto::anon_decrypt(setup.wallet_handle, &verkey, &encrypted_msg).unwrap();
+            assert_eq!(MESSAGE.as_bytes().to_vec(), msg);
+        }
+
+        #[test]
+        fn indy_pack_message_works_for_key_manager() {
+            let receiver_setup = Setup::wallet();
+            let sender_setup = Setup::key();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let receiver_vk = crypto::create_key_with_key_manager(receiver_setup.wallet_handle, None, KEY_MANAGER).unwrap();
+
+            let receiver_keys = json!([receiver_vk]).to_string();
+            let message = crypto::pack_message(sender_setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_keys, Some(&sender_setup.verkey)).unwrap();
+
+            let res = crypto::unpack_message(receiver_setup.wallet_handle, &message).unwrap();
+            let res: serde_json::Value = serde_json::from_slice(&res).unwrap();
+            assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+            assert_eq!(sender_setup.verkey, res["sender_verkey"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_pack_message_v2_works_for_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let sender_vk = crypto::create_key_with_key_manager(setup.wallet_handle, None, KEY_MANAGER).unwrap();
+            let receiver_vk = crypto::create_key_with_key_manager(setup.wallet_handle, None, KEY_MANAGER).unwrap();
+
+            let receiver_kids = json!([receiver_vk]).to_string();
+            let jwe = crypto::pack_message_v2(setup.wallet_handle, AGENT_MESSAGE.as_bytes(), &receiver_kids, Some(&sender_vk), None).unwrap();
+
+            let res = crypto::unpack_message_v2(setup.wallet_handle, &jwe).unwrap();
+            let res: serde_json::Value = serde_json::from_slice(&res).unwrap();
+            assert_eq!(AGENT_MESSAGE, res["message"].as_str().unwrap());
+            assert_eq!(sender_vk, res["sender_kid"].as_str().unwrap());
+        }
+
+        #[test]
+        fn indy_sign_request_works_for_did_in_key_manager() {
+            let setup = Setup::wallet();
+            crypto::register_key_manager(KEY_MANAGER, false).unwrap();
+
+            let (did, verkey) = did::create_my_did(setup.wallet_handle,
+                                                   &json!({"seed": MY1_SEED, "key_manager": KEY_MANAGER}).to_string()).unwrap();
+            assert_eq!(VERKEY_MY1, verkey);
+
+            let request = ledger::build_nym_request(&did, DID_MY1, None, None, None).unwrap();
+            let signed_request = ledger::sign_request(setup.wallet_handle, &did, &request).unwrap();
+            let signed_request: serde_json::Value = serde_json::from_str(&signed_request).unwrap();
+            assert!(signed_request["signature"].is_string());
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -588,6 +979,27 @@ mod medium_cases {
             assert!(res.is_ok());
         }
     }
+
+    mod key_manager {
+        use super::*;
+
+        #[test]
+        fn indy_register_key_manager_fails_for_duplicate_name() {
+            Setup::empty();
+            crypto::register_key_manager("file_duplicate", false).unwrap();
+
+            let res = crypto::register_key_manager("file_duplicate", true);
+            assert_eq!(ErrorCode::CommonInvalidStructure, res.unwrap_err());
+        }
+
+        #[test]
+        fn indy_create_key_fails_for_unknown_key_manager() {
+            let setup = Setup::wallet();
+
+            let res = crypto::create_key_with_key_manager(setup.wallet_handle, None, "unknown_key_manager");
+            assert_code!(ErrorCode::UnknownCryptoTypeError, res);
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -21,6 +21,8 @@ use std::collections::HashMap;
 
 pub const ENCRYPTED_MESSAGE: &'static [u8; 45] = &[187, 227, 10, 29, 46, 178, 12, 179, 197, 69, 171, 70, 228, 204, 52, 22, 199, 54, 62, 13, 115, 5, 216, 66, 20, 131, 121, 29, 251, 224, 253, 201, 75, 73, 225, 237, 219, 133, 35, 217, 131, 135, 232, 129, 32];
 pub const SIGNATURE: &'static [u8; 64] = &[20, 191, 100, 213, 101, 12, 197, 198, 203, 49, 89, 220, 205, 192, 224, 221, 97, 77, 220, 190, 90, 60, 142, 23, 16, 240, 189, 129, 45, 148, 245, 8, 102, 95, 95, 249, 100, 89, 41, 227, 213, 25, 100, 1, 232, 188, 245, 235, 186, 21, 52, 176, 236, 11, 99, 70, 155, 159, 89, 215, 197, 239, 138, 5];
+pub const DID_KEY: &'static str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+pub const DID_KEY_VERKEY: &'static str = "48GdbJyVULjHDaBNS6ct9oAGtckZUS5v8asrPzvZ7R1w";
 
 mod high_cases {
     use super::*;
@@ -83,6 +85,14 @@ mod high_cases {
             let received_verkey = did::key_for_did(-1, setup.wallet_handle, DID_V1).unwrap();
             assert_eq!(VERKEY, received_verkey);
         }
+
+        #[test]
+        fn indy_key_for_did_works_for_did_key_without_ledger() {
+            let setup = Setup::wallet();
+
+            let received_verkey = did::key_for_did(-1, setup.wallet_handle, DID_KEY).unwrap();
+            assert_eq!(DID_KEY_VERKEY, received_verkey);
+        }
     }
 
     mod key_for_local_did {
@@ -121,6 +131,14 @@ mod high_cases {
             let received_verkey = did::key_for_local_did(setup.wallet_handle, &setup.did).unwrap();
             assert_eq!(setup.verkey, received_verkey);
         }
+
+        #[test]
+        fn indy_key_for_local_did_works_for_unknown_did_key() {
+            let setup = Setup::wallet();
+
+            let received_verkey = did::key_for_local_did(setup.wallet_handle, DID_KEY).unwrap();
+            assert_eq!(DID_KEY_VERKEY, received_verkey);
+        }
     }
 
     mod set_endpoint_for_did {
@@ -457,6 +475,46 @@ mod high_cases {
             let res = did::create_my_did(setup.wallet_handle, &json!({"did": did}).to_string());
             assert_code!(ErrorCode::DidAlreadyExistsError, res);
         }
+
+        #[test]
+        fn indy_create_my_did_works_for_key_method() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "key", "seed": MY1_SEED}).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            assert!(my_did.starts_with("did:key:z6Mk"));
+            assert_eq!(VERKEY_MY1, my_verkey);
+            assert_eq!(my_verkey, did::key_for_local_did(setup.wallet_handle, &my_did).unwrap());
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_peer_method() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "peer", "seed": MY1_SEED}).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            assert!(my_did.starts_with("did:peer:0z6Mk"));
+            assert_eq!(my_verkey, did::key_for_local_did(setup.wallet_handle, &my_did).unwrap());
+        }
+
+        #[test]
+        fn indy_create_my_did_works_for_peer_method_with_service() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({
+                "method_name": "peer",
+                "service": {
+                    "serviceEndpoint": "https://example.com/endpoint",
+                    "accept": ["didcomm/v2"]
+                }
+            }).to_string();
+            let (my_did, my_verkey) = did::create_my_did(setup.wallet_handle, &my_did_json).unwrap();
+
+            assert!(my_did.starts_with("did:peer:2.Ez6LS"));
+            assert_eq!(my_verkey, did::key_for_did(-1, setup.wallet_handle, &my_did).unwrap());
+        }
     }
 
     mod replace_keys_start {
@@ -862,6 +920,15 @@ mod medium_cases {
             let res = did::create_my_did(INVALID_WALLET_HANDLE, "{}");
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
+
+        #[test]
+        fn indy_create_my_did_works_for_service_without_peer_method() {
+            let setup = Setup::wallet();
+
+            let my_did_json = json!({"method_name": "key", "service": {"serviceEndpoint": "https://example.com/endpoint"}}).to_string();
+            let res = did::create_my_did(setup.wallet_handle, &my_did_json);
+            assert_code!(ErrorCode::CommonInvalidParam3, res);
+        }
     }
 
     mod replace_keys_start {
@@ -875,6 +942,16 @@ mod medium_cases {
             assert_code!(ErrorCode::WalletInvalidHandle, res);
         }
 
+        #[test]
+        fn indy_replace_keys_start_works_for_did_key() {
+            let setup = Setup::wallet();
+
+            let (my_did, _) = did::create_my_did(setup.wallet_handle, &json!({"method_name": "key"}).to_string()).unwrap();
+
+            let res = did::replace_keys_start(setup.wallet_handle, &my_did, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         fn indy_replace_keys_start_works_for_seed() {
             let setup = Setup::did();
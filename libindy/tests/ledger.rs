@@ -19,7 +19,7 @@ use crate::utils::domain::ledger::request::DEFAULT_LIBIDY_DID;
 use crate::utils::Setup;
 use crate::utils::types::*;
 
-use self::indy::ErrorCode;
+use self::indy::{ErrorCode, SearchHandle};
 use self::rand::distributions::Alphanumeric;
 
 use crate::api::{PoolHandle, WalletHandle, INVALID_WALLET_HANDLE, INVALID_POOL_HANDLE};
@@ -916,6 +916,100 @@ mod high_cases {
         }
     }
 
+    mod download_ledger_txns {
+        use super::*;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_download_ledger_txns_works_for_pool_ledger() {
+            let setup = Setup::pool();
+
+            let result = ledger::download_ledger_txns(setup.pool_handle, Some("POOL"), 1, 4, None).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+            assert_eq!(json!({"from": 1, "to": 4}), result);
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_download_ledger_txns_works_for_domain_ledger_up_to_current_size() {
+            let setup = Setup::pool();
+
+            let result = ledger::download_ledger_txns(setup.pool_handle, None, 1, -1, None).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+            assert_eq!(1, result["from"].as_u64().unwrap());
+            assert!(result["to"].as_u64().unwrap() > 0);
+            assert!(result.get("txns").is_none());
+        }
+    }
+
+    mod ledger_txns_search {
+        use super::*;
+
+        fn _fetch_all(search_handle: SearchHandle) -> Vec<serde_json::Value> {
+            let mut txns = Vec::new();
+
+            loop {
+                let batch = ledger::fetch_ledger_txns_search_next_batch(search_handle).unwrap();
+                let batch: Vec<serde_json::Value> = serde_json::from_str(&batch).unwrap();
+
+                if batch.is_empty() {
+                    return txns;
+                }
+
+                assert!(batch.len() <= 100);
+                txns.extend(batch);
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_ledger_txns_search_works_for_pool_ledger() {
+            let setup = Setup::pool();
+
+            let search_handle = ledger::open_ledger_txns_search(setup.pool_handle, Some("POOL"), 1, 4).unwrap();
+
+            let txns = _fetch_all(search_handle);
+            assert_eq!(4, txns.len());
+            assert_eq!(constants::NODE, txns[0]["txn"]["type"].as_str().unwrap());
+            assert_eq!(1, txns[0]["txnMetadata"]["seqNo"].as_u64().unwrap());
+
+            ledger::close_ledger_txns_search(search_handle).unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_ledger_txns_search_works_for_domain_ledger_up_to_current_size() {
+            let setup = Setup::pool();
+
+            let result = ledger::download_ledger_txns(setup.pool_handle, None, 1, -1, None).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+            let to = result["to"].as_u64().unwrap();
+
+            // search is served from local replica
+            let search_handle = ledger::open_ledger_txns_search(setup.pool_handle, Some("DOMAIN"), 2, to as i32).unwrap();
+
+            let txns = _fetch_all(search_handle);
+            assert_eq!(to as usize - 1, txns.len());
+            assert_eq!(2, txns[0]["txnMetadata"]["seqNo"].as_u64().unwrap());
+            assert_eq!(to, txns[txns.len() - 1]["txnMetadata"]["seqNo"].as_u64().unwrap());
+
+            ledger::close_ledger_txns_search(search_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_ledger_txns_search_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = ledger::fetch_ledger_txns_search_next_batch(SearchHandle(0));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = ledger::close_ledger_txns_search(SearchHandle(0));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod pool_config {
         use super::*;
 
@@ -73,6 +73,31 @@ mod collect {
         assert!(threadpool_threads_count.contains(&json!({"tags":{"label":"panic"},"value":0})));
     }
 
+    #[test]
+    fn collect_metrics_contains_pool_nodes_statistics() {
+        let result_metrics = metrics::collect_metrics().unwrap();
+        let metrics_map = serde_json::from_str::<HashMap<String, Value>>(&result_metrics).unwrap();
+
+        assert!(metrics_map.contains_key("pool_nodes_status"));
+        assert!(metrics_map.get("pool_nodes_status").unwrap().is_array());
+    }
+
+    #[test]
+    fn collect_metrics_contains_ledger_cache_statistics() {
+        let result_metrics = metrics::collect_metrics().unwrap();
+        let metrics_map = serde_json::from_str::<HashMap<String, Value>>(&result_metrics).unwrap();
+
+        assert!(metrics_map.contains_key("ledger_cache"));
+
+        let ledger_cache = metrics_map
+            .get("ledger_cache")
+            .unwrap()
+            .as_array()
+            .unwrap();
+
+        assert!(ledger_cache.contains(&json!({"tags":{"label":"lru_size"},"value":0})));
+    }
+
     #[test]
     fn collect_metrics_includes_commands_count() {
         let setup = Setup::empty();
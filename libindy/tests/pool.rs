@@ -164,6 +164,62 @@ mod high_cases {
         }
     }
 
+    mod get_status {
+        use super::*;
+        use crate::utils::ledger;
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn indy_get_pool_status_works() {
+            let setup = Setup::pool();
+
+            let get_nym_request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            ledger::submit_request(setup.pool_handle, &get_nym_request).unwrap();
+
+            let status = pool::get_status(setup.pool_handle).unwrap();
+            let status: serde_json::Value = serde_json::from_str(&status).unwrap();
+
+            let nodes = status["nodes"].as_object().unwrap();
+            assert!(!nodes.is_empty());
+            assert!(nodes.values().any(|node| node["replies"].as_u64().unwrap() > 0 && node["lastReplyTime"].is_u64()));
+        }
+
+        #[test]
+        fn indy_get_pool_status_works_for_offline_pool() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let status = pool::get_status(pool_handle).unwrap();
+            let status: serde_json::Value = serde_json::from_str(&status).unwrap();
+            assert_eq!(json!({"nodes": {}}), status);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
+    mod resolve {
+        use super::*;
+        use crate::utils::ledger;
+
+        #[test]
+        fn indy_resolve_works_for_did() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger_with_namespace(&setup.name, &offline_snapshot(), Some("test:offline")).unwrap();
+
+            let id = format!("did:indy:test:offline:{}", DID_MY1);
+            let (resolved_id, nym_data) = ledger::resolve(&id).unwrap();
+            assert_eq!(id, resolved_id);
+
+            let nym_data: serde_json::Value = serde_json::from_str(&nym_data).unwrap();
+            assert_eq!(DID_MY1, nym_data["did"].as_str().unwrap());
+            assert_eq!(VERKEY_MY1, nym_data["verkey"].as_str().unwrap());
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
+
     mod set_protocol_version {
         use super::*;
 
@@ -173,6 +229,135 @@ mod high_cases {
             pool::set_protocol_version(2).unwrap();
         }
     }
+
+    mod offline {
+        use super::*;
+        use crate::utils::ledger;
+
+        #[test]
+        fn open_pool_ledger_works_for_offline_mode() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            pool::refresh(pool_handle).unwrap();
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn submit_request_works_for_offline_pool_read() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let get_nym_request = ledger::build_get_nym_request(None, DID_MY1).unwrap();
+            let get_nym_response = ledger::submit_request(pool_handle, &get_nym_request).unwrap();
+
+            let nym_data = ledger::parse_get_nym_response(&get_nym_response).unwrap();
+            let nym_data: serde_json::Value = serde_json::from_str(&nym_data).unwrap();
+            assert_eq!(DID_MY1, nym_data["did"].as_str().unwrap());
+            assert_eq!(VERKEY_MY1, nym_data["verkey"].as_str().unwrap());
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn submit_request_works_for_offline_pool_write() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let nym_request = ledger::build_nym_request(DID_TRUSTEE, DID_MY2, Some(VERKEY_MY2), None, None).unwrap();
+            let res = ledger::submit_request(pool_handle, &nym_request);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn create_pool_snapshot_works() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_pool_ledger(&setup.name).unwrap();
+
+            let info = ledger::create_pool_snapshot(pool_handle).unwrap();
+            let info: serde_json::Value = serde_json::from_str(&info).unwrap();
+            assert!(info["ledgerSize"].as_u64().unwrap() > 0);
+            assert!(info["txnRootHash"].is_string());
+
+            pool::close(pool_handle).unwrap();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(r#"{"offline": true}"#)).unwrap();
+
+            let get_nym_request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let get_nym_response = ledger::submit_request(pool_handle, &get_nym_request).unwrap();
+
+            let nym_data = ledger::parse_get_nym_response(&get_nym_response).unwrap();
+            let nym_data: serde_json::Value = serde_json::from_str(&nym_data).unwrap();
+            assert_eq!(DID_TRUSTEE, nym_data["did"].as_str().unwrap());
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn download_ledger_txns_works_for_offline_pool() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let result = ledger::download_ledger_txns(pool_handle, None, 1, -1, None).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+            assert_eq!(json!({"from": 1, "to": 1}), result);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn ledger_txns_search_works_for_offline_pool() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let search_handle = ledger::open_ledger_txns_search(pool_handle, None, 1, -1).unwrap();
+
+            let txns = ledger::fetch_ledger_txns_search_next_batch(search_handle).unwrap();
+            let txns: serde_json::Value = serde_json::from_str(&txns).unwrap();
+            assert_eq!(offline_snapshot()["txns"], txns);
+
+            let txns = ledger::fetch_ledger_txns_search_next_batch(search_handle).unwrap();
+            let txns: serde_json::Value = serde_json::from_str(&txns).unwrap();
+            assert_eq!(json!([]), txns);
+
+            ledger::close_ledger_txns_search(search_handle).unwrap();
+
+            let res = ledger::fetch_ledger_txns_search_next_batch(search_handle);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn download_ledger_txns_works_for_offline_pool_and_file() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let path = environment::tmp_file_path(&format!("{}_txns.jsonl", setup.name));
+            let _ = std::fs::remove_file(&path);
+            let options = json!({"file": path.to_str().unwrap()}).to_string();
+
+            let result = ledger::download_ledger_txns(pool_handle, Some("DOMAIN"), 1, 1, Some(&options)).unwrap();
+            let result: serde_json::Value = serde_json::from_str(&result).unwrap();
+            assert_eq!(json!({"from": 1, "to": 1, "file": path.to_str().unwrap()}), result);
+
+            let content = std::fs::read_to_string(&path).unwrap();
+            let txns: Vec<serde_json::Value> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+            assert_eq!(offline_snapshot()["txns"].as_array().unwrap(), &txns);
+
+            pool::close(pool_handle).unwrap();
+        }
+    }
 }
 
 #[cfg(not(feature = "only_high_cases"))]
@@ -331,6 +516,24 @@ mod medium_cases {
             assert_code!(ErrorCode::CommonInvalidStructure, res);
         }
 
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn open_pool_ledger_works_for_invalid_transport_config() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let config = r#"{"transport": {"type": "http", "gateways": {"Node1": "tcp://127.0.0.1:9702"}}}"#;
+            let res = pool::open_pool_ledger(&setup.name, Some(config));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let config = r#"{"transport": {"type": "ws", "gateways": {"Node1": "wss://gateway.example.com/Node1"}, "proxy": "http://proxy.example.com:3128"}}"#;
+            let res = pool::open_pool_ledger(&setup.name, Some(config));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
         #[test]
         #[cfg(feature = "local_nodes_pool")]
         fn open_pool_ledger_works_for_incompatible_protocol_version() {
@@ -380,6 +583,25 @@ mod medium_cases {
 
             pool::close(pool_handle).unwrap();
         }
+
+        #[test]
+        #[cfg(feature = "local_nodes_pool")]
+        fn open_pool_ledger_works_for_config_verify_write_replies() {
+            let setup = Setup::empty();
+
+            let config = json!({"verify_write_replies": true}).to_string();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let pool_handle = pool::open_pool_ledger(&setup.name, Some(&config)).unwrap();
+
+            let request = ledger::build_get_nym_request(None, DID_TRUSTEE).unwrap();
+            let _ = ledger::submit_request(pool_handle, &request).unwrap();
+
+            pool::close(pool_handle).unwrap();
+        }
     }
 
     mod close {
@@ -477,6 +699,79 @@ mod medium_cases {
         }
     }
 
+    mod get_status {
+        use super::*;
+
+        #[test]
+        fn indy_get_pool_status_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = pool::get_status(0);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
+    mod resolve {
+        use super::*;
+        use crate::utils::ledger;
+
+        #[test]
+        fn indy_create_pool_ledger_config_works_for_invalid_namespace() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json_with_namespace(txn_file_path.as_path(), Some("Sovrin::staging"));
+
+            let res = pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str()));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_open_pool_ledger_works_for_opened_namespace() {
+            let setup = Setup::empty();
+            let other_name = format!("{}_other", setup.name);
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger_with_namespace(&setup.name, &offline_snapshot(), Some("test:busy")).unwrap();
+
+            let res = pool::create_and_open_offline_pool_ledger_with_namespace(&other_name, &offline_snapshot(), Some("test:busy"));
+            assert_code!(ErrorCode::CommonInvalidState, res);
+
+            pool::close(pool_handle).unwrap();
+            pool::delete(&other_name).unwrap();
+        }
+
+        #[test]
+        fn indy_resolve_works_for_unknown_namespace() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger_with_namespace(&setup.name, &offline_snapshot(), Some("test:known")).unwrap();
+
+            let res = ledger::resolve(&format!("did:indy:test:unknown:{}", DID_MY1));
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_resolve_works_for_closed_pool() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger_with_namespace(&setup.name, &offline_snapshot(), Some("test:closed")).unwrap();
+            pool::close(pool_handle).unwrap();
+
+            let res = ledger::resolve(&format!("did:indy:test:closed:{}", DID_MY1));
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+
+        #[test]
+        fn indy_resolve_works_for_unsupported_id() {
+            Setup::empty();
+
+            let res = ledger::resolve(&format!("did:sov:{}", DID_MY1));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod set_protocol_version {
         use super::*;
 
@@ -486,4 +781,201 @@ mod medium_cases {
             assert_code!(ErrorCode::PoolIncompatibleProtocolVersion, res);
         }
     }
+
+    mod offline {
+        use super::*;
+
+        #[test]
+        fn download_ledger_txns_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = ledger::download_ledger_txns(0, None, 1, -1, None);
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+
+        #[test]
+        fn download_ledger_txns_works_for_invalid_range() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let res = ledger::download_ledger_txns(pool_handle, None, 0, 1, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = ledger::download_ledger_txns(pool_handle, None, 2, 1, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn download_ledger_txns_works_for_range_out_of_ledger() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let res = ledger::download_ledger_txns(pool_handle, None, 1, 5, None);
+            assert_code!(ErrorCode::LedgerNotFound, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn download_ledger_txns_works_for_invalid_ledger_type() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let res = ledger::download_ledger_txns(pool_handle, Some("UNKNOWN"), 1, -1, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = ledger::download_ledger_txns(pool_handle, Some("256"), 1, -1, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn open_pool_ledger_works_for_offline_mode_without_snapshot() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let res = pool::open_pool_ledger(&setup.name, Some(r#"{"offline": true}"#));
+            assert_code!(ErrorCode::CommonInvalidState, res);
+        }
+
+        #[test]
+        fn import_pool_snapshot_works_for_not_created_pool() {
+            let setup = Setup::empty();
+
+            let snapshot_path = pool::create_snapshot_file(&setup.name, &offline_snapshot());
+
+            let res = pool::import_pool_snapshot(&setup.name, snapshot_path.as_path(), None);
+            assert_code!(ErrorCode::PoolLedgerNotCreatedError, res);
+        }
+
+        #[test]
+        fn import_pool_snapshot_works_for_unsigned_snapshot() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let snapshot_path = pool::create_snapshot_file(&setup.name, &offline_snapshot());
+
+            let res = pool::import_pool_snapshot(&setup.name, snapshot_path.as_path(), None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn import_pool_snapshot_works_for_root_hash_mismatch() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let mut snapshot = offline_snapshot();
+            snapshot["multiSignature"] = json!({
+                "signature": "",
+                "participants": [],
+                "value": {"ledger_id": 1, "txn_root_hash": "11111111111111111111111111111111"}
+            });
+            let snapshot_path = pool::create_snapshot_file(&setup.name, &snapshot);
+
+            let res = pool::import_pool_snapshot(&setup.name, snapshot_path.as_path(), None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn import_pool_snapshot_works_for_signature_by_unknown_nodes() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool_with_bls_keys(&setup.name, &pool::gen_bls_sign_keys(), None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let snapshot = pool::sign_snapshot(&offline_snapshot(), &pool::gen_bls_sign_keys());
+            let snapshot_path = pool::create_snapshot_file(&setup.name, &snapshot);
+
+            let res = pool::import_pool_snapshot(&setup.name, snapshot_path.as_path(), None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn import_pool_snapshot_works_for_disabled_signature_verification() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let snapshot_path = pool::create_snapshot_file(&setup.name, &offline_snapshot());
+
+            let res = pool::import_pool_snapshot(&setup.name, snapshot_path.as_path(), Some(r#"{"verify_signature": false}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn open_pool_ledger_works_for_offline_mode_with_replaced_snapshot() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+            pool::close(pool_handle).unwrap();
+
+            let mut snapshot_path = environment::pool_path(&setup.name);
+            snapshot_path.push("snapshot.json");
+            std::fs::write(&snapshot_path, offline_snapshot().to_string()).unwrap();
+
+            let res = pool::open_pool_ledger(&setup.name, Some(r#"{"offline": true}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn create_pool_snapshot_works_for_offline_pool() {
+            let setup = Setup::empty();
+
+            let pool_handle = pool::create_and_open_offline_pool_ledger(&setup.name, &offline_snapshot()).unwrap();
+
+            let res = ledger::create_pool_snapshot(pool_handle);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+
+            pool::close(pool_handle).unwrap();
+        }
+
+        #[test]
+        fn open_pool_ledger_works_for_offline_mode_with_gateway_transport() {
+            let setup = Setup::empty();
+
+            let txn_file_path = pool::create_genesis_txn_file_for_test_pool(&setup.name, None, None);
+            let pool_config = pool::pool_config_json(txn_file_path.as_path());
+            pool::create_pool_ledger_config(&setup.name, Some(pool_config.as_str())).unwrap();
+
+            let config = r#"{"offline": true, "transport": {"type": "http", "gateways": {"Node1": "https://gateway.example.com/Node1"}}}"#;
+            let res = pool::open_pool_ledger(&setup.name, Some(config));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+}
+
+fn offline_snapshot() -> serde_json::Value {
+    json!({
+        "ledgerId": 1,
+        "txns": [
+            {
+                "txn": {
+                    "type": "1",
+                    "data": {"dest": DID_MY1, "verkey": VERKEY_MY1},
+                    "metadata": {"from": DID_TRUSTEE, "reqId": 1}
+                },
+                "txnMetadata": {"seqNo": 1, "txnTime": 1600000000},
+                "reqSignature": {},
+                "ver": "1"
+            }
+        ]
+    })
 }
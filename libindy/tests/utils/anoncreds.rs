@@ -84,6 +84,28 @@ pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
 
+pub fn issuer_create_revoc_reg_manager(wallet_handle: WalletHandle, issuer_did: &str, cred_def_id: &str, config_json: Option<&str>,
+                                       tails_writer_handle: i32) -> Result<(String, String, String), IndyError> {
+    anoncreds::issuer_create_revoc_reg_manager(wallet_handle, issuer_did, cred_def_id, config_json, tails_writer_handle).wait()
+}
+
+pub fn issuer_create_credential_with_revoc_reg_manager(wallet_handle: WalletHandle, cred_offer_json: &str, cred_req_json: &str, cred_values_json: &str,
+                                                       tails_writer_handle: i32, blob_storage_reader_handle: i32) -> Result<(String, Option<String>, Option<String>), IndyError> {
+    anoncreds::issuer_create_credential_with_revoc_reg_manager(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, tails_writer_handle, blob_storage_reader_handle).wait()
+}
+
+pub fn issuer_get_revoc_reg_manager_state(wallet_handle: WalletHandle, cred_def_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_revoc_reg_manager_state(wallet_handle, cred_def_id).wait()
+}
+
+pub fn issuer_mark_revoc_reg_published(wallet_handle: WalletHandle, rev_reg_id: &str) -> Result<(), IndyError> {
+    anoncreds::issuer_mark_revoc_reg_published(wallet_handle, rev_reg_id).wait()
+}
+
+pub fn issuer_get_issued_credentials(wallet_handle: WalletHandle, query_json: Option<&str>) -> Result<String, IndyError> {
+    anoncreds::issuer_get_issued_credentials(wallet_handle, query_json).wait()
+}
+
 pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta: &str, other_rev_reg_delta: &str) -> Result<String, IndyError> {
     anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta, other_rev_reg_delta).wait()
 }
@@ -141,6 +163,10 @@ pub fn prover_get_credentials_for_proof_req(wallet_handle: WalletHandle, proof_r
     anoncreds::prover_get_credentials_for_proof_req(wallet_handle, proof_request_json).wait()
 }
 
+pub fn prover_select_credentials_for_proof_req(wallet_handle: WalletHandle, proof_request_json: &str, policy_json: Option<&str>) -> Result<(String, String), IndyError> {
+    anoncreds::prover_select_credentials_for_proof_req(wallet_handle, proof_request_json, policy_json).wait()
+}
+
 pub fn prover_search_credentials_for_proof_req(wallet_handle: WalletHandle, proof_request_json: &str, extra_query_json: Option<&str>) -> Result<i32, IndyError> {
     anoncreds::prover_search_credentials_for_proof_req(wallet_handle, proof_request_json, extra_query_json).wait()
 }
@@ -165,6 +191,11 @@ pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                         cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_verify_proof_with_report(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -183,6 +214,10 @@ pub fn to_unqualified(entity: &str) -> Result<String, IndyError> {
     anoncreds::to_unqualified(entity).wait()
 }
 
+pub fn encode_credential_values(cred_values_json: &str) -> Result<String, IndyError> {
+    anoncreds::encode_credential_values(cred_values_json).wait()
+}
+
 pub fn default_cred_def_config() -> String {
     serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false }).unwrap()
 }
@@ -21,4 +21,37 @@ pub fn purge_schema_cache(wallet_handle: WalletHandle, options_json: &str) -> Re
 
 pub fn purge_cred_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
     cache::purge_cred_def_cache(wallet_handle, options_json).wait()
-}
\ No newline at end of file
+}
+pub fn get_nym_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, target_did: &str, options_json: &str) -> Result<String, IndyError> {
+    cache::get_nym(pool_handle, wallet_handle, submitter_did, target_did, options_json).wait()
+}
+
+pub fn get_attrib_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, target_did: &str,
+                        raw: Option<&str>, hash: Option<&str>, enc: Option<&str>, options_json: &str) -> Result<String, IndyError> {
+    cache::get_attrib(pool_handle, wallet_handle, submitter_did, target_did, raw, hash, enc, options_json).wait()
+}
+
+pub fn get_revoc_reg_def_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, id: &str, options_json: &str) -> Result<String, IndyError> {
+    cache::get_revoc_reg_def(pool_handle, wallet_handle, submitter_did, id, options_json).wait()
+}
+
+pub fn get_revoc_reg_delta_cache(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, revoc_reg_def_id: &str,
+                                 from: Option<i64>, to: i64, options_json: &str) -> Result<(String, u64), IndyError> {
+    cache::get_revoc_reg_delta(pool_handle, wallet_handle, submitter_did, revoc_reg_def_id, from, to, options_json).wait()
+}
+
+pub fn purge_nym_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_nym_cache(wallet_handle, options_json).wait()
+}
+
+pub fn purge_attrib_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_attrib_cache(wallet_handle, options_json).wait()
+}
+
+pub fn purge_revoc_reg_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_revoc_reg_def_cache(wallet_handle, options_json).wait()
+}
+
+pub fn purge_revoc_reg_delta_cache(wallet_handle: WalletHandle, options_json: &str) -> Result<(), IndyError> {
+    cache::purge_revoc_reg_delta_cache(wallet_handle, options_json).wait()
+}
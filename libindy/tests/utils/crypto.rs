@@ -1,16 +1,34 @@
 extern crate futures;
 
-use indy::IndyError;
+use indy::{ErrorCode, IndyError};
 use indy::crypto;
 use self::futures::Future;
 
-use indy::WalletHandle;
+use indy::{WalletHandle, CommandHandle};
+
+use crate::utils::callback;
+use crate::utils::file_key_manager::FileKeyManager;
+
+use std::collections::HashSet;
+use std::ffi::CString;
+use std::sync::Mutex;
+use super::libc::c_char;
 
 pub fn create_key(wallet_handle: WalletHandle, seed: Option<&str>) -> Result<String, IndyError> {
     let key_json = json!({"seed": seed}).to_string();
     crypto::create_key(wallet_handle, Some(&key_json)).wait()
 }
 
+pub fn create_key_with_key_manager(wallet_handle: WalletHandle, seed: Option<&str>, key_manager: &str) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed, "key_manager": key_manager}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
+pub fn create_key_with_crypto_type(wallet_handle: WalletHandle, seed: Option<&str>, crypto_type: &str) -> Result<String, IndyError> {
+    let key_json = json!({"seed": seed, "crypto_type": crypto_type}).to_string();
+    crypto::create_key(wallet_handle, Some(&key_json)).wait()
+}
+
 pub fn set_key_metadata(wallet_handle: WalletHandle, verkey: &str, metadata: &str) -> Result<(), IndyError> {
     crypto::set_key_metadata(wallet_handle, verkey, metadata).wait()
 }
@@ -49,4 +67,103 @@ pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys:
 
 pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> Result<Vec<u8>, IndyError> {
     crypto::unpack_message(wallet_handle, jwe).wait()
-}
\ No newline at end of file
+}
+
+pub fn pack_message_v2(wallet_handle: WalletHandle, message: &[u8], receiver_kids: &str, sender_kid: Option<&str>, options_json: Option<&str>) -> Result<Vec<u8>, IndyError> {
+    crypto::pack_message_v2(wallet_handle, message, receiver_kids, sender_kid, options_json).wait()
+}
+
+pub fn unpack_message_v2(wallet_handle: WalletHandle, jwe: &[u8]) -> Result<Vec<u8>, IndyError> {
+    crypto::unpack_message_v2(wallet_handle, jwe).wait()
+}
+pub fn register_key_manager(name: &str, force_create: bool) -> Result<(), ErrorCode> {
+    lazy_static! {
+        static ref REGISTERED_KEY_MANAGERS: Mutex<HashSet<String>> = Default::default();
+    }
+
+    let mut key_managers = REGISTERED_KEY_MANAGERS.lock().unwrap();
+
+    if key_managers.contains(name) & !force_create {
+        return Ok(());
+    }
+
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let name_ = CString::new(name).unwrap();
+
+    let err = unsafe {
+        indy_register_key_manager(
+            command_handle,
+            name_.as_ptr(),
+            Some(FileKeyManager::create_key),
+            Some(FileKeyManager::sign),
+            Some(FileKeyManager::crypto_box),
+            Some(FileKeyManager::crypto_box_open),
+            Some(FileKeyManager::crypto_box_seal_open),
+            Some(FileKeyManager::key_agreement),
+            Some(FileKeyManager::free),
+            cb
+        )
+    };
+
+    key_managers.insert(name.to_string());
+
+    super::results::result_to_empty(err as i32, receiver)
+}
+
+extern {
+    #[no_mangle]
+    pub fn indy_register_key_manager(command_handle: CommandHandle,
+                                     name: *const c_char,
+                                     create_key: Option<KeyManagerCreateKey>,
+                                     sign: Option<KeyManagerSign>,
+                                     crypto_box: Option<KeyManagerCryptoBox>,
+                                     crypto_box_open: Option<KeyManagerCryptoBoxOpen>,
+                                     crypto_box_seal_open: Option<KeyManagerCryptoBoxSealOpen>,
+                                     key_agreement: Option<KeyManagerKeyAgreement>,
+                                     free: Option<KeyManagerFree>,
+                                     cb: Option<ResponseEmptyCB>) -> ErrorCode;
+}
+
+pub type KeyManagerCreateKey = extern fn(crypto_type: *const c_char,
+                                         seed: *const u8,
+                                         seed_len: usize,
+                                         verkey_p: *mut *const u8,
+                                         verkey_len_p: *mut usize,
+                                         key_ref_p: *mut *const c_char) -> ErrorCode;
+pub type KeyManagerSign = extern fn(key_ref: *const c_char,
+                                    message: *const u8,
+                                    message_len: usize,
+                                    signature_p: *mut *const u8,
+                                    signature_len_p: *mut usize) -> ErrorCode;
+pub type KeyManagerCryptoBox = extern fn(key_ref: *const c_char,
+                                         their_vk: *const u8,
+                                         their_vk_len: usize,
+                                         message: *const u8,
+                                         message_len: usize,
+                                         nonce: *const u8,
+                                         nonce_len: usize,
+                                         encrypted_p: *mut *const u8,
+                                         encrypted_len_p: *mut usize) -> ErrorCode;
+pub type KeyManagerCryptoBoxOpen = extern fn(key_ref: *const c_char,
+                                             their_vk: *const u8,
+                                             their_vk_len: usize,
+                                             encrypted: *const u8,
+                                             encrypted_len: usize,
+                                             nonce: *const u8,
+                                             nonce_len: usize,
+                                             decrypted_p: *mut *const u8,
+                                             decrypted_len_p: *mut usize) -> ErrorCode;
+pub type KeyManagerCryptoBoxSealOpen = extern fn(key_ref: *const c_char,
+                                                 encrypted: *const u8,
+                                                 encrypted_len: usize,
+                                                 decrypted_p: *mut *const u8,
+                                                 decrypted_len_p: *mut usize) -> ErrorCode;
+pub type KeyManagerKeyAgreement = extern fn(key_ref: *const c_char,
+                                            their_pk: *const u8,
+                                            their_pk_len: usize,
+                                            shared_secret_p: *mut *const u8,
+                                            shared_secret_len_p: *mut usize) -> ErrorCode;
+pub type KeyManagerFree = extern fn(buffer: *const u8) -> ErrorCode;
+
+pub type ResponseEmptyCB = extern fn(xcommand_handle: CommandHandle, err: i32);
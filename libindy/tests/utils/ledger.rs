@@ -14,7 +14,7 @@ use std::sync::{Once};
 use std::mem;
 use std::ffi::CString;
 
-use indy::{WalletHandle, PoolHandle};
+use indy::{WalletHandle, PoolHandle, SearchHandle};
 
 pub static mut SCHEMA_ID: &'static str = "";
 pub static mut SCHEMA_ID_V2: &'static str = "";
@@ -182,6 +182,30 @@ pub fn parse_get_cred_def_response(get_cred_def_response: &str) -> Result<(Strin
     ledger::parse_get_cred_def_response(get_cred_def_response).wait()
 }
 
+pub fn resolve(id: &str) -> Result<(String, String), IndyError> {
+    ledger::resolve(id).wait()
+}
+
+pub fn download_ledger_txns(pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32, options_json: Option<&str>) -> Result<String, IndyError> {
+    ledger::download_ledger_txns(pool_handle, ledger_type, from, to, options_json).wait()
+}
+
+pub fn open_ledger_txns_search(pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32) -> Result<SearchHandle, IndyError> {
+    ledger::open_ledger_txns_search(pool_handle, ledger_type, from, to).wait()
+}
+
+pub fn fetch_ledger_txns_search_next_batch(search_handle: SearchHandle) -> Result<String, IndyError> {
+    ledger::fetch_ledger_txns_search_next_batch(search_handle).wait()
+}
+
+pub fn close_ledger_txns_search(search_handle: SearchHandle) -> Result<(), IndyError> {
+    ledger::close_ledger_txns_search(search_handle).wait()
+}
+
+pub fn create_pool_snapshot(pool_handle: PoolHandle) -> Result<String, IndyError> {
+    ledger::create_pool_snapshot(pool_handle).wait()
+}
+
 pub fn parse_get_revoc_reg_def_response(get_revoc_reg_def_response: &str) -> Result<(String, String), IndyError> {
     ledger::parse_get_revoc_reg_def_response(get_revoc_reg_def_response).wait()
 }
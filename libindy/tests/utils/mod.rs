@@ -52,6 +52,9 @@ pub(crate) use indy::ErrorCode;
 #[path = "../../indy-utils/src/inmem_wallet.rs"]
 pub mod inmem_wallet;
 
+#[path = "../../indy-utils/src/file_key_manager.rs"]
+pub mod file_key_manager;
+
 #[path = "../../indy-utils/src/wql.rs"]
 pub mod wql;
 
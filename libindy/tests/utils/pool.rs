@@ -1,8 +1,14 @@
 extern crate byteorder;
 extern crate futures;
+extern crate openssl;
 extern crate rmp_serde;
+extern crate rust_base58;
+extern crate ursa;
 
 use self::byteorder::{LittleEndian, WriteBytesExt};
+use self::openssl::sha::Sha256;
+use self::rust_base58::{FromBase58, ToBase58};
+use self::ursa::bls::{Bls, Generator, MultiSignature, SignKey, VerKey};
 
 use std::fs;
 use std::io::Write;
@@ -17,9 +23,13 @@ use crate::utils::types::{Response, ResponseType};
 use crate::utils::{environment, test};
 use crate::api::PoolHandle;
 
+const DEFAULT_GENERATOR: &str = "3LHpUjiyFC2q2hD7MnwwNmVXiuaFbQx2XkAFJWzswCjgN1utjsCeLzHsKk1nJvFEaS4fcrUmVAkdhtPCYbrVyATZcmzwJReTcJqwqBCPTmTQ9uWPwz6rEncKb2pYYYFcdHa8N17HzVyTqKfgPi4X9pMetfT3A5xCHq54R2pDNYWVLDX";
+
 #[derive(Serialize, Deserialize)]
 struct PoolConfig {
-    pub genesis_txn: String
+    pub genesis_txn: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
 pub fn create_genesis_txn_file(pool_name: &str,
@@ -56,6 +66,28 @@ pub fn create_genesis_txn_file_for_test_pool(pool_name: &str,
     create_genesis_txn_file(pool_name, txn_file_data.as_str(), txn_file_path)
 }
 
+/// Generates BLS keys for test pool nodes, so ledger snapshots can be signed in tests.
+pub fn gen_bls_sign_keys() -> Vec<SignKey> {
+    test::gen_txns().iter().map(|_| SignKey::new(None).unwrap()).collect()
+}
+
+pub fn create_genesis_txn_file_for_test_pool_with_bls_keys(pool_name: &str,
+                                                           sign_keys: &[SignKey],
+                                                           txn_file_path: Option<&Path>) -> PathBuf {
+    let gen = Generator::from_bytes(&DEFAULT_GENERATOR.from_base58().unwrap()).unwrap();
+
+    let node_txns = test::gen_txns().iter().zip(sign_keys)
+        .map(|(txn, sign_key)| {
+            let mut txn: serde_json::Value = serde_json::from_str(txn).unwrap();
+            txn["txn"]["data"]["data"]["blskey"] = json!(VerKey::new(&gen, sign_key).unwrap().as_bytes().to_base58());
+            txn.to_string()
+        })
+        .collect::<Vec<String>>();
+
+    let txn_file_data = node_txns.join("\n");
+    create_genesis_txn_file(pool_name, txn_file_data.as_str(), txn_file_path)
+}
+
 pub fn create_genesis_txn_file_for_test_pool_with_invalid_nodes(pool_name: &str,
                                                                 txn_file_path: Option<&Path>) -> PathBuf {
     let test_pool_ip = environment::test_pool_ip();
@@ -103,8 +135,13 @@ pub fn create_genesis_txn_file_for_test_pool_with_wrong_ips(pool_name: &str,
 
 // Note that to be config valid it assumes genesis txt file is already exists
 pub fn pool_config_json(txn_file_path: &Path) -> String {
+    pool_config_json_with_namespace(txn_file_path, None)
+}
+
+pub fn pool_config_json_with_namespace(txn_file_path: &Path, namespace: Option<&str>) -> String {
     let config = PoolConfig {
-        genesis_txn: txn_file_path.to_string_lossy().to_string()
+        genesis_txn: txn_file_path.to_string_lossy().to_string(),
+        namespace: namespace.map(String::from),
     };
 
     serde_json::to_string(&config).unwrap()
@@ -114,7 +151,6 @@ pub fn create_pool_ledger_config(pool_name: &str, pool_config: Option<&str>) ->
     pool::create_pool_ledger_config(pool_name, pool_config).wait()
 }
 
-#[cfg(feature = "local_nodes_pool")]
 pub fn open_pool_ledger(pool_name: &str, config: Option<&str>) -> Result<PoolHandle, IndyError> {
     pool::open_pool_ledger(pool_name, config).wait()
 }
@@ -174,6 +210,10 @@ pub fn refresh(pool_handle: PoolHandle) -> Result<(), IndyError> {
     pool::refresh_pool_ledger(pool_handle).wait()
 }
 
+pub fn get_status(pool_handle: PoolHandle) -> Result<String, IndyError> {
+    pool::get_pool_status(pool_handle).wait()
+}
+
 pub fn close(pool_handle: PoolHandle) -> Result<(), IndyError> {
     pool::close_pool_ledger(pool_handle).wait()
 }
@@ -182,6 +222,93 @@ pub fn delete(pool_name: &str) -> Result<(), IndyError> {
     pool::delete_pool_ledger(pool_name).wait()
 }
 
+pub fn import_pool_snapshot(pool_name: &str, snapshot_path: &Path, options: Option<&str>) -> Result<(), IndyError> {
+    pool::import_pool_snapshot(pool_name, snapshot_path.to_str().unwrap(), options).wait()
+}
+
+pub fn create_snapshot_file(name: &str, snapshot: &serde_json::Value) -> PathBuf {
+    let path = environment::tmp_file_path(&format!("{}_snapshot.json", name));
+
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+
+    let mut f = fs::File::create(&path).unwrap();
+    f.write_all(snapshot.to_string().as_bytes()).unwrap();
+    f.flush().unwrap();
+
+    path
+}
+
+/// Adds multi-signature of the txn root hash made by all nodes but the last one.
+pub fn sign_snapshot(snapshot: &serde_json::Value, sign_keys: &[SignKey]) -> serde_json::Value {
+    let leaves = snapshot["txns"].as_array().unwrap().iter()
+        .map(|txn| _hash(&[&[0x00], &rmp_serde::to_vec_named(txn).unwrap()]))
+        .collect::<Vec<Vec<u8>>>();
+
+    let value = json!({
+        "ledger_id": snapshot["ledgerId"],
+        "txn_root_hash": _merkle_root(&leaves).to_base58(),
+        "state_root_hash": "",
+        "pool_state_root_hash": "",
+        "timestamp": 1600000000
+    });
+    let message = rmp_serde::to_vec_named(&value).unwrap();
+
+    let signers = &sign_keys[..sign_keys.len() - 1];
+    let signatures = signers.iter().map(|sign_key| Bls::sign(&message, sign_key).unwrap()).collect::<Vec<_>>();
+    let signature = MultiSignature::new(signatures.iter().collect::<Vec<_>>().as_slice()).unwrap();
+
+    let participants = test::gen_txns().iter().take(signers.len())
+        .map(|txn| serde_json::from_str::<serde_json::Value>(txn).unwrap()["txn"]["data"]["data"]["alias"].clone())
+        .collect::<Vec<serde_json::Value>>();
+
+    let mut snapshot = snapshot.clone();
+    snapshot["multiSignature"] = json!({
+        "signature": signature.as_bytes().to_base58(),
+        "participants": participants,
+        "value": value
+    });
+    snapshot
+}
+
+fn _hash(parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finish().to_vec()
+}
+
+// RFC 6962 merkle tree hash of the leaf hashes
+fn _merkle_root(leaves: &[Vec<u8>]) -> Vec<u8> {
+    if leaves.len() == 1 {
+        return leaves[0].clone();
+    }
+
+    let mut split = 1;
+    while split * 2 < leaves.len() {
+        split *= 2;
+    }
+
+    _hash(&[&[0x01], &_merkle_root(&leaves[..split]), &_merkle_root(&leaves[split..])])
+}
+
+pub fn create_and_open_offline_pool_ledger(pool_name: &str, snapshot: &serde_json::Value) -> Result<PoolHandle, IndyError> {
+    create_and_open_offline_pool_ledger_with_namespace(pool_name, snapshot, None)
+}
+
+pub fn create_and_open_offline_pool_ledger_with_namespace(pool_name: &str, snapshot: &serde_json::Value, namespace: Option<&str>) -> Result<PoolHandle, IndyError> {
+    let sign_keys = gen_bls_sign_keys();
+
+    let txn_file_path = create_genesis_txn_file_for_test_pool_with_bls_keys(pool_name, &sign_keys, None);
+    let pool_config = pool_config_json_with_namespace(txn_file_path.as_path(), namespace);
+    create_pool_ledger_config(pool_name, Some(pool_config.as_str()))?;
+
+    let snapshot_path = create_snapshot_file(pool_name, &sign_snapshot(snapshot, &sign_keys));
+    import_pool_snapshot(pool_name, snapshot_path.as_path(), None)?;
+
+    open_pool_ledger(pool_name, Some(r#"{"offline": true}"#))
+}
+
 pub fn set_protocol_version(protocol_version: usize) -> Result<(), IndyError> {
     pool::set_protocol_version(protocol_version).wait()
 }
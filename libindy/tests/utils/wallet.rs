@@ -18,7 +18,7 @@ use super::libc::c_char;
 use crate::utils::constants::{TYPE, INMEM_TYPE, WALLET_CREDENTIALS};
 
 use std::path::{Path, PathBuf};
-use indy::{WalletHandle, CommandHandle};
+use indy::{WalletHandle, CommandHandle, IndyHandle};
 
 pub fn register_wallet_storage(xtype: &str, force_create: bool) -> Result<(), ErrorCode> {
     lazy_static! {
@@ -108,10 +108,26 @@ pub fn delete_wallet(config: &str, credentials: &str) -> Result<(), IndyError> {
     wallet::delete_wallet(config, credentials).wait()
 }
 
+pub fn list_wallets(storage_type: Option<&str>, storage_config: Option<&str>, storage_credentials: Option<&str>) -> Result<String, IndyError> {
+    wallet::list_wallets(storage_type, storage_config, storage_credentials).wait()
+}
+
 pub fn close_wallet(wallet_handle: WalletHandle) -> Result<(), IndyError> {
     wallet::close_wallet(wallet_handle).wait()
 }
 
+pub fn begin_transaction(wallet_handle: WalletHandle) -> Result<(), IndyError> {
+    wallet::begin_transaction(wallet_handle).wait()
+}
+
+pub fn commit_transaction(wallet_handle: WalletHandle) -> Result<(), IndyError> {
+    wallet::commit_transaction(wallet_handle).wait()
+}
+
+pub fn rollback_transaction(wallet_handle: WalletHandle) -> Result<(), IndyError> {
+    wallet::rollback_transaction(wallet_handle).wait()
+}
+
 pub fn close_and_delete_wallet(wallet_handle: WalletHandle, wallet_config: &str) -> Result<(), IndyError> {
     close_wallet(wallet_handle)?;
     delete_wallet(wallet_config, WALLET_CREDENTIALS)
@@ -121,6 +137,22 @@ pub fn export_wallet(wallet_handle: WalletHandle, export_config_json: &str) -> R
     wallet::export_wallet(wallet_handle, export_config_json).wait()
 }
 
+pub fn rekey_wallet(wallet_handle: WalletHandle, new_credentials: &str) -> Result<(), IndyError> {
+    wallet::rekey_wallet(wallet_handle, new_credentials).wait()
+}
+
+pub fn check_wallet(wallet_handle: WalletHandle, options: Option<&str>) -> Result<String, IndyError> {
+    wallet::check_wallet(wallet_handle, options).wait()
+}
+
+pub fn subscribe_wallet_events(wallet_handle: WalletHandle, type_filter: Option<&str>, event_cb: Option<extern fn(CommandHandle, *const c_char)>) -> Result<IndyHandle, IndyError> {
+    wallet::subscribe_wallet_events(wallet_handle, type_filter, event_cb).wait()
+}
+
+pub fn unsubscribe_wallet_events(subscription_handle: IndyHandle) -> Result<(), IndyError> {
+    wallet::unsubscribe_wallet_events(subscription_handle).wait()
+}
+
 pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Result<(), IndyError> {
     wallet::import_wallet(config, credentials, import_config).wait()
 }
@@ -137,6 +169,17 @@ pub fn prepare_export_wallet_config(path: &Path) -> String {
     serde_json::to_string(&json).unwrap()
 }
 
+pub fn prepare_chunked_export_wallet_config(path: &Path, base_manifest: Option<&Path>, verkey: &str) -> String {
+    let json = json!({
+            "path": path.to_str().unwrap(),
+            "key": "export_key",
+            "version": 1,
+            "base_manifest": base_manifest.map(|path| path.to_str().unwrap()),
+            "verkey": verkey,
+        });
+    serde_json::to_string(&json).unwrap()
+}
+
 pub fn generate_wallet_key(config: Option<&str>) -> Result<String, IndyError> {
     wallet::generate_wallet_key(config).wait()
 }
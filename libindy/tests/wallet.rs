@@ -7,7 +7,7 @@ extern crate indyrs as indy;
 extern crate indyrs as api;
 
 use crate::utils::inmem_wallet::InmemWallet;
-use crate::utils::{environment, wallet, test, did};
+use crate::utils::{environment, wallet, test, did, crypto};
 use crate::utils::constants::*;
 use crate::utils::Setup;
 
@@ -147,6 +147,49 @@ mod high_cases {
         }
     }
 
+    mod list_wallets {
+        use super::*;
+
+        #[test]
+        fn indy_list_wallets_works() {
+            let setup = Setup::empty();
+
+            let path = _custom_path(&setup.name);
+            let storage_config = json!({"path": &path}).to_string();
+
+            let wallets = wallet::list_wallets(None, Some(&storage_config), None).unwrap();
+            assert_eq!("[]", wallets);
+
+            let config = json!({
+                "id": &setup.name,
+                "storage_type": "default",
+                "storage_config": {
+                    "path": &path,
+                }
+            }).to_string();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let wallets = wallet::list_wallets(Some("default"), Some(&storage_config), None).unwrap();
+            let wallets: Vec<String> = serde_json::from_str(&wallets).unwrap();
+            assert_eq!(vec![setup.name.clone()], wallets);
+
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS).unwrap();
+        }
+
+        #[test]
+        fn indy_list_wallets_works_for_default_path() {
+            let setup = Setup::wallet();
+
+            let config: serde_json::Value = serde_json::from_str(&setup.wallet_config).unwrap();
+            let id = config["id"].as_str().unwrap().to_string();
+
+            let wallets = wallet::list_wallets(None, None, None).unwrap();
+            let wallets: Vec<String> = serde_json::from_str(&wallets).unwrap();
+            assert!(wallets.contains(&id));
+        }
+    }
+
     mod open_wallet {
         use super::*;
 
@@ -224,6 +267,118 @@ mod high_cases {
         }
     }
 
+    mod wallet_transaction {
+        use super::*;
+
+        #[test]
+        fn indy_wallet_transaction_commit_works() {
+            let setup = Setup::wallet();
+
+            wallet::begin_transaction(setup.wallet_handle).unwrap();
+            let (did, verkey) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+            wallet::commit_transaction(setup.wallet_handle).unwrap();
+
+            let key = did::key_for_local_did(setup.wallet_handle, &did).unwrap();
+            assert_eq!(verkey, key);
+        }
+
+        #[test]
+        fn indy_wallet_transaction_rollback_works() {
+            let setup = Setup::wallet();
+
+            wallet::begin_transaction(setup.wallet_handle).unwrap();
+            let (did, _) = did::create_and_store_my_did(setup.wallet_handle, None).unwrap();
+            wallet::rollback_transaction(setup.wallet_handle).unwrap();
+
+            let res = did::key_for_local_did(setup.wallet_handle, &did);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+        }
+    }
+
+    mod rekey_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_rekey_wallet_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let (did, verkey) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            wallet::rekey_wallet(wallet_handle, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+
+            let key = did::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(verkey, key);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let res = wallet::open_wallet(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+            let key = did::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(verkey, key);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+        }
+
+        #[test]
+        fn indy_rekey_wallet_works_for_plugged() {
+            Setup::empty();
+            InmemWallet::cleanup();
+
+            wallet::register_wallet_storage(INMEM_TYPE, false).unwrap();
+            wallet::create_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let (did, verkey) = did::create_and_store_my_did(wallet_handle, None).unwrap();
+
+            wallet::rekey_wallet(wallet_handle, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let wallet_handle = wallet::open_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS_ARGON2I_INT).unwrap();
+            let key = did::key_for_local_did(wallet_handle, &did).unwrap();
+            assert_eq!(verkey, key);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            InmemWallet::cleanup();
+        }
+    }
+
+    mod check_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_check_wallet_works() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let report = wallet::check_wallet(setup.wallet_handle, None).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert!(report["total"].as_u64().unwrap() > 0);
+            assert_eq!(json!([]), report["broken"]);
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_quarantine() {
+            let setup = Setup::wallet();
+
+            did::create_my_did(setup.wallet_handle, "{}").unwrap();
+
+            let report = wallet::check_wallet(setup.wallet_handle, Some(r#"{"quarantine": true}"#)).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+            assert_eq!(json!([]), report["broken"]);
+        }
+    }
+
     mod export_wallet {
         use super::*;
 
@@ -281,6 +436,103 @@ mod high_cases {
             wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
             cleanup_file(&path);
         }
+
+        #[test]
+        fn indy_import_wallet_works_for_incremental_chunked_export() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let backup_dir = wallet::export_wallet_path(&setup.name);
+            let base_path = backup_dir.join("backup1");
+            let path = backup_dir.join("backup2");
+            let _ = fs::remove_dir_all(&backup_dir);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let verkey = crypto::create_key(wallet_handle, None).unwrap();
+
+            let (did1, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+            wallet::export_wallet(wallet_handle, &wallet::prepare_chunked_export_wallet_config(&base_path, None, &verkey)).unwrap();
+
+            let (did2, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+            let config_json = wallet::prepare_chunked_export_wallet_config(&path, Some(&base_path), &verkey);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::get_my_did_with_metadata(wallet_handle, &did1).unwrap();
+            did::get_my_did_with_metadata(wallet_handle, &did2).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+
+        #[test]
+        fn indy_import_wallet_works_for_chunked_export_signed_by_other_key() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let backup_dir = wallet::export_wallet_path(&setup.name);
+            let path = backup_dir.join("backup1");
+            let _ = fs::remove_dir_all(&backup_dir);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let verkey = crypto::create_key(wallet_handle, None).unwrap();
+            let other_verkey = crypto::create_key(wallet_handle, None).unwrap();
+
+            wallet::export_wallet(wallet_handle, &wallet::prepare_chunked_export_wallet_config(&path, None, &verkey)).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            let res = wallet::import_wallet(&config, WALLET_CREDENTIALS, &wallet::prepare_chunked_export_wallet_config(&path, None, &other_verkey));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            fs::remove_dir_all(&backup_dir).unwrap();
+        }
+    }
+
+    mod subscribe_wallet_events {
+        use super::*;
+        use crate::utils::non_secrets;
+        use std::ffi::CStr;
+        use std::sync::Mutex;
+        use indy::CommandHandle;
+        use libc::c_char;
+
+        lazy_static! {
+            static ref EVENTS: Mutex<Vec<serde_json::Value>> = Default::default();
+        }
+
+        extern fn _event_cb(_command_handle: CommandHandle, event_json: *const c_char) {
+            let event_json = unsafe { CStr::from_ptr(event_json).to_str().unwrap() };
+            EVENTS.lock().unwrap().push(serde_json::from_str(event_json).unwrap());
+        }
+
+        #[test]
+        fn indy_subscribe_wallet_events_works() {
+            let setup = Setup::wallet();
+
+            let subscription_handle = wallet::subscribe_wallet_events(setup.wallet_handle, Some("events_type"), Some(_event_cb)).unwrap();
+
+            non_secrets::add_wallet_record(setup.wallet_handle, "events_type", "id1", "value", None).unwrap();
+            non_secrets::add_wallet_record(setup.wallet_handle, "other_type", "id1", "value", None).unwrap();
+            non_secrets::delete_wallet_record(setup.wallet_handle, "events_type", "id1").unwrap();
+
+            wallet::unsubscribe_wallet_events(subscription_handle).unwrap();
+            non_secrets::add_wallet_record(setup.wallet_handle, "events_type", "id2", "value", None).unwrap();
+
+            let events = EVENTS.lock().unwrap();
+            assert_eq!(vec![
+                json!({"event": "add", "type": "events_type", "id": "id1"}),
+                json!({"event": "delete", "type": "events_type", "id": "id1"}),
+            ], *events);
+        }
     }
 
     mod generate_wallet_key {
@@ -461,6 +713,26 @@ mod medium_cases {
         }
     }
 
+    mod list_wallets {
+        use super::*;
+
+        #[test]
+        fn indy_list_wallets_works_for_unknown_type() {
+            Setup::empty();
+
+            let res = wallet::list_wallets(Some("unknown_type"), None, None);
+            assert_code!(ErrorCode::WalletUnknownTypeError, res);
+        }
+
+        #[test]
+        fn indy_list_wallets_works_for_invalid_storage_config() {
+            Setup::empty();
+
+            let res = wallet::list_wallets(None, Some("{invalid}"), None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod open_wallet {
         use super::*;
 
@@ -594,6 +866,115 @@ mod medium_cases {
         }
     }
 
+    mod wallet_transaction {
+        use super::*;
+
+        #[test]
+        fn indy_wallet_begin_transaction_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::begin_transaction(INVALID_WALLET_HANDLE);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_wallet_begin_transaction_works_for_twice() {
+            let setup = Setup::wallet();
+
+            wallet::begin_transaction(setup.wallet_handle).unwrap();
+            let res = wallet::begin_transaction(setup.wallet_handle);
+            assert_code!(ErrorCode::WalletStorageError, res);
+
+            wallet::rollback_transaction(setup.wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_wallet_commit_transaction_works_for_not_started() {
+            let setup = Setup::wallet();
+
+            let res = wallet::commit_transaction(setup.wallet_handle);
+            assert_code!(ErrorCode::WalletStorageError, res);
+        }
+
+        #[test]
+        fn indy_wallet_begin_transaction_works_for_plugged_without_transaction_handlers() {
+            let setup = Setup::plugged_wallet();
+
+            let res = wallet::begin_transaction(setup.wallet_handle);
+            assert_code!(ErrorCode::WalletStorageError, res);
+        }
+    }
+
+    mod subscribe_wallet_events {
+        use super::*;
+
+        #[test]
+        fn indy_subscribe_wallet_events_works_for_missed_event_cb() {
+            let setup = Setup::wallet();
+
+            let res = wallet::subscribe_wallet_events(setup.wallet_handle, None, None);
+            assert_code!(ErrorCode::CommonInvalidParam4, res);
+        }
+
+        #[test]
+        fn indy_subscribe_wallet_events_works_for_invalid_handle() {
+            Setup::empty();
+
+            extern fn _event_cb(_command_handle: indy::CommandHandle, _event_json: *const libc::c_char) {}
+
+            let res = wallet::subscribe_wallet_events(INVALID_WALLET_HANDLE, None, Some(_event_cb));
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_unsubscribe_wallet_events_works_for_unknown_handle() {
+            Setup::empty();
+
+            let res = wallet::unsubscribe_wallet_events(-1);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
+    mod rekey_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_rekey_wallet_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::rekey_wallet(INVALID_WALLET_HANDLE, WALLET_CREDENTIALS_RAW);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_rekey_wallet_works_for_invalid_credentials() {
+            let setup = Setup::wallet();
+
+            let res = wallet::rekey_wallet(setup.wallet_handle, "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod check_wallet {
+        use super::*;
+
+        #[test]
+        fn indy_check_wallet_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::check_wallet(INVALID_WALLET_HANDLE, None);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_invalid_options() {
+            let setup = Setup::wallet();
+
+            let res = wallet::check_wallet(setup.wallet_handle, Some(r#"{"quarantine": "yes"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod export_wallet {
         use super::*;
         use std::fs;
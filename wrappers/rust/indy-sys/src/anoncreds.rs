@@ -71,6 +71,43 @@ extern {
                                          cred_revoc_id: CString,
                                          cb: Option<ResponseStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_issuer_create_revoc_reg_manager(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                issuer_did: CString,
+                                                cred_def_id: CString,
+                                                config_json: CString,
+                                                tails_writer_handle: TailWriterHandle,
+                                                cb: Option<ResponseStringStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_create_credential_with_revoc_reg_manager(command_handle: CommandHandle,
+                                                                wallet_handle: WalletHandle,
+                                                                cred_offer_json: CString,
+                                                                cred_req_json: CString,
+                                                                cred_values_json: CString,
+                                                                tails_writer_handle: TailWriterHandle,
+                                                                blob_storage_reader_handle: BlobStorageReaderHandle,
+                                                                cb: Option<ResponseStringStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_get_revoc_reg_manager_state(command_handle: CommandHandle,
+                                                   wallet_handle: WalletHandle,
+                                                   cred_def_id: CString,
+                                                   cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_mark_revoc_reg_published(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                rev_reg_id: CString,
+                                                cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_issuer_get_issued_credentials(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              query_json: CString,
+                                              cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_issuer_merge_revocation_registry_deltas(command_handle: CommandHandle,
                                                         rev_reg_delta_json: CString,
@@ -176,6 +213,13 @@ extern {
                                                               search_handle: SearchHandle,
                                                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_prover_select_credentials_for_proof_req(command_handle: CommandHandle,
+                                                        wallet_handle: WalletHandle,
+                                                        proof_request_json: CString,
+                                                        policy_json: CString,
+                                                        cb: Option<ResponseStringStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_prover_create_proof(command_handle: CommandHandle,
                                     wallet_handle: WalletHandle,
@@ -197,6 +241,16 @@ extern {
                                       rev_regs_json: CString,
                                       cb: Option<ResponseBoolCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                  proof_request_json: CString,
+                                                  proof_json: CString,
+                                                  schemas_json: CString,
+                                                  credential_defs_json: CString,
+                                                  rev_reg_defs_json: CString,
+                                                  rev_regs_json: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_revocation_state(command_handle: CommandHandle,
                                         blob_storage_reader_handle: BlobStorageReaderHandle,
@@ -223,5 +277,10 @@ extern {
     pub fn indy_to_unqualified(command_handle: CommandHandle,
                                entity: CString,
                                cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_encode_credential_values(command_handle: CommandHandle,
+                                         cred_values_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
 }
 
@@ -33,4 +33,69 @@ extern {
                                      wallet_handle: WalletHandle,
                                      options_json: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_nym(command_handle: CommandHandle,
+                        pool_handle: PoolHandle,
+                        wallet_handle: WalletHandle,
+                        submitter_did: CString,
+                        target_did: CString,
+                        options_json: CString,
+                        cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_attrib(command_handle: CommandHandle,
+                           pool_handle: PoolHandle,
+                           wallet_handle: WalletHandle,
+                           submitter_did: CString,
+                           target_did: CString,
+                           raw: CString,
+                           hash: CString,
+                           enc: CString,
+                           options_json: CString,
+                           cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_revoc_reg_def(command_handle: CommandHandle,
+                                  pool_handle: PoolHandle,
+                                  wallet_handle: WalletHandle,
+                                  submitter_did: CString,
+                                  id: CString,
+                                  options_json: CString,
+                                  cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_revoc_reg_delta(command_handle: CommandHandle,
+                                    pool_handle: PoolHandle,
+                                    wallet_handle: WalletHandle,
+                                    submitter_did: CString,
+                                    revoc_reg_def_id: CString,
+                                    from: i64,
+                                    to: i64,
+                                    options_json: CString,
+                                    cb: Option<ResponseStringU64CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_purge_nym_cache(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                options_json: CString,
+                                cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_purge_attrib_cache(command_handle: CommandHandle,
+                                   wallet_handle: WalletHandle,
+                                   options_json: CString,
+                                   cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_purge_revoc_reg_def_cache(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          options_json: CString,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_purge_revoc_reg_delta_cache(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            options_json: CString,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
 }
@@ -87,5 +87,75 @@ extern {
                                jwe_msg: BString,
                                jwe_len: u32,
                                cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_pack_message_v2(command_handle: CommandHandle,
+                                wallet_handle: WalletHandle,
+                                message: BString,
+                                message_len: u32,
+                                receiver_kids: CString,
+                                sender_kid: CString,
+                                options_json: CString,
+                                cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_unpack_message_v2(command_handle: CommandHandle,
+                                  wallet_handle: WalletHandle,
+                                  jwe_msg: BString,
+                                  jwe_len: u32,
+                                  cb: Option<ResponseSliceCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_register_key_manager(command_handle: CommandHandle,
+                                     name: CString,
+                                     create_key: Option<KeyManagerCreateKey>,
+                                     sign: Option<KeyManagerSign>,
+                                     crypto_box: Option<KeyManagerCryptoBox>,
+                                     crypto_box_open: Option<KeyManagerCryptoBoxOpen>,
+                                     crypto_box_seal_open: Option<KeyManagerCryptoBoxSealOpen>,
+                                     key_agreement: Option<KeyManagerKeyAgreement>,
+                                     free: Option<KeyManagerFree>,
+                                     cb: Option<ResponseEmptyCB>) -> Error;
 }
 
+pub type KeyManagerCreateKey = extern fn(crypto_type: CString,
+                                         seed: BString,
+                                         seed_len: usize,
+                                         verkey_p: *mut BString,
+                                         verkey_len_p: *mut usize,
+                                         key_ref_p: *mut CString) -> Error;
+pub type KeyManagerSign = extern fn(key_ref: CString,
+                                    message: BString,
+                                    message_len: usize,
+                                    signature_p: *mut BString,
+                                    signature_len_p: *mut usize) -> Error;
+pub type KeyManagerCryptoBox = extern fn(key_ref: CString,
+                                         their_vk: BString,
+                                         their_vk_len: usize,
+                                         message: BString,
+                                         message_len: usize,
+                                         nonce: BString,
+                                         nonce_len: usize,
+                                         encrypted_p: *mut BString,
+                                         encrypted_len_p: *mut usize) -> Error;
+pub type KeyManagerCryptoBoxOpen = extern fn(key_ref: CString,
+                                             their_vk: BString,
+                                             their_vk_len: usize,
+                                             encrypted: BString,
+                                             encrypted_len: usize,
+                                             nonce: BString,
+                                             nonce_len: usize,
+                                             decrypted_p: *mut BString,
+                                             decrypted_len_p: *mut usize) -> Error;
+pub type KeyManagerCryptoBoxSealOpen = extern fn(key_ref: CString,
+                                                 encrypted: BString,
+                                                 encrypted_len: usize,
+                                                 decrypted_p: *mut BString,
+                                                 decrypted_len_p: *mut usize) -> Error;
+pub type KeyManagerKeyAgreement = extern fn(key_ref: CString,
+                                            their_pk: BString,
+                                            their_pk_len: usize,
+                                            shared_secret_p: *mut BString,
+                                            shared_secret_len_p: *mut usize) -> Error;
+pub type KeyManagerFree = extern fn(buffer: BString) -> Error;
+
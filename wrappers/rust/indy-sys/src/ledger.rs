@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle, WalletHandle, PoolHandle};
+use {CString, Error, CommandHandle, WalletHandle, PoolHandle, SearchHandle};
 
 extern {
     #[no_mangle]
@@ -117,6 +117,43 @@ extern {
                                             get_cred_def_response: CString,
                                             cb: Option<ResponseStringStringCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_resolve(command_handle: CommandHandle,
+                        id: CString,
+                        cb: Option<ResponseStringStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_download_ledger_txns(command_handle: CommandHandle,
+                                     pool_handle: PoolHandle,
+                                     ledger_type: CString,
+                                     from: i32,
+                                     to: i32,
+                                     options_json: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_open_ledger_txns_search(command_handle: CommandHandle,
+                                        pool_handle: PoolHandle,
+                                        ledger_type: CString,
+                                        from: i32,
+                                        to: i32,
+                                        cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_fetch_ledger_txns_search_next_batch(command_handle: CommandHandle,
+                                                    search_handle: SearchHandle,
+                                                    cb: Option<ResponseStringCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_close_ledger_txns_search(command_handle: CommandHandle,
+                                         search_handle: SearchHandle,
+                                         cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_create_pool_snapshot(command_handle: CommandHandle,
+                                     pool_handle: PoolHandle,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_build_node_request(command_handle: CommandHandle,
                                    submitter_did: CString,
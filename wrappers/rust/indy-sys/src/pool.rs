@@ -35,6 +35,18 @@ extern {
                                           config_name: CString,
                                           cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_import_pool_snapshot(command_handle: CommandHandle,
+                                     config_name: CString,
+                                     snapshot_path: CString,
+                                     options: CString,
+                                     cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_get_pool_status(command_handle: CommandHandle,
+                                handle: PoolHandle,
+                                cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_set_protocol_version(command_handle: CommandHandle,
                                      protocol_version: usize,
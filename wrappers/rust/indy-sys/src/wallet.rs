@@ -1,6 +1,6 @@
 use super::*;
 
-use {BString, CString, Error, CommandHandle, StorageHandle};
+use {BString, CString, Error, CommandHandle, IndyHandle, StorageHandle};
 
 extern {
 
@@ -33,6 +33,28 @@ extern {
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_transactions(command_handle: CommandHandle,
+                                                     type_: CString,
+                                                     begin_transaction: Option<WalletBeginTransaction>,
+                                                     commit_transaction: Option<WalletCommitTransaction>,
+                                                     rollback_transaction: Option<WalletRollbackTransaction>,
+                                                     cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_list(command_handle: CommandHandle,
+                                             type_: CString,
+                                             list_storages: Option<WalletListStorages>,
+                                             free_storage_list: Option<WalletFreeStorageList>,
+                                             cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_list_wallets(command_handle: CommandHandle,
+                             storage_type: CString,
+                             storage_config: CString,
+                             storage_credentials: CString,
+                             cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_create_wallet(command_handle: CommandHandle,
                               config: CString,
@@ -51,6 +73,19 @@ extern {
                               export_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_rekey_wallet(command_handle: CommandHandle,
+                             wallet_handle: WalletHandle,
+                             new_credentials: CString,
+                             progress_cb: Option<extern fn(command_handle_: CommandHandle, processed: u32, total: u32)>,
+                             cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_check_wallet(command_handle: CommandHandle,
+                             wallet_handle: WalletHandle,
+                             options_json: CString,
+                             cb: Option<ResponseStringCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_import_wallet(command_handle: CommandHandle,
                               config: CString,
@@ -63,6 +98,33 @@ extern {
                              wallet_handle: WalletHandle,
                              cb: Option<ResponseEmptyCB>) -> Error;
 
+    #[no_mangle]
+    pub fn indy_wallet_begin_transaction(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_wallet_commit_transaction(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_wallet_rollback_transaction(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            cb: Option<ResponseEmptyCB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_subscribe_wallet_events(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        type_filter: CString,
+                                        event_cb: Option<extern fn(command_handle_: CommandHandle, event_json: CString)>,
+                                        cb: Option<ResponseI32CB>) -> Error;
+
+    #[no_mangle]
+    pub fn indy_unsubscribe_wallet_events(command_handle: CommandHandle,
+                                          subscription_handle: IndyHandle,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
+
     #[no_mangle]
     pub fn indy_delete_wallet(command_handle: CommandHandle,
                               config: CString,
@@ -155,3 +217,11 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: StorageHandle,
                                                  record_handle_p: *mut RecordHandle) -> Error;
 pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                       search_handle: SearchHandle) -> Error;
+pub type WalletBeginTransaction = extern fn(storage_handle: StorageHandle) -> Error;
+pub type WalletCommitTransaction = extern fn(storage_handle: StorageHandle) -> Error;
+pub type WalletRollbackTransaction = extern fn(storage_handle: StorageHandle) -> Error;
+pub type WalletListStorages = extern fn(config: CString,
+                                        credentials: CString,
+                                        ids_json_p: *mut CString,
+                                        ids_handle_p: *mut IndyHandle) -> Error;
+pub type WalletFreeStorageList = extern fn(ids_handle: IndyHandle) -> Error;
@@ -55,7 +55,7 @@ pub fn issuer_create_schema(issuer_did: &str, name: &str, version: &str, attrs:
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _issuer_create_schema(command_handle: CommandHandle, issuer_did: &str, name: &str, version: &str, attrs: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _issuer_create_schema(command_handle: CommandHandle, issuer_did: &str, name: &str, version: &str, attrs: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let issuer_did = c_str!(issuer_did);
     let name = c_str!(name);
     let version = c_str!(version);
@@ -118,7 +118,7 @@ pub fn issuer_create_and_store_credential_def(wallet_handle: WalletHandle, issue
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _issuer_create_and_store_credential_def(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, schema_json: &str, tag: &str, signature_type: Option<&str>, config_json: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _issuer_create_and_store_credential_def(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, schema_json: &str, tag: &str, signature_type: Option<&str>, config_json: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let issuer_did = c_str!(issuer_did);
     let schema_json = c_str!(schema_json);
     let tag = c_str!(tag);
@@ -160,7 +160,7 @@ pub fn issuer_rotate_credential_def_start(wallet_handle: WalletHandle, cred_def_
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _issuer_rotate_credential_def_start(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, config: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _issuer_rotate_credential_def_start(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, config: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let cred_def_id = c_str!(cred_def_id);
     let config_str = opt_c_str!(config);
 
@@ -190,7 +190,7 @@ pub fn issuer_rotate_credential_def_apply(wallet_handle: WalletHandle, cred_def_
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _issuer_rotate_credential_def_apply(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _issuer_rotate_credential_def_apply(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let cred_def_id = c_str!(cred_def_id);
 
     ErrorCode::from(unsafe {
@@ -285,7 +285,7 @@ pub fn issuer_create_and_store_revoc_reg(wallet_handle: WalletHandle,
     ResultHandler::str_str_str(command_handle, err, receiver)
 }
 
-fn _issuer_create_and_store_revoc_reg(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, revoc_def_type: Option<&str>, tag: &str, cred_def_id: &str, config_json: &str, tails_writer_handle: TailsWriterHandle, cb: Option<ResponseStringStringStringCB>) -> ErrorCode {
+pub(crate) fn _issuer_create_and_store_revoc_reg(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, revoc_def_type: Option<&str>, tag: &str, cred_def_id: &str, config_json: &str, tails_writer_handle: TailsWriterHandle, cb: Option<ResponseStringStringStringCB>) -> ErrorCode {
     let issuer_did = c_str!(issuer_did);
     let revoc_def_type_str = opt_c_str!(revoc_def_type);
     let tag = c_str!(tag);
@@ -323,7 +323,7 @@ pub fn issuer_create_credential_offer(wallet_handle: WalletHandle, cred_def_id:
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _issuer_create_credential_offer(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _issuer_create_credential_offer(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let cred_def_id = c_str!(cred_def_id);
 
     ErrorCode::from(unsafe {
@@ -386,7 +386,7 @@ pub fn issuer_create_credential(wallet_handle: WalletHandle,
     ResultHandler::str_optstr_optstr(command_handle, err, receiver)
 }
 
-fn _issuer_create_credential(
+pub(crate) fn _issuer_create_credential(
     command_handle: CommandHandle,
     wallet_handle: WalletHandle,
     cred_offer_json: &str,
@@ -430,7 +430,7 @@ pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _issuer_revoke_credential(command_handle: CommandHandle,
+pub(crate) fn _issuer_revoke_credential(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
                              blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
                              rev_reg_id: &str,
@@ -444,6 +444,192 @@ fn _issuer_revoke_credential(command_handle: CommandHandle,
     })
 }
 
+/// Create a revocation registry manager for the credential definition and its first (active) revocation registry.
+///
+/// The manager keeps a pool of revocation registries for the credential definition and switches
+/// issuance to the next registry when the active one is full (see issuer_create_credential_with_revoc_reg_manager).
+/// Definitions and initial entries of managed registries still must be published to the ledger by the issuer.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `issuer_did`: a DID of the issuer
+/// * `cred_def_id`: id of stored in ledger credential definition
+/// * `config_json`: (optional) manager configuration as json:
+///     {
+///         "issuance_type": (optional) type of issuance of managed registries,
+///         "max_cred_num": (optional) maximum number of credentials each managed registry can process (default 100000),
+///         "tag_prefix": (optional) prefix of managed registry tags (default "rev_reg_"),
+///         "rollover_threshold": (optional) part of the active registry capacity to be used before
+///                               the next registry is created, in (0, 1] range (default 0.8)
+///     }
+/// * `tails_writer_handle`: handle of blob storage to store tails
+///
+/// # Returns
+/// * `revoc_reg_id`: identifier of created revocation registry definition
+/// * `revoc_reg_def_json`: public part of revocation registry definition
+/// * `revoc_reg_entry_json`: revocation registry entry that defines initial state of revocation registry
+pub fn issuer_create_revoc_reg_manager(wallet_handle: WalletHandle,
+                                       issuer_did: &str,
+                                       cred_def_id: &str,
+                                       config_json: Option<&str>,
+                                       tails_writer_handle: TailsWriterHandle) -> Box<dyn Future<Item=(String, String, String), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string_string();
+
+    let err = _issuer_create_revoc_reg_manager(command_handle, wallet_handle, issuer_did, cred_def_id, config_json, tails_writer_handle, cb);
+
+    ResultHandler::str_str_str(command_handle, err, receiver)
+}
+
+pub(crate) fn _issuer_create_revoc_reg_manager(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, cred_def_id: &str, config_json: Option<&str>, tails_writer_handle: TailsWriterHandle, cb: Option<ResponseStringStringStringCB>) -> ErrorCode {
+    let issuer_did = c_str!(issuer_did);
+    let cred_def_id = c_str!(cred_def_id);
+    let config_json_str = opt_c_str!(config_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_revoc_reg_manager(command_handle, wallet_handle, issuer_did.as_ptr(), cred_def_id.as_ptr(), opt_c_ptr!(config_json, config_json_str), tails_writer_handle, cb)
+    })
+}
+
+/// Check Cred Request for the given Cred Offer and issue Credential for the given Cred Request
+/// using the active revocation registry of the revocation registry manager for the credential definition of the Cred Offer.
+///
+/// The next registry marked as published (see issuer_mark_revoc_reg_published) becomes active when the active one is full.
+/// If there is none, the call fails with `AnoncredsRevocationRegistryFullError` and the next registry is created as pending
+/// (if not created yet) until it is published and marked as published.
+/// Once usage of the active registry crosses the rollover threshold, the next registry is created in advance as pending.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_offer_json`: a cred offer created by create_credential_offer
+/// * `cred_req_json`: a credential request created by store_credential
+/// * `cred_values_json`: a credential containing attribute values for each of requested attribute names.
+/// * `tails_writer_handle`: handle of blob storage to store tails of new registries
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `cred_json`: Credential json containing signed credential values
+/// * `cred_revoc_id`: local id for revocation info (Can be used for revocation of this credential)
+/// * `revoc_reg_delta_json`: Revocation registry delta json with a newly issued credential
+pub fn issuer_create_credential_with_revoc_reg_manager(wallet_handle: WalletHandle,
+                                                       cred_offer_json: &str,
+                                                       cred_req_json: &str,
+                                                       cred_values_json: &str,
+                                                       tails_writer_handle: TailsWriterHandle,
+                                                       blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string_opt_string();
+
+    let err = _issuer_create_credential_with_revoc_reg_manager(command_handle, wallet_handle, cred_offer_json, cred_req_json, cred_values_json, tails_writer_handle, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr_optstr(command_handle, err, receiver)
+}
+
+pub(crate) fn _issuer_create_credential_with_revoc_reg_manager(
+    command_handle: CommandHandle,
+    wallet_handle: WalletHandle,
+    cred_offer_json: &str,
+    cred_req_json: &str,
+    cred_values_json: &str,
+    tails_writer_handle: TailsWriterHandle,
+    blob_storage_reader_handle: BlobStorageReaderHandle,
+    cb: Option<ResponseStringStringStringCB>
+) -> ErrorCode {
+    let cred_offer_json = c_str!(cred_offer_json);
+    let cred_req_json = c_str!(cred_req_json);
+    let cred_values_json = c_str!(cred_values_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credential_with_revoc_reg_manager(command_handle, wallet_handle, cred_offer_json.as_ptr(), cred_req_json.as_ptr(), cred_values_json.as_ptr(), tails_writer_handle, blob_storage_reader_handle, cb)
+    })
+}
+
+/// Get state of revocation registries managed by the revocation registry manager for the credential definition.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_def_id`: id of credential definition the manager is created for
+///
+/// # Returns
+/// * `registries_json`: list of managed revocation registries in order of creation
+///     [{
+///         "rev_reg_id": string,
+///         "tag": string,
+///         "status": string, - "pending", "ready", "active" or "full"
+///         "max_cred_num": number,
+///         "issued": number,
+///         "revoked": number,
+///         "rev_reg_def": (only for "pending" registries) revocation registry definition to be published to the ledger,
+///         "rev_reg": (only for "pending" registries) initial revocation registry entry
+///     }]
+pub fn issuer_get_revoc_reg_manager_state(wallet_handle: WalletHandle, cred_def_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_revoc_reg_manager_state(command_handle, wallet_handle, cred_def_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _issuer_get_revoc_reg_manager_state(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_def_id = c_str!(cred_def_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_revoc_reg_manager_state(command_handle, wallet_handle, cred_def_id.as_ptr(), cb)
+    })
+}
+
+/// Mark pending revocation registry managed by the revocation registry manager as published to the ledger,
+/// so it can become active for issuance.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `rev_reg_id`: id of pending revocation registry
+pub fn issuer_mark_revoc_reg_published(wallet_handle: WalletHandle, rev_reg_id: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_mark_revoc_reg_published(command_handle, wallet_handle, rev_reg_id, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _issuer_mark_revoc_reg_published(command_handle: CommandHandle, wallet_handle: WalletHandle, rev_reg_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_mark_revoc_reg_published(command_handle, wallet_handle, rev_reg_id.as_ptr(), cb)
+    })
+}
+
+/// Gets credentials issued by the issuer with revocation support.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `query_json`: Wql query filter for issued credentials searching based on tags
+///     ("cred_def_id", "rev_reg_id", "cred_rev_id" and "status").
+///     where query: indy-sdk/doc/design/011-wallet-query-language/README.md
+///
+/// # Returns
+/// * `issued_credentials_json`: list of issued credentials
+///     [{
+///         "cred_def_id": string,
+///         "rev_reg_id": string,
+///         "cred_rev_id": string,
+///         "status": string, - "issued" or "revoked"
+///     }]
+pub fn issuer_get_issued_credentials(wallet_handle: WalletHandle, query_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_issued_credentials(command_handle, wallet_handle, query_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _issuer_get_issued_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let query_json_str = opt_c_str!(query_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_issued_credentials(command_handle, wallet_handle, opt_c_ptr!(query_json, query_json_str), cb)
+    })
+}
+
 /// Merge two revocation registry deltas (returned by create_credential or revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
@@ -461,7 +647,7 @@ pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta_json: &str, other_r
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _issuer_merge_revocation_registry_deltas(command_handle: CommandHandle, rev_reg_delta_json: &str, other_rev_reg_delta_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _issuer_merge_revocation_registry_deltas(command_handle: CommandHandle, rev_reg_delta_json: &str, other_rev_reg_delta_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let rev_reg_delta_json = c_str!(rev_reg_delta_json);
     let other_rev_reg_delta_json = c_str!(other_rev_reg_delta_json);
 
@@ -488,7 +674,7 @@ pub fn prover_create_master_secret(wallet_handle: WalletHandle, master_secret_id
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_create_master_secret(command_handle: CommandHandle, wallet_handle: WalletHandle, master_secret_id: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_create_master_secret(command_handle: CommandHandle, wallet_handle: WalletHandle, master_secret_id: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let master_secret_id_str = opt_c_str!(master_secret_id);
 
     ErrorCode::from(unsafe {
@@ -519,7 +705,7 @@ pub fn prover_get_credential(wallet_handle: WalletHandle, cred_id: &str) -> Box<
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_get_credential(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_get_credential(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let cred_id = c_str!(cred_id);
 
     ErrorCode::from(unsafe {
@@ -540,7 +726,7 @@ pub fn prover_delete_credential(wallet_handle: WalletHandle, cred_id: &str) -> B
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _prover_delete_credential(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _prover_delete_credential(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let cred_id = c_str!(cred_id);
 
     ErrorCode::from(unsafe {
@@ -585,7 +771,7 @@ pub fn prover_create_credential_req(wallet_handle: WalletHandle, prover_did: &st
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _prover_create_credential_req(command_handle: CommandHandle, wallet_handle: WalletHandle, prover_did: &str, cred_offer_json: &str, cred_def_json: &str, master_secret_id: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _prover_create_credential_req(command_handle: CommandHandle, wallet_handle: WalletHandle, prover_did: &str, cred_offer_json: &str, cred_def_json: &str, master_secret_id: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let prover_did = c_str!(prover_did);
     let cred_offer_json = c_str!(cred_offer_json);
     let cred_def_json = c_str!(cred_def_json);
@@ -632,7 +818,7 @@ pub fn prover_set_credential_attr_tag_policy(wallet_handle: WalletHandle, cred_d
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _prover_set_credential_attr_tag_policy(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, tag_attrs_json: Option<&str>, retroactive: bool, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _prover_set_credential_attr_tag_policy(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, tag_attrs_json: Option<&str>, retroactive: bool, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let cred_def_id = c_str!(cred_def_id);
     let tag_attrs_json_str = opt_c_str!(tag_attrs_json);
 
@@ -658,7 +844,7 @@ pub fn prover_get_credential_attr_tag_policy(wallet_handle: WalletHandle, cred_i
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_get_credential_attr_tag_policy(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_get_credential_attr_tag_policy(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let cred_id = c_str!(cred_id);
 
     ErrorCode::from(unsafe {
@@ -701,7 +887,7 @@ pub fn prover_store_credential(wallet_handle: WalletHandle, cred_id: Option<&str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_store_credential(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: Option<&str>, cred_req_metadata_json: &str, cred_json: &str, cred_def_json: &str, rev_reg_def_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_store_credential(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_id: Option<&str>, cred_req_metadata_json: &str, cred_json: &str, cred_def_json: &str, rev_reg_def_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let cred_id_str = opt_c_str!(cred_id);
     let cred_req_metadata_json = c_str!(cred_req_metadata_json);
     let cred_json = c_str!(cred_json);
@@ -745,7 +931,7 @@ pub fn prover_get_credentials(wallet_handle: WalletHandle, filter_json: Option<&
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_get_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, filter_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_get_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, filter_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let filter_json_str = opt_c_str!(filter_json);
 
     ErrorCode::from(unsafe {
@@ -776,7 +962,7 @@ pub fn prover_search_credentials(wallet_handle: WalletHandle, query_json: Option
     ResultHandler::handle_usize(command_handle, err, receiver)
 }
 
-fn _prover_search_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseI32UsizeCB>) -> ErrorCode {
+pub(crate) fn _prover_search_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseI32UsizeCB>) -> ErrorCode {
     let query_json_str = opt_c_str!(query_json);
 
     ErrorCode::from(unsafe {
@@ -808,7 +994,7 @@ pub fn prover_fetch_credentials(search_handle: SearchHandle, count: usize) -> Bo
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_fetch_credentials(command_handle: CommandHandle, search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_fetch_credentials(command_handle: CommandHandle, search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
         anoncreds::indy_prover_fetch_credentials(command_handle, search_handle, count, cb)
     })
@@ -826,7 +1012,7 @@ pub fn prover_close_credentials_search(search_handle: SearchHandle) -> Box<dyn F
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _prover_close_credentials_search(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _prover_close_credentials_search(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
         anoncreds::indy_prover_close_credentials_search(command_handle, search_handle, cb)
     })
@@ -852,6 +1038,14 @@ fn _prover_close_credentials_search(command_handle: CommandHandle, search_handle
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -872,19 +1066,36 @@ fn _prover_close_credentials_search(command_handle: CommandHandle, search_handle
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see encode_credential_values)
 ///     }
 /// `predicate_referent`: Proof-request local identifier of requested attribute predicate
 /// `predicate_info`: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (Currently ">=" only)
-///         "p_value": int predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<filter_json>, // see above
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// `equality_referent`: Proof-request local identifier of requested equality
+/// `equality_info`: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// `membership_referent`: Proof-request local identifier of requested membership
+/// `membership_info`: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// `non_revoc_interval`: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -919,7 +1130,7 @@ pub fn prover_get_credentials_for_proof_req(wallet_handle: WalletHandle, proof_r
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_get_credentials_for_proof_req(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_get_credentials_for_proof_req(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let proof_request_json = c_str!(proof_request_json);
 
     ErrorCode::from(unsafe {
@@ -948,6 +1159,14 @@ fn _prover_get_credentials_for_proof_req(command_handle: CommandHandle, wallet_h
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -974,19 +1193,36 @@ fn _prover_get_credentials_for_proof_req(command_handle: CommandHandle, wallet_h
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see encode_credential_values)
 ///     }
 /// `predicate_referent`: Proof-request local identifier of requested attribute predicate
 /// `predicate_info`: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (Currently ">=" only)
-///         "p_value": int predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<filter_json>, // see above
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// `equality_referent`: Proof-request local identifier of requested equality
+/// `equality_info`: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// `membership_referent`: Proof-request local identifier of requested membership
+/// `membership_info`: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// `non_revoc_interval`: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -1005,7 +1241,7 @@ pub fn prover_search_credentials_for_proof_req(wallet_handle: WalletHandle,
     ResultHandler::handle(command_handle, err, receiver)
 }
 
-fn _prover_search_credentials_for_proof_req(command_handle: CommandHandle,
+pub(crate) fn _prover_search_credentials_for_proof_req(command_handle: CommandHandle,
                                             wallet_handle: WalletHandle,
                                             proof_request_json: &str,
                                             extra_query_json: Option<&str>, cb: Option<ResponseI32CB>) -> ErrorCode {
@@ -1056,7 +1292,7 @@ pub fn prover_fetch_credentials_for_proof_req(search_handle: SearchHandle, item_
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_fetch_credentials_for_proof_req(command_handle: CommandHandle, search_handle: SearchHandle, item_referent: &str, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_fetch_credentials_for_proof_req(command_handle: CommandHandle, search_handle: SearchHandle, item_referent: &str, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
     let item_referent = c_str!(item_referent);
 
     ErrorCode::from(unsafe {
@@ -1076,12 +1312,57 @@ pub fn prover_close_credentials_search_for_proof_req(search_handle: SearchHandle
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
         anoncreds::indy_prover_close_credentials_search_for_proof_req(command_handle, search_handle, cb)
     })
 }
 
+/// Selects credentials from the wallet to satisfy the given proof request.
+///
+/// For each requested attribute and predicate the most suitable credential is chosen according to the selection policy.
+/// The result can be passed to prover_create_proof as `requested_credentials_json`
+/// after the listed revocation states are built (see create_revocation_state).
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by open_wallet).
+/// * `proof_request_json`: proof request json (see prover_search_credentials_for_proof_req)
+/// * `policy_json`: (Optional) selection policy json. Preferences are applied in the listed order:
+///     {
+///         "prefer_issuers": Optional<[string]>, // issuer DIDs in order of preference
+///         "prefer_non_revocable": Optional<bool>, // (false by default) prefer credentials which can't be revoked
+///         "newest": Optional<bool>, // (false by default) prefer the most recently stored credentials
+///         "minimize_credentials": Optional<bool>, // (false by default) use as few different credentials as possible
+///         "self_attested_attributes": Optional<{"attr_referent": string}>, // values for requested attributes
+///                                     // without restrictions which have no matching credential in the wallet
+///     }
+///
+/// # Returns
+/// * `requested_credentials_json`: requested credentials json (see prover_create_proof).
+///     All selected attributes are marked as revealed.
+/// * `rev_states_json`: revocation states required to create the proof.
+///     [{
+///         "rev_reg_id": string, // identifier of revocation registry definition
+///         "cred_rev_id": string, // identifier of credential in the revocation registry
+///         "timestamp": int, // timestamp the revocation state must be built for
+///     }]
+pub fn prover_select_credentials_for_proof_req(wallet_handle: WalletHandle, proof_request_json: &str, policy_json: Option<&str>) -> Box<dyn Future<Item=(String, String), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = _prover_select_credentials_for_proof_req(command_handle, wallet_handle, proof_request_json, policy_json, cb);
+
+    ResultHandler::str_str(command_handle, err, receiver)
+}
+
+pub(crate) fn _prover_select_credentials_for_proof_req(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_request_json: &str, policy_json: Option<&str>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let policy_json_str = opt_c_str!(policy_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_select_credentials_for_proof_req(command_handle, wallet_handle, proof_request_json.as_ptr(), opt_c_ptr!(policy_json, policy_json_str), cb)
+    })
+}
+
 /// Creates a proof according to the given proof request
 /// Either a corresponding credential with optionally revealed attributes or self-attested attribute must be provided
 /// for each requested attribute (see indy_prover_get_credentials_for_pool_req).
@@ -1105,6 +1386,14 @@ fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -1165,19 +1454,36 @@ fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
+///         "encoding": Optional<"default" | "date" | {"decimal": int}>, // if specified verifier checks that
+///                     // revealed encoded value is produced from raw value by this encoding
+///                     // (see encode_credential_values)
 ///     }
 /// predicate_referent: Proof-request local identifier of requested attribute predicate
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
 ///         "p_type": predicate type (Currently >= only)
-///         "p_value": predicate value
+///         "p_value": int predicate value (32-bit signed integer, 64-bit values are not supported)
 ///         "restrictions": Optional<wql query>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval this attribute
 ///                        // (overrides proof level interval)
 ///     }
+/// `equality_referent`: Proof-request local identifier of requested equality
+/// `equality_info`: Describes requested equality of attribute values
+///     {
+///         "attr_referents": [string, string, ...], // referents of requested attributes with "name"
+///                                                  // (the attributes can have different names and come from different credentials).
+///                                                  // Unrevealed values are proven to be equal without disclosing them.
+///     }
+/// `membership_referent`: Proof-request local identifier of requested membership
+/// `membership_info`: Describes requested membership of attribute value in a set
+///     {
+///         "attr_referent": string, // referent of requested attribute with "name"
+///         "values": [string, ...], // raw values of the set encoded by "encoding" of the requested attribute (at most 100).
+///                                  // The attribute must be revealed: membership of unrevealed value is not supported.
+///     }
 /// non_revoc_interval: Defines non-revocation interval
 ///     {
 ///         "from": Optional<int>, // timestamp of interval beginning
@@ -1222,7 +1528,7 @@ pub fn prover_create_proof(wallet_handle: WalletHandle, proof_req_json: &str, re
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prover_create_proof(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str, master_secret_id: &str, schemas_json: &str, credential_defs_json: &str, rev_states_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _prover_create_proof(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_req_json: &str, requested_credentials_json: &str, master_secret_id: &str, schemas_json: &str, credential_defs_json: &str, rev_states_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let proof_req_json = c_str!(proof_req_json);
     let requested_credentials_json = c_str!(requested_credentials_json);
     let master_secret_id = c_str!(master_secret_id);
@@ -1257,6 +1563,14 @@ fn _prover_create_proof(command_handle: CommandHandle, wallet_handle: WalletHand
 ///              "<predicate_referent>": <predicate_info>, // see below
 ///              ...,
 ///          },
+///         "requested_equalities": Optional<{ // set of requested equalities of attribute values
+///              "<equality_referent>": <equality_info>, // see below
+///              ...,
+///          }>,
+///         "requested_memberships": Optional<{ // set of requested memberships of attribute values in sets
+///              "<membership_referent>": <membership_info>, // see below
+///              ...,
+///          }>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
 ///                        // for date in this interval for each attribute
@@ -1332,7 +1646,7 @@ pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas
     ResultHandler::bool(command_handle, err, receiver)
 }
 
-fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
+pub(crate) fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, cb: Option<ResponseBoolCB>) -> ErrorCode {
     let proof_request_json = c_str!(proof_request_json);
     let proof_json = c_str!(proof_json);
     let schemas_json = c_str!(schemas_json);
@@ -1345,6 +1659,62 @@ fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &st
     })
 }
 
+/// Verifies a proof (of multiple credential) and reports result of each performed check.
+///
+/// Unlike `verifier_verify_proof` it doesn't stop on the first failed check,
+/// so it can be used to find out why a proof is rejected.
+///
+/// # Arguments
+/// The same as for `verifier_verify_proof`.
+///
+/// # Returns
+/// * `report_json`: verification report
+///     {
+///         "valid": bool, // true if all checks are passed (the same as `verifier_verify_proof` result)
+///         "structure": <check_result>, // received referents correspond to requested ones
+///         "attributes": {"<attr_referent>": <referent_report>},
+///         "predicates": {"<predicate_referent>": <referent_report>},
+///         "equalities": Optional<{"<equality_referent>": <check_result>}>,
+///         "memberships": Optional<{"<membership_referent>": <check_result>}>,
+///         "sub_proofs": [{
+///             "schema_id": string,
+///             "cred_def_id": string,
+///             "rev_reg_id": Optional<string>,
+///             "timestamp": Optional<int>,
+///             "resolved": <check_result>, // schema, credential definition and revocation registry are provided
+///         }],
+///         "crypto_proof": Optional<<check_result>>, // primary and non-revocation proofs of all sub proofs
+///     }
+/// referent_report:
+///     {
+///         "status": "revealed" | "unrevealed" | "self_attested" | "proven" | "missing",
+///         "sub_proof_index": Optional<int>,
+///         "value": Optional<<check_result>>, // revealed value corresponds to crypto proof and requested encoding
+///         "restrictions": Optional<<check_result>>,
+///         "non_revoked": Optional<<check_result>>, // timestamp is provided for requested non-revocation interval
+///     }
+/// check_result: {"valid": bool, "error": Optional<string>}
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_verify_proof_with_report(command_handle, proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _verifier_verify_proof_with_report(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_reg_defs_json = c_str!(rev_reg_defs_json);
+    let rev_regs_json = c_str!(rev_regs_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_report(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    })
+}
+
 
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
@@ -1378,7 +1748,7 @@ pub fn create_revocation_state(blob_storage_reader_handle: BlobStorageReaderHand
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _create_revocation_state(command_handle: CommandHandle, blob_storage_reader_handle: BlobStorageReaderHandle, rev_reg_def_json: &str, rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _create_revocation_state(command_handle: CommandHandle, blob_storage_reader_handle: BlobStorageReaderHandle, rev_reg_def_json: &str, rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let rev_reg_def_json = c_str!(rev_reg_def_json);
     let rev_reg_delta_json = c_str!(rev_reg_delta_json);
     let cred_rev_id = c_str!(cred_rev_id);
@@ -1420,7 +1790,7 @@ pub fn update_revocation_state(blob_storage_reader_handle: BlobStorageReaderHand
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _update_revocation_state(command_handle: CommandHandle, blob_storage_reader_handle: BlobStorageReaderHandle, rev_state_json: &str, rev_reg_def_json: &str, rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _update_revocation_state(command_handle: CommandHandle, blob_storage_reader_handle: BlobStorageReaderHandle, rev_state_json: &str, rev_reg_def_json: &str, rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let rev_state_json = c_str!(rev_state_json);
     let rev_reg_def_json = c_str!(rev_reg_def_json);
     let rev_reg_delta_json = c_str!(rev_reg_delta_json);
@@ -1451,7 +1821,7 @@ pub fn generate_nonce() -> Box<dyn Future<Item=String, Error=IndyError>> {
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _generate_nonce(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _generate_nonce(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
         anoncreds::indy_generate_nonce(command_handle, cb)
     })
@@ -1487,10 +1857,43 @@ pub fn to_unqualified(entity: &str) -> Box<dyn Future<Item=String, Error=IndyErr
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _to_unqualified(command_handle: CommandHandle, entity: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _to_unqualified(command_handle: CommandHandle, entity: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let entity = c_str!(entity);
 
     ErrorCode::from(unsafe {
         anoncreds::indy_to_unqualified(command_handle, entity.as_ptr(), cb)
     })
 }
+
+/// Encode raw credential attribute values into the format expected by `issuer_create_credential`.
+///
+/// # Arguments
+/// * `cred_values_json`: raw attribute values with optional encoding scheme for each attribute.
+///     {
+///         "<attr_name>": <raw value as string> | { // "default" encoding is used for plain string
+///             "raw": string,
+///             "encoding": Optional<"default" | "date" | {"decimal": int}> // "default" if not specified
+///         },
+///     }
+///     Encoding schemes:
+///         "default" - 32-bit integer is encoded as itself, any other value as decimal representation of its SHA-256 hash.
+///         "date" - date in "YYYY-MM-DD" format is encoded as number of days since 1970-01-01 (can be negative).
+///         {"decimal": N} - decimal number with at most N fractional digits is encoded as integer number of 10^-N units.
+///
+/// # Returns
+/// * `encoded_cred_values_json`: credential values in the format of `cred_values_json` of `issuer_create_credential`.
+pub fn encode_credential_values(cred_values_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _encode_credential_values(command_handle, cred_values_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _encode_credential_values(command_handle: CommandHandle, cred_values_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_values_json = c_str!(cred_values_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_encode_credential_values(command_handle, cred_values_json.as_ptr(), cb)
+    })
+}
@@ -0,0 +1,505 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use async_api::{IndyFuture, from_json, map, parse};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use anoncreds;
+use {WalletHandle, BlobStorageReaderHandle};
+
+/// Credential schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    pub ver: String,
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub attr_names: Vec<String>,
+    /// Sequence number of the schema transaction on the ledger.
+    pub seq_no: Option<u32>,
+}
+
+/// Credential definition. `value` contains public keys of the issuer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialDefinition {
+    pub ver: String,
+    pub id: String,
+    pub schema_id: String,
+    #[serde(rename = "type")]
+    pub signature_type: String,
+    pub tag: String,
+    pub value: Value,
+}
+
+/// Configuration of credential definition creation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CredentialDefinitionConfig {
+    pub support_revocation: bool,
+}
+
+/// Revocation registry definition. `value` contains public keys and tails information.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevocationRegistryDefinition {
+    pub ver: String,
+    pub id: String,
+    pub revoc_def_type: String,
+    pub tag: String,
+    pub cred_def_id: String,
+    pub value: Value,
+}
+
+/// Revocation registry accumulator at the given moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevocationRegistry {
+    pub ver: String,
+    pub value: Value,
+}
+
+/// Changes of revocation registry accumulator between two moments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevocationRegistryDelta {
+    pub ver: String,
+    pub value: Value,
+}
+
+/// Revocation state of the credential used for proving of non-revocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevocationState {
+    pub witness: Value,
+    pub rev_reg: Value,
+    pub timestamp: u64,
+}
+
+/// Credential offer. `key_correctness_proof` is opaque Ursa data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialOffer {
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub key_correctness_proof: Value,
+    pub nonce: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method_name: Option<String>,
+}
+
+/// Credential request. Blinded secrets are opaque Ursa data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialRequest {
+    pub prover_did: String,
+    pub cred_def_id: String,
+    pub blinded_ms: Value,
+    pub blinded_ms_correctness_proof: Value,
+    pub nonce: String,
+}
+
+/// Credential request and its private metadata used for storing of the issued credential.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatedCredentialRequest {
+    pub request: CredentialRequest,
+    pub metadata: Value,
+}
+
+/// Raw and encoded representations of credential attribute.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeValues {
+    pub raw: String,
+    pub encoded: String,
+}
+
+/// Issued credential. Signatures are opaque Ursa data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Credential {
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub rev_reg_id: Option<String>,
+    pub values: HashMap<String, AttributeValues>,
+    pub signature: Value,
+    pub signature_correctness_proof: Value,
+    pub rev_reg: Option<Value>,
+    pub witness: Option<Value>,
+}
+
+/// Result of credential issuance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssuedCredential {
+    pub credential: Credential,
+    /// Local id for revocation info.
+    pub cred_revoc_id: Option<String>,
+    /// Revocation registry delta with a newly issued credential.
+    pub revoc_reg_delta: Option<RevocationRegistryDelta>,
+}
+
+/// Short information about credential stored in the prover wallet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CredentialInfo {
+    pub referent: String,
+    pub attrs: HashMap<String, String>,
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub rev_reg_id: Option<String>,
+    pub cred_rev_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NonRevokedInterval {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AttributeInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub names: Option<Vec<String>>,
+    /// WQL query restricting credentials that can be used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+    /// Encoding scheme the revealed value must follow: "default", "date" or {"decimal": N}.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PredicateInfo {
+    pub name: String,
+    /// One of ">=", ">", "<=", "<".
+    pub p_type: String,
+    /// 32-bit signed integer: 64-bit predicate values are not supported.
+    pub p_value: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EqualityInfo {
+    /// Referents of requested attributes which values must be equal.
+    pub attr_referents: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MembershipInfo {
+    /// Referent of requested attribute which value must belong to the set.
+    pub attr_referent: String,
+    /// Raw values of the set (at most 100). Only revealed value can be checked.
+    pub values: Vec<String>,
+}
+
+/// Proof request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofRequest {
+    pub name: String,
+    pub version: String,
+    pub nonce: String,
+    #[serde(default)]
+    pub requested_attributes: HashMap<String, AttributeInfo>,
+    #[serde(default)]
+    pub requested_predicates: HashMap<String, PredicateInfo>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub requested_equalities: HashMap<String, EqualityInfo>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub requested_memberships: HashMap<String, MembershipInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<NonRevokedInterval>,
+    /// Version of proof request. "1.0" if not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ver: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestedAttribute {
+    pub cred_id: String,
+    pub timestamp: Option<u64>,
+    pub revealed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestedPredicate {
+    pub cred_id: String,
+    pub timestamp: Option<u64>,
+}
+
+/// Credentials chosen by the prover to satisfy proof request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestedCredentials {
+    pub self_attested_attributes: HashMap<String, String>,
+    pub requested_attributes: HashMap<String, RequestedAttribute>,
+    pub requested_predicates: HashMap<String, RequestedPredicate>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Identifier {
+    pub schema_id: String,
+    pub cred_def_id: String,
+    pub rev_reg_id: Option<String>,
+    pub timestamp: Option<u64>,
+}
+
+/// Proof. `proof` is opaque Ursa data, `requested_proof` contains revealed values.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Proof {
+    pub proof: Value,
+    pub requested_proof: Value,
+    pub identifiers: Vec<Identifier>,
+}
+
+/// Create credential schema entity that describes credential attributes list.
+pub fn issuer_create_schema(issuer_did: &str, name: &str, version: &str, attr_names: &[&str]) -> IndyFuture<Schema> {
+    let attr_names = try_json!(&attr_names);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = anoncreds::_issuer_create_schema(command_handle, issuer_did, name, version, &attr_names, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(_, schema_json)| from_json(&schema_json))
+}
+
+/// Create credential definition entity and store its private part in the wallet.
+pub fn issuer_create_and_store_credential_def(wallet_handle: WalletHandle, issuer_did: &str, schema: &Schema, tag: &str,
+                                              signature_type: Option<&str>, config: &CredentialDefinitionConfig) -> IndyFuture<CredentialDefinition> {
+    let schema = try_json!(schema);
+    let config = try_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = anoncreds::_issuer_create_and_store_credential_def(command_handle, wallet_handle, issuer_did, &schema, tag, signature_type, &config, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(_, cred_def_json)| from_json(&cred_def_json))
+}
+
+/// Create credential offer that will be used by prover for credential request creation.
+pub fn issuer_create_credential_offer(wallet_handle: WalletHandle, cred_def_id: &str) -> IndyFuture<CredentialOffer> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_issuer_create_credential_offer(command_handle, wallet_handle, cred_def_id, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Check credential request and create credential for the given values.
+pub fn issuer_create_credential(wallet_handle: WalletHandle,
+                                cred_offer: &CredentialOffer,
+                                cred_req: &CredentialRequest,
+                                cred_values: &HashMap<String, AttributeValues>,
+                                rev_reg_id: Option<&str>,
+                                blob_storage_reader_handle: BlobStorageReaderHandle) -> IndyFuture<IssuedCredential> {
+    let cred_offer = try_json!(cred_offer);
+    let cred_req = try_json!(cred_req);
+    let cred_values = try_json!(cred_values);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string_opt_string();
+
+    let err = anoncreds::_issuer_create_credential(command_handle, wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id, blob_storage_reader_handle, cb);
+
+    map(AsyncResultHandler::str_optstr_optstr(command_handle, err, receiver),
+        |(credential, cred_revoc_id, revoc_reg_delta)| {
+            Ok(IssuedCredential {
+                credential: from_json(&credential)?,
+                cred_revoc_id,
+                revoc_reg_delta: match revoc_reg_delta {
+                    Some(revoc_reg_delta) => Some(from_json(&revoc_reg_delta)?),
+                    None => None
+                },
+            })
+        })
+}
+
+/// Creates a master secret with a given id and stores it in the wallet. Returns id of the master secret.
+pub fn prover_create_master_secret(wallet_handle: WalletHandle, master_secret_id: Option<&str>) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_prover_create_master_secret(command_handle, wallet_handle, master_secret_id, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Creates a credential request for the given credential offer.
+pub fn prover_create_credential_req(wallet_handle: WalletHandle, prover_did: &str, cred_offer: &CredentialOffer,
+                                    cred_def: &CredentialDefinition, master_secret_id: &str) -> IndyFuture<CreatedCredentialRequest> {
+    let cred_offer = try_json!(cred_offer);
+    let cred_def = try_json!(cred_def);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = anoncreds::_prover_create_credential_req(command_handle, wallet_handle, prover_did, &cred_offer, &cred_def, master_secret_id, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(request, metadata)| {
+            Ok(CreatedCredentialRequest {
+                request: from_json(&request)?,
+                metadata: from_json(&metadata)?,
+            })
+        })
+}
+
+/// Check credential provided by issuer for the given credential request and store it in the wallet.
+/// Returns id of the stored credential.
+pub fn prover_store_credential(wallet_handle: WalletHandle, cred_id: Option<&str>, cred_req_metadata: &Value, credential: &Credential,
+                               cred_def: &CredentialDefinition, rev_reg_def: Option<&RevocationRegistryDefinition>) -> IndyFuture<String> {
+    let cred_req_metadata = try_json!(cred_req_metadata);
+    let credential = try_json!(credential);
+    let cred_def = try_json!(cred_def);
+    let rev_reg_def = try_opt_json!(rev_reg_def);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_prover_store_credential(command_handle, wallet_handle, cred_id, &cred_req_metadata, &credential, &cred_def,
+                                                  rev_reg_def.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Gets human readable credential by the given id.
+pub fn prover_get_credential(wallet_handle: WalletHandle, cred_id: &str) -> IndyFuture<CredentialInfo> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_prover_get_credential(command_handle, wallet_handle, cred_id, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Deletes credential by given id.
+pub fn prover_delete_credential(wallet_handle: WalletHandle, cred_id: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = anoncreds::_prover_delete_credential(command_handle, wallet_handle, cred_id, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Gets human readable credentials according to the WQL query.
+pub fn prover_get_credentials(wallet_handle: WalletHandle, query: Option<&Value>) -> IndyFuture<Vec<CredentialInfo>> {
+    let query = try_opt_json!(query);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_prover_get_credentials(command_handle, wallet_handle, query.as_ref().map(String::as_str), cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Creates a proof according to the given proof request.
+///
+/// `rev_states` maps revocation registry id to the states of the registry by timestamp.
+pub fn prover_create_proof(wallet_handle: WalletHandle,
+                           proof_req: &ProofRequest,
+                           requested_credentials: &RequestedCredentials,
+                           master_secret_id: &str,
+                           schemas: &HashMap<String, Schema>,
+                           cred_defs: &HashMap<String, CredentialDefinition>,
+                           rev_states: &HashMap<String, HashMap<u64, RevocationState>>) -> IndyFuture<Proof> {
+    let proof_req = try_json!(proof_req);
+    let requested_credentials = try_json!(requested_credentials);
+    let schemas = try_json!(schemas);
+    let cred_defs = try_json!(cred_defs);
+    let rev_states = try_json!(rev_states);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_prover_create_proof(command_handle, wallet_handle, &proof_req, &requested_credentials, master_secret_id,
+                                              &schemas, &cred_defs, &rev_states, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Verifies a proof (of multiple credential).
+///
+/// `rev_regs` maps revocation registry id to the accumulators of the registry by timestamp.
+pub fn verifier_verify_proof(proof_req: &ProofRequest,
+                             proof: &Proof,
+                             schemas: &HashMap<String, Schema>,
+                             cred_defs: &HashMap<String, CredentialDefinition>,
+                             rev_reg_defs: &HashMap<String, RevocationRegistryDefinition>,
+                             rev_regs: &HashMap<String, HashMap<u64, RevocationRegistry>>) -> IndyFuture<bool> {
+    let proof_req = try_json!(proof_req);
+    let proof = try_json!(proof);
+    let schemas = try_json!(schemas);
+    let cred_defs = try_json!(cred_defs);
+    let rev_reg_defs = try_json!(rev_reg_defs);
+    let rev_regs = try_json!(rev_regs);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = anoncreds::_verifier_verify_proof(command_handle, &proof_req, &proof, &schemas, &cred_defs, &rev_reg_defs, &rev_regs, cb);
+
+    AsyncResultHandler::bool(command_handle, err, receiver)
+}
+
+/// Verifies a proof and returns report of each performed check. See `anoncreds::verifier_verify_proof_with_report`.
+pub fn verifier_verify_proof_with_report(proof_req: &ProofRequest,
+                                         proof: &Proof,
+                                         schemas: &HashMap<String, Schema>,
+                                         cred_defs: &HashMap<String, CredentialDefinition>,
+                                         rev_reg_defs: &HashMap<String, RevocationRegistryDefinition>,
+                                         rev_regs: &HashMap<String, HashMap<u64, RevocationRegistry>>) -> IndyFuture<Value> {
+    let proof_req = try_json!(proof_req);
+    let proof = try_json!(proof);
+    let schemas = try_json!(schemas);
+    let cred_defs = try_json!(cred_defs);
+    let rev_reg_defs = try_json!(rev_reg_defs);
+    let rev_regs = try_json!(rev_regs);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_verifier_verify_proof_with_report(command_handle, &proof_req, &proof, &schemas, &cred_defs, &rev_reg_defs, &rev_regs, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Create revocation state for a credential in the particular time moment.
+pub fn create_revocation_state(blob_storage_reader_handle: BlobStorageReaderHandle, rev_reg_def: &RevocationRegistryDefinition,
+                               rev_reg_delta: &RevocationRegistryDelta, timestamp: u64, cred_rev_id: &str) -> IndyFuture<RevocationState> {
+    let rev_reg_def = try_json!(rev_reg_def);
+    let rev_reg_delta = try_json!(rev_reg_delta);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_create_revocation_state(command_handle, blob_storage_reader_handle, &rev_reg_def, &rev_reg_delta, timestamp, cred_rev_id, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Generates 80-bit numbers that can be used as a nonce for proof request.
+pub fn generate_nonce() -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_generate_nonce(command_handle, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Encodes raw attribute values by standardized schemes. See `anoncreds::encode_credential_values`.
+pub fn encode_credential_values(cred_values: &Value) -> IndyFuture<HashMap<String, AttributeValues>> {
+    let cred_values = try_json!(cred_values);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = anoncreds::_encode_credential_values(command_handle, &cred_values, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_deserialization_works() {
+        let schema: Schema = ::serde_json::from_str(r#"{"ver":"1.0","id":"NcYxiDXkpYi6ov5FcYDi1e:2:gvt:1.0","name":"gvt","version":"1.0","attrNames":["age","name"],"seqNo":null}"#).unwrap();
+
+        assert_eq!("gvt", schema.name);
+        assert_eq!(vec!["age".to_string(), "name".to_string()], schema.attr_names);
+        assert_eq!(None, schema.seq_no);
+    }
+}
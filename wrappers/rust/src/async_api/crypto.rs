@@ -0,0 +1,179 @@
+use async_api::{IndyFuture, from_json, map};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use crypto;
+use WalletHandle;
+
+/// Information for creation of a new key pair.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyConfig {
+    /// Seed that allows deterministic key creation. Can be UTF-8, base64 or hex string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    /// Crypto type of the key: ed25519 (default), secp256k1 or p256.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto_type: Option<String>,
+    /// Name of registered key manager to hold the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_manager: Option<String>,
+}
+
+/// Message decrypted by `auth_decrypt`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthDecryptedMessage {
+    pub sender_verkey: String,
+    pub message: Vec<u8>,
+}
+
+/// Message unpacked by `unpack_message`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnpackedMessage {
+    pub message: String,
+    pub recipient_verkey: String,
+    /// Absent for anoncrypted messages.
+    pub sender_verkey: Option<String>,
+}
+
+/// Options of `pack_message_v2`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackOptionsV2 {
+    /// Content encryption algorithm: XC20P (default) or A256GCM.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enc: Option<String>,
+}
+
+/// Message unpacked by `unpack_message_v2`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnpackedMessageV2 {
+    pub message: String,
+    pub recipient_kid: String,
+    /// Absent for anoncrypted messages.
+    pub sender_kid: Option<String>,
+}
+
+/// Creates key pair in the wallet. Returns verkey of generated key pair.
+pub fn create_key(wallet_handle: WalletHandle, config: Option<&KeyConfig>) -> IndyFuture<String> {
+    let config = try_opt_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = crypto::_create_key(command_handle, wallet_handle, config.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Saves metadata for the given key.
+pub fn set_key_metadata(wallet_handle: WalletHandle, verkey: &str, metadata: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = crypto::_set_key_metadata(command_handle, wallet_handle, verkey, metadata, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Retrieves metadata for the given key.
+pub fn get_key_metadata(wallet_handle: WalletHandle, verkey: &str) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = crypto::_get_key_metadata(command_handle, wallet_handle, verkey, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Signs a message with a key stored in the wallet.
+pub fn sign(wallet_handle: WalletHandle, signer_vk: &str, message: &[u8]) -> IndyFuture<Vec<u8>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_sign(command_handle, wallet_handle, signer_vk, message, cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Verifies a message signature.
+pub fn verify(signer_vk: &str, message: &[u8], signature: &[u8]) -> IndyFuture<bool> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = crypto::_verify(command_handle, signer_vk, message, signature, cb);
+
+    AsyncResultHandler::bool(command_handle, err, receiver)
+}
+
+/// Encrypts a message by authenticated-encryption scheme.
+pub fn auth_crypt(wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8]) -> IndyFuture<Vec<u8>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_auth_crypt(command_handle, wallet_handle, sender_vk, recipient_vk, message, cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Decrypts a message by authenticated-encryption scheme.
+pub fn auth_decrypt(wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8]) -> IndyFuture<AuthDecryptedMessage> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_slice();
+
+    let err = crypto::_auth_decrypt(command_handle, wallet_handle, recipient_vk, encrypted_message, cb);
+
+    map(AsyncResultHandler::str_slice(command_handle, err, receiver),
+        |(sender_verkey, message)| Ok(AuthDecryptedMessage { sender_verkey, message }))
+}
+
+/// Encrypts a message by anonymous-encryption scheme.
+pub fn anon_crypt(recipient_vk: &str, message: &[u8]) -> IndyFuture<Vec<u8>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_anon_crypt(command_handle, recipient_vk, message, cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Decrypts a message by anonymous-encryption scheme.
+pub fn anon_decrypt(wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8]) -> IndyFuture<Vec<u8>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_anon_decrypt(command_handle, wallet_handle, recipient_vk, encrypted_message, cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Packs a message into JWE. Message is anoncrypted if `sender_vk` is not set.
+pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys: &[&str], sender_vk: Option<&str>) -> IndyFuture<Vec<u8>> {
+    let receiver_keys = try_json!(&receiver_keys);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_pack_message(command_handle, wallet_handle, message, &receiver_keys, sender_vk, cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Unpacks JWE created by `pack_message`.
+pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> IndyFuture<UnpackedMessage> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_unpack_message(command_handle, wallet_handle, jwe, cb);
+
+    map(AsyncResultHandler::slice(command_handle, err, receiver),
+        |message| from_json(&String::from_utf8_lossy(&message)))
+}
+
+/// Packs a message into DIDComm v2 JWE. Message is anoncrypted if `sender_kid` is not set.
+pub fn pack_message_v2(wallet_handle: WalletHandle, message: &[u8], receiver_kids: &[&str], sender_kid: Option<&str>, options: Option<&PackOptionsV2>) -> IndyFuture<Vec<u8>> {
+    let receiver_kids = try_json!(&receiver_kids);
+    let options = try_opt_json!(options);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_pack_message_v2(command_handle, wallet_handle, message, &receiver_kids, sender_kid, options.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Unpacks DIDComm v2 JWE created by `pack_message_v2`.
+pub fn unpack_message_v2(wallet_handle: WalletHandle, jwe: &[u8]) -> IndyFuture<UnpackedMessageV2> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = crypto::_unpack_message_v2(command_handle, wallet_handle, jwe, cb);
+
+    map(AsyncResultHandler::slice(command_handle, err, receiver),
+        |message| from_json(&String::from_utf8_lossy(&message)))
+}
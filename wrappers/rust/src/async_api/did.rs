@@ -0,0 +1,221 @@
+use async_api::{IndyFuture, map, parse};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use did;
+use {WalletHandle, PoolHandle};
+
+/// Information for creation of a new DID owned by the caller.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DidConfig {
+    /// DID to create. If not set it is derived from the verkey.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did: Option<String>,
+    /// Seed that allows deterministic key creation. Can be UTF-8, base64 or hex string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    /// Crypto type of the key: ed25519 (default), secp256k1 or p256.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto_type: Option<String>,
+    /// Use full verkey as a DID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cid: Option<bool>,
+    /// Method name to create fully qualified DID. `key` and `peer` create self-certifying DIDs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method_name: Option<String>,
+    /// DIDComm service to embed into `did:peer` DID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<DidService>,
+    /// Name of registered key manager to hold the key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_manager: Option<String>,
+}
+
+/// DIDComm service endpoint of `did:peer` DID.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidService {
+    pub service_endpoint: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routing_keys: Vec<String>,
+    #[serde(default)]
+    pub accept: Vec<String>,
+}
+
+/// Identity of other party.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TheirDidInfo {
+    pub did: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verkey: Option<String>,
+}
+
+/// Key information for DID keys rotation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdentityKeyConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crypto_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_manager: Option<String>,
+}
+
+/// DID and its verification key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DidWithVerkey {
+    pub did: String,
+    pub verkey: String,
+}
+
+/// DID stored in the wallet with its metadata.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidWithMetadata {
+    pub did: String,
+    pub verkey: String,
+    pub temp_verkey: Option<String>,
+    pub metadata: Option<String>,
+}
+
+/// Endpoint of the DID.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Endpoint {
+    pub address: String,
+    pub transport_key: Option<String>,
+}
+
+/// Creates keys for a new DID and stores them in the wallet.
+///
+/// See `did::create_and_store_my_did` for details.
+pub fn create_and_store_my_did(wallet_handle: WalletHandle, config: &DidConfig) -> IndyFuture<DidWithVerkey> {
+    let config = try_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = did::_create_and_store_my_did(command_handle, wallet_handle, &config, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(did, verkey)| Ok(DidWithVerkey { did, verkey }))
+}
+
+/// Generates temporary keys for an existing DID. Returns new verkey.
+pub fn replace_keys_start(wallet_handle: WalletHandle, did: &str, config: &IdentityKeyConfig) -> IndyFuture<String> {
+    let config = try_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_replace_keys_start(command_handle, wallet_handle, did, &config, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Applies temporary keys as main for an existing DID.
+pub fn replace_keys_apply(wallet_handle: WalletHandle, did: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = did::_replace_keys_apply(command_handle, wallet_handle, did, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Saves their DID for a pairwise connection in the wallet.
+pub fn store_their_did(wallet_handle: WalletHandle, identity: &TheirDidInfo) -> IndyFuture<()> {
+    let identity = try_json!(identity);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = did::_store_their_did(command_handle, wallet_handle, &identity, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Returns ver key for the given DID. Resolves it on the ledger if needed.
+pub fn key_for_did(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_key_for_did(command_handle, pool_handle, wallet_handle, did, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Returns ver key for the given DID stored in the wallet.
+pub fn key_for_local_did(wallet_handle: WalletHandle, did: &str) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_key_for_local_did(command_handle, wallet_handle, did, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Sets endpoint information for the given DID.
+pub fn set_endpoint_for_did(wallet_handle: WalletHandle, did: &str, address: &str, transport_key: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = did::_set_endpoint_for_did(command_handle, wallet_handle, did, address, transport_key, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Returns endpoint information for the given DID.
+pub fn get_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str) -> IndyFuture<Endpoint> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string();
+
+    let err = did::_get_endpoint_for_did(command_handle, wallet_handle, pool_handle, did, cb);
+
+    map(AsyncResultHandler::str_optstr(command_handle, err, receiver),
+        |(address, transport_key)| Ok(Endpoint { address, transport_key }))
+}
+
+/// Saves metadata for the given DID.
+pub fn set_did_metadata(wallet_handle: WalletHandle, did: &str, metadata: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = did::_set_did_metadata(command_handle, wallet_handle, did, metadata, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Retrieves metadata for the given DID.
+pub fn get_did_metadata(wallet_handle: WalletHandle, did: &str) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_get_did_metadata(command_handle, wallet_handle, did, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Retrieves information about the given DID stored in the wallet.
+pub fn get_my_did_with_metadata(wallet_handle: WalletHandle, my_did: &str) -> IndyFuture<DidWithMetadata> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_get_my_did_with_metadata(command_handle, wallet_handle, my_did, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Retrieves information about all DIDs stored in the wallet.
+pub fn list_my_dids_with_metadata(wallet_handle: WalletHandle) -> IndyFuture<Vec<DidWithMetadata>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_list_my_dids_with_metadata(command_handle, wallet_handle, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Retrieves abbreviated verkey if it is possible otherwise return full verkey.
+pub fn abbreviate_verkey(did: &str, verkey: &str) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_abbreviate_verkey(command_handle, did, verkey, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Updates DID stored in the wallet to make it fully qualified.
+pub fn qualify_did(wallet_handle: WalletHandle, did: &str, method: &str) -> IndyFuture<String> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = did::_qualify_did(command_handle, wallet_handle, did, method, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
@@ -0,0 +1,366 @@
+use serde_json::{Map, Value};
+
+use async_api::{IndyFuture, from_json, map, parse};
+use async_api::anoncreds::{Schema, CredentialDefinition, RevocationRegistryDefinition, RevocationRegistry, RevocationRegistryDelta};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use ledger;
+use {WalletHandle, PoolHandle};
+
+/// Ledger request built by Libindy.
+///
+/// Fields not covered by the structure (signatures, endorser, transaction author agreement acceptance, etc.)
+/// are kept in `extra`, so the request can be passed back to Libindy without losses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerRequest {
+    pub req_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    pub operation: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<usize>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Reply of the pool to submitted request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerReply {
+    /// "REPLY" for processed request, "REQNACK" or "REJECT" otherwise.
+    pub op: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl LedgerReply {
+    pub fn is_reply(&self) -> bool {
+        self.op == "REPLY"
+    }
+}
+
+/// NYM transaction data parsed from GET_NYM reply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NymData {
+    pub did: String,
+    pub verkey: Option<String>,
+    pub role: Option<String>,
+}
+
+/// Metadata of the ledger reply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseMetadata {
+    pub seq_no: Option<u64>,
+    pub txn_time: Option<u64>,
+    pub last_seq_no: Option<u64>,
+    pub last_txn_time: Option<u64>,
+}
+
+/// Revocation registry accumulator parsed from GET_REVOC_REG reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedRevocationRegistry {
+    pub id: String,
+    pub revoc_reg: RevocationRegistry,
+    pub timestamp: u64,
+}
+
+/// Revocation registry delta parsed from GET_REVOC_REG_DELTA reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedRevocationRegistryDelta {
+    pub id: String,
+    pub revoc_reg_delta: RevocationRegistryDelta,
+    pub timestamp: u64,
+}
+
+/// Signs and submits request message to validator pool.
+pub fn sign_and_submit_request(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, request: &LedgerRequest) -> IndyFuture<LedgerReply> {
+    let request = try_json!(request);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_sign_and_submit_request(command_handle, pool_handle, wallet_handle, submitter_did, &request, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Publishes request message to validator pool (no signing, unlike `sign_and_submit_request`).
+pub fn submit_request(pool_handle: PoolHandle, request: &LedgerRequest) -> IndyFuture<LedgerReply> {
+    let request = try_json!(request);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_submit_request(command_handle, pool_handle, &request, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Signs request message.
+pub fn sign_request(wallet_handle: WalletHandle, submitter_did: &str, request: &LedgerRequest) -> IndyFuture<LedgerRequest> {
+    let request = try_json!(request);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_sign_request(command_handle, wallet_handle, submitter_did, &request, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Multi signs request message.
+pub fn multi_sign_request(wallet_handle: WalletHandle, submitter_did: &str, request: &LedgerRequest) -> IndyFuture<LedgerRequest> {
+    let request = try_json!(request);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_multi_sign_request(command_handle, wallet_handle, submitter_did, &request, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a NYM request.
+pub fn build_nym_request(submitter_did: &str, target_did: &str, verkey: Option<&str>, alias: Option<&str>, role: Option<&str>) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_nym_request(command_handle, submitter_did, target_did, verkey, alias, role, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_NYM request.
+pub fn build_get_nym_request(submitter_did: Option<&str>, target_did: &str) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_nym_request(command_handle, submitter_did, target_did, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses a GET_NYM reply to get NYM data.
+pub fn parse_get_nym_response(reply: &LedgerReply) -> IndyFuture<NymData> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_parse_get_nym_response(command_handle, &reply, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_TXN request.
+pub fn build_get_txn_request(submitter_did: Option<&str>, ledger_type: Option<&str>, seq_no: i32) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_txn_request(command_handle, submitter_did, ledger_type, seq_no, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds an ATTRIB request. One of `hash`, `raw` or `enc` must be set.
+pub fn build_attrib_request(submitter_did: &str, target_did: &str, hash: Option<&str>, raw: Option<&Value>, enc: Option<&str>) -> IndyFuture<LedgerRequest> {
+    let raw = try_opt_json!(raw);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_attrib_request(command_handle, submitter_did, target_did, hash, raw.as_ref().map(String::as_str), enc, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_ATTRIB request. One of `raw`, `hash` or `enc` must be set.
+pub fn build_get_attrib_request(submitter_did: Option<&str>, target_did: &str, raw: Option<&str>, hash: Option<&str>, enc: Option<&str>) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_attrib_request(command_handle, submitter_did, target_did, raw, hash, enc, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a SCHEMA request.
+pub fn build_schema_request(submitter_did: &str, schema: &Schema) -> IndyFuture<LedgerRequest> {
+    let schema = try_json!(schema);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_schema_request(command_handle, submitter_did, &schema, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_SCHEMA request.
+pub fn build_get_schema_request(submitter_did: Option<&str>, id: &str) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_schema_request(command_handle, submitter_did, id, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses a GET_SCHEMA reply to get schema.
+pub fn parse_get_schema_response(reply: &LedgerReply) -> IndyFuture<Schema> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = ledger::_parse_get_schema_response(command_handle, &reply, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(_, schema)| from_json(&schema))
+}
+
+/// Builds a CRED_DEF request.
+pub fn build_cred_def_request(submitter_did: &str, cred_def: &CredentialDefinition) -> IndyFuture<LedgerRequest> {
+    let cred_def = try_json!(cred_def);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_cred_def_request(command_handle, submitter_did, &cred_def, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_CRED_DEF request.
+pub fn build_get_cred_def_request(submitter_did: Option<&str>, id: &str) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_cred_def_request(command_handle, submitter_did, id, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses a GET_CRED_DEF reply to get credential definition.
+pub fn parse_get_cred_def_response(reply: &LedgerReply) -> IndyFuture<CredentialDefinition> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = ledger::_parse_get_cred_def_response(command_handle, &reply, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(_, cred_def)| from_json(&cred_def))
+}
+
+/// Builds a REVOC_REG_DEF request.
+pub fn build_revoc_reg_def_request(submitter_did: &str, revoc_reg_def: &RevocationRegistryDefinition) -> IndyFuture<LedgerRequest> {
+    let revoc_reg_def = try_json!(revoc_reg_def);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_revoc_reg_def_request(command_handle, submitter_did, &revoc_reg_def, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_REVOC_REG_DEF request.
+pub fn build_get_revoc_reg_def_request(submitter_did: Option<&str>, id: &str) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_revoc_reg_def_request(command_handle, submitter_did, id, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses a GET_REVOC_REG_DEF reply to get revocation registry definition.
+pub fn parse_get_revoc_reg_def_response(reply: &LedgerReply) -> IndyFuture<RevocationRegistryDefinition> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = ledger::_parse_get_revoc_reg_def_response(command_handle, &reply, cb);
+
+    map(AsyncResultHandler::str_str(command_handle, err, receiver),
+        |(_, revoc_reg_def)| from_json(&revoc_reg_def))
+}
+
+/// Builds a REVOC_REG_ENTRY request.
+pub fn build_revoc_reg_entry_request(submitter_did: &str, revoc_reg_def_id: &str, rev_def_type: &str,
+                                     revoc_reg_delta: &RevocationRegistryDelta) -> IndyFuture<LedgerRequest> {
+    let revoc_reg_delta = try_json!(revoc_reg_delta);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_revoc_reg_entry_request(command_handle, submitter_did, revoc_reg_def_id, rev_def_type, &revoc_reg_delta, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds a GET_REVOC_REG request.
+pub fn build_get_revoc_reg_request(submitter_did: Option<&str>, revoc_reg_def_id: &str, timestamp: i64) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_revoc_reg_request(command_handle, submitter_did, revoc_reg_def_id, timestamp, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses a GET_REVOC_REG reply to get revocation registry accumulator.
+pub fn parse_get_revoc_reg_response(reply: &LedgerReply) -> IndyFuture<ParsedRevocationRegistry> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string_u64();
+
+    let err = ledger::_parse_get_revoc_reg_response(command_handle, &reply, cb);
+
+    map(AsyncResultHandler::str_str_u64(command_handle, err, receiver),
+        |(id, revoc_reg, timestamp)| Ok(ParsedRevocationRegistry { id, revoc_reg: from_json(&revoc_reg)?, timestamp }))
+}
+
+/// Builds a GET_REVOC_REG_DELTA request.
+pub fn build_get_revoc_reg_delta_request(submitter_did: Option<&str>, revoc_reg_def_id: &str, from: i64, to: i64) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_build_get_revoc_reg_delta_request(command_handle, submitter_did, revoc_reg_def_id, from, to, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses a GET_REVOC_REG_DELTA reply to get revocation registry delta.
+pub fn parse_get_revoc_reg_delta_response(reply: &LedgerReply) -> IndyFuture<ParsedRevocationRegistryDelta> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string_u64();
+
+    let err = ledger::_parse_get_revoc_reg_delta_response(command_handle, &reply, cb);
+
+    map(AsyncResultHandler::str_str_u64(command_handle, err, receiver),
+        |(id, revoc_reg_delta, timestamp)| Ok(ParsedRevocationRegistryDelta { id, revoc_reg_delta: from_json(&revoc_reg_delta)?, timestamp }))
+}
+
+/// Parses a reply to get its metadata.
+pub fn get_response_metadata(reply: &LedgerReply) -> IndyFuture<ResponseMetadata> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = ledger::_get_response_metadata(command_handle, &reply, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ledger_request_roundtrip_keeps_unknown_fields() {
+        let request_json = r#"{"reqId":1,"identifier":"V4SGRU86Z58d6TV7PBUe6f","operation":{"type":"105","dest":"V4SGRU86Z58d6TV7PBUe6f"},"protocolVersion":2,"signature":"sig"}"#;
+
+        let request: LedgerRequest = ::serde_json::from_str(request_json).unwrap();
+
+        assert_eq!(1, request.req_id);
+        assert_eq!("sig", request.extra["signature"]);
+        assert_eq!(::serde_json::from_str::<Value>(request_json).unwrap(), ::serde_json::to_value(&request).unwrap());
+    }
+
+    #[test]
+    fn ledger_reply_deserialization_works_for_reqnack() {
+        let reply: LedgerReply = ::serde_json::from_str(r#"{"op":"REQNACK","reqId":1,"reason":"some reason"}"#).unwrap();
+
+        assert!(!reply.is_reply());
+        assert_eq!(Some("some reason".to_string()), reply.reason);
+        assert_eq!(None, reply.result);
+    }
+}
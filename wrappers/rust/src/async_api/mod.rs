@@ -0,0 +1,111 @@
+//! Futures 0.3 API of Libindy.
+//!
+//! Functions of this module return `IndyFuture` that implements `std::future::Future`,
+//! so they can be `.await`-ed from async code and spawned on any modern executor (e.g. tokio).
+//! Instead of raw JSON strings functions accept and return typed structures that are
+//! serialized to (and parsed from) JSON accepted by Libindy.
+//!
+//! The module is available with `async_api` feature enabled.
+//! It uses the same callback bridge to `indy-sys` as the futures 0.1 API of this crate.
+
+use std::future::Future;
+use std::pin::Pin;
+
+use futures03::future::ready;
+use futures03::FutureExt;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use {ErrorCode, IndyError};
+
+/// Future returned by functions of `async_api` module.
+pub type IndyFuture<T> = Pin<Box<dyn Future<Output=Result<T, IndyError>> + Send>>;
+
+macro_rules! try_json {
+    ($value:expr) => {
+        match ::async_api::to_json($value) {
+            Ok(json) => json,
+            Err(err) => return ::async_api::fail(err)
+        }
+    }
+}
+
+macro_rules! try_opt_json {
+    ($value:expr) => {
+        match $value.map(::async_api::to_json) {
+            Some(Ok(json)) => Some(json),
+            Some(Err(err)) => return ::async_api::fail(err),
+            None => None
+        }
+    }
+}
+
+pub mod anoncreds;
+pub mod crypto;
+pub mod did;
+pub mod ledger;
+pub mod non_secrets;
+pub mod payments;
+pub mod pool;
+pub mod wallet;
+
+pub(crate) fn to_json<T: Serialize>(value: &T) -> Result<String, IndyError> {
+    ::serde_json::to_string(value)
+        .map_err(|err| _invalid_structure(format!("Unable to serialize value: {}", err)))
+}
+
+pub(crate) fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, IndyError> {
+    ::serde_json::from_str(json)
+        .map_err(|err| _invalid_structure(format!("Unable to parse Libindy result: {}", err)))
+}
+
+pub(crate) fn fail<T: Send + 'static>(err: IndyError) -> IndyFuture<T> {
+    Box::pin(ready(Err(err)))
+}
+
+/// Parses JSON result of the future into `T`.
+pub(crate) fn parse<T: DeserializeOwned + Send + 'static>(future: IndyFuture<String>) -> IndyFuture<T> {
+    map(future, |json| from_json(&json))
+}
+
+pub(crate) fn map<T, R, F>(future: IndyFuture<T>, f: F) -> IndyFuture<R>
+    where T: Send + 'static, R: Send + 'static, F: FnOnce(T) -> Result<R, IndyError> + Send + 'static {
+    Box::pin(future.map(|res| res.and_then(f)))
+}
+
+fn _invalid_structure(message: String) -> IndyError {
+    IndyError {
+        error_code: ErrorCode::CommonInvalidStructure,
+        message,
+        indy_backtrace: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Value {
+        field: String
+    }
+
+    #[test]
+    fn parse_works() {
+        let future: IndyFuture<String> = Box::pin(ready(Ok(r#"{"field":"value"}"#.to_string())));
+
+        let value: Value = futures03::executor::block_on(parse(future)).unwrap();
+
+        assert_eq!(Value { field: "value".to_string() }, value);
+    }
+
+    #[test]
+    fn parse_works_for_invalid_json() {
+        let future: IndyFuture<String> = Box::pin(ready(Ok(r#"{"other":"value"}"#.to_string())));
+
+        let err = futures03::executor::block_on(parse::<Value>(future)).unwrap_err();
+
+        assert_eq!(ErrorCode::CommonInvalidStructure, err.error_code);
+    }
+}
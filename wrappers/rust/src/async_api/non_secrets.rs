@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use async_api::{IndyFuture, parse};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use wallet;
+use {WalletHandle, SearchHandle};
+
+/// Tags of wallet record. Names starting with "~" are stored un-encrypted.
+pub type Tags = HashMap<String, String>;
+
+/// Options of fetching a single wallet record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordOptions {
+    pub retrieve_type: bool,
+    pub retrieve_value: bool,
+    pub retrieve_tags: bool,
+}
+
+impl Default for RecordOptions {
+    fn default() -> RecordOptions {
+        RecordOptions {
+            retrieve_type: false,
+            retrieve_value: true,
+            retrieve_tags: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortBy {
+    Id,
+    TagName(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Options of wallet records search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchOptions {
+    pub retrieve_records: bool,
+    pub retrieve_total_count: bool,
+    pub retrieve_type: bool,
+    pub retrieve_value: bool,
+    pub retrieve_tags: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_by: Option<SortBy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_order: Option<SortOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> SearchOptions {
+        SearchOptions {
+            retrieve_records: true,
+            retrieve_total_count: false,
+            retrieve_type: false,
+            retrieve_value: true,
+            retrieve_tags: false,
+            sort_by: None,
+            sort_order: None,
+            offset: None,
+            limit: None,
+        }
+    }
+}
+
+/// Wallet record. Optional fields are present only if requested by options.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletRecord {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub type_: Option<String>,
+    pub value: Option<String>,
+    pub tags: Option<Tags>,
+}
+
+/// Batch of wallet records fetched from the search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRecords {
+    pub total_count: Option<usize>,
+    pub records: Option<Vec<WalletRecord>>,
+}
+
+/// Create a new non-secret record in the wallet.
+pub fn add_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, value: &str, tags: Option<&Tags>) -> IndyFuture<()> {
+    let tags = try_opt_json!(tags);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_add_wallet_record(command_handle, wallet_handle, type_, id, value, tags.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Update a non-secret wallet record value.
+pub fn update_wallet_record_value(wallet_handle: WalletHandle, type_: &str, id: &str, value: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_update_wallet_record_value(command_handle, wallet_handle, type_, id, value, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Update a non-secret wallet record tags.
+pub fn update_wallet_record_tags(wallet_handle: WalletHandle, type_: &str, id: &str, tags: &Tags) -> IndyFuture<()> {
+    let tags = try_json!(tags);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_update_wallet_record_tags(command_handle, wallet_handle, type_, id, &tags, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Add new tags to the wallet record.
+pub fn add_wallet_record_tags(wallet_handle: WalletHandle, type_: &str, id: &str, tags: &Tags) -> IndyFuture<()> {
+    let tags = try_json!(tags);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_add_wallet_record_tags(command_handle, wallet_handle, type_, id, &tags, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Delete tags from the wallet record.
+pub fn delete_wallet_record_tags(wallet_handle: WalletHandle, type_: &str, id: &str, tag_names: &[&str]) -> IndyFuture<()> {
+    let tag_names = try_json!(&tag_names);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_delete_wallet_record_tags(command_handle, wallet_handle, type_, id, &tag_names, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Delete an existing wallet record in the wallet.
+pub fn delete_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_delete_wallet_record(command_handle, wallet_handle, type_, id, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Get a wallet record by id.
+pub fn get_wallet_record(wallet_handle: WalletHandle, type_: &str, id: &str, options: &RecordOptions) -> IndyFuture<WalletRecord> {
+    let options = try_json!(options);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = wallet::_get_wallet_record(command_handle, wallet_handle, type_, id, &options, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Search for wallet records by MongoDB style query to record tags.
+///
+/// See `wallet::open_wallet_search` for query language details.
+pub fn open_wallet_search(wallet_handle: WalletHandle, type_: &str, query: &Value, options: &SearchOptions) -> IndyFuture<SearchHandle> {
+    let query = try_json!(query);
+    let options = try_json!(options);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = wallet::_open_wallet_search(command_handle, wallet_handle, type_, &query, &options, cb);
+
+    AsyncResultHandler::handle(command_handle, err, receiver)
+}
+
+/// Fetch next records for wallet search.
+pub fn fetch_wallet_search_next_records(wallet_handle: WalletHandle, wallet_search_handle: SearchHandle, count: usize) -> IndyFuture<SearchRecords> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = wallet::_fetch_wallet_search_next_records(command_handle, wallet_handle, wallet_search_handle, count, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Close wallet search (make search handle invalid).
+pub fn close_wallet_search(wallet_search_handle: SearchHandle) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_close_wallet_search(command_handle, wallet_search_handle, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn search_options_serialization_works_for_sort_by_tag() {
+        let options = SearchOptions {
+            sort_by: Some(SortBy::TagName("~tag".to_string())),
+            sort_order: Some(SortOrder::Desc),
+            ..SearchOptions::default()
+        };
+
+        assert_eq!(json!({
+            "retrieveRecords": true,
+            "retrieveTotalCount": false,
+            "retrieveType": false,
+            "retrieveValue": true,
+            "retrieveTags": false,
+            "sortBy": {"tagName": "~tag"},
+            "sortOrder": "desc"
+        }), ::serde_json::to_value(&options).unwrap());
+    }
+
+    #[test]
+    fn search_records_deserialization_works() {
+        let records: SearchRecords = ::serde_json::from_str(r#"{"totalCount":null,"records":[{"type":null,"id":"id","value":"value","tags":null}]}"#).unwrap();
+
+        assert_eq!(None, records.total_count);
+        assert_eq!("value", records.records.unwrap()[0].value.as_ref().unwrap());
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use async_api::{IndyFuture, from_json, map, parse};
+use async_api::ledger::{LedgerRequest, LedgerReply};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use payments;
+use WalletHandle;
+
+/// Configuration of payment address creation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PaymentAddressConfig {
+    /// Seed that allows deterministic creation of payment address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+}
+
+/// Output of payment transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentOutput {
+    /// Payment address of recipient.
+    pub recipient: String,
+    /// Amount of tokens to transfer to this payment address.
+    pub amount: u64,
+}
+
+/// Payment source (UTXO) owned by the payment address.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentSource {
+    pub source: String,
+    pub payment_address: String,
+    pub amount: u64,
+    pub extra: Option<String>,
+}
+
+/// Page of payment sources.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentSources {
+    pub sources: Vec<PaymentSource>,
+    /// Pointer to the next slice of payment sources if any.
+    pub next: Option<i64>,
+}
+
+/// Receipt of payment transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentReceipt {
+    /// Receipt that can be used for payment referencing and verification.
+    pub receipt: String,
+    /// Payment address of recipient.
+    pub recipient: String,
+    pub amount: u64,
+    pub extra: Option<String>,
+}
+
+/// Ledger request built by payment method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentRequest {
+    pub request: LedgerRequest,
+    pub payment_method: String,
+}
+
+/// Fees for ledger transactions: transaction type to amount.
+pub type Fees = HashMap<String, u64>;
+
+/// Create the payment address for specified payment method.
+pub fn create_payment_address(wallet_handle: WalletHandle, payment_method: &str, config: &PaymentAddressConfig) -> IndyFuture<String> {
+    let config = try_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_create_payment_address(command_handle, wallet_handle, payment_method, &config, cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+/// Lists all payment addresses that are stored in the wallet.
+pub fn list_payment_addresses(wallet_handle: WalletHandle) -> IndyFuture<Vec<String>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_list_payment_addresses(command_handle, wallet_handle, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Modifies Indy request by adding information how to pay fees for this transaction.
+pub fn add_request_fees(wallet_handle: WalletHandle, submitter_did: Option<&str>, request: &LedgerRequest,
+                        inputs: &[&str], outputs: &[PaymentOutput], extra: Option<&Value>) -> IndyFuture<PaymentRequest> {
+    let request = try_json!(request);
+    let inputs = try_json!(&inputs);
+    let outputs = try_json!(&outputs);
+    let extra = try_opt_json!(extra);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = payments::_add_request_fees(command_handle, wallet_handle, submitter_did, &request, &inputs, &outputs, extra.as_ref().map(String::as_str), cb);
+
+    _payment_request(AsyncResultHandler::str_str(command_handle, err, receiver))
+}
+
+/// Parses response for Indy request with fees.
+pub fn parse_response_with_fees(payment_method: &str, reply: &LedgerReply) -> IndyFuture<Vec<PaymentReceipt>> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_parse_response_with_fees(command_handle, payment_method, &reply, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds Indy request for getting sources list for payment address.
+pub fn build_get_payment_sources_with_from_request(wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_address: &str, from: Option<i64>) -> IndyFuture<PaymentRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = payments::_build_get_payment_sources_with_from_request(command_handle, wallet_handle, submitter_did, payment_address, from, cb);
+
+    _payment_request(AsyncResultHandler::str_str(command_handle, err, receiver))
+}
+
+/// Parses response for Indy request for getting sources list.
+pub fn parse_get_payment_sources_with_from_response(payment_method: &str, reply: &LedgerReply) -> IndyFuture<PaymentSources> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_i64();
+
+    let err = payments::_parse_get_payment_sources_with_from_response(command_handle, payment_method, &reply, cb);
+
+    map(AsyncResultHandler::str_i64(command_handle, err, receiver),
+        |(sources, next)| Ok(PaymentSources { sources: from_json(&sources)?, next: if next >= 0 { Some(next) } else { None } }))
+}
+
+/// Builds Indy request for doing payment.
+pub fn build_payment_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, inputs: &[&str], outputs: &[PaymentOutput], extra: Option<&Value>) -> IndyFuture<PaymentRequest> {
+    let inputs = try_json!(&inputs);
+    let outputs = try_json!(&outputs);
+    let extra = try_opt_json!(extra);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = payments::_build_payment_req(command_handle, wallet_handle, submitter_did, &inputs, &outputs, extra.as_ref().map(String::as_str), cb);
+
+    _payment_request(AsyncResultHandler::str_str(command_handle, err, receiver))
+}
+
+/// Parses response for Indy request for payment txn.
+pub fn parse_payment_response(payment_method: &str, reply: &LedgerReply) -> IndyFuture<Vec<PaymentReceipt>> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_parse_payment_response(command_handle, payment_method, &reply, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds Indy request for doing minting.
+pub fn build_mint_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, outputs: &[PaymentOutput], extra: Option<&Value>) -> IndyFuture<PaymentRequest> {
+    let outputs = try_json!(&outputs);
+    let extra = try_opt_json!(extra);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = payments::_build_mint_req(command_handle, wallet_handle, submitter_did, &outputs, extra.as_ref().map(String::as_str), cb);
+
+    _payment_request(AsyncResultHandler::str_str(command_handle, err, receiver))
+}
+
+/// Builds Indy request for setting fees for transactions in the ledger.
+pub fn build_set_txn_fees_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str, fees: &Fees) -> IndyFuture<LedgerRequest> {
+    let fees = try_json!(fees);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_build_set_txn_fees_req(command_handle, wallet_handle, submitter_did, payment_method, &fees, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Builds Indy request for getting fees for transactions in the ledger.
+pub fn build_get_txn_fees_req(wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str) -> IndyFuture<LedgerRequest> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_build_get_txn_fees_req(command_handle, wallet_handle, submitter_did, payment_method, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Parses response for Indy request for getting fees.
+pub fn parse_get_txn_fees_response(payment_method: &str, reply: &LedgerReply) -> IndyFuture<Fees> {
+    let reply = try_json!(reply);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = payments::_parse_get_txn_fees_response(command_handle, payment_method, &reply, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Signs a message with a payment address.
+pub fn sign_with_address(wallet_handle: WalletHandle, address: &str, message: &[u8]) -> IndyFuture<Vec<u8>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = payments::_sign_with_address(command_handle, wallet_handle, address, message, cb);
+
+    AsyncResultHandler::slice(command_handle, err, receiver)
+}
+
+/// Verify a signature with a payment address.
+pub fn verify_with_address(address: &str, message: &[u8], signature: &[u8]) -> IndyFuture<bool> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_bool();
+
+    let err = payments::_verify_with_address(command_handle, address, message, signature, cb);
+
+    AsyncResultHandler::bool(command_handle, err, receiver)
+}
+
+fn _payment_request(future: IndyFuture<(String, String)>) -> IndyFuture<PaymentRequest> {
+    map(future, |(request, payment_method)| Ok(PaymentRequest { request: from_json(&request)?, payment_method }))
+}
@@ -0,0 +1,137 @@
+use async_api::{IndyFuture, parse};
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use pool;
+use PoolHandle;
+
+/// Pool ledger configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolConfig {
+    /// Path to the file that contains pool genesis transactions.
+    pub genesis_txn: String,
+    /// Optional did:indy namespace served by the pool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+}
+
+/// Runtime configuration of opened pool. Not set values take Libindy defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PoolOpenConfig {
+    /// Timeout for network request (in sec).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<i64>,
+    /// Extended timeout for network request (in sec).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_timeout: Option<i64>,
+    /// Maximal number of simultaneous connections to pool nodes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conn_limit: Option<usize>,
+    /// Time (in sec) after which idle connection to a node is closed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conn_active_timeout: Option<i64>,
+    /// Names of nodes which will have a priority during request sending.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preordered_nodes: Option<Vec<String>>,
+    /// Number of nodes to send read requests to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_read_nodes: Option<u8>,
+    /// Open pool in read-only mode backed by imported ledger snapshot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline: Option<bool>,
+    /// Verify state proofs of write transaction replies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verify_write_replies: Option<bool>,
+}
+
+/// Pool ledger configuration stored locally.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolInfo {
+    pub pool: String,
+}
+
+/// Creates a new local pool ledger configuration that can be used later to connect pool nodes.
+///
+/// See `pool::create_pool_ledger_config` for details.
+pub fn create_pool_ledger_config(pool_name: &str, config: Option<&PoolConfig>) -> IndyFuture<()> {
+    let config = try_opt_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = pool::_create_pool_ledger_config(command_handle, pool_name, config.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Opens pool ledger and performs connecting to pool nodes.
+///
+/// See `pool::open_pool_ledger` for details.
+pub fn open_pool_ledger(pool_name: &str, config: Option<&PoolOpenConfig>) -> IndyFuture<PoolHandle> {
+    let config = try_opt_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = pool::_open_pool_ledger(command_handle, pool_name, config.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::handle(command_handle, err, receiver)
+}
+
+/// Refreshes a local copy of a pool ledger and updates pool nodes connections.
+pub fn refresh_pool_ledger(pool_handle: PoolHandle) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = pool::_refresh_pool_ledger(command_handle, pool_handle, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Lists names of created pool ledgers.
+pub fn list_pools() -> IndyFuture<Vec<PoolInfo>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = pool::_list_pools(command_handle, cb);
+
+    parse(AsyncResultHandler::str(command_handle, err, receiver))
+}
+
+/// Closes opened pool ledger, opened nodes connections and frees allocated resources.
+pub fn close_pool_ledger(pool_handle: PoolHandle) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = pool::_close_pool_ledger(command_handle, pool_handle, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Deletes created pool ledger configuration.
+pub fn delete_pool_ledger(pool_name: &str) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = pool::_delete_pool_ledger(command_handle, pool_name, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Set PROTOCOL_VERSION to specific version.
+pub fn set_protocol_version(protocol_version: usize) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = pool::_set_protocol_version(command_handle, protocol_version, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_open_config_serialization_skips_not_set_values() {
+        let config = PoolOpenConfig {
+            timeout: Some(20),
+            number_read_nodes: Some(3),
+            ..PoolOpenConfig::default()
+        };
+
+        assert_eq!(r#"{"timeout":20,"number_read_nodes":3}"#, ::serde_json::to_string(&config).unwrap());
+    }
+}
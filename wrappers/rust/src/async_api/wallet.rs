@@ -0,0 +1,196 @@
+use serde_json::Value;
+
+use async_api::IndyFuture;
+use utils::callbacks::{ClosureHandler, AsyncResultHandler};
+
+use wallet;
+use WalletHandle;
+
+/// Wallet configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletConfig {
+    /// Identifier of the wallet.
+    pub id: String,
+    /// Type of the wallet storage. Defaults to 'default'.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_type: Option<String>,
+    /// Storage configuration json. Supported keys are defined by wallet type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_config: Option<Value>,
+}
+
+impl WalletConfig {
+    pub fn new(id: &str) -> WalletConfig {
+        WalletConfig {
+            id: id.to_string(),
+            storage_type: None,
+            storage_config: None,
+        }
+    }
+}
+
+/// Algorithm to use for wallet key derivation.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum KeyDerivationMethod {
+    #[serde(rename = "ARGON2I_MOD")]
+    Argon2iMod,
+    #[serde(rename = "ARGON2I_INT")]
+    Argon2iInt,
+    #[serde(rename = "RAW")]
+    Raw,
+}
+
+/// Wallet credentials.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletCredentials {
+    /// Key or passphrase used for wallet key derivation.
+    pub key: String,
+    /// If present than wallet master key will be rotated to a new one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rekey: Option<String>,
+    /// Credentials for wallet storage. Supported keys are defined by wallet type.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_credentials: Option<Value>,
+    /// Algorithm to use for `key` derivation. Defaults to ARGON2I_MOD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_derivation_method: Option<KeyDerivationMethod>,
+    /// Algorithm to use for `rekey` derivation. Defaults to ARGON2I_MOD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rekey_derivation_method: Option<KeyDerivationMethod>,
+}
+
+impl WalletCredentials {
+    pub fn new(key: &str, key_derivation_method: Option<KeyDerivationMethod>) -> WalletCredentials {
+        WalletCredentials {
+            key: key.to_string(),
+            rekey: None,
+            storage_credentials: None,
+            key_derivation_method,
+            rekey_derivation_method: None,
+        }
+    }
+}
+
+/// Configuration of wallet export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportConfig {
+    /// Path of the file that contains exported wallet content.
+    pub path: String,
+    /// Key or passphrase used for export key derivation.
+    pub key: String,
+    /// Algorithm to use for export key derivation. Defaults to ARGON2I_MOD.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_derivation_method: Option<KeyDerivationMethod>,
+}
+
+/// Configuration of wallet import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportConfig {
+    /// Path of the file that contains exported wallet content.
+    pub path: String,
+    /// Key used for export of the wallet.
+    pub key: String,
+}
+
+/// Seed for deterministic generation of wallet key.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+}
+
+/// Creates a new secure wallet with the given unique name.
+///
+/// See `wallet::create_wallet` for details.
+pub fn create_wallet(config: &WalletConfig, credentials: &WalletCredentials) -> IndyFuture<()> {
+    let config = try_json!(config);
+    let credentials = try_json!(credentials);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_create_wallet(command_handle, &config, &credentials, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Opens the wallet.
+///
+/// See `wallet::open_wallet` for details.
+pub fn open_wallet(config: &WalletConfig, credentials: &WalletCredentials) -> IndyFuture<WalletHandle> {
+    let config = try_json!(config);
+    let credentials = try_json!(credentials);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_wallethandle();
+
+    let err = wallet::_open_wallet(command_handle, &config, &credentials, cb);
+
+    AsyncResultHandler::wallethandle(command_handle, err, receiver)
+}
+
+/// Exports opened wallet to the file.
+pub fn export_wallet(wallet_handle: WalletHandle, export_config: &ExportConfig) -> IndyFuture<()> {
+    let export_config = try_json!(export_config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_export_wallet(command_handle, wallet_handle, &export_config, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Creates a new secure wallet and then imports its content from the file created by `export_wallet`.
+pub fn import_wallet(config: &WalletConfig, credentials: &WalletCredentials, import_config: &ImportConfig) -> IndyFuture<()> {
+    let config = try_json!(config);
+    let credentials = try_json!(credentials);
+    let import_config = try_json!(import_config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_import_wallet(command_handle, &config, &credentials, &import_config, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Closes opened wallet and frees allocated resources.
+pub fn close_wallet(wallet_handle: WalletHandle) -> IndyFuture<()> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_close_wallet(command_handle, wallet_handle, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Deletes created wallet.
+pub fn delete_wallet(config: &WalletConfig, credentials: &WalletCredentials) -> IndyFuture<()> {
+    let config = try_json!(config);
+    let credentials = try_json!(credentials);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = wallet::_delete_wallet(command_handle, &config, &credentials, cb);
+
+    AsyncResultHandler::empty(command_handle, err, receiver)
+}
+
+/// Generates wallet master key that can be used with RAW key derivation method.
+pub fn generate_wallet_key(config: Option<&KeyConfig>) -> IndyFuture<String> {
+    let config = try_opt_json!(config);
+
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = wallet::_generate_wallet_key(command_handle, config.as_ref().map(String::as_str), cb);
+
+    AsyncResultHandler::str(command_handle, err, receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wallet_credentials_serialization_works() {
+        let credentials = WalletCredentials::new("key", Some(KeyDerivationMethod::Raw));
+
+        assert_eq!(r#"{"key":"key","key_derivation_method":"RAW"}"#, ::serde_json::to_string(&credentials).unwrap());
+    }
+}
@@ -7,7 +7,7 @@ use std::ffi::CString;
 use utils::callbacks::{ClosureHandler, ResultHandler};
 
 use ffi::cache;
-use ffi::{ResponseEmptyCB, ResponseStringCB};
+use ffi::{ResponseEmptyCB, ResponseStringCB, ResponseStringU64CB};
 use {WalletHandle, CommandHandle, PoolHandle};
 
 /// Get schema json data for specified schema id.
@@ -176,4 +176,329 @@ fn _purge_cred_def_cache(command_handle: CommandHandle, wallet_handle: WalletHan
     let options_json = c_str!(options_json);
 
     ErrorCode::from(unsafe { cache::indy_purge_cred_def_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
-}
\ No newline at end of file
+}
+
+/// Get NYM data for specified DID.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `target_did` - DID to get NYM data for.
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// # Returns
+/// NYM data json.
+/// {
+///     did: DID as base58-encoded string for 16 or 32 bit DID value.
+///     verkey: verification key as base58-encoded string.
+///     role: Role associated number
+/// }
+pub fn get_nym(pool_handle: PoolHandle,
+               wallet_handle: WalletHandle,
+               submitter_did: &str,
+               target_did: &str,
+               options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_nym(command_handle, pool_handle, wallet_handle, submitter_did, target_did, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_nym(command_handle: CommandHandle,
+            pool_handle: PoolHandle,
+            wallet_handle: WalletHandle,
+            submitter_did: &str,
+            target_did: &str,
+            options_json: &str,
+            cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let target_did = c_str!(target_did);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_get_nym(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), target_did.as_ptr(), options_json.as_ptr(), cb)
+        }
+    )
+}
+
+/// Get ATTRIB data for specified DID and attribute.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `target_did` - Target DID as base58-encoded string for 16 or 32 bit DID value.
+/// * `raw` - (Optional) Requested attribute name.
+/// * `hash` - (Optional) Requested attribute hash.
+/// * `enc` - (Optional) Requested attribute encrypted value.
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// # Returns
+/// Attribute data as stored on the ledger.
+pub fn get_attrib(pool_handle: PoolHandle,
+                  wallet_handle: WalletHandle,
+                  submitter_did: &str,
+                  target_did: &str,
+                  raw: Option<&str>,
+                  hash: Option<&str>,
+                  enc: Option<&str>,
+                  options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_attrib(command_handle, pool_handle, wallet_handle, submitter_did, target_did, raw, hash, enc, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_attrib(command_handle: CommandHandle,
+               pool_handle: PoolHandle,
+               wallet_handle: WalletHandle,
+               submitter_did: &str,
+               target_did: &str,
+               raw: Option<&str>,
+               hash: Option<&str>,
+               enc: Option<&str>,
+               options_json: &str,
+               cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let target_did = c_str!(target_did);
+    let raw_str = opt_c_str!(raw);
+    let hash_str = opt_c_str!(hash);
+    let enc_str = opt_c_str!(enc);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_get_attrib(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), target_did.as_ptr(),
+                                   opt_c_ptr!(raw, raw_str),
+                                   opt_c_ptr!(hash, hash_str),
+                                   opt_c_ptr!(enc, enc_str),
+                                   options_json.as_ptr(), cb)
+        }
+    )
+}
+
+/// Get revocation registry definition json data for specified revocation registry definition id.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `id` - identifier of revocation registry definition.
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// # Returns
+/// Revocation Registry Definition json.
+pub fn get_revoc_reg_def(pool_handle: PoolHandle,
+                         wallet_handle: WalletHandle,
+                         submitter_did: &str,
+                         id: &str,
+                         options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_revoc_reg_def(command_handle, pool_handle, wallet_handle, submitter_did, id, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_revoc_reg_def(command_handle: CommandHandle,
+                      pool_handle: PoolHandle,
+                      wallet_handle: WalletHandle,
+                      submitter_did: &str,
+                      id: &str,
+                      options_json: &str,
+                      cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let id = c_str!(id);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_get_revoc_reg_def(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), id.as_ptr(), options_json.as_ptr(), cb)
+        }
+    )
+}
+
+/// Get revocation registry delta for specified revocation registry definition id and interval.
+/// Deltas are cached per requested interval.
+/// If data is present inside of cache, cached data is returned.
+/// Otherwise data is fetched from the ledger and stored inside of cache for future use.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `revoc_reg_def_id` - ID of the corresponding Revocation Registry Definition in ledger.
+/// * `from` - (Optional) Requested time represented as a total number of seconds from Unix Epoch.
+/// * `to` - Requested time represented as a total number of seconds from Unix Epoch.
+/// * `options_json` -
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// # Returns
+/// Revocation Registry Delta json and timestamp of the delta on the ledger.
+pub fn get_revoc_reg_delta(pool_handle: PoolHandle,
+                           wallet_handle: WalletHandle,
+                           submitter_did: &str,
+                           revoc_reg_def_id: &str,
+                           from: Option<i64>,
+                           to: i64,
+                           options_json: &str) -> Box<dyn Future<Item=(String, u64), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_u64();
+
+    let err = _get_revoc_reg_delta(command_handle, pool_handle, wallet_handle, submitter_did, revoc_reg_def_id, from, to, options_json, cb);
+
+    ResultHandler::str_u64(command_handle, err, receiver)
+}
+
+fn _get_revoc_reg_delta(command_handle: CommandHandle,
+                        pool_handle: PoolHandle,
+                        wallet_handle: WalletHandle,
+                        submitter_did: &str,
+                        revoc_reg_def_id: &str,
+                        from: Option<i64>,
+                        to: i64,
+                        options_json: &str,
+                        cb: Option<ResponseStringU64CB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let revoc_reg_def_id = c_str!(revoc_reg_def_id);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(
+        unsafe {
+            cache::indy_get_revoc_reg_delta(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), revoc_reg_def_id.as_ptr(),
+                                            from.unwrap_or(-1), to, options_json.as_ptr(), cb)
+        }
+    )
+}
+
+/// Purge NYM cache.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_nym_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_nym_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_nym_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_nym_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
+
+/// Purge ATTRIB cache.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_attrib_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_attrib_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_attrib_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_attrib_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
+
+/// Purge revocation registry definition cache.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_revoc_reg_def_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_revoc_reg_def_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_revoc_reg_def_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_revoc_reg_def_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
+
+/// Purge revocation registry delta cache.
+///
+/// EXPERIMENTAL
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `options_json` -
+///  {
+///    maxAge: (int, optional, -1 by default) Purge cached data if older than this many seconds. -1 means purge all.
+///  }
+pub fn purge_revoc_reg_delta_cache(wallet_handle: WalletHandle, options_json: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _purge_revoc_reg_delta_cache(command_handle, wallet_handle, options_json, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _purge_revoc_reg_delta_cache(command_handle: CommandHandle, wallet_handle: WalletHandle, options_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe { cache::indy_purge_revoc_reg_delta_cache(command_handle, wallet_handle, options_json.as_ptr(), cb) })
+}
@@ -24,7 +24,9 @@ use {WalletHandle, CommandHandle};
 /// {
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
-///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Currently only 'ed25519' value is supported for this field.
+///     "crypto_type": string, // Optional (if not set then ed25519 curve is used); Supported values are 'ed25519', 'secp256k1' and 'p256'.
+///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+///               to create the key in. Secret key never enters the wallet in this case.
 /// }
 /// # Returns
 /// verkey of generated key pair, also used as key identifier
@@ -36,7 +38,7 @@ pub fn create_key(wallet_handle: WalletHandle, my_key_json: Option<&str>) -> Box
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _create_key(command_handle: CommandHandle, wallet_handle: WalletHandle, my_key_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _create_key(command_handle: CommandHandle, wallet_handle: WalletHandle, my_key_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let my_key_json = opt_c_str_json!(my_key_json);
 
     ErrorCode::from(unsafe { crypto::indy_create_key(command_handle, wallet_handle, my_key_json.as_ptr(), cb) })
@@ -55,7 +57,7 @@ pub fn set_key_metadata(wallet_handle: WalletHandle, verkey: &str, metadata: &st
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _set_key_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, verkey: &str, metadata: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _set_key_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, verkey: &str, metadata: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let verkey = c_str!(verkey);
     let metadata = c_str!(metadata);
 
@@ -76,7 +78,7 @@ pub fn get_key_metadata(wallet_handle: WalletHandle, verkey: &str) -> Box<dyn Fu
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _get_key_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _get_key_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let verkey = c_str!(verkey);
 
     ErrorCode::from(unsafe { crypto::indy_get_key_metadata(command_handle, wallet_handle, verkey.as_ptr(), cb) })
@@ -97,7 +99,7 @@ pub fn sign(wallet_handle: WalletHandle, signer_vk: &str, message: &[u8]) -> Box
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _sign(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vk: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _sign(command_handle: CommandHandle, wallet_handle: WalletHandle, signer_vk: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
     let signer_vk = c_str!(signer_vk);
     ErrorCode::from(unsafe {
         crypto::indy_crypto_sign(command_handle, wallet_handle, signer_vk.as_ptr(),
@@ -123,7 +125,7 @@ pub fn verify(signer_vk: &str, message: &[u8], signature: &[u8]) -> Box<dyn Futu
     ResultHandler::bool(command_handle, err, receiver)
 }
 
-fn _verify(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signature: &[u8], cb: Option<ResponseBoolCB>) -> ErrorCode {
+pub(crate) fn _verify(command_handle: CommandHandle, signer_vk: &str, message: &[u8], signature: &[u8], cb: Option<ResponseBoolCB>) -> ErrorCode {
     let signer_vk = c_str!(signer_vk);
 
     ErrorCode::from(unsafe {
@@ -159,7 +161,7 @@ pub fn auth_crypt(wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &s
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _auth_crypt(command_handle: CommandHandle, wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _auth_crypt(command_handle: CommandHandle, wallet_handle: WalletHandle, sender_vk: &str, recipient_vk: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
     let sender_vk = c_str!(sender_vk);
     let recipient_vk = c_str!(recipient_vk);
     ErrorCode::from(unsafe {
@@ -197,7 +199,7 @@ pub fn auth_decrypt(wallet_handle: WalletHandle, recipient_vk: &str, encrypted_m
     ResultHandler::str_slice(command_handle, err, receiver)
 }
 
-fn _auth_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8], cb: Option<ResponseStringSliceCB>) -> ErrorCode {
+pub(crate) fn _auth_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8], cb: Option<ResponseStringSliceCB>) -> ErrorCode {
     let recipient_vk = c_str!(recipient_vk);
     ErrorCode::from(unsafe {
         crypto::indy_crypto_auth_decrypt(command_handle,
@@ -234,7 +236,7 @@ pub fn anon_crypt(recipient_vk: &str, message: &[u8]) -> Box<dyn Future<Item=Vec
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _anon_crypt(command_handle: CommandHandle, recipient_vk: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _anon_crypt(command_handle: CommandHandle, recipient_vk: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
     let recipient_vk = c_str!(recipient_vk);
     ErrorCode::from(unsafe {
         crypto::indy_crypto_anon_crypt(command_handle,
@@ -271,7 +273,7 @@ pub fn anon_decrypt(wallet_handle: WalletHandle, recipient_vk: &str, encrypted_m
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _anon_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _anon_decrypt(command_handle: CommandHandle, wallet_handle: WalletHandle, recipient_vk: &str, encrypted_message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
     let recipient_vk = c_str!(recipient_vk);
     ErrorCode::from(unsafe {
         crypto::indy_crypto_anon_decrypt(command_handle,
@@ -301,7 +303,7 @@ pub fn pack_message(wallet_handle: WalletHandle, message: &[u8], receiver_keys:
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _pack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _pack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, message: &[u8], receiver_keys: &str, sender: Option<&str>, cb: Option<ResponseSliceCB>) -> ErrorCode {
     let receiver_keys = c_str!(receiver_keys);
     let sender_str = opt_c_str!(sender);
 
@@ -334,7 +336,7 @@ pub fn unpack_message(wallet_handle: WalletHandle, jwe: &[u8]) -> Box<dyn Future
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _unpack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, jwe: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _unpack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, jwe: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
         crypto::indy_unpack_message(command_handle,
                                     wallet_handle,
@@ -344,3 +346,118 @@ fn _unpack_message(command_handle: CommandHandle, wallet_handle: WalletHandle, j
     })
 }
 
+/// Packs a message into DIDComm v2 encrypted envelope
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `message`: the message to be encrypted
+/// * `receiver_kids`: a JSON array as a string containing a list of the receivers key ids (verkeys or DID URLs)
+/// * `sender_kid`: the sender's key id. When None is used in this parameter, anoncrypt is used
+/// * `options_json`: pack options: {"enc": "XC20P" (default) or "A256GCM"}
+/// # Returns
+/// a JWE in General JSON Serialization
+pub fn pack_message_v2(wallet_handle: WalletHandle, message: &[u8], receiver_kids: &str, sender_kid: Option<&str>, options_json: Option<&str>) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _pack_message_v2(command_handle, wallet_handle, message, receiver_kids, sender_kid, options_json, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+pub(crate) fn _pack_message_v2(command_handle: CommandHandle, wallet_handle: WalletHandle, message: &[u8], receiver_kids: &str, sender_kid: Option<&str>, options_json: Option<&str>, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let receiver_kids = c_str!(receiver_kids);
+    let sender_kid_str = opt_c_str!(sender_kid);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_pack_message_v2(command_handle,
+                                     wallet_handle,
+                                     message.as_ptr() as *const u8,
+                                     message.len() as u32,
+                                     receiver_kids.as_ptr(),
+                                     opt_c_ptr!(sender_kid, sender_kid_str),
+                                     opt_c_ptr!(options_json, options_json_str),
+                                     cb)
+    })
+}
+
+/// Unpacks DIDComm v2 encrypted envelope packed using pack_message_v2
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open).
+/// * `jwe`: a pointer to the first byte of the JWE string
+/// # Returns
+/// a json structure that contains a decrypted message, recipient_kid and a sender_kid if packed with authcrypt
+pub fn unpack_message_v2(wallet_handle: WalletHandle, jwe: &[u8]) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _unpack_message_v2(command_handle, wallet_handle, jwe, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+pub(crate) fn _unpack_message_v2(command_handle: CommandHandle, wallet_handle: WalletHandle, jwe: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        crypto::indy_unpack_message_v2(command_handle,
+                                       wallet_handle,
+                                       jwe.as_ptr() as *const u8,
+                                       jwe.len() as u32,
+                                       cb)
+    })
+}
+
+/// Registers external key manager (e.g. KMS or HSM) implementation.
+///
+/// Keys created with `key_manager` option are held by the key manager: the wallet stores only
+/// verkey and key reference, signing and decryption are delegated to the handlers.
+///
+/// # Arguments
+/// * `name` - Key manager name.
+/// * `create_key` - Key creation handler
+/// * `sign` - Signing handler
+/// * `crypto_box` - Authenticated encryption handler
+/// * `crypto_box_open` - Authenticated decryption handler
+/// * `crypto_box_seal_open` - Anonymous decryption handler
+/// * `key_agreement` - X25519 key agreement handler
+/// * `free` - Handler that allows to de-allocate buffers returned by other handlers (called for every returned buffer, even if the handler failed)
+pub fn register_key_manager(name: &str,
+                            create_key: Option<crypto::KeyManagerCreateKey>,
+                            sign: Option<crypto::KeyManagerSign>,
+                            crypto_box: Option<crypto::KeyManagerCryptoBox>,
+                            crypto_box_open: Option<crypto::KeyManagerCryptoBoxOpen>,
+                            crypto_box_seal_open: Option<crypto::KeyManagerCryptoBoxSealOpen>,
+                            key_agreement: Option<crypto::KeyManagerKeyAgreement>,
+                            free: Option<crypto::KeyManagerFree>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_key_manager(command_handle, name, create_key, sign, crypto_box, crypto_box_open,
+                                    crypto_box_seal_open, key_agreement, free, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _register_key_manager(command_handle: CommandHandle,
+                                    name: &str,
+                                    create_key: Option<crypto::KeyManagerCreateKey>,
+                                    sign: Option<crypto::KeyManagerSign>,
+                                    crypto_box: Option<crypto::KeyManagerCryptoBox>,
+                                    crypto_box_open: Option<crypto::KeyManagerCryptoBoxOpen>,
+                                    crypto_box_seal_open: Option<crypto::KeyManagerCryptoBoxSealOpen>,
+                                    key_agreement: Option<crypto::KeyManagerKeyAgreement>,
+                                    free: Option<crypto::KeyManagerFree>,
+                                    cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let name = c_str!(name);
+
+    ErrorCode::from(unsafe {
+        crypto::indy_register_key_manager(command_handle,
+                                          name.as_ptr(),
+                                          create_key,
+                                          sign,
+                                          crypto_box,
+                                          crypto_box_open,
+                                          crypto_box_seal_open,
+                                          key_agreement,
+                                          free,
+                                          cb)
+    })
+}
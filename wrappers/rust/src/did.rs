@@ -32,9 +32,20 @@ use {CommandHandle, WalletHandle, PoolHandle};
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values are 'ed25519', 'secp256k1' and 'p256';
+///               verkeys of non-ed25519 keys are suffixed with ':<crypto_type>')
+///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+///               to create the key in. Secret key never enters the wallet in this case.
 ///     "cid": bool, (optional; if not set then false is used;)
 ///     "method_name": string, (optional) method name to create fully qualified did.
+///               "key" creates self-certifying `did:key` identifier (Example: `did:key:z6Mk...`);
+///               "peer" creates `did:peer` identifier of numalgo 0 or numalgo 2 if "service" is set.
+///     "service": object, (optional; only for "peer" method) DIDComm service embedded into did:peer:
+///         {
+///             "serviceEndpoint": string,
+///             "routingKeys": [string], (optional)
+///             "accept": [string], (optional)
+///         }
 /// }
 ///
 /// # Returns
@@ -48,7 +59,7 @@ pub fn create_and_store_my_did(wallet_handle: WalletHandle, did_json: &str) -> B
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _create_and_store_my_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did_json: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _create_and_store_my_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did_json: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let did_json = c_str!(did_json);
 
     ErrorCode::from(unsafe { did::indy_create_and_store_my_did(command_handle, wallet_handle, did_json.as_ptr(), cb) })
@@ -67,7 +78,10 @@ fn _create_and_store_my_did(command_handle: CommandHandle, wallet_handle: Wallet
 ///     "seed": string, (optional) Seed that allows deterministic key creation (if not set random one will be created).
 ///                                Can be UTF-8, base64 or hex string.
 ///     "crypto_type": string, (optional; if not set then ed25519 curve is used;
-///               currently only 'ed25519' value is supported for this field)
+///               supported values are 'ed25519', 'secp256k1' and 'p256';
+///               verkeys of non-ed25519 keys are suffixed with ':<crypto_type>')
+///     "key_manager": string, (optional) Name of registered key manager (see indy_register_key_manager)
+///               to create the key in. Secret key never enters the wallet in this case.
 /// }
 ///
 /// # Returns
@@ -80,7 +94,7 @@ pub fn replace_keys_start(wallet_handle: WalletHandle, tgt_did: &str, identity_j
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _replace_keys_start(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, identity_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _replace_keys_start(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, identity_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let tgt_did = c_str!(tgt_did);
     let identity_json = c_str!(identity_json);
 
@@ -100,7 +114,7 @@ pub fn replace_keys_apply(wallet_handle: WalletHandle, tgt_did: &str) -> Box<dyn
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _replace_keys_apply(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let tgt_did = c_str!(tgt_did);
 
     ErrorCode::from(unsafe { did::indy_replace_keys_apply(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
@@ -129,7 +143,7 @@ pub fn store_their_did(wallet_handle: WalletHandle, identity_json: &str) -> Box<
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _store_their_did(command_handle: CommandHandle, wallet_handle: WalletHandle, identity_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _store_their_did(command_handle: CommandHandle, wallet_handle: WalletHandle, identity_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let identity_json = c_str!(identity_json);
 
     ErrorCode::from(unsafe { did::indy_store_their_did(command_handle, wallet_handle, identity_json.as_ptr(), cb) })
@@ -148,6 +162,9 @@ fn _store_their_did(command_handle: CommandHandle, wallet_handle: WalletHandle,
 /// Note that "new" makes similar wallet record as "Key::create_key".
 /// As result we can use returned ver key in all generic crypto and messaging functions.
 ///
+/// Keys of self-certifying `did:key` and `did:peer` DIDs are resolved from the DID itself
+/// without wallet or ledger lookup.
+///
 /// # Arguments
 /// * `pool_handle` - Pool handle (created by Pool::open).
 /// * `wallet_handle` - Wallet handle (created by Wallet::open).
@@ -163,7 +180,7 @@ pub fn key_for_did(pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &s
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _key_for_did(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _key_for_did(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let did = c_str!(did);
 
     ErrorCode::from(unsafe { did::indy_key_for_did(command_handle, pool_handle, wallet_handle, did.as_ptr(), cb) })
@@ -180,6 +197,9 @@ fn _key_for_did(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_h
 /// Note that "new" makes similar wallet record as "Key::create_key".
 /// As result we can use returned ver key in all generic crypto and messaging functions.
 ///
+/// Keys of self-certifying `did:key` and `did:peer` DIDs are resolved from the DID itself
+/// without wallet or ledger lookup.
+///
 /// # Arguments
 /// * `wallet_handle` - Wallet handle (created by Wallet::open).
 /// * `did` - The DID to resolve key.
@@ -194,7 +214,7 @@ pub fn key_for_local_did(wallet_handle: WalletHandle, did: &str) -> Box<dyn Futu
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _key_for_local_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _key_for_local_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let did = c_str!(did);
 
     ErrorCode::from(unsafe { did::indy_key_for_local_did(command_handle, wallet_handle, did.as_ptr(), cb) })
@@ -215,7 +235,7 @@ pub fn set_endpoint_for_did(wallet_handle: WalletHandle, did: &str, address: &st
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _set_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, address: &str, transport_key: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _set_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, address: &str, transport_key: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let did = c_str!(did);
     let address = c_str!(address);
     let transport_key = c_str!(transport_key);
@@ -240,7 +260,7 @@ pub fn get_endpoint_for_did(wallet_handle: WalletHandle, pool_handle: PoolHandle
     ResultHandler::str_optstr(command_handle, err, receiver)
 }
 
-fn _get_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _get_endpoint_for_did(command_handle: CommandHandle, wallet_handle: WalletHandle, pool_handle: PoolHandle, did: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let did = c_str!(did);
 
     ErrorCode::from(unsafe { did::indy_get_endpoint_for_did(command_handle, wallet_handle, pool_handle, did.as_ptr(), cb) })
@@ -260,7 +280,7 @@ pub fn set_did_metadata(wallet_handle: WalletHandle, tgt_did: &str, metadata: &s
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _set_did_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, metadata: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _set_did_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, metadata: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let tgt_did = c_str!(tgt_did);
     let metadata = c_str!(metadata);
 
@@ -283,7 +303,7 @@ pub fn get_did_metadata(wallet_handle: WalletHandle, tgt_did: &str) -> Box<dyn F
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _get_did_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _get_did_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, tgt_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let tgt_did = c_str!(tgt_did);
 
     ErrorCode::from(unsafe { did::indy_get_did_metadata(command_handle, wallet_handle, tgt_did.as_ptr(), cb) })
@@ -309,7 +329,7 @@ pub fn get_my_did_with_metadata(wallet_handle: WalletHandle, my_did: &str) -> Bo
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _get_my_did_with_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, my_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _get_my_did_with_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, my_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let my_did = c_str!(my_did);
 
     ErrorCode::from(unsafe { did::indy_get_my_did_with_meta(command_handle, wallet_handle, my_did.as_ptr(), cb) })
@@ -335,7 +355,7 @@ pub fn list_my_dids_with_metadata(wallet_handle: WalletHandle) -> Box<dyn Future
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _list_my_dids_with_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _list_my_dids_with_metadata(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
     ErrorCode::from(unsafe { did::indy_list_my_dids_with_meta(command_handle, wallet_handle, cb) })
 }
 
@@ -355,7 +375,7 @@ pub fn abbreviate_verkey(tgt_did: &str, verkey: &str) -> Box<dyn Future<Item=Str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _abbreviate_verkey(command_handle: CommandHandle, tgt_did: &str, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _abbreviate_verkey(command_handle: CommandHandle, tgt_did: &str, verkey: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let tgt_did = c_str!(tgt_did);
     let verkey = c_str!(verkey);
 
@@ -383,7 +403,7 @@ pub fn qualify_did(wallet_handle: WalletHandle, did: &str, method: &str) -> Box<
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _qualify_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, method: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _qualify_did(command_handle: CommandHandle, wallet_handle: WalletHandle, did: &str, method: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let did = c_str!(did);
     let method = c_str!(method);
 
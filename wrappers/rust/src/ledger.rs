@@ -7,12 +7,14 @@ use std::ptr::null;
 use futures::Future;
 
 use ffi::ledger;
-use ffi::{ResponseStringCB,
+use ffi::{ResponseEmptyCB,
+          ResponseI32CB,
+          ResponseStringCB,
           ResponseStringStringCB,
           ResponseStringStringU64CB};
 
 use utils::callbacks::{ClosureHandler, ResultHandler};
-use {WalletHandle, CommandHandle, PoolHandle};
+use {WalletHandle, CommandHandle, PoolHandle, SearchHandle};
 
 /// Signs and submits request message to validator pool.
 ///
@@ -36,7 +38,7 @@ pub fn sign_and_submit_request(pool_handle: PoolHandle, wallet_handle: WalletHan
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _sign_and_submit_request(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _sign_and_submit_request(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let request_json = c_str!(request_json);
 
@@ -68,7 +70,7 @@ pub fn submit_request(pool_handle: PoolHandle, request_json: &str) -> Box<dyn Fu
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _submit_request(command_handle: CommandHandle, pool_handle: PoolHandle, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _submit_request(command_handle: CommandHandle, pool_handle: PoolHandle, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let request_json = c_str!(request_json);
 
     ErrorCode::from(unsafe { ledger::indy_submit_request(command_handle, pool_handle, request_json.as_ptr(), cb) })
@@ -82,7 +84,7 @@ pub fn submit_action(pool_handle: PoolHandle, request_json: &str, nodes: Option<
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _submit_action(command_handle: CommandHandle, pool_handle: PoolHandle, request_json: &str, nodes: Option<&str>, wait_timeout: Option<i32>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _submit_action(command_handle: CommandHandle, pool_handle: PoolHandle, request_json: &str, nodes: Option<&str>, wait_timeout: Option<i32>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let request_json = c_str!(request_json);
     let nodes_str = opt_c_str!(nodes);
 
@@ -111,7 +113,7 @@ pub fn sign_request(wallet_handle: WalletHandle, submitter_did: &str, request_js
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _sign_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _sign_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let request_json = c_str!(request_json);
 
@@ -138,7 +140,7 @@ pub fn multi_sign_request(wallet_handle: WalletHandle, submitter_did: &str, requ
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _multi_sign_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _multi_sign_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: &str, request_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let request_json = c_str!(request_json);
 
@@ -161,7 +163,7 @@ pub fn build_get_ddo_request(submitter_did: Option<&str>, target_did: &str) -> B
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_ddo_request(command_handle: CommandHandle, submitter_did: Option<&str>, target_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_ddo_request(command_handle: CommandHandle, submitter_did: Option<&str>, target_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let target_did = c_str!(target_did);
 
@@ -195,7 +197,7 @@ pub fn build_nym_request(submitter_did: &str, target_did: &str, verkey: Option<&
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_nym_request(command_handle: CommandHandle,
+pub(crate) fn _build_nym_request(command_handle: CommandHandle,
                       submitter_did: &str,
                       target_did: &str,
                       verkey: Option<&str>,
@@ -236,7 +238,7 @@ pub fn build_get_nym_request(submitter_did: Option<&str>, target_did: &str) -> B
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_nym_request(command_handle: CommandHandle, submitter_did: Option<&str>, target_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_nym_request(command_handle: CommandHandle, submitter_did: Option<&str>, target_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let target_did = c_str!(target_did);
 
@@ -269,7 +271,7 @@ pub fn parse_get_nym_response(get_nym_response: &str) -> Box<dyn Future<Item=Str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _parse_get_nym_response(command_handle: CommandHandle, get_nym_response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _parse_get_nym_response(command_handle: CommandHandle, get_nym_response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let get_nym_response = c_str!(get_nym_response);
 
     ErrorCode::from(unsafe { ledger::indy_parse_get_nym_response(command_handle, get_nym_response.as_ptr(), cb) })
@@ -295,7 +297,7 @@ pub fn build_get_txn_request(submitter_did: Option<&str>, ledger_type: Option<&s
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_txn_request(command_handle: CommandHandle, submitter_did: Option<&str>, ledger_type: Option<&str>, seq_no: i32, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_txn_request(command_handle: CommandHandle, submitter_did: Option<&str>, ledger_type: Option<&str>, seq_no: i32, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let ledger_type_str = opt_c_str!(ledger_type);
 
@@ -322,7 +324,7 @@ pub fn build_attrib_request(submitter_did: &str, target_did: &str, hash: Option<
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_attrib_request(command_handle: CommandHandle, submitter_did: &str, target_did: &str, hash: Option<&str>, raw: Option<&str>, enc: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_attrib_request(command_handle: CommandHandle, submitter_did: &str, target_did: &str, hash: Option<&str>, raw: Option<&str>, enc: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let target_did = c_str!(target_did);
 
@@ -360,7 +362,7 @@ pub fn build_get_attrib_request(submitter_did: Option<&str>, target_did: &str, r
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_attrib_request(command_handle: CommandHandle, submitter_did: Option<&str>, target_did: &str, raw: Option<&str>, hash: Option<&str>, enc: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_attrib_request(command_handle: CommandHandle, submitter_did: Option<&str>, target_did: &str, raw: Option<&str>, hash: Option<&str>, enc: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let target_did = c_str!(target_did);
 
@@ -403,7 +405,7 @@ pub fn build_schema_request(submitter_did: &str, data: &str) -> Box<dyn Future<I
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_schema_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_schema_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let data = c_str!(data);
 
@@ -426,7 +428,7 @@ pub fn build_get_schema_request(submitter_did: Option<&str>, id: &str) -> Box<dy
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_schema_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_schema_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let id = c_str!(id);
 
@@ -455,7 +457,7 @@ pub fn parse_get_schema_response(get_schema_response: &str) -> Box<dyn Future<It
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _parse_get_schema_response(command_handle: CommandHandle, get_schema_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _parse_get_schema_response(command_handle: CommandHandle, get_schema_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let get_schema_response = c_str!(get_schema_response);
 
     ErrorCode::from(unsafe { ledger::indy_parse_get_schema_response(command_handle, get_schema_response.as_ptr(), cb) })
@@ -490,7 +492,7 @@ pub fn build_cred_def_request(submitter_did: &str, data: &str) -> Box<dyn Future
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_cred_def_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_cred_def_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let data = c_str!(data);
 
@@ -514,7 +516,7 @@ pub fn build_get_cred_def_request(submitter_did: Option<&str>, id: &str) -> Box<
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_cred_def_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_cred_def_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let id = c_str!(id);
 
@@ -547,12 +549,147 @@ pub fn parse_get_cred_def_response(get_cred_def_response: &str) -> Box<dyn Futur
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _parse_get_cred_def_response(command_handle: CommandHandle, get_cred_def_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _parse_get_cred_def_response(command_handle: CommandHandle, get_cred_def_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let get_cred_def_response = c_str!(get_cred_def_response);
 
     ErrorCode::from(unsafe { ledger::indy_parse_get_cred_def_response(command_handle, get_cred_def_response.as_ptr(), cb) })
 }
 
+/// Resolves fully qualified did:indy identifier of a ledger object
+/// using the opened pool configured with the namespace of the identifier.
+///
+/// # Arguments
+/// * `id` - fully qualified identifier of the object:
+///     did:indy:<namespace>:<did> - DID
+///     did:indy:<namespace>:<did>/anoncreds/v0/SCHEMA/<name>/<version> - schema
+///     did:indy:<namespace>:<did>/anoncreds/v0/CLAIM_DEF/<schema seq no>/<tag> - credential definition
+///
+/// # Returns
+/// Requested identifier and parsed object json (NYM data, schema or credential definition).
+pub fn resolve(id: &str) -> Box<dyn Future<Item=(String, String), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_string();
+
+    let err = _resolve(command_handle, id, cb);
+
+    ResultHandler::str_str(command_handle, err, receiver)
+}
+
+pub(crate) fn _resolve(command_handle: CommandHandle, id: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    let id = c_str!(id);
+
+    ErrorCode::from(unsafe { ledger::indy_resolve(command_handle, id.as_ptr(), cb) })
+}
+
+/// Downloads a range of ledger transactions verified against the ledger merkle root.
+/// Verified transactions are kept in the local replica of the ledger
+/// and appended to the file from `file` option if it's set.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `ledger_type` - (Optional) type of the ledger: DOMAIN (default), POOL, CONFIG or any number.
+/// * `from` - sequence number of the first transaction to download (starting from 1).
+/// * `to` - sequence number of the last transaction to download, -1 to download up to the current ledger size.
+/// * `options_json` - (Optional) download options:
+///     {
+///         file: (Optional) path to the file to append downloaded transactions to (one json per line).
+///     }
+///
+/// # Returns
+/// Download result json: {from, to, file (if `file` option is set)}
+pub fn download_ledger_txns(pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _download_ledger_txns(command_handle, pool_handle, ledger_type, from, to, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _download_ledger_txns(command_handle: CommandHandle, pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let ledger_type_str = opt_c_str!(ledger_type);
+    let options_json_str = opt_c_str!(options_json);
+
+    ErrorCode::from(unsafe { ledger::indy_download_ledger_txns(command_handle, pool_handle, opt_c_ptr!(ledger_type, ledger_type_str), from, to, opt_c_ptr!(options_json, options_json_str), cb) })
+}
+
+/// Opens search over a range of ledger transactions downloaded to the local replica of the ledger.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `ledger_type` - (Optional) type of the ledger: DOMAIN (default), POOL, CONFIG or any number.
+/// * `from` - sequence number of the first transaction to read (starting from 1).
+/// * `to` - sequence number of the last transaction to read, -1 to read up to the current ledger size.
+///
+/// # Returns
+/// Ledger transactions search handle
+pub fn open_ledger_txns_search(pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32) -> Box<dyn Future<Item=SearchHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _open_ledger_txns_search(command_handle, pool_handle, ledger_type, from, to, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+pub(crate) fn _open_ledger_txns_search(command_handle: CommandHandle, pool_handle: PoolHandle, ledger_type: Option<&str>, from: i32, to: i32, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let ledger_type_str = opt_c_str!(ledger_type);
+
+    ErrorCode::from(unsafe { ledger::indy_open_ledger_txns_search(command_handle, pool_handle, opt_c_ptr!(ledger_type, ledger_type_str), from, to, cb) })
+}
+
+/// Fetches next batch (at most 100) of transactions of the ledger transactions search.
+///
+/// # Arguments
+/// * `search_handle` - ledger transactions search handle (created by open_ledger_txns_search).
+///
+/// # Returns
+/// Array of transactions ordered by seqNo, empty array if all transactions of the range are fetched.
+pub fn fetch_ledger_txns_search_next_batch(search_handle: SearchHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _fetch_ledger_txns_search_next_batch(command_handle, search_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _fetch_ledger_txns_search_next_batch(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { ledger::indy_fetch_ledger_txns_search_next_batch(command_handle, search_handle, cb) })
+}
+
+/// Close ledger transactions search (make search handle invalid)
+///
+/// # Arguments
+/// * `search_handle` - ledger transactions search handle (created by open_ledger_txns_search).
+pub fn close_ledger_txns_search(search_handle: SearchHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _close_ledger_txns_search(command_handle, search_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _close_ledger_txns_search(command_handle: CommandHandle, search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { ledger::indy_close_ledger_txns_search(command_handle, search_handle, cb) })
+}
+
+/// Creates a local snapshot of the domain ledger from catchup of the opened pool
+/// to use by the pool opened in offline mode later.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+///
+/// # Returns
+/// Snapshot info json: {ledgerSize, txnRootHash}
+pub fn create_pool_snapshot(pool_handle: PoolHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _create_pool_snapshot(command_handle, pool_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _create_pool_snapshot(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { ledger::indy_create_pool_snapshot(command_handle, pool_handle, cb) })
+}
+
 /// Builds a NODE request. Request to add a new node to the pool, or updates existing in the pool.
 ///
 /// # Arguments
@@ -579,7 +716,7 @@ pub fn build_node_request(submitter_did: &str, target_did: &str, data: &str) ->
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_node_request(command_handle: CommandHandle, submitter_did: &str, target_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_node_request(command_handle: CommandHandle, submitter_did: &str, target_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let target_did = c_str!(target_did);
     let data = c_str!(data);
@@ -602,7 +739,7 @@ pub fn build_get_validator_info_request(submitter_did: &str) -> Box<dyn Future<I
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_validator_info_request(command_handle: CommandHandle, submitter_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_validator_info_request(command_handle: CommandHandle, submitter_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
 
     ErrorCode::from(unsafe {
@@ -630,7 +767,7 @@ pub fn build_pool_config_request(submitter_did: &str, writes: bool, force: bool)
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_pool_config_request(command_handle: CommandHandle, submitter_did: &str, writes: bool, force: bool, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_pool_config_request(command_handle: CommandHandle, submitter_did: &str, writes: bool, force: bool, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
 
     ErrorCode::from(unsafe { ledger::indy_build_pool_config_request(command_handle, submitter_did.as_ptr(), writes, force, cb) })
@@ -654,7 +791,7 @@ pub fn build_pool_restart_request(submitter_did: &str, action: &str, datetime: O
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_pool_restart_request(command_handle: CommandHandle, submitter_did: &str, action: &str, datetime: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_pool_restart_request(command_handle: CommandHandle, submitter_did: &str, action: &str, datetime: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let action = c_str!(action);
     let datetime_str = opt_c_str!(datetime);
@@ -706,7 +843,7 @@ pub fn build_pool_upgrade_request(submitter_did: &str,
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_pool_upgrade_request(command_handle: CommandHandle,
+pub(crate) fn _build_pool_upgrade_request(command_handle: CommandHandle,
                                submitter_did: &str,
                                name: &str,
                                version: &str,
@@ -779,7 +916,7 @@ pub fn build_revoc_reg_def_request(submitter_did: &str, data: &str) -> Box<dyn F
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_revoc_reg_def_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_revoc_reg_def_request(command_handle: CommandHandle, submitter_did: &str, data: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let data = c_str!(data);
 
@@ -803,7 +940,7 @@ pub fn build_get_revoc_reg_def_request(submitter_did: Option<&str>, id: &str) ->
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_revoc_reg_def_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_revoc_reg_def_request(command_handle: CommandHandle, submitter_did: Option<&str>, id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let id = c_str!(id);
 
@@ -840,7 +977,7 @@ pub fn parse_get_revoc_reg_def_response(get_revoc_reg_def_response: &str) -> Box
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _parse_get_revoc_reg_def_response(command_handle: CommandHandle, get_revoc_reg_def_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _parse_get_revoc_reg_def_response(command_handle: CommandHandle, get_revoc_reg_def_response: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let get_revoc_reg_def_response = c_str!(get_revoc_reg_def_response);
 
     ErrorCode::from(unsafe { ledger::indy_parse_get_revoc_reg_def_response(command_handle, get_revoc_reg_def_response.as_ptr(), cb) })
@@ -877,7 +1014,7 @@ pub fn build_revoc_reg_entry_request(submitter_did: &str, revoc_reg_def_id: &str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_revoc_reg_entry_request(command_handle: CommandHandle, submitter_did: &str, revoc_reg_def_id: &str, rev_def_type: &str, value: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_revoc_reg_entry_request(command_handle: CommandHandle, submitter_did: &str, revoc_reg_def_id: &str, rev_def_type: &str, value: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let revoc_reg_def_id = c_str!(revoc_reg_def_id);
     let rev_def_type = c_str!(rev_def_type);
@@ -904,7 +1041,7 @@ pub fn build_get_revoc_reg_request(submitter_did: Option<&str>, revoc_reg_def_id
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_revoc_reg_request(command_handle: CommandHandle, submitter_did: Option<&str>, revoc_reg_def_id: &str, timestamp: i64, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_revoc_reg_request(command_handle: CommandHandle, submitter_did: Option<&str>, revoc_reg_def_id: &str, timestamp: i64, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let revoc_reg_def_id = c_str!(revoc_reg_def_id);
 
@@ -932,7 +1069,7 @@ pub fn parse_get_revoc_reg_response(get_revoc_reg_response: &str) -> Box<dyn Fut
     ResultHandler::str_str_u64(command_handle, err, receiver)
 }
 
-fn _parse_get_revoc_reg_response(command_handle: CommandHandle, get_revoc_reg_response: &str, cb: Option<ResponseStringStringU64CB>) -> ErrorCode {
+pub(crate) fn _parse_get_revoc_reg_response(command_handle: CommandHandle, get_revoc_reg_response: &str, cb: Option<ResponseStringStringU64CB>) -> ErrorCode {
     let get_revoc_reg_response = c_str!(get_revoc_reg_response);
 
     ErrorCode::from(unsafe { ledger::indy_parse_get_revoc_reg_response(command_handle, get_revoc_reg_response.as_ptr(), cb) })
@@ -958,7 +1095,7 @@ pub fn build_get_revoc_reg_delta_request(submitter_did: Option<&str>, revoc_reg_
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_revoc_reg_delta_request(command_handle: CommandHandle, submitter_did: Option<&str>, revoc_reg_def_id: &str, from: i64, to: i64, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_revoc_reg_delta_request(command_handle: CommandHandle, submitter_did: Option<&str>, revoc_reg_def_id: &str, from: i64, to: i64, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let revoc_reg_def_id = c_str!(revoc_reg_def_id);
 
@@ -989,7 +1126,7 @@ pub fn parse_get_revoc_reg_delta_response(get_revoc_reg_delta_response: &str) ->
     ResultHandler::str_str_u64(command_handle, err, receiver)
 }
 
-fn _parse_get_revoc_reg_delta_response(command_handle: CommandHandle, get_revoc_reg_delta_response: &str, cb: Option<ResponseStringStringU64CB>) -> ErrorCode {
+pub(crate) fn _parse_get_revoc_reg_delta_response(command_handle: CommandHandle, get_revoc_reg_delta_response: &str, cb: Option<ResponseStringStringU64CB>) -> ErrorCode {
     let get_revoc_reg_delta_response = c_str!(get_revoc_reg_delta_response);
 
     ErrorCode::from(unsafe { ledger::indy_parse_get_revoc_reg_delta_response(command_handle, get_revoc_reg_delta_response.as_ptr(), cb) })
@@ -1033,7 +1170,7 @@ pub fn get_response_metadata(response: &str) -> Box<dyn Future<Item=String, Erro
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _get_response_metadata(command_handle: CommandHandle, response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _get_response_metadata(command_handle: CommandHandle, response: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let response = c_str!(response);
 
     ErrorCode::from(unsafe { ledger::indy_get_response_metadata(command_handle, response.as_ptr(), cb) })
@@ -1077,7 +1214,7 @@ pub fn build_auth_rule_request(submitter_did: &str, txn_type: &str, action: &str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_auth_rule_request(command_handle: CommandHandle,
+pub(crate) fn _build_auth_rule_request(command_handle: CommandHandle,
                             submitter_did: &str,
                             txn_type: &str,
                             action: &str,
@@ -1134,7 +1271,7 @@ pub fn build_auth_rules_request(submitter_did: &str, data: &str) -> Box<dyn Futu
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_auth_rules_request(command_handle: CommandHandle,
+pub(crate) fn _build_auth_rules_request(command_handle: CommandHandle,
                              submitter_did: &str,
                              data: &str,
                              cb: Option<ResponseStringCB>) -> ErrorCode {
@@ -1174,7 +1311,7 @@ pub fn build_get_auth_rule_request(submitter_did: Option<&str>, txn_type: Option
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_auth_rule_request(command_handle: CommandHandle,
+pub(crate) fn _build_get_auth_rule_request(command_handle: CommandHandle,
                                 submitter_did: Option<&str>,
                                 txn_type: Option<&str>,
                                 action: Option<&str>,
@@ -1240,7 +1377,7 @@ pub fn build_txn_author_agreement_request(submitter_did: &str, text: Option<&str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_txn_author_agreement_request(command_handle: CommandHandle,
+pub(crate) fn _build_txn_author_agreement_request(command_handle: CommandHandle,
                                        submitter_did: &str,
                                        text: Option<&str>,
                                        version: &str,
@@ -1280,7 +1417,7 @@ pub fn build_disable_all_txn_author_agreements_request(submitter_did: &str) -> B
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_disable_all_txn_author_agreements_request(command_handle: CommandHandle,
+pub(crate) fn _build_disable_all_txn_author_agreements_request(command_handle: CommandHandle,
                                                     submitter_did: &str,
                                                     cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
@@ -1315,7 +1452,7 @@ pub fn build_get_txn_author_agreement_request(submitter_did: Option<&str>, data:
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_txn_author_agreement_request(command_handle: CommandHandle,
+pub(crate) fn _build_get_txn_author_agreement_request(command_handle: CommandHandle,
                                            submitter_did: Option<&str>,
                                            data: Option<&str>,
                                            cb: Option<ResponseStringCB>) -> ErrorCode {
@@ -1355,7 +1492,7 @@ pub fn build_acceptance_mechanisms_request(submitter_did: &str, aml: &str, versi
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_acceptance_mechanisms_request(command_handle: CommandHandle,
+pub(crate) fn _build_acceptance_mechanisms_request(command_handle: CommandHandle,
                                         submitter_did: &str,
                                         aml: &str,
                                         version: &str,
@@ -1396,7 +1533,7 @@ pub fn build_get_acceptance_mechanisms_request(submitter_did: Option<&str>, time
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_acceptance_mechanisms_request(command_handle: CommandHandle,
+pub(crate) fn _build_get_acceptance_mechanisms_request(command_handle: CommandHandle,
                                             submitter_did: Option<&str>,
                                             timestamp: Option<i64>,
                                             version: Option<&str>,
@@ -1447,7 +1584,7 @@ pub fn append_txn_author_agreement_acceptance_to_request(request_json: &str,
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _append_txn_author_agreement_acceptance_to_request(command_handle: CommandHandle,
+pub(crate) fn _append_txn_author_agreement_acceptance_to_request(command_handle: CommandHandle,
                                                       request_json: &str,
                                                       text: Option<&str>,
                                                       version: Option<&str>,
@@ -1498,7 +1635,7 @@ pub fn append_request_endorser(request_json: &str,
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _append_request_endorser(command_handle: CommandHandle,
+pub(crate) fn _append_request_endorser(command_handle: CommandHandle,
                             request_json: &str,
                             endorser_did: &str,
                             cb: Option<ResponseStringCB>) -> ErrorCode {
@@ -1529,7 +1666,7 @@ pub fn build_ledgers_freeze_request(submitter_did: &str, ledgers_ids: Vec<u64>)
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_ledgers_freeze_request(command_handle: CommandHandle, submitter_did: &str, ledgers_ids: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_ledgers_freeze_request(command_handle: CommandHandle, submitter_did: &str, ledgers_ids: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
     let ledgers_ids = c_str!(ledgers_ids);
 
@@ -1564,7 +1701,7 @@ pub fn build_get_frozen_ledgers_request(submitter_did: &str) -> Box<dyn Future<I
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_frozen_ledgers_request(command_handle: CommandHandle, submitter_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_frozen_ledgers_request(command_handle: CommandHandle, submitter_did: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did = c_str!(submitter_did);
 
     ErrorCode::from(unsafe {
@@ -1,4 +1,6 @@
 extern crate futures;
+#[cfg(feature = "async_api")]
+extern crate futures03;
 #[macro_use]
 extern crate lazy_static;
 extern crate log;
@@ -32,6 +34,8 @@ pub mod pool;
 pub mod wallet;
 pub mod cache;
 pub mod metrics;
+#[cfg(feature = "async_api")]
+pub mod async_api;
 mod utils;
 
 use std::ffi::CString;
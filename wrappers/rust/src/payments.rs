@@ -48,7 +48,7 @@ pub fn create_payment_address(wallet_handle: WalletHandle, payment_method: &str,
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _create_payment_address(command_handle: CommandHandle, wallet_handle: WalletHandle, payment_method: &str, config: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _create_payment_address(command_handle: CommandHandle, wallet_handle: WalletHandle, payment_method: &str, config: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let config = c_str!(config);
 
@@ -70,7 +70,7 @@ pub fn list_payment_addresses(wallet_handle: WalletHandle) -> Box<dyn Future<Ite
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _list_payment_addresses(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _list_payment_addresses(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
     ErrorCode::from(unsafe { payments::indy_list_payment_addresses(command_handle, wallet_handle, cb) })
 }
 
@@ -122,7 +122,7 @@ pub fn add_request_fees(wallet_handle: WalletHandle,
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _add_request_fees(command_handle: CommandHandle,
+pub(crate) fn _add_request_fees(command_handle: CommandHandle,
                      wallet_handle: WalletHandle,
                      submitter_did: Option<&str>,
                      req_json: &str,
@@ -173,7 +173,7 @@ pub fn parse_response_with_fees(payment_method: &str, resp_json: &str) -> Box<dy
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _parse_response_with_fees(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _parse_response_with_fees(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let resp_json = c_str!(resp_json);
 
@@ -202,7 +202,7 @@ pub fn build_get_payment_sources_request(wallet_handle: WalletHandle, submitter_
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _build_get_payment_sources_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_address: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_payment_sources_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_address: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let payment_address = c_str!(payment_address);
 
@@ -230,7 +230,7 @@ pub fn build_get_payment_sources_with_from_request(wallet_handle: WalletHandle,
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _build_get_payment_sources_with_from_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_address: &str, from: Option<i64>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_payment_sources_with_from_request(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_address: &str, from: Option<i64>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let payment_address = c_str!(payment_address);
 
@@ -262,7 +262,7 @@ pub fn parse_get_payment_sources_response(payment_method: &str, resp_json: &str)
     Box::new(ResultHandler::str(command_handle, err, receiver))
 }
 
-fn _parse_get_payment_sources_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _parse_get_payment_sources_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let resp_json = c_str!(resp_json);
 
@@ -293,7 +293,7 @@ pub fn parse_get_payment_sources_with_from_response(payment_method: &str, resp_j
     Box::new(ResultHandler::str_i64(command_handle, err, receiver).map(|(s, i)| (s, if i >= 0 {Some(i)} else {None})).into_future())
 }
 
-fn _parse_get_payment_sources_with_from_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringI64CB>) -> ErrorCode {
+pub(crate) fn _parse_get_payment_sources_with_from_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringI64CB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let resp_json = c_str!(resp_json);
 
@@ -332,7 +332,7 @@ pub fn build_payment_req(wallet_handle: WalletHandle, submitter_did: Option<&str
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _build_payment_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, inputs: &str, outputs: &str, extra: Option<&str>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _build_payment_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, inputs: &str, outputs: &str, extra: Option<&str>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let inputs = c_str!(inputs);
     let outputs = c_str!(outputs);
@@ -372,7 +372,7 @@ pub fn parse_payment_response(payment_method: &str, resp_json: &str) -> Box<dyn
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _parse_payment_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _parse_payment_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let resp_json = c_str!(resp_json);
 
@@ -411,7 +411,7 @@ pub fn prepare_extra_with_acceptance_data(extra_json: Option<&str>,
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _prepare_extra_with_acceptance_data(command_handle: CommandHandle,
+pub(crate) fn _prepare_extra_with_acceptance_data(command_handle: CommandHandle,
                                        extra_json: Option<&str>,
                                        text: Option<&str>,
                                        version: Option<&str>,
@@ -461,7 +461,7 @@ pub fn build_mint_req(wallet_handle: WalletHandle, submitter_did: Option<&str>,
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _build_mint_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, outputs_json: &str, extra: Option<&str>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _build_mint_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, outputs_json: &str, extra: Option<&str>, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let outputs_json = c_str!(outputs_json);
     let extra_str = opt_c_str!(extra);
@@ -492,7 +492,7 @@ pub fn build_set_txn_fees_req(wallet_handle: WalletHandle, submitter_did: Option
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_set_txn_fees_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str, fees_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_set_txn_fees_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str, fees_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let payment_method = c_str!(payment_method);
     let fees_json = c_str!(fees_json);
@@ -518,7 +518,7 @@ pub fn build_get_txn_fees_req(wallet_handle: WalletHandle, submitter_did: Option
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _build_get_txn_fees_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _build_get_txn_fees_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, payment_method: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let payment_method = c_str!(payment_method);
 
@@ -547,7 +547,7 @@ pub fn parse_get_txn_fees_response(payment_method: &str, resp_json: &str) -> Box
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _parse_get_txn_fees_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _parse_get_txn_fees_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let resp_json = c_str!(resp_json);
 
@@ -562,7 +562,7 @@ pub fn build_verify_payment_req(wallet_handle: WalletHandle, submitter_did: Opti
     ResultHandler::str_str(command_handle, err, receiver)
 }
 
-fn _build_verify_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, receipt: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
+pub(crate) fn _build_verify_req(command_handle: CommandHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, receipt: &str, cb: Option<ResponseStringStringCB>) -> ErrorCode {
     let submitter_did_str = opt_c_str!(submitter_did);
     let receipt = c_str!(receipt);
 
@@ -579,7 +579,7 @@ pub fn parse_verify_payment_response(payment_method: &str, resp_json: &str) -> B
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _parse_verify_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _parse_verify_response(command_handle: CommandHandle, payment_method: &str, resp_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let payment_method = c_str!(payment_method);
     let resp_json = c_str!(resp_json);
 
@@ -625,7 +625,7 @@ pub fn get_request_info(get_auth_rule_resp_json: &str, requester_info_json: &str
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _get_request_info(command_handle: CommandHandle, get_auth_rule_resp_json: &str, requester_info_json: &str, fees_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _get_request_info(command_handle: CommandHandle, get_auth_rule_resp_json: &str, requester_info_json: &str, fees_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let get_auth_rule_resp_json = c_str!(get_auth_rule_resp_json);
     let requester_info_json = c_str!(requester_info_json);
     let fees_json = c_str!(fees_json);
@@ -643,7 +643,7 @@ pub fn sign_with_address(wallet_handle: WalletHandle, address: &str, message: &[
     ResultHandler::slice(command_handle, err, receiver)
 }
 
-fn _sign_with_address(command_handle: CommandHandle, wallet_handle: WalletHandle, address: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
+pub(crate) fn _sign_with_address(command_handle: CommandHandle, wallet_handle: WalletHandle, address: &str, message: &[u8], cb: Option<ResponseSliceCB>) -> ErrorCode {
     let address = c_str!(address);
     ErrorCode::from(unsafe {
         payments::indy_sign_with_address(command_handle, wallet_handle, address.as_ptr(),
@@ -661,7 +661,7 @@ pub fn verify_with_address(address: &str, message: &[u8], signature: &[u8]) -> B
     ResultHandler::bool(command_handle, err, receiver)
 }
 
-fn _verify_with_address(command_handle: CommandHandle, address: &str, message: &[u8], signature: &[u8], cb: Option<ResponseBoolCB>) -> ErrorCode {
+pub(crate) fn _verify_with_address(command_handle: CommandHandle, address: &str, message: &[u8], signature: &[u8], cb: Option<ResponseBoolCB>) -> ErrorCode {
     let address = c_str!(address);
 
     ErrorCode::from(unsafe {
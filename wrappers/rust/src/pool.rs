@@ -29,7 +29,7 @@ pub fn create_pool_ledger_config(pool_name: &str, pool_config: Option<&str>) ->
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _create_pool_ledger_config(command_handle: CommandHandle, pool_name: &str, pool_config: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _create_pool_ledger_config(command_handle: CommandHandle, pool_name: &str, pool_config: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let pool_name = c_str!(pool_name);
     let pool_config_str = opt_c_str!(pool_config);
 
@@ -70,7 +70,7 @@ pub fn open_pool_ledger(pool_name: &str, config: Option<&str>) -> Box<dyn Future
     ResultHandler::handle(command_handle, err, receiver)
 }
 
-fn _open_pool_ledger(command_handle: CommandHandle, pool_name: &str, config: Option<&str>, cb: Option<ResponseI32CB>) -> ErrorCode {
+pub(crate) fn _open_pool_ledger(command_handle: CommandHandle, pool_name: &str, config: Option<&str>, cb: Option<ResponseI32CB>) -> ErrorCode {
     let pool_name = c_str!(pool_name);
     let config_str = opt_c_str!(config);
 
@@ -89,7 +89,7 @@ pub fn refresh_pool_ledger(pool_handle: PoolHandle) -> Box<dyn Future<Item=(), E
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _refresh_pool_ledger(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _refresh_pool_ledger(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     ErrorCode::from(unsafe { pool::indy_refresh_pool_ledger(command_handle, pool_handle, cb) })
 }
 
@@ -102,7 +102,7 @@ pub fn list_pools() -> Box<dyn Future<Item=String, Error=IndyError>> {
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _list_pools(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _list_pools(command_handle: CommandHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
     ErrorCode::from(unsafe { pool::indy_list_pools(command_handle, cb) })
 }
 
@@ -118,7 +118,7 @@ pub fn close_pool_ledger(pool_handle: PoolHandle) -> Box<dyn Future<Item=(), Err
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _close_pool_ledger(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _close_pool_ledger(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     ErrorCode::from(unsafe { pool::indy_close_pool_ledger(command_handle, pool_handle, cb) })
 }
 
@@ -134,12 +134,53 @@ pub fn delete_pool_ledger(pool_name: &str) -> Box<dyn Future<Item=(), Error=Indy
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _delete_pool_ledger(command_handle: CommandHandle, pool_name: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _delete_pool_ledger(command_handle: CommandHandle, pool_name: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let pool_name = c_str!(pool_name);
 
     ErrorCode::from(unsafe { pool::indy_delete_pool_ledger_config(command_handle, pool_name.as_ptr(), cb) })
 }
 
+/// Imports a local snapshot of the domain ledger to use by pools opened in offline mode.
+///
+/// # Arguments
+/// * `config_name` - Name of the pool ledger configuration.
+/// * `snapshot_path` - Path to the snapshot json file.
+/// * `options` - Optional import options json (reserved for the future use).
+pub fn import_pool_snapshot(pool_name: &str, snapshot_path: &str, options: Option<&str>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_pool_snapshot(command_handle, pool_name, snapshot_path, options, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _import_pool_snapshot(command_handle: CommandHandle, pool_name: &str, snapshot_path: &str, options: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let pool_name = c_str!(pool_name);
+    let snapshot_path = c_str!(snapshot_path);
+    let options_str = opt_c_str!(options);
+
+    ErrorCode::from(unsafe { pool::indy_import_pool_snapshot(command_handle, pool_name.as_ptr(), snapshot_path.as_ptr(), opt_c_ptr!(options, options_str), cb) })
+}
+
+/// Returns health statistics of the nodes of opened pool ledger.
+///
+/// # Arguments
+/// * `handle` - pool handle returned by open_ledger.
+///
+/// # Returns
+/// Pool status json with per-node replies, latency, timeouts, NACK/REJECT and BLS failures counts.
+pub fn get_pool_status(pool_handle: PoolHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_pool_status(command_handle, pool_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _get_pool_status(command_handle: CommandHandle, pool_handle: PoolHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { pool::indy_get_pool_status(command_handle, pool_handle, cb) })
+}
+
 /// Set PROTOCOL_VERSION to specific version.
 ///
 /// There is a global property PROTOCOL_VERSION that used in every request to the pool and
@@ -159,7 +200,7 @@ pub fn set_protocol_version(protocol_version: usize) -> Box<dyn Future<Item=(),
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _set_protocol_version(command_handle: CommandHandle, protocol_version: usize, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _set_protocol_version(command_handle: CommandHandle, protocol_version: usize, cb: Option<ResponseEmptyCB>) -> ErrorCode {
 
     ErrorCode::from(unsafe {
       pool::indy_set_protocol_version(command_handle, protocol_version, cb)
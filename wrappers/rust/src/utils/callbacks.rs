@@ -12,6 +12,13 @@ use std::sync::Mutex;
 use futures::*;
 use futures::sync::oneshot;
 
+#[cfg(feature = "async_api")]
+use futures03::compat::Future01CompatExt;
+#[cfg(feature = "async_api")]
+use futures03::FutureExt;
+#[cfg(feature = "async_api")]
+use async_api::IndyFuture;
+
 lazy_static! {
     static ref CALLBACKS_EMPTY: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(), IndyError>>>> = Default::default();
     static ref CALLBACKS_SLICE: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<Vec<u8>, IndyError>>>> = Default::default();
@@ -23,6 +30,7 @@ lazy_static! {
     static ref CALLBACKS_STR_STR_U64: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, String, u64), IndyError>>>> = Default::default();
     static ref CALLBACKS_STR: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<String, IndyError>>>> = Default::default();
     static ref CALLBACKS_STR_I64: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, i64), IndyError>>>> = Default::default();
+    static ref CALLBACKS_STR_U64: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, u64), IndyError>>>> = Default::default();
     static ref CALLBACKS_STR_STR: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, String), IndyError>>>> = Default::default();
     static ref CALLBACKS_STR_OPTSTR: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, Option<String>), IndyError>>>> = Default::default();
     static ref CALLBACKS_STR_STR_STR: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, String, String), IndyError>>>> = Default::default();
@@ -80,6 +88,10 @@ impl ClosureHandler {
            CALLBACKS_STR_I64,
            (rust_str!(str1), num));
 
+    cb_ec!(cb_ec_string_u64(str1:*const c_char, u: u64)->(String, u64),
+           CALLBACKS_STR_U64,
+           (rust_str!(str1), u));
+
     cb_ec!(cb_ec_string_string(str1:*const c_char, str2:*const c_char)->(String, String),
            CALLBACKS_STR_STR,
            (rust_str!(str1), rust_str!(str2)));
@@ -137,6 +149,7 @@ impl ResultHandler {
     result_handler!(bool(bool), CALLBACKS_BOOL);
     result_handler!(str(String), CALLBACKS_STR);
     result_handler!(str_i64((String, i64)), CALLBACKS_STR_I64);
+    result_handler!(str_u64((String, u64)), CALLBACKS_STR_U64);
     result_handler!(handle_usize((CommandHandle, usize)), CALLBACKS_HANDLE_USIZE);
     result_handler!(str_slice((String, Vec<u8>)), CALLBACKS_STR_SLICE);
     result_handler!(str_str((String, String)), CALLBACKS_STR_STR);
@@ -146,6 +159,46 @@ impl ResultHandler {
     result_handler!(str_str_u64((String, String, u64)), CALLBACKS_STR_STR_U64);
 }
 
+#[cfg(feature = "async_api")]
+macro_rules! async_result_handler {
+    ($name:ident($res_type:ty), $map:ident) => (
+    pub fn $name(command_handle: CommandHandle,
+                 err: ErrorCode,
+                 rx: sync::oneshot::Receiver<Result<$res_type, IndyError>>) -> IndyFuture<$res_type> {
+        if err != ErrorCode::Success {
+            let mut callbacks = $map.lock().unwrap();
+            callbacks.remove(&command_handle).unwrap();
+            Box::pin(futures03::future::ready(Err(IndyError::new(err))))
+        } else {
+            Box::pin(rx
+                .compat()
+                .map(|res| res.unwrap_or_else(|_| panic!("channel error!"))))
+        }
+    }
+    )
+}
+
+/// Same as `ResultHandler` but resolves callbacks into `std::future::Future` (futures 0.3)
+/// that can be awaited from async code and sent between threads.
+#[cfg(feature = "async_api")]
+pub struct AsyncResultHandler {}
+
+#[cfg(feature = "async_api")]
+impl AsyncResultHandler {
+    async_result_handler!(empty(()), CALLBACKS_EMPTY);
+    async_result_handler!(handle(CommandHandle), CALLBACKS_HANDLE);
+    async_result_handler!(wallethandle(WalletHandle), CALLBACKS_WALLETHANDLE);
+    async_result_handler!(slice(Vec<u8>), CALLBACKS_SLICE);
+    async_result_handler!(bool(bool), CALLBACKS_BOOL);
+    async_result_handler!(str(String), CALLBACKS_STR);
+    async_result_handler!(str_i64((String, i64)), CALLBACKS_STR_I64);
+    async_result_handler!(str_slice((String, Vec<u8>)), CALLBACKS_STR_SLICE);
+    async_result_handler!(str_str((String, String)), CALLBACKS_STR_STR);
+    async_result_handler!(str_optstr((String, Option<String>)), CALLBACKS_STR_OPTSTR);
+    async_result_handler!(str_optstr_optstr((String, Option<String>, Option<String>)), CALLBACKS_STR_OPTSTR_OPTSTR);
+    async_result_handler!(str_str_u64((String, String, u64)), CALLBACKS_STR_STR_U64);
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -188,4 +241,18 @@ mod test {
         assert_eq!(str1, "This is a test".to_string());
         assert_eq!(str2, Some("The second string has something".to_string()));
     }
+
+    #[cfg(feature = "async_api")]
+    #[test]
+    fn async_result_handler_str() {
+        let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+        let future = AsyncResultHandler::str(command_handle, ErrorCode::Success, receiver);
+
+        let callback = cb.unwrap();
+        callback(command_handle, 0, CString::new("This is a test").unwrap().as_ptr());
+
+        let str1 = futures03::executor::block_on(future).unwrap();
+        assert_eq!(str1, "This is a test".to_string());
+    }
 }
@@ -5,6 +5,8 @@ use {ErrorCode, IndyError};
 use std::ffi::CString;
 use std::ptr::null;
 
+use libc::c_char;
+
 use utils::callbacks::{ClosureHandler, ResultHandler};
 
 use ffi::{wallet, non_secrets};
@@ -12,7 +14,7 @@ use ffi::{ResponseEmptyCB,
           ResponseStringCB,
           ResponseI32CB,
           ResponseWalletHandleCB};
-use {CommandHandle, WalletHandle, SearchHandle};
+use {CommandHandle, IndyHandle, WalletHandle, SearchHandle};
 
 /// Registers custom wallet implementation.
 ///
@@ -88,7 +90,7 @@ pub fn register_wallet_storage(xtype: &str,
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _register_storage(command_handle: CommandHandle,
+pub(crate) fn _register_storage(command_handle: CommandHandle,
                      xtype: &str,
                      create: Option<wallet::WalletCreate>,
                      open: Option<wallet::WalletOpen>,
@@ -148,6 +150,71 @@ fn _register_storage(command_handle: CommandHandle,
     })
 }
 
+/// Registers transaction handlers for the custom wallet storage previously
+/// registered with `register_wallet_storage`.
+///
+/// Storages without these handlers don't support wallet transactions.
+///
+/// # Arguments
+/// * `xtype` - Storage type name.
+/// * `begin_transaction` - starts a storage transaction.
+/// * `commit_transaction` - commits the started storage transaction.
+/// * `rollback_transaction` - rolls back the started storage transaction.
+pub fn register_wallet_storage_transactions(xtype: &str,
+                                            begin_transaction: Option<wallet::WalletBeginTransaction>,
+                                            commit_transaction: Option<wallet::WalletCommitTransaction>,
+                                            rollback_transaction: Option<wallet::WalletRollbackTransaction>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_storage_transactions(command_handle, xtype, begin_transaction, commit_transaction, rollback_transaction, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _register_storage_transactions(command_handle: CommandHandle,
+                                  xtype: &str,
+                                  begin_transaction: Option<wallet::WalletBeginTransaction>,
+                                  commit_transaction: Option<wallet::WalletCommitTransaction>,
+                                  rollback_transaction: Option<wallet::WalletRollbackTransaction>,
+                                  cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_register_wallet_storage_transactions(command_handle, xtype.as_ptr(), begin_transaction, commit_transaction, rollback_transaction, cb)
+    })
+}
+
+/// Registers storages listing handlers for the custom wallet storage previously
+/// registered with `register_wallet_storage`.
+///
+/// Wallets of storages without these handlers can't be listed with `list_wallets`.
+///
+/// # Arguments
+/// * `xtype` - Storage type name.
+/// * `list_storages` - returns ids of existing storages.
+/// * `free_storage_list` - frees the ids returned by `list_storages`.
+pub fn register_wallet_storage_list(xtype: &str,
+                                    list_storages: Option<wallet::WalletListStorages>,
+                                    free_storage_list: Option<wallet::WalletFreeStorageList>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_storage_list(command_handle, xtype, list_storages, free_storage_list, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _register_storage_list(command_handle: CommandHandle,
+                          xtype: &str,
+                          list_storages: Option<wallet::WalletListStorages>,
+                          free_storage_list: Option<wallet::WalletFreeStorageList>,
+                          cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_register_wallet_storage_list(command_handle, xtype.as_ptr(), list_storages, free_storage_list, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name.
 ///
 /// # Arguments
@@ -163,7 +230,7 @@ pub fn create_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=(),
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _create_wallet(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _create_wallet(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let config = c_str!(config);
     let credentials = c_str!(credentials);
 
@@ -196,7 +263,7 @@ pub fn open_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=Walle
     ResultHandler::wallethandle(command_handle, err, receiver)
 }
 
-fn _open_wallet(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseWalletHandleCB>) -> ErrorCode {
+pub(crate) fn _open_wallet(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseWalletHandleCB>) -> ErrorCode {
     let config = c_str!(config);
     let credentials = c_str!(credentials);
 
@@ -216,6 +283,9 @@ fn _open_wallet(command_handle: CommandHandle, config: &str, credentials: &str,
 ///   {
 ///     "path": path of the file that contains exported wallet content
 ///     "key": passphrase used to derive export key
+///     "version": optional<int> 0 - single export file (default), 1 - manifest file with "chunks" directory
+///     "base_manifest": optional<string> manifest of previous version 1 export to make incremental backup to
+///     "verkey": optional<string> verkey of wallet key to sign the manifest with, required for version 1
 ///   }
 pub fn export_wallet(wallet_handle: WalletHandle, export_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -225,7 +295,7 @@ pub fn export_wallet(wallet_handle: WalletHandle, export_config: &str) -> Box<dy
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, export_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, export_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let export_config = c_str!(export_config);
 
     ErrorCode::from(unsafe {
@@ -233,6 +303,124 @@ fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, ex
     })
 }
 
+/// Rotates keys of opened wallet and re-encrypts all wallet records with them in place
+///
+/// If the operation is interrupted the wallet can be opened with the new credentials only,
+/// re-encryption of remaining records is finished by open_wallet.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `new_credentials` - New wallet credentials json
+///   {
+///       "key": string, New key or passphrase used for wallet key derivation.
+///       "key_derivation_method": optional<string> Algorithm to use for wallet key derivation:
+///                          ARGON2I_MOD - derive secured wallet master key (used by default)
+///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
+///                          RAW - raw wallet key master provided (skip derivation).
+///   }
+pub fn rekey_wallet(wallet_handle: WalletHandle, new_credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _rekey_wallet(command_handle, wallet_handle, new_credentials, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _rekey_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, new_credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let new_credentials = c_str!(new_credentials);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_rekey_wallet(command_handle, wallet_handle, new_credentials.as_ptr(), None, cb)
+    })
+}
+
+/// Checks that all records of opened wallet can be decrypted and are consistent with their HMAC
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `options` - (optional) check options json
+///   {
+///       "quarantine": optional<bool>, Move broken records to "Indy::Quarantine" record type. false by default.
+///   }
+///
+/// # Returns
+/// report json
+///   {
+///       "total": int, amount of checked records
+///       "broken": [{
+///           "type": optional<string>, type of the broken record (absent if it can't be decrypted)
+///           "id": optional<string>, id of the broken record (absent if it can't be decrypted)
+///           "raw_id": string, base64 encoded id of the record as it is stored in the storage
+///           "reason": string, description of the damage
+///           "quarantined": bool, whether the record was moved to quarantine
+///       }]
+///   }
+pub fn check_wallet(wallet_handle: WalletHandle, options: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_wallet(command_handle, wallet_handle, options, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _check_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, options: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let options_str = opt_c_str!(options);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_check_wallet(command_handle, wallet_handle, opt_c_ptr!(options, options_str), cb)
+    })
+}
+
+/// Subscribes to changes of records of opened wallet
+///
+/// Changes made inside of a wallet transaction are reported after the transaction is committed.
+///
+/// # Arguments:
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `type_filter` - (optional) type of records to receive events for, all types if not set
+/// * `event_cb` - callback called for each change with event json:
+///   {
+///       "event": string, one of "add", "update", "delete"
+///       "type": string, type of the record
+///       "id": string, id of the record
+///   }
+///
+/// # Returns
+/// * `subscription_handle` - handle to pass to unsubscribe_wallet_events
+pub fn subscribe_wallet_events(wallet_handle: WalletHandle, type_filter: Option<&str>, event_cb: Option<extern fn(command_handle_: CommandHandle, event_json: *const c_char)>) -> Box<dyn Future<Item=IndyHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _subscribe_wallet_events(command_handle, wallet_handle, type_filter, event_cb, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+pub(crate) fn _subscribe_wallet_events(command_handle: CommandHandle, wallet_handle: WalletHandle, type_filter: Option<&str>, event_cb: Option<extern fn(command_handle_: CommandHandle, event_json: *const c_char)>, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let type_filter_str = opt_c_str!(type_filter);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_subscribe_wallet_events(command_handle, wallet_handle, opt_c_ptr!(type_filter, type_filter_str), event_cb, cb)
+    })
+}
+
+/// Cancels subscription made with subscribe_wallet_events
+///
+/// # Arguments:
+/// * `subscription_handle` - subscription handle returned by subscribe_wallet_events
+pub fn unsubscribe_wallet_events(subscription_handle: IndyHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _unsubscribe_wallet_events(command_handle, subscription_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _unsubscribe_wallet_events(command_handle: CommandHandle, subscription_handle: IndyHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+      wallet::indy_unsubscribe_wallet_events(command_handle, subscription_handle, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name and then imports its content
 /// according to fields provided in import_config
 /// This can be seen as an create call with additional content import
@@ -253,8 +441,9 @@ fn _export_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, ex
 ///   }
 /// * `import_config` - JSON containing settings for input operation.
 ///   {
-///     "path": path of the file that contains exported wallet content
+///     "path": path of the file that contains exported wallet content or manifest of version 1 export
 ///     "key": passphrase used to derive export key
+///     "verkey": optional<string> verkey the manifest is signed with, required for version 1
 ///   }
 pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -264,7 +453,7 @@ pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Bo
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _import_wallet(command_handle: CommandHandle, config: &str, credentials: &str, import_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _import_wallet(command_handle: CommandHandle, config: &str, credentials: &str, import_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let config = c_str!(config);
     let credentials = c_str!(credentials);
     let import_config = c_str!(import_config);
@@ -283,7 +472,7 @@ pub fn delete_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=(),
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _delete_wallet(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _delete_wallet(command_handle: CommandHandle, config: &str, credentials: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let config = c_str!(config);
     let credentials = c_str!(credentials);
 
@@ -292,6 +481,37 @@ fn _delete_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Lists wallets existing in the wallet storage.
+///
+/// # Arguments
+/// * `storage_type` - (optional) type of the wallet storage. Defaults to 'default'.
+/// * `storage_config` - (optional) storage configuration json, the same as "storage_config" of `create_wallet` config.
+/// * `storage_credentials` - (optional) storage credentials json, the same as "storage_credentials" of `create_wallet` credentials.
+///
+/// # Returns
+/// Sorted json list of ids of existing wallets.
+pub fn list_wallets(storage_type: Option<&str>, storage_config: Option<&str>, storage_credentials: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _list_wallets(command_handle, storage_type, storage_config, storage_credentials, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+pub(crate) fn _list_wallets(command_handle: CommandHandle, storage_type: Option<&str>, storage_config: Option<&str>, storage_credentials: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let storage_type_str = opt_c_str!(storage_type);
+    let storage_config_str = opt_c_str!(storage_config);
+    let storage_credentials_str = opt_c_str!(storage_credentials);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_list_wallets(command_handle,
+                                opt_c_ptr!(storage_type, storage_type_str),
+                                opt_c_ptr!(storage_config, storage_config_str),
+                                opt_c_ptr!(storage_credentials, storage_credentials_str),
+                                cb)
+    })
+}
+
 /// Closes opened wallet and frees allocated resources.
 ///
 /// # Arguments
@@ -304,10 +524,61 @@ pub fn close_wallet(wallet_handle: WalletHandle) -> Box<dyn Future<Item=(), Erro
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _close_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _close_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     ErrorCode::from(unsafe { wallet::indy_close_wallet(command_handle, wallet_handle, cb) })
 }
 
+/// Starts a wallet transaction.
+///
+/// All wallet changes made until `commit_transaction` or `rollback_transaction`
+/// are applied atomically. Only one transaction can be started per wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+pub fn begin_transaction(wallet_handle: WalletHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _begin_transaction(command_handle, wallet_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _begin_transaction(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_wallet_begin_transaction(command_handle, wallet_handle, cb) })
+}
+
+/// Commits the started wallet transaction.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+pub fn commit_transaction(wallet_handle: WalletHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _commit_transaction(command_handle, wallet_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _commit_transaction(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_wallet_commit_transaction(command_handle, wallet_handle, cb) })
+}
+
+/// Rolls back the started wallet transaction discarding all changes made in it.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+pub fn rollback_transaction(wallet_handle: WalletHandle) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _rollback_transaction(command_handle, wallet_handle, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+pub(crate) fn _rollback_transaction(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_wallet_rollback_transaction(command_handle, wallet_handle, cb) })
+}
+
 /// Create a new non-secret record in the wallet
 ///
 /// # Arguments
@@ -334,7 +605,7 @@ pub fn add_wallet_record(wallet_handle: WalletHandle, xtype: &str, id: &str, val
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _add_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, value: &str, tags_json: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _add_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, value: &str, tags_json: Option<&str>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
     let value = c_str!(value);
@@ -365,7 +636,7 @@ pub fn update_wallet_record_value(wallet_handle: WalletHandle, xtype: &str, id:
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _update_wallet_record_value(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, value: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _update_wallet_record_value(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, value: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
     let value = c_str!(value);
@@ -404,7 +675,7 @@ pub fn update_wallet_record_tags(wallet_handle: WalletHandle, xtype: &str, id: &
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _update_wallet_record_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, tags_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _update_wallet_record_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, tags_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
     let tags_json = c_str!(tags_json);
@@ -440,7 +711,7 @@ pub fn add_wallet_record_tags(wallet_handle: WalletHandle, xtype: &str, id: &str
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _add_wallet_record_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, tags_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _add_wallet_record_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, tags_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
     let tags_json = c_str!(tags_json);
@@ -466,7 +737,7 @@ pub fn delete_wallet_record_tags(wallet_handle: WalletHandle, xtype: &str, id: &
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _delete_wallet_record_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, tag_names_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _delete_wallet_record_tags(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, tag_names_json: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
     let tag_names_json = c_str!(tag_names_json);
@@ -490,7 +761,7 @@ pub fn delete_wallet_record(wallet_handle: WalletHandle, xtype: &str, id: &str)
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
 
@@ -527,7 +798,7 @@ pub fn get_wallet_record(wallet_handle: WalletHandle, xtype: &str, id: &str, opt
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _get_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, options_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _get_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, id: &str, options_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let id = c_str!(id);
     let options_json = c_str!(options_json);
@@ -561,6 +832,10 @@ fn _get_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHandle
 ///    retrieveType: (optional, false by default) Retrieve record type,
 ///    retrieveValue: (optional, true by default) Retrieve record value,
 ///    retrieveTags: (optional, false by default) Retrieve record tags,
+///    sortBy: (optional) Sort records by id ("id") or by unencrypted tag value ({"tagName": "~tagName"}),
+///    sortOrder: (optional, "asc" by default) Sort order: "asc" or "desc",
+///    offset: (optional) Number of matching records to skip,
+///    limit: (optional) Max number of records to retrieve,
 ///  }
 /// # Returns
 /// * `search_handle` - Wallet search handle that can be used later
@@ -573,7 +848,7 @@ pub fn open_wallet_search(wallet_handle: WalletHandle, xtype: &str, query_json:
     ResultHandler::handle(command_handle, err, receiver)
 }
 
-fn _open_wallet_search(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, query_json: &str, options_json: &str, cb: Option<ResponseI32CB>) -> ErrorCode {
+pub(crate) fn _open_wallet_search(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, query_json: &str, options_json: &str, cb: Option<ResponseI32CB>) -> ErrorCode {
     let xtype = c_str!(xtype);
     let query_json = c_str!(query_json);
     let options_json = c_str!(options_json);
@@ -611,7 +886,7 @@ pub fn fetch_wallet_search_next_records(wallet_handle: WalletHandle, wallet_sear
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _fetch_wallet_search_next_records(command_handle: CommandHandle, wallet_handle: WalletHandle, wallet_search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _fetch_wallet_search_next_records(command_handle: CommandHandle, wallet_handle: WalletHandle, wallet_search_handle: SearchHandle, count: usize, cb: Option<ResponseStringCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
       non_secrets::indy_fetch_wallet_search_next_records(command_handle, wallet_handle, wallet_search_handle, count, cb)
     })
@@ -629,13 +904,13 @@ pub fn close_wallet_search(wallet_search_handle: SearchHandle) -> Box<dyn Future
     ResultHandler::empty(command_handle, err, receiver)
 }
 
-fn _close_wallet_search(command_handle: CommandHandle, wallet_search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+pub(crate) fn _close_wallet_search(command_handle: CommandHandle, wallet_search_handle: SearchHandle, cb: Option<ResponseEmptyCB>) -> ErrorCode {
     ErrorCode::from(unsafe {
       non_secrets::indy_close_wallet_search(command_handle, wallet_search_handle, cb)
     })
 }
 
-fn _default_credentials(credentials: Option<&str>) -> CString {
+pub(crate) fn _default_credentials(credentials: Option<&str>) -> CString {
     match credentials {
         Some(s) => c_str!(s),
         None => c_str!(r#"{"key":""}"#)
@@ -663,7 +938,7 @@ pub fn generate_wallet_key(config: Option<&str>) -> Box<dyn Future<Item=String,
     ResultHandler::str(command_handle, err, receiver)
 }
 
-fn _generate_wallet_key(command_handle: CommandHandle, config: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+pub(crate) fn _generate_wallet_key(command_handle: CommandHandle, config: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
     let config = opt_c_str_json!(config);
 
     ErrorCode::from(unsafe { wallet::indy_generate_wallet_key(command_handle, config.as_ptr(), cb) })
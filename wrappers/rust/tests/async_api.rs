@@ -0,0 +1,137 @@
+#![cfg(feature = "async_api")]
+
+extern crate indyrs as indy;
+#[macro_use] extern crate serde_json;
+#[macro_use] extern crate serde_derive;
+extern crate rmp_serde;
+extern crate byteorder;
+extern crate futures03;
+
+#[macro_use]
+mod utils;
+
+use futures03::executor::block_on;
+
+use indy::ErrorCode;
+use indy::async_api::{anoncreds, crypto, did, ledger, non_secrets, wallet};
+use indy::async_api::did::DidConfig;
+use indy::async_api::wallet::{WalletConfig, WalletCredentials, KeyDerivationMethod};
+
+use utils::constants::{DID_1, SEED_1, VERKEY_1};
+use utils::rand::random_string;
+
+const WALLET_KEY: &str = "8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY";
+
+struct TestWallet {
+    config: WalletConfig,
+    credentials: WalletCredentials,
+    handle: indy::WalletHandle,
+}
+
+impl TestWallet {
+    fn new() -> TestWallet {
+        let config = WalletConfig::new(&random_string(20));
+        let credentials = WalletCredentials::new(WALLET_KEY, Some(KeyDerivationMethod::Raw));
+
+        block_on(wallet::create_wallet(&config, &credentials)).unwrap();
+        let handle = block_on(wallet::open_wallet(&config, &credentials)).unwrap();
+
+        TestWallet { config, credentials, handle }
+    }
+}
+
+impl Drop for TestWallet {
+    fn drop(&mut self) {
+        block_on(wallet::close_wallet(self.handle)).unwrap();
+        block_on(wallet::delete_wallet(&self.config, &self.credentials)).unwrap();
+    }
+}
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn async_api_futures_are_send() {
+    let wallet = TestWallet::new();
+
+    let future = did::create_and_store_my_did(wallet.handle, &DidConfig::default());
+    assert_send(&future);
+
+    block_on(future).unwrap();
+}
+
+#[test]
+fn async_api_wallet_works_for_duplicate() {
+    let wallet = TestWallet::new();
+
+    let err = block_on(wallet::create_wallet(&wallet.config, &wallet.credentials)).unwrap_err();
+
+    assert_eq!(ErrorCode::WalletAlreadyExistsError, err.error_code);
+}
+
+#[test]
+fn async_api_did_works() {
+    let wallet = TestWallet::new();
+
+    let config = DidConfig { seed: Some(SEED_1.to_string()), ..DidConfig::default() };
+
+    let created = block_on(did::create_and_store_my_did(wallet.handle, &config)).unwrap();
+    assert_eq!(DID_1, created.did);
+    assert_eq!(VERKEY_1, created.verkey);
+
+    block_on(did::set_did_metadata(wallet.handle, DID_1, "metadata")).unwrap();
+
+    let did_with_meta = block_on(did::get_my_did_with_metadata(wallet.handle, DID_1)).unwrap();
+    assert_eq!(VERKEY_1, did_with_meta.verkey);
+    assert_eq!(Some("metadata".to_string()), did_with_meta.metadata);
+}
+
+#[test]
+fn async_api_crypto_works() {
+    let wallet = TestWallet::new();
+
+    let verkey = block_on(crypto::create_key(wallet.handle, None)).unwrap();
+
+    let signature = block_on(crypto::sign(wallet.handle, &verkey, b"message")).unwrap();
+    assert!(block_on(crypto::verify(&verkey, b"message", &signature)).unwrap());
+
+    let jwe = block_on(crypto::pack_message(wallet.handle, b"message", &[verkey.as_str()], Some(verkey.as_str()))).unwrap();
+    let unpacked = block_on(crypto::unpack_message(wallet.handle, &jwe)).unwrap();
+    assert_eq!("message", unpacked.message);
+    assert_eq!(Some(verkey), unpacked.sender_verkey);
+}
+
+#[test]
+fn async_api_non_secrets_works() {
+    let wallet = TestWallet::new();
+
+    let tags: non_secrets::Tags = hashmap!["~tag".to_string() => "value".to_string()];
+
+    block_on(non_secrets::add_wallet_record(wallet.handle, "type", "id", "value", Some(&tags))).unwrap();
+
+    let options = non_secrets::RecordOptions { retrieve_tags: true, ..non_secrets::RecordOptions::default() };
+    let record = block_on(non_secrets::get_wallet_record(wallet.handle, "type", "id", &options)).unwrap();
+    assert_eq!(Some("value".to_string()), record.value);
+    assert_eq!(Some(tags), record.tags);
+
+    let search_handle = block_on(non_secrets::open_wallet_search(wallet.handle, "type", &json!({"~tag": "value"}), &non_secrets::SearchOptions::default())).unwrap();
+    let records = block_on(non_secrets::fetch_wallet_search_next_records(wallet.handle, search_handle, 10)).unwrap();
+    assert_eq!(1, records.records.unwrap().len());
+    block_on(non_secrets::close_wallet_search(search_handle)).unwrap();
+}
+
+#[test]
+fn async_api_ledger_works() {
+    let request = block_on(ledger::build_nym_request(DID_1, DID_1, Some(VERKEY_1), None, None)).unwrap();
+
+    assert_eq!(Some(DID_1.to_string()), request.identifier);
+    assert_eq!(json!({"type": "1", "dest": DID_1, "verkey": VERKEY_1}), request.operation);
+}
+
+#[test]
+fn async_api_anoncreds_schema_works() {
+    let schema = block_on(anoncreds::issuer_create_schema(DID_1, "gvt", "1.0", &["name", "age"])).unwrap();
+    assert_eq!("gvt", schema.name);
+
+    let request = block_on(ledger::build_schema_request(DID_1, &schema)).unwrap();
+    assert_eq!("101", request.operation["type"]);
+}